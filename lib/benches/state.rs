@@ -0,0 +1,100 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use seelen_core::state::{Settings, Theme, WindowManagerLayout, WmArea, WmNode};
+
+// copies of the bundled themes and layouts, so the crate can be benchmarked on its own
+const THEMES: [&str; 2] = [
+    include_str!("fixtures/themes/bubbles.yml"),
+    include_str!("fixtures/themes/rainbow.yml"),
+];
+
+const LAYOUTS: [(&str, &str); 4] = [
+    ("BSP", include_str!("fixtures/layouts/BSP.json")),
+    ("Grid", include_str!("fixtures/layouts/Grid.json")),
+    ("Tall", include_str!("fixtures/layouts/Tall.json")),
+    ("Wide", include_str!("fixtures/layouts/Wide.json")),
+];
+
+const WINDOWS: [usize; 3] = [4, 16, 64];
+
+/// Adds a window following the priorities of the nodes like the tiler does,
+/// the conditions are not evaluated.
+fn add_window(node: &mut WmNode, handle: isize) -> bool {
+    match node {
+        WmNode::Leaf(leaf) if leaf.handle.is_none() => {
+            leaf.handle = Some(handle);
+            true
+        }
+        WmNode::Leaf(_) | WmNode::Stack(_) => false,
+        WmNode::Fallback(fallback) => {
+            fallback.handles.push(handle);
+            fallback.active = Some(handle);
+            true
+        }
+        WmNode::Vertical(n) => add_to_children(&mut n.children, handle),
+        WmNode::Horizontal(n) => add_to_children(&mut n.children, handle),
+    }
+}
+
+fn add_to_children(children: &mut [WmNode], handle: isize) -> bool {
+    let mut children: Vec<&mut WmNode> = children.iter_mut().collect();
+    children.sort_by_key(|child| child.priority());
+    children.into_iter().any(|child| add_window(child, handle))
+}
+
+/// Fills a copy of the structure with the windows and computes their rects
+fn layout_pass(structure: &WmNode, windows: usize) -> usize {
+    let mut tree = structure.clone();
+    for handle in 1..=windows {
+        add_window(&mut tree, handle as isize);
+    }
+    let area = WmArea {
+        x: 0.0,
+        y: 0.0,
+        width: 1920.0,
+        height: 1080.0,
+    };
+    let mut placed = Vec::with_capacity(windows);
+    tree.place(area, 8.0, &mut placed);
+    placed.len()
+}
+
+fn settings(c: &mut Criterion) {
+    let content = serde_json::to_string_pretty(&Settings::default()).unwrap();
+    c.bench_function("settings/parse", |b| {
+        b.iter(|| serde_json::from_str::<Settings>(black_box(&content)).unwrap())
+    });
+}
+
+fn themes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("themes/parse");
+    for files in [1, 10, 50] {
+        group.bench_with_input(BenchmarkId::from_parameter(files), &files, |b, &files| {
+            b.iter(|| {
+                for content in THEMES.iter().cycle().take(files) {
+                    black_box(serde_yaml::from_str::<Theme>(content).unwrap());
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn layouts(c: &mut Criterion) {
+    let mut group = c.benchmark_group("layouts");
+    for (name, content) in LAYOUTS {
+        group.bench_with_input(BenchmarkId::new("parse", name), content, |b, content| {
+            b.iter(|| serde_json::from_str::<WindowManagerLayout>(black_box(content)).unwrap())
+        });
+        let layout: WindowManagerLayout = serde_json::from_str(content).unwrap();
+        for windows in WINDOWS {
+            let id = BenchmarkId::new(format!("place/{}", name), windows);
+            group.bench_with_input(id, &windows, |b, &windows| {
+                b.iter(|| layout_pass(black_box(&layout.structure), windows))
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, settings, themes, layouts);
+criterion_main!(benches);
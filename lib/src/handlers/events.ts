@@ -5,6 +5,9 @@ export enum SeelenEvent {
   GlobalFocusChanged = 'global-focus-changed',
   GlobalMouseMove = 'global-mouse-move',
   GlobalMonitorsChanged = 'global-monitors-changed',
+  MonitorScaleChanged = 'monitor-scale-changed',
+  GlobalAlwaysOnTopChanged = 'global-always-on-top-changed',
+  GlobalUrgentChanged = 'global-urgent-changed',
 
   HandleLayeredHitboxes = 'handle-layered',
 
@@ -21,8 +24,17 @@ export enum SeelenEvent {
 
   PowerStatus = 'power-status',
   BatteriesStatus = 'batteries-status',
+  PowerProfileChanged = 'power-profile-changed',
 
   ColorsChanged = 'colors-changed',
+  LayoutDirectionChanged = 'layout-direction-changed',
+  LiveThemeCssChanged = 'live-theme-css-changed',
+
+  KeybindingsConflicts = 'keybindings-conflicts',
+  KeybindingsHints = 'keybindings-hints',
+
+  WebviewHeartbeat = 'webview-heartbeat',
+  PerfMetrics = 'perf-metrics',
 
   TrayInfo = 'tray-info',
 
@@ -49,10 +61,57 @@ export enum SeelenEvent {
   WMSetLayout = 'wm-set-layout',
   WMSetOverlayVisibility = 'wm-set-overlay-visibility',
   WMSetActiveWindow = 'wm-set-active-window',
+  WMSetZonesOverlay = 'wm-set-zones-overlay',
 
   WallStop = 'wall-stop',
+  WallDailyWallpaperChanged = 'wall-daily-wallpaper-changed',
+  WallWidgetClick = 'wall-widget-click',
+
+  WindowSwitcherChanged = 'window-switcher-changed',
+  WindowTagsChanged = 'window-tags-changed',
+
+  NightLightChanged = 'night-light-changed',
+
+  BrightnessChanged = 'brightness-changed',
+
+  BluetoothChanged = 'bluetooth-changed',
+
+  RemovableDrivesChanged = 'removable-drives-changed',
+
+  StorageChanged = 'storage-changed',
+
+  PrintersChanged = 'printers-changed',
+
+  PrivateSessionChanged = 'private-session-changed',
+
+  RecycleBinChanged = 'recycle-bin-changed',
+  NotesChanged = 'notes-changed',
+  QuickSettingsChanged = 'quick-settings-changed',
+
+  SessionChanged = 'session-changed',
+
+  CaptureTaken = 'capture-taken',
+
+  ColorPickerLoupe = 'color-picker-loupe',
+
+  BreakReminderChanged = 'break-reminder-changed',
+
+  StartMenuItemsChanged = 'start-menu-items-changed',
+
+  ProcessesChanged = 'processes-changed',
+
+  ImeChanged = 'ime-changed',
+
+  ClockTick = 'clock-tick',
+
+  OsdShow = 'osd-show',
+
+  UpdaterStatus = 'updater-status',
+  VersionUpgraded = 'version-upgraded',
 
   StateSettingsChanged = 'settings-changed',
+  StateSettingsConflict = 'settings-conflict',
+  StateLockedSettingsChanged = 'locked-settings',
   StateWegItemsChanged = 'weg-items',
   StateThemesChanged = 'themes',
   StatePlaceholdersChanged = 'placeholders',
@@ -60,4 +119,5 @@ export enum SeelenEvent {
   StateSettingsByAppChanged = 'settings-by-app',
   StateHistoryChanged = 'history',
   StateIconPacksChanged = 'icon-packs',
+  StateWidgetsChanged = 'widgets',
 }
@@ -13,24 +13,86 @@ export enum SeelenCommand {
   SwitchWorkspace = 'switch_workspace',
   SendKeys = 'send_keys',
   GetIcon = 'get_icon',
+  InvalidateIconCache = 'invalidate_icon_cache',
   GetSystemColors = 'get_system_colors',
   SimulateFullscreen = 'simulate_fullscreen',
   CheckForUpdates = 'check_for_updates',
   /** Restart the app after install the update so it returns a promise resolved with `never` */
   InstallLastAvailableUpdate = 'install_last_available_update',
+  UpdaterGetStatus = 'updater_get_status',
+  UpdaterDownload = 'updater_download',
+  GetVersionUpgrade = 'get_version_upgrade',
+  DismissVersionUpgrade = 'dismiss_version_upgrade',
+  SetLogLevel = 'set_log_level',
 
   // Seelen Settings
   SetAutoStart = 'set_auto_start',
   GetAutoStartStatus = 'get_auto_start_status',
+  StartupGetStatus = 'startup_get_status',
+  StartupRegister = 'startup_register',
+  StartupUnregister = 'startup_unregister',
   StateGetThemes = 'state_get_themes',
+  LiveThemeGetCss = 'live_theme_get_css',
+  LiveThemeInjectCss = 'live_theme_inject_css',
   StateGetPlaceholders = 'state_get_placeholders',
+  StatePinToolbarItem = 'state_pin_toolbar_item',
   StateGetLayouts = 'state_get_layouts',
   StateGetWegItems = 'state_get_weg_items',
+  StatePauseFileListener = 'state_pause_file_listener',
+  StateResumeFileListener = 'state_resume_file_listener',
   StateGetSettings = 'state_get_settings',
+  PatchSettings = 'patch_settings',
+  StateGetSettingsHash = 'state_get_settings_hash',
+  StateWriteSettings = 'state_write_settings',
+  StateGetSettingsConflict = 'state_get_settings_conflict',
+  StateResolveSettingsConflict = 'state_resolve_settings_conflict',
+  StateGetLockedSettings = 'state_get_locked_settings',
   StateGetSpecificAppsConfigurations = 'state_get_specific_apps_configurations',
   StateGetWallpaper = 'state_get_wallpaper',
   StateSetWallpaper = 'state_set_wallpaper',
   StateGetHistory = 'state_get_history',
+  StateGetWidgets = 'state_get_widgets',
+  SearchSettings = 'search_settings',
+  GetConfigSchema = 'get_config_schema',
+  WidgetCall = 'widget_call',
+
+  // Onboarding
+  OnboardingDetectCapabilities = 'onboarding_detect_capabilities',
+  OnboardingApplyPreset = 'onboarding_apply_preset',
+
+  // Marketplace
+  MarketplaceSearch = 'marketplace_search',
+  MarketplaceRefresh = 'marketplace_refresh',
+  MarketplaceInstall = 'marketplace_install',
+  MarketplaceCheckUpdates = 'marketplace_check_updates',
+  MarketplaceUpdate = 'marketplace_update',
+
+  // Window Effects
+  ToggleWindowEffects = 'toggle_window_effects',
+
+  // Window Filters
+  WindowFilterExplain = 'window_filter_explain',
+  WindowFilterInspectAtCursor = 'window_filter_inspect_at_cursor',
+
+  // Always on Top
+  ToggleAlwaysOnTop = 'toggle_always_on_top',
+  GetAlwaysOnTopWindows = 'get_always_on_top_windows',
+
+  // Urgency
+  GetUrgentWindows = 'get_urgent_windows',
+  ClearUrgency = 'clear_urgency',
+
+  // Window Switcher
+  WindowSwitcherGetState = 'window_switcher_get_state',
+  WindowSwitcherOpen = 'window_switcher_open',
+  WindowSwitcherSelect = 'window_switcher_select',
+  WindowSwitcherCommit = 'window_switcher_commit',
+  WindowSwitcherCancel = 'window_switcher_cancel',
+
+  // Window Tags
+  WindowTagsGetState = 'window_tags_get_state',
+  WindowTagsSet = 'window_tags_set',
+  WindowTagsSetFilter = 'window_tags_set_filter',
 
   // Media
   MediaPrev = 'media_prev',
@@ -43,25 +105,147 @@ export enum SeelenCommand {
   // Brightness
   GetMainMonitorBrightness = 'get_main_monitor_brightness',
   SetMainMonitorBrightness = 'set_main_monitor_brightness',
+  BrightnessGetAll = 'brightness_get_all',
+  BrightnessSet = 'brightness_set',
+
+  // Bluetooth
+  BluetoothGetState = 'bluetooth_get_state',
+  BluetoothSetEnabled = 'bluetooth_set_enabled',
+  BluetoothToggle = 'bluetooth_toggle',
+  BluetoothConnect = 'bluetooth_connect',
+  BluetoothDisconnect = 'bluetooth_disconnect',
+
+  // Removable Drives
+  RemovableDrivesGetAll = 'removable_drives_get_all',
+  RemovableDriveEject = 'removable_drive_eject',
+
+  // Storage
+  StorageGetState = 'storage_get_state',
+
+  // Printers
+  PrintersGetAll = 'printers_get_all',
+  PrinterPause = 'printer_pause',
+  PrinterResume = 'printer_resume',
+  PrintJobPause = 'print_job_pause',
+  PrintJobResume = 'print_job_resume',
+  PrintJobCancel = 'print_job_cancel',
+
+  // Recycle Bin
+  GetRecycleBinState = 'get_recycle_bin_state',
+  EmptyRecycleBin = 'empty_recycle_bin',
+  OpenRecycleBin = 'open_recycle_bin',
+
+  // Session
+  SessionGetState = 'session_get_state',
+
+  // Input Method
+  ImeGetState = 'ime_get_state',
+
+  // Clock
+  ClockGetState = 'clock_get_state',
+
+  // Screen Capture
+  CaptureScreen = 'capture_screen',
+  CaptureWindow = 'capture_window',
+  CaptureRegion = 'capture_region',
+
+  // Color Picker
+  ColorPickerPick = 'color_picker_pick',
+
+  // App Usage
+  AppUsageGetReport = 'app_usage_get_report',
+  AppUsageGetHistory = 'app_usage_get_history',
+  AppUsageClear = 'app_usage_clear',
+
+  // Private Session
+  PrivateSessionGet = 'private_session_get',
+  PrivateSessionSet = 'private_session_set',
+  PrivateSessionToggle = 'private_session_toggle',
+
+  // Break Reminder
+  BreakReminderGetState = 'break_reminder_get_state',
+  BreakReminderTakeBreak = 'break_reminder_take_break',
+  BreakReminderSnooze = 'break_reminder_snooze',
+  BreakReminderSkip = 'break_reminder_skip',
+
+  // Diagnostics
+  DiagnosticsGetReports = 'diagnostics_get_reports',
+  DiagnosticsClear = 'diagnostics_clear',
+  DiagnosticsExport = 'diagnostics_export',
+
+  // Restore Points
+  RestorePointsGetAll = 'restore_points_get_all',
+  RestorePointsCreate = 'restore_points_create',
+  RestorePointsApply = 'restore_points_apply',
+  RestorePointsDelete = 'restore_points_delete',
+
+  // Sticky Notes
+  NotesGetAll = 'notes_get_all',
+  NotesCreate = 'notes_create',
+  NotesUpdate = 'notes_update',
+  NotesDelete = 'notes_delete',
+
+  // Quick Settings
+  QuickSettingsGetState = 'quick_settings_get_state',
+  QuickSettingsToggle = 'quick_settings_toggle',
+  QuickSettingsSetVolume = 'quick_settings_set_volume',
+  QuickSettingsSetBrightness = 'quick_settings_set_brightness',
+
+  // Display
+  DisplayGetAll = 'display_get_all',
+  DisplaySetMode = 'display_set_mode',
+  DisplaySetOrientation = 'display_set_orientation',
+  DisplaySetPrimary = 'display_set_primary',
+
+  // Night Light
+  NightLightGetState = 'night_light_get_state',
+  NightLightSetActive = 'night_light_set_active',
+  NightLightToggle = 'night_light_toggle',
 
   // Power
   LogOut = 'log_out',
+  Lock = 'lock',
+  SwitchUser = 'switch_user',
   Suspend = 'suspend',
   Restart = 'restart',
   Shutdown = 'shutdown',
+  GetPowerProfile = 'get_power_profile',
+  GetLayoutDirection = 'get_layout_direction',
+
+  // Fancy Toolbar
+  ToolbarRunItemAction = 'toolbar_run_item_action',
 
   // SeelenWeg
   WegCloseApp = 'weg_close_app',
+  WegGatherWindows = 'weg_gather_windows',
   WegToggleWindowState = 'weg_toggle_window_state',
   WegRequestUpdatePreviews = 'weg_request_update_previews',
   WegPinItem = 'weg_pin_item',
+  WegMoveItem = 'weg_move_item',
+  WegLaunchItem = 'weg_launch_item',
+  WegSetMinimizeTargets = 'weg_set_minimize_targets',
+
+  // Seelen Wall
+  WallSetWidgetRects = 'wall_set_widget_rects',
+  DailyWallpaperGet = 'daily_wallpaper_get',
 
   // Windows Manager
   SetWindowPosition = 'set_window_position',
   RequestFocus = 'request_focus',
+  WmLayoutValidate = 'wm_layout_validate',
+  WmLayoutPreview = 'wm_layout_preview',
+  WmGetSizeHints = 'wm_get_size_hints',
+
+  // Processes
+  ProcessesGetRunning = 'processes_get_running',
+
+  // Start Menu
+  GetStartMenuItems = 'get_start_menu_items',
 
   // App Launcher
   LauncherGetApps = 'launcher_get_apps',
+  LauncherGetItemActions = 'launcher_get_item_actions',
+  LauncherRunItemAction = 'launcher_run_item_action',
 
   // Tray Icons
   TempGetByEventTrayInfo = 'temp_get_by_event_tray_info',
@@ -78,6 +262,24 @@ export enum SeelenCommand {
   // Notifications
   NotificationsClose = 'notifications_close',
   NotificationsCloseAll = 'notifications_close_all',
+
+  // Keybindings
+  GetKeybindingsConflicts = 'get_keybindings_conflicts',
+  KeybindingsDiagnose = 'keybindings_diagnose',
+
+  // Supervisor
+  WebviewHeartbeat = 'webview_heartbeat',
+
+  // Perf
+  GetPerfMetrics = 'get_perf_metrics',
+
+  // Emit Scheduler
+  SubscribeScheduledEvent = 'subscribe_scheduled_event',
+  UnsubscribeScheduledEvent = 'unsubscribe_scheduled_event',
+
+  // Event Registry
+  SubscribeEvent = 'subscribe_event',
+  UnsubscribeEvent = 'unsubscribe_event',
 }
 
 type ReturnTypeByCommand = Record<SeelenCommand, unknown> & {
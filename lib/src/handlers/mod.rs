@@ -8,6 +8,9 @@ impl SeelenEvent {
     pub const GlobalFocusChanged: &str = "global-focus-changed";
     pub const GlobalMouseMove: &str = "global-mouse-move";
     pub const GlobalMonitorsChanged: &str = "global-monitors-changed";
+    pub const MonitorScaleChanged: &str = "monitor-scale-changed";
+    pub const GlobalAlwaysOnTopChanged: &str = "global-always-on-top-changed";
+    pub const GlobalUrgentChanged: &str = "global-urgent-changed";
 
 
     pub const HandleLayeredHitboxes: &str = "handle-layered";
@@ -25,8 +28,17 @@ impl SeelenEvent {
 
     pub const PowerStatus: &str = "power-status";
     pub const BatteriesStatus: &str = "batteries-status";
+    pub const PowerProfileChanged: &str = "power-profile-changed";
 
     pub const ColorsChanged: &str = "colors-changed";
+    pub const LayoutDirectionChanged: &str = "layout-direction-changed";
+    pub const LiveThemeCssChanged: &str = "live-theme-css-changed";
+
+    pub const KeybindingsConflicts: &str = "keybindings-conflicts";
+    pub const KeybindingsHints: &str = "keybindings-hints";
+
+    pub const WebviewHeartbeat: &str = "webview-heartbeat";
+    pub const PerfMetrics: &str = "perf-metrics";
 
     pub const TrayInfo: &str = "tray-info";
 
@@ -48,15 +60,64 @@ impl SeelenEvent {
     pub const WMAddWindow: &str = "add-window";
     pub const WMUpdateWindow: &str = "update-window";
     pub const WMRemoveWindow: &str = "remove-window";
-    
+
     pub const WMForceRetiling: &str = "wm-force-retiling";
     pub const WMSetLayout: &str = "wm-set-layout";
     pub const WMSetOverlayVisibility: &str = "wm-set-overlay-visibility";
     pub const WMSetActiveWindow: &str = "wm-set-active-window";
+    pub const WMSetZonesOverlay: &str = "wm-set-zones-overlay";
 
     pub const WallStop: &str = "wall-stop";
+    pub const WallDailyWallpaperChanged: &str = "wall-daily-wallpaper-changed";
+    pub const WallWidgetClick: &str = "wall-widget-click";
+
+    pub const WindowSwitcherChanged: &str = "window-switcher-changed";
+    pub const WindowTagsChanged: &str = "window-tags-changed";
+
+    pub const NightLightChanged: &str = "night-light-changed";
+
+    pub const BrightnessChanged: &str = "brightness-changed";
+
+    pub const BluetoothChanged: &str = "bluetooth-changed";
+
+    pub const RemovableDrivesChanged: &str = "removable-drives-changed";
+
+    pub const StorageChanged: &str = "storage-changed";
+
+    pub const PrintersChanged: &str = "printers-changed";
+
+    pub const PrivateSessionChanged: &str = "private-session-changed";
+
+    pub const RecycleBinChanged: &str = "recycle-bin-changed";
+
+    pub const NotesChanged: &str = "notes-changed";
+
+    pub const QuickSettingsChanged: &str = "quick-settings-changed";
+
+    pub const SessionChanged: &str = "session-changed";
+
+    pub const CaptureTaken: &str = "capture-taken";
+
+    pub const ColorPickerLoupe: &str = "color-picker-loupe";
+
+    pub const BreakReminderChanged: &str = "break-reminder-changed";
+
+    pub const StartMenuItemsChanged: &str = "start-menu-items-changed";
+
+    pub const ProcessesChanged: &str = "processes-changed";
+
+    pub const ImeChanged: &str = "ime-changed";
+
+    pub const ClockTick: &str = "clock-tick";
+
+    pub const OsdShow: &str = "osd-show";
+
+    pub const UpdaterStatus: &str = "updater-status";
+    pub const VersionUpgraded: &str = "version-upgraded";
 
     pub const StateSettingsChanged: &str = "settings-changed";
+    pub const StateSettingsConflict: &str = "settings-conflict";
+    pub const StateLockedSettingsChanged: &str = "locked-settings";
     pub const StateWegItemsChanged: &str = "weg-items";
     pub const StateThemesChanged: &str = "themes";
     pub const StatePlaceholdersChanged: &str = "placeholders";
@@ -64,4 +125,5 @@ impl SeelenEvent {
     pub const StateSettingsByAppChanged: &str = "settings-by-app";
     pub const StateHistoryChanged: &str = "history";
     pub const StateIconPacksChanged: &str = "icon-packs";
+    pub const StateWidgetsChanged: &str = "widgets";
 }
@@ -1,21 +1,43 @@
-use seelen_core::state::{
-    AppConfig, IconPack, Placeholder, Settings, Theme, WegItem, WindowManagerLayout,
-};
+use std::path::Path;
 
-fn write_schema<T>(path: &str)
-where
-    T: schemars::JsonSchema,
-{
-    let schema = schemars::schema_for!(T);
-    std::fs::write(path, serde_json::to_string_pretty(&schema).unwrap()).unwrap();
+use seelen_core::state::ConfigSchema;
+
+/// Committed copies of the schemas, `scripts/build_schemas.ts` updates them from `./dist`
+const COMMITTED: [&str; 2] = ["../static/schemas", "../documentation/schemas"];
+
+/// Compares the generated schemas with the committed ones, returns the outdated files
+fn check() -> Vec<String> {
+    let mut outdated = Vec::new();
+    for kind in ConfigSchema::ALL {
+        let generated = kind.json();
+        for folder in COMMITTED {
+            let path = Path::new(folder).join(format!("{}.schema.json", kind.file_stem()));
+            let committed = std::fs::read_to_string(&path).unwrap_or_default();
+            if committed.replace("\r\n", "\n").trim_end() != generated.trim_end() {
+                outdated.push(path.display().to_string());
+            }
+        }
+    }
+    outdated
 }
 
 fn main() {
-    write_schema::<Settings>("./dist/settings.schema.json");
-    write_schema::<Placeholder>("./dist/placeholder.schema.json");
-    write_schema::<Theme>("./dist/theme.schema.json");
-    write_schema::<WindowManagerLayout>("./dist/layout.schema.json");
-    write_schema::<Vec<AppConfig>>("./dist/settings_by_app.schema.json");
-    write_schema::<Vec<WegItem>>("./dist/weg_items.schema.json");
-    write_schema::<IconPack>("./dist/icon_pack.schema.json");
+    if std::env::args().any(|arg| arg == "--check") {
+        let outdated = check();
+        if !outdated.is_empty() {
+            eprintln!("Outdated schemas, run `npm run build:schemas`:");
+            for path in outdated {
+                eprintln!("  {}", path);
+            }
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    std::fs::create_dir_all("./dist").unwrap();
+    for kind in ConfigSchema::ALL {
+        let stem = kind.file_stem();
+        std::fs::write(format!("./dist/{}.schema.json", stem), kind.json()).unwrap();
+        std::fs::write(format!("./dist/{}.md", stem), kind.markdown()).unwrap();
+    }
 }
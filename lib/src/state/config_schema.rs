@@ -0,0 +1,211 @@
+use schemars::{schema::RootSchema, JsonSchema};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{AppConfig, IconPack, Placeholder, Settings, Theme, WegItem, WindowManagerLayout};
+
+/// User config files documented by a JSON schema, generated from the serde types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ConfigSchema {
+    Settings,
+    Theme,
+    Placeholder,
+    Layout,
+    AppConfigs,
+    WegItems,
+    IconPack,
+}
+
+impl ConfigSchema {
+    pub const ALL: [Self; 7] = [
+        Self::Settings,
+        Self::Theme,
+        Self::Placeholder,
+        Self::Layout,
+        Self::AppConfigs,
+        Self::WegItems,
+        Self::IconPack,
+    ];
+
+    /// name of the generated files, example: `settings` -> `settings.schema.json` and `settings.md`
+    pub fn file_stem(&self) -> &'static str {
+        match self {
+            Self::Settings => "settings",
+            Self::Theme => "theme",
+            Self::Placeholder => "placeholder",
+            Self::Layout => "layout",
+            Self::AppConfigs => "settings_by_app",
+            Self::WegItems => "weg_items",
+            Self::IconPack => "icon_pack",
+        }
+    }
+
+    fn root_schema(&self) -> RootSchema {
+        match self {
+            Self::Settings => schemars::schema_for!(Settings),
+            Self::Theme => schemars::schema_for!(Theme),
+            Self::Placeholder => schemars::schema_for!(Placeholder),
+            Self::Layout => schemars::schema_for!(WindowManagerLayout),
+            Self::AppConfigs => schemars::schema_for!(Vec<AppConfig>),
+            Self::WegItems => schemars::schema_for!(Vec<WegItem>),
+            Self::IconPack => schemars::schema_for!(IconPack),
+        }
+    }
+
+    pub fn schema(&self) -> Value {
+        serde_json::to_value(self.root_schema()).unwrap_or_default()
+    }
+
+    /// Content of the `.schema.json` file, keeps the keywords in the order of the schema
+    /// instead of the alphabetical one of `Value`, so the generated files are stable.
+    pub fn json(&self) -> String {
+        serde_json::to_string_pretty(&self.root_schema()).unwrap_or_default()
+    }
+
+    /// Reference of the config file as markdown, one section per type of the schema
+    pub fn markdown(&self) -> String {
+        let schema = self.schema();
+        let title = schema
+            .get("title")
+            .and_then(Value::as_str)
+            .unwrap_or(self.file_stem());
+
+        let mut md = format!("# {}\n\n", title);
+        md.push_str(&format!(
+            "> Generated from the source code, the JSON schema is available as [{0}.schema.json](./{0}.schema.json).\n\n",
+            self.file_stem()
+        ));
+        write_section(&schema, &mut md);
+
+        if let Some(Value::Object(definitions)) = schema.get("definitions") {
+            md.push_str("## Definitions\n\n");
+            for (name, definition) in definitions {
+                md.push_str(&format!("### {}\n\n", name));
+                write_section(definition, &mut md);
+            }
+        }
+        md
+    }
+}
+
+/// Markdown tables break on pipes and new lines
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+/// Human readable type of the schema, references are linked to their section
+fn type_name(schema: &Value) -> String {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = reference.trim_start_matches("#/definitions/");
+        return format!("[{}](#{})", name, name.to_lowercase());
+    }
+
+    if let Some(Value::Array(values)) = schema.get("enum") {
+        return values
+            .iter()
+            .map(|value| format!("`{}`", value))
+            .collect::<Vec<_>>()
+            .join(" \\| ");
+    }
+
+    for combinator in ["allOf", "anyOf", "oneOf"] {
+        if let Some(Value::Array(options)) = schema.get(combinator) {
+            return options
+                .iter()
+                .map(type_name)
+                .collect::<Vec<_>>()
+                .join(" \\| ");
+        }
+    }
+
+    let types: Vec<&str> = match schema.get("type") {
+        Some(Value::String(kind)) => vec![kind.as_str()],
+        Some(Value::Array(kinds)) => kinds.iter().filter_map(Value::as_str).collect(),
+        _ => return "any".to_string(),
+    };
+
+    types
+        .into_iter()
+        .map(|kind| match kind {
+            "array" => match schema.get("items") {
+                Some(items) => format!("{}[]", type_name(items)),
+                None => "any[]".to_string(),
+            },
+            "object" => match schema.get("additionalProperties") {
+                Some(value @ Value::Object(_)) => format!("map of {}", type_name(value)),
+                _ => "object".to_string(),
+            },
+            kind => kind.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" \\| ")
+}
+
+/// Defaults too long to fit on a table are left to the JSON schema
+fn default_value(schema: &Value) -> String {
+    match schema.get("default") {
+        Some(value) => {
+            let value = value.to_string();
+            if value.len() > 40 {
+                "...".to_string()
+            } else {
+                format!("`{}`", cell(&value))
+            }
+        }
+        None => String::new(),
+    }
+}
+
+fn write_section(schema: &Value, md: &mut String) {
+    if let Some(description) = schema.get("description").and_then(Value::as_str) {
+        md.push_str(&format!("{}\n\n", description));
+    }
+
+    if let Some(Value::Object(properties)) = schema.get("properties") {
+        let required: Vec<&str> = match schema.get("required") {
+            Some(Value::Array(required)) => required.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        md.push_str("| Property | Type | Default | Description |\n");
+        md.push_str("| -------- | ---- | ------- | ----------- |\n");
+        for (key, property) in properties {
+            let key = if required.contains(&key.as_str()) {
+                format!("`{}` (required)", key)
+            } else {
+                format!("`{}`", key)
+            };
+            let description = property
+                .get("description")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            md.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                key,
+                type_name(property),
+                default_value(property),
+                cell(description)
+            ));
+        }
+        md.push('\n');
+        return;
+    }
+
+    // enums with documented variants
+    for combinator in ["oneOf", "anyOf"] {
+        if let Some(Value::Array(variants)) = schema.get(combinator) {
+            for variant in variants {
+                let description = variant
+                    .get("description")
+                    .and_then(Value::as_str)
+                    .map(|description| format!(": {}", cell(description)))
+                    .unwrap_or_default();
+                md.push_str(&format!("- {}{}\n", type_name(variant), description));
+            }
+            md.push('\n');
+            return;
+        }
+    }
+
+    md.push_str(&format!("Type: {}\n\n", type_name(schema)));
+}
@@ -0,0 +1,10 @@
+/** User config files documented by a JSON schema, generated from the serde types */
+export enum ConfigSchema {
+  Settings = 'settings',
+  Theme = 'theme',
+  Placeholder = 'placeholder',
+  Layout = 'layout',
+  AppConfigs = 'appConfigs',
+  WegItems = 'wegItems',
+  IconPack = 'iconPack',
+}
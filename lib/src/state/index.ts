@@ -7,7 +7,12 @@ export * from './wm_layout';
 export * from './placeholder';
 export * from './settings_by_app';
 export * from './settings_by_monitor';
+export * from './settings_conflict';
+export * from './settings_search';
 export * from './icon_pack';
+export * from './config_schema';
+export * from './marketplace';
+export * from './widget';
 
 export interface LauncherHistory {
   [x: string]: string[];
@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::ResourceMetadata;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum MarketplaceResourceKind {
+    Theme,
+    Placeholder,
+    Layout,
+    /// Resource containing more than one kind
+    Bundle,
+}
+
+/// Resource published on the marketplace.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketplaceEntry {
+    /// Unique id of the resource, used as filename on install.
+    pub id: String,
+    pub kind: MarketplaceResourceKind,
+    pub info: ResourceMetadata,
+    pub version: String,
+    /// Url of the `.slu` file
+    pub url: String,
+    /// Hex encoded sha256 of the `.slu` file, checked before install.
+    pub sha256: String,
+    /// Url of a preview image
+    #[serde(default)]
+    pub preview: Option<String>,
+    /// Average rating from 0 to 5
+    #[serde(default)]
+    pub rating: f32,
+    #[serde(default)]
+    pub ratings_count: u32,
+    #[serde(default)]
+    pub downloads: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MarketplaceIndex {
+    pub resources: Vec<MarketplaceEntry>,
+}
+
+/// Resource id -> installed version
+pub type InstalledResources = HashMap<String, String>;
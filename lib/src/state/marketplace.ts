@@ -0,0 +1,34 @@
+import { ResourceMetadata } from '.';
+
+export enum MarketplaceResourceKind {
+  Theme = 'theme',
+  Placeholder = 'placeholder',
+  Layout = 'layout',
+  /** Resource containing more than one kind */
+  Bundle = 'bundle',
+}
+
+export interface MarketplaceEntry {
+  /** Unique id of the resource, used as filename on install */
+  id: string;
+  kind: MarketplaceResourceKind;
+  info: ResourceMetadata;
+  version: string;
+  /** Url of the `.slu` file */
+  url: string;
+  /** Hex encoded sha256 of the `.slu` file, checked before install */
+  sha256: string;
+  /** Url of a preview image */
+  preview: string | null;
+  /** Average rating from 0 to 5 */
+  rating: number;
+  ratingsCount: number;
+  downloads: number;
+}
+
+export interface MarketplaceIndex {
+  resources: MarketplaceEntry[];
+}
+
+/** Resource id -> installed version */
+export type InstalledResources = Record<string, string>;
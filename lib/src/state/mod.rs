@@ -1,19 +1,29 @@
+mod config_schema;
 mod icon_pack;
+mod marketplace;
 mod placeholder;
 mod settings;
 mod settings_by_app;
 mod settings_by_monitor;
+mod settings_conflict;
+mod settings_search;
 mod theme;
 mod weg_items;
+mod widget;
 mod wm_layout;
 
+pub use config_schema::*;
 pub use icon_pack::*;
+pub use marketplace::*;
 pub use placeholder::*;
 pub use settings::*;
 pub use settings_by_app::*;
 pub use settings_by_monitor::*;
+pub use settings_conflict::*;
+pub use settings_search::*;
 pub use theme::*;
 pub use weg_items::*;
+pub use widget::*;
 pub use wm_layout::*;
 
 use schemars::JsonSchema;
@@ -1,4 +1,7 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -46,6 +49,9 @@ macro_rules! common_item {
                 ///
                 $(#[$scope])*
                 on_click_v2: Option<String>,
+                /// Actions executed by Seelen when interacting with the item, no code is needed.
+                #[serde(default)]
+                actions: ToolbarItemActions,
                 /// Styles to be added to the item. This follow the same interface of React's `style` prop.
                 #[serde(default)]
                 style: HashMap<String, Option<StyleValue>>,
@@ -66,6 +72,38 @@ pub enum StyleValue {
     Float(f64),
 }
 
+/// Action executed by Seelen, the same actions available for the external controllers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ToolbarItemAction {
+    /// Action of the catalog (`seelen-ui.exe get actions`), example:
+    /// `{ id: "virtual-desk.switch-workspace", params: { index: 0 } }`
+    Action {
+        id: String,
+        #[serde(default)]
+        params: HashMap<String, serde_json::Value>,
+    },
+    /// Arguments forwarded to the Seelen CLI, example: `["wm", "toggle-float"]`
+    Cli(Vec<String>),
+    /// Function of a user script, example: `{ file: "media.rhai", function: "next" }`
+    Script { file: String, function: String },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ToolbarItemWheelActions {
+    pub up: Option<ToolbarItemAction>,
+    pub down: Option<ToolbarItemAction>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ToolbarItemActions {
+    pub on_click: Option<ToolbarItemAction>,
+    pub on_middle_click: Option<ToolbarItemAction>,
+    pub on_wheel: ToolbarItemWheelActions,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum DateUpdateInterval {
@@ -100,6 +138,7 @@ common_item! {
     /// function imgFromUrl (url: string, size: number = 16): string
     /// function imgFromPath (path: string, size: number = 16): string
     /// function imgFromExe (exe_path: string, size: number = 16): string
+    /// function findBy(list: object[], key: string, value: any): object | null
     /// function t(path: string): string
     /// ```
     struct TextToolbarItem {}
@@ -118,6 +157,17 @@ common_item! {
     /// ## Date Item Scope
     /// ```ts
     /// const date: string; // the formatted date
+    ///
+    /// interface ClockEntry {
+    ///     id: string;
+    ///     label: string | null;
+    ///     timezone: string;
+    ///     text: string; // the time with the format of the clock
+    ///     week: number; // ISO 8601 week number
+    /// }
+    ///
+    /// // clocks of the settings by id
+    /// const clocks: Record<string, ClockEntry>;
     /// ```
     struct DateToolbarItem {
         /// @deprecated -- v2 uses settings date format instead (it will perform the minimal updates)
@@ -247,6 +297,14 @@ common_item! {
         #[serde(default = "WorkspaceToolbarItem::default_mode")]
         mode: WorkspaceToolbarItemMode,
     }
+
+    /// ## Widget Item Scope
+    /// this module does no expand the scope of the item, the content is rendered by the widget package
+    struct WidgetToolbarItem {
+        /// Id of the installed widget to render
+        #[serde(default)]
+        widget_id: String,
+    }
 }
 
 impl DateToolbarItem {
@@ -279,6 +337,7 @@ pub enum ToolbarItem {
     Device(DeviceToolbarItem),
     Settings(SettingsToolbarItem),
     Workspaces(WorkspaceToolbarItem),
+    Widget(WidgetToolbarItem),
 }
 
 impl ToolbarItem {
@@ -295,6 +354,7 @@ impl ToolbarItem {
             ToolbarItem::Device(item) => item.id.clone(),
             ToolbarItem::Settings(item) => item.id.clone(),
             ToolbarItem::Workspaces(item) => item.id.clone(),
+            ToolbarItem::Widget(item) => item.id.clone(),
         }
     }
 
@@ -311,6 +371,7 @@ impl ToolbarItem {
             ToolbarItem::Device(item) => item.id = id,
             ToolbarItem::Settings(item) => item.id = id,
             ToolbarItem::Workspaces(item) => item.id = id,
+            ToolbarItem::Widget(item) => item.id = id,
         }
     }
 }
@@ -328,11 +389,62 @@ pub struct PlaceholderInfo {
     pub filename: String,
 }
 
+/// Fragment files to include, a single path or a list of them
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum PlaceholderInclude {
+    One(PathBuf),
+    Many(Vec<PathBuf>),
+}
+
+impl Default for PlaceholderInclude {
+    fn default() -> Self {
+        Self::Many(Vec::new())
+    }
+}
+
+impl PlaceholderInclude {
+    pub fn paths(&self) -> &[PathBuf] {
+        match self {
+            Self::One(path) => std::slice::from_ref(path),
+            Self::Many(paths) => paths,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths().is_empty()
+    }
+}
+
+/// Reusable part of a placeholder, shared by including it from the placeholder files
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PlaceholderFragment {
+    /// Other fragments, relative to the folder of this fragment
+    pub include: PlaceholderInclude,
+    pub left: Vec<ToolbarItem>,
+    pub center: Vec<ToolbarItem>,
+    pub right: Vec<ToolbarItem>,
+}
+
+impl PlaceholderFragment {
+    /// Adds the items of `other` after the current ones
+    pub fn extend(&mut self, other: PlaceholderFragment) {
+        self.left.extend(other.left);
+        self.center.extend(other.center);
+        self.right.extend(other.right);
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct Placeholder {
     /// Metadata about the placeholder
     pub info: PlaceholderInfo,
+    /// Fragments with shared items, relative to the folder of the placeholder, example:
+    /// `fragments/clock.yml`. Their items go before the own items of each side.
+    #[serde(skip_serializing_if = "PlaceholderInclude::is_empty")]
+    pub include: PlaceholderInclude,
     /// Items to be displayed in the toolbar
     pub left: Vec<ToolbarItem>,
     /// Items to be displayed in the toolbar
@@ -342,6 +454,15 @@ pub struct Placeholder {
 }
 
 impl Placeholder {
+    /// Puts the items of the resolved includes before the own ones, the includes are cleared
+    /// so saving the placeholder writes a standalone copy.
+    pub fn apply_fragments(&mut self, fragments: PlaceholderFragment) {
+        self.include = PlaceholderInclude::default();
+        self.left.splice(0..0, fragments.left);
+        self.center.splice(0..0, fragments.center);
+        self.right.splice(0..0, fragments.right);
+    }
+
     fn sanitize_items(dict: &mut HashSet<String>, items: Vec<ToolbarItem>) -> Vec<ToolbarItem> {
         let mut result = Vec::new();
         for mut item in items {
@@ -363,3 +484,20 @@ impl Placeholder {
         self.right = Self::sanitize_items(&mut dict, std::mem::take(&mut self.right));
     }
 }
+
+/// Live data sources that can be pinned as items of the toolbar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum ToolbarPinSource {
+    RemovableDrive {
+        /// drive letter without the colon (e.g. `E`)
+        letter: String,
+    },
+    Printer {
+        name: String,
+    },
+    /// A network interface, e.g. a connected VPN
+    NetworkInterface {
+        name: String,
+    },
+}
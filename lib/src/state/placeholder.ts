@@ -10,6 +10,7 @@ export enum ToolbarModuleType {
   Tray = 'tray',
   Device = 'device',
   Notifications = 'notifications',
+  Widget = 'widget',
 }
 
 export enum WorkspaceTMMode {
@@ -31,6 +32,21 @@ export enum DeviceTMSubType {
   Memory = 'memory',
 }
 
+/** Action executed by Seelen, the same actions available for the external controllers */
+export type ToolbarItemAction =
+  | { action: { id: string; params: Record<string, unknown> } }
+  | { cli: string[] }
+  | { script: { file: string; function: string } };
+
+export interface ToolbarItemActions {
+  onClick: ToolbarItemAction | null;
+  onMiddleClick: ToolbarItemAction | null;
+  onWheel: {
+    up: ToolbarItemAction | null;
+    down: ToolbarItemAction | null;
+  };
+}
+
 export interface BaseToolbarModule {
   id: string;
   type: ToolbarModuleType;
@@ -40,6 +56,8 @@ export interface BaseToolbarModule {
   /** @deprecated, use `onClickV2` instead */
   onClick: string | null;
   onClickV2: string | null;
+  /** Actions executed by Seelen when interacting with the item, no code is needed */
+  actions: ToolbarItemActions;
   style: Record<string, any>;
 }
 
@@ -90,6 +108,12 @@ export interface WorkspacesTM extends BaseToolbarModule {
   mode: WorkspaceTMMode;
 }
 
+export interface WidgetTM extends BaseToolbarModule {
+  type: ToolbarModuleType.Widget;
+  /** Id of the installed widget to render */
+  widgetId: string;
+}
+
 export type ToolbarModule =
   | GenericToolbarModule
   | DateToolbarModule
@@ -100,7 +124,8 @@ export type ToolbarModule =
   | NetworkTM
   | MediaTM
   | DeviceTM
-  | NotificationsTM;
+  | NotificationsTM
+  | WidgetTM;
 
 export interface CreatorInfo {
   displayName: string;
@@ -109,9 +134,42 @@ export interface CreatorInfo {
   filename: string;
 }
 
+/** Reusable part of a placeholder, shared by including it from the placeholder files */
+export interface PlaceholderFragment {
+  include: string | string[];
+  left: ToolbarModule[];
+  center: ToolbarModule[];
+  right: ToolbarModule[];
+}
+
 export interface Placeholder {
   info: CreatorInfo;
+  /** Fragments with shared items, relative to the folder of the placeholder */
+  include?: string | string[];
   left: ToolbarModule[];
   center: ToolbarModule[];
   right: ToolbarModule[];
 }
+
+export interface RemovableDrivePinSource {
+  kind: 'RemovableDrive';
+  /** drive letter without the colon (e.g. `E`) */
+  letter: string;
+}
+
+export interface PrinterPinSource {
+  kind: 'Printer';
+  name: string;
+}
+
+/** A network interface, e.g. a connected VPN */
+export interface NetworkInterfacePinSource {
+  kind: 'NetworkInterface';
+  name: string;
+}
+
+/** Live data sources that can be pinned as items of the toolbar */
+export type ToolbarPinSource =
+  | RemovableDrivePinSource
+  | PrinterPinSource
+  | NetworkInterfacePinSource;
@@ -11,7 +11,7 @@ use serde_alias::serde_alias;
 
 use crate::rect::Rect;
 
-use super::MonitorConfiguration;
+use super::{MonitorConfiguration, WindowCorners, WorkspaceMonitorRule};
 
 // ============== Fancy Toolbar Settings ==============
 
@@ -61,6 +61,15 @@ pub enum HideMode {
     OnOverlap,
 }
 
+/// What to do with the pinned items whose target doesn't exist, e.g. on an unplugged drive
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub enum WegOrphanedItems {
+    /// keep the item greyed out until the target is available again
+    Keep,
+    /// remove the item from the dock
+    Remove,
+}
+
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub enum SeelenWegSide {
     Left,
@@ -93,6 +102,10 @@ pub struct SeelenWegSettings {
     pub padding: u32,
     /// space between items in px
     pub space_between_items: u32,
+    /// show the connected removable drives at the end of the dock
+    pub pin_removable_drives: bool,
+    /// what to do with the items whose target is missing
+    pub orphaned_items: WegOrphanedItems,
 }
 
 impl Default for SeelenWegSettings {
@@ -108,6 +121,8 @@ impl Default for SeelenWegSettings {
             margin: 8,
             padding: 8,
             space_between_items: 8,
+            pin_removable_drives: false,
+            orphaned_items: WegOrphanedItems::Keep,
         }
     }
 }
@@ -128,6 +143,12 @@ pub struct Border {
     pub enabled: bool,
     pub width: f64,
     pub offset: f64,
+    /// hex color of the focused window border, `None` to use the system accent color
+    pub color: Option<String>,
+    /// corner radius in px
+    pub radius: f64,
+    /// duration in milliseconds of the transition between focused windows, 0 to disable it
+    pub animation_duration: u64,
 }
 
 #[serde_alias(SnakeCase)]
@@ -138,6 +159,41 @@ pub struct FloatingWindowSettings {
     pub height: f64,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum ZonesModifier {
+    Shift,
+    Ctrl,
+    Alt,
+}
+
+#[serde_alias(SnakeCase)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ZonesSettings {
+    pub enabled: bool,
+    /// key to hold while dragging a window to show the zones of the layout
+    pub modifier: ZonesModifier,
+}
+
+#[serde_alias(SnakeCase)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SnapSettings {
+    /// adopt the windows snapped by Windows into the layout instead of moving them back
+    pub interop: bool,
+    /// disable the native snap layouts flyout and bar, dragging a window to the top of the
+    /// monitor shows the zones of the layout instead
+    pub replace_flyout: bool,
+}
+
+/// Corners applied by the window manager, the rules of the apps have priority
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WmCornersSettings {
+    pub tiled: WindowCorners,
+    pub floating: WindowCorners,
+}
+
 #[serde_alias(SnakeCase)]
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
@@ -159,8 +215,16 @@ pub struct WindowManagerSettings {
     pub workspace_margin: Rect,
     /// floating window settings
     pub floating: FloatingWindowSettings,
+    /// drag snapping to the zones of the layout
+    pub zones: ZonesSettings,
+    /// cooperation with the native snap layouts of Windows
+    pub snap: SnapSettings,
+    /// corners of the tiled and floating windows
+    pub corners: WmCornersSettings,
     /// default layout
     pub default_layout: String,
+    /// swap left and right on the directional commands (focus) while the UI is right-to-left
+    pub mirror_directions_on_rtl: bool,
 }
 
 impl Default for Border {
@@ -169,6 +233,9 @@ impl Default for Border {
             enabled: true,
             width: 3.0,
             offset: 0.0,
+            color: None,
+            radius: 8.0,
+            animation_duration: 150,
         }
     }
 }
@@ -182,6 +249,15 @@ impl Default for FloatingWindowSettings {
     }
 }
 
+impl Default for ZonesSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            modifier: ZonesModifier::Shift,
+        }
+    }
+}
+
 impl Default for WindowManagerSettings {
     fn default() -> Self {
         Self {
@@ -193,7 +269,11 @@ impl Default for WindowManagerSettings {
             workspace_padding: 10,
             workspace_margin: Rect::default(),
             floating: FloatingWindowSettings::default(),
+            zones: ZonesSettings::default(),
+            snap: SnapSettings::default(),
+            corners: WmCornersSettings::default(),
             default_layout: String::from("default.yml"),
+            mirror_directions_on_rtl: false,
         }
     }
 }
@@ -262,6 +342,18 @@ impl SeelenLauncherSettings {
 
 // ================= Seelen Wall ================
 
+/// Where the wallpapers shown by the wall come from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum WallpaperSource {
+    /// the backgrounds selected by the user
+    #[default]
+    Files,
+    /// daily image of bing.com
+    Bing,
+    /// image of the Windows Spotlight (lock screen) feed
+    Spotlight,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SeelenWallWallpaper {
@@ -269,21 +361,56 @@ pub struct SeelenWallWallpaper {
     pub path: PathBuf,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SeelenWallWidgetKind {
+    Clock,
+    Notes,
+    /// a note of the sticky notes folder
+    StickyNote,
+}
+
+/// Interactive widget drawn over the wallpaper
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SeelenWallWidget {
+    pub id: String,
+    pub kind: SeelenWallWidgetKind,
+    /// distance in logical pixels from the left of the wall
+    #[serde(default)]
+    pub x: i32,
+    /// distance in logical pixels from the top of the wall
+    #[serde(default)]
+    pub y: i32,
+    /// content of the notes widget
+    #[serde(default)]
+    pub text: String,
+    /// note shown by the sticky note widget, the newest one if not set
+    #[serde(default)]
+    pub note_id: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct SeelenWallSettings {
     pub enabled: bool,
+    /// the daily sources are downloaded and cached, `backgrounds` is used while offline
+    pub source: WallpaperSource,
     pub backgrounds: Vec<SeelenWallWallpaper>,
     /// update interval in seconds
     pub interval: u64,
+    /// widgets on the wallpaper, only their area receives clicks, the rest goes to the desktop
+    pub widgets: Vec<SeelenWallWidget>,
 }
 
 impl Default for SeelenWallSettings {
     fn default() -> Self {
         Self {
             enabled: true,
+            source: WallpaperSource::Files,
             backgrounds: vec![],
             interval: 60,
+            widgets: vec![],
         }
     }
 }
@@ -294,6 +421,20 @@ impl SeelenWallSettings {
     }
 }
 
+/// How the native wallpaper is drawn on the monitors
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum WallpaperFit {
+    /// cropped to cover the whole monitor
+    #[default]
+    Fill,
+    /// scaled to be fully visible, with bars on the uncovered sides
+    Fit,
+    /// a single image across all the monitors
+    Span,
+    /// repeated at its original size
+    Tile,
+}
+
 // ============== Ahk Variables ==============
 
 #[macro_export]
@@ -462,6 +603,283 @@ impl Default for AhkVarList {
     }
 }
 
+// ============== Actions ==============
+
+/// User defined name for an action of the registry with preset params, usable everywhere
+/// an action id is expected (keybindings, triggers, cli, toolbar and remote controllers)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionAlias {
+    /// example: `ws-1`
+    pub name: String,
+    /// id of the aliased action, example: `virtual-desk.switch-workspace`
+    pub id: String,
+    /// params of the action, the ones given when running the alias take precedence
+    #[serde(default)]
+    pub params: HashMap<String, serde_json::Value>,
+}
+
+// ============== Keybindings ==============
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum KeybindingAction {
+    /// Shows/Hides the app launcher
+    ToggleLauncher,
+    /// Opens the settings window
+    OpenSettings,
+    /// Switches to the workspace at the given index
+    SwitchWorkspace(usize),
+    /// Sends the focused window to the workspace at the given index and switches to it
+    MoveToWorkspace(usize),
+    /// Sends the focused window to the workspace at the given index
+    SendToWorkspace(usize),
+    /// Switches to the next workspace
+    SwitchNextWorkspace,
+    /// Switches to the previous workspace
+    SwitchPrevWorkspace,
+    /// Window manager subcommand and its arguments, example: `["focus", "left"]`
+    WindowManager(Vec<String>),
+    /// Arguments forwarded to the Seelen CLI, example: `["weg", "foreground-or-run-app", "0"]`
+    Cli(Vec<String>),
+    /// External program to execute
+    Run { program: String, args: Vec<String> },
+    /// Action of the registry or user alias (`seelen-ui.exe get actions`), example:
+    /// `{ id: "manager.toggle-float" }`
+    Action {
+        id: String,
+        #[serde(default)]
+        params: HashMap<String, serde_json::Value>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum KeyMatchMode {
+    /// keys are matched by the character they produce on the current keyboard layout
+    #[default]
+    VirtualKey,
+    /// keys are matched by their physical position, named as on a US QWERTY keyboard,
+    /// so bindings keep working when switching between layouts like AZERTY or Cyrillic
+    ScanCode,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Keybinding {
+    /// keys combination, example: "Win + Shift + 1"
+    pub keys: String,
+    /// follow-up combinations to be pressed after `keys` (leader-key style), example: ["W", "1"]
+    #[serde(default)]
+    pub then: Vec<String>,
+    /// action to execute when the combination is pressed
+    pub action: KeybindingAction,
+    /// overrides the global match mode for this binding
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_mode: Option<KeyMatchMode>,
+}
+
+impl Keybinding {
+    pub fn new(keys: &str, action: KeybindingAction) -> Self {
+        Self {
+            keys: keys.to_string(),
+            then: Vec::new(),
+            action,
+            match_mode: None,
+        }
+    }
+
+    /// all the steps of the binding, the leader combination included
+    pub fn sequence(&self) -> Vec<&str> {
+        std::iter::once(self.keys.as_str())
+            .chain(self.then.iter().map(String::as_str))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct KeybindingsSettings {
+    /// enable or disable the integrated hotkey manager, when enabled AHK shortcuts are not started
+    pub enabled: bool,
+    /// list of keybindings
+    pub bindings: Vec<Keybinding>,
+    /// max time in milliseconds to wait for the next key of a multi-step binding
+    pub sequence_timeout: u64,
+    /// how the keys of the bindings are matched against the pressed ones
+    pub match_mode: KeyMatchMode,
+}
+
+impl Default for KeybindingsSettings {
+    fn default() -> Self {
+        use KeybindingAction::*;
+        let wm = |args: &[&str]| WindowManager(args.iter().map(|a| a.to_string()).collect());
+
+        let mut bindings = vec![
+            // launcher
+            Keybinding::new("Win + Space", ToggleLauncher),
+            // wm
+            Keybinding::new("Win + Shift + I", wm(&["reserve", "top"])),
+            Keybinding::new("Win + Shift + K", wm(&["reserve", "bottom"])),
+            Keybinding::new("Win + Shift + J", wm(&["reserve", "left"])),
+            Keybinding::new("Win + Shift + L", wm(&["reserve", "right"])),
+            Keybinding::new("Win + Shift + U", wm(&["reserve", "float"])),
+            Keybinding::new("Win + Shift + O", wm(&["reserve", "stack"])),
+            Keybinding::new("Win + Shift + W", wm(&["focus", "up"])),
+            Keybinding::new("Win + Shift + S", wm(&["focus", "down"])),
+            Keybinding::new("Win + Shift + A", wm(&["focus", "left"])),
+            Keybinding::new("Win + Shift + D", wm(&["focus", "right"])),
+            Keybinding::new("Win + Shift + E", wm(&["focus", "latest"])),
+            Keybinding::new("Win + Alt + =", wm(&["width", "increase"])),
+            Keybinding::new("Win + Alt + -", wm(&["width", "decrease"])),
+            Keybinding::new("Win + Shift + =", wm(&["height", "increase"])),
+            Keybinding::new("Win + Shift + -", wm(&["height", "decrease"])),
+            Keybinding::new("Win + Alt + 0", wm(&["reset-workspace-size"])),
+            Keybinding::new("Win + Alt + M", wm(&["send-to-cursor"])),
+            // focus history
+            Keybinding::new("Alt + `", Cli(vec!["focus".into(), "previous".into()])),
+            // miscellaneous
+            Keybinding::new("Win + K", OpenSettings),
+        ];
+
+        // virtual desktops
+        for (idx, key) in ["1", "2", "3", "4", "5", "6", "7", "8", "9", "0"]
+            .iter()
+            .enumerate()
+        {
+            bindings.push(Keybinding::new(
+                &format!("Alt + {key}"),
+                SwitchWorkspace(idx),
+            ));
+            bindings.push(Keybinding::new(
+                &format!("Alt + Shift + {key}"),
+                MoveToWorkspace(idx),
+            ));
+            bindings.push(Keybinding::new(
+                &format!("Win + Shift + {key}"),
+                SendToWorkspace(idx),
+            ));
+        }
+
+        Self {
+            enabled: false,
+            bindings,
+            sequence_timeout: 1500,
+            match_mode: KeyMatchMode::default(),
+        }
+    }
+}
+
+// ============== Logging ==============
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LoggingSettings {
+    /// default level for all modules
+    pub level: LogLevel,
+    /// level overrides by module path, ex: `seelen_ui::hook` or `seelen_ui::seelen_weg`
+    pub modules: HashMap<String, LogLevel>,
+    /// trace every window event received by the global hook
+    pub win_events: bool,
+    /// max size in bytes of the log file before rotating it
+    pub max_file_size: u64,
+    /// amount of rotated log files to keep
+    pub max_files: usize,
+}
+
+impl Default for LoggingSettings {
+    fn default() -> Self {
+        Self {
+            level: LogLevel::Info,
+            modules: HashMap::new(),
+            win_events: false,
+            max_file_size: 5 * 1024 * 1024,
+            max_files: 5,
+        }
+    }
+}
+
+// ============== Triggers ==============
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum TriggerEvent {
+    /// A window of the given executable gets the focus, example: `firefox.exe`
+    FocusChanged { exe: String },
+    /// The active workspace changes, to any workspace if `index` is not set
+    WorkspaceSwitched { index: Option<usize> },
+    /// The battery goes below the given percentage while discharging
+    BatteryBelow { percentage: u8 },
+    /// A process of the given executable starts, including background processes, example: `obs64.exe`
+    ProcessStarted { exe: String },
+    /// A process of the given executable exits
+    ProcessStopped { exe: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum TriggerAction {
+    /// External program to execute, arguments are passed as they are (no shell is used).
+    /// It runs with low integrity, so it can't write on the user files, registry or other apps.
+    Run { program: String, args: Vec<String> },
+    /// Arguments forwarded to the Seelen CLI, example: `["wm", "toggle-float"]`
+    Cli(Vec<String>),
+    /// Url that will receive a POST request with the event as json body
+    Webhook { url: String },
+    /// Action of the registry or user alias, example: `{ id: "private.on" }`
+    Action {
+        id: String,
+        #[serde(default)]
+        params: HashMap<String, serde_json::Value>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Trigger {
+    #[serde(default = "Trigger::default_enabled")]
+    pub enabled: bool,
+    pub on: TriggerEvent,
+    pub action: TriggerAction,
+}
+
+impl Trigger {
+    fn default_enabled() -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TriggersSettings {
+    pub enabled: bool,
+    /// min time in milliseconds between two executions of the same trigger
+    pub cooldown: u64,
+    /// max executions per minute of all the triggers together
+    pub max_per_minute: u32,
+    pub triggers: Vec<Trigger>,
+}
+
+impl Default for TriggersSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cooldown: 5000,
+            max_per_minute: 30,
+            triggers: Vec::new(),
+        }
+    }
+}
+
 // ========================== Seelen Updates ==============================
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -472,19 +890,480 @@ pub enum UpdateChannel {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-#[serde(rename_all = "camelCase")]
+#[serde(default, rename_all = "camelCase")]
 pub struct UpdaterSettings {
     pub channel: UpdateChannel,
+    /// download the updates on background, they will be installed on next restart
+    pub auto_download: bool,
 }
 
 impl Default for UpdaterSettings {
     fn default() -> Self {
         Self {
             channel: UpdateChannel::Release,
+            auto_download: true,
         }
     }
 }
 
+// ========================== Native Taskbar ==============================
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub enum NativeTaskbarMode {
+    /// hidden on the monitors where seelenweg is enabled
+    Auto,
+    Visible,
+    Hidden,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NativeTaskbarSettings {
+    pub mode: NativeTaskbarMode,
+    /// open the seelen launcher instead of the start menu when the Windows key is pressed alone
+    pub suppress_start_menu: bool,
+}
+
+impl Default for NativeTaskbarSettings {
+    fn default() -> Self {
+        Self {
+            mode: NativeTaskbarMode::Auto,
+            suppress_start_menu: false,
+        }
+    }
+}
+
+// ========================== Window Switcher ==============================
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WindowSwitcherSettings {
+    /// replace the native Alt + Tab with the seelen window switcher
+    pub enabled: bool,
+    /// capture a thumbnail of each window when the switcher is opened
+    pub thumbnails: bool,
+}
+
+impl Default for WindowSwitcherSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            thumbnails: true,
+        }
+    }
+}
+
+// ============================ Night Light ================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NightLightSettings {
+    /// turn the night light on and off automatically between `start` and `end`
+    pub scheduled: bool,
+    /// local time with format `HH:MM`
+    pub start: String,
+    /// local time with format `HH:MM`
+    pub end: String,
+    /// color temperature in kelvin while active, 6500 is neutral
+    pub temperature: u32,
+    /// duration in milliseconds of the transition between temperatures
+    pub transition_duration: u64,
+}
+
+impl Default for NightLightSettings {
+    fn default() -> Self {
+        Self {
+            scheduled: false,
+            start: "20:00".to_string(),
+            end: "07:00".to_string(),
+            temperature: 3400,
+            transition_duration: 3000,
+        }
+    }
+}
+
+// ============================= Brightness ================================
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BrightnessSettings {
+    /// changing the brightness of a monitor changes all the others by the same proportion
+    pub linked: bool,
+}
+
+// ============================ Hardware Keys ==============================
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HardwareKeysSettings {
+    /// handle the volume and media keys instead of Windows, emitting OSD events so Seelen
+    /// can show its own popups instead of the native flyouts
+    pub enabled: bool,
+    /// percent of volume changed by each press of the volume keys
+    pub volume_step: u32,
+    /// percent of brightness changed by each brightness up/down command
+    pub brightness_step: u32,
+}
+
+impl Default for HardwareKeysSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            volume_step: 2,
+            brightness_step: 10,
+        }
+    }
+}
+
+// ============================ Screen Capture ===============================
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CaptureSettings {
+    /// save the captures as png files on `folder`
+    pub save_to_folder: bool,
+    /// folder where the captures are saved, if null `Pictures/Screenshots` is used
+    pub folder: Option<PathBuf>,
+    /// copy the captured image to the clipboard
+    pub copy_to_clipboard: bool,
+}
+
+impl Default for CaptureSettings {
+    fn default() -> Self {
+        Self {
+            save_to_folder: true,
+            folder: None,
+            copy_to_clipboard: true,
+        }
+    }
+}
+
+// ============================== App Usage ==================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AppUsageSettings {
+    /// track the time that each app is on foreground
+    pub enabled: bool,
+    /// days to keep on the usage history
+    pub retention_days: u32,
+    /// show a notification after using the same app for a long time
+    pub nudge: bool,
+    /// minutes of continuous use of the same app before the notification is shown
+    pub nudge_after: u32,
+}
+
+impl Default for AppUsageSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            retention_days: 90,
+            nudge: false,
+            nudge_after: 120,
+        }
+    }
+}
+
+// ============================ Break Reminder ===============================
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BreakReminderSettings {
+    pub enabled: bool,
+    /// minutes of activity between breaks, time away from the computer is not counted
+    pub interval: u32,
+    /// minutes of each break, being away for this time counts as a break
+    pub duration: u32,
+    /// minutes to postpone a break when snoozed
+    pub snooze: u32,
+    /// dim the monitors while on break
+    pub dim: bool,
+    /// brightness of the monitors while dimmed, from 0 to 100
+    pub dim_level: u32,
+}
+
+impl Default for BreakReminderSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: 50,
+            duration: 5,
+            snooze: 5,
+            dim: false,
+            dim_level: 20,
+        }
+    }
+}
+
+// ============================ Accessibility ================================
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AccessibilitySettings {
+    /// announce the workspace to screen readers when switching workspaces
+    pub announce_workspaces: bool,
+    /// announce the focused window to screen readers when the focus is moved by a wm command
+    pub announce_focus: bool,
+}
+
+// ============================= Diagnostics =================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DiagnosticsSettings {
+    /// save a minidump and the recent logs when the app crashes, nothing leaves the machine
+    /// unless the diagnostics bundle is exported
+    pub crash_reports: bool,
+    /// amount of crash reports to keep, the oldest are removed
+    pub max_reports: usize,
+}
+
+impl Default for DiagnosticsSettings {
+    fn default() -> Self {
+        Self {
+            crash_reports: false,
+            max_reports: 10,
+        }
+    }
+}
+
+/// Adaptations applied while the battery saver of Windows is on and the device is on battery
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PowerSavingSettings {
+    pub enabled: bool,
+    /// max times per second that the mouse is polled and high frequency events are sent
+    pub max_emit_rate: u32,
+    /// pause wallpaper videos and slideshows
+    pub pause_wallpaper: bool,
+    /// poll brightness, wifi networks and metrics less often
+    pub reduce_sampling: bool,
+    /// disable the animations of the focus border and the webviews
+    pub disable_animations: bool,
+}
+
+impl Default for PowerSavingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_emit_rate: 10,
+            pause_wallpaper: true,
+            reduce_sampling: true,
+            disable_animations: true,
+        }
+    }
+}
+
+/// Rules used to decide which windows are shown on the dock and tiled by the window manager
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WindowFiltersSettings {
+    /// ignore tool windows and windows that can't be activated, unless they are app windows
+    pub by_styles: bool,
+    /// ignore windows with a parent or owner, like dialogs and popups
+    pub by_owner: bool,
+    /// window classes that are always ignored
+    pub class_blacklist: Vec<String>,
+    /// windows narrower than this are ignored, in logical pixels, 0 to disable
+    pub min_width: u32,
+    /// windows shorter than this are ignored, in logical pixels, 0 to disable
+    pub min_height: u32,
+}
+
+impl Default for WindowFiltersSettings {
+    fn default() -> Self {
+        Self {
+            by_styles: true,
+            by_owner: true,
+            // used by edge for the tabs shown as independent windows on alt + tab
+            class_blacklist: vec!["Windows.Internal.Shell.TabProxyWindow".to_string()],
+            min_width: 0,
+            min_height: 0,
+        }
+    }
+}
+
+/// Local HTTP API for companions (Stream Deck, phones, etc.), mirrors the IPC methods
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RemoteControlSettings {
+    pub enabled: bool,
+    /// port listened on 127.0.0.1
+    pub port: u16,
+    /// required as `Authorization: Bearer <token>` on every request, the server is not
+    /// started while it has less than 24 characters
+    pub token: String,
+}
+
+impl Default for RemoteControlSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 47331,
+            token: String::new(),
+        }
+    }
+}
+
+/// Dark or light mode forced on the titlebars of the app windows
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub enum TitlebarMode {
+    /// each app decides
+    Untouched,
+    /// follow the dark/light mode of the system colors
+    System,
+    Dark,
+    Light,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TitlebarSettings {
+    pub mode: TitlebarMode,
+    /// paint the titlebars with the accent color, only supported on Windows 11
+    pub accent_caption: bool,
+}
+
+impl Default for TitlebarSettings {
+    fn default() -> Self {
+        Self {
+            mode: TitlebarMode::Untouched,
+            accent_caption: false,
+        }
+    }
+}
+
+/// Opacity changed by the user with the `window opacity` commands
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct WindowOpacitySettings {
+    /// apply the last opacity set on a window to all the windows of the same app, also after restart
+    pub remember_per_app: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StorageSettings {
+    /// notify when a volume is low on space or a disk is overheating or failing
+    pub alerts: bool,
+    /// percent of free space of a volume below which it is considered low
+    pub low_space_threshold: u32,
+    /// temperature in celsius from which a disk is considered overheating
+    pub temperature_threshold: u32,
+}
+
+impl Default for StorageSettings {
+    fn default() -> Self {
+        Self {
+            alerts: true,
+            low_space_threshold: 10,
+            temperature_threshold: 60,
+        }
+    }
+}
+
+/// Limits the memory used by Seelen while its surfaces can't be seen
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ResourceGovernorSettings {
+    pub enabled: bool,
+    /// seconds that the toolbar or dock must be hidden before unloading its content
+    pub suspend_after: u64,
+    /// unload the content of the toolbars, docks and wallpaper while the displays are off
+    pub suspend_on_display_off: bool,
+    /// percent of used system memory from which the caches are trimmed
+    pub memory_pressure_threshold: u32,
+}
+
+impl Default for ResourceGovernorSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            suspend_after: 300,
+            suspend_on_display_off: true,
+            memory_pressure_threshold: 85,
+        }
+    }
+}
+
+/// Corner of the monitor where the picture-in-picture windows are placed
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub enum PipCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PictureInPictureSettings {
+    /// place, pin and keep on top the windows matching the picture-in-picture app rules
+    pub enabled: bool,
+    /// corner where new picture-in-picture windows are placed
+    pub corner: PipCorner,
+    /// distance in pixels to the borders of the work area
+    pub margin: u32,
+    /// preset widths as percent of the width of the monitor, the aspect ratio is kept
+    pub sizes: Vec<u32>,
+}
+
+impl Default for PictureInPictureSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            corner: PipCorner::BottomRight,
+            margin: 16,
+            sizes: vec![20, 30, 40],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ClockConfig {
+    /// unique id, used by the toolbar items to pick this clock
+    pub id: String,
+    pub label: Option<String>,
+    /// IANA time zone id like `America/New_York`, the local time zone if none
+    pub timezone: Option<String>,
+    /// strftime-like format, e.g. `%H:%M`, `%a %d %b` or `W%V`
+    pub format: String,
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            label: None,
+            timezone: None,
+            format: "%H:%M".to_owned(),
+        }
+    }
+}
+
+/// How often the clocks are updated, the ticks are aligned to the boundaries of the wall clock
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum ClockTickMode {
+    /// each second or minute, depending on the formats in use
+    #[default]
+    Auto,
+    /// each second, even if the formats don't show the seconds
+    Seconds,
+    /// each 100ms, needed to show the tenths of second
+    Precise,
+}
+
+/// Clocks computed by the background and emitted on each tick, so the clock widgets
+/// don't need their own timers
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ClockSettings {
+    pub clocks: Vec<ClockConfig>,
+    /// the ticks slow down to each minute while the toolbars are hidden or the displays are off
+    pub tick_mode: ClockTickMode,
+}
+
 // ======================== Final Settings Struct ===============================
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -493,6 +1372,18 @@ pub enum VirtualDesktopStrategy {
     Seelen,
 }
 
+/// languages written from right to left, used to resolve `TextDirection::Auto`
+pub const RTL_LANGUAGES: [&str; 5] = ["ar", "fa", "he", "ps", "ur"];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum TextDirection {
+    /// right-to-left if the language is written that way
+    #[default]
+    Auto,
+    Ltr,
+    Rtl,
+}
+
 #[serde_alias(SnakeCase)]
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
@@ -513,6 +1404,10 @@ pub struct Settings {
     pub ahk_enabled: bool,
     /// ahk variables
     pub ahk_variables: AhkVarList,
+    /// integrated hotkey manager config
+    pub keybindings: KeybindingsSettings,
+    /// user defined names for the actions of the registry
+    pub action_aliases: Vec<ActionAlias>,
     /// list of selected themes
     #[serde(alias = "selected_theme")]
     pub selected_themes: Vec<String>,
@@ -520,14 +1415,62 @@ pub struct Settings {
     pub icon_packs: Vec<String>,
     /// enable or disable dev tools tab in settings
     pub dev_tools: bool,
+    /// max times per second that high frequency events (mouse move, focus) are sent to the UI
+    pub max_emit_rate: u32,
     /// language to use, if null the system locale is used
     pub language: Option<String>,
+    /// direction of the layout of the surfaces (toolbar, dock, launcher)
+    pub text_direction: TextDirection,
     /// MomentJS date format
     pub date_format: String,
     /// what virtual desktop implementation will be used, in case Native is not available we use Seelen
     pub virtual_desktop_strategy: VirtualDesktopStrategy,
+    /// workspaces bound to a monitor, their windows are moved to it when it is connected
+    pub workspace_monitor_rules: Vec<WorkspaceMonitorRule>,
     /// Updater Settings
     pub updater: UpdaterSettings,
+    /// Logging Settings
+    pub logging: LoggingSettings,
+    /// automations executed on internal events
+    pub triggers: TriggersSettings,
+    /// native windows taskbar and start menu config
+    pub native_taskbar: NativeTaskbarSettings,
+    /// alt + tab replacement config
+    pub window_switcher: WindowSwitcherSettings,
+    /// color temperature scheduler config
+    pub night_light: NightLightSettings,
+    /// brightness control config
+    pub brightness: BrightnessSettings,
+    /// screenshots config
+    pub capture: CaptureSettings,
+    /// app usage time tracking config
+    pub app_usage: AppUsageSettings,
+    /// eye strain break reminders config
+    pub break_reminder: BreakReminderSettings,
+    /// screen reader integration config
+    pub accessibility: AccessibilitySettings,
+    /// local crash reports config
+    pub diagnostics: DiagnosticsSettings,
+    /// rules to decide which windows are shown on the dock and tiled
+    pub window_filters: WindowFiltersSettings,
+    /// adaptations applied on battery saver
+    pub power_saving: PowerSavingSettings,
+    /// local http api config
+    pub remote_control: RemoteControlSettings,
+    /// dark/light titlebars enforcement
+    pub titlebar: TitlebarSettings,
+    /// opacity of the windows changed by the user
+    pub window_opacity: WindowOpacitySettings,
+    /// disk space and health alerts config
+    pub storage: StorageSettings,
+    /// memory limits of the background process and webviews
+    pub resource_governor: ResourceGovernorSettings,
+    /// placement of the picture-in-picture windows
+    pub picture_in_picture: PictureInPictureSettings,
+    /// extra clocks with their own time zone and format
+    pub clock: ClockSettings,
+    /// volume, brightness and media keys
+    pub hardware_keys: HardwareKeysSettings,
 }
 
 impl Default for Settings {
@@ -543,11 +1486,37 @@ impl Default for Settings {
             wall: SeelenWallSettings::default(),
             launcher: SeelenLauncherSettings::default(),
             ahk_variables: AhkVarList::default(),
+            keybindings: KeybindingsSettings::default(),
+            action_aliases: Vec::new(),
             dev_tools: false,
+            max_emit_rate: 30,
             language: Some(Self::get_system_language()),
+            text_direction: TextDirection::Auto,
             date_format: "ddd D MMM, hh:mm A".to_owned(),
             virtual_desktop_strategy: VirtualDesktopStrategy::Native,
+            workspace_monitor_rules: Vec::new(),
             updater: UpdaterSettings::default(),
+            logging: LoggingSettings::default(),
+            triggers: TriggersSettings::default(),
+            native_taskbar: NativeTaskbarSettings::default(),
+            window_switcher: WindowSwitcherSettings::default(),
+            night_light: NightLightSettings::default(),
+            brightness: BrightnessSettings::default(),
+            capture: CaptureSettings::default(),
+            app_usage: AppUsageSettings::default(),
+            break_reminder: BreakReminderSettings::default(),
+            accessibility: AccessibilitySettings::default(),
+            diagnostics: DiagnosticsSettings::default(),
+            window_filters: WindowFiltersSettings::default(),
+            power_saving: PowerSavingSettings::default(),
+            remote_control: RemoteControlSettings::default(),
+            titlebar: TitlebarSettings::default(),
+            window_opacity: WindowOpacitySettings::default(),
+            storage: StorageSettings::default(),
+            resource_governor: ResourceGovernorSettings::default(),
+            picture_in_picture: PictureInPictureSettings::default(),
+            clock: ClockSettings::default(),
+            hardware_keys: HardwareKeysSettings::default(),
         }
     }
 }
@@ -564,6 +1533,18 @@ impl Settings {
         }
     }
 
+    pub fn is_rtl(&self) -> bool {
+        match self.text_direction {
+            TextDirection::Ltr => false,
+            TextDirection::Rtl => true,
+            TextDirection::Auto => self
+                .language
+                .as_deref()
+                .and_then(|language| language.split('-').next())
+                .is_some_and(|language| RTL_LANGUAGES.contains(&language)),
+        }
+    }
+
     pub fn sanitize(&mut self) {
         self.launcher.sanitize();
         self.wall.sanitize();
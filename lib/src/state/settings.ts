@@ -1,6 +1,13 @@
-import { Obtainable, SeelenCommand, SeelenEvent } from '../handlers';
+import { invoke, Obtainable, SeelenCommand, SeelenEvent } from '../handlers';
 import { Rect } from '../utils';
-import { MonitorConfiguration } from './settings_by_monitor';
+import { WindowCorners } from './settings_by_app';
+import { MonitorConfiguration, WorkspaceMonitorRule } from './settings_by_monitor';
+
+export enum TextDirection {
+  Auto = 'Auto',
+  Ltr = 'Ltr',
+  Rtl = 'Rtl',
+}
 
 export enum VirtualDesktopStrategy {
   Native = 'Native',
@@ -18,6 +25,11 @@ export enum HideMode {
   OnOverlap = 'On-Overlap',
 }
 
+export enum WegOrphanedItems {
+  Keep = 'Keep',
+  Remove = 'Remove',
+}
+
 export enum SeelenWegSide {
   Left = 'Left',
   Right = 'Right',
@@ -30,11 +42,53 @@ export class SeelenWallWallpaper {
   path: string = '';
 }
 
+export enum WallpaperFit {
+  Fill = 'Fill',
+  Fit = 'Fit',
+  Span = 'Span',
+  Tile = 'Tile',
+}
+
+export enum SeelenWallWidgetKind {
+  Clock = 'clock',
+  Notes = 'notes',
+  /** a note of the sticky notes folder */
+  StickyNote = 'sticky_note',
+}
+
+/** Interactive widget drawn over the wallpaper */
+export class SeelenWallWidget {
+  id: string = crypto.randomUUID();
+  kind: SeelenWallWidgetKind = SeelenWallWidgetKind.Clock;
+  /** distance in logical pixels from the left of the wall */
+  x: number = 0;
+  /** distance in logical pixels from the top of the wall */
+  y: number = 0;
+  /** content of the notes widget */
+  text: string = '';
+  /** note shown by the sticky note widget, the newest one if not set */
+  noteId: string | null = null;
+}
+
+/** Where the wallpapers shown by the wall come from */
+export enum WallpaperSource {
+  /** the backgrounds selected by the user */
+  Files = 'Files',
+  /** daily image of bing.com */
+  Bing = 'Bing',
+  /** image of the Windows Spotlight (lock screen) feed */
+  Spotlight = 'Spotlight',
+}
+
 export class SeelenWallSettings {
   enabled: boolean = true;
+  /** the daily sources are downloaded and cached, `backgrounds` is used while offline */
+  source: WallpaperSource = WallpaperSource.Files;
   backgrounds: SeelenWallWallpaper[] = [];
   /** Interval in seconds */
   interval: number = 60;
+  /** widgets on the wallpaper, only their area receives clicks, the rest goes to the desktop */
+  widgets: SeelenWallWidget[] = [];
 }
 
 export enum SeelenLauncherMonitor {
@@ -55,6 +109,154 @@ export class SeelenLauncherSettings {
   runners: SeelenLauncherRunner[] = [];
 }
 
+/**
+ * User defined name for an action of the registry with preset params, usable everywhere
+ * an action id is expected (keybindings, triggers, cli, toolbar and remote controllers)
+ */
+export interface ActionAlias {
+  name: string;
+  /** id of the aliased action, example: `virtual-desk.switch-workspace` */
+  id: string;
+  /** params of the action, the ones given when running the alias take precedence */
+  params: Record<string, unknown>;
+}
+
+export type KeybindingAction =
+  | 'toggleLauncher'
+  | 'openSettings'
+  | 'switchNextWorkspace'
+  | 'switchPrevWorkspace'
+  | { switchWorkspace: number }
+  | { moveToWorkspace: number }
+  | { sendToWorkspace: number }
+  | { windowManager: string[] }
+  | { cli: string[] }
+  | { run: { program: string; args: string[] } }
+  | { action: { id: string; params: Record<string, unknown> } };
+
+export enum KeyMatchMode {
+  /** keys are matched by the character they produce on the current keyboard layout */
+  VirtualKey = 'VirtualKey',
+  /**
+   * keys are matched by their physical position, named as on a US QWERTY keyboard,
+   * so bindings keep working when switching between layouts like AZERTY or Cyrillic
+   */
+  ScanCode = 'ScanCode',
+}
+
+export class Keybinding {
+  keys: string;
+  /** follow-up combinations to be pressed after `keys` (leader-key style) */
+  then: string[] = [];
+  action: KeybindingAction;
+  /** overrides the global match mode for this binding */
+  matchMode?: KeyMatchMode;
+
+  constructor(keys: string, action: KeybindingAction) {
+    this.keys = keys;
+    this.action = action;
+  }
+}
+
+const workspaceKeys = ['1', '2', '3', '4', '5', '6', '7', '8', '9', '0'];
+
+export class KeybindingsSettings {
+  enabled: boolean = false;
+  bindings: Keybinding[] = [
+    // launcher
+    new Keybinding('Win + Space', 'toggleLauncher'),
+    // wm
+    new Keybinding('Win + Shift + I', { windowManager: ['reserve', 'top'] }),
+    new Keybinding('Win + Shift + K', { windowManager: ['reserve', 'bottom'] }),
+    new Keybinding('Win + Shift + J', { windowManager: ['reserve', 'left'] }),
+    new Keybinding('Win + Shift + L', { windowManager: ['reserve', 'right'] }),
+    new Keybinding('Win + Shift + U', { windowManager: ['reserve', 'float'] }),
+    new Keybinding('Win + Shift + O', { windowManager: ['reserve', 'stack'] }),
+    new Keybinding('Win + Shift + W', { windowManager: ['focus', 'up'] }),
+    new Keybinding('Win + Shift + S', { windowManager: ['focus', 'down'] }),
+    new Keybinding('Win + Shift + A', { windowManager: ['focus', 'left'] }),
+    new Keybinding('Win + Shift + D', { windowManager: ['focus', 'right'] }),
+    new Keybinding('Win + Shift + E', { windowManager: ['focus', 'latest'] }),
+    new Keybinding('Win + Alt + =', { windowManager: ['width', 'increase'] }),
+    new Keybinding('Win + Alt + -', { windowManager: ['width', 'decrease'] }),
+    new Keybinding('Win + Shift + =', { windowManager: ['height', 'increase'] }),
+    new Keybinding('Win + Shift + -', { windowManager: ['height', 'decrease'] }),
+    new Keybinding('Win + Alt + 0', { windowManager: ['reset-workspace-size'] }),
+    new Keybinding('Win + Alt + M', { windowManager: ['send-to-cursor'] }),
+    // focus history
+    new Keybinding('Alt + `', { cli: ['focus', 'previous'] }),
+    // miscellaneous
+    new Keybinding('Win + K', 'openSettings'),
+    // virtual desktops
+    ...workspaceKeys.flatMap((key, idx) => [
+      new Keybinding(`Alt + ${key}`, { switchWorkspace: idx }),
+      new Keybinding(`Alt + Shift + ${key}`, { moveToWorkspace: idx }),
+      new Keybinding(`Win + Shift + ${key}`, { sendToWorkspace: idx }),
+    ]),
+  ];
+  /** max time in milliseconds to wait for the next key of a multi-step binding */
+  sequenceTimeout: number = 1500;
+  /** how the keys of the bindings are matched against the pressed ones */
+  matchMode: KeyMatchMode = KeyMatchMode.VirtualKey;
+}
+
+export interface KeybindingHint {
+  keys: string;
+  /** null if the key leads to another step of the sequence */
+  action: KeybindingAction | null;
+}
+
+export interface KeybindingsHints {
+  /** already pressed steps, empty when the sequence was completed or cancelled */
+  pressed: string[];
+  next: KeybindingHint[];
+}
+
+export enum LogLevel {
+  Off = 'Off',
+  Error = 'Error',
+  Warn = 'Warn',
+  Info = 'Info',
+  Debug = 'Debug',
+  Trace = 'Trace',
+}
+
+export class LoggingSettings {
+  level: LogLevel = LogLevel.Info;
+  modules: Record<string, LogLevel> = {};
+  winEvents: boolean = false;
+  maxFileSize: number = 5 * 1024 * 1024;
+  maxFiles: number = 5;
+}
+
+export type TriggerEvent =
+  | { focusChanged: { exe: string } }
+  | { workspaceSwitched: { index: number | null } }
+  | { batteryBelow: { percentage: number } }
+  | { processStarted: { exe: string } }
+  | { processStopped: { exe: string } };
+
+export type TriggerAction =
+  | { run: { program: string; args: string[] } }
+  | { cli: string[] }
+  | { webhook: { url: string } }
+  | { action: { id: string; params: Record<string, unknown> } };
+
+export interface Trigger {
+  enabled: boolean;
+  on: TriggerEvent;
+  action: TriggerAction;
+}
+
+export class TriggersSettings {
+  enabled: boolean = false;
+  /** min time in milliseconds between two executions of the same trigger */
+  cooldown: number = 5000;
+  /** max executions per minute of all the triggers together */
+  maxPerMinute: number = 30;
+  triggers: Trigger[] = [];
+}
+
 export enum UpdateChannel {
   Release = 'Release',
   Beta = 'Beta',
@@ -63,6 +265,238 @@ export enum UpdateChannel {
 
 export class UpdaterSettings {
   channel: UpdateChannel = UpdateChannel.Nightly;
+  /** download the updates on background, they will be installed on next restart */
+  autoDownload: boolean = true;
+}
+
+export enum NativeTaskbarMode {
+  Auto = 'Auto',
+  Visible = 'Visible',
+  Hidden = 'Hidden',
+}
+
+export class NativeTaskbarSettings {
+  mode: NativeTaskbarMode = NativeTaskbarMode.Auto;
+  /** open the seelen launcher instead of the start menu when the Windows key is pressed alone */
+  suppressStartMenu: boolean = false;
+}
+
+export class WindowSwitcherSettings {
+  /** replace the native Alt + Tab with the seelen window switcher */
+  enabled: boolean = false;
+  /** capture a thumbnail of each window when the switcher is opened */
+  thumbnails: boolean = true;
+}
+
+export class NightLightSettings {
+  /** turn the night light on and off automatically between `start` and `end` */
+  scheduled: boolean = false;
+  /** local time with format `HH:MM` */
+  start: string = '20:00';
+  /** local time with format `HH:MM` */
+  end: string = '07:00';
+  /** color temperature in kelvin while active, 6500 is neutral */
+  temperature: number = 3400;
+  /** duration in milliseconds of the transition between temperatures */
+  transitionDuration: number = 3000;
+}
+
+export class BrightnessSettings {
+  /** changing the brightness of a monitor changes all the others by the same proportion */
+  linked: boolean = false;
+}
+
+export class HardwareKeysSettings {
+  /**
+   * handle the volume and media keys instead of Windows, emitting OSD events so Seelen
+   * can show its own popups instead of the native flyouts
+   */
+  enabled: boolean = false;
+  /** percent of volume changed by each press of the volume keys */
+  volumeStep: number = 2;
+  /** percent of brightness changed by each brightness up/down command */
+  brightnessStep: number = 10;
+}
+
+export class CaptureSettings {
+  /** save the captures as png files on `folder` */
+  saveToFolder: boolean = true;
+  /** folder where the captures are saved, if null `Pictures/Screenshots` is used */
+  folder: string | null = null;
+  /** copy the captured image to the clipboard */
+  copyToClipboard: boolean = true;
+}
+
+export class AppUsageSettings {
+  /** track the time that each app is on foreground */
+  enabled: boolean = true;
+  /** days to keep on the usage history */
+  retentionDays: number = 90;
+  /** show a notification after using the same app for a long time */
+  nudge: boolean = false;
+  /** minutes of continuous use of the same app before the notification is shown */
+  nudgeAfter: number = 120;
+}
+
+export class BreakReminderSettings {
+  enabled: boolean = false;
+  /** minutes of activity between breaks, time away from the computer is not counted */
+  interval: number = 50;
+  /** minutes of each break, being away for this time counts as a break */
+  duration: number = 5;
+  /** minutes to postpone a break when snoozed */
+  snooze: number = 5;
+  /** dim the monitors while on break */
+  dim: boolean = false;
+  /** brightness of the monitors while dimmed, from 0 to 100 */
+  dimLevel: number = 20;
+}
+
+export class AccessibilitySettings {
+  /** announce the workspace to screen readers when switching workspaces */
+  announceWorkspaces: boolean = false;
+  /** announce the focused window to screen readers when the focus is moved by a wm command */
+  announceFocus: boolean = false;
+}
+
+export class DiagnosticsSettings {
+  /**
+   * save a minidump and the recent logs when the app crashes, nothing leaves the machine
+   * unless the diagnostics bundle is exported
+   */
+  crashReports: boolean = false;
+  /** amount of crash reports to keep, the oldest are removed */
+  maxReports: number = 10;
+}
+
+/** Adaptations applied while the battery saver of Windows is on and the device is on battery */
+export class PowerSavingSettings {
+  enabled: boolean = true;
+  /** max times per second that the mouse is polled and high frequency events are sent */
+  maxEmitRate: number = 10;
+  /** pause wallpaper videos and slideshows */
+  pauseWallpaper: boolean = true;
+  /** poll brightness, wifi networks and metrics less often */
+  reduceSampling: boolean = true;
+  /** disable the animations of the focus border and the webviews */
+  disableAnimations: boolean = true;
+}
+
+/** Rules used to decide which windows are shown on the dock and tiled by the window manager */
+export class WindowFiltersSettings {
+  /** ignore tool windows and windows that can't be activated, unless they are app windows */
+  byStyles: boolean = true;
+  /** ignore windows with a parent or owner, like dialogs and popups */
+  byOwner: boolean = true;
+  /** window classes that are always ignored */
+  classBlacklist: string[] = ['Windows.Internal.Shell.TabProxyWindow'];
+  /** windows narrower than this are ignored, in logical pixels, 0 to disable */
+  minWidth: number = 0;
+  /** windows shorter than this are ignored, in logical pixels, 0 to disable */
+  minHeight: number = 0;
+}
+
+/** Local HTTP API for companions (Stream Deck, phones, etc.), mirrors the IPC methods */
+export class RemoteControlSettings {
+  enabled: boolean = false;
+  /** port listened on 127.0.0.1 */
+  port: number = 47331;
+  /**
+   * required as `Authorization: Bearer <token>` on every request, the server is not
+   * started while empty
+   */
+  token: string = '';
+}
+
+/** Dark or light mode forced on the titlebars of the app windows */
+export enum TitlebarMode {
+  /** each app decides */
+  Untouched = 'Untouched',
+  /** follow the dark/light mode of the system colors */
+  System = 'System',
+  Dark = 'Dark',
+  Light = 'Light',
+}
+
+export class TitlebarSettings {
+  mode: TitlebarMode = TitlebarMode.Untouched;
+  /** paint the titlebars with the accent color, only supported on Windows 11 */
+  accentCaption: boolean = false;
+}
+
+/** Opacity changed by the user with the `window opacity` commands */
+export class WindowOpacitySettings {
+  /** apply the last opacity set on a window to all the windows of the same app, also after restart */
+  rememberPerApp: boolean = false;
+}
+
+export class StorageSettings {
+  /** notify when a volume is low on space or a disk is overheating or failing */
+  alerts: boolean = true;
+  /** percent of free space of a volume below which it is considered low */
+  lowSpaceThreshold: number = 10;
+  /** temperature in celsius from which a disk is considered overheating */
+  temperatureThreshold: number = 60;
+}
+
+/** Limits the memory used by Seelen while its surfaces can't be seen */
+export class ResourceGovernorSettings {
+  enabled: boolean = true;
+  /** seconds that the toolbar or dock must be hidden before unloading its content */
+  suspendAfter: number = 300;
+  /** unload the content of the toolbars, docks and wallpaper while the displays are off */
+  suspendOnDisplayOff: boolean = true;
+  /** percent of used system memory from which the caches are trimmed */
+  memoryPressureThreshold: number = 85;
+}
+
+/** Corner of the monitor where the picture-in-picture windows are placed */
+export enum PipCorner {
+  TopLeft = 'TopLeft',
+  TopRight = 'TopRight',
+  BottomLeft = 'BottomLeft',
+  BottomRight = 'BottomRight',
+}
+
+export class PictureInPictureSettings {
+  /** place, pin and keep on top the windows matching the picture-in-picture app rules */
+  enabled: boolean = true;
+  /** corner where new picture-in-picture windows are placed */
+  corner: PipCorner = PipCorner.BottomRight;
+  /** distance in pixels to the borders of the work area */
+  margin: number = 16;
+  /** preset widths as percent of the width of the monitor, the aspect ratio is kept */
+  sizes: number[] = [20, 30, 40];
+}
+
+export class ClockConfig {
+  /** unique id, used by the toolbar items to pick this clock */
+  id: string = '';
+  label: string | null = null;
+  /** IANA time zone id like `America/New_York`, the local time zone if null */
+  timezone: string | null = null;
+  /** strftime-like format, e.g. `%H:%M`, `%a %d %b` or `W%V` */
+  format: string = '%H:%M';
+}
+
+/** How often the clocks are updated, the ticks are aligned to the boundaries of the wall clock */
+export enum ClockTickMode {
+  /** each second or minute, depending on the formats in use */
+  Auto = 'Auto',
+  /** each second, even if the formats don't show the seconds */
+  Seconds = 'Seconds',
+  /** each 100ms, needed to show the tenths of second */
+  Precise = 'Precise',
+}
+
+/**
+ * Clocks computed by the background and emitted on each tick, so the clock widgets
+ * don't need their own timers
+ */
+export class ClockSettings {
+  clocks: ClockConfig[] = [];
+  /** the ticks slow down to each minute while the toolbars are hidden or the displays are off */
+  tickMode: ClockTickMode = ClockTickMode.Auto;
 }
 
 export class Settings extends Obtainable<Settings>(
@@ -77,13 +511,47 @@ export class Settings extends Obtainable<Settings>(
   monitors: MonitorConfiguration[] = [new MonitorConfiguration()];
   ahkEnabled: boolean = true;
   ahkVariables: AhkVarList = new AhkVarList();
+  keybindings: KeybindingsSettings = new KeybindingsSettings();
+  actionAliases: ActionAlias[] = [];
   selectedThemes: string[] = ['default'];
   iconPacks: string[] = ['system'];
   devTools: boolean = false;
+  maxEmitRate: number = 30;
   language: string = '';
+  textDirection: TextDirection = TextDirection.Auto;
   dateFormat: string = 'ddd D MMM, hh:mm A';
   virtualDesktopStrategy: VirtualDesktopStrategy = VirtualDesktopStrategy.Native;
+  workspaceMonitorRules: WorkspaceMonitorRule[] = [];
   updater: UpdaterSettings = new UpdaterSettings();
+  logging: LoggingSettings = new LoggingSettings();
+  triggers: TriggersSettings = new TriggersSettings();
+  nativeTaskbar: NativeTaskbarSettings = new NativeTaskbarSettings();
+  windowSwitcher: WindowSwitcherSettings = new WindowSwitcherSettings();
+  nightLight: NightLightSettings = new NightLightSettings();
+  brightness: BrightnessSettings = new BrightnessSettings();
+  capture: CaptureSettings = new CaptureSettings();
+  appUsage: AppUsageSettings = new AppUsageSettings();
+  breakReminder: BreakReminderSettings = new BreakReminderSettings();
+  accessibility: AccessibilitySettings = new AccessibilitySettings();
+  diagnostics: DiagnosticsSettings = new DiagnosticsSettings();
+  windowFilters: WindowFiltersSettings = new WindowFiltersSettings();
+  powerSaving: PowerSavingSettings = new PowerSavingSettings();
+  remoteControl: RemoteControlSettings = new RemoteControlSettings();
+  titlebar: TitlebarSettings = new TitlebarSettings();
+  windowOpacity: WindowOpacitySettings = new WindowOpacitySettings();
+  storage: StorageSettings = new StorageSettings();
+  resourceGovernor: ResourceGovernorSettings = new ResourceGovernorSettings();
+  pictureInPicture: PictureInPictureSettings = new PictureInPictureSettings();
+  clock: ClockSettings = new ClockSettings();
+  hardwareKeys: HardwareKeysSettings = new HardwareKeysSettings();
+
+  /**
+   * Changes a single setting and persists it.
+   * @param pointer JSON pointer to the setting, e.g. `/fancyToolbar/enabled`
+   */
+  static async patch(pointer: string, value: unknown): Promise<void> {
+    await invoke(SeelenCommand.PatchSettings, { pointer, value });
+  }
 }
 
 export class FancyToolbarSettings {
@@ -104,12 +572,17 @@ export class SeelenWegSettings {
   margin: number = 8;
   padding: number = 8;
   spaceBetweenItems: number = 8;
+  pinRemovableDrives: boolean = false;
+  orphanedItems: WegOrphanedItems = WegOrphanedItems.Keep;
 }
 
 export class Border {
   enabled: boolean = true;
   width: number = 3.0;
   offset: number = 0.0;
+  color: string | null = null;
+  radius: number = 8.0;
+  animationDuration: number = 150;
 }
 
 export class FloatingWindowSettings {
@@ -117,6 +590,28 @@ export class FloatingWindowSettings {
   height: number = 500.0;
 }
 
+export enum ZonesModifier {
+  Shift = 'Shift',
+  Ctrl = 'Ctrl',
+  Alt = 'Alt',
+}
+
+export class ZonesSettings {
+  enabled: boolean = true;
+  modifier: ZonesModifier = ZonesModifier.Shift;
+}
+
+export class SnapSettings {
+  interop: boolean = false;
+  replaceFlyout: boolean = false;
+}
+
+/** Corners applied by the window manager, the rules of the apps have priority */
+export class WmCornersSettings {
+  tiled: WindowCorners = WindowCorners.Default;
+  floating: WindowCorners = WindowCorners.Default;
+}
+
 export class WindowManagerSettings {
   enabled: boolean = false;
   autoStackingByCategory: boolean = true;
@@ -126,7 +621,11 @@ export class WindowManagerSettings {
   workspacePadding: number = 10.0;
   workspaceMargin: Rect = new Rect();
   floating: FloatingWindowSettings = new FloatingWindowSettings();
+  zones: ZonesSettings = new ZonesSettings();
+  snap: SnapSettings = new SnapSettings();
+  corners: WmCornersSettings = new WmCornersSettings();
   defaultLayout: string = 'default.yml';
+  mirrorDirectionsOnRtl: boolean = false;
 }
 
 export class AhkVar {
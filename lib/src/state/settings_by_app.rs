@@ -16,6 +16,133 @@ pub enum AppExtraFlag {
     Pinned,
     /// Hide this app on the dock/taskbar.
     Hidden,
+    /// Keep the windows of this app above the other windows.
+    AlwaysOnTop,
+    /// Don't restore the last position of the floating windows of this app.
+    NoPositionMemory,
+    /// Ignore the minimum and maximum size of the windows of this app when tiling them in the wm.
+    IgnoreSizeHints,
+    /// Handle the windows of this app as picture-in-picture videos, placed on a corner,
+    /// pinned to all the workspaces and kept on top.
+    PictureInPicture,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowBackdrop {
+    /// Mica material, used by the main window of the system apps.
+    Mica,
+    /// Mica material tinted with the wallpaper color, used by tabbed windows.
+    MicaAlt,
+    /// Acrylic material, used by transient windows like menus and flyouts.
+    Acrylic,
+}
+
+/// Corner preference of the windows, only supported on Windows 11
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowCorners {
+    /// decided by the system and the app
+    #[default]
+    Default,
+    Round,
+    RoundSmall,
+    Square,
+}
+
+#[serde_alias(SnakeCase)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AppWindowEffects {
+    /// opacity of the window from 0.1 to 1, `None` to leave it untouched
+    pub opacity: Option<f32>,
+    /// backdrop material drawn by the system behind the window, `None` to leave it untouched
+    pub backdrop: Option<WindowBackdrop>,
+}
+
+/// How the windows of the app are handled while fullscreen, exclusive or borderless
+#[serde_alias(SnakeCase)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FullscreenPolicy {
+    /// remove the window from the layout of the wm while it is fullscreen
+    pub never_tile: bool,
+    /// hide the dock, the wm overlay and the focus border while the window is fullscreen
+    pub hide_overlays: bool,
+    /// don't hide the toolbar while the window is fullscreen
+    pub keep_toolbar: bool,
+}
+
+impl Default for FullscreenPolicy {
+    fn default() -> Self {
+        Self {
+            never_tile: false,
+            hide_overlays: true,
+            keep_toolbar: false,
+        }
+    }
+}
+
+/// Audio rules applied to the sessions of the app
+#[serde_alias(SnakeCase)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AppAudioRules {
+    /// mute the app while none of its windows is focused
+    pub mute_unfocused: bool,
+    /// maximum volume of the app from 0 to 1, `None` to leave it untouched
+    pub max_volume: Option<f32>,
+}
+
+#[serde_alias(SnakeCase)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AppIdleRules {
+    /// minutes without focus after which the windows of the app are minimized
+    pub minimize_after: Option<u32>,
+    /// minutes without focus after which the windows of the app are closed
+    pub close_after: Option<u32>,
+    /// local time (`HH:MM`) at which the windows of the app are closed every day
+    pub close_at: Option<String>,
+    /// seconds before closing the windows that a notification is shown
+    pub warn_before: u32,
+}
+
+impl Default for AppIdleRules {
+    fn default() -> Self {
+        Self {
+            minimize_after: None,
+            close_after: None,
+            close_at: None,
+            warn_before: 60,
+        }
+    }
+}
+
+#[serde_alias(SnakeCase)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TitleRule {
+    /// regex matched against the title of the window
+    pub pattern: String,
+    /// replacement for the matches, capture groups can be used as `$1` or `${name}`
+    #[serde(default)]
+    pub replace: String,
+    #[serde(skip)]
+    pub regex: Option<Regex>,
+}
+
+impl TitleRule {
+    pub fn cache_regex(&mut self) {
+        self.regex = Regex::new(&self.pattern).ok();
+    }
+
+    pub fn apply(&self, title: &str) -> String {
+        match self.regex.as_ref() {
+            Some(re) => re.replace_all(title, self.replace.as_str()).into_owned(),
+            None => title.to_string(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
@@ -141,7 +268,43 @@ pub struct AppConfig {
     /// extra specific options/settings for the app
     #[serde(default)]
     pub options: Vec<AppExtraFlag>,
+    /// visual effects applied to the windows of the app
+    pub effects: Option<AppWindowEffects>,
+    /// behavior while a window of the app is fullscreen, `None` to use the default policy
+    pub fullscreen: Option<FullscreenPolicy>,
+    /// corners of the windows of the app, `None` to use the defaults of the window manager
+    pub corners: Option<WindowCorners>,
+    /// mute and volume rules of the audio of the app
+    pub audio: Option<AppAudioRules>,
+    /// actions applied to the windows of the app while they are not used
+    pub idle: Option<AppIdleRules>,
+    /// rules to rewrite the title shown on the dock, toolbar and window switcher, applied in order
+    #[serde(default)]
+    pub title_rules: Vec<TitleRule>,
     /// is this config bundled with seelen ui.
     #[serde(default)]
     pub is_bundled: bool,
 }
+
+impl AppConfig {
+    pub fn cache_regex(&mut self) {
+        self.identifier.cache_regex();
+        self.title_rules.iter_mut().for_each(TitleRule::cache_regex);
+    }
+
+    /// Returns the title to be shown for a window of this app, the original title
+    /// is kept if the rules would leave it empty.
+    pub fn display_title(&self, title: &str) -> String {
+        if self.title_rules.is_empty() {
+            return title.to_string();
+        }
+        let result = self
+            .title_rules
+            .iter()
+            .fold(title.to_string(), |title, rule| rule.apply(&title));
+        match result.trim() {
+            "" => title.to_string(),
+            trimmed => trimmed.to_string(),
+        }
+    }
+}
@@ -4,6 +4,67 @@ export enum AppExtraFlag {
   Unmanage = 'unmanage',
   Pinned = 'pinned',
   Hidden = 'hidden',
+  AlwaysOnTop = 'always_on_top',
+  NoPositionMemory = 'no_position_memory',
+  IgnoreSizeHints = 'ignore_size_hints',
+  PictureInPicture = 'picture_in_picture',
+}
+
+export enum WindowBackdrop {
+  Mica = 'mica',
+  MicaAlt = 'mica_alt',
+  Acrylic = 'acrylic',
+}
+
+/** Corner preference of the windows, only supported on Windows 11 */
+export enum WindowCorners {
+  /** decided by the system and the app */
+  Default = 'default',
+  Round = 'round',
+  RoundSmall = 'round_small',
+  Square = 'square',
+}
+
+export interface AppWindowEffects {
+  opacity: number | null;
+  backdrop: WindowBackdrop | null;
+}
+
+export interface FullscreenPolicy {
+  neverTile: boolean;
+  hideOverlays: boolean;
+  keepToolbar: boolean;
+}
+
+export class FullscreenPolicy {
+  static default(): FullscreenPolicy {
+    return { neverTile: false, hideOverlays: true, keepToolbar: false };
+  }
+}
+
+/** Audio rules applied to the sessions of the app */
+export interface AppAudioRules {
+  /** mute the app while none of its windows is focused */
+  muteUnfocused: boolean;
+  /** maximum volume of the app from 0 to 1, `null` to leave it untouched */
+  maxVolume: number | null;
+}
+
+/** Actions applied to the windows of the app while they are not used */
+export interface AppIdleRules {
+  /** minutes without focus after which the windows are minimized */
+  minimizeAfter: number | null;
+  /** minutes without focus after which the windows are closed */
+  closeAfter: number | null;
+  /** local time (`HH:MM`) at which the windows are closed every day */
+  closeAt: string | null;
+  /** seconds before closing the windows that a notification is shown */
+  warnBefore: number;
+}
+
+export interface TitleRule {
+  pattern: string;
+  replace: string;
 }
 
 export enum AppIdentifierType {
@@ -50,6 +111,12 @@ export interface AppConfiguration {
   boundWorkspace: number | null;
   identifier: AppIdentifier;
   options: Array<AppExtraFlag>;
+  effects: AppWindowEffects | null;
+  fullscreen: FullscreenPolicy | null;
+  corners: WindowCorners | null;
+  audio: AppAudioRules | null;
+  idle: AppIdleRules | null;
+  titleRules: TitleRule[];
   isBundled: boolean;
 }
 
@@ -63,6 +130,12 @@ export class AppConfiguration {
       identifier: AppIdentifier.placeholder(),
       isBundled: false,
       options: [],
+      effects: null,
+      fullscreen: null,
+      corners: null,
+      audio: null,
+      idle: null,
+      titleRules: [],
     };
   }
 }
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::rect::Rect;
 
-use super::SeelenWallWallpaper;
+use super::{NativeTaskbarMode, SeelenWallWallpaper};
 
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
@@ -67,6 +67,30 @@ impl Default for SeelenWallSettingsByMonitor {
     }
 }
 
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NativeTaskbarSettingsByMonitor {
+    /// if null the global mode is used
+    pub mode: Option<NativeTaskbarMode>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NightLightSettingsByMonitor {
+    pub enabled: bool,
+    /// if null the global temperature is used
+    pub temperature: Option<u32>,
+}
+
+impl Default for NightLightSettingsByMonitor {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            temperature: None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub enum WorkspaceIdentifierType {
     #[serde(alias = "name")]
@@ -90,6 +114,14 @@ pub struct WorkspaceConfiguration {
     pub backgrounds: Option<Vec<SeelenWallWallpaper>>,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceMonitorRule {
+    pub workspace: WorkspaceIdentifier,
+    /// id of the monitor, as returned by `seelen-ui.exe get monitors`
+    pub monitor: String,
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct MonitorConfiguration {
@@ -97,6 +129,8 @@ pub struct MonitorConfiguration {
     pub weg: SeelenWegSettingsByMonitor,
     pub wm: WindowManagerSettingsByMonitor,
     pub wall: SeelenWallSettingsByMonitor,
+    pub taskbar: NativeTaskbarSettingsByMonitor,
+    pub night_light: NightLightSettingsByMonitor,
     /// list of settings by workspace on this monitor
     pub workspaces_v2: Vec<WorkspaceConfiguration>,
 }
@@ -1,5 +1,5 @@
 import { Rect } from '../utils';
-import { SeelenWallWallpaper } from './settings';
+import { NativeTaskbarMode, SeelenWallWallpaper } from './settings';
 
 export class FancyToolbarSettingsByMonitor {
   enabled: boolean = true;
@@ -22,6 +22,17 @@ export class SeelenWallSettingsByMonitor {
   backgrounds: SeelenWallWallpaper[] | null = null;
 }
 
+export class NativeTaskbarSettingsByMonitor {
+  /** if null the global mode is used */
+  mode: NativeTaskbarMode | null = null;
+}
+
+export class NightLightSettingsByMonitor {
+  enabled: boolean = true;
+  /** if null the global temperature is used */
+  temperature: number | null = null;
+}
+
 export enum WorkspaceIdentifierType {
   Name = 'name',
   Index = 'index',
@@ -47,11 +58,24 @@ export class WorkspaceConfiguration {
   }
 }
 
+export class WorkspaceMonitorRule {
+  workspace: WorkspaceIdentifier;
+  /** id of the monitor, as returned by `seelen-ui.exe get monitors` */
+  monitor: string;
+
+  constructor(workspace: WorkspaceIdentifier, monitor: string) {
+    this.workspace = workspace;
+    this.monitor = monitor;
+  }
+}
+
 export class MonitorConfiguration {
   tb: FancyToolbarSettingsByMonitor = new FancyToolbarSettingsByMonitor();
   wall: SeelenWallSettingsByMonitor = new SeelenWallSettingsByMonitor();
   weg: SeelenWegSettingsByMonitor = new SeelenWegSettingsByMonitor();
   wm: WindowManagerSettingsByMonitor = new WindowManagerSettingsByMonitor();
+  taskbar: NativeTaskbarSettingsByMonitor = new NativeTaskbarSettingsByMonitor();
+  nightLight: NightLightSettingsByMonitor = new NightLightSettingsByMonitor();
   /** list of settings by workspace on this monitor */
   workspacesV2: WorkspaceConfiguration[] = [];
 }
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+use super::Settings;
+
+/// Emitted when settings.json was modified by someone else while Seelen was writing it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsConflict {
+    /// settings from which both writers started
+    pub base: Settings,
+    /// settings written by Seelen (settings window, tray, patches)
+    pub mine: Settings,
+    /// settings currently on the file
+    pub theirs: Settings,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SettingsConflictResolution {
+    KeepMine,
+    TakeTheirs,
+    /// apply the changes of both sides, on the same setting the changes made by Seelen win
+    Merge,
+}
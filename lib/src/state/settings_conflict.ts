@@ -0,0 +1,18 @@
+import { Settings } from './settings';
+
+/** Emitted when settings.json was modified by someone else while Seelen was writing it */
+export interface SettingsConflict {
+  /** settings from which both writers started */
+  base: Settings;
+  /** settings written by Seelen (settings window, tray, patches) */
+  mine: Settings;
+  /** settings currently on the file */
+  theirs: Settings;
+}
+
+export enum SettingsConflictResolution {
+  KeepMine = 'KeepMine',
+  TakeTheirs = 'TakeTheirs',
+  /** apply the changes of both sides, on the same setting the changes made by Seelen win */
+  Merge = 'Merge',
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+use super::{ConfigSchema, Settings};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsSearchResult {
+    /// path of the setting on the settings file, example: `windowManager.workspaceGap`
+    pub key: String,
+    /// page of the settings window where the setting is shown
+    pub route: String,
+    /// label shown on the settings window, on the current language
+    pub label: Option<String>,
+    pub description: Option<String>,
+}
+
+impl Settings {
+    /// JSON schema of the settings, including the doc comments of each field as description
+    pub fn schema() -> serde_json::Value {
+        ConfigSchema::Settings.schema()
+    }
+}
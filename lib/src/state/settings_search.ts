@@ -0,0 +1,9 @@
+export interface SettingsSearchResult {
+  /** path of the setting on the settings file, example: `windowManager.workspaceGap` */
+  key: string;
+  /** page of the settings window where the setting is shown */
+  route: string;
+  /** label shown on the settings window, on the current language */
+  label: string | null;
+  description: string | null;
+}
@@ -1,3 +1,5 @@
+import { Obtainable, SeelenCommand, SeelenEvent } from '../handlers';
+
 export interface ThemeCssByApp {
   /** Css Styles for the dock/taskbar */
   weg: string;
@@ -30,3 +32,9 @@ export interface Theme {
   /** Css Styles of the theme */
   styles: ThemeCssByApp;
 }
+
+/** Css injected by theme developers over the loaded themes, it is not saved on disk */
+export const LiveThemeCss = Obtainable<ThemeCssByApp>(
+  SeelenCommand.LiveThemeGetCss,
+  SeelenEvent.LiveThemeCssChanged,
+);
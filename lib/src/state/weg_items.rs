@@ -3,6 +3,8 @@ use std::{collections::HashSet, path::PathBuf};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use super::WegOrphanedItems;
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PinnedWegItemData {
     /// Direct path to file, forder or program.
@@ -28,6 +30,22 @@ pub struct PinnedWegItemData {
     /// true if self.path is a folder
     #[serde(default)]
     pub is_dir: bool,
+    /// true if self.path doesn't exist, e.g. the program is on an unplugged drive.
+    /// This is updated on sanitize and when drives are connected or removed.
+    #[serde(default)]
+    pub orphaned: bool,
+    /// Arguments passed to the program, on `.lnk` files these replace the arguments of the link.
+    #[serde(default)]
+    pub arguments: Option<String>,
+    /// Folder where the program is started, by default the one chosen by the program or link.
+    #[serde(default)]
+    pub working_dir: Option<PathBuf>,
+    /// Start the program as administrator, it will ask for permissions (UAC).
+    #[serde(default)]
+    pub run_as_admin: bool,
+    /// Clicking the item starts a new instance even if the app already has open windows.
+    #[serde(default)]
+    pub new_instance: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -36,11 +54,57 @@ pub enum WegItem {
     #[serde(alias = "PinnedApp")]
     Pinned(PinnedWegItemData),
     Temporal(PinnedWegItemData),
+    /// Line dividing the groups of items
     Separator {
+        #[serde(default)]
         id: String,
     },
+    /// Empty space taking a share of the free space of the dock, used to push the next items
+    Spacer {
+        #[serde(default)]
+        id: String,
+        /// share of the free space taken, like the css `flex-grow`, 0 to only use `min_size`
+        #[serde(default = "WegItem::default_grow")]
+        grow: f32,
+        /// size in pixels kept even if there is no free space
+        #[serde(default)]
+        min_size: u32,
+    },
+    /// Static text, used as title of a group of items
+    Label {
+        #[serde(default)]
+        id: String,
+        text: String,
+    },
     Media,
     StartMenu,
+    RecycleBin,
+}
+
+impl WegItem {
+    fn default_grow() -> f32 {
+        1.0
+    }
+
+    /// Identifier of the item, unique across all the weg items
+    pub fn id(&self) -> &str {
+        match self {
+            WegItem::Pinned(data) | WegItem::Temporal(data) => &data.execution_command,
+            WegItem::Separator { id } | WegItem::Spacer { id, .. } | WegItem::Label { id, .. } => {
+                id
+            }
+            WegItem::Media => "Media",
+            WegItem::StartMenu => "StartMenu",
+            WegItem::RecycleBin => "RecycleBin",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WegItemsSide {
+    Left,
+    Center,
+    Right,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -59,6 +123,11 @@ impl Default for WegItems {
                 path: "C:\\Windows\\explorer.exe".into(),
                 execution_command: "C:\\Windows\\explorer.exe".into(),
                 is_dir: false,
+                orphaned: false,
+                arguments: None,
+                working_dir: None,
+                run_as_admin: false,
+                new_instance: false,
             })],
             right: vec![WegItem::Media],
         }
@@ -66,61 +135,106 @@ impl Default for WegItems {
 }
 
 impl WegItems {
-    fn sanitize_items(dict: &mut HashSet<String>, items: Vec<WegItem>) -> Vec<WegItem> {
+    fn sanitize_items(
+        dict: &mut HashSet<String>,
+        items: Vec<WegItem>,
+        orphaned: WegOrphanedItems,
+    ) -> Vec<WegItem> {
         let mut result = Vec::new();
         for mut item in items {
             match &mut item {
-                WegItem::Pinned(data) => {
-                    if !data.path.exists() {
+                WegItem::Pinned(data) | WegItem::Temporal(data) => {
+                    data.orphaned = !data.path.exists();
+                    if data.orphaned && orphaned == WegOrphanedItems::Remove {
                         continue;
                     }
                     if data.execution_command.is_empty() {
                         data.execution_command = data.path.to_string_lossy().to_string();
                     }
-                    if !dict.contains(&data.execution_command) {
-                        dict.insert(data.execution_command.clone());
-                        result.push(item);
-                    }
                 }
-                WegItem::Temporal(data) => {
-                    if !data.path.exists() {
-                        continue;
-                    }
-                    if data.execution_command.is_empty() {
-                        data.execution_command = data.path.to_string_lossy().to_string();
-                    }
-                    if !dict.contains(&data.execution_command) {
-                        dict.insert(data.execution_command.clone());
-                        result.push(item);
+                WegItem::Spacer { grow, .. } => {
+                    if !grow.is_finite() || *grow < 0.0 {
+                        *grow = WegItem::default_grow();
                     }
                 }
-                WegItem::Separator { id } => {
-                    if !dict.contains(id) {
-                        dict.insert(id.clone());
-                        result.push(item);
-                    }
-                }
-                WegItem::StartMenu => {
-                    if !dict.contains("StartMenu") {
-                        dict.insert("StartMenu".to_owned());
-                        result.push(item);
+                WegItem::Label { text, .. } => {
+                    *text = text.trim().to_owned();
+                    if text.is_empty() {
+                        continue;
                     }
                 }
-                WegItem::Media => {
-                    if !dict.contains("Media") {
-                        dict.insert("Media".to_owned());
-                        result.push(item);
-                    }
+                _ => {}
+            }
+            // the layout items are written by hand, so the id is optional on the config
+            if let WegItem::Separator { id }
+            | WegItem::Spacer { id, .. }
+            | WegItem::Label { id, .. } = &mut item
+            {
+                if id.is_empty() {
+                    *id = uuid::Uuid::new_v4().to_string();
                 }
             }
+            if dict.insert(item.id().to_owned()) {
+                result.push(item);
+            }
         }
         result
     }
 
-    pub fn sanitize(&mut self) {
+    pub fn sanitize(&mut self, orphaned: WegOrphanedItems) {
         let mut dict = HashSet::new();
-        self.left = Self::sanitize_items(&mut dict, std::mem::take(&mut self.left));
-        self.center = Self::sanitize_items(&mut dict, std::mem::take(&mut self.center));
-        self.right = Self::sanitize_items(&mut dict, std::mem::take(&mut self.right));
+        self.left = Self::sanitize_items(&mut dict, std::mem::take(&mut self.left), orphaned);
+        self.center = Self::sanitize_items(&mut dict, std::mem::take(&mut self.center), orphaned);
+        self.right = Self::sanitize_items(&mut dict, std::mem::take(&mut self.right), orphaned);
+    }
+
+    fn side_mut(&mut self, side: WegItemsSide) -> &mut Vec<WegItem> {
+        match side {
+            WegItemsSide::Left => &mut self.left,
+            WegItemsSide::Center => &mut self.center,
+            WegItemsSide::Right => &mut self.right,
+        }
+    }
+
+    /// Moves the item to `index` of `side`, returns false if the item doesn't exist
+    pub fn move_item(&mut self, id: &str, side: WegItemsSide, index: usize) -> bool {
+        let item = [
+            WegItemsSide::Left,
+            WegItemsSide::Center,
+            WegItemsSide::Right,
+        ]
+        .into_iter()
+        .find_map(|side| {
+            let items = self.side_mut(side);
+            let position = items.iter().position(|item| item.id() == id)?;
+            Some(items.remove(position))
+        });
+
+        match item {
+            Some(item) => {
+                let items = self.side_mut(side);
+                items.insert(index.min(items.len()), item);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Rechecks if the targets of the items exist, returns true if any item changed
+    pub fn refresh_orphaned(&mut self) -> bool {
+        let mut changed = false;
+        for item in self
+            .left
+            .iter_mut()
+            .chain(self.center.iter_mut())
+            .chain(self.right.iter_mut())
+        {
+            if let WegItem::Pinned(data) | WegItem::Temporal(data) = item {
+                let orphaned = !data.path.exists();
+                changed |= data.orphaned != orphaned;
+                data.orphaned = orphaned;
+            }
+        }
+        changed
     }
 }
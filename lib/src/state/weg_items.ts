@@ -2,8 +2,11 @@ export enum SwItemType {
   Pinned = 'Pinned',
   TemporalApp = 'TemporalPin',
   Separator = 'Separator',
+  Spacer = 'Spacer',
+  Label = 'Label',
   Media = 'Media',
   Start = 'StartMenu',
+  RecycleBin = 'RecycleBin',
 }
 
 export interface PinnedWegItem {
@@ -11,6 +14,7 @@ export interface PinnedWegItem {
   path: string;
   execution_command: string;
   is_dir: boolean;
+  orphaned: boolean;
 }
 
 export interface TemporalPinnedWegItem {
@@ -18,6 +22,7 @@ export interface TemporalPinnedWegItem {
   path: string;
   execution_command: string;
   is_dir: boolean;
+  orphaned: boolean;
 }
 
 export interface SeparatorWegItem {
@@ -25,6 +30,21 @@ export interface SeparatorWegItem {
   id: string;
 }
 
+export interface SpacerWegItem {
+  type: SwItemType.Spacer;
+  id: string;
+  /** share of the free space taken, like the css `flex-grow` */
+  grow: number;
+  /** size in pixels kept even if there is no free space */
+  min_size: number;
+}
+
+export interface LabelWegItem {
+  type: SwItemType.Label;
+  id: string;
+  text: string;
+}
+
 export interface MediaWegItem {
   type: SwItemType.Media;
 }
@@ -33,12 +53,25 @@ export interface StartWegItem {
   type: SwItemType.Start;
 }
 
+export interface RecycleBinWegItem {
+  type: SwItemType.RecycleBin;
+}
+
 export type WegItem =
   | PinnedWegItem
   | TemporalPinnedWegItem
   | SeparatorWegItem
+  | SpacerWegItem
+  | LabelWegItem
   | MediaWegItem
-  | StartWegItem;
+  | StartWegItem
+  | RecycleBinWegItem;
+
+export enum WegItemsSide {
+  Left = 'Left',
+  Center = 'Center',
+  Right = 'Right',
+}
 
 export interface WegItems {
   left: WegItem[];
@@ -0,0 +1,106 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::handlers::SeelenEvent;
+
+use super::ResourceMetadata;
+
+/// Data sources a widget can access, should be declared on the manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum WidgetPermission {
+    /// Read the workspaces and switch between them.
+    Workspaces,
+    /// Read the focused window.
+    FocusedWindow,
+    /// Read the open windows, send them to other workspaces and move them.
+    Windows,
+    /// Read the user settings and themes.
+    Settings,
+    Media,
+    Power,
+    Network,
+    Notifications,
+    /// Read the space of the volumes and the health of the disks.
+    Storage,
+}
+
+impl WidgetPermission {
+    /// IPC methods allowed by this permission
+    pub fn methods(&self) -> &'static [&'static str] {
+        match self {
+            Self::Workspaces => &["getWorkspaces", "switchWorkspace"],
+            Self::FocusedWindow => &["getFocusedWindow"],
+            Self::Windows => &["getWindows", "sendToWorkspace", "moveWindow"],
+            Self::Settings => &["getSettings", "getThemes"],
+            Self::Storage => &["getStorage"],
+            Self::Media | Self::Power | Self::Network | Self::Notifications => &[],
+        }
+    }
+
+    /// Events allowed by this permission
+    pub fn events(&self) -> &'static [&'static str] {
+        match self {
+            Self::Workspaces => &[
+                SeelenEvent::WorkspacesChanged,
+                SeelenEvent::ActiveWorkspaceChanged,
+            ],
+            Self::FocusedWindow => &[SeelenEvent::GlobalFocusChanged],
+            Self::Windows => &[],
+            Self::Settings => &[
+                SeelenEvent::StateSettingsChanged,
+                SeelenEvent::StateThemesChanged,
+            ],
+            Self::Media => &[
+                SeelenEvent::MediaSessions,
+                SeelenEvent::MediaInputs,
+                SeelenEvent::MediaOutputs,
+            ],
+            Self::Power => &[SeelenEvent::PowerStatus, SeelenEvent::BatteriesStatus],
+            Self::Network => &[
+                SeelenEvent::NetworkAdapters,
+                SeelenEvent::NetworkDefaultLocalIp,
+                SeelenEvent::NetworkInternetConnection,
+            ],
+            Self::Notifications => &[SeelenEvent::Notifications],
+            Self::Storage => &[SeelenEvent::StorageChanged],
+        }
+    }
+}
+
+/// Third party toolbar widget, loaded from a package folder (`widgets/<id>/widget.yml`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Widget {
+    /// Id of the widget, is overridden by the program on load with the package folder name.
+    pub id: String,
+    /// Metadata about the widget
+    pub info: ResourceMetadata,
+    /// Version of the widget package
+    pub version: String,
+    /// Html file relative to the package folder, should contain all the scripts and styles inlined.
+    #[serde(default = "Widget::default_entry")]
+    pub entry: String,
+    /// Data sources requested by the widget, any other access will be rejected.
+    pub permissions: Vec<WidgetPermission>,
+    /// Content of the entry file, is overridden by the program on load.
+    pub html: String,
+}
+
+impl Widget {
+    fn default_entry() -> String {
+        "index.html".to_string()
+    }
+
+    pub fn allows_method(&self, method: &str) -> bool {
+        self.permissions
+            .iter()
+            .any(|permission| permission.methods().contains(&method))
+    }
+
+    pub fn allows_event(&self, event: &str) -> bool {
+        self.permissions
+            .iter()
+            .any(|permission| permission.events().contains(&event))
+    }
+}
@@ -0,0 +1,39 @@
+import { Obtainable, SeelenCommand, SeelenEvent } from '../handlers';
+import { ResourceMetadata } from '.';
+
+export enum WidgetPermission {
+  /** Read the workspaces and switch between them */
+  Workspaces = 'workspaces',
+  /** Read the focused window */
+  FocusedWindow = 'focusedWindow',
+  /** Read the open windows, send them to other workspaces and move them */
+  Windows = 'windows',
+  /** Read the user settings and themes */
+  Settings = 'settings',
+  Media = 'media',
+  Power = 'power',
+  Network = 'network',
+  Notifications = 'notifications',
+  /** Read the space of the volumes and the health of the disks */
+  Storage = 'storage',
+}
+
+export class Widget {
+  /** Id of the widget, is overridden by the program on load with the package folder name */
+  id: string = '';
+  /** Metadata about the widget */
+  info: ResourceMetadata = new ResourceMetadata();
+  /** Version of the widget package */
+  version: string = '';
+  /** Html file relative to the package folder, should contain all the scripts and styles inlined */
+  entry: string = 'index.html';
+  /** Data sources requested by the widget, any other access will be rejected */
+  permissions: WidgetPermission[] = [];
+  /** Content of the entry file, is overridden by the program on load */
+  html: string = '';
+}
+
+export const WidgetList = Obtainable<Widget[]>(
+  SeelenCommand.StateGetWidgets,
+  SeelenEvent.StateWidgetsChanged,
+);
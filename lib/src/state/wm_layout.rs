@@ -175,12 +175,39 @@ pub enum NoFallbackBehavior {
     Unmanaged,
 }
 
+/// Static area used by the zones mode, values are fractions (0 to 1) of the monitor work area
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct WmZone {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl WmZone {
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+}
+
+/// Zones shown while a window is dragged holding the zones modifier
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WmZonesOverlay {
+    pub zones: Vec<WmZone>,
+    /// index of the zone under the cursor
+    pub active: Option<usize>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct WindowManagerLayout {
     pub info: WManagerLayoutInfo,
     pub structure: WmNode,
     pub no_fallback_behavior: NoFallbackBehavior,
+    /// Zones where windows can be dropped while holding the zones modifier, these
+    /// coexist with the tiling structure.
+    pub zones: Vec<WmZone>,
 }
 
 impl Default for WindowManagerLayout {
@@ -196,6 +223,127 @@ impl Default for WindowManagerLayout {
                 handles: vec![],
             }),
             no_fallback_behavior: NoFallbackBehavior::Float,
+            zones: vec![],
+        }
+    }
+}
+
+/// Problem found on a layout file, used by the layout editor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WmLayoutError {
+    /// path to the invalid value, example: `structure.children[1].growFactor`
+    pub path: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WmLayoutValidation {
+    /// layout with the default values applied, None if the file can't be parsed
+    pub layout: Option<WindowManagerLayout>,
+    pub errors: Vec<WmLayoutError>,
+}
+
+/// Placement of a fake window, values are fractions (0 to 1) of the monitor work area
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WmPreviewWindow {
+    /// order in which the window was added, starting at 1
+    pub index: usize,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// Space given to a node while placing its windows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WmArea {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl WmNode {
+    /// Same placement as the layout rendered by the window manager, empty nodes are not shown
+    /// and the space left by the gaps is shared between the visible children by their grow factor.
+    pub fn place(&self, area: WmArea, gap: f32, windows: &mut Vec<WmPreviewWindow>) {
+        if self.is_empty() {
+            return;
+        }
+
+        let push = |handle: isize| {
+            windows.push(WmPreviewWindow {
+                index: handle as usize,
+                x: area.x,
+                y: area.y,
+                width: area.width,
+                height: area.height,
+            })
+        };
+
+        let (children, is_vertical) = match self {
+            WmNode::Leaf(n) => {
+                n.handle.into_iter().for_each(push);
+                return;
+            }
+            WmNode::Stack(n) => {
+                n.handles.iter().copied().for_each(push);
+                return;
+            }
+            WmNode::Fallback(n) => {
+                n.handles.iter().copied().for_each(push);
+                return;
+            }
+            WmNode::Vertical(n) => (&n.children, true),
+            WmNode::Horizontal(n) => (&n.children, false),
+        };
+
+        let visible: Vec<&WmNode> = children.iter().filter(|c| !c.is_empty()).collect();
+        let total: f32 = visible.iter().map(|c| c.grow_factor().get()).sum();
+        let gaps = gap * (visible.len() as f32 - 1.0);
+        let mut offset = 0.0;
+        for child in visible {
+            let share = child.grow_factor().get() / total;
+            let child_area = match is_vertical {
+                true => WmArea {
+                    y: area.y + offset,
+                    height: (area.height - gaps) * share,
+                    ..area
+                },
+                false => WmArea {
+                    x: area.x + offset,
+                    width: (area.width - gaps) * share,
+                    ..area
+                },
+            };
+            offset += gap
+                + match is_vertical {
+                    true => child_area.height,
+                    false => child_area.width,
+                };
+            child.place(child_area, gap, windows);
         }
     }
 }
+
+/// Size limits reported by a window, in physical pixels without the shadow. 0 means no limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WmSizeHints {
+    pub min_width: i32,
+    pub min_height: i32,
+    pub max_width: i32,
+    pub max_height: i32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WmLayoutPreview {
+    /// windows on the same node (stacks and fallbacks) share the same placement
+    pub windows: Vec<WmPreviewWindow>,
+    /// windows that don't fit on the layout, handled by `noFallbackBehavior`
+    pub unplaced: Vec<usize>,
+}
@@ -59,8 +59,59 @@ export interface WmFallbackNode extends WmNodeBase {
 
 export type WmNode = WmVerticalNode | WmHorizontalNode | WmLeafNode | WmStackNode | WmFallbackNode;
 
+export interface WmZone {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+export interface WmZonesOverlay {
+  zones: WmZone[];
+  active: number | null;
+}
+
 export interface WindowManagerLayout {
   info: WManagerLayoutInfo;
   structure: WmNode;
   noFallbackBehavior: NoFallbackBehavior;
+  zones: WmZone[];
+}
+
+/** Problem found on a layout file, used by the layout editor */
+export interface WmLayoutError {
+  /** path to the invalid value, example: `structure.children[1].growFactor` */
+  path: string;
+  message: string;
+}
+
+export interface WmLayoutValidation {
+  /** layout with the default values applied, null if the file can't be parsed */
+  layout: WindowManagerLayout | null;
+  errors: WmLayoutError[];
+}
+
+/** Placement of a fake window, values are fractions (0 to 1) of the monitor work area */
+export interface WmPreviewWindow {
+  /** order in which the window was added, starting at 1 */
+  index: number;
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+/** Size limits reported by a window, in physical pixels without the shadow. 0 means no limit. */
+export interface WmSizeHints {
+  minWidth: number;
+  minHeight: number;
+  maxWidth: number;
+  maxHeight: number;
+}
+
+export interface WmLayoutPreview {
+  /** windows on the same node (stacks and fallbacks) share the same placement */
+  windows: WmPreviewWindow[];
+  /** windows that don't fit on the layout, handled by `noFallbackBehavior` */
+  unplaced: number[];
 }
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppUsage {
+    /// app user model id or lowercased executable path
+    pub app: String,
+    pub name: String,
+    /// foreground time in seconds
+    pub seconds: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppUsageReport {
+    /// local date as `YYYY-MM-DD`
+    pub date: String,
+    /// foreground time of all the apps in seconds
+    pub total: u64,
+    /// most used apps first
+    pub apps: Vec<AppUsage>,
+}
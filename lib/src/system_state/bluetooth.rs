@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BluetoothDevice {
+    /// 48 bits address of the device, used as id by the commands
+    pub address: u64,
+    pub name: String,
+    pub connected: bool,
+    pub low_energy: bool,
+    /// from 0 to 100, only available if the device reports it
+    pub battery: Option<u8>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BluetoothState {
+    /// false if there is no bluetooth adapter
+    pub available: bool,
+    /// state of the radio, devices can't be connected while it is off
+    pub enabled: bool,
+    /// paired devices
+    pub devices: Vec<BluetoothDevice>,
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BreakPhase {
+    #[default]
+    Disabled,
+    /// counting the activity until the next break
+    Working,
+    /// same as working but the break was postponed
+    Snoozed,
+    OnBreak,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BreakReminderState {
+    pub phase: BreakPhase,
+    /// true while the user is away, the activity is not counted
+    pub paused: bool,
+    /// seconds until the next break or until the end of the current one
+    pub remaining: u64,
+    /// unix timestamp in milliseconds when the phase ends, `None` while paused or disabled
+    pub ends_at: Option<u64>,
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BrightnessSource {
+    /// internal panels of laptops and tablets
+    Wmi,
+    /// external monitors supporting DDC/CI
+    Ddc,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorBrightness {
+    /// same id used for the monitors on the rest of the app (e.g. `DISPLAY1`)
+    pub id: String,
+    pub source: BrightnessSource,
+    pub min: u32,
+    pub max: u32,
+    pub current: u32,
+}
@@ -0,0 +1,23 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CaptureKind {
+    /// all the monitors
+    Screen,
+    Window,
+    Region,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureResult {
+    pub kind: CaptureKind,
+    /// saved png file, `None` if saving to a folder is disabled
+    pub path: Option<PathBuf>,
+    /// true if the image was copied to the clipboard
+    pub copied: bool,
+    pub width: u32,
+    pub height: u32,
+}
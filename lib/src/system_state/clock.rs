@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// A configured clock at the current tick
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClockEntry {
+    pub id: String,
+    pub label: Option<String>,
+    /// IANA id of the time zone used
+    pub timezone: String,
+    /// current time with the format of the clock applied
+    pub text: String,
+    /// ISO 8601 week number
+    pub week: u32,
+}
+
+/// Emitted on each aligned tick (second or minute)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClockState {
+    /// unix timestamp in milliseconds of the tick
+    pub time: u64,
+    pub clocks: Vec<ClockEntry>,
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColorFormat {
+    /// `#1a2b3c`
+    #[default]
+    Hex,
+    /// `rgb(26, 43, 60)`
+    Rgb,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PickedColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    /// color as copied to the clipboard
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColorPickerLoupe {
+    /// cursor position in physical pixels
+    pub x: i32,
+    pub y: i32,
+    /// side of the square area around the cursor, odd so the cursor is the center pixel
+    pub size: u32,
+    /// `0xRRGGBB` colors of the area, row by row
+    pub pixels: Vec<u32>,
+}
@@ -0,0 +1,19 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::state::WallpaperSource;
+
+/// Cached image of a daily wallpaper source, with the metadata given by the provider
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyWallpaper {
+    pub source: WallpaperSource,
+    pub path: PathBuf,
+    pub title: Option<String>,
+    pub copyright: Option<String>,
+    /// page with more information about the image
+    pub link: Option<String>,
+    /// unix timestamp in seconds of the download
+    pub fetched_at: u64,
+}
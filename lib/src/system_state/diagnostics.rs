@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+    /// name of the report folder inside the diagnostics folder
+    pub id: String,
+    /// unix timestamp in milliseconds
+    pub timestamp: u64,
+    /// panic message or exception code
+    pub cause: String,
+    pub location: Option<String>,
+    /// version of Seelen UI that crashed
+    pub version: String,
+    pub has_minidump: bool,
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+use crate::rect::Rect;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplayOrientation {
+    Landscape,
+    Portrait,
+    LandscapeFlipped,
+    PortraitFlipped,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayMode {
+    /// in physical pixels, as the display is in landscape orientation
+    pub width: u32,
+    pub height: u32,
+    /// in hertz
+    pub refresh_rate: u32,
+    pub bits_per_pixel: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayDevice {
+    /// same id used for the monitors on the rest of the app (e.g. `DISPLAY1`)
+    pub id: String,
+    /// friendly name of the monitor connected to the device
+    pub label: String,
+    pub is_primary: bool,
+    /// position of the top left corner on the virtual screen
+    pub x: i32,
+    pub y: i32,
+    pub orientation: DisplayOrientation,
+    pub current_mode: DisplayMode,
+    /// modes supported by the display, sorted from the biggest to the smallest
+    pub modes: Vec<DisplayMode>,
+}
+
+/// Emitted when the scale (DPI) of a monitor changes, so the surfaces can be resized crisply
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorScale {
+    /// same id used for the monitors on the rest of the app (e.g. `DISPLAY1`)
+    pub id: String,
+    /// 1.0 = 96 dpi (100%)
+    pub scale_factor: f32,
+    /// in physical pixels
+    pub work_area: Rect,
+}
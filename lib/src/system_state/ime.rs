@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// Input method of the focused window, used by CJK users to switch between latin letters and
+/// the native characters of the language.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImeState {
+    /// the focused window has an input method
+    pub available: bool,
+    /// the input method is converting the typed keys into native characters
+    pub enabled: bool,
+    /// a composition or candidate window is being shown
+    pub composing: bool,
+}
@@ -1,4 +1,6 @@
 import { Obtainable, SeelenCommand, SeelenEvent } from '../handlers';
+import { AppConfiguration, AppExtraFlag, WallpaperSource } from '../state';
+import { Rect } from '../utils';
 
 export interface UIColors {
   background: string;
@@ -49,3 +51,683 @@ export class UIColors extends Obtainable<UIColors>(
     }
   }
 }
+
+export enum SeelenFeature {
+  WindowManager = 'windowManager',
+  Dock = 'dock',
+  Toolbar = 'toolbar',
+}
+
+/** Running program that could conflict with a Seelen UI feature */
+export interface ConflictingSoftware {
+  name: string;
+  exe: string;
+  conflictsWith: SeelenFeature;
+}
+
+export interface SystemCapabilities {
+  /** native virtual desktops can be managed by Seelen UI */
+  nativeVirtualDesktops: boolean;
+  /** Seelen UI is running with admin rights */
+  isElevated: boolean;
+  conflictingSoftware: ConflictingSoftware[];
+}
+
+export enum OnboardingPreset {
+  /** only the dock replacing the native taskbar */
+  DockOnly = 'dockOnly',
+  /** toolbar, dock, tiling window manager and launcher */
+  FullTiling = 'fullTiling',
+  /** only the toolbar */
+  Minimal = 'minimal',
+}
+
+export class StartupTaskOptions {
+  /** run with admin rights, needed to manage elevated windows */
+  elevated: boolean = true;
+  /** seconds to wait after login before starting */
+  delay: number = 0;
+}
+
+/** Current state of the login startup task on the Task Scheduler */
+export interface StartupTaskStatus {
+  registered: boolean;
+  elevated: boolean;
+  delay: number;
+  /** executable launched by the task */
+  exe: string | null;
+}
+
+export interface SwitcherWindow {
+  hwnd: number;
+  /** title rewritten by the title rules of the app */
+  title: string;
+  appName: string;
+  exe: string | null;
+  iconPath: string | null;
+  /** is null while the thumbnail is being captured or if the window is minimized */
+  thumbnailPath: string | null;
+  isMinimized: boolean;
+  /** tags of the app of the window */
+  tags: string[];
+}
+
+/** Snapshot of the windows shown by the switcher, ordered by most recently used */
+export interface WindowSwitcherState {
+  visible: boolean;
+  windows: SwitcherWindow[];
+  /** index of the selected window on `windows` */
+  selected: number;
+  /** tag used to filter the windows, if any */
+  tag: string | null;
+}
+
+export interface WindowTagsState {
+  /** tags by app identity (AUMID or lowercase exe path), shared by all the windows of the app */
+  apps: Record<string, string[]>;
+  /** only the windows with this tag are shown on the switcher and the dock */
+  filter: string | null;
+}
+
+export enum DisplayOrientation {
+  Landscape = 'Landscape',
+  Portrait = 'Portrait',
+  LandscapeFlipped = 'LandscapeFlipped',
+  PortraitFlipped = 'PortraitFlipped',
+}
+
+export interface DisplayMode {
+  /** in physical pixels, as the display is in landscape orientation */
+  width: number;
+  height: number;
+  /** in hertz */
+  refreshRate: number;
+  bitsPerPixel: number;
+}
+
+export interface DisplayDevice {
+  /** same id used for the monitors on the rest of the app (e.g. `DISPLAY1`) */
+  id: string;
+  /** friendly name of the monitor connected to the device */
+  label: string;
+  isPrimary: boolean;
+  /** position of the top left corner on the virtual screen */
+  x: number;
+  y: number;
+  orientation: DisplayOrientation;
+  currentMode: DisplayMode;
+  /** modes supported by the display, sorted from the biggest to the smallest */
+  modes: DisplayMode[];
+}
+
+/** Emitted when the scale (DPI) of a monitor changes, so the surfaces can be resized crisply */
+export interface MonitorScale {
+  /** same id used for the monitors on the rest of the app (e.g. `DISPLAY1`) */
+  id: string;
+  /** 1.0 = 96 dpi (100%) */
+  scaleFactor: number;
+  /** in physical pixels */
+  workArea: Rect;
+}
+
+export interface NightLightState {
+  active: boolean;
+  /** true if the user toggled it, the override lasts until the next scheduled change */
+  manual: boolean;
+}
+
+export enum BrightnessSource {
+  /** internal panels of laptops and tablets */
+  Wmi = 'Wmi',
+  /** external monitors supporting DDC/CI */
+  Ddc = 'Ddc',
+}
+
+export interface MonitorBrightness {
+  /** same id used for the monitors on the rest of the app (e.g. `DISPLAY1`) */
+  id: string;
+  source: BrightnessSource;
+  min: number;
+  max: number;
+  current: number;
+}
+
+export interface BluetoothDevice {
+  /** 48 bits address of the device, used as id by the commands */
+  address: number;
+  name: string;
+  connected: boolean;
+  lowEnergy: boolean;
+  /** from 0 to 100, only available if the device reports it */
+  battery: number | null;
+}
+
+export interface BluetoothState {
+  /** false if there is no bluetooth adapter */
+  available: boolean;
+  /** state of the radio, devices can't be connected while it is off */
+  enabled: boolean;
+  /** paired devices */
+  devices: BluetoothDevice[];
+}
+
+export enum RemovableDriveKind {
+  /** usb sticks and external disks */
+  Usb = 'Usb',
+  /** memory cards on built-in readers */
+  Card = 'Card',
+  Optical = 'Optical',
+}
+
+export interface RemovableDrive {
+  /** drive letter without the colon (e.g. `E`) */
+  letter: string;
+  /** volume label, empty if the volume has no label */
+  label: string;
+  kind: RemovableDriveKind;
+  /** capacity in bytes */
+  total: number;
+  /** free space in bytes */
+  free: number;
+}
+
+export interface StorageVolume {
+  /** drive letter without the colon (e.g. `C`) */
+  letter: string;
+  /** volume label, empty if the volume has no label */
+  label: string;
+  /** e.g. `NTFS` */
+  fileSystem: string;
+  /** capacity in bytes */
+  total: number;
+  /** free space in bytes */
+  free: number;
+  /** number of the physical disk, `null` if the volume spans several disks */
+  disk: number | null;
+}
+
+/** Health of a physical disk, each value is `null` if the disk doesn't report it */
+export interface DiskHealth {
+  /** same number of `\\.\PhysicalDriveN` */
+  number: number;
+  model: string;
+  /** in celsius */
+  temperature: number | null;
+  /** SMART reallocated sectors count, only available on ATA disks */
+  reallocatedSectors: number | null;
+  /** the disk predicts a failure soon */
+  failurePredicted: boolean | null;
+}
+
+export interface StorageState {
+  /** fixed volumes, removable drives are not included */
+  volumes: StorageVolume[];
+  /** disks of the volumes */
+  disks: DiskHealth[];
+}
+
+export class StorageState extends Obtainable<StorageState>(
+  SeelenCommand.StorageGetState,
+  SeelenEvent.StorageChanged,
+) {}
+
+export enum PrinterProblem {
+  Error = 'Error',
+  PaperOut = 'PaperOut',
+  PaperJam = 'PaperJam',
+  PaperProblem = 'PaperProblem',
+  TonerLow = 'TonerLow',
+  NoToner = 'NoToner',
+  DoorOpen = 'DoorOpen',
+  UserIntervention = 'UserIntervention',
+}
+
+export enum PrintJobStatus {
+  Queued = 'Queued',
+  Spooling = 'Spooling',
+  Printing = 'Printing',
+  Paused = 'Paused',
+  Error = 'Error',
+  Deleting = 'Deleting',
+}
+
+export interface PrintJob {
+  id: number;
+  document: string;
+  user: string;
+  status: PrintJobStatus;
+  /** 0 if the driver doesn't report it */
+  totalPages: number;
+  pagesPrinted: number;
+}
+
+export interface Printer {
+  name: string;
+  isDefault: boolean;
+  paused: boolean;
+  offline: boolean;
+  problems: PrinterProblem[];
+  /** pending jobs on the queue, in printing order */
+  jobs: PrintJob[];
+}
+
+export interface RecycleBinState {
+  /** items on the recycle bins of all the drives */
+  items: number;
+  /** total size in bytes */
+  size: number;
+}
+
+export enum SessionChange {
+  Lock = 'Lock',
+  Unlock = 'Unlock',
+  /** the session was connected from a remote desktop client */
+  RemoteConnect = 'RemoteConnect',
+  RemoteDisconnect = 'RemoteDisconnect',
+  /** the session was connected to the physical console (e.g. switching back to this user) */
+  ConsoleConnect = 'ConsoleConnect',
+  ConsoleDisconnect = 'ConsoleDisconnect',
+}
+
+export interface SessionState {
+  locked: boolean;
+  /** true if the session is being used through remote desktop */
+  remote: boolean;
+  /** change that triggered the event, `null` on the initial state */
+  change: SessionChange | null;
+}
+
+export enum CaptureKind {
+  /** all the monitors */
+  Screen = 'Screen',
+  Window = 'Window',
+  Region = 'Region',
+}
+
+export interface CaptureResult {
+  kind: CaptureKind;
+  /** saved png file, `null` if saving to a folder is disabled */
+  path: string | null;
+  /** true if the image was copied to the clipboard */
+  copied: boolean;
+  width: number;
+  height: number;
+}
+
+export enum ColorFormat {
+  /** `#1a2b3c` */
+  Hex = 'Hex',
+  /** `rgb(26, 43, 60)` */
+  Rgb = 'Rgb',
+}
+
+export interface PickedColor {
+  r: number;
+  g: number;
+  b: number;
+  /** color as copied to the clipboard */
+  text: string;
+}
+
+export interface ColorPickerLoupe {
+  /** cursor position in physical pixels */
+  x: number;
+  y: number;
+  /** side of the square area around the cursor, odd so the cursor is the center pixel */
+  size: number;
+  /** `0xRRGGBB` colors of the area, row by row */
+  pixels: number[];
+}
+
+export interface AppUsage {
+  /** app user model id or lowercased executable path */
+  app: string;
+  name: string;
+  /** foreground time in seconds */
+  seconds: number;
+}
+
+export interface AppUsageReport {
+  /** local date as `YYYY-MM-DD` */
+  date: string;
+  /** foreground time of all the apps in seconds */
+  total: number;
+  /** most used apps first */
+  apps: AppUsage[];
+}
+
+export enum BreakPhase {
+  Disabled = 'Disabled',
+  /** counting the activity until the next break */
+  Working = 'Working',
+  /** same as working but the break was postponed */
+  Snoozed = 'Snoozed',
+  OnBreak = 'OnBreak',
+}
+
+export interface BreakReminderState {
+  phase: BreakPhase;
+  /** true while the user is away, the activity is not counted */
+  paused: boolean;
+  /** seconds until the next break or until the end of the current one */
+  remaining: number;
+  /** unix timestamp in milliseconds when the phase ends, `null` while paused or disabled */
+  endsAt: number | null;
+}
+
+export interface CrashReport {
+  /** name of the report folder inside the diagnostics folder */
+  id: string;
+  /** unix timestamp in milliseconds */
+  timestamp: number;
+  /** panic message or exception code */
+  cause: string;
+  location: string | null;
+  /** version of Seelen UI that crashed */
+  version: string;
+  hasMinidump: boolean;
+}
+
+export interface StartMenuItem {
+  /** path to the shortcut file */
+  path: string;
+  /** file name of the shortcut without extension */
+  label: string;
+  /** program the shortcut points to, `null` if it can't be resolved */
+  target: string | null;
+  arguments: string | null;
+  /** extracted icon of the shortcut */
+  icon: string | null;
+}
+
+export interface ProcessInfo {
+  pid: number;
+  parentPid: number;
+  /** file name of the executable, example: `obs64.exe` */
+  name: string;
+  /** `null` if the path can't be read, e.g. protected system processes */
+  exe: string | null;
+}
+
+export enum ProcessEventKind {
+  Started = 'Started',
+  Stopped = 'Stopped',
+}
+
+/** Process started or stopped, including background processes without windows */
+export interface ProcessEvent {
+  kind: ProcessEventKind;
+  process: ProcessInfo;
+}
+
+export enum RestorePointReason {
+  Manual = 'Manual',
+  /** before installing or updating a resource from the marketplace */
+  Marketplace = 'Marketplace',
+  /** before loading a resource from a `.slu` file or uri */
+  ResourceImport = 'ResourceImport',
+  /** before applying another restore point, so the rollback can be reverted */
+  Rollback = 'Rollback',
+  /** before migrating the settings on the first run of a new version */
+  VersionUpgrade = 'VersionUpgrade',
+}
+
+export interface RestorePoint {
+  /** name of the folder inside `data_dir/restore` */
+  id: string;
+  /** unix timestamp in milliseconds */
+  timestamp: number;
+  reason: RestorePointReason;
+  /** resource or restore point that caused the snapshot */
+  source: string | null;
+  /** version of Seelen UI that created the snapshot */
+  version: string;
+}
+
+/**
+ * Input method of the focused window, used by CJK users to switch between latin letters and
+ * the native characters of the language.
+ */
+export interface ImeState {
+  /** the focused window has an input method */
+  available: boolean;
+  /** the input method is converting the typed keys into native characters */
+  enabled: boolean;
+  /** a composition or candidate window is being shown */
+  composing: boolean;
+}
+
+export enum OsdKind {
+  Volume = 'Volume',
+  Brightness = 'Brightness',
+  Media = 'Media',
+}
+
+/** Emitted when a volume, brightness or media key is handled, to be shown as a popup */
+export interface OsdEvent {
+  kind: OsdKind;
+  /** new level from 0 to 1, null for media keys */
+  value: number | null;
+  muted: boolean;
+  /** name of the audio device, id of the monitor or title of the media */
+  label: string | null;
+}
+
+/** Cached image of a daily wallpaper source, with the metadata given by the provider */
+export interface DailyWallpaper {
+  source: WallpaperSource;
+  path: string;
+  title: string | null;
+  copyright: string | null;
+  /** page with more information about the image */
+  link: string | null;
+  /** unix timestamp in seconds of the download */
+  fetchedAt: number;
+}
+
+/** A configured clock at the current tick */
+export interface ClockEntry {
+  id: string;
+  label: string | null;
+  /** IANA id of the time zone used */
+  timezone: string;
+  /** current time with the format of the clock applied */
+  text: string;
+  /** ISO 8601 week number */
+  week: number;
+}
+
+/** Emitted on each aligned tick (second or minute) */
+export interface ClockState {
+  /** unix timestamp in milliseconds of the tick */
+  time: number;
+  clocks: ClockEntry[];
+}
+
+/** Adaptations applied while the battery saver of Windows is on */
+export interface PowerProfileState {
+  /** battery saver of Windows is on */
+  batterySaver: boolean;
+  onBattery: boolean;
+  /** the power saving adaptations are being applied */
+  saving: boolean;
+  /** wallpaper videos and slideshows should be paused */
+  pauseWallpaper: boolean;
+  /** animations and transitions should be disabled */
+  disableAnimations: boolean;
+}
+
+export class PowerProfileState extends Obtainable<PowerProfileState>(
+  SeelenCommand.GetPowerProfile,
+  SeelenEvent.PowerProfileChanged,
+) {}
+
+/** Direction of the layout resolved from the settings, followed by all the surfaces */
+export interface LayoutDirectionState {
+  rtl: boolean;
+}
+
+export class LayoutDirectionState extends Obtainable<LayoutDirectionState>(
+  SeelenCommand.GetLayoutDirection,
+  SeelenEvent.LayoutDirectionChanged,
+) {}
+
+/** Sticky note stored as a markdown file on `data_dir/notes` */
+export interface Note {
+  /** name of the file without the extension */
+  id: string;
+  path: string;
+  /** first line of the content without the heading marks */
+  title: string;
+  content: string;
+  /** unix timestamp in milliseconds of the last change */
+  modified: number;
+}
+
+export const NoteList = Obtainable<Note[]>(SeelenCommand.NotesGetAll, SeelenEvent.NotesChanged);
+
+export enum QuickSettingsToggle {
+  Wifi = 'Wifi',
+  Bluetooth = 'Bluetooth',
+  NightLight = 'NightLight',
+  DoNotDisturb = 'DoNotDisturb',
+  Mute = 'Mute',
+}
+
+/** Snapshot of the toggles and sliders of the quick settings panel */
+export interface QuickSettingsState {
+  /** `null` if there is no wifi adapter */
+  wifi: boolean | null;
+  /** `null` if there is no bluetooth adapter */
+  bluetooth: boolean | null;
+  nightLight: boolean;
+  /** focus assist, `null` if its state can't be read */
+  doNotDisturb: boolean | null;
+  /** volume of the default output device from 0 to 1, `null` if there is no output device */
+  volume: number | null;
+  muted: boolean;
+  /** brightness of the main monitor from 0 to 100, `null` if it can't be changed */
+  brightness: number | null;
+}
+
+export class QuickSettingsState extends Obtainable<QuickSettingsState>(
+  SeelenCommand.QuickSettingsGetState,
+  SeelenEvent.QuickSettingsChanged,
+) {}
+
+/** Window requesting the attention of the user by flashing its taskbar button */
+export interface UrgentWindow {
+  hwnd: number;
+  /** id of the workspace containing the window */
+  workspace: string | null;
+}
+
+/** Checks applied to decide if a window is shown on the dock and tiled by the window manager */
+export enum WindowFilterKind {
+  /** the executable of the window can be read, it can't without admin rights on elevated apps */
+  Program = 'Program',
+  /** windows of `C:\Windows\SystemApps`, like the start menu or the lock screen */
+  SystemApp = 'SystemApp',
+  Visible = 'Visible',
+  /** windows with a parent or owner, like dialogs and popups */
+  Owner = 'Owner',
+  /** windows created by Seelen UI */
+  SeelenOverlay = 'SeelenOverlay',
+  /** classes set on `windowFilters.classBlacklist` */
+  Class = 'Class',
+  /** tool windows and windows that can't be activated */
+  Styles = 'Styles',
+  /** frames of UWP apps without content */
+  GhostFrame = 'GhostFrame',
+  SuspendedUwp = 'SuspendedUwp',
+  /** windows of apps configured to be hidden from the dock */
+  AppConfig = 'AppConfig',
+  /** empty titles and the titles of shell windows */
+  Title = 'Title',
+  /** windows smaller than `windowFilters.minWidth` or `windowFilters.minHeight` */
+  Size = 'Size',
+  /** composition and candidate windows of input methods */
+  Ime = 'Ime',
+  /** windows without title bar and always on top windows */
+  Caption = 'Caption',
+  Minimized = 'Minimized',
+  /** windows hidden by the system, like the ones on other virtual desktops */
+  Cloaked = 'Cloaked',
+  /** windows floated by the user or in fullscreen */
+  WmState = 'WmState',
+  /** windows of apps configured to be forced, unmanaged or pinned on the window manager */
+  WmAppConfig = 'WmAppConfig',
+}
+
+export enum WindowFilterOutcome {
+  /** the window passed the check, the next checks decide */
+  Passed = 'Passed',
+  /** the window is excluded */
+  Rejected = 'Rejected',
+  /** the window is included regardless of the next checks */
+  Forced = 'Forced',
+}
+
+export interface WindowFilterStep {
+  filter: WindowFilterKind;
+  outcome: WindowFilterOutcome;
+  /** human readable value that was checked */
+  detail: string;
+}
+
+export interface WindowFilterVerdict {
+  included: boolean;
+  /** first check that rejected or forced the window, `null` if all of them passed */
+  decidedBy: WindowFilterKind | null;
+  /** every check of the chain in evaluation order, including the ones after the decision */
+  steps: WindowFilterStep[];
+}
+
+/** Explanation of why a window is or isn't shown on the dock and tiled */
+export interface WindowFilterReport {
+  hwnd: number;
+  title: string;
+  class: string;
+  exe: string | null;
+  weg: WindowFilterVerdict;
+  wm: WindowFilterVerdict;
+}
+
+/** Everything used to match a window against the app configs, useful to write `applications.yml` rules */
+export interface WindowInspection {
+  hwnd: number;
+  title: string;
+  class: string;
+  exe: string | null;
+  /** app user model id, set on UWP apps and on some apps to group their windows */
+  aumid: string | null;
+  /** configs matching the window in priority order, only the first one is applied */
+  matchedConfigs: AppConfiguration[];
+  /** options of the applied config */
+  appliedFlags: AppExtraFlag[];
+  weg: WindowFilterVerdict;
+  wm: WindowFilterVerdict;
+}
+
+/** Entries of a changelog category (features, fix, etc) of a release */
+export interface ChangelogSection {
+  kind: string;
+  entries: string[];
+}
+
+export interface ChangelogRelease {
+  /** `Unreleased` for the entries of development builds */
+  version: string;
+  sections: ChangelogSection[];
+}
+
+/** Sent on the first run after the installed version changes */
+export interface VersionUpgrade {
+  /** null if the last used version is unknown (installed before the version was tracked) */
+  previous: string | null;
+  current: string;
+  /** releases between the previous and the current version, newest first */
+  notes: ChangelogRelease[];
+  /** description of the settings migrations applied */
+  migrations: string[];
+}
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+/// Direction of the layout resolved from the settings, followed by all the surfaces
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutDirectionState {
+    pub rtl: bool,
+}
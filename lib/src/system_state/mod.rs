@@ -1,3 +1,69 @@
+mod app_usage;
+mod bluetooth;
+mod break_reminder;
+mod brightness;
+mod capture;
+mod clock;
+mod color_picker;
+mod daily_wallpaper;
+mod diagnostics;
+mod display;
+mod ime;
+mod layout_direction;
+mod night_light;
+mod notes;
+mod onboarding;
+mod osd;
+mod power_profile;
+mod printers;
+mod processes;
+mod quick_settings;
+mod recycle_bin;
+mod removable_drives;
+mod restore_points;
+mod session;
+mod start_menu;
+mod startup;
+mod storage;
+mod urgency;
+mod version_upgrade;
+mod window_filter;
+mod window_switcher;
+mod window_tags;
+
+pub use app_usage::*;
+pub use bluetooth::*;
+pub use break_reminder::*;
+pub use brightness::*;
+pub use capture::*;
+pub use clock::*;
+pub use color_picker::*;
+pub use daily_wallpaper::*;
+pub use diagnostics::*;
+pub use display::*;
+pub use ime::*;
+pub use layout_direction::*;
+pub use night_light::*;
+pub use notes::*;
+pub use onboarding::*;
+pub use osd::*;
+pub use power_profile::*;
+pub use printers::*;
+pub use processes::*;
+pub use quick_settings::*;
+pub use recycle_bin::*;
+pub use removable_drives::*;
+pub use restore_points::*;
+pub use session::*;
+pub use start_menu::*;
+pub use startup::*;
+pub use storage::*;
+pub use urgency::*;
+pub use version_upgrade::*;
+pub use window_filter::*;
+pub use window_switcher::*;
+pub use window_tags::*;
+
 use serde::Serialize;
 
 /// https://learn.microsoft.com/is-is/uwp/api/windows.ui.viewmanagement.uicolortype?view=winrt-19041
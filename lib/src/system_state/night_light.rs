@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NightLightState {
+    pub active: bool,
+    /// true if the user toggled it, the override lasts until the next scheduled change
+    pub manual: bool,
+}
@@ -0,0 +1,17 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Sticky note stored as a markdown file on `data_dir/notes`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Note {
+    /// name of the file without the extension
+    pub id: String,
+    pub path: PathBuf,
+    /// first line of the content without the heading marks
+    pub title: String,
+    pub content: String,
+    /// unix timestamp in milliseconds of the last change
+    pub modified: u64,
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SeelenFeature {
+    WindowManager,
+    Dock,
+    Toolbar,
+}
+
+/// Running program that could conflict with a Seelen UI feature
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictingSoftware {
+    pub name: String,
+    pub exe: String,
+    pub conflicts_with: SeelenFeature,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemCapabilities {
+    /// native virtual desktops can be managed by Seelen UI
+    pub native_virtual_desktops: bool,
+    /// Seelen UI is running with admin rights
+    pub is_elevated: bool,
+    pub conflicting_software: Vec<ConflictingSoftware>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OnboardingPreset {
+    /// only the dock replacing the native taskbar
+    DockOnly,
+    /// toolbar, dock, tiling window manager and launcher
+    FullTiling,
+    /// only the toolbar
+    Minimal,
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OsdKind {
+    Volume,
+    Brightness,
+    Media,
+}
+
+/// Emitted when a volume, brightness or media key is handled, to be shown as a popup
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OsdEvent {
+    pub kind: OsdKind,
+    /// new level from 0 to 1, none for media keys
+    pub value: Option<f32>,
+    pub muted: bool,
+    /// name of the audio device, id of the monitor or title of the media
+    pub label: Option<String>,
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// Adaptations applied while the battery saver of Windows is on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerProfileState {
+    /// battery saver of Windows is on
+    pub battery_saver: bool,
+    pub on_battery: bool,
+    /// the power saving adaptations are being applied
+    pub saving: bool,
+    /// wallpaper videos and slideshows should be paused
+    pub pause_wallpaper: bool,
+    /// animations and transitions should be disabled
+    pub disable_animations: bool,
+}
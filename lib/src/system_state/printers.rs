@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PrinterProblem {
+    Error,
+    PaperOut,
+    PaperJam,
+    PaperProblem,
+    TonerLow,
+    NoToner,
+    DoorOpen,
+    UserIntervention,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PrintJobStatus {
+    Queued,
+    Spooling,
+    Printing,
+    Paused,
+    Error,
+    Deleting,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrintJob {
+    pub id: u32,
+    pub document: String,
+    pub user: String,
+    pub status: PrintJobStatus,
+    /// 0 if the driver doesn't report it
+    pub total_pages: u32,
+    pub pages_printed: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Printer {
+    pub name: String,
+    pub is_default: bool,
+    pub paused: bool,
+    pub offline: bool,
+    pub problems: Vec<PrinterProblem>,
+    /// pending jobs on the queue, in printing order
+    pub jobs: Vec<PrintJob>,
+}
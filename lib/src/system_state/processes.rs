@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub parent_pid: u32,
+    /// file name of the executable, example: `obs64.exe`
+    pub name: String,
+    /// `None` if the path can't be read, e.g. protected system processes
+    pub exe: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProcessEventKind {
+    Started,
+    Stopped,
+}
+
+/// Process started or stopped, including background processes without windows
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessEvent {
+    pub kind: ProcessEventKind,
+    pub process: ProcessInfo,
+}
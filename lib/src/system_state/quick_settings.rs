@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QuickSettingsToggle {
+    Wifi,
+    Bluetooth,
+    NightLight,
+    DoNotDisturb,
+    Mute,
+}
+
+/// Snapshot of the toggles and sliders of the quick settings panel
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickSettingsState {
+    /// `None` if there is no wifi adapter
+    pub wifi: Option<bool>,
+    /// `None` if there is no bluetooth adapter
+    pub bluetooth: Option<bool>,
+    pub night_light: bool,
+    /// focus assist, `None` if its state can't be read
+    pub do_not_disturb: Option<bool>,
+    /// volume of the default output device from 0 to 1, `None` if there is no output device
+    pub volume: Option<f32>,
+    pub muted: bool,
+    /// brightness of the main monitor from 0 to 100, `None` if it can't be changed
+    pub brightness: Option<u32>,
+}
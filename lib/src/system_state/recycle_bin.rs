@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecycleBinState {
+    /// items on the recycle bins of all the drives
+    pub items: u64,
+    /// total size in bytes
+    pub size: u64,
+}
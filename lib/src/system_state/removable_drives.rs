@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RemovableDriveKind {
+    /// usb sticks and external disks
+    Usb,
+    /// memory cards on built-in readers
+    Card,
+    Optical,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemovableDrive {
+    /// drive letter without the colon (e.g. `E`)
+    pub letter: char,
+    /// volume label, empty if the volume has no label
+    pub label: String,
+    pub kind: RemovableDriveKind,
+    /// capacity in bytes
+    pub total: u64,
+    /// free space in bytes
+    pub free: u64,
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RestorePointReason {
+    Manual,
+    /// before installing or updating a resource from the marketplace
+    Marketplace,
+    /// before loading a resource from a `.slu` file or uri
+    ResourceImport,
+    /// before applying another restore point, so the rollback can be reverted
+    Rollback,
+    /// before migrating the settings on the first run of a new version
+    VersionUpgrade,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestorePoint {
+    /// name of the folder inside `data_dir/restore`
+    pub id: String,
+    /// unix timestamp in milliseconds
+    pub timestamp: u64,
+    pub reason: RestorePointReason,
+    /// resource or restore point that caused the snapshot
+    pub source: Option<String>,
+    /// version of Seelen UI that created the snapshot
+    pub version: String,
+}
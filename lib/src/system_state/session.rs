@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionChange {
+    Lock,
+    Unlock,
+    /// the session was connected from a remote desktop client
+    RemoteConnect,
+    RemoteDisconnect,
+    /// the session was connected to the physical console (e.g. switching back to this user)
+    ConsoleConnect,
+    ConsoleDisconnect,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionState {
+    pub locked: bool,
+    /// true if the session is being used through remote desktop
+    pub remote: bool,
+    /// change that triggered the event, `None` on the initial state
+    pub change: Option<SessionChange>,
+}
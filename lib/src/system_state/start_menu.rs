@@ -0,0 +1,18 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Shortcut on the user or common Start Menu folders
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartMenuItem {
+    /// path to the shortcut file
+    pub path: PathBuf,
+    /// file name of the shortcut without extension
+    pub label: String,
+    /// program the shortcut points to, `None` if it can't be resolved
+    pub target: Option<PathBuf>,
+    pub arguments: Option<String>,
+    /// extracted icon of the shortcut
+    pub icon: Option<PathBuf>,
+}
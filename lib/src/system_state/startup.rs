@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StartupTaskOptions {
+    /// run with admin rights, needed to manage elevated windows
+    pub elevated: bool,
+    /// seconds to wait after login before starting
+    pub delay: u32,
+}
+
+impl Default for StartupTaskOptions {
+    fn default() -> Self {
+        Self {
+            elevated: true,
+            delay: 0,
+        }
+    }
+}
+
+/// Current state of the login startup task on the Task Scheduler
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StartupTaskStatus {
+    pub registered: bool,
+    pub elevated: bool,
+    pub delay: u32,
+    /// executable launched by the task
+    pub exe: Option<String>,
+}
+
+impl StartupTaskStatus {
+    pub fn options(&self) -> StartupTaskOptions {
+        StartupTaskOptions {
+            elevated: self.elevated,
+            delay: self.delay,
+        }
+    }
+}
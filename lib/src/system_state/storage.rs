@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageVolume {
+    /// drive letter without the colon (e.g. `C`)
+    pub letter: char,
+    /// volume label, empty if the volume has no label
+    pub label: String,
+    /// e.g. `NTFS`
+    pub file_system: String,
+    /// capacity in bytes
+    pub total: u64,
+    /// free space in bytes
+    pub free: u64,
+    /// number of the physical disk, `None` if the volume spans several disks
+    pub disk: Option<u32>,
+}
+
+/// Health of a physical disk, each value is `None` if the disk doesn't report it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskHealth {
+    /// same number of `\\.\PhysicalDriveN`
+    pub number: u32,
+    pub model: String,
+    /// in celsius
+    pub temperature: Option<u32>,
+    /// SMART reallocated sectors count, only available on ATA disks
+    pub reallocated_sectors: Option<u64>,
+    /// the disk predicts a failure soon
+    pub failure_predicted: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageState {
+    /// fixed volumes, removable drives are not included
+    pub volumes: Vec<StorageVolume>,
+    /// disks of the volumes
+    pub disks: Vec<DiskHealth>,
+}
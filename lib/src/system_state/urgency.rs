@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+/// Window requesting the attention of the user by flashing its taskbar button
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UrgentWindow {
+    pub hwnd: isize,
+    /// id of the workspace containing the window
+    pub workspace: Option<String>,
+}
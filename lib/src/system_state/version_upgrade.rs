@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// Entries of a changelog category (features, fix, etc) of a release
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangelogSection {
+    pub kind: String,
+    pub entries: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangelogRelease {
+    /// `Unreleased` for the entries of development builds
+    pub version: String,
+    pub sections: Vec<ChangelogSection>,
+}
+
+/// Sent on the first run after the installed version changes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionUpgrade {
+    /// null if the last used version is unknown (installed before the version was tracked)
+    pub previous: Option<String>,
+    pub current: String,
+    /// releases between the previous and the current version, newest first
+    pub notes: Vec<ChangelogRelease>,
+    /// description of the settings migrations applied
+    pub migrations: Vec<String>,
+}
@@ -0,0 +1,102 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::state::{AppConfig, AppExtraFlag};
+
+/// Checks applied to decide if a window is shown on the dock and tiled by the window manager
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WindowFilterKind {
+    /// the executable of the window can be read, it can't without admin rights on elevated apps
+    Program,
+    /// windows of `C:\Windows\SystemApps`, like the start menu or the lock screen
+    SystemApp,
+    Visible,
+    /// windows with a parent or owner, like dialogs and popups
+    Owner,
+    /// windows created by Seelen UI
+    SeelenOverlay,
+    /// classes set on `windowFilters.classBlacklist`
+    Class,
+    /// tool windows and windows that can't be activated
+    Styles,
+    /// frames of UWP apps without content
+    GhostFrame,
+    SuspendedUwp,
+    /// windows of apps configured to be hidden from the dock
+    AppConfig,
+    /// empty titles and the titles of shell windows
+    Title,
+    /// windows smaller than `windowFilters.minWidth` or `windowFilters.minHeight`
+    Size,
+    /// composition and candidate windows of input methods
+    Ime,
+    /// windows without title bar and always on top windows
+    Caption,
+    Minimized,
+    /// windows hidden by the system, like the ones on other virtual desktops
+    Cloaked,
+    /// windows floated by the user or in fullscreen
+    WmState,
+    /// windows of apps configured to be forced, unmanaged or pinned on the window manager
+    WmAppConfig,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WindowFilterOutcome {
+    /// the window passed the check, the next checks decide
+    Passed,
+    /// the window is excluded
+    Rejected,
+    /// the window is included regardless of the next checks
+    Forced,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowFilterStep {
+    pub filter: WindowFilterKind,
+    pub outcome: WindowFilterOutcome,
+    /// human readable value that was checked
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowFilterVerdict {
+    pub included: bool,
+    /// first check that rejected or forced the window, `None` if all of them passed
+    pub decided_by: Option<WindowFilterKind>,
+    /// every check of the chain in evaluation order, including the ones after the decision
+    pub steps: Vec<WindowFilterStep>,
+}
+
+/// Explanation of why a window is or isn't shown on the dock and tiled
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowFilterReport {
+    pub hwnd: isize,
+    pub title: String,
+    pub class: String,
+    pub exe: Option<PathBuf>,
+    pub weg: WindowFilterVerdict,
+    pub wm: WindowFilterVerdict,
+}
+
+/// Everything used to match a window against the app configs, useful to write `applications.yml` rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowInspection {
+    pub hwnd: isize,
+    pub title: String,
+    pub class: String,
+    pub exe: Option<PathBuf>,
+    /// app user model id, set on UWP apps and on some apps to group their windows
+    pub aumid: Option<String>,
+    /// configs matching the window in priority order, only the first one is applied
+    pub matched_configs: Vec<AppConfig>,
+    /// options of the applied config
+    pub applied_flags: Vec<AppExtraFlag>,
+    pub weg: WindowFilterVerdict,
+    pub wm: WindowFilterVerdict,
+}
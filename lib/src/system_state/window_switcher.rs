@@ -0,0 +1,31 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitcherWindow {
+    pub hwnd: isize,
+    /// title rewritten by the title rules of the app
+    pub title: String,
+    pub app_name: String,
+    pub exe: Option<PathBuf>,
+    pub icon_path: Option<PathBuf>,
+    /// is `None` while the thumbnail is being captured or if the window is minimized
+    pub thumbnail_path: Option<PathBuf>,
+    pub is_minimized: bool,
+    /// tags of the app of the window
+    pub tags: Vec<String>,
+}
+
+/// Snapshot of the windows shown by the switcher, ordered by most recently used
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowSwitcherState {
+    pub visible: bool,
+    pub windows: Vec<SwitcherWindow>,
+    /// index of the selected window on `windows`
+    pub selected: usize,
+    /// tag used to filter the windows, if any
+    pub tag: Option<String>,
+}
@@ -0,0 +1,12 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowTagsState {
+    /// tags by app identity (AUMID or lowercase exe path), shared by all the windows of the app
+    pub apps: BTreeMap<String, Vec<String>>,
+    /// only the windows with this tag are shown on the switcher and the dock
+    pub filter: Option<String>,
+}
@@ -0,0 +1,14 @@
+import { invoke } from '@tauri-apps/api/core';
+import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
+
+import { SeelenCommand, SeelenEvent } from '../handlers';
+
+/**
+ * Answers the heartbeat pings of the background supervisor,
+ * if the webview stops answering it will be recreated.
+ */
+export async function respondHeartbeats() {
+  await getCurrentWebviewWindow().listen(SeelenEvent.WebviewHeartbeat, () => {
+    invoke(SeelenCommand.WebviewHeartbeat);
+  });
+}
@@ -1,5 +1,8 @@
+export * from './heartbeat';
 export * from './hooks';
 export * from './layered_hitbox';
+export * from './scheduled_events';
+export * from './subscribed_events';
 
 export function getRootElement() {
   const element = document.getElementById('root');
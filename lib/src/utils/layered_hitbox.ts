@@ -1,6 +1,7 @@
 import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
 
 import { SeelenEvent } from '../handlers';
+import { listenScheduled } from './scheduled_events';
 
 export async function declareDocumentAsLayeredHitbox() {
   const webview = getCurrentWebviewWindow();
@@ -26,7 +27,7 @@ export async function declareDocumentAsLayeredHitbox() {
     is_layered_enabled = event.payload;
   });
 
-  webview.listen<[x: number, y: number]>(SeelenEvent.GlobalMouseMove, (event) => {
+  await listenScheduled<[x: number, y: number]>(SeelenEvent.GlobalMouseMove, (event) => {
     if (!is_layered_enabled) {
       return;
     }
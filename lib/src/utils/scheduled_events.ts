@@ -0,0 +1,22 @@
+import { invoke } from '@tauri-apps/api/core';
+import { EventCallback, UnlistenFn } from '@tauri-apps/api/event';
+import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
+
+import { SeelenCommand, SeelenEvent } from '../handlers';
+
+/**
+ * Listens a high frequency event (mouse move, focus changes), these are coalesced by the
+ * background and only sent to the webviews that subscribed to them.
+ */
+export async function listenScheduled<T>(
+  event: SeelenEvent,
+  cb: EventCallback<T>,
+): Promise<UnlistenFn> {
+  const webview = getCurrentWebviewWindow();
+  const unlisten = await webview.listen<T>(event, cb);
+  await invoke(SeelenCommand.SubscribeScheduledEvent, { event });
+  return () => {
+    unlisten();
+    invoke(SeelenCommand.UnsubscribeScheduledEvent, { event });
+  };
+}
@@ -0,0 +1,23 @@
+import { invoke } from '@tauri-apps/api/core';
+import { EventCallback, UnlistenFn } from '@tauri-apps/api/event';
+import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
+
+import { SeelenCommand, SeelenEvent } from '../handlers';
+
+/**
+ * Listens a global event only on the current webview and subscribes to it on the background.
+ * After the first subscription the webview stops receiving the global events it didn't
+ * subscribe to (on its own listeners), events listened with `listen` are not affected.
+ */
+export async function listenSubscribed<T>(
+  event: SeelenEvent,
+  cb: EventCallback<T>,
+): Promise<UnlistenFn> {
+  const webview = getCurrentWebviewWindow();
+  const unlisten = await webview.listen<T>(event, cb);
+  await invoke(SeelenCommand.SubscribeEvent, { event });
+  return () => {
+    unlisten();
+    invoke(SeelenCommand.UnsubscribeEvent, { event });
+  };
+}
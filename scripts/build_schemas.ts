@@ -4,4 +4,11 @@ import fs from 'fs';
 (async function main() {
   execSync('cd ./lib && cargo run');
   fs.cpSync('lib/dist', 'documentation/schemas', { recursive: true });
+  // shipped with the app, so user config files can reference them as `$schema`
+  fs.mkdirSync('static/schemas', { recursive: true });
+  for (const file of fs.readdirSync('lib/dist')) {
+    if (file.endsWith('.schema.json')) {
+      fs.copyFileSync(`lib/dist/${file}`, `static/schemas/${file}`);
+    }
+  }
 })();
@@ -1,9 +1,41 @@
 import { path } from '@tauri-apps/api';
+import { invoke } from '@tauri-apps/api/core';
+import { getCurrentWindow } from '@tauri-apps/api/window';
 import { writeTextFile } from '@tauri-apps/plugin-fs';
 import yaml from 'js-yaml';
-import { LauncherHistory } from 'seelen-core';
+import { LauncherHistory, SeelenCommand } from 'seelen-core';
+
+import { LauncherItem, LauncherItemAction } from '../shared/store/domain';
 
 export async function SaveHistory(history: LauncherHistory) {
   const yaml_route = await path.join(await path.appDataDir(), 'history');
   await writeTextFile(yaml_route, yaml.dump(history));
 }
+
+export const ItemActionLabels: Record<LauncherItemAction, string> = {
+  open: 'item.open',
+  runAsAdmin: 'item.run_as_admin',
+  openLocation: 'item.open_location',
+  copyPath: 'item.copy_path',
+  uninstall: 'item.uninstall',
+};
+
+export function getItemActions(item: LauncherItem): Promise<LauncherItemAction[]> {
+  return invoke(SeelenCommand.LauncherGetItemActions, { item });
+}
+
+export async function runItemAction(item: LauncherItem, action: LauncherItemAction) {
+  await invoke(SeelenCommand.LauncherRunItemAction, { item, action });
+  // copying the path keeps the launcher open to continue searching
+  if (action !== 'copyPath') {
+    getCurrentWindow().hide();
+  }
+}
+
+/** Shift+Enter, the second action of the item if any */
+export async function runAlternativeAction(item: LauncherItem) {
+  const [, alternative] = await getItemActions(item);
+  if (alternative) {
+    await runItemAction(item, alternative);
+  }
+}
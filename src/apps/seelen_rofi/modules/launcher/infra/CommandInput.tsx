@@ -26,7 +26,7 @@ export const CommandInput = ({
   const { t } = useTranslation();
 
   return <Tooltip open={showHelp} title="Ctrl + F" placement="top">
-    <Tooltip open={showHelp} title="Enter" placement="right">
+    <Tooltip open={showHelp} title="Enter / Shift + Enter" placement="right">
       <AutoComplete
         ref={inputRef as any}
         className="launcher-header-command-input"
@@ -1,5 +1,4 @@
 import { convertFileSrc, invoke } from '@tauri-apps/api/core';
-import { getCurrentWindow } from '@tauri-apps/api/window';
 import { Dropdown, Menu } from 'antd';
 import { memo } from 'react';
 import { useTranslation } from 'react-i18next';
@@ -8,25 +7,24 @@ import { SeelenCommand } from 'seelen-core';
 import { OverflowTooltip } from 'src/apps/shared/components/OverflowTooltip';
 
 import { StartMenuApp } from '../../shared/store/domain';
+import { useItemActions } from './ItemActions';
 
 export const Item = memo(({ item, hidden }: { item: StartMenuApp; hidden: boolean }) => {
   const { label, icon, path } = item;
 
   const { t } = useTranslation();
-
-  function onClick() {
-    invoke(SeelenCommand.OpenFile, { path });
-    getCurrentWindow().hide();
-  }
+  const { menuItems, onOpenChange, onClick, onKeyDown } = useItemActions({ kind: 'app', path });
 
   const shortPath = path.slice(path.indexOf('\\Programs\\') + 10);
 
   return (
     <Dropdown
       trigger={['contextMenu']}
+      onOpenChange={onOpenChange}
       dropdownRender={() => (
         <Menu
           items={[
+            ...menuItems,
             {
               label: t('item.pin'),
               key: 'pin',
@@ -34,13 +32,6 @@ export const Item = memo(({ item, hidden }: { item: StartMenuApp; hidden: boolea
                 invoke(SeelenCommand.WegPinItem, { path });
               },
             },
-            {
-              label: t('item.open_location'),
-              key: 'open',
-              onClick() {
-                invoke(SeelenCommand.SelectFileOnExplorer, { path });
-              },
-            },
           ]}
         />
       )}
@@ -49,6 +40,7 @@ export const Item = memo(({ item, hidden }: { item: StartMenuApp; hidden: boolea
         style={{ display: hidden ? 'none' : undefined }}
         className="launcher-item"
         onClick={onClick}
+        onKeyDown={onKeyDown}
       >
         <img className="launcher-item-icon" src={convertFileSrc(icon)} alt={label} />
         <OverflowTooltip className="launcher-item-label" text={label} />
@@ -0,0 +1,39 @@
+import { ItemType } from 'antd/es/menu/interface';
+import { KeyboardEventHandler, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+
+import { LauncherItem, LauncherItemAction } from '../../shared/store/domain';
+import { getItemActions, ItemActionLabels, runAlternativeAction, runItemAction } from '../app';
+
+/** Context menu and keyboard handling of the actions resolved for a launcher result */
+export function useItemActions(item: LauncherItem) {
+  const [actions, setActions] = useState<LauncherItemAction[]>([]);
+  const { t } = useTranslation();
+
+  const menuItems: ItemType[] = actions.map((action) => ({
+    key: action,
+    label: t(ItemActionLabels[action]),
+    onClick() {
+      runItemAction(item, action).catch(console.error);
+    },
+  }));
+
+  function onOpenChange(open: boolean) {
+    if (open) {
+      getItemActions(item).then(setActions).catch(console.error);
+    }
+  }
+
+  function onClick() {
+    runItemAction(item, 'open').catch(console.error);
+  }
+
+  const onKeyDown: KeyboardEventHandler = (e) => {
+    if (e.key === 'Enter' && e.shiftKey) {
+      e.preventDefault();
+      runAlternativeAction(item).catch(console.error);
+    }
+  };
+
+  return { menuItems, onOpenChange, onClick, onKeyDown };
+}
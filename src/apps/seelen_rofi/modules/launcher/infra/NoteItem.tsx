@@ -0,0 +1,31 @@
+import { Dropdown, Menu } from 'antd';
+import { memo } from 'react';
+import { Note } from 'seelen-core';
+
+import { Icon } from 'src/apps/shared/components/Icon';
+import { OverflowTooltip } from 'src/apps/shared/components/OverflowTooltip';
+
+import { useItemActions } from './ItemActions';
+
+export const NoteItem = memo(({ note }: { note: Note }) => {
+  const { menuItems, onOpenChange, onClick, onKeyDown } = useItemActions({
+    kind: 'note',
+    path: note.path,
+  });
+
+  const excerpt = note.content.trim().split('\n').slice(1).join(' ').trim();
+
+  return (
+    <Dropdown
+      trigger={['contextMenu']}
+      onOpenChange={onOpenChange}
+      dropdownRender={() => <Menu items={menuItems} />}
+    >
+      <button className="launcher-item launcher-note" onClick={onClick} onKeyDown={onKeyDown}>
+        <Icon className="launcher-item-icon" iconName="MdStickyNote2" />
+        <OverflowTooltip className="launcher-item-label" text={note.title || note.id} />
+        <OverflowTooltip className="launcher-item-path" text={excerpt} />
+      </button>
+    </Dropdown>
+  );
+});
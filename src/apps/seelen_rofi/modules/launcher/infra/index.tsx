@@ -8,10 +8,12 @@ import { useSelector } from 'react-redux';
 import { SeelenCommand, useWindowFocusChange } from 'seelen-core';
 
 import { Selectors } from '../../shared/store/app';
-import { SaveHistory } from '../app';
+import { runItemAction, SaveHistory } from '../app';
+import { Icon } from 'src/apps/shared/components/Icon';
 
 import { CommandInput } from './CommandInput';
 import { Item } from './Item';
+import { NoteItem } from './NoteItem';
 import { RunnerSelector } from './RunnerSelector';
 
 export function Launcher() {
@@ -23,6 +25,8 @@ export function Launcher() {
   const history = useSelector(Selectors.history);
   const runners = useSelector(Selectors.settings.runners);
   const apps = useSelector(Selectors.apps);
+  const notes = useSelector(Selectors.notes);
+  const privateSession = useSelector(Selectors.privateSession);
 
   const selectorRef = useRef<HTMLInputElement>(null);
   const inputRef = useRef<HTMLInputElement>(null);
@@ -45,13 +49,18 @@ export function Launcher() {
   const matchingHistory = selectedHistory
     .filter((value) => value.toLowerCase().includes(command))
     .map((value) => ({ value }));
+  // notes are only shown while searching, to not bury the apps
+  const matchingNotes = command
+    ? notes.filter((note) => note.content.toLowerCase().includes(command))
+    : [];
 
   const onInputKeyDown: KeyboardEventHandler<HTMLInputElement> = (e) => {
     if (!showHistory || matchingHistory.length === 0) {
       if (e.key === 'Enter') {
-        invoke(SeelenCommand.OpenFile, { path: command });
-        getCurrentWindow().hide();
-        if (selectedRunner) {
+        // Shift+Enter runs the command as administrator
+        const action = e.shiftKey ? 'runAsAdmin' : 'open';
+        runItemAction({ kind: 'command', path: command }, action).catch(console.error);
+        if (selectedRunner && !privateSession) {
           SaveHistory({
             ...history,
             [selectedRunner.id]: [...new Set([command, ...(history[selectedRunner.id] || [])])],
@@ -102,9 +111,21 @@ export function Launcher() {
           inputRef={inputRef}
           showHelp={showHelp}
         />
+        <Tooltip title={t(privateSession ? 'header.private_on' : 'header.private_off')}>
+          <button
+            className="launcher-header-private"
+            data-active={privateSession}
+            onClick={() => invoke(SeelenCommand.PrivateSessionToggle).catch(console.error)}
+          >
+            <Icon iconName={privateSession ? 'PiDetectiveFill' : 'PiDetective'} />
+          </button>
+        </Tooltip>
       </div>
       <Tooltip open={showHelp} title="Tab / Shift + Tab" placement="left">
         <div className="launcher-body">
+          {matchingNotes.map((note) => (
+            <NoteItem key={note.id} note={note} />
+          ))}
           {apps.map((item) => (
             <Item
               key={item.path}
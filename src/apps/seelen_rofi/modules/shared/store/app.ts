@@ -9,6 +9,8 @@ const initialState: LauncherState = {
   colors: UIColors.default(),
   apps: [],
   history: {},
+  notes: [],
+  privateSession: false,
   settings: new SeelenLauncherSettings(),
 };
 
@@ -1,4 +1,4 @@
-import { LauncherHistory, SeelenLauncherSettings } from 'seelen-core';
+import { LauncherHistory, Note, SeelenLauncherSettings } from 'seelen-core';
 import { IRootState } from 'src/shared.interfaces';
 
 export interface StartMenuApp {
@@ -7,7 +7,20 @@ export interface StartMenuApp {
   path: string;
 }
 
+export type LauncherItemKind = 'app' | 'command' | 'note';
+
+export interface LauncherItem {
+  kind: LauncherItemKind;
+  path: string;
+}
+
+/** resolved by the background for each item, the first one is the default action */
+export type LauncherItemAction = 'open' | 'runAsAdmin' | 'openLocation' | 'copyPath' | 'uninstall';
+
 export interface LauncherState extends IRootState<SeelenLauncherSettings> {
   apps: StartMenuApp[];
   history: LauncherHistory;
+  notes: Note[];
+  /** while active the history is not saved */
+  privateSession: boolean;
 }
@@ -1,6 +1,14 @@
 import { configureStore } from '@reduxjs/toolkit';
 import { invoke } from '@tauri-apps/api/core';
-import { LauncherHistory, SeelenCommand, Settings, UIColors } from 'seelen-core';
+import { listen } from '@tauri-apps/api/event';
+import {
+  LauncherHistory,
+  NoteList,
+  SeelenCommand,
+  SeelenEvent,
+  Settings,
+  UIColors,
+} from 'seelen-core';
 
 import { Actions, RootSlice } from './app';
 
@@ -33,8 +41,20 @@ export async function initStore() {
   dispatch(Actions.setSettings(settings.launcher));
   dispatch(Actions.setApps(await invoke(SeelenCommand.LauncherGetApps)));
   dispatch(Actions.setHistory(await LauncherHistory.getAsync()));
+  dispatch(Actions.setNotes(await NoteList.getAsync()));
+  dispatch(Actions.setPrivateSession(await invoke(SeelenCommand.PrivateSessionGet)));
+
+  // apps installed or uninstalled
+  await listen(SeelenEvent.StartMenuItemsChanged, async () => {
+    dispatch(Actions.setApps(await invoke(SeelenCommand.LauncherGetApps)));
+  });
+
+  await listen<boolean>(SeelenEvent.PrivateSessionChanged, (event) => {
+    dispatch(Actions.setPrivateSession(event.payload));
+  });
 
   LauncherHistory.onChange((history) => dispatch(Actions.setHistory(history)));
+  NoteList.onChange((notes) => dispatch(Actions.setNotes(notes)));
   Settings.onChange((settings) => {
     i18n.changeLanguage(settings.language);
     dispatch(Actions.setSettings(settings.launcher));
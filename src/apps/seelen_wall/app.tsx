@@ -2,9 +2,10 @@ import { getCurrentWindow } from '@tauri-apps/api/window';
 import { useAnimate } from 'framer-motion';
 import { useEffect, useState } from 'react';
 import { useSelector } from 'react-redux';
-import { useInterval } from 'seelen-core';
+import { useInterval, WallpaperSource } from 'seelen-core';
 
 import { ThemedWallpaper, Wallpaper } from './modules/wallpaper/infra';
+import { WallWidgets } from './modules/widgets/infra';
 
 import { Selectors } from './modules/shared/store/app';
 
@@ -13,10 +14,15 @@ export function App() {
   const [scope, animate] = useAnimate<HTMLDivElement>();
 
   const version = useSelector(Selectors.version);
-  const { backgrounds, interval } = useSelector(Selectors.settings);
+  const powerSaving = useSelector(Selectors.powerSaving);
+  const dailyWallpaper = useSelector(Selectors.dailyWallpaper);
+  const { backgrounds, interval, source } = useSelector(Selectors.settings);
+
+  const daily =
+    source !== WallpaperSource.Files && dailyWallpaper?.source === source ? dailyWallpaper : null;
 
   useInterval(() => {
-    if (backgrounds.length > 1) {
+    if (!daily && backgrounds.length > 1 && !powerSaving) {
       animate(scope.current, { opacity: 0.1 }).then(() => {
         setCurrentBg((currentIdx) => currentIdx + 1);
       });
@@ -28,20 +34,42 @@ export function App() {
   }, []);
 
   const background = backgrounds[currentBg % backgrounds.length];
-  if (!background) {
-    return <ThemedWallpaper />;
-  }
   return (
-    <Wallpaper
-      key={version}
-      path={background.path}
-      containerRef={scope}
-      onLoad={() => {
-        animate(scope.current, { opacity: 1 });
-      }}
-      onError={() => {
-        setCurrentBg((currentIdx) => currentIdx + 1);
-      }}
-    />
+    <>
+      {daily ? (
+        <>
+          <Wallpaper
+            key={`${version}-${daily.fetchedAt}`}
+            path={daily.path}
+            containerRef={scope}
+            onLoad={() => {
+              animate(scope.current, { opacity: 1 });
+            }}
+            onError={() => {}}
+          />
+          {(daily.title || daily.copyright) && (
+            <div className="wallpaper-info">
+              {daily.title && <div className="wallpaper-info-title">{daily.title}</div>}
+              {daily.copyright && <div className="wallpaper-info-copyright">{daily.copyright}</div>}
+            </div>
+          )}
+        </>
+      ) : background ? (
+        <Wallpaper
+          key={version}
+          path={background.path}
+          containerRef={scope}
+          onLoad={() => {
+            animate(scope.current, { opacity: 1 });
+          }}
+          onError={() => {
+            setCurrentBg((currentIdx) => currentIdx + 1);
+          }}
+        />
+      ) : (
+        <ThemedWallpaper />
+      )}
+      <WallWidgets />
+    </>
   );
 }
@@ -1,5 +1,6 @@
 import { createRoot } from 'react-dom/client';
 import { Provider } from 'react-redux';
+import { respondHeartbeats } from 'seelen-core';
 
 import { initStore, store } from './modules/shared/store/infra';
 
@@ -14,6 +15,7 @@ import './styles/global.css';
 
 async function Main() {
   wrapConsole();
+  await respondHeartbeats();
   await initStore();
 
   const container = getRootContainer();
@@ -10,6 +10,9 @@ const initialState: RootState = {
   settings: new SeelenWallSettings(),
   colors: UIColors.default(),
   stop: false,
+  powerSaving: false,
+  notes: [],
+  dailyWallpaper: null,
 };
 
 export const RootSlice = createSlice({
@@ -1,7 +1,12 @@
-import { SeelenWallSettings } from 'seelen-core';
+import { DailyWallpaper, Note, SeelenWallSettings } from 'seelen-core';
 import { IRootState } from 'src/shared.interfaces';
 
 export interface RootState extends IRootState<SeelenWallSettings> {
   stop: boolean;
+  /** battery saver is on and the wallpaper should not be animated */
+  powerSaving: boolean;
   version: number;
+  notes: Note[];
+  /** image of the Bing/Spotlight source, null while it is not downloaded */
+  dailyWallpaper: DailyWallpaper | null;
 }
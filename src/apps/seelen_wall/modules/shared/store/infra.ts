@@ -1,7 +1,17 @@
 import { configureStore } from '@reduxjs/toolkit';
+import { invoke } from '@tauri-apps/api/core';
 import { getCurrentWebview } from '@tauri-apps/api/webview';
-import { SeelenEvent, Settings } from 'seelen-core';
+import {
+  DailyWallpaper,
+  listenSubscribed,
+  NoteList,
+  PowerProfileState,
+  SeelenCommand,
+  SeelenEvent,
+  Settings,
+} from 'seelen-core';
 
+import { forwardWidgetClicks } from '../../widgets/infra';
 import { Actions, RootSlice } from './app';
 import { StartThemingTool } from 'src/apps/shared/styles';
 
@@ -23,14 +33,29 @@ export async function initStore() {
     store.dispatch(Actions.setSettings(settings.wall));
   });
 
+  const powerProfile = await PowerProfileState.getAsync();
+  store.dispatch(Actions.setPowerSaving(powerProfile.pauseWallpaper));
+  PowerProfileState.onChange((state) => {
+    store.dispatch(Actions.setPowerSaving(state.pauseWallpaper));
+  });
+
+  store.dispatch(Actions.setNotes(await NoteList.getAsync()));
+  NoteList.onChange((notes) => store.dispatch(Actions.setNotes(notes)));
+
+  store.dispatch(Actions.setDailyWallpaper(await invoke(SeelenCommand.DailyWallpaperGet)));
+  await listenSubscribed<DailyWallpaper>(SeelenEvent.WallDailyWallpaperChanged, ({ payload }) => {
+    store.dispatch(Actions.setDailyWallpaper(payload));
+  });
+
   webview.listen<boolean>(SeelenEvent.WallStop, ({ payload }) => {
     store.dispatch(Actions.setStop(payload));
   });
 
-  webview.listen(SeelenEvent.GlobalMonitorsChanged, () => {
+  await listenSubscribed(SeelenEvent.GlobalMonitorsChanged, () => {
     let version = store.getState().version;
     store.dispatch(Actions.setVersion(version + 1));
   });
 
+  await forwardWidgetClicks();
   StartThemingTool();
 }
@@ -17,19 +17,21 @@ export interface Props {
 
 export function Wallpaper({ path, containerRef, onLoad, onError }: Props) {
   let stoped = useSelector(Selectors.stop);
+  let powerSaving = useSelector(Selectors.powerSaving);
+  let paused = stoped || powerSaving;
   let wallpaper: ReactNode = null;
 
   const videoRef = useRef<HTMLVideoElement>(null);
 
   useEffect(() => {
     if (videoRef.current) {
-      if (stoped) {
+      if (paused) {
         videoRef.current.pause();
       } else {
         videoRef.current.play();
       }
     }
-  }, [stoped]);
+  }, [paused]);
 
   if (['.png', '.jpg', '.jpeg', '.webp', '.gif'].some((ext) => path.endsWith(ext))) {
     wallpaper = <img src={convertFileSrc(path)} onLoad={onLoad} onError={onError} />;
@@ -42,7 +44,7 @@ export function Wallpaper({ path, containerRef, onLoad, onError }: Props) {
         src={convertFileSrc(path)}
         onLoadedData={onLoad}
         onError={onError}
-        autoPlay={!stoped}
+        autoPlay={!paused}
         loop
         muted
         playsInline
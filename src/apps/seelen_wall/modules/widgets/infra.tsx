@@ -0,0 +1,161 @@
+import { invoke } from '@tauri-apps/api/core';
+import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
+import { debounce } from 'lodash';
+import moment from 'moment';
+import { useEffect, useRef, useState } from 'react';
+import { useSelector } from 'react-redux';
+import {
+  Rect,
+  SeelenCommand,
+  SeelenEvent,
+  SeelenWallWidget,
+  SeelenWallWidgetKind,
+  useInterval,
+} from 'seelen-core';
+
+import { Selectors } from '../shared/store/app';
+
+interface WallWidgetClick {
+  pressed: boolean;
+  x: number;
+  y: number;
+}
+
+// the backend only takes the clicks inside these rects from the desktop
+const reportWidgetRects = debounce(async () => {
+  const view = getCurrentWebviewWindow();
+  const { x, y } = await view.outerPosition();
+  const scale = await view.scaleFactor();
+  const toPhysical = (size: number) => Math.round(size * scale);
+
+  const rects: Rect[] = [];
+  document.querySelectorAll('.wall-widget').forEach((element) => {
+    const domRect = element.getBoundingClientRect();
+    const left = x + toPhysical(domRect.left);
+    const top = y + toPhysical(domRect.top);
+    rects.push({
+      left,
+      top,
+      right: left + toPhysical(domRect.width),
+      bottom: top + toPhysical(domRect.height),
+    });
+  });
+  invoke(SeelenCommand.WallSetWidgetRects, { rects });
+}, 300);
+
+/** Replays the clicks taken from the desktop as if the webview had received them */
+export async function forwardWidgetClicks() {
+  const view = getCurrentWebviewWindow();
+  let pressedOn: Element | null = null;
+
+  view.listen<WallWidgetClick>(SeelenEvent.WallWidgetClick, async ({ payload }) => {
+    const { x, y } = await view.outerPosition();
+    const scale = await view.scaleFactor();
+    const clientX = (payload.x - x) / scale;
+    const clientY = (payload.y - y) / scale;
+    const target = document.elementFromPoint(clientX, clientY);
+    const init = { bubbles: true, cancelable: true, clientX, clientY };
+
+    if (payload.pressed) {
+      pressedOn = target;
+      target?.dispatchEvent(new MouseEvent('mousedown', init));
+      return;
+    }
+
+    target?.dispatchEvent(new MouseEvent('mouseup', init));
+    if (target && pressedOn?.contains(target)) {
+      target.dispatchEvent(new MouseEvent('click', init));
+    }
+    pressedOn = null;
+  });
+}
+
+function ClockWidget() {
+  const [now, setNow] = useState(moment());
+  const [showDate, setShowDate] = useState(false);
+
+  useInterval(() => setNow(moment()), 1000);
+
+  return (
+    <div className="wall-widget-clock" onClick={() => setShowDate(!showDate)}>
+      <span className="wall-widget-clock-time">{now.format('HH:mm')}</span>
+      {showDate && <span className="wall-widget-clock-date">{now.format('dddd, D MMMM')}</span>}
+    </div>
+  );
+}
+
+function NotesWidget({ text }: { text: string }) {
+  const [collapsed, setCollapsed] = useState(false);
+  const [title = '', ...lines] = text.split('\n');
+
+  return (
+    <div className="wall-widget-notes" onClick={() => setCollapsed(!collapsed)}>
+      <b className="wall-widget-notes-title">{title}</b>
+      {!collapsed && <p className="wall-widget-notes-content">{lines.join('\n')}</p>}
+    </div>
+  );
+}
+
+function StickyNoteWidget({ noteId }: { noteId: string | null }) {
+  const [collapsed, setCollapsed] = useState(false);
+  const notes = useSelector(Selectors.notes);
+  const note = noteId ? notes.find((note) => note.id === noteId) : notes[0];
+
+  if (!note) {
+    return null;
+  }
+
+  // the title is the first line, so it is not repeated on the content
+  const content = note.content.trim().split('\n').slice(1).join('\n').trim();
+  return (
+    <div className="wall-widget-notes" onClick={() => setCollapsed(!collapsed)}>
+      <b className="wall-widget-notes-title">{note.title}</b>
+      {!collapsed && <p className="wall-widget-notes-content">{content}</p>}
+    </div>
+  );
+}
+
+function WallWidget({ widget }: { widget: SeelenWallWidget }) {
+  const ref = useRef<HTMLDivElement>(null);
+
+  useEffect(() => {
+    if (!ref.current) {
+      return;
+    }
+    // collapsing or the clock showing the date changes the size
+    const observer = new ResizeObserver(() => reportWidgetRects());
+    observer.observe(ref.current);
+    return () => {
+      observer.disconnect();
+      reportWidgetRects();
+    };
+  }, []);
+
+  return (
+    <div ref={ref} className="wall-widget" style={{ left: widget.x, top: widget.y }}>
+      {widget.kind === SeelenWallWidgetKind.Clock && <ClockWidget />}
+      {widget.kind === SeelenWallWidgetKind.Notes && <NotesWidget text={widget.text} />}
+      {widget.kind === SeelenWallWidgetKind.StickyNote && (
+        <StickyNoteWidget noteId={widget.noteId} />
+      )}
+    </div>
+  );
+}
+
+export function WallWidgets() {
+  const { widgets } = useSelector(Selectors.settings);
+  const version = useSelector(Selectors.version);
+
+  // the wall is moved when the monitors change
+  useEffect(() => {
+    reportWidgetRects();
+  }, [widgets, version]);
+
+  return (
+    <div className="wall-widgets">
+      {widgets.map((widget) => (
+        <WallWidget key={widget.id} widget={widget} />
+      ))}
+    </div>
+  );
+}
@@ -2,6 +2,7 @@ import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
 import { useEffect } from 'react';
 
 import { Layout } from './modules/layout/infra';
+import { ZonesOverlay } from './modules/zones/infra';
 
 import { ErrorBoundary } from '../seelenweg/components/Error';
 
@@ -15,6 +16,7 @@ export function App() {
   return (
     <ErrorBoundary fallback={<div>Something went wrong</div>}>
       <Layout />
+      <ZonesOverlay />
     </ErrorBoundary>
   );
 }
@@ -1,12 +1,11 @@
 import { invoke } from '@tauri-apps/api/core';
 import { getCurrentWindow } from '@tauri-apps/api/window';
-import { useCallback, useEffect, useRef } from 'react';
+import { useCallback, useEffect, useRef, useState } from 'react';
 import { useSelector } from 'react-redux';
-import { SeelenCommand } from 'seelen-core';
+import { SeelenCommand, WmSizeHints } from 'seelen-core';
 
 import { Selectors } from '../../../shared/store/app';
 
-import { toPhysicalPixels } from '../../../../../shared';
 import { cx } from '../../../../../shared/styles';
 import { ReservedContainer } from './reserved';
 
@@ -22,39 +21,66 @@ export function LeafContainer({ hwnd, growFactor }: Props) {
   const activeWindow = useSelector(Selectors.activeWindow);
   const borderSettings = useSelector(Selectors.settings.border);
 
+  const [sizeHints, setSizeHints] = useState<WmSizeHints | null>(null);
+
   const ref = useRef<HTMLDivElement>(null);
 
+  const border = borderSettings.enabled ? borderSettings.width + borderSettings.offset : 0;
+
+  useEffect(() => {
+    invoke<WmSizeHints | null>(SeelenCommand.WmGetSizeHints, { hwnd })
+      .then(setSizeHints)
+      .catch(() => setSizeHints(null));
+  }, [hwnd]);
+
   const updateSize = useCallback(async () => {
     if (!ref.current) {
       return;
     }
 
-    const border = borderSettings.enabled ? borderSettings.width + borderSettings.offset : 0;
     const domRect = ref.current.getBoundingClientRect();
-    const { x: windowX, y: windowY } = await getCurrentWindow().outerPosition();
-    const top = windowY + toPhysicalPixels(domRect.top + border);
-    const left = windowX + toPhysicalPixels(domRect.left + border);
+    // tiles overflowing the monitor are clipped so the window is floated instead of overlapping
+    const visible = {
+      top: Math.max(domRect.top, 0),
+      left: Math.max(domRect.left, 0),
+      right: Math.min(domRect.right, window.innerWidth),
+      bottom: Math.min(domRect.bottom, window.innerHeight),
+    };
+    const currentWindow = getCurrentWindow();
+    const { x: windowX, y: windowY } = await currentWindow.outerPosition();
+    // scale of the monitor where the layout is rendered, devicePixelRatio can be outdated
+    // while the window is being moved between monitors with different scaling
+    const scale = await currentWindow.scaleFactor();
+    const toPhysical = (size: number) => Math.round(size * scale);
+    const top = windowY + toPhysical(visible.top + border);
+    const left = windowX + toPhysical(visible.left + border);
     invoke(SeelenCommand.SetWindowPosition, {
       hwnd: hwnd,
       rect: {
         top,
         left,
-        right: left + toPhysicalPixels(domRect.width - border * 2),
-        bottom: top + toPhysicalPixels(domRect.height - border * 2),
+        right: left + toPhysical(visible.right - visible.left - border * 2),
+        bottom: top + toPhysical(visible.bottom - visible.top - border * 2),
       },
     });
-  }, [hwnd]);
+  }, [hwnd, border]);
 
   useEffect(() => {
     updateSize();
   });
 
+  // windows with a minimum size take a bigger share of the layout, limits are in physical pixels
+  const toLogical = (size: number | undefined) =>
+    size ? size / window.devicePixelRatio + border * 2 : undefined;
+
   const isFocused = activeWindow === hwnd;
   return (
     <div
       ref={ref}
       style={{
         flexGrow: growFactor,
+        minWidth: toLogical(sizeHints?.minWidth),
+        minHeight: toLogical(sizeHints?.minHeight),
       }}
       className={cx('wm-container', 'wm-leaf', {
         'wm-leaf-focused': isFocused,
@@ -13,6 +13,7 @@ const initialState: RootState = {
   activeWindow: 0,
   reservation: null,
   overlayVisible: true,
+  zones: null,
 };
 
 export const RootSlice = createSlice({
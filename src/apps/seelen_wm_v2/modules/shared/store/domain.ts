@@ -1,4 +1,4 @@
-import { WindowManagerSettings, WmNode } from 'seelen-core';
+import { WindowManagerSettings, WmNode, WmZonesOverlay } from 'seelen-core';
 
 import { Reservation } from '../../layout/domain';
 
@@ -18,4 +18,5 @@ export interface RootState extends IRootState<WindowManagerSettings> {
   activeWindow: number;
   reservation: Reservation | null;
   overlayVisible: boolean;
+  zones: WmZonesOverlay | null;
 }
@@ -1,7 +1,7 @@
 import { configureStore } from '@reduxjs/toolkit';
 import { listen } from '@tauri-apps/api/event';
 import { getCurrentWebview } from '@tauri-apps/api/webview';
-import { SeelenEvent, Settings, UIColors, WmNode } from 'seelen-core';
+import { SeelenEvent, Settings, UIColors, WmNode, WmZonesOverlay } from 'seelen-core';
 
 import { Actions, RootSlice } from './app';
 
@@ -53,13 +53,19 @@ export async function loadStore() {
     store.dispatch(Actions.setLayout(e.payload));
   });
 
-  await listen<void>(SeelenEvent.WMForceRetiling, () => {
+  await view.listen<WmZonesOverlay | null>(SeelenEvent.WMSetZonesOverlay, (e) => {
+    store.dispatch(Actions.setZones(e.payload));
+  });
+
+  // emitted to all the monitors or only to this one
+  await view.listen<void>(SeelenEvent.WMForceRetiling, () => {
     store.dispatch(Actions.forceUpdate());
   });
 
   await listen<boolean>(SeelenEvent.WMSetOverlayVisibility, ({ payload }) => {
     store.dispatch(Actions.setOverlayVisible(payload));
-    document.body.style.opacity = payload ? '1' : '0';
+    // only the layout is hidden, the zones overlay is shown while dragging managed windows
+    document.body.classList.toggle('wm-overlay-hidden', !payload);
   });
 
   await listen<number>(SeelenEvent.WMSetActiveWindow, ({ payload }) => {
@@ -0,0 +1,32 @@
+import { useSelector } from 'react-redux';
+
+import { Selectors } from '../../shared/store/app';
+
+import { cx } from '../../../../shared/styles';
+
+import './index.css';
+
+export function ZonesOverlay() {
+  const overlay = useSelector(Selectors.zones);
+
+  if (!overlay) {
+    return null;
+  }
+
+  return (
+    <div className="wm-zones">
+      {overlay.zones.map((zone, idx) => (
+        <div
+          key={idx}
+          className={cx('wm-zone', { 'wm-zone-active': idx === overlay.active })}
+          style={{
+            left: `${zone.x * 100}%`,
+            top: `${zone.y * 100}%`,
+            width: `${zone.width * 100}%`,
+            height: `${zone.height * 100}%`,
+          }}
+        />
+      ))}
+    </div>
+  );
+}
@@ -3,7 +3,7 @@ import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
 import { createRoot } from 'react-dom/client';
 import { I18nextProvider } from 'react-i18next';
 import { Provider } from 'react-redux';
-import { declareDocumentAsLayeredHitbox, SeelenCommand } from 'seelen-core';
+import { declareDocumentAsLayeredHitbox, respondHeartbeats, SeelenCommand } from 'seelen-core';
 
 import { loadStore, registerStoreEvents, store } from './modules/shared/store/infra';
 import { loadConstants } from './modules/shared/utils/infra';
@@ -21,6 +21,7 @@ import './styles/global.css';
 
 async function Main() {
   wrapConsole();
+  await respondHeartbeats();
   await declareDocumentAsLayeredHitbox();
   await loadConstants();
   await loadStore();
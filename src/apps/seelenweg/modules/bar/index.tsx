@@ -1,5 +1,5 @@
 import { Reorder } from 'framer-motion';
-import { useCallback, useState } from 'react';
+import { useCallback, useEffect, useState } from 'react';
 import { useTranslation } from 'react-i18next';
 import { useDispatch, useSelector } from 'react-redux';
 import {
@@ -13,7 +13,10 @@ import {
 
 import { BackgroundByLayersV2 } from '../../components/BackgroundByLayers/infra';
 import { FileOrFolder } from '../item/infra/File';
+import { LayoutItem } from '../item/infra/Layout';
 import { MediaSession } from '../item/infra/MediaSession';
+import { RecycleBin } from '../item/infra/RecycleBin';
+import { RemovableDriveItem } from '../item/infra/RemovableDrive';
 import { StartMenu } from '../item/infra/StartMenu';
 import { UserApplication } from '../item/infra/UserApplication';
 
@@ -23,7 +26,7 @@ import { SwItem } from '../shared/store/domain';
 
 import { cx } from '../../../shared/styles';
 import { WithContextMenu } from '../../components/WithContextMenu';
-import { savePinnedItems } from '../shared/store/storeApi';
+import { updateMinimizeTargets } from '../shared/utils/infra';
 import { getSeelenWegMenu } from './menu';
 
 const Separator1: SeparatorWegItem = {
@@ -58,6 +61,7 @@ export function SeelenWeg() {
   const pinnedOnLeft = useSelector(Selectors.itemsOnLeft);
   const pinnedOnCenter = useSelector(Selectors.itemsOnCenter);
   const pinnedOnRight = useSelector(Selectors.itemsOnRight);
+  const removableDrives = useSelector(Selectors.removableDrives);
 
   const [isActive, setActive] = useState(false);
 
@@ -68,6 +72,11 @@ export function SeelenWeg() {
     setActive(focused);
   });
 
+  // any change on the bar can move the items
+  useEffect(() => {
+    updateMinimizeTargets();
+  });
+
   const getSeparatorComplementarySize = useCallback(
     (sideElements: number, centerElements: number) => {
       let size = '1px';
@@ -107,13 +116,10 @@ export function SeelenWeg() {
         return;
       }
 
-      if (app.type !== SwItemType.Separator) {
-        extractedPinned.push(app);
-      }
+      extractedPinned.push(app);
     });
 
     dispatch(RootActions.setItemsOnRight(extractedPinned));
-    savePinnedItems();
   }, []);
 
   const isHorizontal =
@@ -158,6 +164,10 @@ export function SeelenWeg() {
             style={getSeparatorComplementarySize(pinnedOnRight.length, pinnedOnCenter.length)}
           />,
           ...pinnedOnRight.map(ItemByType),
+          // drives are not saved so they can't be reordered
+          ...(settings.pinRemovableDrives ? removableDrives : []).map((drive) => (
+            <RemovableDriveItem key={`drive-${drive.letter}`} drive={drive} />
+          )),
         ]}
       </Reorder.Group>
     </WithContextMenu>
@@ -187,5 +197,17 @@ function ItemByType(item: SwItem) {
     return <StartMenu key="start-menu" item={item} />;
   }
 
+  if (item.type === SwItemType.RecycleBin) {
+    return <RecycleBin key="recycle-bin" item={item} />;
+  }
+
+  if (
+    item.type === SwItemType.Separator ||
+    item.type === SwItemType.Spacer ||
+    item.type === SwItemType.Label
+  ) {
+    return <LayoutItem key={item.id} item={item} />;
+  }
+
   return null;
 }
@@ -8,7 +8,13 @@ import { BackgroundByLayersV2 } from '../../components/BackgroundByLayers/infra'
 import { store } from '../shared/store/infra';
 import { dialog } from 'src/apps/settings/modules/shared/tauri/infra';
 
-import { isPinnedApp, isTemporalApp, RootActions } from '../shared/store/app';
+import {
+  isPinnedApp,
+  isTemporalApp,
+  isWindowElsewhere,
+  OwnMonitor,
+  RootActions,
+} from '../shared/store/app';
 
 import { AppsSides, ExtendedPinnedWegItem, ExtendedTemporalWegItem } from '../shared/store/domain';
 
@@ -30,6 +36,13 @@ export function getSeelenWegMenu(t: TFunction): ItemType[] {
         store.dispatch(RootActions.addStartModule());
       },
     },
+    {
+      key: 'add-recycle-bin-module',
+      label: t('taskbar_menu.recycle_bin'),
+      onClick() {
+        store.dispatch(RootActions.addRecycleBinModule());
+      },
+    },
     {
       type: 'divider',
     },
@@ -153,12 +166,32 @@ export function getMenuForItem(
     {
       key: 'weg_runas',
       label: t('app_menu.run_as'),
-      onClick: () => invoke(SeelenCommand.RunAsAdmin, { path: item.execution_command }),
+      onClick: () =>
+        invoke(SeelenCommand.WegLaunchItem, { item: { ...item, run_as_admin: true } }),
     },
   );
 
   if (item.opens.length) {
+    const state = store.getState();
+    const onTop = item.opens.find((hwnd) => state.alwaysOnTop.includes(hwnd));
+
+    if (item.opens.some((hwnd) => isWindowElsewhere(state, hwnd))) {
+      menu.push({
+        key: 'weg_gather_windows',
+        label: t('app_menu.gather_windows'),
+        onClick: () =>
+          invoke(SeelenCommand.WegGatherWindows, { handles: item.opens, monitor: OwnMonitor }),
+      });
+    }
+
     menu.push(
+      {
+        key: 'weg_always_on_top',
+        label: onTop ? t('app_menu.disable_always_on_top') : t('app_menu.always_on_top'),
+        onClick() {
+          invoke(SeelenCommand.ToggleAlwaysOnTop, { hwnd: onTop || item.opens[0] });
+        },
+      },
       {
         key: 'weg_copy_hwnd',
         label: t('app_menu.copy_handles'),
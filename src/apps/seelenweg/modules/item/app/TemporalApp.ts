@@ -22,6 +22,11 @@ export class SwTemporalAppUtils {
       path: item.exe,
       execution_command: item.execution_path,
       is_dir: false,
+      orphaned: false,
+      arguments: null,
+      working_dir: null,
+      run_as_admin: false,
+      new_instance: false,
       title: item.exe.split('\\').at(-1) || 'Unknown',
       opens: [item.hwnd],
     };
@@ -1,13 +1,20 @@
 import { Reorder } from 'framer-motion';
-import { PropsWithChildren, useRef } from 'react';
+import { CSSProperties, PropsWithChildren, useRef } from 'react';
+
+import { saveItemPosition } from '../../shared/store/storeApi';
+
+import { cx } from '../../../../shared/styles';
 
 import { SwItem } from '../../shared/store/domain';
 
 interface Props extends PropsWithChildren {
   item: SwItem;
+  /** hidden by the tag filter, but kept on the list so the order is not lost */
+  hidden?: boolean;
+  style?: CSSProperties;
 }
 
-export function DraggableItem({ children, item }: Props) {
+export function DraggableItem({ children, item, hidden, style }: Props) {
   const ref = useRef<HTMLDivElement>(null);
 
   return (
@@ -16,11 +23,13 @@ export function DraggableItem({ children, item }: Props) {
       ref={ref}
       value={item}
       drag
-      className="weg-item-drag-container"
+      style={style}
+      className={cx('weg-item-drag-container', { 'weg-item-drag-container-hidden': hidden })}
       onDragStart={() => {
         ref.current?.classList.add('dragging');
       }}
       onDragEnd={() => {
+        saveItemPosition(item);
         setTimeout(() => {
           ref.current?.classList.remove('dragging');
         }, 150);
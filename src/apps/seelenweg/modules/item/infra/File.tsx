@@ -10,6 +10,7 @@ import InlineSVG from 'src/apps/seelenweg/components/InlineSvg';
 
 import { ExtendedPinnedWegItem } from '../../shared/store/domain';
 
+import { cx } from '../../../../shared/styles';
 import { WithContextMenu } from '../../../components/WithContextMenu';
 import { DraggableItem } from './DraggableItem';
 import { getMenuForItem } from './Menu';
@@ -37,9 +38,9 @@ export const FileOrFolder = memo(({ item }: Props) => {
     <DraggableItem item={item}>
       <WithContextMenu items={getMenuForItem(t, item)}>
         <div
-          className="weg-item"
+          className={cx('weg-item', { 'weg-item-orphaned': item.orphaned })}
           onClick={() => {
-            invoke(SeelenCommand.OpenFile, { path: item.path });
+            invoke(SeelenCommand.WegLaunchItem, { item });
           }}
           onContextMenu={(e) => e.stopPropagation()}
         >
@@ -0,0 +1,55 @@
+import { memo } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useSelector } from 'react-redux';
+import {
+  LabelWegItem,
+  SeelenWegSide,
+  SeparatorWegItem,
+  SpacerWegItem,
+  SwItemType,
+} from 'seelen-core';
+
+import { Selectors } from '../../shared/store/app';
+
+import { WithContextMenu } from '../../../components/WithContextMenu';
+import { DraggableItem } from './DraggableItem';
+import { getMenuForItem } from './Menu';
+
+interface Props {
+  item: SeparatorWegItem | SpacerWegItem | LabelWegItem;
+}
+
+/** Separators, spacers and labels, only used to group the items of the dock */
+export const LayoutItem = memo(({ item }: Props) => {
+  const settings = useSelector(Selectors.settings);
+
+  const { t } = useTranslation();
+
+  const isHorizontal =
+    settings.position === SeelenWegSide.Top || settings.position === SeelenWegSide.Bottom;
+
+  // the spacers grow like a flex item, the size is set on the drag container that is the flex child
+  const style =
+    item.type === SwItemType.Spacer
+      ? {
+          flexGrow: item.grow,
+          flexShrink: 0,
+          [isHorizontal ? 'minWidth' : 'minHeight']: `${item.min_size}px`,
+        }
+      : undefined;
+
+  return (
+    <DraggableItem item={item} style={style}>
+      <WithContextMenu items={getMenuForItem(t, item)}>
+        <div
+          className={`weg-layout-item weg-item-${item.type.toLowerCase()}`}
+          onContextMenu={(e) => e.stopPropagation()}
+        >
+          {item.type === SwItemType.Label && (
+            <span className="weg-item-label-text">{item.text}</span>
+          )}
+        </div>
+      </WithContextMenu>
+    </DraggableItem>
+  );
+});
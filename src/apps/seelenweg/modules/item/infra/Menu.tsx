@@ -36,6 +36,51 @@ export function getMenuForItem(t: TFunction, item: SwItem): ItemType[] {
     ];
   }
 
+  if (
+    item.type === SwItemType.Separator ||
+    item.type === SwItemType.Spacer ||
+    item.type === SwItemType.Label
+  ) {
+    return [
+      {
+        key: 'remove',
+        label: t('layout_menu.remove'),
+        onClick() {
+          store.dispatch(RootActions.removeLayoutItem(item.id));
+        },
+      },
+    ];
+  }
+
+  if (item.type === SwItemType.RecycleBin) {
+    return [
+      {
+        key: 'open',
+        label: t('recycle_bin_menu.open'),
+        onClick() {
+          invoke(SeelenCommand.OpenRecycleBin).catch(console.error);
+        },
+      },
+      {
+        key: 'empty',
+        label: t('recycle_bin_menu.empty'),
+        onClick() {
+          invoke(SeelenCommand.EmptyRecycleBin).catch(console.error);
+        },
+      },
+      {
+        type: 'divider',
+      },
+      {
+        key: 'remove',
+        label: t('recycle_bin_menu.remove'),
+        onClick() {
+          store.dispatch(RootActions.removeRecycleBinModule());
+        },
+      },
+    ];
+  }
+
   if (item.type === SwItemType.Pinned) {
     return [
       {
@@ -0,0 +1,58 @@
+import { invoke } from '@tauri-apps/api/core';
+import { Tooltip } from 'antd';
+import { memo } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useSelector } from 'react-redux';
+import { RecycleBinWegItem, SeelenCommand } from 'seelen-core';
+
+import { BackgroundByLayersV2 } from '../../../components/BackgroundByLayers/infra';
+
+import { Selectors } from '../../shared/store/app';
+
+import { Icon } from '../../../../shared/components/Icon';
+import { WithContextMenu } from '../../../components/WithContextMenu';
+import { DraggableItem } from './DraggableItem';
+import { getMenuForItem } from './Menu';
+import { formatBytes } from './RemovableDrive';
+
+interface Props {
+  item: RecycleBinWegItem;
+}
+
+export const RecycleBin = memo(({ item }: Props) => {
+  const recycleBin = useSelector(Selectors.recycleBin);
+
+  const { t } = useTranslation();
+
+  const isEmpty = recycleBin.items === 0;
+
+  return (
+    <DraggableItem item={item}>
+      <WithContextMenu items={getMenuForItem(t, item)}>
+        <Tooltip
+          title={
+            isEmpty
+              ? t('recycle_bin_menu.empty_tooltip')
+              : t('recycle_bin_menu.items', {
+                  count: recycleBin.items,
+                  size: formatBytes(recycleBin.size),
+                })
+          }
+          placement="top"
+          showArrow={false}
+        >
+          <div
+            className="weg-item weg-item-recycle-bin"
+            data-empty={isEmpty}
+            onClick={() => invoke(SeelenCommand.OpenRecycleBin).catch(console.error)}
+            onContextMenu={(e) => e.stopPropagation()}
+          >
+            <BackgroundByLayersV2 prefix="item" />
+            <Icon iconName={isEmpty ? 'MdDeleteOutline' : 'MdDelete'} className="weg-item-icon" />
+            {!isEmpty && <div className="weg-item-recycle-bin-fill" />}
+          </div>
+        </Tooltip>
+      </WithContextMenu>
+    </DraggableItem>
+  );
+});
@@ -0,0 +1,87 @@
+import { invoke } from '@tauri-apps/api/core';
+import { Tooltip } from 'antd';
+import { ItemType } from 'antd/es/menu/interface';
+import { memo } from 'react';
+import { useTranslation } from 'react-i18next';
+import { RemovableDrive, RemovableDriveKind, SeelenCommand } from 'seelen-core';
+
+import { BackgroundByLayersV2 } from '../../../components/BackgroundByLayers/infra';
+
+import { Icon } from '../../../../shared/components/Icon';
+import { WithContextMenu } from '../../../components/WithContextMenu';
+
+interface Props {
+  drive: RemovableDrive;
+}
+
+const IconByKind: Record<RemovableDriveKind, string> = {
+  [RemovableDriveKind.Usb]: 'MdUsb',
+  [RemovableDriveKind.Card]: 'MdSdCard',
+  [RemovableDriveKind.Optical]: 'MdAlbum',
+};
+
+export function formatBytes(bytes: number) {
+  const units = ['B', 'KB', 'MB', 'GB', 'TB'];
+  let idx = 0;
+  while (bytes >= 1024 && idx < units.length - 1) {
+    bytes /= 1024;
+    idx++;
+  }
+  return `${bytes.toFixed(idx > 1 ? 1 : 0)} ${units[idx]}`;
+}
+
+export const RemovableDriveItem = memo(({ drive }: Props) => {
+  const { t } = useTranslation();
+
+  const root = `${drive.letter}:\\`;
+  const name = drive.label ? `${drive.label} (${drive.letter}:)` : `${drive.letter}:`;
+  const used = drive.total > 0 ? (drive.total - drive.free) / drive.total : 0;
+
+  const menu: ItemType[] = [
+    {
+      key: 'open',
+      label: t('drive_menu.open'),
+      onClick: () => invoke(SeelenCommand.OpenFile, { path: root }),
+    },
+    {
+      key: 'eject',
+      label: t('drive_menu.eject'),
+      onClick: () => {
+        invoke(SeelenCommand.RemovableDriveEject, { letter: drive.letter }).catch(console.error);
+      },
+    },
+    {
+      key: 'pin_to_toolbar',
+      label: t('drive_menu.pin_to_toolbar'),
+      onClick: () => {
+        invoke(SeelenCommand.StatePinToolbarItem, {
+          source: { kind: 'RemovableDrive', letter: drive.letter },
+        }).catch(console.error);
+      },
+    },
+  ];
+
+  return (
+    <div className="weg-item-drag-container">
+      <WithContextMenu items={menu}>
+        <Tooltip
+          title={`${name} - ${t('drive_menu.free', { free: formatBytes(drive.free) })}`}
+          placement="top"
+          showArrow={false}
+        >
+          <div
+            className="weg-item weg-item-drive"
+            onClick={() => invoke(SeelenCommand.OpenFile, { path: root })}
+            onContextMenu={(e) => e.stopPropagation()}
+          >
+            <BackgroundByLayersV2 prefix="item" />
+            <Icon iconName={IconByKind[drive.kind]} className="weg-item-icon" />
+            <div className="weg-item-drive-usage">
+              <div className="weg-item-drive-usage-fill" style={{ width: `${used * 100}%` }} />
+            </div>
+          </div>
+        </Tooltip>
+      </WithContextMenu>
+    </div>
+  );
+});
@@ -1,14 +1,14 @@
 import { invoke } from '@tauri-apps/api/core';
 import { Popover } from 'antd';
-import { memo, useEffect, useState } from 'react';
+import { memo, useEffect, useRef, useState } from 'react';
 import { useTranslation } from 'react-i18next';
 import { useSelector } from 'react-redux';
 import { SeelenCommand, SeelenWegSide, useWindowFocusChange } from 'seelen-core';
 
 import { BackgroundByLayersV2 } from '../../../components/BackgroundByLayers/infra';
-import { updatePreviews } from '../../shared/utils/infra';
+import { removeMinimizeTarget, setMinimizeTarget, updatePreviews } from '../../shared/utils/infra';
 
-import { Selectors } from '../../shared/store/app';
+import { isWindowElsewhere, Selectors } from '../../shared/store/app';
 
 import {
   ExtendedPinnedWegItem,
@@ -30,10 +30,26 @@ export const UserApplication = memo(({ item }: Props) => {
   const isFocused = useSelector(
     (state: RootState) => state.focusedApp && item.opens.includes(state.focusedApp.hwnd),
   );
+  const isAlwaysOnTop = useSelector((state: RootState) =>
+    item.opens.some((hwnd) => state.alwaysOnTop.includes(hwnd)),
+  );
+  const isUrgent = useSelector((state: RootState) =>
+    item.opens.some((hwnd) => state.urgent.includes(hwnd)),
+  );
+  const isElsewhere = useSelector(
+    (state: RootState) =>
+      !!item.opens.length && item.opens.every((hwnd) => isWindowElsewhere(state, hwnd)),
+  );
+  const isFilteredOut = useSelector((state: RootState) => {
+    const tag = state.tagFilter;
+    return !!tag && !item.opens.some((hwnd) => state.openApps[hwnd]?.tags?.includes(tag));
+  });
 
   const [openPreview, setOpenPreview] = useState(false);
   const settings = useSelector(Selectors.settings);
 
+  const ref = useRef<HTMLDivElement>(null);
+
   const { t } = useTranslation();
   const calculatePlacement = (position: any) => {
     switch (position) {
@@ -73,8 +89,17 @@ export const UserApplication = memo(({ item }: Props) => {
     }
   }, [item]);
 
+  useEffect(() => {
+    const element = ref.current;
+    if (!element) {
+      return;
+    }
+    setMinimizeTarget(element, item.opens);
+    return () => removeMinimizeTarget(element);
+  }, [item.opens]);
+
   return (
-    <DraggableItem item={item}>
+    <DraggableItem item={item} hidden={isFilteredOut}>
       <WithContextMenu items={getMenuForItem(t, item) || []}>
         <Popover
           open={openPreview}
@@ -96,15 +121,12 @@ export const UserApplication = memo(({ item }: Props) => {
           }
         >
           <div
-            className="weg-item"
+            ref={ref}
+            className={cx('weg-item', { 'weg-item-orphaned': item.orphaned })}
             onClick={() => {
               let hwnd = item.opens[0];
-              if (!hwnd) {
-                if (item.path.endsWith('.lnk')) {
-                  invoke(SeelenCommand.OpenFile, { path: item.path });
-                } else {
-                  invoke(SeelenCommand.OpenFile, { path: item.execution_command });
-                }
+              if (!hwnd || item.new_instance) {
+                invoke(SeelenCommand.WegLaunchItem, { item });
               } else {
                 invoke(SeelenCommand.WegToggleWindowState, { hwnd });
               }
@@ -119,10 +141,13 @@ export const UserApplication = memo(({ item }: Props) => {
           >
             <BackgroundByLayersV2 prefix="item" />
             <img className="weg-item-icon" src={item.icon} draggable={false} />
+            {isAlwaysOnTop && <div className="weg-item-always-on-top" />}
             <div
               className={cx('weg-item-open-sign', {
                 'weg-item-open-sign-active': !!item.opens.length,
                 'weg-item-open-sign-focused': isFocused,
+                'weg-item-open-sign-urgent': isUrgent && !isFocused,
+                'weg-item-open-sign-elsewhere': isElsewhere && !isFocused,
               })}
             />
           </div>
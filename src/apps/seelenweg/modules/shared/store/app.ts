@@ -1,4 +1,5 @@
 import { createSlice, current, PayloadAction } from '@reduxjs/toolkit';
+import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
 import { PinnedWegItem, SeelenWegSettings, SwItemType, UIColors } from 'seelen-core';
 
 import { SwTemporalAppUtils } from '../../item/app/TemporalApp';
@@ -22,9 +23,18 @@ const initialState: RootState = {
   itemsOnRight: [],
   openApps: {},
   focusedApp: null,
+  alwaysOnTop: [],
+  urgent: [],
+  tagFilter: null,
+  activeWorkspace: null,
   isOverlaped: false,
   settings: new SeelenWegSettings(),
   mediaSessions: [],
+  removableDrives: [],
+  recycleBin: {
+    items: 0,
+    size: 0,
+  },
   colors: UIColors.default(),
 };
 
@@ -142,6 +152,30 @@ export const RootSlice = createSlice({
       state.itemsOnRight = state.itemsOnRight.filter(filter);
       savePinnedItems(current(state));
     },
+    addRecycleBinModule(state) {
+      const all = [...state.itemsOnLeft, ...state.itemsOnCenter, ...state.itemsOnRight];
+      if (!all.some((current) => current.type === SwItemType.RecycleBin)) {
+        state.itemsOnRight.push({
+          type: SwItemType.RecycleBin,
+        });
+      }
+      savePinnedItems(current(state));
+    },
+    removeRecycleBinModule(state) {
+      const filter = (current: SwItem) => current.type !== SwItemType.RecycleBin;
+      state.itemsOnLeft = state.itemsOnLeft.filter(filter);
+      state.itemsOnCenter = state.itemsOnCenter.filter(filter);
+      state.itemsOnRight = state.itemsOnRight.filter(filter);
+      savePinnedItems(current(state));
+    },
+    /** Removes a separator, spacer or label */
+    removeLayoutItem(state, action: PayloadAction<string>) {
+      const filter = (current: SwItem) => !('id' in current) || current.id !== action.payload;
+      state.itemsOnLeft = state.itemsOnLeft.filter(filter);
+      state.itemsOnCenter = state.itemsOnCenter.filter(filter);
+      state.itemsOnRight = state.itemsOnRight.filter(filter);
+      savePinnedItems(current(state));
+    },
     addOpenApp(state, action: PayloadAction<AppFromBackground>) {
       const new_app = action.payload;
 
@@ -183,6 +217,7 @@ export const RootSlice = createSlice({
       const found = state.openApps[action.payload.hwnd];
       if (found) {
         found.title = action.payload.title;
+        found.tags = action.payload.tags;
       }
     },
     removeOpenApp(state, action: PayloadAction<HWND>) {
@@ -206,6 +241,21 @@ export const RootActions = RootSlice.actions;
 export const Selectors = StateBuilder.compositeSelector(initialState);
 export const SelectOpenApp = (hwnd: HWND) => (state: RootState) => state.openApps[hwnd];
 
+/** id of the monitor of this dock, the label of the view is `seelenweg/<monitor id>` */
+export const OwnMonitor = getCurrentWebviewWindow().label.replace(/^seelenweg\//, '');
+
+/** the window lives on other monitor or workspace than this dock */
+export const isWindowElsewhere = (state: RootState, hwnd: HWND) => {
+  const app = state.openApps[hwnd];
+  if (!app) {
+    return false;
+  }
+  const otherMonitor = !!app.monitor && app.monitor !== OwnMonitor;
+  const otherWorkspace =
+    !!app.workspace && !!state.activeWorkspace && app.workspace !== state.activeWorkspace;
+  return otherMonitor || otherWorkspace;
+};
+
 export const isPinnedApp = (item: SwItem): item is ExtendedPinnedWegItem => {
   return item.type === SwItemType.Pinned;
 };
@@ -1,9 +1,14 @@
 import { modify } from 'readable-types';
 import {
+  LabelWegItem,
   MediaWegItem,
   PinnedWegItem,
+  RecycleBinState,
+  RecycleBinWegItem,
+  RemovableDrive,
   SeelenWegSettings,
   SeparatorWegItem,
+  SpacerWegItem,
   StartWegItem,
   SwItemType,
 } from 'seelen-core';
@@ -21,6 +26,12 @@ export interface AppFromBackground {
   icon_path: string;
   hwnd: HWND;
   creator_hwnd: HWND;
+  /** tags of the app, set by the user */
+  tags: string[];
+  /** id of the monitor where the window is */
+  monitor: string | null;
+  /** id of the workspace of the window */
+  workspace: string | null;
 }
 
 export enum AppsSides {
@@ -63,8 +74,11 @@ export type SwItem =
   | ExtendedPinnedWegItem
   | ExtendedTemporalWegItem
   | SeparatorWegItem
+  | SpacerWegItem
+  | LabelWegItem
   | MediaWegItem
-  | StartWegItem;
+  | StartWegItem
+  | RecycleBinWegItem;
 
 export interface RootState extends IRootState<SeelenWegSettings> {
   itemsOnLeft: SwItem[];
@@ -73,6 +87,15 @@ export interface RootState extends IRootState<SeelenWegSettings> {
   openApps: Record<HWND, AppFromBackground>;
   // ----------------------
   focusedApp: FocusedApp | null;
+  alwaysOnTop: HWND[];
+  /** windows requesting attention */
+  urgent: HWND[];
+  /** only the apps with a window with this tag are shown */
+  tagFilter: string | null;
+  /** null until the first workspace switch */
+  activeWorkspace: string | null;
   isOverlaped: boolean;
   mediaSessions: MediaSession[];
+  removableDrives: RemovableDrive[];
+  recycleBin: RecycleBinState;
 }
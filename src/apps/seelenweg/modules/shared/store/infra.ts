@@ -1,8 +1,22 @@
 import { configureStore } from '@reduxjs/toolkit';
+import { invoke } from '@tauri-apps/api/core';
 import { listen as listenGlobal } from '@tauri-apps/api/event';
 import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
 import { debounce } from 'lodash';
-import { SeelenEvent, SeelenWegSettings, SeelenWegSide, SwItemType, UIColors, WegItem } from 'seelen-core';
+import {
+  listenScheduled,
+  RecycleBinState,
+  RemovableDrive,
+  SeelenCommand,
+  SeelenEvent,
+  SeelenWegSettings,
+  SeelenWegSide,
+  SwItemType,
+  UIColors,
+  UrgentWindow,
+  WegItem,
+  WindowTagsState,
+} from 'seelen-core';
 
 import { SwPinnedAppUtils } from '../../item/app/PinnedApp';
 import { SwTemporalAppUtils } from '../../item/app/TemporalApp';
@@ -88,17 +102,51 @@ export async function registerStoreEvents() {
   const onFocusChanged = debounce((app: FocusedApp) => {
     store.dispatch(RootActions.setFocusedApp(app));
   }, 200);
-  await view.listen<FocusedApp>(SeelenEvent.GlobalFocusChanged, (e) => {
+  await listenScheduled<FocusedApp>(SeelenEvent.GlobalFocusChanged, (e) => {
     onFocusChanged(e.payload);
     if (e.payload.name != 'Seelen UI') {
       onFocusChanged.flush();
     }
   });
 
+  const alwaysOnTop = await invoke<HWND[]>(SeelenCommand.GetAlwaysOnTopWindows);
+  store.dispatch(RootActions.setAlwaysOnTop(alwaysOnTop));
+  await listenGlobal<HWND[]>(SeelenEvent.GlobalAlwaysOnTopChanged, (event) => {
+    store.dispatch(RootActions.setAlwaysOnTop(event.payload));
+  });
+
+  const urgent = await invoke<UrgentWindow[]>(SeelenCommand.GetUrgentWindows);
+  store.dispatch(RootActions.setUrgent(urgent.map((w) => w.hwnd)));
+  await listenGlobal<UrgentWindow[]>(SeelenEvent.GlobalUrgentChanged, (event) => {
+    store.dispatch(RootActions.setUrgent(event.payload.map((w) => w.hwnd)));
+  });
+
+  const tags = await invoke<WindowTagsState>(SeelenCommand.WindowTagsGetState);
+  store.dispatch(RootActions.setTagFilter(tags.filter));
+  await listenGlobal<WindowTagsState>(SeelenEvent.WindowTagsChanged, (event) => {
+    store.dispatch(RootActions.setTagFilter(event.payload.filter));
+  });
+
+  await listenGlobal<string>(SeelenEvent.ActiveWorkspaceChanged, (event) => {
+    store.dispatch(RootActions.setActiveWorkspace(event.payload));
+  });
+
   await listenGlobal<MediaSession[]>('media-sessions', (event) => {
     store.dispatch(RootActions.setMediaSessions(event.payload));
   });
 
+  store.dispatch(
+    RootActions.setRemovableDrives(await invoke(SeelenCommand.RemovableDrivesGetAll)),
+  );
+  await listenGlobal<RemovableDrive[]>(SeelenEvent.RemovableDrivesChanged, (event) => {
+    store.dispatch(RootActions.setRemovableDrives(event.payload));
+  });
+
+  store.dispatch(RootActions.setRecycleBin(await invoke(SeelenCommand.GetRecycleBinState)));
+  await listenGlobal<RecycleBinState>(SeelenEvent.RecycleBinChanged, (event) => {
+    store.dispatch(RootActions.setRecycleBin(event.payload));
+  });
+
   await initUIColors();
 
   await listenGlobal<unknown>(SeelenEvent.StateWegItemsChanged, async () => {
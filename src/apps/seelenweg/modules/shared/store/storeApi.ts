@@ -3,7 +3,7 @@ import { invoke } from '@tauri-apps/api/core';
 import { writeTextFile } from '@tauri-apps/plugin-fs';
 import yaml from 'js-yaml';
 import { debounce } from 'lodash';
-import { SwItemType, WegItem, WegItems } from 'seelen-core';
+import { SeelenCommand, SwItemType, WegItem, WegItems, WegItemsSide } from 'seelen-core';
 
 import { store } from './infra';
 
@@ -25,6 +25,11 @@ export const savePinnedItems = debounce(
             path: item.path,
             execution_command: item.execution_command,
             is_dir: item.is_dir,
+            orphaned: item.orphaned,
+            arguments: item.arguments,
+            working_dir: item.working_dir,
+            run_as_admin: item.run_as_admin,
+            new_instance: item.new_instance,
           });
           break;
         default:
@@ -47,6 +52,50 @@ export const savePinnedItems = debounce(
   1000,
 );
 
+function getItemId(item: SwItem): string {
+  switch (item.type) {
+    case SwItemType.Pinned:
+    case SwItemType.TemporalApp:
+      return item.execution_command;
+    case SwItemType.Separator:
+    case SwItemType.Spacer:
+    case SwItemType.Label:
+      return item.id;
+    default:
+      return item.type;
+  }
+}
+
+/** Persists the current position of the item, the reorder is already applied on the local state */
+export async function saveItemPosition(item: SwItem, state: RootState = store.getState()) {
+  // temporal apps are not saved
+  if (item.type === SwItemType.TemporalApp) {
+    return;
+  }
+
+  const id = getItemId(item);
+  const sides: [WegItemsSide, SwItem[]][] = [
+    [WegItemsSide.Left, state.itemsOnLeft],
+    [WegItemsSide.Center, state.itemsOnCenter],
+    [WegItemsSide.Right, state.itemsOnRight],
+  ];
+
+  for (const [side, items] of sides) {
+    const saved = items.filter((current) => current.type !== SwItemType.TemporalApp);
+    const index = saved.findIndex((current) => getItemId(current) === id);
+    if (index !== -1) {
+      IsSavingPinnedItems.current = true;
+      try {
+        await invoke(SeelenCommand.WegMoveItem, { id, side, index });
+      } catch (error) {
+        IsSavingPinnedItems.current = false;
+        console.error(error);
+      }
+      return;
+    }
+  }
+}
+
 export const loadPinnedItems = async (): Promise<WegItems> => {
   return invoke<WegItems>('state_get_weg_items');
 };
@@ -1,6 +1,8 @@
 import { path } from '@tauri-apps/api';
 import { invoke } from '@tauri-apps/api/core';
-import { SeelenCommand } from 'seelen-core';
+import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
+import { debounce } from 'lodash';
+import { Rect, SeelenCommand } from 'seelen-core';
 
 import { getGeneratedFilesPath } from './app';
 
@@ -38,6 +40,42 @@ export async function updatePreviews(hwnds: HWND[]) {
   invoke(SeelenCommand.WegRequestUpdatePreviews, { handles: hwnds });
 }
 
+/** dock items of the open apps, used as target of the minimize animations */
+const minimizeTargets = new Map<HTMLElement, HWND[]>();
+
+export function setMinimizeTarget(element: HTMLElement, hwnds: HWND[]) {
+  minimizeTargets.set(element, hwnds);
+  updateMinimizeTargets();
+}
+
+export function removeMinimizeTarget(element: HTMLElement) {
+  minimizeTargets.delete(element);
+  updateMinimizeTargets();
+}
+
+// debounced to wait for the reorder and auto hide animations
+export const updateMinimizeTargets = debounce(async () => {
+  const view = getCurrentWebviewWindow();
+  const { x, y } = await view.outerPosition();
+  const scale = await view.scaleFactor();
+  const toPhysical = (size: number) => Math.round(size * scale);
+
+  const targets: Record<HWND, Rect> = {};
+  minimizeTargets.forEach((hwnds, element) => {
+    const domRect = element.getBoundingClientRect();
+    const left = x + toPhysical(domRect.left);
+    const top = y + toPhysical(domRect.top);
+    const rect = {
+      left,
+      top,
+      right: left + toPhysical(domRect.width),
+      bottom: top + toPhysical(domRect.height),
+    };
+    hwnds.forEach((hwnd) => (targets[hwnd] = rect));
+  });
+  invoke(SeelenCommand.WegSetMinimizeTargets, { targets });
+}, 300);
+
 export async function iconPathFromExePath(exePath: string) {
   const parts = exePath.split('\\');
   const fileName = parts.at(-1)?.replace('.exe', '.png') || 'missing.png';
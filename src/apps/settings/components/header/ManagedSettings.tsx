@@ -0,0 +1,49 @@
+import { listen } from '@tauri-apps/api/event';
+import { Button, Popover } from 'antd';
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { invoke, SeelenCommand, SeelenEvent } from 'seelen-core';
+
+import { Icon } from 'src/apps/shared/components/Icon';
+
+/** Lists the settings locked by the administrators of the machine */
+export function ManagedSettings() {
+  const [locked, setLocked] = useState<string[]>([]);
+
+  const { t } = useTranslation();
+
+  useEffect(() => {
+    invoke(SeelenCommand.StateGetLockedSettings).then((pointers) => setLocked(pointers as string[]));
+    const unlisten = listen<string[]>(SeelenEvent.StateLockedSettingsChanged, (e) =>
+      setLocked(e.payload),
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  if (!locked.length) {
+    return null;
+  }
+
+  const content = (
+    <div>
+      <p>{t('managed.message')}</p>
+      <ul style={{ margin: '8px 0 0', paddingLeft: 18, fontWeight: 400 }}>
+        {locked.map((pointer) => (
+          <li key={pointer}>
+            <code>{pointer.slice(1).split('/').join(' › ')}</code>
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+
+  return (
+    <Popover title={t('managed.title')} content={content} placement="bottomRight">
+      <Button type="text">
+        <Icon iconName="TbLock" />
+      </Button>
+    </Popover>
+  );
+}
@@ -0,0 +1,50 @@
+import { AutoComplete, Input } from 'antd';
+import { useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useDispatch } from 'react-redux';
+import { invoke, SeelenCommand, SettingsSearchResult } from 'seelen-core';
+
+import { RootActions } from '../../modules/shared/store/app/reducer';
+
+import { Route } from '../navigation/routes';
+
+export function SettingsSearch() {
+  const [results, setResults] = useState<SettingsSearchResult[]>([]);
+
+  const { t } = useTranslation();
+  const dispatch = useDispatch();
+
+  const onSearch = async (query: string) => {
+    const found = await invoke(SeelenCommand.SearchSettings, { query });
+    setResults(found as SettingsSearchResult[]);
+  };
+
+  const onSelect = (key: string) => {
+    const result = results.find((r) => r.key === key);
+    if (result) {
+      dispatch(RootActions.setRoute(result.route as Route));
+    }
+  };
+
+  const options = results.map((result) => ({
+    value: result.key,
+    label: (
+      <div title={result.description || undefined}>
+        <div>{result.label || result.key}</div>
+        <small>{t(`header.labels.${result.route}`)}</small>
+      </div>
+    ),
+  }));
+
+  return (
+    <AutoComplete
+      style={{ width: 240 }}
+      options={options}
+      onSearch={onSearch}
+      onSelect={onSelect}
+      popupMatchSelectWidth={320}
+    >
+      <Input.Search placeholder={t('header.search')} allowClear />
+    </AutoComplete>
+  );
+}
@@ -1,13 +1,23 @@
+import { listen } from '@tauri-apps/api/event';
 import { Badge, Button, Tooltip } from 'antd';
 import { useEffect, useState } from 'react';
 import { useTranslation } from 'react-i18next';
-import { invoke, SeelenCommand } from 'seelen-core';
+import { invoke, SeelenCommand, SeelenEvent } from 'seelen-core';
 
 import { Icon } from 'src/apps/shared/components/Icon';
 
+interface UpdaterStatus {
+  version: string | null;
+  downloading: boolean;
+  downloaded: number;
+  total: number | null;
+  ready: boolean;
+}
+
 export function UpdateButton() {
-  const [downloading, setDownloading] = useState<boolean>(false);
+  const [installing, setInstalling] = useState<boolean>(false);
   const [update, setUpdate] = useState<boolean>(false);
+  const [status, setStatus] = useState<UpdaterStatus | null>(null);
 
   const { t } = useTranslation();
 
@@ -15,26 +25,40 @@ export function UpdateButton() {
     invoke(SeelenCommand.CheckForUpdates)
       .then(setUpdate)
       .catch(() => setUpdate(false));
+    invoke(SeelenCommand.UpdaterGetStatus).then((s) => setStatus(s as UpdaterStatus));
+    const unlisten = listen<UpdaterStatus>(SeelenEvent.UpdaterStatus, (e) => setStatus(e.payload));
+    return () => {
+      unlisten.then((fn) => fn());
+    };
   }, []);
 
-  if (!update) {
+  if (!update && !status?.ready) {
     return null;
   }
 
+  const downloading = installing || !!status?.downloading;
+  let title = t('update.available');
+  if (status?.ready) {
+    title = t('update.ready');
+  } else if (status?.downloading) {
+    const percent = status.total ? Math.floor((status.downloaded / status.total) * 100) : 0;
+    title = `${t('update.downloading')} ${percent}%`;
+  }
+
   return (
-    <Tooltip title={downloading ? t('update.downloading') : t('update.available')}>
+    <Tooltip title={title}>
       <Button
         type="text"
         loading={downloading}
         onClick={() => {
           if (!downloading) {
-            setDownloading(true);
-            invoke(SeelenCommand.InstallLastAvailableUpdate).finally(() => setDownloading(false));
+            setInstalling(true);
+            invoke(SeelenCommand.InstallLastAvailableUpdate).finally(() => setInstalling(false));
           }
         }}
       >
         <Badge dot>
-          <Icon iconName="TbDownload" />
+          <Icon iconName={status?.ready ? 'TbRefresh' : 'TbDownload'} />
         </Badge>
       </Button>
     </Tooltip>
@@ -0,0 +1,73 @@
+import { listen } from '@tauri-apps/api/event';
+import { Modal } from 'antd';
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { invoke, SeelenCommand, SeelenEvent, VersionUpgrade } from 'seelen-core';
+
+/** Changelog of the versions installed since the last run */
+export function WhatsNew() {
+  const [upgrade, setUpgrade] = useState<VersionUpgrade | null>(null);
+
+  const { t } = useTranslation();
+
+  useEffect(() => {
+    invoke(SeelenCommand.GetVersionUpgrade).then((upgrade) =>
+      setUpgrade(upgrade as VersionUpgrade | null),
+    );
+    const unlisten = listen<VersionUpgrade>(SeelenEvent.VersionUpgraded, (e) =>
+      setUpgrade(e.payload),
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  if (!upgrade) {
+    return null;
+  }
+
+  function onClose() {
+    setUpgrade(null);
+    invoke(SeelenCommand.DismissVersionUpgrade);
+  }
+
+  return (
+    <Modal
+      open
+      centered
+      title={t('whats_new.title').replace('{{version}}', upgrade.current)}
+      onOk={onClose}
+      onCancel={onClose}
+      cancelButtonProps={{ style: { display: 'none' } }}
+      okText={t('whats_new.ok')}
+    >
+      <div style={{ maxHeight: '60vh', overflowY: 'auto' }}>
+        {upgrade.migrations.length > 0 && (
+          <>
+            <b>{t('whats_new.migrations')}</b>
+            <ul>
+              {upgrade.migrations.map((migration) => (
+                <li key={migration}>{migration}</li>
+              ))}
+            </ul>
+          </>
+        )}
+        {upgrade.notes.map((release) => (
+          <div key={release.version}>
+            <h3>{release.version}</h3>
+            {release.sections.map((section) => (
+              <div key={section.kind}>
+                <b>{section.kind}</b>
+                <ul>
+                  {section.entries.map((entry, idx) => (
+                    <li key={idx}>{entry}</li>
+                  ))}
+                </ul>
+              </div>
+            ))}
+          </div>
+        ))}
+      </div>
+    </Modal>
+  );
+}
@@ -11,7 +11,10 @@ import { RootActions } from '../../modules/shared/store/app/reducer';
 import { RootSelectors } from '../../modules/shared/store/app/selectors';
 
 import { RouteExtraInfo } from './ExtraInfo';
+import { ManagedSettings } from './ManagedSettings';
+import { SettingsSearch } from './SettingsSearch';
 import { UpdateButton } from './UpdateButton';
+import { WhatsNew } from './WhatsNew';
 import cs from './index.module.css';
 
 export const Header = () => {
@@ -48,7 +51,10 @@ export const Header = () => {
         {ExtraInfo && <ExtraInfo />}
       </div>
       <div className={cs.actions}>
+        <SettingsSearch />
+        <ManagedSettings />
         <UpdateButton />
+        <WhatsNew />
         <Button
           style={{ minWidth: 60 }}
           children={t('cancel')}
@@ -1,12 +1,13 @@
-import { Button, Modal, Switch } from 'antd';
+import { Button, InputNumber, Modal, Select, Switch } from 'antd';
 import { useState } from 'react';
 import { useTranslation } from 'react-i18next';
 import { useDispatch, useSelector } from 'react-redux';
-import { MonitorConfiguration, Rect } from 'seelen-core';
+import { MonitorConfiguration, NativeTaskbarMode, Rect } from 'seelen-core';
 
 import { WindowManagerSpacingSettings } from '../../WindowManager/main/infra/GlobalPaddings';
 
 import { newSelectors, RootActions } from '../../shared/store/app/reducer';
+import { OptionsFromEnum } from '../../shared/utils/app';
 import { Monitor } from 'src/apps/settings/components/monitor';
 import { SettingsGroup, SettingsOption } from 'src/apps/settings/components/SettingsBox';
 
@@ -148,6 +149,42 @@ export function MonitorConfig({
             <b>{t('weg.enable')}</b>
             <Switch value={m.weg.enabled} onChange={(v) => onToggle('weg', v)} />
           </SettingsOption>
+          <SettingsOption>
+            <b>{t('native_taskbar.mode')}</b>
+            <Select
+              style={{ width: '120px' }}
+              allowClear
+              placeholder={t('native_taskbar.inherit')}
+              value={m.taskbar.mode ?? undefined}
+              options={OptionsFromEnum(NativeTaskbarMode)}
+              onChange={(mode) => onChange({ ...m, taskbar: { mode: mode ?? null } })}
+            />
+          </SettingsOption>
+          <SettingsOption>
+            <b>{t('monitors_configurations.night_light')}</b>
+            <Switch
+              value={m.nightLight.enabled}
+              onChange={(enabled) => onChange({ ...m, nightLight: { ...m.nightLight, enabled } })}
+            />
+          </SettingsOption>
+          <SettingsOption>
+            <b>{t('monitors_configurations.night_light_temperature')}</b>
+            <InputNumber
+              style={{ width: '120px' }}
+              min={1200}
+              max={6500}
+              step={100}
+              placeholder={t('monitors_configurations.inherit')}
+              value={m.nightLight.temperature}
+              disabled={!m.nightLight.enabled}
+              onChange={(temperature) =>
+                onChange({
+                  ...m,
+                  nightLight: { ...m.nightLight, temperature: temperature ?? null },
+                })
+              }
+            />
+          </SettingsOption>
           {/* <SettingsOption>
             <b>{t('wall.enable')}</b>
             <Switch value={m.wall.enabled} onChange={(v) => onToggle('wall', v)} />
@@ -0,0 +1,33 @@
+import { invoke } from '@tauri-apps/api/core';
+import { Button, Modal } from 'antd';
+import { SeelenCommand, SettingsConflictResolution } from 'seelen-core';
+
+import i18n from '../../i18n';
+
+/** @param onResolved called after the resolution is applied to reload the settings */
+export const ShowSettingsConflict = (onResolved: () => void) => {
+  const resolve = async (resolution: SettingsConflictResolution) => {
+    modal.destroy();
+    await invoke(SeelenCommand.StateResolveSettingsConflict, { resolution });
+    onResolved();
+  };
+
+  const modal = Modal.confirm({
+    title: i18n.t('conflict.title'),
+    content: <p>{i18n.t('conflict.message')}</p>,
+    centered: true,
+    footer: (
+      <div style={{ display: 'flex', justifyContent: 'flex-end', gap: 8, marginTop: 12 }}>
+        <Button onClick={() => resolve(SettingsConflictResolution.TakeTheirs)}>
+          {i18n.t('conflict.take_theirs')}
+        </Button>
+        <Button onClick={() => resolve(SettingsConflictResolution.KeepMine)}>
+          {i18n.t('conflict.keep_mine')}
+        </Button>
+        <Button type="primary" onClick={() => resolve(SettingsConflictResolution.Merge)}>
+          {i18n.t('conflict.merge')}
+        </Button>
+      </div>
+    ),
+  });
+};
@@ -1,25 +1,38 @@
 import { convertFileSrc } from '@tauri-apps/api/core';
-import { Button, InputNumber, Switch } from 'antd';
+import { Button, Input, InputNumber, Select, Switch } from 'antd';
 import { Reorder } from 'framer-motion';
+import { useEffect, useState } from 'react';
 import { useTranslation } from 'react-i18next';
 import { useDispatch, useSelector } from 'react-redux';
-import { SeelenWallWallpaper } from 'seelen-core';
+import {
+  Note,
+  NoteList,
+  SeelenWallWallpaper,
+  SeelenWallWidget,
+  SeelenWallWidgetKind,
+  WallpaperSource,
+} from 'seelen-core';
 
 import { dialog } from '../shared/tauri/infra';
 
 import { newSelectors, RootActions } from '../shared/store/app/reducer';
 import { Icon } from 'src/apps/shared/components/Icon';
 
-import { SettingsGroup, SettingsOption } from '../../components/SettingsBox';
+import { SettingsGroup, SettingsOption, SettingsSubGroup } from '../../components/SettingsBox';
 import cs from './index.module.css';
 
 export function WallSettings() {
   const wall = useSelector(newSelectors.wall);
-  const { enabled, backgrounds, interval } = wall;
+  const { enabled, backgrounds, interval, widgets, source } = wall;
+  const [notes, setNotes] = useState<Note[]>([]);
 
   const dispatch = useDispatch();
   const { t } = useTranslation();
 
+  useEffect(() => {
+    NoteList.getAsync().then(setNotes);
+  }, []);
+
   function onChangeEnabled(value: boolean) {
     dispatch(RootActions.setWall({ ...wall, enabled: value }));
   }
@@ -56,6 +69,22 @@ export function WallSettings() {
     onChangeBackgrounds([...backgrounds, ...newBackgrounds]);
   }
 
+  function onChangeWidgets(widgets: SeelenWallWidget[]) {
+    dispatch(RootActions.setWall({ ...wall, widgets }));
+  }
+
+  function onChangeWidget(idx: number, changes: Partial<SeelenWallWidget>) {
+    let newWidgets = [...widgets];
+    newWidgets[idx] = { ...newWidgets[idx]!, ...changes };
+    onChangeWidgets(newWidgets);
+  }
+
+  function onRemoveWidget(idx: number) {
+    let newWidgets = [...widgets];
+    newWidgets.splice(idx, 1);
+    onChangeWidgets(newWidgets);
+  }
+
   function onRemoveBackground(idx: number) {
     let newBackgrounds = [...backgrounds];
     newBackgrounds.splice(idx, 1);
@@ -69,9 +98,25 @@ export function WallSettings() {
           <b>{t('wall.enable')}</b>
           <Switch value={enabled} onChange={onChangeEnabled} />
         </SettingsOption>
+        <SettingsOption>
+          <b>{t('wall.source.label')}</b>
+          <Select
+            value={source}
+            options={Object.values(WallpaperSource).map((value) => ({
+              label: t(`wall.source.${value}`),
+              value,
+            }))}
+            onChange={(source) => dispatch(RootActions.setWall({ ...wall, source }))}
+          />
+        </SettingsOption>
         <SettingsOption>
           <b>{t('wall.interval')}</b>
-          <InputNumber value={interval} onChange={onChangeInterval} min={1} />
+          <InputNumber
+            value={interval}
+            onChange={onChangeInterval}
+            min={1}
+            disabled={source !== WallpaperSource.Files}
+          />
         </SettingsOption>
       </SettingsGroup>
 
@@ -112,6 +157,79 @@ export function WallSettings() {
           </Button>
         </SettingsOption>
       </SettingsGroup>
+
+      <SettingsGroup>
+        <b>{t('wall.widgets.label')}</b>
+        <p>{t('wall.widgets.description')}</p>
+        {widgets.map((widget, idx) => (
+          <SettingsSubGroup
+            key={widget.id}
+            label={
+              <SettingsOption>
+                <Select
+                  style={{ width: '120px' }}
+                  value={widget.kind}
+                  options={Object.values(SeelenWallWidgetKind).map((kind) => ({
+                    label: t(`wall.widgets.kinds.${kind}`),
+                    value: kind,
+                  }))}
+                  onSelect={(kind) => onChangeWidget(idx, { kind })}
+                />
+                <Button type="primary" onClick={() => onRemoveWidget(idx)}>
+                  <Icon iconName="IoTrash" size={14} />
+                </Button>
+              </SettingsOption>
+            }
+          >
+            <SettingsOption>
+              <span>{t('wall.widgets.position')}</span>
+              <div>
+                <InputNumber
+                  value={widget.x}
+                  prefix="X"
+                  onChange={(x) => onChangeWidget(idx, { x: x || 0 })}
+                />
+                <InputNumber
+                  value={widget.y}
+                  prefix="Y"
+                  onChange={(y) => onChangeWidget(idx, { y: y || 0 })}
+                />
+              </div>
+            </SettingsOption>
+            {widget.kind === SeelenWallWidgetKind.Notes && (
+              <Input.TextArea
+                value={widget.text}
+                placeholder={t('wall.widgets.notes_placeholder')}
+                autoSize={{ minRows: 2 }}
+                onChange={(e) => onChangeWidget(idx, { text: e.target.value })}
+              />
+            )}
+            {widget.kind === SeelenWallWidgetKind.StickyNote && (
+              <SettingsOption>
+                <span>{t('wall.widgets.note')}</span>
+                <Select
+                  style={{ width: '200px' }}
+                  value={widget.noteId}
+                  placeholder={t('wall.widgets.note_placeholder')}
+                  allowClear
+                  options={notes.map((note) => ({ label: note.title || note.id, value: note.id }))}
+                  onChange={(noteId) => onChangeWidget(idx, { noteId: noteId ?? null })}
+                />
+              </SettingsOption>
+            )}
+          </SettingsSubGroup>
+        ))}
+        <SettingsOption>
+          <span />
+          <Button
+            type="primary"
+            className={cs.backgroundAdd}
+            onClick={() => onChangeWidgets([...widgets, new SeelenWallWidget()])}
+          >
+            <Icon iconName="MdLibraryAdd" size={14} />
+          </Button>
+        </SettingsOption>
+      </SettingsGroup>
     </>
   );
 }
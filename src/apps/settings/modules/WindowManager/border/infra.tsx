@@ -1,4 +1,4 @@
-import { InputNumber, Switch } from 'antd';
+import { ColorPicker, InputNumber, Switch } from 'antd';
 import { useTranslation } from 'react-i18next';
 
 import { useAppDispatch, useAppSelector, useDispatchCallback } from '../../shared/utils/infra';
@@ -12,6 +12,9 @@ export const BorderSettings = () => {
   const enabled = useAppSelector(BorderSelectors.enabled);
   const offset = useAppSelector(BorderSelectors.offset);
   const width = useAppSelector(BorderSelectors.width);
+  const color = useAppSelector(BorderSelectors.color);
+  const radius = useAppSelector(BorderSelectors.radius);
+  const animationDuration = useAppSelector(BorderSelectors.animationDuration);
 
   const dispatch = useAppDispatch();
   const { t } = useTranslation();
@@ -28,6 +31,18 @@ export const BorderSettings = () => {
     dispatch(BorderActions.setWidth(value || 0));
   });
 
+  const updateColor = useDispatchCallback((value: string | null) => {
+    dispatch(BorderActions.setColor(value));
+  });
+
+  const updateRadius = useDispatchCallback((value: number | null) => {
+    dispatch(BorderActions.setRadius(value || 0));
+  });
+
+  const updateAnimationDuration = useDispatchCallback((value: number | null) => {
+    dispatch(BorderActions.setAnimationDuration(value || 0));
+  });
+
   return (
     <SettingsSubGroup
       label={
@@ -45,6 +60,29 @@ export const BorderSettings = () => {
         <span>{t('wm.border.width')}</span>
         <InputNumber value={width} onChange={updateWidth} />
       </SettingsOption>
+      <SettingsOption>
+        <span>{t('wm.border.color')}</span>
+        <ColorPicker
+          value={color}
+          allowClear
+          showText
+          onChangeComplete={(value) => updateColor(value.toHexString())}
+          onClear={() => updateColor(null)}
+        />
+      </SettingsOption>
+      <SettingsOption>
+        <span>{t('wm.border.radius')}</span>
+        <InputNumber value={radius} min={0} onChange={updateRadius} />
+      </SettingsOption>
+      <SettingsOption>
+        <span>{t('wm.border.animation_duration')}</span>
+        <InputNumber
+          value={animationDuration}
+          min={0}
+          step={50}
+          onChange={updateAnimationDuration}
+        />
+      </SettingsOption>
     </SettingsSubGroup>
   );
 };
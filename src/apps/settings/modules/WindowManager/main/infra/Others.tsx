@@ -1,16 +1,31 @@
-import { InputNumber } from 'antd';
+import { InputNumber, Select, Switch } from 'antd';
 import { useTranslation } from 'react-i18next';
 import { useDispatch } from 'react-redux';
+import {
+  SnapSettings,
+  WindowCorners,
+  WmCornersSettings,
+  ZonesModifier,
+  ZonesSettings,
+} from 'seelen-core';
 
 import { useAppSelector } from '../../../shared/utils/infra';
 
 import { SeelenWmSelectors } from '../../../shared/store/app/selectors';
 import { WManagerSettingsActions } from '../app';
 
-import { SettingsGroup, SettingsOption } from '../../../../components/SettingsBox';
+import {
+  SettingsGroup,
+  SettingsOption,
+  SettingsSubGroup,
+} from '../../../../components/SettingsBox';
 
 export const OthersConfigs = () => {
   const resizeDelta = useAppSelector(SeelenWmSelectors.resizeDelta);
+  const mirrorDirectionsOnRtl = useAppSelector(SeelenWmSelectors.mirrorDirectionsOnRtl);
+  const zones = useAppSelector(SeelenWmSelectors.zones);
+  const snap = useAppSelector(SeelenWmSelectors.snap);
+  const corners = useAppSelector(SeelenWmSelectors.corners);
 
   const dispatch = useDispatch();
   const { t } = useTranslation();
@@ -19,6 +34,23 @@ export const OthersConfigs = () => {
     dispatch(WManagerSettingsActions.setResizeDelta(value || 0));
   };
 
+  const onChangeZones = (value: Partial<ZonesSettings>) => {
+    dispatch(WManagerSettingsActions.setZones({ ...zones, ...value }));
+  };
+
+  const onChangeSnap = (value: Partial<SnapSettings>) => {
+    dispatch(WManagerSettingsActions.setSnap({ ...snap, ...value }));
+  };
+
+  const onChangeCorners = (value: Partial<WmCornersSettings>) => {
+    dispatch(WManagerSettingsActions.setCorners({ ...corners, ...value }));
+  };
+
+  const cornersOptions = Object.values(WindowCorners).map((value) => ({
+    label: t(`wm.corners.options.${value}`),
+    value,
+  }));
+
   return (
     <>
       <SettingsGroup>
@@ -26,6 +58,70 @@ export const OthersConfigs = () => {
           <span>{t('wm.resize_delta')}</span>
           <InputNumber value={resizeDelta} onChange={onChangeResizeDelta} min={1} max={40} />
         </SettingsOption>
+        <SettingsOption>
+          <span>{t('wm.mirror_directions_on_rtl')}</span>
+          <Switch
+            checked={mirrorDirectionsOnRtl}
+            onChange={(value) => dispatch(WManagerSettingsActions.setMirrorDirectionsOnRtl(value))}
+          />
+        </SettingsOption>
+      </SettingsGroup>
+      <SettingsGroup>
+        <SettingsSubGroup
+          label={
+            <SettingsOption>
+              <span>{t('wm.zones.enable')}</span>
+              <Switch checked={zones.enabled} onChange={(enabled) => onChangeZones({ enabled })} />
+            </SettingsOption>
+          }
+        >
+          <SettingsOption>
+            <span>{t('wm.zones.modifier')}</span>
+            <Select
+              style={{ width: '120px' }}
+              value={zones.modifier}
+              disabled={!zones.enabled}
+              options={Object.values(ZonesModifier).map((modifier) => ({
+                label: modifier,
+                value: modifier,
+              }))}
+              onSelect={(modifier) => onChangeZones({ modifier })}
+            />
+          </SettingsOption>
+        </SettingsSubGroup>
+      </SettingsGroup>
+      <SettingsGroup>
+        <SettingsOption>
+          <span>{t('wm.snap.interop')}</span>
+          <Switch checked={snap.interop} onChange={(interop) => onChangeSnap({ interop })} />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('wm.snap.replace_flyout')}</span>
+          <Switch
+            checked={snap.replaceFlyout}
+            onChange={(replaceFlyout) => onChangeSnap({ replaceFlyout })}
+          />
+        </SettingsOption>
+      </SettingsGroup>
+      <SettingsGroup>
+        <SettingsOption>
+          <span>{t('wm.corners.tiled')}</span>
+          <Select
+            style={{ width: '120px' }}
+            value={corners.tiled}
+            options={cornersOptions}
+            onSelect={(tiled) => onChangeCorners({ tiled })}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('wm.corners.floating')}</span>
+          <Select
+            style={{ width: '120px' }}
+            value={corners.floating}
+            options={cornersOptions}
+            onSelect={(floating) => onChangeCorners({ floating })}
+          />
+        </SettingsOption>
       </SettingsGroup>
     </>
   );
@@ -5,12 +5,19 @@ export enum WmApplicationOptions {
   Unmanage = `${AppExtraFlag.Unmanage}`,
   ForceManage = `${AppExtraFlag.Force}`,
   Pinned = `${AppExtraFlag.Pinned}`,
+  NoPositionMemory = `${AppExtraFlag.NoPositionMemory}`,
+  IgnoreSizeHints = `${AppExtraFlag.IgnoreSizeHints}`,
 }
 
 export enum WegApplicationOptions {
   Hidden = `${AppExtraFlag.Hidden}`,
 }
 
+export enum WindowApplicationOptions {
+  AlwaysOnTop = `${AppExtraFlag.AlwaysOnTop}`,
+  PictureInPicture = `${AppExtraFlag.PictureInPicture}`,
+}
+
 export interface AppConfigurationExtended extends AppConfiguration {
   key: number;
 }
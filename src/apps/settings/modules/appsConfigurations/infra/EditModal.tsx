@@ -1,17 +1,34 @@
 import { createSelector } from '@reduxjs/toolkit';
-import { ConfigProvider, Input, Modal, Select, Switch } from 'antd';
+import { ConfigProvider, Input, InputNumber, Modal, Select, Switch } from 'antd';
 import React, { useEffect, useState } from 'react';
 import { useTranslation } from 'react-i18next';
 import { useSelector } from 'react-redux';
-import { AppConfiguration, AppExtraFlag, AppIdentifier } from 'seelen-core';
+import {
+  AppAudioRules,
+  AppConfiguration,
+  AppExtraFlag,
+  AppIdentifier,
+  AppIdleRules,
+  AppWindowEffects,
+  FullscreenPolicy,
+  TitleRule,
+  WindowBackdrop,
+  WindowCorners,
+} from 'seelen-core';
 
 import { ownSelector, RootSelectors } from '../../shared/store/app/selectors';
 
 import { RootState } from '../../shared/store/domain';
-import { AppConfigurationExtended, WegApplicationOptions, WmApplicationOptions } from '../domain';
+import {
+  AppConfigurationExtended,
+  WegApplicationOptions,
+  WindowApplicationOptions,
+  WmApplicationOptions,
+} from '../domain';
 
 import { SettingsGroup, SettingsOption, SettingsSubGroup } from '../../../components/SettingsBox';
 import { Identifier } from './Identifier';
+import { TitleRules } from './TitleRules';
 import cs from './index.module.css';
 
 interface Props {
@@ -66,6 +83,53 @@ export const EditAppModal = ({ idx, onCancel, onSave, isNew, open, readonlyApp }
     });
   };
 
+  const onChangeEffects = (effects: Partial<AppWindowEffects>) => {
+    const newEffects = { opacity: null, backdrop: null, ...app.effects, ...effects };
+    const isEmpty = newEffects.opacity == null && newEffects.backdrop == null;
+    setApp({ ...app, effects: isEmpty ? null : newEffects });
+  };
+
+  const fullscreen = app.fullscreen || FullscreenPolicy.default();
+  const onChangeFullscreen = (changes: Partial<FullscreenPolicy>) => {
+    const defaults = FullscreenPolicy.default();
+    const policy = { ...fullscreen, ...changes };
+    const isDefault = (Object.keys(defaults) as Array<keyof FullscreenPolicy>).every(
+      (key) => policy[key] === defaults[key],
+    );
+    setApp({ ...app, fullscreen: isDefault ? null : policy });
+  };
+
+  const onChangeAudio = (changes: Partial<AppAudioRules>) => {
+    const audio = { muteUnfocused: false, maxVolume: null, ...app.audio, ...changes };
+    const isEmpty = !audio.muteUnfocused && audio.maxVolume == null;
+    setApp({ ...app, audio: isEmpty ? null : audio });
+  };
+
+  const onChangeIdle = (changes: Partial<AppIdleRules>) => {
+    const idle = {
+      minimizeAfter: null,
+      closeAfter: null,
+      closeAt: null,
+      warnBefore: 60,
+      ...app.idle,
+      ...changes,
+    };
+    const isEmpty = idle.minimizeAfter == null && idle.closeAfter == null && !idle.closeAt;
+    setApp({ ...app, idle: isEmpty ? null : idle });
+  };
+
+  const onChangeTitleRules = (titleRules: TitleRule[]) => setApp({ ...app, titleRules });
+
+  const backdropOptions = Object.values(WindowBackdrop).map((value) => ({
+    label: t(`apps_configurations.app.backdrops.${value}`),
+    value,
+  }));
+
+  const cornersOptions = Object.values(WindowCorners).map((value) => ({
+    label: t(`wm.corners.options.${value}`),
+    value,
+  }));
+
   const monitorsOptions = monitors.map((_, i) => ({ label: `Monitor ${i + 1}`, value: i }));
   const workspaceOptions = Array.from({ length: 10 }).map((_, i) => ({
     label: `Workspace ${i + 1}`,
@@ -171,7 +235,146 @@ export const EditAppModal = ({ idx, onCancel, onSave, isNew, open, readonlyApp }
               </SettingsOption>
             ))}
           </SettingsSubGroup>
+
+          <SettingsSubGroup label={t('apps_configurations.app.window_options_label')}>
+            {Object.values(WindowApplicationOptions).map((value, i) => (
+              <SettingsOption key={i}>
+                <span>{t(`apps_configurations.app.options.${value}`)}</span>
+                <Switch
+                  value={app.options.includes(value as any as AppExtraFlag)}
+                  onChange={onChangeOption.bind(this, value as any as AppExtraFlag)}
+                />
+              </SettingsOption>
+            ))}
+          </SettingsSubGroup>
+        </SettingsGroup>
+
+        <SettingsGroup>
+          <SettingsSubGroup label={t('apps_configurations.app.effects_label')}>
+            <SettingsOption>
+              <span>{t('apps_configurations.app.opacity')}</span>
+              <InputNumber
+                value={app.effects?.opacity}
+                placeholder={t('apps_configurations.app.opacity_placeholder')}
+                min={0.1}
+                max={1}
+                step={0.05}
+                onChange={(value) => onChangeEffects({ opacity: value })}
+              />
+            </SettingsOption>
+            <SettingsOption>
+              <span>{t('apps_configurations.app.backdrop')}</span>
+              <Select
+                value={app.effects?.backdrop}
+                placeholder={t('apps_configurations.app.backdrop_placeholder')}
+                allowClear
+                options={backdropOptions}
+                onChange={(value) => onChangeEffects({ backdrop: value ?? null })}
+              />
+            </SettingsOption>
+            <SettingsOption>
+              <span>{t('apps_configurations.app.corners')}</span>
+              <Select
+                value={app.corners}
+                placeholder={t('apps_configurations.app.corners_placeholder')}
+                allowClear
+                options={cornersOptions}
+                onChange={(value) => setApp({ ...app, corners: value ?? null })}
+              />
+            </SettingsOption>
+          </SettingsSubGroup>
         </SettingsGroup>
+
+        <SettingsGroup>
+          <SettingsSubGroup label={t('apps_configurations.app.fullscreen_label')}>
+            <SettingsOption>
+              <span>{t('apps_configurations.app.fullscreen.never_tile')}</span>
+              <Switch
+                value={fullscreen.neverTile}
+                onChange={(neverTile) => onChangeFullscreen({ neverTile })}
+              />
+            </SettingsOption>
+            <SettingsOption>
+              <span>{t('apps_configurations.app.fullscreen.hide_overlays')}</span>
+              <Switch
+                value={fullscreen.hideOverlays}
+                onChange={(hideOverlays) => onChangeFullscreen({ hideOverlays })}
+              />
+            </SettingsOption>
+            <SettingsOption>
+              <span>{t('apps_configurations.app.fullscreen.keep_toolbar')}</span>
+              <Switch
+                value={fullscreen.keepToolbar}
+                onChange={(keepToolbar) => onChangeFullscreen({ keepToolbar })}
+              />
+            </SettingsOption>
+          </SettingsSubGroup>
+        </SettingsGroup>
+
+        <SettingsGroup>
+          <SettingsSubGroup label={t('apps_configurations.app.audio_label')}>
+            <SettingsOption>
+              <span>{t('apps_configurations.app.audio.mute_unfocused')}</span>
+              <Switch
+                value={!!app.audio?.muteUnfocused}
+                onChange={(muteUnfocused) => onChangeAudio({ muteUnfocused })}
+              />
+            </SettingsOption>
+            <SettingsOption>
+              <span>{t('apps_configurations.app.audio.max_volume')}</span>
+              <InputNumber
+                value={app.audio?.maxVolume}
+                placeholder={t('apps_configurations.app.audio.max_volume_placeholder')}
+                min={0}
+                max={1}
+                step={0.05}
+                onChange={(value) => onChangeAudio({ maxVolume: value })}
+              />
+            </SettingsOption>
+          </SettingsSubGroup>
+        </SettingsGroup>
+
+        <SettingsGroup>
+          <SettingsSubGroup label={t('apps_configurations.app.idle_label')}>
+            <SettingsOption>
+              <span>{t('apps_configurations.app.idle.minimize_after')}</span>
+              <InputNumber
+                value={app.idle?.minimizeAfter}
+                placeholder={t('apps_configurations.app.idle.never')}
+                min={1}
+                onChange={(value) => onChangeIdle({ minimizeAfter: value })}
+              />
+            </SettingsOption>
+            <SettingsOption>
+              <span>{t('apps_configurations.app.idle.close_after')}</span>
+              <InputNumber
+                value={app.idle?.closeAfter}
+                placeholder={t('apps_configurations.app.idle.never')}
+                min={1}
+                onChange={(value) => onChangeIdle({ closeAfter: value })}
+              />
+            </SettingsOption>
+            <SettingsOption>
+              <span>{t('apps_configurations.app.idle.close_at')}</span>
+              <Input
+                value={app.idle?.closeAt || ''}
+                placeholder="18:00"
+                onChange={(e) => onChangeIdle({ closeAt: e.target.value.trim() || null })}
+              />
+            </SettingsOption>
+            <SettingsOption>
+              <span>{t('apps_configurations.app.idle.warn_before')}</span>
+              <InputNumber
+                value={app.idle?.warnBefore ?? 60}
+                min={0}
+                disabled={!app.idle?.closeAfter && !app.idle?.closeAt}
+                onChange={(value) => onChangeIdle({ warnBefore: value || 0 })}
+              />
+            </SettingsOption>
+          </SettingsSubGroup>
+        </SettingsGroup>
+
+        <TitleRules rules={app.titleRules || []} onChange={onChangeTitleRules} />
       </ConfigProvider>
     </Modal>
   );
@@ -0,0 +1,70 @@
+import { Button, Input } from 'antd';
+import { useTranslation } from 'react-i18next';
+import { TitleRule } from 'seelen-core';
+
+import { Icon } from '../../../../shared/components/Icon';
+import { SettingsGroup, SettingsOption, SettingsSubGroup } from '../../../components/SettingsBox';
+import cs from './Identifier.module.css';
+
+interface Props {
+  rules: TitleRule[];
+  onChange: (rules: TitleRule[]) => void;
+}
+
+export function TitleRules({ rules, onChange }: Props) {
+  const { t } = useTranslation();
+
+  const onChangeRule = (idx: number, rule: Partial<TitleRule>) => {
+    onChange(rules.map((r, i) => (i === idx ? { ...r, ...rule } : r)));
+  };
+
+  const onRemoveRule = (idx: number) => {
+    onChange(rules.filter((_, i) => i !== idx));
+  };
+
+  const onAddRule = () => {
+    onChange([...rules, { pattern: '', replace: '' }]);
+  };
+
+  return (
+    <SettingsGroup>
+      <SettingsOption>
+        <b>{t('apps_configurations.title_rules.label')}</b>
+        <Button type="dashed" onClick={onAddRule}>
+          {t('apps_configurations.title_rules.add')}
+        </Button>
+      </SettingsOption>
+      {rules.map((rule, idx) => (
+        <SettingsSubGroup key={idx} label={`#${idx + 1}`}>
+          <SettingsOption>
+            <span>{t('apps_configurations.title_rules.pattern')}</span>
+            <Input
+              value={rule.pattern}
+              placeholder="(.*) - Visual Studio Code"
+              onChange={(e) => onChangeRule(idx, { pattern: e.target.value })}
+            />
+          </SettingsOption>
+          <SettingsOption>
+            <span>{t('apps_configurations.title_rules.replace')}</span>
+            <Input
+              value={rule.replace}
+              placeholder="$1"
+              onChange={(e) => onChangeRule(idx, { replace: e.target.value })}
+            />
+          </SettingsOption>
+          <SettingsOption>
+            <span>{t('apps_configurations.title_rules.remove')}</span>
+            <Button
+              type="text"
+              danger
+              onClick={() => onRemoveRule(idx)}
+              className={cs.removeButton}
+            >
+              <Icon iconName="IoTrash" />
+            </Button>
+          </SettingsOption>
+        </SettingsSubGroup>
+      ))}
+    </SettingsGroup>
+  );
+}
@@ -0,0 +1,75 @@
+import { Button, Input, InputNumber, Switch } from 'antd';
+import { useTranslation } from 'react-i18next';
+import { useDispatch, useSelector } from 'react-redux';
+import { RemoteControlSettings } from 'seelen-core';
+
+import { newSelectors, RootActions } from '../shared/store/app/reducer';
+
+import { SettingsGroup, SettingsOption, SettingsSubGroup } from '../../components/SettingsBox';
+
+// shorter tokens are refused by the background, see `MIN_TOKEN_LENGTH`
+const MIN_TOKEN_LENGTH = 24;
+
+function generateToken() {
+  return crypto.randomUUID().replace(/-/g, '');
+}
+
+export function RemoteControl() {
+  const settings = useSelector(newSelectors.remoteControl);
+
+  const dispatch = useDispatch();
+  const { t } = useTranslation();
+
+  const onChange = (changes: Partial<RemoteControlSettings>) => {
+    dispatch(RootActions.setRemoteControl({ ...settings, ...changes }));
+  };
+
+  const onToggle = (enabled: boolean) => {
+    // the server is not started without a long enough token
+    const token = settings.token.length >= MIN_TOKEN_LENGTH ? settings.token : generateToken();
+    onChange({ enabled, token });
+  };
+
+  return (
+    <SettingsGroup>
+      <SettingsSubGroup
+        label={
+          <SettingsOption>
+            <b>{t('devtools.remote_control.enabled')}</b>
+            <Switch checked={settings.enabled} onChange={onToggle} />
+          </SettingsOption>
+        }
+      >
+        <p>{t('devtools.remote_control.description')}</p>
+        <SettingsOption>
+          <span>{t('devtools.remote_control.port')}</span>
+          <InputNumber
+            min={1024}
+            max={65535}
+            value={settings.port}
+            onChange={(port) => {
+              if (port) {
+                onChange({ port });
+              }
+            }}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('devtools.remote_control.token')}</span>
+          <Input.Password value={settings.token} readOnly style={{ width: 200 }} />
+        </SettingsOption>
+        <SettingsOption>
+          <span />
+          <div>
+            <Button onClick={() => navigator.clipboard.writeText(settings.token)}>
+              {t('devtools.remote_control.copy')}
+            </Button>
+            <Button onClick={() => onChange({ token: generateToken() })}>
+              {t('devtools.remote_control.regenerate')}
+            </Button>
+          </div>
+        </SettingsOption>
+      </SettingsSubGroup>
+    </SettingsGroup>
+  );
+}
@@ -0,0 +1,207 @@
+import { invoke } from '@tauri-apps/api/core';
+import { Button, Collapse, InputNumber, Modal, Select, Switch, Tag } from 'antd';
+import { useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useDispatch, useSelector } from 'react-redux';
+import {
+  SeelenCommand,
+  WindowFilterOutcome,
+  WindowFilterReport,
+  WindowFilterVerdict,
+  WindowFiltersSettings,
+  WindowInspection,
+} from 'seelen-core';
+
+import { newSelectors, RootActions } from '../shared/store/app/reducer';
+
+import { SettingsGroup, SettingsOption, SettingsSubGroup } from '../../components/SettingsBox';
+
+/** seconds before inspecting the window under the cursor */
+const INSPECT_DELAY = 3;
+
+const OutcomeColor: Record<WindowFilterOutcome, string> = {
+  [WindowFilterOutcome.Passed]: 'default',
+  [WindowFilterOutcome.Rejected]: 'red',
+  [WindowFilterOutcome.Forced]: 'green',
+};
+
+function Verdict({ label, verdict }: { label: string; verdict: WindowFilterVerdict }) {
+  return (
+    <div>
+      <b>{label}: </b>
+      <Tag color={verdict.included ? 'green' : 'red'}>{verdict.decidedBy || '-'}</Tag>
+      <ul>
+        {verdict.steps.map((step, idx) => (
+          <li key={idx}>
+            <Tag color={OutcomeColor[step.outcome]}>{step.filter}</Tag>
+            <span>{step.detail}</span>
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+}
+
+export function WindowFilters() {
+  const [reports, setReports] = useState<WindowFilterReport[] | null>(null);
+  const [inspection, setInspection] = useState<WindowInspection | null>(null);
+  const [inspectCountdown, setInspectCountdown] = useState(0);
+
+  const settings = useSelector(newSelectors.windowFilters);
+
+  const dispatch = useDispatch();
+  const { t } = useTranslation();
+
+  const onChange = (changes: Partial<WindowFiltersSettings>) => {
+    dispatch(RootActions.setWindowFilters({ ...settings, ...changes }));
+  };
+
+  const onExplain = () => {
+    invoke<WindowFilterReport[]>(SeelenCommand.WindowFilterExplain).then(setReports);
+  };
+
+  // gives time to move the cursor over the window to inspect
+  const onInspect = () => {
+    let remaining = INSPECT_DELAY;
+    setInspectCountdown(remaining);
+    const interval = setInterval(() => {
+      remaining--;
+      setInspectCountdown(remaining);
+      if (remaining <= 0) {
+        clearInterval(interval);
+        invoke<WindowInspection>(SeelenCommand.WindowFilterInspectAtCursor, { flash: true }).then(
+          setInspection,
+        );
+      }
+    }, 1000);
+  };
+
+  return (
+    <SettingsGroup>
+      <SettingsSubGroup label={t('devtools.window_filters.label')}>
+        <SettingsOption>
+          <span>{t('devtools.window_filters.by_styles')}</span>
+          <Switch checked={settings.byStyles} onChange={(byStyles) => onChange({ byStyles })} />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('devtools.window_filters.by_owner')}</span>
+          <Switch checked={settings.byOwner} onChange={(byOwner) => onChange({ byOwner })} />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('devtools.window_filters.class_blacklist')}</span>
+          <Select
+            mode="tags"
+            style={{ width: 300 }}
+            value={settings.classBlacklist}
+            onChange={(classBlacklist) => onChange({ classBlacklist })}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('devtools.window_filters.min_width')}</span>
+          <InputNumber
+            min={0}
+            value={settings.minWidth}
+            onChange={(minWidth) => onChange({ minWidth: minWidth || 0 })}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('devtools.window_filters.min_height')}</span>
+          <InputNumber
+            min={0}
+            value={settings.minHeight}
+            onChange={(minHeight) => onChange({ minHeight: minHeight || 0 })}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('devtools.window_filters.explain')}</span>
+          <Button onClick={onExplain}>{t('devtools.window_filters.explain')}</Button>
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('devtools.window_filters.inspect')}</span>
+          <Button onClick={onInspect} disabled={inspectCountdown > 0}>
+            {inspectCountdown > 0
+              ? t('devtools.window_filters.inspect_countdown', { seconds: inspectCountdown })
+              : t('devtools.window_filters.inspect_button')}
+          </Button>
+        </SettingsOption>
+      </SettingsSubGroup>
+
+      <Modal
+        open={!!reports}
+        title={t('devtools.window_filters.explain')}
+        footer={null}
+        width={800}
+        onCancel={() => setReports(null)}
+      >
+        <Collapse
+          items={(reports || []).map((report) => ({
+            key: report.hwnd,
+            label: (
+              <span>
+                <Tag color={report.weg.included ? 'green' : 'default'}>
+                  {t('devtools.window_filters.dock')}
+                </Tag>
+                <Tag color={report.wm.included ? 'green' : 'default'}>
+                  {t('devtools.window_filters.tiled')}
+                </Tag>
+                {report.title || report.class}
+              </span>
+            ),
+            children: (
+              <>
+                <p>
+                  {report.class} - {report.exe}
+                </p>
+                <Verdict label={t('devtools.window_filters.dock')} verdict={report.weg} />
+                <Verdict label={t('devtools.window_filters.tiled')} verdict={report.wm} />
+              </>
+            ),
+          }))}
+        />
+      </Modal>
+
+      <Modal
+        open={!!inspection}
+        title={t('devtools.window_filters.inspect')}
+        footer={null}
+        width={800}
+        onCancel={() => setInspection(null)}
+      >
+        {inspection && (
+          <>
+            <p>
+              <b>Exe:</b> {inspection.exe}
+            </p>
+            <p>
+              <b>Class:</b> {inspection.class}
+            </p>
+            <p>
+              <b>Title:</b> {inspection.title}
+            </p>
+            <p>
+              <b>AUMID:</b> {inspection.aumid || '-'}
+            </p>
+            <p>
+              <b>{t('devtools.window_filters.matched_configs')}:</b>{' '}
+              {inspection.matchedConfigs.map((config, idx) => (
+                <Tag key={idx} color={idx === 0 ? 'green' : 'default'}>
+                  {config.name}
+                </Tag>
+              ))}
+              {inspection.matchedConfigs.length === 0 && '-'}
+            </p>
+            <p>
+              <b>{t('devtools.window_filters.applied_flags')}:</b>{' '}
+              {inspection.appliedFlags.map((flag) => (
+                <Tag key={flag}>{flag}</Tag>
+              ))}
+              {inspection.appliedFlags.length === 0 && '-'}
+            </p>
+            <Verdict label={t('devtools.window_filters.dock')} verdict={inspection.weg} />
+            <Verdict label={t('devtools.window_filters.tiled')} verdict={inspection.wm} />
+          </>
+        )}
+      </Modal>
+    </SettingsGroup>
+  );
+}
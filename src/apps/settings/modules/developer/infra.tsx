@@ -9,6 +9,8 @@ import { resolveDataPath } from '../shared/config/infra';
 
 import { newSelectors, RootActions } from '../shared/store/app/reducer';
 import { LoadCustomConfigFile } from './app';
+import { RemoteControl } from './RemoteControl';
+import { WindowFilters } from './WindowFilters';
 
 import { SettingsGroup, SettingsOption, SettingsSubGroup } from '../../components/SettingsBox';
 
@@ -77,6 +79,10 @@ export function DeveloperTools() {
           <Switch onChange={simulateFullscreen} />
         </SettingsOption>
       </SettingsGroup>
+
+      <WindowFilters />
+
+      <RemoteControl />
     </>
   );
 }
@@ -0,0 +1,38 @@
+import { Switch } from 'antd';
+import { useTranslation } from 'react-i18next';
+import { useDispatch, useSelector } from 'react-redux';
+import { AccessibilitySettings } from 'seelen-core';
+
+import { newSelectors, RootActions } from '../../../shared/store/app/reducer';
+
+import { SettingsGroup, SettingsOption } from '../../../../components/SettingsBox';
+
+export function Accessibility() {
+  const settings = useSelector(newSelectors.accessibility);
+
+  const { t } = useTranslation();
+  const dispatch = useDispatch();
+
+  const onChange = (changes: Partial<AccessibilitySettings>) => {
+    dispatch(RootActions.setAccessibility({ ...settings, ...changes }));
+  };
+
+  return (
+    <SettingsGroup>
+      <SettingsOption>
+        <span>{t('general.accessibility.announce_workspaces')}</span>
+        <Switch
+          checked={settings.announceWorkspaces}
+          onChange={(announceWorkspaces) => onChange({ announceWorkspaces })}
+        />
+      </SettingsOption>
+      <SettingsOption>
+        <span>{t('general.accessibility.announce_focus')}</span>
+        <Switch
+          checked={settings.announceFocus}
+          onChange={(announceFocus) => onChange({ announceFocus })}
+        />
+      </SettingsOption>
+    </SettingsGroup>
+  );
+}
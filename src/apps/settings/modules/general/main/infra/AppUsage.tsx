@@ -0,0 +1,71 @@
+import { invoke } from '@tauri-apps/api/core';
+import { Button, InputNumber, Switch } from 'antd';
+import { useTranslation } from 'react-i18next';
+import { useDispatch, useSelector } from 'react-redux';
+import { AppUsageSettings, SeelenCommand } from 'seelen-core';
+
+import { newSelectors, RootActions } from '../../../shared/store/app/reducer';
+
+import {
+  SettingsGroup,
+  SettingsOption,
+  SettingsSubGroup,
+} from '../../../../components/SettingsBox';
+
+export function AppUsage() {
+  const settings = useSelector(newSelectors.appUsage);
+
+  const { t } = useTranslation();
+  const dispatch = useDispatch();
+
+  const onChange = (changes: Partial<AppUsageSettings>) => {
+    dispatch(RootActions.setAppUsage({ ...settings, ...changes }));
+  };
+
+  return (
+    <SettingsGroup>
+      <SettingsSubGroup
+        label={
+          <SettingsOption>
+            <b>{t('general.app_usage.enabled')}</b>
+            <Switch checked={settings.enabled} onChange={(enabled) => onChange({ enabled })} />
+          </SettingsOption>
+        }
+      >
+        <SettingsOption>
+          <span>{t('general.app_usage.retention_days')}</span>
+          <InputNumber
+            min={1}
+            max={3650}
+            value={settings.retentionDays}
+            disabled={!settings.enabled}
+            onChange={(retentionDays) => onChange({ retentionDays: retentionDays || 1 })}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('general.app_usage.nudge')}</span>
+          <Switch
+            checked={settings.nudge}
+            disabled={!settings.enabled}
+            onChange={(nudge) => onChange({ nudge })}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('general.app_usage.nudge_after')}</span>
+          <InputNumber
+            min={1}
+            value={settings.nudgeAfter}
+            disabled={!settings.enabled || !settings.nudge}
+            onChange={(nudgeAfter) => onChange({ nudgeAfter: nudgeAfter || 1 })}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('general.app_usage.clear')}</span>
+          <Button onClick={() => invoke(SeelenCommand.AppUsageClear)}>
+            {t('general.app_usage.clear')}
+          </Button>
+        </SettingsOption>
+      </SettingsSubGroup>
+    </SettingsGroup>
+  );
+}
@@ -0,0 +1,71 @@
+import { InputNumber, Switch } from 'antd';
+import { useTranslation } from 'react-i18next';
+import { useDispatch, useSelector } from 'react-redux';
+import { BreakReminderSettings } from 'seelen-core';
+
+import { newSelectors, RootActions } from '../../../shared/store/app/reducer';
+
+import {
+  SettingsGroup,
+  SettingsOption,
+  SettingsSubGroup,
+} from '../../../../components/SettingsBox';
+
+export function BreakReminder() {
+  const settings = useSelector(newSelectors.breakReminder);
+
+  const { t } = useTranslation();
+  const dispatch = useDispatch();
+
+  const onChange = (changes: Partial<BreakReminderSettings>) => {
+    dispatch(RootActions.setBreakReminder({ ...settings, ...changes }));
+  };
+
+  const minutesOption = (key: 'interval' | 'duration' | 'snooze') => (
+    <SettingsOption>
+      <span>{t(`general.break_reminder.${key}`)}</span>
+      <InputNumber
+        min={1}
+        max={600}
+        value={settings[key]}
+        disabled={!settings.enabled}
+        onChange={(value) => onChange({ [key]: value || 1 })}
+      />
+    </SettingsOption>
+  );
+
+  return (
+    <SettingsGroup>
+      <SettingsSubGroup
+        label={
+          <SettingsOption>
+            <b>{t('general.break_reminder.enabled')}</b>
+            <Switch checked={settings.enabled} onChange={(enabled) => onChange({ enabled })} />
+          </SettingsOption>
+        }
+      >
+        {minutesOption('interval')}
+        {minutesOption('duration')}
+        {minutesOption('snooze')}
+        <SettingsOption>
+          <span>{t('general.break_reminder.dim')}</span>
+          <Switch
+            checked={settings.dim}
+            disabled={!settings.enabled}
+            onChange={(dim) => onChange({ dim })}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('general.break_reminder.dim_level')}</span>
+          <InputNumber
+            min={0}
+            max={100}
+            value={settings.dimLevel}
+            disabled={!settings.enabled || !settings.dim}
+            onChange={(dimLevel) => onChange({ dimLevel: dimLevel ?? 0 })}
+          />
+        </SettingsOption>
+      </SettingsSubGroup>
+    </SettingsGroup>
+  );
+}
@@ -0,0 +1,76 @@
+import { Button, Input, Switch } from 'antd';
+import { useTranslation } from 'react-i18next';
+import { useDispatch, useSelector } from 'react-redux';
+import { CaptureSettings } from 'seelen-core';
+
+import { dialog } from '../../../shared/tauri/infra';
+
+import { newSelectors, RootActions } from '../../../shared/store/app/reducer';
+
+import {
+  SettingsGroup,
+  SettingsOption,
+  SettingsSubGroup,
+} from '../../../../components/SettingsBox';
+
+export function Capture() {
+  const settings = useSelector(newSelectors.capture);
+
+  const { t } = useTranslation();
+  const dispatch = useDispatch();
+
+  const onChange = (changes: Partial<CaptureSettings>) => {
+    dispatch(RootActions.setCapture({ ...settings, ...changes }));
+  };
+
+  async function selectFolder() {
+    const folder = await dialog.open({
+      title: t('general.capture.folder'),
+      directory: true,
+    });
+    if (folder) {
+      onChange({ folder });
+    }
+  }
+
+  return (
+    <SettingsGroup>
+      <SettingsSubGroup
+        label={
+          <SettingsOption>
+            <b>{t('general.capture.save_to_folder')}</b>
+            <Switch
+              checked={settings.saveToFolder}
+              onChange={(saveToFolder) => onChange({ saveToFolder })}
+            />
+          </SettingsOption>
+        }
+      >
+        <SettingsOption>
+          <span>{t('general.capture.folder')}</span>
+          <div style={{ display: 'flex', gap: '4px' }}>
+            <Input
+              readOnly
+              style={{ width: '240px' }}
+              placeholder={t('general.capture.default_folder')}
+              value={settings.folder || ''}
+              disabled={!settings.saveToFolder}
+              allowClear
+              onChange={(e) => onChange({ folder: e.target.value || null })}
+            />
+            <Button onClick={selectFolder} disabled={!settings.saveToFolder}>
+              {t('general.capture.select_folder')}
+            </Button>
+          </div>
+        </SettingsOption>
+      </SettingsSubGroup>
+      <SettingsOption>
+        <b>{t('general.capture.copy_to_clipboard')}</b>
+        <Switch
+          checked={settings.copyToClipboard}
+          onChange={(copyToClipboard) => onChange({ copyToClipboard })}
+        />
+      </SettingsOption>
+    </SettingsGroup>
+  );
+}
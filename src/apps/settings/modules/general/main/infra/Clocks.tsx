@@ -0,0 +1,99 @@
+import { Button, Input, Select } from 'antd';
+import { useTranslation } from 'react-i18next';
+import { useDispatch, useSelector } from 'react-redux';
+import { ClockConfig, ClockTickMode } from 'seelen-core';
+
+import { newSelectors, RootActions } from '../../../shared/store/app/reducer';
+import { Icon } from 'src/apps/shared/components/Icon';
+
+import {
+  SettingsGroup,
+  SettingsOption,
+  SettingsSubGroup,
+} from '../../../../components/SettingsBox';
+
+export function Clocks() {
+  const settings = useSelector(newSelectors.clock);
+
+  const { t } = useTranslation();
+  const dispatch = useDispatch();
+
+  const onChangeClocks = (clocks: ClockConfig[]) => {
+    dispatch(RootActions.setClock({ ...settings, clocks }));
+  };
+
+  const onChangeClock = (idx: number, changes: Partial<ClockConfig>) => {
+    onChangeClocks(settings.clocks.map((c, i) => (i === idx ? { ...c, ...changes } : c)));
+  };
+
+  const onAddClock = () => {
+    onChangeClocks([
+      ...settings.clocks,
+      { ...new ClockConfig(), id: `clock${settings.clocks.length + 1}` },
+    ]);
+  };
+
+  return (
+    <SettingsGroup>
+      <SettingsOption>
+        <b>{t('general.clocks.label')}</b>
+        <Button type="dashed" onClick={onAddClock}>
+          {t('general.clocks.add')}
+        </Button>
+      </SettingsOption>
+      <SettingsOption>
+        <span>{t('general.clocks.tick_mode.label')}</span>
+        <Select
+          style={{ width: '200px' }}
+          value={settings.tickMode}
+          options={Object.values(ClockTickMode).map((mode) => ({
+            value: mode,
+            label: t(`general.clocks.tick_mode.options.${mode.toLowerCase()}`),
+          }))}
+          onSelect={(tickMode) => dispatch(RootActions.setClock({ ...settings, tickMode }))}
+        />
+      </SettingsOption>
+      {settings.clocks.map((clock, idx) => (
+        <SettingsSubGroup key={idx} label={clock.label || clock.id}>
+          <SettingsOption>
+            <span>{t('general.clocks.id')}</span>
+            <Input value={clock.id} onChange={(e) => onChangeClock(idx, { id: e.target.value })} />
+          </SettingsOption>
+          <SettingsOption>
+            <span>{t('general.clocks.name')}</span>
+            <Input
+              value={clock.label || ''}
+              onChange={(e) => onChangeClock(idx, { label: e.target.value || null })}
+            />
+          </SettingsOption>
+          <SettingsOption>
+            <span>{t('general.clocks.timezone')}</span>
+            <Input
+              value={clock.timezone || ''}
+              placeholder="America/New_York"
+              onChange={(e) => onChangeClock(idx, { timezone: e.target.value.trim() || null })}
+            />
+          </SettingsOption>
+          <SettingsOption>
+            <span>{t('general.clocks.format')}</span>
+            <Input
+              value={clock.format}
+              placeholder="%H:%M"
+              onChange={(e) => onChangeClock(idx, { format: e.target.value })}
+            />
+          </SettingsOption>
+          <SettingsOption>
+            <span>{t('general.clocks.remove')}</span>
+            <Button
+              type="text"
+              danger
+              onClick={() => onChangeClocks(settings.clocks.filter((_, i) => i !== idx))}
+            >
+              <Icon iconName="IoTrash" />
+            </Button>
+          </SettingsOption>
+        </SettingsSubGroup>
+      ))}
+    </SettingsGroup>
+  );
+}
@@ -0,0 +1,56 @@
+import { InputNumber, Switch } from 'antd';
+import { useTranslation } from 'react-i18next';
+import { useDispatch, useSelector } from 'react-redux';
+import { HardwareKeysSettings } from 'seelen-core';
+
+import { newSelectors, RootActions } from '../../../shared/store/app/reducer';
+
+import {
+  SettingsGroup,
+  SettingsOption,
+  SettingsSubGroup,
+} from '../../../../components/SettingsBox';
+
+export function HardwareKeys() {
+  const settings = useSelector(newSelectors.hardwareKeys);
+
+  const { t } = useTranslation();
+  const dispatch = useDispatch();
+
+  const onChange = (changes: Partial<HardwareKeysSettings>) => {
+    dispatch(RootActions.setHardwareKeys({ ...settings, ...changes }));
+  };
+
+  return (
+    <SettingsGroup>
+      <SettingsSubGroup
+        label={
+          <SettingsOption>
+            <b>{t('general.hardware_keys.enabled')}</b>
+            <Switch checked={settings.enabled} onChange={(enabled) => onChange({ enabled })} />
+          </SettingsOption>
+        }
+      >
+        <SettingsOption>
+          <span>{t('general.hardware_keys.volume_step')}</span>
+          <InputNumber
+            min={1}
+            max={25}
+            value={settings.volumeStep}
+            disabled={!settings.enabled}
+            onChange={(volumeStep) => onChange({ volumeStep: volumeStep || 1 })}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('general.hardware_keys.brightness_step')}</span>
+          <InputNumber
+            min={1}
+            max={50}
+            value={settings.brightnessStep}
+            onChange={(brightnessStep) => onChange({ brightnessStep: brightnessStep || 1 })}
+          />
+        </SettingsOption>
+      </SettingsSubGroup>
+    </SettingsGroup>
+  );
+}
@@ -0,0 +1,85 @@
+import { invoke } from '@tauri-apps/api/core';
+import { Button, Input, InputNumber, Switch } from 'antd';
+import { useTranslation } from 'react-i18next';
+import { useDispatch, useSelector } from 'react-redux';
+import { NightLightSettings, SeelenCommand } from 'seelen-core';
+
+import { newSelectors, RootActions } from '../../../shared/store/app/reducer';
+
+import {
+  SettingsGroup,
+  SettingsOption,
+  SettingsSubGroup,
+} from '../../../../components/SettingsBox';
+
+export function NightLight() {
+  const settings = useSelector(newSelectors.nightLight);
+
+  const { t } = useTranslation();
+  const dispatch = useDispatch();
+
+  const onChange = (changes: Partial<NightLightSettings>) => {
+    dispatch(RootActions.setNightLight({ ...settings, ...changes }));
+  };
+
+  const onToggle = () => {
+    invoke(SeelenCommand.NightLightToggle).catch(console.error);
+  };
+
+  return (
+    <SettingsGroup>
+      <SettingsOption>
+        <b>{t('general.night_light.label')}</b>
+        <Button onClick={onToggle}>{t('general.night_light.toggle')}</Button>
+      </SettingsOption>
+      <SettingsOption>
+        <span>{t('general.night_light.temperature')}</span>
+        <InputNumber
+          min={1200}
+          max={6500}
+          step={100}
+          value={settings.temperature}
+          onChange={(temperature) => onChange({ temperature: temperature || 6500 })}
+        />
+      </SettingsOption>
+      <SettingsOption>
+        <span>{t('general.night_light.transition_duration')}</span>
+        <InputNumber
+          min={0}
+          step={500}
+          value={settings.transitionDuration}
+          onChange={(transitionDuration) => onChange({ transitionDuration: transitionDuration || 0 })}
+        />
+      </SettingsOption>
+      <SettingsSubGroup
+        label={
+          <SettingsOption>
+            <span>{t('general.night_light.scheduled')}</span>
+            <Switch checked={settings.scheduled} onChange={(scheduled) => onChange({ scheduled })} />
+          </SettingsOption>
+        }
+      >
+        <SettingsOption>
+          <span>{t('general.night_light.start')}</span>
+          <Input
+            style={{ width: '120px' }}
+            placeholder="HH:MM"
+            value={settings.start}
+            disabled={!settings.scheduled}
+            onChange={(e) => onChange({ start: e.target.value })}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('general.night_light.end')}</span>
+          <Input
+            style={{ width: '120px' }}
+            placeholder="HH:MM"
+            value={settings.end}
+            disabled={!settings.scheduled}
+            onChange={(e) => onChange({ end: e.target.value })}
+          />
+        </SettingsOption>
+      </SettingsSubGroup>
+    </SettingsGroup>
+  );
+}
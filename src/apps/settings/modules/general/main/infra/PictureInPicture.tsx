@@ -0,0 +1,77 @@
+import { InputNumber, Select, Switch } from 'antd';
+import { useTranslation } from 'react-i18next';
+import { useDispatch, useSelector } from 'react-redux';
+import { PictureInPictureSettings, PipCorner } from 'seelen-core';
+
+import { newSelectors, RootActions } from '../../../shared/store/app/reducer';
+
+import {
+  SettingsGroup,
+  SettingsOption,
+  SettingsSubGroup,
+} from '../../../../components/SettingsBox';
+
+export function PictureInPicture() {
+  const settings = useSelector(newSelectors.pictureInPicture);
+
+  const { t } = useTranslation();
+  const dispatch = useDispatch();
+
+  const onChange = (changes: Partial<PictureInPictureSettings>) => {
+    dispatch(RootActions.setPictureInPicture({ ...settings, ...changes }));
+  };
+
+  const onChangeSizes = (values: string[]) => {
+    const sizes = values
+      .map((value) => parseInt(value, 10))
+      .filter((size) => size >= 5 && size <= 100);
+    onChange({ sizes });
+  };
+
+  const cornerOptions = Object.values(PipCorner).map((value) => ({
+    label: t(`general.picture_in_picture.corners.${value}`),
+    value,
+  }));
+
+  return (
+    <SettingsGroup>
+      <SettingsSubGroup
+        label={
+          <SettingsOption>
+            <b>{t('general.picture_in_picture.enabled')}</b>
+            <Switch checked={settings.enabled} onChange={(enabled) => onChange({ enabled })} />
+          </SettingsOption>
+        }
+      >
+        <SettingsOption>
+          <span>{t('general.picture_in_picture.corner')}</span>
+          <Select
+            value={settings.corner}
+            options={cornerOptions}
+            disabled={!settings.enabled}
+            onChange={(corner) => onChange({ corner })}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('general.picture_in_picture.margin')}</span>
+          <InputNumber
+            min={0}
+            max={200}
+            value={settings.margin}
+            disabled={!settings.enabled}
+            onChange={(margin) => onChange({ margin: margin || 0 })}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('general.picture_in_picture.sizes')}</span>
+          <Select
+            mode="tags"
+            value={settings.sizes.map(String)}
+            disabled={!settings.enabled}
+            onChange={onChangeSizes}
+          />
+        </SettingsOption>
+      </SettingsSubGroup>
+    </SettingsGroup>
+  );
+}
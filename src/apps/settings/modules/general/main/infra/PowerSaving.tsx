@@ -0,0 +1,71 @@
+import { InputNumber, Switch } from 'antd';
+import { useTranslation } from 'react-i18next';
+import { useDispatch, useSelector } from 'react-redux';
+import { PowerSavingSettings } from 'seelen-core';
+
+import { newSelectors, RootActions } from '../../../shared/store/app/reducer';
+
+import {
+  SettingsGroup,
+  SettingsOption,
+  SettingsSubGroup,
+} from '../../../../components/SettingsBox';
+
+export function PowerSaving() {
+  const settings = useSelector(newSelectors.powerSaving);
+
+  const { t } = useTranslation();
+  const dispatch = useDispatch();
+
+  const onChange = (changes: Partial<PowerSavingSettings>) => {
+    dispatch(RootActions.setPowerSaving({ ...settings, ...changes }));
+  };
+
+  return (
+    <SettingsGroup>
+      <SettingsSubGroup
+        label={
+          <SettingsOption>
+            <b>{t('general.power_saving.enabled')}</b>
+            <Switch checked={settings.enabled} onChange={(enabled) => onChange({ enabled })} />
+          </SettingsOption>
+        }
+      >
+        <SettingsOption>
+          <span>{t('general.power_saving.max_emit_rate')}</span>
+          <InputNumber
+            min={1}
+            max={120}
+            value={settings.maxEmitRate}
+            disabled={!settings.enabled}
+            onChange={(maxEmitRate) => onChange({ maxEmitRate: maxEmitRate || 1 })}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('general.power_saving.pause_wallpaper')}</span>
+          <Switch
+            checked={settings.pauseWallpaper}
+            disabled={!settings.enabled}
+            onChange={(pauseWallpaper) => onChange({ pauseWallpaper })}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('general.power_saving.reduce_sampling')}</span>
+          <Switch
+            checked={settings.reduceSampling}
+            disabled={!settings.enabled}
+            onChange={(reduceSampling) => onChange({ reduceSampling })}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('general.power_saving.disable_animations')}</span>
+          <Switch
+            checked={settings.disableAnimations}
+            disabled={!settings.enabled}
+            onChange={(disableAnimations) => onChange({ disableAnimations })}
+          />
+        </SettingsOption>
+      </SettingsSubGroup>
+    </SettingsGroup>
+  );
+}
@@ -0,0 +1,67 @@
+import { InputNumber, Switch } from 'antd';
+import { useTranslation } from 'react-i18next';
+import { useDispatch, useSelector } from 'react-redux';
+import { ResourceGovernorSettings } from 'seelen-core';
+
+import { newSelectors, RootActions } from '../../../shared/store/app/reducer';
+
+import {
+  SettingsGroup,
+  SettingsOption,
+  SettingsSubGroup,
+} from '../../../../components/SettingsBox';
+
+export function ResourceGovernor() {
+  const settings = useSelector(newSelectors.resourceGovernor);
+
+  const { t } = useTranslation();
+  const dispatch = useDispatch();
+
+  const onChange = (changes: Partial<ResourceGovernorSettings>) => {
+    dispatch(RootActions.setResourceGovernor({ ...settings, ...changes }));
+  };
+
+  return (
+    <SettingsGroup>
+      <SettingsSubGroup
+        label={
+          <SettingsOption>
+            <b>{t('general.resource_governor.enabled')}</b>
+            <Switch checked={settings.enabled} onChange={(enabled) => onChange({ enabled })} />
+          </SettingsOption>
+        }
+      >
+        <SettingsOption>
+          <span>{t('general.resource_governor.suspend_after')}</span>
+          <InputNumber
+            min={30}
+            max={3600}
+            value={settings.suspendAfter}
+            disabled={!settings.enabled}
+            onChange={(suspendAfter) => onChange({ suspendAfter: suspendAfter || 30 })}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('general.resource_governor.suspend_on_display_off')}</span>
+          <Switch
+            checked={settings.suspendOnDisplayOff}
+            disabled={!settings.enabled}
+            onChange={(suspendOnDisplayOff) => onChange({ suspendOnDisplayOff })}
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('general.resource_governor.memory_pressure_threshold')}</span>
+          <InputNumber
+            min={50}
+            max={99}
+            value={settings.memoryPressureThreshold}
+            disabled={!settings.enabled}
+            onChange={(memoryPressureThreshold) =>
+              onChange({ memoryPressureThreshold: memoryPressureThreshold || 50 })
+            }
+          />
+        </SettingsOption>
+      </SettingsSubGroup>
+    </SettingsGroup>
+  );
+}
@@ -0,0 +1,61 @@
+import { InputNumber, Switch } from 'antd';
+import { useTranslation } from 'react-i18next';
+import { useDispatch, useSelector } from 'react-redux';
+import { StorageSettings } from 'seelen-core';
+
+import { newSelectors, RootActions } from '../../../shared/store/app/reducer';
+
+import {
+  SettingsGroup,
+  SettingsOption,
+  SettingsSubGroup,
+} from '../../../../components/SettingsBox';
+
+export function Storage() {
+  const settings = useSelector(newSelectors.storage);
+
+  const { t } = useTranslation();
+  const dispatch = useDispatch();
+
+  const onChange = (changes: Partial<StorageSettings>) => {
+    dispatch(RootActions.setStorage({ ...settings, ...changes }));
+  };
+
+  return (
+    <SettingsGroup>
+      <SettingsSubGroup
+        label={
+          <SettingsOption>
+            <b>{t('general.storage.alerts')}</b>
+            <Switch checked={settings.alerts} onChange={(alerts) => onChange({ alerts })} />
+          </SettingsOption>
+        }
+      >
+        <SettingsOption>
+          <span>{t('general.storage.low_space_threshold')}</span>
+          <InputNumber
+            min={1}
+            max={99}
+            value={settings.lowSpaceThreshold}
+            disabled={!settings.alerts}
+            onChange={(lowSpaceThreshold) =>
+              onChange({ lowSpaceThreshold: lowSpaceThreshold || 1 })
+            }
+          />
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('general.storage.temperature_threshold')}</span>
+          <InputNumber
+            min={30}
+            max={100}
+            value={settings.temperatureThreshold}
+            disabled={!settings.alerts}
+            onChange={(temperatureThreshold) =>
+              onChange({ temperatureThreshold: temperatureThreshold || 30 })
+            }
+          />
+        </SettingsOption>
+      </SettingsSubGroup>
+    </SettingsGroup>
+  );
+}
@@ -0,0 +1,43 @@
+import { Select, Switch } from 'antd';
+import { useTranslation } from 'react-i18next';
+import { useDispatch, useSelector } from 'react-redux';
+import { TitlebarMode, TitlebarSettings } from 'seelen-core';
+
+import { newSelectors, RootActions } from '../../../shared/store/app/reducer';
+
+import { SettingsGroup, SettingsOption } from '../../../../components/SettingsBox';
+
+export function Titlebar() {
+  const settings = useSelector(newSelectors.titlebar);
+
+  const { t } = useTranslation();
+  const dispatch = useDispatch();
+
+  const onChange = (changes: Partial<TitlebarSettings>) => {
+    dispatch(RootActions.setTitlebar({ ...settings, ...changes }));
+  };
+
+  return (
+    <SettingsGroup>
+      <SettingsOption>
+        <b>{t('general.titlebar.mode')}</b>
+        <Select
+          style={{ width: '200px' }}
+          value={settings.mode}
+          options={Object.values(TitlebarMode).map((mode) => ({
+            value: mode,
+            label: t(`general.titlebar.modes.${mode.toLowerCase()}`),
+          }))}
+          onSelect={(mode) => onChange({ mode })}
+        />
+      </SettingsOption>
+      <SettingsOption>
+        <span>{t('general.titlebar.accent_caption')}</span>
+        <Switch
+          checked={settings.accentCaption}
+          onChange={(accentCaption) => onChange({ accentCaption })}
+        />
+      </SettingsOption>
+    </SettingsGroup>
+  );
+}
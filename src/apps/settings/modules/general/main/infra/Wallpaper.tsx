@@ -1,18 +1,24 @@
 import { convertFileSrc, invoke } from '@tauri-apps/api/core';
-import { Button } from 'antd';
+import { Button, Select } from 'antd';
+import { useState } from 'react';
 import { useTranslation } from 'react-i18next';
-import { useDispatch } from 'react-redux';
-import { SeelenCommand } from 'seelen-core';
+import { useDispatch, useSelector } from 'react-redux';
+import { SeelenCommand, WallpaperFit } from 'seelen-core';
 
 import { dialog } from '../../../shared/tauri/infra';
 
-import { RootActions } from '../../../shared/store/app/reducer';
+import { newSelectors, RootActions } from '../../../shared/store/app/reducer';
 
 import { Monitor } from '../../../../components/monitor';
-import { SettingsOption } from '../../../../components/SettingsBox';
+import { SettingsGroup, SettingsOption } from '../../../../components/SettingsBox';
 import cs from './index.module.css';
 
 export function Wallpaper() {
+  const [fit, setFit] = useState(WallpaperFit.Fill);
+  const [monitor, setMonitor] = useState<number | null>(null);
+
+  const monitors = useSelector(newSelectors.monitors);
+
   const { t } = useTranslation();
   const dispatch = useDispatch();
 
@@ -28,12 +34,12 @@ export function Wallpaper() {
       return;
     }
 
-    await invoke(SeelenCommand.StateSetWallpaper, { path: file });
+    await invoke(SeelenCommand.StateSetWallpaper, { path: file, fit, monitor });
     dispatch(RootActions.setWallpaper(convertFileSrc(file)));
   }
 
   return (
-    <>
+    <SettingsGroup>
       <SettingsOption>
         <Monitor />
         <div className={cs.wallpaperButton}>
@@ -42,6 +48,39 @@ export function Wallpaper() {
           </Button>
         </div>
       </SettingsOption>
-    </>
+      <SettingsOption>
+        <b>{t('general.wallpaper.fit')}</b>
+        <Select
+          style={{ width: '200px' }}
+          value={fit}
+          options={Object.values(WallpaperFit).map((value) => ({
+            label: t(`general.wallpaper.fits.${value.toLowerCase()}`),
+            value,
+          }))}
+          onChange={(fit) => {
+            setFit(fit);
+            if (fit === WallpaperFit.Span) {
+              setMonitor(null);
+            }
+          }}
+        />
+      </SettingsOption>
+      <SettingsOption>
+        <b>{t('general.wallpaper.monitor')}</b>
+        <Select
+          style={{ width: '200px' }}
+          value={monitor}
+          disabled={fit === WallpaperFit.Span}
+          options={[
+            { label: t('general.wallpaper.all_monitors'), value: null },
+            ...monitors.map((_, idx) => ({
+              label: t('monitors_configurations.label').replace('{{index}}', `${idx + 1}`),
+              value: idx,
+            })),
+          ]}
+          onChange={setMonitor}
+        />
+      </SettingsOption>
+    </SettingsGroup>
   );
 }
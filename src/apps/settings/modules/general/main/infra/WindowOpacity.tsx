@@ -0,0 +1,31 @@
+import { Switch } from 'antd';
+import { useTranslation } from 'react-i18next';
+import { useDispatch, useSelector } from 'react-redux';
+import { WindowOpacitySettings } from 'seelen-core';
+
+import { newSelectors, RootActions } from '../../../shared/store/app/reducer';
+
+import { SettingsGroup, SettingsOption } from '../../../../components/SettingsBox';
+
+export function WindowOpacity() {
+  const settings = useSelector(newSelectors.windowOpacity);
+
+  const { t } = useTranslation();
+  const dispatch = useDispatch();
+
+  const onChange = (changes: Partial<WindowOpacitySettings>) => {
+    dispatch(RootActions.setWindowOpacity({ ...settings, ...changes }));
+  };
+
+  return (
+    <SettingsGroup>
+      <SettingsOption>
+        <span>{t('general.window_opacity.remember_per_app')}</span>
+        <Switch
+          checked={settings.rememberPerApp}
+          onChange={(rememberPerApp) => onChange({ rememberPerApp })}
+        />
+      </SettingsOption>
+    </SettingsGroup>
+  );
+}
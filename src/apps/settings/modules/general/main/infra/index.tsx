@@ -1,5 +1,6 @@
-import { Input, Select, Switch, Tooltip } from 'antd';
-import { ChangeEvent, useState } from 'react';
+import { Input, InputNumber, Select, Switch, Tooltip } from 'antd';
+import { ChangeEvent, useEffect, useState } from 'react';
+import { StartupTaskOptions, TextDirection } from 'seelen-core';
 import { useTranslation } from 'react-i18next';
 import { useSelector } from 'react-redux';
 
@@ -12,19 +13,42 @@ import { Icon } from 'src/apps/shared/components/Icon';
 
 import { LanguageList } from '../../../../../shared/lang';
 import { SettingsGroup, SettingsOption } from '../../../../components/SettingsBox';
+import { AppUsage } from './AppUsage';
+import { Accessibility } from './Accessibility';
+import { BreakReminder } from './BreakReminder';
+import { Capture } from './Capture';
+import { Clocks } from './Clocks';
 import { Colors } from './Colors';
+import { HardwareKeys } from './HardwareKeys';
+import { NightLight } from './NightLight';
+import { PictureInPicture } from './PictureInPicture';
+import { PowerSaving } from './PowerSaving';
+import { ResourceGovernor } from './ResourceGovernor';
+import { Storage } from './Storage';
 import { Themes } from './Themes';
+import { Titlebar } from './Titlebar';
+import { Wallpaper } from './Wallpaper';
+import { WindowOpacity } from './WindowOpacity';
 
 export function General() {
   const [changingAutostart, setChangingAutostart] = useState(false);
+  const [startupOptions, setStartupOptions] = useState<StartupTaskOptions | null>(null);
 
   const autostartStatus = useSelector(RootSelectors.autostart);
   const language = useSelector(RootSelectors.language);
+  const textDirection = useSelector(RootSelectors.textDirection);
   const dateFormat = useSelector(RootSelectors.dateFormat);
+  const brightness = useSelector(RootSelectors.brightness);
 
   const { t } = useTranslation();
   const dispatch = useAppDispatch();
 
+  useEffect(() => {
+    if (autostartStatus) {
+      startup.status().then(({ elevated, delay }) => setStartupOptions({ elevated, delay }));
+    }
+  }, [autostartStatus]);
+
   const onAutoStart = async (value: boolean) => {
     setChangingAutostart(true);
     if (value) {
@@ -36,6 +60,14 @@ export function General() {
     dispatch(RootActions.setAutostart(value));
   };
 
+  const onStartupOptionsChange = async (options: StartupTaskOptions) => {
+    setChangingAutostart(true);
+    await startup.register(options).catch(console.error);
+    const { elevated, delay } = await startup.status();
+    setStartupOptions({ elevated, delay });
+    setChangingAutostart(false);
+  };
+
   const onDateFormatChange = (e: ChangeEvent<HTMLInputElement>) =>
     dispatch(RootActions.setDateFormat(e.target.value));
 
@@ -50,6 +82,28 @@ export function General() {
             loading={changingAutostart || autostartStatus === null}
           />
         </SettingsOption>
+        {autostartStatus && startupOptions && (
+          <>
+            <SettingsOption>
+              <span>{t('general.startup_elevated')}</span>
+              <Switch
+                value={startupOptions.elevated}
+                loading={changingAutostart}
+                onChange={(elevated) => onStartupOptionsChange({ ...startupOptions, elevated })}
+              />
+            </SettingsOption>
+            <SettingsOption>
+              <span>{t('general.startup_delay')}</span>
+              <InputNumber
+                min={0}
+                max={600}
+                value={startupOptions.delay}
+                disabled={changingAutostart}
+                onChange={(delay) => onStartupOptionsChange({ ...startupOptions, delay: delay || 0 })}
+              />
+            </SettingsOption>
+          </>
+        )}
       </SettingsGroup>
       <SettingsGroup>
         <SettingsOption>
@@ -63,6 +117,18 @@ export function General() {
             onSelect={(value) => dispatch(RootActions.setLanguage(value))}
           />
         </SettingsOption>
+        <SettingsOption>
+          <b>{t('general.text_direction.label')}</b>
+          <Select
+            style={{ width: '200px' }}
+            value={textDirection}
+            options={Object.values(TextDirection).map((value) => ({
+              label: t(`general.text_direction.options.${value}`),
+              value,
+            }))}
+            onSelect={(value) => dispatch(RootActions.setTextDirection(value))}
+          />
+        </SettingsOption>
         <SettingsOption>
           <div style={{ display: 'flex', alignItems: 'center', gap: '6px' }}>
             <b>{t('general.date_format')}</b>
@@ -85,8 +151,46 @@ export function General() {
         </SettingsOption>
       </SettingsGroup>
 
+      <Wallpaper />
+
       <Colors />
 
+      <NightLight />
+
+      <SettingsGroup>
+        <SettingsOption>
+          <b>{t('general.brightness.linked')}</b>
+          <Switch
+            value={brightness.linked}
+            onChange={(linked) => dispatch(RootActions.setBrightness({ ...brightness, linked }))}
+          />
+        </SettingsOption>
+      </SettingsGroup>
+
+      <HardwareKeys />
+
+      <Capture />
+
+      <AppUsage />
+
+      <BreakReminder />
+
+      <Accessibility />
+
+      <PowerSaving />
+
+      <ResourceGovernor />
+
+      <PictureInPicture />
+
+      <Clocks />
+
+      <Storage />
+
+      <Titlebar />
+
+      <WindowOpacity />
+
       <SettingsGroup>
         <div style={{ marginBottom: '6px' }}>
           <b>{t('general.theme.label')}</b>
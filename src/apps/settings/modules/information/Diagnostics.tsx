@@ -0,0 +1,76 @@
+import { invoke } from '@tauri-apps/api/core';
+import { Button, message, Switch } from 'antd';
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useDispatch, useSelector } from 'react-redux';
+import { CrashReport, SeelenCommand } from 'seelen-core';
+
+import { newSelectors, RootActions } from '../shared/store/app/reducer';
+
+import { SettingsGroup, SettingsOption, SettingsSubGroup } from '../../components/SettingsBox';
+
+export function Diagnostics() {
+  const [reports, setReports] = useState<CrashReport[]>([]);
+  const [exporting, setExporting] = useState(false);
+
+  const settings = useSelector(newSelectors.diagnostics);
+
+  const dispatch = useDispatch();
+  const { t } = useTranslation();
+
+  const loadReports = () => {
+    invoke<CrashReport[]>(SeelenCommand.DiagnosticsGetReports).then(setReports);
+  };
+
+  useEffect(loadReports, []);
+
+  const onExport = async () => {
+    setExporting(true);
+    try {
+      const path = await invoke<string>(SeelenCommand.DiagnosticsExport);
+      message.success(t('extras.diagnostics.exported', { path }));
+      await invoke(SeelenCommand.SelectFileOnExplorer, { path });
+    } catch (e) {
+      message.error(String(e));
+    }
+    setExporting(false);
+  };
+
+  const onClear = async () => {
+    await invoke(SeelenCommand.DiagnosticsClear);
+    loadReports();
+  };
+
+  return (
+    <SettingsGroup>
+      <SettingsSubGroup
+        label={
+          <SettingsOption>
+            <span>{t('extras.diagnostics.crash_reports')}</span>
+            <Switch
+              value={settings.crashReports}
+              onChange={(crashReports) =>
+                dispatch(RootActions.setDiagnostics({ ...settings, crashReports }))
+              }
+            />
+          </SettingsOption>
+        }
+      >
+        <SettingsOption>
+          <span>
+            {t('extras.diagnostics.reports')}: {reports.length}
+          </span>
+          <Button danger disabled={!reports.length} onClick={onClear}>
+            {t('extras.diagnostics.clear')}
+          </Button>
+        </SettingsOption>
+        <SettingsOption>
+          <span>{t('extras.diagnostics.export')}</span>
+          <Button loading={exporting} onClick={onExport}>
+            {t('extras.diagnostics.export')}
+          </Button>
+        </SettingsOption>
+      </SettingsSubGroup>
+    </SettingsGroup>
+  );
+}
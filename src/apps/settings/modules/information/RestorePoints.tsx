@@ -0,0 +1,67 @@
+import { invoke } from '@tauri-apps/api/core';
+import { Button, message, Popconfirm } from 'antd';
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { RestorePoint, SeelenCommand } from 'seelen-core';
+
+import { SettingsGroup, SettingsOption, SettingsSubGroup } from '../../components/SettingsBox';
+
+export function RestorePoints() {
+  const [points, setPoints] = useState<RestorePoint[]>([]);
+
+  const { t } = useTranslation();
+
+  const loadPoints = () => {
+    invoke<RestorePoint[]>(SeelenCommand.RestorePointsGetAll).then(setPoints);
+  };
+
+  useEffect(loadPoints, []);
+
+  const run = async (command: SeelenCommand, args?: Record<string, unknown>) => {
+    try {
+      await invoke(command, args);
+    } catch (e) {
+      message.error(String(e));
+    }
+    loadPoints();
+  };
+
+  return (
+    <SettingsGroup>
+      <SettingsSubGroup
+        label={
+          <SettingsOption>
+            <span>{t('extras.restore_points.label')}</span>
+            <Button onClick={() => run(SeelenCommand.RestorePointsCreate)}>
+              {t('extras.restore_points.create')}
+            </Button>
+          </SettingsOption>
+        }
+      >
+        {points.map((point) => (
+          <SettingsOption key={point.id}>
+            <span>
+              {new Date(point.timestamp).toLocaleString()} -{' '}
+              {t(`extras.restore_points.reasons.${point.reason}`)}
+              {point.source ? ` (${point.source})` : ''}
+            </span>
+            <Button.Group>
+              <Popconfirm
+                title={t('extras.restore_points.apply_confirm')}
+                onConfirm={() => run(SeelenCommand.RestorePointsApply, { id: point.id })}
+              >
+                <Button>{t('extras.restore_points.apply')}</Button>
+              </Popconfirm>
+              <Button
+                danger
+                onClick={() => run(SeelenCommand.RestorePointsDelete, { id: point.id })}
+              >
+                {t('extras.restore_points.delete')}
+              </Button>
+            </Button.Group>
+          </SettingsOption>
+        ))}
+      </SettingsSubGroup>
+    </SettingsGroup>
+  );
+}
@@ -6,6 +6,8 @@ import { useDispatch, useSelector } from 'react-redux';
 import { UpdateChannel } from 'seelen-core';
 
 import { EnvConfig } from '../shared/config/infra';
+import { Diagnostics } from './Diagnostics';
+import { RestorePoints } from './RestorePoints';
 import cs from './infra.module.css';
 
 import { newSelectors, RootActions } from '../shared/store/app/reducer';
@@ -35,6 +37,10 @@ export function Information() {
     dispatch(RootActions.setUpdater({ ...updaterSettings, channel }));
   }
 
+  function onToggleAutoDownload(autoDownload: boolean) {
+    dispatch(RootActions.setUpdater({ ...updaterSettings, autoDownload }));
+  }
+
   return (
     <div className={cs.info}>
       <SettingsGroup>
@@ -77,8 +83,20 @@ export function Information() {
             options={Object.values(UpdateChannel).map((c) => ({ value: c, label: c }))}
           />
         </SettingsOption>
+        <SettingsOption>
+          <span>{t('update.auto_download')}</span>
+          <Switch
+            value={updaterSettings.autoDownload}
+            disabled={isMsixBuild}
+            onChange={onToggleAutoDownload}
+          />
+        </SettingsOption>
       </SettingsGroup>
 
+      <Diagnostics />
+
+      <RestorePoints />
+
       <SettingsGroup>
         <SettingsOption>
           <span>{t('extras.relaunch')}</span>
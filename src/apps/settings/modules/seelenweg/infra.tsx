@@ -1,9 +1,16 @@
 import { Button, InputNumber, Select, Switch } from 'antd';
 import { useTranslation } from 'react-i18next';
-import { HideMode, SeelenWegMode, SeelenWegSide } from 'seelen-core';
+import {
+  HideMode,
+  NativeTaskbarMode,
+  SeelenWegMode,
+  SeelenWegSide,
+  WegOrphanedItems,
+} from 'seelen-core';
 
 import { useAppDispatch, useAppSelector } from '../shared/utils/infra';
 
+import { newSelectors, RootActions } from '../shared/store/app/reducer';
 import { RootSelectors } from '../shared/store/app/selectors';
 import { OptionsFromEnum } from '../shared/utils/app';
 import { SeelenWegActions } from './app';
@@ -13,6 +20,7 @@ import { SettingsGroup, SettingsOption, SettingsSubGroup } from '../../component
 
 export const SeelenWegSettings = () => {
   const settings = useAppSelector(RootSelectors.seelenweg);
+  const nativeTaskbar = useAppSelector(newSelectors.nativeTaskbar);
 
   const dispatch = useAppDispatch();
   const { t } = useTranslation();
@@ -83,6 +91,29 @@ export const SeelenWegSettings = () => {
         </SettingsSubGroup>
       </SettingsGroup>
 
+      <SettingsGroup>
+        <SettingsSubGroup label={t('native_taskbar.label')}>
+          <SettingsOption>
+            <div>{t('native_taskbar.mode')}</div>
+            <Select
+              style={{ width: '120px' }}
+              value={nativeTaskbar.mode}
+              options={OptionsFromEnum(NativeTaskbarMode)}
+              onChange={(mode) => dispatch(RootActions.setNativeTaskbar({ ...nativeTaskbar, mode }))}
+            />
+          </SettingsOption>
+          <SettingsOption>
+            <div>{t('native_taskbar.suppress_start_menu')}</div>
+            <Switch
+              checked={nativeTaskbar.suppressStartMenu}
+              onChange={(suppressStartMenu) =>
+                dispatch(RootActions.setNativeTaskbar({ ...nativeTaskbar, suppressStartMenu }))
+              }
+            />
+          </SettingsOption>
+        </SettingsSubGroup>
+      </SettingsGroup>
+
       <SettingsGroup>
         <SettingsSubGroup label={t('weg.items.label')}>
           <SettingsOption>
@@ -113,6 +144,22 @@ export const SeelenWegSettings = () => {
               onChange={(value) => dispatch(SeelenWegActions.setVisibleSeparators(value))}
             />
           </SettingsOption>
+          <SettingsOption>
+            <div>{t('weg.items.pin_removable_drives')}</div>
+            <Switch
+              checked={settings.pinRemovableDrives}
+              onChange={(value) => dispatch(SeelenWegActions.setPinRemovableDrives(value))}
+            />
+          </SettingsOption>
+          <SettingsOption>
+            <div>{t('weg.items.orphaned_items')}</div>
+            <Select
+              style={{ width: '120px' }}
+              value={settings.orphanedItems}
+              options={OptionsFromEnum(WegOrphanedItems)}
+              onChange={(value) => dispatch(SeelenWegActions.setOrphanedItems(value))}
+            />
+          </SettingsOption>
         </SettingsSubGroup>
       </SettingsGroup>
     </>
@@ -30,10 +30,30 @@ export const StateToJsonSettings = (state: RootState): Settings => {
     'ahkVariables',
     'devTools',
     'language',
+    'textDirection',
     'dateFormat',
     'virtualDesktopStrategy',
+    'workspaceMonitorRules',
     'updater',
     'wall',
     'launcher',
+    'nativeTaskbar',
+    'nightLight',
+    'brightness',
+    'capture',
+    'appUsage',
+    'breakReminder',
+    'accessibility',
+    'powerSaving',
+    'diagnostics',
+    'windowFilters',
+    'remoteControl',
+    'titlebar',
+    'windowOpacity',
+    'storage',
+    'resourceGovernor',
+    'pictureInPicture',
+    'clock',
+    'hardwareKeys',
   ]);
 };
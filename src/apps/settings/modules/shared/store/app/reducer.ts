@@ -1,12 +1,31 @@
 import { createSlice, PayloadAction } from '@reduxjs/toolkit';
 import { cloneDeep, pick } from 'lodash';
 import {
+  AccessibilitySettings,
+  AppUsageSettings,
+  BreakReminderSettings,
+  DiagnosticsSettings,
+  BrightnessSettings,
+  CaptureSettings,
+  ClockSettings,
+  HardwareKeysSettings,
+  NativeTaskbarSettings,
+  NightLightSettings,
+  PictureInPictureSettings,
+  PowerSavingSettings,
+  RemoteControlSettings,
+  ResourceGovernorSettings,
   SeelenLauncherSettings,
   SeelenWallSettings,
   SeelenWegSettings,
+  StorageSettings,
+  TextDirection,
+  TitlebarSettings,
   UIColors,
   UpdateChannel,
   VirtualDesktopStrategy,
+  WindowFiltersSettings,
+  WindowOpacitySettings,
 } from 'seelen-core';
 
 import { AppsConfigSlice } from '../../../appsConfigurations/app/reducer';
@@ -44,13 +63,33 @@ const initialState: RootState = {
   selectedThemes: [],
   devTools: false,
   language: navigator.language.split('-')[0] || 'en',
+  textDirection: TextDirection.Auto,
   dateFormat: 'ddd D MMM, hh:mm A',
   colors: UIColors.default(),
   wallpaper: null,
   virtualDesktopStrategy: VirtualDesktopStrategy.Native,
+  workspaceMonitorRules: [],
   updater: {
     channel: UpdateChannel.Release,
   },
+  nativeTaskbar: new NativeTaskbarSettings(),
+  nightLight: new NightLightSettings(),
+  brightness: new BrightnessSettings(),
+  capture: new CaptureSettings(),
+  appUsage: new AppUsageSettings(),
+  breakReminder: new BreakReminderSettings(),
+  accessibility: new AccessibilitySettings(),
+  powerSaving: new PowerSavingSettings(),
+  diagnostics: new DiagnosticsSettings(),
+  windowFilters: new WindowFiltersSettings(),
+  remoteControl: new RemoteControlSettings(),
+  titlebar: new TitlebarSettings(),
+  windowOpacity: new WindowOpacitySettings(),
+  storage: new StorageSettings(),
+  resourceGovernor: new ResourceGovernorSettings(),
+  pictureInPicture: new PictureInPictureSettings(),
+  clock: new ClockSettings(),
+  hardwareKeys: new HardwareKeysSettings(),
 };
 
 function toBeSaved<S, A, R>(fn: (state: S, action: A) => R) {
@@ -79,11 +118,30 @@ export const RootSlice = createSlice({
       return action.payload;
     },
     setDateFormat: toBeSaved(reducers.setDateFormat),
+    setTextDirection: toBeSaved(reducers.setTextDirection),
     setWall: toBeSaved(reducers.setWall),
     setLauncher: toBeSaved(reducers.setLauncher),
     setDevTools: toBeSaved(reducers.setDevTools),
     setUpdater: toBeSavedAndRestarted(reducers.setUpdater),
     setMonitors: toBeSaved(reducers.setMonitors),
+    setNativeTaskbar: toBeSaved(reducers.setNativeTaskbar),
+    setNightLight: toBeSaved(reducers.setNightLight),
+    setBrightness: toBeSaved(reducers.setBrightness),
+    setCapture: toBeSaved(reducers.setCapture),
+    setAppUsage: toBeSaved(reducers.setAppUsage),
+    setBreakReminder: toBeSaved(reducers.setBreakReminder),
+    setAccessibility: toBeSaved(reducers.setAccessibility),
+    setPowerSaving: toBeSaved(reducers.setPowerSaving),
+    setDiagnostics: toBeSaved(reducers.setDiagnostics),
+    setWindowFilters: toBeSaved(reducers.setWindowFilters),
+    setRemoteControl: toBeSaved(reducers.setRemoteControl),
+    setTitlebar: toBeSaved(reducers.setTitlebar),
+    setWindowOpacity: toBeSaved(reducers.setWindowOpacity),
+    setStorage: toBeSaved(reducers.setStorage),
+    setResourceGovernor: toBeSaved(reducers.setResourceGovernor),
+    setPictureInPicture: toBeSaved(reducers.setPictureInPicture),
+    setClock: toBeSaved(reducers.setClock),
+    setHardwareKeys: toBeSaved(reducers.setHardwareKeys),
     setLanguage: (state, action: PayloadAction<string>) => {
       state.language = action.payload;
       state.toBeSaved = true;
@@ -1,9 +1,19 @@
 import { configureStore } from '@reduxjs/toolkit';
+import { invoke } from '@tauri-apps/api/core';
 import { listen as listenGlobal } from '@tauri-apps/api/event';
 import { Modal } from 'antd';
 import { cloneDeep } from 'lodash';
-import { AppConfiguration, SeelenEvent, Settings, Theme, UIColors } from 'seelen-core';
-
+import {
+  AppConfiguration,
+  SeelenCommand,
+  SeelenEvent,
+  Settings,
+  SettingsConflict,
+  Theme,
+  UIColors,
+} from 'seelen-core';
+
+import { ShowSettingsConflict } from '../../SettingsConflict/infra';
 import { startup } from '../tauri/infra';
 
 import { RootActions, RootReducer } from './app/reducer';
@@ -14,6 +24,18 @@ import { RootState } from './domain';
 import { saveUserSettings, UserSettingsLoader } from './storeApi';
 
 const IsSavingSettings = { current: false };
+/** hash of settings.json from which the current state was loaded */
+const SettingsHash = { current: '' };
+
+async function refreshSettingsHash() {
+  SettingsHash.current = await invoke<string>(SeelenCommand.StateGetSettingsHash);
+}
+
+function onSettingsConflictResolved() {
+  // the resolved settings will be received on the settings changed event
+  store.dispatch(RootActions.setToBeSaved(false));
+  LoadSettingsToStore();
+}
 
 export const store = configureStore({
   reducer: RootReducer,
@@ -61,6 +83,10 @@ export async function registerStoreEvents() {
       return;
     }
     const currentState = store.getState();
+    // unsaved changes are kept, saving them will raise a conflict as the hash is outdated
+    if (currentState.toBeSaved) {
+      return;
+    }
     const newState: RootState = {
       ...currentState,
       ...event.payload,
@@ -68,7 +94,17 @@ export async function registerStoreEvents() {
       toBeRestarted: false,
     };
     store.dispatch(RootActions.setState(newState));
+    refreshSettingsHash();
   });
+
+  await listenGlobal<SettingsConflict>(SeelenEvent.StateSettingsConflict, () => {
+    ShowSettingsConflict(onSettingsConflictResolved);
+  });
+
+  // conflicts raised while the settings window was closed
+  if (await invoke(SeelenCommand.StateGetSettingsConflict)) {
+    ShowSettingsConflict(onSettingsConflictResolved);
+  }
 }
 
 export const LoadSettingsToStore = async (customPath?: string) => {
@@ -76,6 +112,7 @@ export const LoadSettingsToStore = async (customPath?: string) => {
     store.dispatch(RootActions.setAutostart(value));
   });
 
+  await refreshSettingsHash();
   const userSettings = await new UserSettingsLoader()
     .withLayouts()
     .withPlaceholders()
@@ -104,7 +141,13 @@ export const SaveStore = async () => {
     };
 
     IsSavingSettings.current = true;
-    await saveUserSettings(settings);
+    const hash = await saveUserSettings(settings, SettingsHash.current);
+    if (hash === null) {
+      // not saved, the conflict is resolved by the user
+      IsSavingSettings.current = false;
+      return;
+    }
+    SettingsHash.current = hash;
 
     let newState = {
       ...currentState,
@@ -94,15 +94,23 @@ export async function saveJsonSettings(settings: UserSettings['jsonSettings']) {
   );
 }
 
+/**
+ * @param baseHash hash of settings.json when the settings were loaded
+ * @returns the new hash of settings.json or null if it was modified by someone else meanwhile
+ */
 export async function saveUserSettings(
   settings: Pick<UserSettings, 'jsonSettings' | 'yamlSettings'>,
-) {
+  baseHash: string,
+): Promise<string | null> {
   const yaml_route = await resolveDataPath('applications.yml');
   await fs.writeTextFile(
     yaml_route,
     yaml.dump(settings.yamlSettings.filter((app) => !app.isBundled)),
   );
-  await saveJsonSettings(settings.jsonSettings);
+  return invoke<string | null>(SeelenCommand.StateWriteSettings, {
+    settings: settings.jsonSettings,
+    baseHash,
+  });
 }
 
 export async function ImportApps() {
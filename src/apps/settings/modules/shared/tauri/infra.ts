@@ -1,6 +1,6 @@
 import { invoke } from '@tauri-apps/api/core';
 import * as oldStartup from '@tauri-apps/plugin-autostart';
-import { SeelenCommand } from 'seelen-core';
+import { SeelenCommand, StartupTaskOptions, StartupTaskStatus } from 'seelen-core';
 
 export * as dialog from '@tauri-apps/plugin-dialog';
 export * as fs from '@tauri-apps/plugin-fs';
@@ -22,4 +22,12 @@ export class startup {
     }
     return await invoke<boolean>('get_auto_start_status');
   }
-}
\ No newline at end of file
+
+  static async status(): Promise<StartupTaskStatus> {
+    return await invoke<StartupTaskStatus>(SeelenCommand.StartupGetStatus);
+  }
+
+  static async register(options: StartupTaskOptions): Promise<void> {
+    await invoke(SeelenCommand.StartupRegister, { options });
+  }
+}
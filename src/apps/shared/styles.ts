@@ -1,7 +1,15 @@
 import { listen } from '@tauri-apps/api/event';
 import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
 import { useEffect, useState } from 'react';
-import { Settings, Theme, UIColors } from 'seelen-core';
+import {
+  LayoutDirectionState,
+  LiveThemeCss,
+  PowerProfileState,
+  Settings,
+  Theme,
+  ThemeCssByApp,
+  UIColors,
+} from 'seelen-core';
 
 import { UserSettingsLoader } from '../settings/modules/shared/store/storeApi';
 
@@ -52,6 +60,14 @@ const KeyByLabel: Record<string, string> = {
   'seelen-wall': 'wall',
 };
 
+function getThemeKey() {
+  const [label, _monitor] = getCurrentWebviewWindow().label.split('/');
+  if (!label) {
+    return;
+  }
+  return KeyByLabel[label] as keyof ThemeCssByApp | undefined;
+}
+
 async function loadThemes(allThemes: Theme[], selected: string[]) {
   const themes = allThemes
     .filter((theme) => selected.includes(theme.info.filename))
@@ -60,12 +76,7 @@ async function loadThemes(allThemes: Theme[], selected: string[]) {
     });
 
   const webviewId = getCurrentWebviewWindow().label;
-  const [label, _monitor] = webviewId.split('/');
-  if (!label) {
-    return;
-  }
-
-  const theme_key = KeyByLabel[label] as keyof Theme['styles'] | undefined;
+  const theme_key = getThemeKey();
   if (!theme_key) {
     return;
   }
@@ -83,6 +94,28 @@ async function loadThemes(allThemes: Theme[], selected: string[]) {
   document.head.appendChild(element);
 }
 
+/** surfaces mirrored on right-to-left layouts, the wm and wall layouts follow the screen */
+const MirroredOnRtl: (keyof ThemeCssByApp | undefined)[] = ['toolbar', 'weg', 'launcher'];
+
+/** Unlayered, so it overrides the selected themes while they are being developed */
+function loadLiveCss(css: ThemeCssByApp) {
+  const theme_key = getThemeKey();
+  if (!theme_key) {
+    return;
+  }
+
+  const id = `${getCurrentWebviewWindow().label}-live`;
+  document.getElementById(id)?.remove();
+  if (!css[theme_key]) {
+    return;
+  }
+
+  let element = document.createElement('style');
+  element.id = id;
+  element.textContent = css[theme_key];
+  document.head.appendChild(element);
+}
+
 export async function StartThemingTool() {
   const userSettings = await new UserSettingsLoader().withThemes().load();
   let allThemes = userSettings.themes;
@@ -101,5 +134,22 @@ export async function StartThemingTool() {
   UIColors.setAssCssVariables(await UIColors.getAsync());
   UIColors.onChange(UIColors.setAssCssVariables);
 
+  const setPowerSaving = (state: PowerProfileState) => {
+    document.documentElement.classList.toggle('power-saving', state.disableAnimations);
+  };
+  setPowerSaving(await PowerProfileState.getAsync());
+  PowerProfileState.onChange(setPowerSaving);
+
+  if (MirroredOnRtl.includes(getThemeKey())) {
+    const setDirection = (state: LayoutDirectionState) => {
+      document.documentElement.dir = state.rtl ? 'rtl' : 'ltr';
+    };
+    setDirection(await LayoutDirectionState.getAsync());
+    LayoutDirectionState.onChange(setDirection);
+  }
+
   await loadThemes(allThemes, selected);
+
+  loadLiveCss(await LiveThemeCss.getAsync());
+  LiveThemeCss.onChange(loadLiveCss);
 }
@@ -1,7 +1,7 @@
 import { createRoot } from 'react-dom/client';
 import { I18nextProvider } from 'react-i18next';
 import { Provider } from 'react-redux';
-import { declareDocumentAsLayeredHitbox } from 'seelen-core';
+import { declareDocumentAsLayeredHitbox, respondHeartbeats } from 'seelen-core';
 
 import { loadStore, registerStoreEvents, store } from './modules/shared/store/infra';
 import { loadConstants } from './modules/shared/utils/infra';
@@ -19,6 +19,7 @@ import './styles/global.css';
 
 async function Main() {
   wrapConsole();
+  await respondHeartbeats();
   await declareDocumentAsLayeredHitbox();
   await loadConstants();
   await loadStore();
@@ -1,7 +1,6 @@
 import moment from 'moment';
-import { useState } from 'react';
 import { useSelector } from 'react-redux';
-import { DateToolbarModule, useInterval } from 'seelen-core';
+import { ClockEntry, DateToolbarModule } from 'seelen-core';
 
 import { Item } from '../item/infra/infra';
 
@@ -13,17 +12,14 @@ interface Props {
 
 export function DateModule({ module }: Props) {
   const dateFormat = useSelector(Selectors.dateFormat);
+  // ticks are emitted by the background aligned to the second or minute
+  const clock = useSelector(Selectors.clock);
 
-  const [date, setDate] = useState(moment().format(dateFormat));
+  const date = moment(clock.time).format(dateFormat);
+  const clocks = clock.clocks.reduce<Record<string, ClockEntry>>((acc, entry) => {
+    acc[entry.id] = entry;
+    return acc;
+  }, {});
 
-  let interval = dateFormat.includes('ss') ? 1000 : 1000 * 60;
-  useInterval(
-    () => {
-      setDate(moment().format(dateFormat));
-    },
-    interval,
-    [dateFormat],
-  );
-
-  return <Item extraVars={{ date }} module={module} />;
+  return <Item extraVars={{ date, clocks }} module={module} />;
 }
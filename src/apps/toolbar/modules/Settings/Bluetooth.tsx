@@ -0,0 +1,72 @@
+import { invoke } from '@tauri-apps/api/core';
+import { Switch, Tooltip } from 'antd';
+import { memo } from 'react';
+import { useTranslation } from 'react-i18next';
+import { useSelector } from 'react-redux';
+import { BluetoothDevice, SeelenCommand } from 'seelen-core';
+
+import { Selectors } from '../shared/store/app';
+
+import { Icon } from '../../../shared/components/Icon';
+
+const BluetoothDeviceItem = memo(({ device }: { device: BluetoothDevice }) => {
+  const { t } = useTranslation();
+
+  const onClick = () => {
+    const command = device.connected
+      ? SeelenCommand.BluetoothDisconnect
+      : SeelenCommand.BluetoothConnect;
+    invoke(command, { address: device.address }).catch(console.error);
+  };
+
+  return (
+    <div className="fast-settings-item fast-settings-bluetooth-device">
+      <Icon iconName={device.connected ? 'MdBluetoothConnected' : 'MdBluetooth'} />
+      <span className="fast-settings-bluetooth-device-name">{device.name}</span>
+      {device.battery !== null && (
+        <span className="fast-settings-bluetooth-device-battery">{device.battery}%</span>
+      )}
+      <Tooltip
+        mouseLeaveDelay={0}
+        arrow={false}
+        title={
+          device.connected ? t('settings.bluetooth.disconnect') : t('settings.bluetooth.connect')
+        }
+      >
+        <button className="fast-settings-item-button" onClick={onClick}>
+          <Icon iconName={device.connected ? 'MdLinkOff' : 'MdLink'} />
+        </button>
+      </Tooltip>
+    </div>
+  );
+});
+
+export function BluetoothSettings() {
+  const bluetooth = useSelector(Selectors.bluetooth);
+
+  const { t } = useTranslation();
+
+  if (!bluetooth.available) {
+    return null;
+  }
+
+  return (
+    <>
+      <span className="fast-settings-label">{t('settings.bluetooth.label')}</span>
+      <div className="fast-settings-item fast-settings-bluetooth-radio">
+        <Icon iconName={bluetooth.enabled ? 'MdBluetooth' : 'MdBluetoothDisabled'} />
+        <span>{bluetooth.enabled ? t('settings.bluetooth.on') : t('settings.bluetooth.off')}</span>
+        <Switch
+          size="small"
+          checked={bluetooth.enabled}
+          onChange={(enabled) => {
+            invoke(SeelenCommand.BluetoothSetEnabled, { enabled }).catch(console.error);
+          }}
+        />
+      </div>
+      {bluetooth.devices.map((device) => (
+        <BluetoothDeviceItem key={device.address} device={device} />
+      ))}
+    </>
+  );
+}
@@ -1,39 +1,70 @@
 import { invoke } from '@tauri-apps/api/core';
 import { emit } from '@tauri-apps/api/event';
 import { Popover, Slider, Tooltip } from 'antd';
-import React, { useEffect, useState } from 'react';
+import { debounce } from 'lodash';
+import React, { memo, useCallback, useEffect, useState } from 'react';
 import { useTranslation } from 'react-i18next';
-import { useSelector } from 'react-redux';
-import { SeelenCommand, useWindowFocusChange } from 'seelen-core';
+import { useDispatch, useSelector } from 'react-redux';
+import {
+  BluetoothState,
+  MonitorBrightness,
+  SeelenCommand,
+  useWindowFocusChange,
+} from 'seelen-core';
 import { SettingsToolbarModule } from 'seelen-core';
 
 import { BackgroundByLayersV2 } from '../../../seelenweg/components/BackgroundByLayers/infra';
 import { Item } from '../item/infra/infra';
 import { VolumeControl } from '../media/infra/MediaControls';
 
-import { Selectors } from '../shared/store/app';
+import { RootActions, Selectors } from '../shared/store/app';
 
 import { RootState } from '../shared/store/domain';
 
+import { BluetoothSettings } from './Bluetooth';
+
 import { Icon } from '../../../shared/components/Icon';
 
 interface Props {
   module: SettingsToolbarModule;
 }
 
-interface Brightness {
-  min: number;
-  max: number;
-  current: number;
-}
+const BrightnessControl = memo(({ monitor }: { monitor: MonitorBrightness }) => {
+  const [internalValue, setInternalValue] = useState(monitor.current);
+
+  useEffect(() => {
+    setInternalValue(monitor.current);
+  }, [monitor.current]);
+
+  const onExternalChange = useCallback(
+    debounce((brightness: number) => {
+      invoke(SeelenCommand.BrightnessSet, { id: monitor.id, brightness }).catch(console.error);
+    }, 100),
+    [monitor.id],
+  );
+
+  const onInternalChange = (value: number) => {
+    setInternalValue(value);
+    onExternalChange(value);
+  };
+
+  return (
+    <div className="fast-settings-item">
+      <Icon iconName="CiBrightnessUp" />
+      <Slider
+        value={internalValue}
+        onChange={onInternalChange}
+        min={monitor.min}
+        max={monitor.max}
+      />
+    </div>
+  );
+});
 
 export function SettingsModule({ module }: Props) {
   const [openPreview, setOpenPreview] = useState(false);
-  const [brightness, setBrightness] = useState<Brightness>({
-    min: 0,
-    max: 0,
-    current: 0,
-  });
+
+  const brightness = useSelector(Selectors.brightness);
 
   const defaultInput = useSelector((state: RootState) =>
     Selectors.mediaInputs(state).find((d) => d.is_default_multimedia),
@@ -43,18 +74,21 @@ export function SettingsModule({ module }: Props) {
   );
 
   const { t } = useTranslation();
+  const dispatch = useDispatch();
 
   useEffect(() => {
     emit('register-media-events');
+    emit('register-brightness-events');
+    emit('register-bluetooth-events');
   }, []);
 
   useEffect(() => {
-    invoke<Brightness>('get_main_monitor_brightness')
-      .then(setBrightness)
-      .catch(() => {
-        // TODO brightness is always failing
-        // console.error(e);
-      });
+    invoke<MonitorBrightness[]>(SeelenCommand.BrightnessGetAll)
+      .then((monitors) => dispatch(RootActions.setBrightness(monitors)))
+      .catch(console.error);
+    invoke<BluetoothState>(SeelenCommand.BluetoothGetState)
+      .then((state) => dispatch(RootActions.setBluetooth(state)))
+      .catch(console.error);
   }, [openPreview]);
 
   useWindowFocusChange((focused) => {
@@ -117,20 +151,31 @@ export function SettingsModule({ module }: Props) {
             </div>
           )}
 
-          {brightness.max > 0 && (
-            <div className="fast-settings-item">
-              <Icon iconName="CiBrightnessUp" />
-              <Slider
-                value={brightness.current}
-                onChange={(value) => setBrightness({ ...brightness, current: value })}
-                min={brightness.min}
-                max={brightness.max}
-              />
-            </div>
+          {brightness.length > 0 && (
+            <span className="fast-settings-label">{t('settings.brightness')}</span>
           )}
 
+          {brightness.map((monitor) => (
+            <BrightnessControl key={monitor.id} monitor={monitor} />
+          ))}
+
+          <BluetoothSettings />
+
           <span className="fast-settings-label">{t('settings.power')}</span>
           <div className="fast-settings-item fast-settings-power">
+            <Tooltip mouseLeaveDelay={0} arrow={false} title={t('settings.lock')}>
+              <button className="fast-settings-item-button" onClick={() => invoke(SeelenCommand.Lock)}>
+                <Icon iconName="BiLock" />
+              </button>
+            </Tooltip>
+            <Tooltip mouseLeaveDelay={0} arrow={false} title={t('settings.switch_user')}>
+              <button
+                className="fast-settings-item-button"
+                onClick={() => invoke(SeelenCommand.SwitchUser)}
+              >
+                <Icon iconName="BiUserCircle" />
+              </button>
+            </Tooltip>
             <Tooltip mouseLeaveDelay={0} arrow={false} title={t('settings.log_out')}>
               <button className="fast-settings-item-button" onClick={() => invoke(SeelenCommand.LogOut)}>
                 <Icon iconName="BiLogOut" />
@@ -0,0 +1,85 @@
+import { invoke } from '@tauri-apps/api/core';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
+import { Reorder } from 'framer-motion';
+import { useEffect, useRef } from 'react';
+import { useSelector } from 'react-redux';
+import { SeelenCommand, WidgetTM } from 'seelen-core';
+
+import { Selectors } from '../shared/store/app';
+
+interface Props {
+  module: WidgetTM;
+}
+
+/** Message sent by the widget to the toolbar via `window.parent.postMessage` */
+interface WidgetRequest {
+  id: number;
+  method: string;
+  params?: any;
+}
+
+export function WidgetModule({ module }: Props) {
+  const widget = useSelector(Selectors.widgets).find((w) => w.id === module.widgetId);
+  const frame = useRef<HTMLIFrameElement>(null);
+
+  useEffect(() => {
+    const unlistens: Promise<UnlistenFn>[] = [];
+
+    function reply(message: object) {
+      // sandboxed frames have an opaque origin so the target should be '*'
+      frame.current?.contentWindow?.postMessage(message, '*');
+    }
+
+    async function onMessage(e: MessageEvent<WidgetRequest>) {
+      if (!frame.current || e.source !== frame.current.contentWindow) {
+        return;
+      }
+
+      const { id, method, params } = e.data;
+      try {
+        // the backend validates the request against the widget permissions
+        const result = await invoke(SeelenCommand.WidgetCall, {
+          widgetId: module.widgetId,
+          method,
+          params,
+        });
+        if (method === 'subscribe') {
+          for (const event of params.events as string[]) {
+            unlistens.push(listen(event, ({ payload }) => reply({ event, payload })));
+          }
+        }
+        reply({ id, result });
+      } catch (error) {
+        reply({ id, error });
+      }
+    }
+
+    window.addEventListener('message', onMessage);
+    return () => {
+      window.removeEventListener('message', onMessage);
+      unlistens.forEach((unlisten) => unlisten.then((fn) => fn()));
+    };
+  }, [module.widgetId, widget?.html]);
+
+  if (!widget) {
+    return null;
+  }
+
+  return (
+    <Reorder.Item
+      as="div"
+      id={module.id}
+      value={module}
+      className="ft-bar-item"
+      style={module.style}
+    >
+      <iframe
+        ref={frame}
+        className="ft-bar-item-content ft-bar-widget"
+        title={widget.info.displayName}
+        sandbox="allow-scripts"
+        srcDoc={widget.html}
+      />
+    </Reorder.Item>
+  );
+}
@@ -15,6 +15,9 @@ interface Props {
 export function WorkspacesModule({ module }: Props) {
   const workspaces = useSelector(Selectors.workspaces);
   const activeWorkspace = useSelector(Selectors.activeWorkspace);
+  const urgentWindows = useSelector(Selectors.urgentWindows);
+
+  const isUrgent = (id: string) => urgentWindows.some((w) => w.workspace === id);
 
   const { mode } = module;
 
@@ -32,6 +35,7 @@ export function WorkspacesModule({ module }: Props) {
               onClick={() => invoke(SeelenCommand.SwitchWorkspace, { idx })}
               className={cx('workspace-dot', {
                 'workspace-dot-active': w.id === activeWorkspace,
+                'workspace-dot-urgent': isUrgent(w.id),
               })}
             />
           ))}
@@ -56,6 +60,7 @@ export function WorkspacesModule({ module }: Props) {
               className={cx('ft-bar-item', {
                 'ft-bar-item-clickable': true,
                 'ft-bar-item-active': w.id === activeWorkspace,
+                'ft-bar-item-urgent': isUrgent(w.id),
               })}
               onClick={() => invoke(SeelenCommand.SwitchWorkspace, { idx })}
             >
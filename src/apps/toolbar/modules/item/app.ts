@@ -1,6 +1,6 @@
 import { invoke } from '@tauri-apps/api/core';
 import { evaluate } from 'mathjs';
-import { SeelenCommand } from 'seelen-core';
+import { ColorFormat, SeelenCommand, ToolbarItemAction } from 'seelen-core';
 
 /** @deprecated remove on v2 */
 export enum Actions {
@@ -80,8 +80,57 @@ const ActionsScope = {
   copyClipboard(text: string) {
     navigator.clipboard.writeText(text);
   },
+  toggleAlwaysOnTop(hwnd?: number) {
+    invoke(SeelenCommand.ToggleAlwaysOnTop, { hwnd }).catch(console.error);
+  },
+  toggleNightLight() {
+    invoke(SeelenCommand.NightLightToggle).catch(console.error);
+  },
+  togglePrivateSession() {
+    invoke(SeelenCommand.PrivateSessionToggle).catch(console.error);
+  },
+  ejectDrive(letter: string) {
+    invoke(SeelenCommand.RemovableDriveEject, { letter }).catch(console.error);
+  },
+  pausePrinter(printer: string) {
+    invoke(SeelenCommand.PrinterPause, { printer }).catch(console.error);
+  },
+  resumePrinter(printer: string) {
+    invoke(SeelenCommand.PrinterResume, { printer }).catch(console.error);
+  },
+  pausePrintJob(printer: string, jobId: number) {
+    invoke(SeelenCommand.PrintJobPause, { printer, jobId }).catch(console.error);
+  },
+  resumePrintJob(printer: string, jobId: number) {
+    invoke(SeelenCommand.PrintJobResume, { printer, jobId }).catch(console.error);
+  },
+  cancelPrintJob(printer: string, jobId: number) {
+    invoke(SeelenCommand.PrintJobCancel, { printer, jobId }).catch(console.error);
+  },
+  openRecycleBin() {
+    invoke(SeelenCommand.OpenRecycleBin).catch(console.error);
+  },
+  emptyRecycleBin() {
+    invoke(SeelenCommand.EmptyRecycleBin).catch(console.error);
+  },
+  captureScreen() {
+    invoke(SeelenCommand.CaptureScreen).catch(console.error);
+  },
+  captureWindow() {
+    invoke(SeelenCommand.CaptureWindow).catch(console.error);
+  },
+  pickColor(format?: ColorFormat) {
+    invoke(SeelenCommand.ColorPickerPick, { format }).catch(console.error);
+  },
 };
 
+/** actions declared on the item are executed by the backend */
+export function runItemAction(action: ToolbarItemAction | null | undefined) {
+  if (action) {
+    invoke(SeelenCommand.ToolbarRunItemAction, { action }).catch(console.error);
+  }
+}
+
 export function safeEval(expression: string, scope: Scope) {
   try {
     evaluate(expression, scope);
@@ -1,7 +1,7 @@
 import { convertFileSrc, invoke } from '@tauri-apps/api/core';
 import { Tooltip } from 'antd';
 import { Reorder } from 'framer-motion';
-import { cloneDeep } from 'lodash';
+import { cloneDeep, throttle } from 'lodash';
 import { evaluate, isResultSet } from 'mathjs';
 import React, { PropsWithChildren, useEffect, useRef } from 'react';
 import { useTranslation } from 'react-i18next';
@@ -11,7 +11,7 @@ import { ToolbarModule } from 'seelen-core';
 import { LAZY_CONSTANTS } from '../../shared/utils/infra';
 
 import { Selectors } from '../../shared/store/app';
-import { performClick, safeEval, Scope } from '../app';
+import { performClick, runItemAction, safeEval, Scope } from '../app';
 
 import { Icon } from '../../../../shared/components/Icon';
 import { cx } from '../../../../shared/styles';
@@ -26,6 +26,11 @@ export interface InnerItemProps extends PropsWithChildren {
   onKeydown?: (e: React.KeyboardEvent) => void;
 }
 
+/** used by pinned items to find their source on the lists of the scope */
+function findBy(list: Record<string, any>[] | null | undefined, key: string, value: unknown) {
+  return list?.find((item) => item[key] === value) ?? null;
+}
+
 interface StringToElementProps {
   text: string;
 }
@@ -160,13 +165,15 @@ export function InnerItem(props: InnerItemProps) {
     clickable = true,
     ...rest
   } = props;
-  const { template, tooltip, onClick: oldOnClick, onClickV2, style, id, badge } = module;
+  const { template, tooltip, onClick: oldOnClick, onClickV2, actions, style, id, badge } = module;
 
   const [mounted, setMounted] = React.useState(false);
   const env = useSelector(Selectors.env);
 
   const { t } = useTranslation();
   const scope = useRef(new Scope());
+  // touchpads send a lot of wheel events for a single scroll
+  const throttledAction = useRef(throttle(runItemAction, 200));
 
   useEffect(() => {
     scope.current.loadInvokeActions();
@@ -177,6 +184,7 @@ export function InnerItem(props: InnerItemProps) {
     scope.current.set('imgFromUrl', StringToElement.imgFromUrl);
     scope.current.set('imgFromPath', StringToElement.imgFromPath);
     scope.current.set('imgFromExe', StringToElement.imgFromExe);
+    scope.current.set('findBy', findBy);
     setMounted(true);
   }, []);
 
@@ -217,7 +225,7 @@ export function InnerItem(props: InnerItemProps) {
         style={style}
         className={cx('ft-bar-item', {
           // onClickProp is omitted cuz it always comes via context menu dropdown wrapper
-          'ft-bar-item-clickable': clickable || oldOnClick || onClickV2,
+          'ft-bar-item-clickable': clickable || oldOnClick || onClickV2 || actions?.onClick,
           'ft-bar-item-active': active,
         })}
         onKeyDown={onKeydownProp}
@@ -229,6 +237,18 @@ export function InnerItem(props: InnerItemProps) {
           }
 
           performClick(oldOnClick, scope.current);
+          runItemAction(actions?.onClick);
+        }}
+        onAuxClick={(e) => {
+          // middle button
+          if (e.button === 1) {
+            runItemAction(actions?.onMiddleClick);
+          }
+        }}
+        onWheel={(e) => {
+          if (e.deltaY !== 0) {
+            throttledAction.current(e.deltaY < 0 ? actions?.onWheel.up : actions?.onWheel.down);
+          }
         }}
         value={module}
         as="div"
@@ -57,6 +57,26 @@ export function GenericItem({ module }: { module: GenericToolbarModule }) {
     name: 'None',
     title: 'No Window Focused',
     exe: null,
+    isAlwaysOnTop: false,
   };
-  return <Item module={module} extraVars={{ window }} />;
+  const nightLight = useSelector(Selectors.nightLight);
+  const privateSession = useSelector(Selectors.privateSession);
+  const removableDrives = useSelector(Selectors.removableDrives);
+  const printers = useSelector(Selectors.printers);
+  const recycleBin = useSelector(Selectors.recycleBin);
+  const ime = useSelector(Selectors.ime);
+  return (
+    <Item
+      module={module}
+      extraVars={{
+        window,
+        nightLight,
+        privateSession,
+        removableDrives,
+        printers,
+        recycleBin,
+        ime,
+      }}
+    />
+  );
 }
@@ -24,6 +24,7 @@ import { SavePlaceholderAsCustom } from './application';
 
 import { cx } from '../../../shared/styles';
 import { TrayModule } from '../Tray';
+import { WidgetModule } from '../Widget';
 import { WorkspacesModule } from '../Workspaces';
 
 const modulesByType: Record<ToolbarModuleType, JSXElementConstructor<{ module: any }>> = {
@@ -38,6 +39,7 @@ const modulesByType: Record<ToolbarModuleType, JSXElementConstructor<{ module: a
   [ToolbarModuleType.Media]: MediaModule,
   [ToolbarModuleType.Device]: DeviceModule,
   [ToolbarModuleType.Notifications]: NotificationsModule,
+  [ToolbarModuleType.Widget]: WidgetModule,
 };
 
 interface Props {
@@ -35,6 +35,34 @@ const initialState: RootState = {
   mediaOutputs: [],
   mediaInputs: [],
   notifications: [],
+  widgets: [],
+  nightLight: {
+    active: false,
+    manual: false,
+  },
+  privateSession: false,
+  brightness: [],
+  bluetooth: {
+    available: false,
+    enabled: false,
+    devices: [],
+  },
+  removableDrives: [],
+  printers: [],
+  recycleBin: {
+    items: 0,
+    size: 0,
+  },
+  ime: {
+    available: false,
+    enabled: false,
+    composing: false,
+  },
+  urgentWindows: [],
+  clock: {
+    time: Date.now(),
+    clocks: [],
+  },
   colors: UIColors.default(),
 };
 
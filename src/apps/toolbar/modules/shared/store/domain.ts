@@ -1,5 +1,18 @@
 import { SoftOpaque } from 'readable-types';
-import { FancyToolbarSettings, Settings } from 'seelen-core';
+import {
+  BluetoothState,
+  ClockState,
+  FancyToolbarSettings,
+  ImeState,
+  MonitorBrightness,
+  NightLightState,
+  Printer,
+  RecycleBinState,
+  RemovableDrive,
+  Settings,
+  UrgentWindow,
+  Widget,
+} from 'seelen-core';
 import { Placeholder } from 'seelen-core';
 
 import { WlanBssEntry } from '../../network/domain';
@@ -129,4 +142,15 @@ export interface RootState extends IRootState<FancyToolbarSettings>, Pick<Settin
   mediaOutputs: MediaDevice[];
   mediaInputs: MediaDevice[];
   notifications: AppNotification[];
+  widgets: Widget[];
+  nightLight: NightLightState;
+  privateSession: boolean;
+  brightness: MonitorBrightness[];
+  bluetooth: BluetoothState;
+  removableDrives: RemovableDrive[];
+  printers: Printer[];
+  recycleBin: RecycleBinState;
+  ime: ImeState;
+  urgentWindows: UrgentWindow[];
+  clock: ClockState;
 }
@@ -1,8 +1,24 @@
 import { configureStore } from '@reduxjs/toolkit';
+import { invoke } from '@tauri-apps/api/core';
 import { listen as listenGlobal } from '@tauri-apps/api/event';
 import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
 import { debounce, throttle } from 'lodash';
-import { SeelenEvent, UIColors } from 'seelen-core';
+import {
+  BluetoothState,
+  ClockState,
+  ImeState,
+  listenScheduled,
+  MonitorBrightness,
+  NightLightState,
+  Printer,
+  RecycleBinState,
+  RemovableDrive,
+  SeelenCommand,
+  SeelenEvent,
+  UIColors,
+  UrgentWindow,
+  WidgetList,
+} from 'seelen-core';
 import { FancyToolbarSettings } from 'seelen-core';
 
 import { IsSavingCustom } from '../../main/application';
@@ -54,7 +70,7 @@ export async function registerStoreEvents() {
   const onFocusChanged = debounce((app: FocusedApp) => {
     store.dispatch(RootActions.setFocused(app));
   }, 200);
-  await listenGlobal<FocusedApp>(SeelenEvent.GlobalFocusChanged, (e) => {
+  await listenScheduled<FocusedApp>(SeelenEvent.GlobalFocusChanged, (e) => {
     onFocusChanged(e.payload);
     if (e.payload.name != 'Seelen UI') {
       onFocusChanged.flush();
@@ -81,6 +97,11 @@ export async function registerStoreEvents() {
     store.dispatch(RootActions.setActiveWorkspace(event.payload));
   });
 
+  store.dispatch(RootActions.setUrgentWindows(await invoke(SeelenCommand.GetUrgentWindows)));
+  await listenGlobal<UrgentWindow[]>(SeelenEvent.GlobalUrgentChanged, (event) => {
+    store.dispatch(RootActions.setUrgentWindows(event.payload));
+  });
+
   await listenGlobal<TrayInfo[]>('tray-info', (event) => {
     store.dispatch(RootActions.setSystemTray(event.payload));
   });
@@ -120,6 +141,51 @@ export async function registerStoreEvents() {
     store.dispatch(RootActions.setWlanBssEntries(event.payload));
   });
 
+  store.dispatch(RootActions.setNightLight(await invoke(SeelenCommand.NightLightGetState)));
+  await listenGlobal<NightLightState>(SeelenEvent.NightLightChanged, (event) => {
+    store.dispatch(RootActions.setNightLight(event.payload));
+  });
+
+  store.dispatch(RootActions.setPrivateSession(await invoke(SeelenCommand.PrivateSessionGet)));
+  await listenGlobal<boolean>(SeelenEvent.PrivateSessionChanged, (event) => {
+    store.dispatch(RootActions.setPrivateSession(event.payload));
+  });
+
+  await listenGlobal<MonitorBrightness[]>(SeelenEvent.BrightnessChanged, (event) => {
+    store.dispatch(RootActions.setBrightness(event.payload));
+  });
+
+  await listenGlobal<BluetoothState>(SeelenEvent.BluetoothChanged, (event) => {
+    store.dispatch(RootActions.setBluetooth(event.payload));
+  });
+
+  store.dispatch(
+    RootActions.setRemovableDrives(await invoke(SeelenCommand.RemovableDrivesGetAll)),
+  );
+  await listenGlobal<RemovableDrive[]>(SeelenEvent.RemovableDrivesChanged, (event) => {
+    store.dispatch(RootActions.setRemovableDrives(event.payload));
+  });
+
+  store.dispatch(RootActions.setPrinters(await invoke(SeelenCommand.PrintersGetAll)));
+  await listenGlobal<Printer[]>(SeelenEvent.PrintersChanged, (event) => {
+    store.dispatch(RootActions.setPrinters(event.payload));
+  });
+
+  store.dispatch(RootActions.setRecycleBin(await invoke(SeelenCommand.GetRecycleBinState)));
+  await listenGlobal<RecycleBinState>(SeelenEvent.RecycleBinChanged, (event) => {
+    store.dispatch(RootActions.setRecycleBin(event.payload));
+  });
+
+  store.dispatch(RootActions.setIme(await invoke(SeelenCommand.ImeGetState)));
+  await listenGlobal<ImeState>(SeelenEvent.ImeChanged, (event) => {
+    store.dispatch(RootActions.setIme(event.payload));
+  });
+
+  store.dispatch(RootActions.setClock(await invoke(SeelenCommand.ClockGetState)));
+  await listenGlobal<ClockState>(SeelenEvent.ClockTick, (event) => {
+    store.dispatch(RootActions.setClock(event.payload));
+  });
+
   await listenGlobal(SeelenEvent.StatePlaceholdersChanged, async () => {
     if (IsSavingCustom.current) {
       IsSavingCustom.current = false;
@@ -129,6 +195,10 @@ export async function registerStoreEvents() {
     setPlaceholder(userSettings);
   });
 
+  await WidgetList.onChange((widgets) => {
+    store.dispatch(RootActions.setWidgets(widgets));
+  });
+
   await initUIColors();
   await StartThemingTool();
   await view.emitTo(view.label, 'store-events-ready');
@@ -156,6 +226,7 @@ export async function loadStore() {
   setPlaceholder(userSettings);
 
   store.dispatch(RootActions.setEnv(userSettings.env));
+  store.dispatch(RootActions.setWidgets(await WidgetList.getAsync()));
 }
 
 export function loadSettingsCSS(settings: FancyToolbarSettings) {
@@ -52,6 +52,8 @@ define_app_errors!(
     Updater(tauri_plugin_updater::Error);
     WinScreenshot(win_screenshot::capture::WSError);
     EvalExpr(evalexpr::EvalexprError);
+    Rhai(Box<rhai::EvalAltResult>);
+    Minisign(minisign_verify::Error);
 );
 
 impl std::fmt::Debug for AppError {
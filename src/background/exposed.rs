@@ -1,33 +1,79 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use seelen_core::state::LogLevel;
 use tauri::{Builder, WebviewWindow, Wry};
 use tauri_plugin_shell::ShellExt;
 
 use crate::error_handler::Result;
 use crate::hook::HookManager;
+use crate::log_error;
+use crate::modules::icon_cache::IconCache;
 use crate::modules::input::Keyboard;
 use crate::modules::virtual_desk::get_vd_manager;
 use crate::seelen::{get_app_handle, Seelen};
+use crate::seelen_bar::handler::*;
 use crate::seelen_rofi::handler::*;
+use crate::seelen_wall::handler::*;
 use crate::seelen_weg::handler::*;
-use crate::seelen_weg::icon_extractor::{
-    extract_and_save_icon_from_file, extract_and_save_icon_umid,
-};
 use crate::seelen_wm_v2::handler::*;
 use crate::state::infrastructure::*;
-use crate::system::brightness::*;
 use crate::utils::is_virtual_desktop_supported as virtual_desktop_supported;
+use crate::utils::logger::SeelenLogger;
 use crate::windows_api::WindowsApi;
-use crate::winevent::{SyntheticFullscreenData, WinEvent};
-use crate::{log_error, utils};
+use crate::winevent::{FullscreenKind, SyntheticFullscreenData, WinEvent};
 
+use crate::modules::always_on_top::infrastructure::*;
+use crate::modules::app_usage::infrastructure::*;
+use crate::modules::bluetooth::infrastructure::*;
+use crate::modules::break_reminder::infrastructure::*;
+use crate::modules::brightness::infrastructure::*;
+use crate::modules::capture::infrastructure::*;
+use crate::modules::clock::infrastructure::*;
+use crate::modules::color_picker::infrastructure::*;
+use crate::modules::daily_wallpaper::infrastructure::*;
+use crate::modules::diagnostics::infrastructure::*;
+use crate::modules::display::infrastructure::*;
+use crate::modules::emit_scheduler::infrastructure::*;
+use crate::modules::event_registry::infrastructure::*;
+use crate::modules::icon_cache::infrastructure::*;
+use crate::modules::ime::infrastructure::*;
+use crate::modules::keybindings::infrastructure::*;
+use crate::modules::layout_direction::infrastructure::*;
+use crate::modules::live_theme::infrastructure::*;
+use crate::modules::marketplace::infrastructure::*;
 use crate::modules::media::infrastructure::*;
+use crate::modules::minimize_targets::infrastructure::*;
 use crate::modules::network::infrastructure::*;
+use crate::modules::night_light::infrastructure::*;
+use crate::modules::notes::infrastructure::*;
 use crate::modules::notifications::infrastructure::*;
+use crate::modules::onboarding::infrastructure::*;
+use crate::modules::perf::infrastructure::*;
 use crate::modules::power::infrastructure::*;
+use crate::modules::printers::infrastructure::*;
+use crate::modules::private_session::infrastructure::*;
+use crate::modules::processes::infrastructure::*;
+use crate::modules::quick_settings::infrastructure::*;
+use crate::modules::recycle_bin::infrastructure::*;
+use crate::modules::removable_drives::infrastructure::*;
+use crate::modules::restore_points::infrastructure::*;
+use crate::modules::session::infrastructure::*;
+use crate::modules::settings_search::infrastructure::*;
+use crate::modules::start_menu::infrastructure::*;
+use crate::modules::startup::infrastructure::*;
+use crate::modules::storage::infrastructure::*;
+use crate::modules::supervisor::infrastructure::*;
 use crate::modules::system_settings::infrastructure::*;
 use crate::modules::tray::infrastructure::*;
+use crate::modules::updater::infrastructure::*;
+use crate::modules::urgency::infrastructure::*;
+use crate::modules::version_upgrade::infrastructure::*;
+use crate::modules::widgets::infrastructure::*;
+use crate::modules::window_effects::infrastructure::*;
+use crate::modules::window_filter::infrastructure::*;
+use crate::modules::window_switcher::infrastructure::*;
+use crate::modules::window_tags::infrastructure::*;
 
 #[tauri::command(async)]
 fn select_file_on_explorer(path: String) -> Result<()> {
@@ -96,16 +142,6 @@ fn show_app_settings() {
     log_error!(Seelen::show_settings());
 }
 
-#[tauri::command(async)]
-async fn set_auto_start(enabled: bool) -> Result<()> {
-    Seelen::set_auto_start(enabled).await
-}
-
-#[tauri::command(async)]
-async fn get_auto_start_status() -> Result<bool> {
-    Seelen::is_auto_start_enabled().await
-}
-
 #[tauri::command(async)]
 fn switch_workspace(idx: usize) -> Result<()> {
     get_vd_manager().switch_to(idx)
@@ -118,11 +154,7 @@ fn send_keys(keys: String) -> Result<()> {
 
 #[tauri::command(async)]
 fn get_icon(path: String) -> Option<PathBuf> {
-    if path.starts_with("shell:AppsFolder") {
-        let umid = path.replace("shell:AppsFolder\\", "");
-        return extract_and_save_icon_umid(&umid).ok();
-    }
-    extract_and_save_icon_from_file(&path).ok()
+    IconCache::of_path_or_umid(&path).ok()
 }
 
 #[tauri::command(async)]
@@ -134,29 +166,23 @@ fn is_virtual_desktop_supported() -> bool {
 fn simulate_fullscreen(webview: WebviewWindow<tauri::Wry>, value: bool) -> Result<()> {
     let handle = webview.hwnd()?;
     let monitor = WindowsApi::monitor_from_window(handle);
+    let data = SyntheticFullscreenData {
+        handle,
+        monitor,
+        kind: FullscreenKind::Borderless,
+    };
     let event = if value {
-        WinEvent::SyntheticFullscreenStart(SyntheticFullscreenData { handle, monitor })
+        WinEvent::SyntheticFullscreenStart(data)
     } else {
-        WinEvent::SyntheticFullscreenEnd(SyntheticFullscreenData { handle, monitor })
+        WinEvent::SyntheticFullscreenEnd(data)
     };
     HookManager::emit_event(event, handle);
     Ok(())
 }
 
 #[tauri::command(async)]
-async fn check_for_updates() -> Result<bool> {
-    Ok(utils::updater::check_for_updates().await?.is_some())
-}
-
-#[tauri::command(async)]
-async fn install_last_available_update() -> Result<()> {
-    let update = utils::updater::check_for_updates()
-        .await?
-        .ok_or("There is no update available")?;
-    utils::updater::trace_update_intallation(update).await?;
-    get_app_handle().restart();
-    #[allow(unreachable_code)]
-    Ok(())
+fn set_log_level(level: LogLevel, module: Option<String>) {
+    SeelenLogger::set_level(module, level);
 }
 
 pub fn register_invoke_handler(app_builder: Builder<Wry>) -> Builder<Wry> {
@@ -173,22 +199,45 @@ pub fn register_invoke_handler(app_builder: Builder<Wry>) -> Builder<Wry> {
         switch_workspace,
         send_keys,
         get_icon,
+        invalidate_icon_cache,
         get_system_colors,
         simulate_fullscreen,
         check_for_updates,
         install_last_available_update,
+        updater_get_status,
+        updater_download,
+        get_version_upgrade,
+        dismiss_version_upgrade,
+        set_log_level,
         // Seelen Settings
         set_auto_start,
         get_auto_start_status,
+        startup_get_status,
+        startup_register,
+        startup_unregister,
         state_get_themes,
+        live_theme_get_css,
+        live_theme_inject_css,
         state_get_placeholders,
+        state_pin_toolbar_item,
         state_get_layouts,
         state_get_weg_items,
+        state_pause_file_listener,
+        state_resume_file_listener,
         state_get_settings,
+        patch_settings,
+        state_get_settings_hash,
+        state_write_settings,
+        state_get_settings_conflict,
+        state_resolve_settings_conflict,
+        state_get_locked_settings,
         state_get_specific_apps_configurations,
         state_get_wallpaper,
         state_set_wallpaper,
         state_get_history,
+        state_get_widgets,
+        search_settings,
+        get_config_schema,
         // Media
         media_prev,
         media_toggle_play_pause,
@@ -199,21 +248,140 @@ pub fn register_invoke_handler(app_builder: Builder<Wry>) -> Builder<Wry> {
         // Brightness
         get_main_monitor_brightness,
         set_main_monitor_brightness,
+        brightness_get_all,
+        brightness_set,
+        // Bluetooth
+        bluetooth_get_state,
+        bluetooth_set_enabled,
+        bluetooth_toggle,
+        bluetooth_connect,
+        bluetooth_disconnect,
+        // Removable Drives
+        removable_drives_get_all,
+        removable_drive_eject,
+        // Storage
+        storage_get_state,
+        // Printers
+        printers_get_all,
+        printer_pause,
+        printer_resume,
+        print_job_pause,
+        print_job_resume,
+        print_job_cancel,
+        // Recycle Bin
+        get_recycle_bin_state,
+        empty_recycle_bin,
+        open_recycle_bin,
+        // Session
+        session_get_state,
+        // Input Method
+        ime_get_state,
+        // Clock
+        clock_get_state,
+        // Screen Capture
+        capture_screen,
+        capture_window,
+        capture_region,
+        // Color Picker
+        color_picker_pick,
+        // App Usage
+        app_usage_get_report,
+        app_usage_get_history,
+        app_usage_clear,
+        // Private Session
+        private_session_get,
+        private_session_set,
+        private_session_toggle,
+        // Break Reminder
+        break_reminder_get_state,
+        break_reminder_take_break,
+        break_reminder_snooze,
+        break_reminder_skip,
+        // Diagnostics
+        diagnostics_get_reports,
+        diagnostics_clear,
+        diagnostics_export,
+        // Restore Points
+        restore_points_get_all,
+        restore_points_create,
+        restore_points_apply,
+        restore_points_delete,
+        notes_get_all,
+        notes_create,
+        notes_update,
+        notes_delete,
+        // Quick Settings
+        quick_settings_get_state,
+        quick_settings_toggle,
+        quick_settings_set_volume,
+        quick_settings_set_brightness,
+        // Display
+        display_get_all,
+        display_set_mode,
+        display_set_orientation,
+        display_set_primary,
+        // Night Light
+        night_light_get_state,
+        night_light_set_active,
+        night_light_toggle,
         // Power
+        get_power_profile,
+        // Layout Direction
+        get_layout_direction,
         log_out,
+        lock,
+        switch_user,
         suspend,
         restart,
         shutdown,
+        // Fancy Toolbar
+        toolbar_run_item_action,
         // SeelenWeg
         weg_close_app,
+        weg_gather_windows,
         weg_toggle_window_state,
         weg_request_update_previews,
         weg_pin_item,
+        weg_move_item,
+        weg_launch_item,
+        weg_set_minimize_targets,
+        wall_set_widget_rects,
+        daily_wallpaper_get,
         // Windows Manager
         set_window_position,
         request_focus,
+        wm_layout_validate,
+        wm_layout_preview,
+        wm_get_size_hints,
+        // Window Effects
+        toggle_window_effects,
+        // Window Filters
+        window_filter_explain,
+        window_filter_inspect_at_cursor,
+        // Always on Top
+        toggle_always_on_top,
+        get_always_on_top_windows,
+        // Urgency
+        get_urgent_windows,
+        clear_urgency,
+        // Window Switcher
+        window_switcher_get_state,
+        window_switcher_open,
+        window_switcher_select,
+        window_switcher_commit,
+        window_switcher_cancel,
+        // Window Tags
+        window_tags_get_state,
+        window_tags_set,
+        window_tags_set_filter,
+        // Processes
+        processes_get_running,
+        // Start Menu
+        get_start_menu_items,
         // App Launcher
         launcher_get_apps,
+        launcher_get_item_actions,
+        launcher_run_item_action,
         // tray icons
         temp_get_by_event_tray_info,
         on_click_tray_icon,
@@ -227,5 +395,29 @@ pub fn register_invoke_handler(app_builder: Builder<Wry>) -> Builder<Wry> {
         // notifications
         notifications_close,
         notifications_close_all,
+        // keybindings
+        get_keybindings_conflicts,
+        keybindings_diagnose,
+        // supervisor
+        webview_heartbeat,
+        // perf
+        get_perf_metrics,
+        // emit scheduler
+        subscribe_scheduled_event,
+        unsubscribe_scheduled_event,
+        // event registry
+        subscribe_event,
+        unsubscribe_event,
+        // widgets
+        widget_call,
+        // onboarding
+        onboarding_detect_capabilities,
+        onboarding_apply_preset,
+        // marketplace
+        marketplace_search,
+        marketplace_refresh,
+        marketplace_install,
+        marketplace_check_updates,
+        marketplace_update,
     ])
 }
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     path::PathBuf,
     sync::{
         atomic::{AtomicBool, AtomicIsize, Ordering},
@@ -9,12 +9,12 @@ use std::{
     time::{Duration, Instant},
 };
 
+use arc_swap::ArcSwap;
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
 use seelen_core::handlers::SeelenEvent;
 use serde::Serialize;
-use tauri::Emitter;
 use windows::Win32::{
     Foundation::HWND,
     UI::{
@@ -29,46 +29,244 @@ use crate::{
     error_handler::Result,
     log_error,
     modules::{
+        accessibility::Announcer,
+        always_on_top::AlwaysOnTop,
+        app_usage::AppUsageTracker,
+        audio_policy::AudioPolicy,
+        color_picker::ColorPicker,
+        diagnostics::latency::InputLatency,
+        emit_scheduler::EmitScheduler,
+        event_registry::EventRegistry,
+        focus_history::FocusHistory,
+        ime::Ime,
         input::{domain::Point, Mouse},
+        picture_in_picture::PictureInPicture,
+        taskbar::NativeTaskbar,
+        titlebar::Titlebar,
+        triggers::TriggerRunner,
+        urgency::Urgency,
         virtual_desk::{get_vd_manager, VirtualDesktopEvent, VirtualDesktopManager},
+        window_effects::WindowEffects,
+        window_switcher::WindowSwitcher,
     },
-    seelen::{get_app_handle, Seelen, SEELEN},
+    seelen::{Seelen, SEELEN},
     seelen_weg::SeelenWeg,
-    seelen_wm_v2::instance::WindowManagerV2,
+    seelen_wm_v2::{border::FocusBorder, instance::WindowManagerV2, zones::WmZones},
     state::{application::FULL_STATE, domain::AppExtraFlag},
-    trace_lock,
-    utils::spawn_named_thread,
-    windows_api::{window::Window, WindowsApi},
-    winevent::WinEvent,
+    t, trace_lock,
+    utils::{
+        event_queue::{EventQueue, EventQueueMetrics, QueueSender},
+        spawn_named_thread,
+    },
+    windows_api::{window::Window, LiveWindowsApi, WindowsApi, WindowsApiBackend},
+    winevent::{FullscreenTracker, WinEvent},
 };
 
 lazy_static! {
     static ref HOOK_MANAGER: Arc<Mutex<HookManager>> = Arc::new(Mutex::new(HookManager::new()));
+    static ref WEG_QUEUE: Result<EventQueue<(WinEvent, Window)>> = EventQueue::new(
+        "Weg Global",
+        coalesce_location_changes,
+        |(event, window)| {
+            log_global_error(
+                "Weg Global",
+                event,
+                &window,
+                SeelenWeg::process_global_win_event(event, &window),
+            )
+        },
+    );
+    static ref WM_QUEUE: Result<EventQueue<(WinEvent, Window)>> = EventQueue::new(
+        "WM Global",
+        coalesce_location_changes,
+        |(event, window)| {
+            InputLatency::on_dequeued(window.hwnd());
+            log_global_error(
+                "WM Global",
+                event,
+                &window,
+                WindowManagerV2::process_win_event(event, &window),
+            )
+        },
+    );
     // Last active window omitting all the seelen overlays
     pub static ref LAST_ACTIVE_NOT_SEELEN: AtomicIsize = AtomicIsize::new(WindowsApi::get_foreground_window().0 as _);
 }
 
 pub static LOG_WIN_EVENTS: AtomicBool = AtomicBool::new(false);
 
+pub type WinEventPredicate = fn(&WinEvent) -> bool;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WinEventConsumer {
+    /// focus tracking, always subscribed
+    Core,
+    VirtualDesk,
+    WegGlobal,
+    WegInstance,
+    Toolbar,
+    WindowManager,
+    Wall,
+    WindowEffects,
+    Titlebar,
+    AlwaysOnTop,
+    WindowSwitcher,
+    FocusHistory,
+    Ime,
+    Urgency,
+    AudioPolicy,
+    PictureInPicture,
+}
+
+/// Declares which events and windows a consumer cares about
+#[derive(Debug, Clone)]
+pub struct WinEventFilter {
+    /// None means all the events
+    events: Option<WinEventPredicate>,
+    /// None means all the windows
+    windows: Option<HashSet<isize>>,
+}
+
+impl WinEventFilter {
+    pub fn events(predicate: WinEventPredicate) -> Self {
+        Self {
+            events: Some(predicate),
+            windows: None,
+        }
+    }
+
+    pub fn only_windows<I: IntoIterator<Item = isize>>(mut self, windows: I) -> Self {
+        self.windows = Some(windows.into_iter().collect());
+        self
+    }
+
+    pub fn matches(&self, event: &WinEvent, origin: HWND) -> bool {
+        let event_matches = self.events.map_or(true, |predicate| predicate(event));
+        let window_matches = self
+            .windows
+            .as_ref()
+            .map_or(true, |windows| windows.contains(&(origin.0 as isize)));
+        event_matches && window_matches
+    }
+}
+
+lazy_static! {
+    static ref SUBSCRIPTIONS: ArcSwap<HashMap<WinEventConsumer, WinEventFilter>> =
+        ArcSwap::from_pointee(HashMap::from([(
+            WinEventConsumer::Core,
+            WinEventFilter::events(|event| matches!(
+                event,
+                WinEvent::SystemForeground | WinEvent::ObjectFocus
+            )),
+        )]));
+}
+
+fn coalesce_location_changes((event, window): &(WinEvent, Window)) -> Option<isize> {
+    match event {
+        WinEvent::ObjectLocationChange => Some(window.address()),
+        _ => None,
+    }
+}
+
+fn log_global_error(name: &str, event: WinEvent, window: &Window, result: Result<()>) {
+    if let Err(err) = result {
+        log::error!(
+            "{} => Event: {:?} Error: {:?} Window: {:?}",
+            name,
+            event,
+            err,
+            window
+        );
+    }
+}
+
+pub fn get_event_queues_metrics() -> Vec<EventQueueMetrics> {
+    [WEG_QUEUE.metrics(), WM_QUEUE.metrics()]
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
 pub struct HookManager {
     skip: HashMap<isize, Vec<WinEvent>>,
+    fullscreen: FullscreenTracker,
 }
 
 #[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct FocusedApp {
     hwnd: isize,
     title: String,
     name: String,
     exe: Option<PathBuf>,
+    is_always_on_top: bool,
+}
+
+impl From<&Window> for FocusedApp {
+    fn from(window: &Window) -> Self {
+        Self {
+            hwnd: window.address(),
+            title: window.display_title(),
+            name: window
+                .app_display_name()
+                .unwrap_or_else(|_| t!("window.unknown_app")),
+            exe: window.exe().ok(),
+            is_always_on_top: window.is_always_on_top(),
+        }
+    }
 }
 
 impl HookManager {
     fn new() -> Self {
         Self {
             skip: HashMap::new(),
+            fullscreen: FullscreenTracker::default(),
         }
     }
 
+    pub fn subscribe(consumer: WinEventConsumer, filter: WinEventFilter) {
+        SUBSCRIPTIONS.rcu(|subscriptions| {
+            let mut subscriptions = HashMap::clone(subscriptions);
+            subscriptions.insert(consumer, filter.clone());
+            subscriptions
+        });
+    }
+
+    pub fn unsubscribe(consumer: WinEventConsumer) {
+        SUBSCRIPTIONS.rcu(|subscriptions| {
+            let mut subscriptions = HashMap::clone(subscriptions);
+            subscriptions.remove(&consumer);
+            subscriptions
+        });
+    }
+
+    /// Subscribes or unsubscribes the consumer depending on the given filter
+    pub fn set_subscription(consumer: WinEventConsumer, filter: Option<WinEventFilter>) {
+        match filter {
+            Some(filter) => Self::subscribe(consumer, filter),
+            None => Self::unsubscribe(consumer),
+        }
+    }
+
+    fn is_subscribed(consumer: WinEventConsumer, event: &WinEvent, origin: HWND) -> bool {
+        SUBSCRIPTIONS
+            .load()
+            .get(&consumer)
+            .is_some_and(|filter| filter.matches(event, origin))
+    }
+
+    /// Returns false if nobody is interested on the event or on the synthetics it could produce
+    pub fn is_event_wanted(event: &WinEvent, origin: HWND) -> bool {
+        let subscriptions = SUBSCRIPTIONS.load();
+        let possible_synthetics = event.possible_synthetics(origin);
+        subscriptions.values().any(|filter| {
+            filter.matches(event, origin)
+                || possible_synthetics
+                    .iter()
+                    .any(|synthetic| filter.matches(synthetic, origin))
+        })
+    }
+
     pub fn run_with_async<F, T>(f: F) -> JoinHandle<T>
     where
         F: FnOnce(&mut HookManager) -> T,
@@ -82,9 +280,9 @@ impl HookManager {
         self.skip.entry(hwnd.0 as _).or_default().push(event)
     }
 
-    fn should_skip(&self, event: WinEvent, hwnd: HWND) -> bool {
+    fn should_skip<A: WindowsApiBackend>(&self, event: WinEvent, hwnd: HWND, api: &A) -> bool {
         // skip foreground on invisible windows
-        if event == WinEvent::SystemForeground && !WindowsApi::is_window_visible(hwnd) {
+        if event == WinEvent::SystemForeground && !api.is_window_visible(hwnd) {
             return true;
         }
         if let Some(v) = self.skip.get(&(hwnd.0 as _)) {
@@ -135,79 +333,141 @@ impl HookManager {
         );
     }
 
-    fn _event(&mut self, event: WinEvent, origin: HWND, seelen: &mut Seelen) {
+    /// Runs the event and the synthetics produced by it through `dispatch` in order,
+    /// the events marked to be skipped are consumed instead of dispatched.
+    fn process<A: WindowsApiBackend>(
+        &mut self,
+        event: WinEvent,
+        origin: HWND,
+        api: &A,
+        mut dispatch: impl FnMut(WinEvent),
+    ) {
         Self::log_event(event, origin);
-
-        if self.should_skip(event, origin) {
+        if self.should_skip(event, origin, api) {
             self.skip_done(event, origin);
-            return;
+        } else {
+            dispatch(event);
+        }
+
+        for synthetic in self.fullscreen.synthetics(&event, origin, api) {
+            Self::log_event(synthetic, origin);
+            if self.should_skip(synthetic, origin, api) {
+                self.skip_done(synthetic, origin);
+            } else {
+                dispatch(synthetic);
+            }
         }
+    }
 
+    fn dispatch(event: WinEvent, origin: HWND, seelen: &mut Seelen) {
         let window = Window::from(origin);
+        let log_error_event =
+            move |name: &str, result: Result<()>| log_global_error(name, event, &window, result);
+
+        let wants = |consumer| Self::is_subscribed(consumer, &event, origin);
+
+        if wants(WinEventConsumer::Ime) {
+            log_error_event("IME", Ime::process_win_event(event, &window));
+        }
+
+        // composition and candidate windows take the focus while typing, they are hidden from
+        // the other consumers so the focused app doesn't flicker and they are never tiled
+        if window.is_ime() {
+            return;
+        }
+
         if event == WinEvent::SystemForeground && !window.is_seelen_overlay() {
             LAST_ACTIVE_NOT_SEELEN.store(origin.0 as _, Ordering::Relaxed);
+            TriggerRunner::on_focus_changed(&window);
+            AppUsageTracker::on_foreground(&window);
         }
 
         if event == WinEvent::ObjectFocus || event == WinEvent::SystemForeground {
-            let title = window.title();
-            log_error!(get_app_handle().emit(
-                SeelenEvent::GlobalFocusChanged,
-                FocusedApp {
-                    title,
-                    hwnd: origin.0 as _,
-                    name: window
-                        .app_display_name()
-                        .unwrap_or(String::from("Error on App Name")),
-                    exe: window.exe().ok(),
-                },
-            ));
-        }
-
-        let log_error_event = move |name: &str, result: Result<()>| {
-            if let Err(err) = result {
-                log::error!(
-                    "{} => Event: {:?} Error: {:?} Window: {:?}",
-                    name,
-                    event,
-                    err,
-                    window
-                );
+            EmitScheduler::schedule(SeelenEvent::GlobalFocusChanged, FocusedApp::from(&window));
+        }
+
+        if wants(WinEventConsumer::VirtualDesk) {
+            if let VirtualDesktopManager::Seelen(vd) = get_vd_manager().as_ref() {
+                log_error_event("Virtual Desk", vd.on_win_event(event, &window));
             }
-        };
+        }
 
-        if let VirtualDesktopManager::Seelen(vd) = get_vd_manager().as_ref() {
-            log_error_event("Virtual Desk", vd.on_win_event(event, &window));
+        if wants(WinEventConsumer::WegGlobal) {
+            WEG_QUEUE.send((event, window));
         }
 
-        let app_state = seelen.state();
-        if app_state.is_weg_enabled() {
-            std::thread::spawn(move || {
-                log_error_event(
-                    "Weg Global",
-                    SeelenWeg::process_global_win_event(event, &window),
-                );
-            });
+        if wants(WinEventConsumer::WindowManager) {
+            WM_QUEUE.send((event, window));
         }
 
-        if app_state.is_window_manager_enabled() {
-            std::thread::spawn(move || {
-                log_error_event(
-                    "WM Global",
-                    WindowManagerV2::process_win_event(event, &window),
-                );
-            });
+        if wants(WinEventConsumer::WindowEffects) {
+            log_error_event(
+                "Window Effects",
+                WindowEffects::process_win_event(event, &window),
+            );
+        }
+
+        if wants(WinEventConsumer::Titlebar) {
+            log_error_event("Titlebar", Titlebar::process_win_event(event, &window));
+        }
+
+        if wants(WinEventConsumer::AlwaysOnTop) {
+            log_error_event(
+                "Always on Top",
+                AlwaysOnTop::process_win_event(event, &window),
+            );
+        }
+
+        if wants(WinEventConsumer::Urgency) {
+            log_error_event("Urgency", Urgency::process_win_event(event, &window));
+        }
+
+        if wants(WinEventConsumer::PictureInPicture) {
+            log_error_event(
+                "Picture in Picture",
+                PictureInPicture::process_win_event(event, &window),
+            );
         }
 
-        if let Some(wall) = seelen.wall_mut() {
-            log_error_event("Wall Instance", wall.process_win_event(event, &window));
+        if wants(WinEventConsumer::AudioPolicy) {
+            log_error_event(
+                "Audio Policy",
+                AudioPolicy::process_win_event(event, &window),
+            );
+        }
+
+        if wants(WinEventConsumer::FocusHistory) {
+            log_error_event(
+                "Focus History",
+                FocusHistory::process_win_event(event, &window),
+            );
+        }
+
+        if wants(WinEventConsumer::WindowSwitcher) {
+            log_error_event(
+                "Window Switcher",
+                WindowSwitcher::process_win_event(event, &window),
+            );
+        }
+
+        if wants(WinEventConsumer::Wall) {
+            if let Some(wall) = seelen.wall_mut() {
+                log_error_event("Wall Instance", wall.process_win_event(event, &window));
+            }
+        }
+
+        let wants_toolbar = wants(WinEventConsumer::Toolbar);
+        let wants_weg = wants(WinEventConsumer::WegInstance);
+        if !wants_toolbar && !wants_weg {
+            return;
         }
 
         for monitor in seelen.monitors_mut() {
-            if let Some(toolbar) = monitor.toolbar_mut() {
+            if let (true, Some(toolbar)) = (wants_toolbar, monitor.toolbar_mut()) {
                 log_error_event("Toolbar Instance", toolbar.process_win_event(event, origin));
             }
 
-            if let Some(weg) = monitor.weg_mut() {
+            if let (true, Some(weg)) = (wants_weg, monitor.weg_mut()) {
                 log_error_event(
                     "Weg Instance",
                     weg.process_individual_win_event(event, origin),
@@ -220,13 +480,9 @@ impl HookManager {
         // Follows lock order: CLI -> DATA -> EVENT to avoid deadlocks
         let mut seelen = trace_lock!(SEELEN);
         let mut hook_manager = trace_lock!(HOOK_MANAGER);
-        hook_manager._event(event, origin, &mut seelen);
-
-        if let Ok(synthetics) = event.get_synthetics(origin) {
-            for synthetic_event in synthetics {
-                hook_manager._event(synthetic_event, origin, &mut seelen)
-            }
-        }
+        hook_manager.process(event, origin, &LiveWindowsApi, |event| {
+            Self::dispatch(event, origin, &mut seelen)
+        });
     }
 }
 
@@ -252,11 +508,14 @@ pub fn process_vd_event(event: VirtualDesktopEvent) -> Result<()> {
                 .iter()
                 .map(|d| d.as_serializable())
                 .collect_vec();
-            get_app_handle().emit(SeelenEvent::WorkspacesChanged, &desktops)?;
+            EventRegistry::emit(SeelenEvent::WorkspacesChanged, &desktops)?;
         }
 
         VirtualDesktopEvent::DesktopChanged { new, old: _ } => {
-            get_app_handle().emit(SeelenEvent::ActiveWorkspaceChanged, new.id())?;
+            EventRegistry::emit(SeelenEvent::ActiveWorkspaceChanged, new.id())?;
+            let idx = get_vd_manager().get_current_idx()?;
+            TriggerRunner::on_workspace_switched(idx);
+            Announcer::on_workspace_switched(&new, idx);
         }
         VirtualDesktopEvent::WindowChanged(window) => {
             let hwnd = HWND(window as _);
@@ -269,6 +528,9 @@ pub fn process_vd_event(event: VirtualDesktopEvent) -> Result<()> {
                         vd.pin_window(window)?;
                     }
                 }
+                if FULL_STATE.load().is_weg_enabled() {
+                    SeelenWeg::update_location(hwnd);
+                }
             }
         }
         _ => {}
@@ -330,12 +592,19 @@ pub extern "system" fn win_event_hook(
         return;
     }
 
+    // raw events should be only used for a fastest and immediately processing
+    log_error!(NativeTaskbar::process_raw_win_event(event, origin));
+    log_error!(FocusBorder::process_raw_win_event(event, origin));
     if FULL_STATE.load().is_weg_enabled() {
-        // raw events should be only used for a fastest and immediately processing
         log_error!(SeelenWeg::process_raw_win_event(event, origin));
     }
 
     let event = WinEvent::from(event);
+    if !HookManager::is_event_wanted(&event, origin) {
+        return;
+    }
+    InputLatency::on_arrival(event, origin);
+
     if event == WinEvent::ObjectLocationChange && !location_delay_completed(origin) {
         return;
     }
@@ -366,18 +635,140 @@ pub fn register_win_hook() -> Result<()> {
     })?;
 
     spawn_named_thread("MouseEventHook", || {
-        let handle = get_app_handle();
         let mut last_pos = Point::default();
         loop {
-            if let Ok(pos) = Mouse::get_cursor_pos() {
-                if last_pos != pos {
-                    let _ = handle.emit(SeelenEvent::GlobalMouseMove, &[pos.get_x(), pos.get_y()]);
+            let wants_mouse_move = EmitScheduler::has_subscribers(SeelenEvent::GlobalMouseMove);
+            // the zones overlay also depends on the held modifier, so it is updated on every frame
+            let is_dragging = WmZones::is_dragging();
+            let wants_loupe = ColorPicker::wants_loupe();
+            if wants_mouse_move || is_dragging || wants_loupe {
+                if let Ok(pos) = Mouse::get_cursor_pos() {
+                    if wants_mouse_move && last_pos != pos {
+                        EmitScheduler::schedule(
+                            SeelenEvent::GlobalMouseMove,
+                            [pos.get_x(), pos.get_y()],
+                        );
+                    }
+                    if is_dragging {
+                        log_error!(WmZones::on_cursor_move(&pos));
+                    }
+                    // the screen under the cursor can change without moving it
+                    if wants_loupe {
+                        log_error!(ColorPicker::emit_loupe(&pos));
+                    }
                     last_pos = pos;
                 }
             }
-            std::thread::sleep(Duration::from_millis(66)); // 15 FPS
+            std::thread::sleep(EmitScheduler::frame_duration());
         }
     })?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        windows_api::mock::{hwnd, MockWindowsApi},
+        winevent::FullscreenKind,
+    };
+
+    use super::*;
+
+    fn replay(
+        manager: &mut HookManager,
+        api: &MockWindowsApi,
+        event: WinEvent,
+        origin: isize,
+    ) -> Vec<WinEvent> {
+        let mut dispatched = Vec::new();
+        manager.process(event, hwnd(origin), api, |event| dispatched.push(event));
+        dispatched
+    }
+
+    #[test]
+    fn test_skipped_events_are_consumed_once() {
+        let mut manager = HookManager::new();
+        let api = MockWindowsApi::default().with_window(1);
+        manager.skip(WinEvent::ObjectShow, hwnd(1));
+
+        assert!(replay(&mut manager, &api, WinEvent::ObjectShow, 1).is_empty());
+        assert_eq!(
+            replay(&mut manager, &api, WinEvent::ObjectShow, 1),
+            vec![WinEvent::ObjectShow]
+        );
+    }
+
+    #[test]
+    fn test_filter_only_matches_the_given_windows() {
+        let filter =
+            WinEventFilter::events(|event| *event == WinEvent::ObjectDestroy).only_windows([1, 2]);
+        assert!(filter.matches(&WinEvent::ObjectDestroy, hwnd(1)));
+        assert!(!filter.matches(&WinEvent::ObjectDestroy, hwnd(3)));
+        assert!(!filter.matches(&WinEvent::ObjectShow, hwnd(2)));
+    }
+
+    #[test]
+    fn test_foreground_of_invisible_window_is_skipped() {
+        let mut manager = HookManager::new();
+        let mut api = MockWindowsApi::default();
+        api.focus(1);
+        assert!(replay(&mut manager, &api, WinEvent::SystemForeground, 1).is_empty());
+    }
+
+    #[test]
+    fn test_fullscreen_sequence() {
+        let mut manager = HookManager::new();
+        let mut api = MockWindowsApi::default().with_window(1).with_window(2);
+        api.set_fullscreen(1, Some(FullscreenKind::Borderless));
+
+        api.focus(1);
+        let events = replay(&mut manager, &api, WinEvent::SystemForeground, 1);
+        let WinEvent::SyntheticFullscreenStart(data) = events[1] else {
+            panic!("expected fullscreen start, got {:?}", events);
+        };
+        assert_eq!(events[0], WinEvent::SystemForeground);
+        assert_eq!(data.handle, hwnd(1));
+
+        // moving the fullscreened window keeps it fullscreened
+        assert_eq!(
+            replay(&mut manager, &api, WinEvent::ObjectLocationChange, 1),
+            vec![WinEvent::ObjectLocationChange]
+        );
+
+        // focusing another window ends the fullscreen
+        api.focus(2);
+        assert_eq!(
+            replay(&mut manager, &api, WinEvent::SystemForeground, 2),
+            vec![
+                WinEvent::SystemForeground,
+                WinEvent::SyntheticFullscreenEnd(data)
+            ]
+        );
+
+        // events of background windows don't produce synthetics
+        assert_eq!(
+            replay(&mut manager, &api, WinEvent::ObjectLocationChange, 1),
+            vec![WinEvent::ObjectLocationChange]
+        );
+    }
+
+    #[test]
+    fn test_exiting_fullscreen_on_foreground() {
+        let mut manager = HookManager::new();
+        let mut api = MockWindowsApi::default().with_window(1);
+        api.set_fullscreen(1, Some(FullscreenKind::Exclusive));
+        api.focus(1);
+        replay(&mut manager, &api, WinEvent::SystemForeground, 1);
+
+        api.set_fullscreen(1, None);
+        let events = replay(&mut manager, &api, WinEvent::ObjectLocationChange, 1);
+        assert!(matches!(
+            events.as_slice(),
+            [
+                WinEvent::ObjectLocationChange,
+                WinEvent::SyntheticFullscreenEnd(_)
+            ]
+        ));
+    }
+}
@@ -3,6 +3,7 @@ use getset::{Getters, MutGetters};
 use crate::{
     error_handler::Result,
     log_error,
+    seelen::Seelen,
     seelen_bar::FancyToolbar,
     seelen_weg::SeelenWeg,
     seelen_wm_v2::instance::WindowManagerV2,
@@ -83,6 +84,24 @@ impl SeelenInstanceContainer {
         Ok(())
     }
 
+    /// Puts back a toolbar recreated by the supervisor, discarded if it was disabled meanwhile
+    pub fn replace_toolbar(&mut self, toolbar: FancyToolbar, settings: &FullState) -> Result<()> {
+        if self.toolbar.is_none() && settings.is_bar_enabled_on_monitor(self.monitor.index()?) {
+            self.toolbar = Some(toolbar);
+            self.ensure_positions()?;
+        }
+        Ok(())
+    }
+
+    /// Puts back a weg recreated by the supervisor, discarded if it was disabled meanwhile
+    pub fn replace_weg(&mut self, weg: SeelenWeg, settings: &FullState) -> Result<()> {
+        if self.weg.is_none() && settings.is_weg_enabled_on_monitor(self.monitor.index()?) {
+            self.weg = Some(weg);
+            self.ensure_positions()?;
+        }
+        Ok(())
+    }
+
     pub fn load_settings(&mut self, settings: &FullState) -> Result<()> {
         if settings.is_bar_enabled_on_monitor(self.monitor.index()?) {
             self.add_toolbar()?;
@@ -96,7 +115,8 @@ impl SeelenInstanceContainer {
             self.weg = None;
         }
 
-        if settings.is_window_manager_enabled() {
+        // on headless mode the layout is tiled natively, see `WindowManagerV2::tile_natively`
+        if settings.is_window_manager_enabled() && !Seelen::is_headless() {
             self.add_wm()?;
         } else {
             self.wm = None;
@@ -27,19 +27,25 @@ use exposed::register_invoke_handler;
 use itertools::Itertools;
 use modules::{
     cli::{
-        application::{attach_console, is_just_getting_info, SEELEN_COMMAND_LINE},
+        application::{attach_console, detach_console, is_just_getting_info, SEELEN_COMMAND_LINE},
         Client,
     },
+    crash_guard::CrashGuard,
+    diagnostics::Diagnostics,
+    event_registry::EventRegistry,
+    ipc::application::IpcClient,
     tray::application::ensure_tray_overflow_creation,
+    updater::SeelenUpdater,
 };
 use plugins::register_plugins;
 use seelen::{Seelen, SEELEN};
 use seelen_core::state::Settings;
+use serde_json::json;
 use tauri::webview_version;
 use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
 use tauri_plugin_shell::ShellExt;
 use tray::try_register_tray_icon;
-use utils::PERFORMANCE_HELPER;
+use utils::{instance_guard::InstanceGuard, PERFORMANCE_HELPER};
 use windows::Win32::Security::{SE_DEBUG_NAME, SE_SHUTDOWN_NAME};
 use windows_api::WindowsApi;
 
@@ -71,6 +77,7 @@ fn register_panic_hook() -> Result<()> {
             cause,
             string_location
         );
+        Diagnostics::on_panic(&cause, &string_location);
     }));
     Ok(())
 }
@@ -110,20 +117,32 @@ fn setup(app: &mut tauri::App<tauri::Wry>) -> Result<()> {
 
     validate_webview_runtime_is_installed(app.handle())?;
 
+    let command = trace_lock!(SEELEN_COMMAND_LINE).clone();
+    let matches = command.get_matches();
+    Seelen::set_safe_mode(matches.get_flag("safe-mode"));
+    Seelen::set_headless(matches.get_flag("headless"));
+
     let mut seelen = trace_lock!(SEELEN);
     seelen.init(app.handle())?;
 
+    if Seelen::is_safe_mode() {
+        log::warn!("Running in safe mode, only the settings window will be available");
+        Seelen::show_settings()?;
+        log_error!(try_register_tray_icon(app));
+        trace_lock!(PERFORMANCE_HELPER).end("setup");
+        return Ok(());
+    }
+
     log_error!(WindowsApi::enable_privilege(SE_SHUTDOWN_NAME));
     log_error!(WindowsApi::enable_privilege(SE_DEBUG_NAME));
 
     // try it at start it on open the program to avoid do it before
     log_error!(ensure_tray_overflow_creation());
 
-    if !tauri::is_dev() {
-        let command = trace_lock!(SEELEN_COMMAND_LINE).clone();
-        if !command.get_matches().get_flag("silent") {
-            Seelen::show_settings()?;
-        }
+    if Seelen::is_headless() {
+        log::info!("Running in headless mode, no webviews will be created");
+    } else if !tauri::is_dev() && !matches.get_flag("silent") {
+        Seelen::show_settings()?;
     }
 
     seelen.start()?;
@@ -140,25 +159,65 @@ fn app_callback(_: &tauri::AppHandle<tauri::Wry>, event: tauri::RunEvent) {
                 api.prevent_exit();
             }
         }
+        tauri::RunEvent::WindowEvent {
+            label,
+            event: tauri::WindowEvent::Destroyed,
+            ..
+        } => {
+            EventRegistry::forget(&label);
+        }
         tauri::RunEvent::Exit => {
             log::info!("───────────────────── Exiting Seelen UI ─────────────────────");
+            log_error!(CrashGuard::on_exit());
             if Seelen::is_running() {
                 trace_lock!(SEELEN).stop();
             }
+            // update downloaded on background is installed on next restart
+            log_error!(SeelenUpdater::install_pending());
         }
         _ => {}
     }
 }
 
-fn is_already_runnning() -> bool {
-    let mut sys = sysinfo::System::new();
-    sys.refresh_processes();
-    sys.processes()
-        .values()
-        .filter(|p| p.exe().is_some_and(|path| path.ends_with("seelen-ui.exe")))
-        .collect_vec()
-        .len()
-        > 1
+/// Sends the current args to the running instance, so it can process them
+fn forward_args_to_running_instance() -> Result<()> {
+    let args = std::env::args().skip(1).collect_vec();
+    let mut client = match IpcClient::connect() {
+        Ok(client) => client,
+        // instances without ipc server, ex: an older version being replaced
+        Err(_) => return forward_args_via_tcp(),
+    };
+
+    // the running instance exits while processing --replace so no response will be sent
+    if args.iter().any(|arg| arg == "--replace") {
+        return client.notify("cli", json!({ "args": args }));
+    }
+
+    if let Err(err) = client.request("cli", json!({ "args": args })) {
+        attach_console()?;
+        eprintln!("{}", err.to_string().lines().next().unwrap_or_default());
+        detach_console()?;
+    }
+    Ok(())
+}
+
+fn forward_args_via_tcp() -> Result<()> {
+    let mut attempts = 0;
+    let mut connection = Client::connect_tcp();
+
+    while connection.is_err() && attempts < 10 {
+        attempts += 1;
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        connection = Client::connect_tcp();
+    }
+
+    let mut writer = BufWriter::new(connection?);
+    let args = std::env::args().collect_vec();
+    let msg = serde_json::to_string(&args)?;
+
+    writer.write_all(msg.as_bytes())?;
+    writer.flush()?;
+    Ok(())
 }
 
 fn main() -> Result<()> {
@@ -180,31 +239,19 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
-    if is_already_runnning() {
-        let mut attempts = 0;
-        let mut connection = Client::connect_tcp();
-
-        while connection.is_err() && attempts < 10 {
-            attempts += 1;
-            std::thread::sleep(std::time::Duration::from_millis(100));
-            connection = Client::connect_tcp();
-        }
-
-        if let Ok(stream) = connection {
-            let mut writer = BufWriter::new(stream);
-
-            let args = std::env::args().collect_vec();
-            let msg = serde_json::to_string(&args)?;
-
-            writer.write_all(msg.as_bytes())?;
-            writer.flush()?;
-            return Ok(());
+    // should live until the app exits
+    let mut instance_guard = InstanceGuard::acquire()?;
+    if !instance_guard.is_owned() {
+        let replacing = matches.get_flag("replace");
+        match forward_args_to_running_instance() {
+            Ok(_) if !replacing => return Ok(()),
+            Ok(_) => log::info!("Waiting for the running instance to exit"),
+            // if the connection fails probably is because the app is been closing
+            Err(err) => log::warn!("Running instance is not reachable: {}", err),
         }
 
-        // if the connection fails probably is because the app is been closing
-        // so we check if the app is already running again to see if we have to
-        // let the instance be created or not
-        if is_already_runnning() {
+        // the running instance releases the guard on exit, then we take over
+        if !instance_guard.wait_ownership(std::time::Duration::from_secs(10)) {
             return Ok(());
         }
     }
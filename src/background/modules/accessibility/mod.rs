@@ -0,0 +1,77 @@
+use windows::{
+    core::BSTR,
+    Win32::{
+        Foundation::HWND,
+        UI::Accessibility::{
+            NotificationKind_ActionCompleted, NotificationProcessing_ImportantMostRecent,
+            UiaClientsAreListening, UiaHostProviderFromHwnd, UiaRaiseNotificationEvent,
+        },
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::virtual_desk::VirtualDesktop,
+    state::application::FULL_STATE,
+    t,
+    windows_api::{window::Window, Com, WindowsApi},
+};
+
+/// groups the announcements so a new one replaces the pending one on the screen reader
+const ACTIVITY_ID: &str = "seelen-ui";
+
+/// Announces keyboard driven changes to screen readers via UI Automation notifications
+pub struct Announcer;
+
+impl Announcer {
+    fn raise(hwnd: isize, text: &str) -> Result<()> {
+        Com::run_with_context(|| unsafe {
+            // avoid creating providers when there is no screen reader running
+            if !UiaClientsAreListening().as_bool() {
+                return Ok(());
+            }
+            let provider = UiaHostProviderFromHwnd(HWND(hwnd as _))?;
+            UiaRaiseNotificationEvent(
+                &provider,
+                NotificationKind_ActionCompleted,
+                NotificationProcessing_ImportantMostRecent,
+                &BSTR::from(text),
+                &BSTR::from(ACTIVITY_ID),
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Raised on a separate thread to don't block the win event hook
+    fn announce(hwnd: HWND, text: String) {
+        let hwnd = hwnd.0 as isize;
+        std::thread::spawn(move || {
+            log_error!(Self::raise(hwnd, &text));
+        });
+    }
+
+    pub fn on_workspace_switched(desktop: &VirtualDesktop, idx: usize) {
+        if !FULL_STATE.load().settings.accessibility.announce_workspaces {
+            return;
+        }
+        let name = desktop
+            .name()
+            .unwrap_or_else(|| t!("accessibility.workspace_default", index = idx + 1));
+        Self::announce(
+            WindowsApi::get_desktop_window(),
+            t!("accessibility.workspace", name = name),
+        );
+    }
+
+    /// Called when the focus is moved by a window manager command
+    pub fn on_focus_moved(window: &Window) {
+        if !FULL_STATE.load().settings.accessibility.announce_focus {
+            return;
+        }
+        Self::announce(
+            window.hwnd(),
+            t!("accessibility.focused", title = window.display_title()),
+        );
+    }
+}
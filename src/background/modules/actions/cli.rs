@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+
+use clap::{Arg, ArgAction, Command};
+use serde_json::Value;
+
+use crate::error_handler::Result;
+
+use super::domain::ActionCatalog;
+
+impl ActionCatalog {
+    pub const CLI_IDENTIFIER: &'static str = "run";
+
+    pub fn get_cli() -> Command {
+        Command::new(Self::CLI_IDENTIFIER)
+            .about("Runs an action or alias, use `get actions` to list them.")
+            .arg_required_else_help(true)
+            .args([
+                Arg::new("action")
+                    .help("Id of the action or name of an alias, ex: manager.toggle-float")
+                    .action(ArgAction::Set)
+                    .required(true),
+                Arg::new("params")
+                    .help("Params of the action as name=value, ex: index=2")
+                    .action(ArgAction::Append)
+                    .num_args(0..),
+            ])
+    }
+
+    pub fn process(matches: &clap::ArgMatches) -> Result<()> {
+        let id = matches
+            .get_one::<String>("action")
+            .ok_or("No action was provided")?;
+        let mut params = HashMap::new();
+        for param in matches.get_many::<String>("params").into_iter().flatten() {
+            let (name, value) = param
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid param {}, expected name=value", param))?;
+            params.insert(name.to_string(), Value::String(value.to_string()));
+        }
+        Self::build().run(id, &params)
+    }
+}
@@ -0,0 +1,49 @@
+use seelen_core::state::ActionAlias;
+use serde::Serialize;
+
+/// Actions that can be bound to keybindings, triggers, toolbar items and external controllers,
+/// returned by `listActions` and `seelen-ui.exe get actions`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionCatalog {
+    /// incremented on breaking changes of the schema
+    pub version: u32,
+    pub actions: Vec<NamedAction>,
+    /// user defined names, accepted everywhere an action id is
+    pub aliases: Vec<ActionAlias>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NamedAction {
+    /// stable id, the path of the command on the CLI joined by dots, ex: `manager.toggle-float`
+    pub id: String,
+    /// label of the group, on the language of the settings
+    pub group: String,
+    /// on the language of the settings
+    pub label: String,
+    pub description: String,
+    /// name of a react-icons icon, same used by the toolbar, ex: `BsGrid1X2Fill`
+    pub icon: String,
+    /// in the order expected by the command
+    pub params: Vec<ActionParam>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionParam {
+    pub name: String,
+    pub description: String,
+    pub kind: ActionParamKind,
+    /// allowed values when `kind` is `choice`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ActionParamKind {
+    Text,
+    Number,
+    Choice,
+}
@@ -0,0 +1,269 @@
+pub mod cli;
+pub mod domain;
+
+use std::collections::HashMap;
+
+use clap::{Arg, Command};
+use serde_json::Value;
+
+use crate::{
+    error_handler::Result,
+    modules::cli::application::{handle_cli_events, SEELEN_COMMAND_LINE},
+    state::application::FULL_STATE,
+    t, trace_lock,
+    utils::{i18n::try_translate, kebab_to_pascal},
+};
+
+use domain::{ActionCatalog, ActionParam, ActionParamKind, NamedAction};
+
+/// Commands of the CLI exposed as actions: (command, icon), the label of the group is
+/// translated as `actions.groups.<command>`.
+/// Queries and debugging commands are left out, they don't make sense on a button.
+const GROUPS: &[(&str, &str)] = &[
+    ("settings", "IoSettingsSharp"),
+    ("virtual-desk", "BsWindowStack"),
+    ("manager", "BsGrid1X2Fill"),
+    ("weg", "BsWindowDock"),
+    ("toolbar", "BsLayoutTextWindow"),
+    ("launcher", "IoSearch"),
+    ("focus", "MdHistory"),
+    ("media", "BsMusicNoteBeamed"),
+    ("power", "IoPower"),
+    ("brightness", "BsBrightnessHigh"),
+    ("pip", "MdPictureInPicture"),
+    ("private", "BsIncognito"),
+    ("tag", "BsTags"),
+    ("window", "MdOpacity"),
+    ("theme", "IoColorPalette"),
+];
+
+/// subcommands only useful while developing Seelen
+const EXCLUDED_SUBCOMMANDS: &[&str] = &["debug"];
+
+/// `virtual-desk.switch-workspace` -> `virtual_desk.switch_workspace`
+fn translation_key(id: &str) -> String {
+    id.replace('-', "_")
+}
+
+impl ActionCatalog {
+    const SCHEMA_VERSION: u32 = 1;
+
+    /// Built from the command line definition, so new commands are available to the
+    /// keybindings, triggers, toolbar and controllers without changes on them
+    pub fn build() -> Self {
+        let command = trace_lock!(SEELEN_COMMAND_LINE).clone();
+        let mut actions = Vec::new();
+        for (name, icon) in GROUPS {
+            if let Some(cmd) = command.find_subcommand(name) {
+                let group = t!(&format!("actions.groups.{}", translation_key(name)));
+                Self::collect(cmd, &[], &group, icon, &mut actions);
+            }
+        }
+        Self {
+            version: Self::SCHEMA_VERSION,
+            actions,
+            aliases: FULL_STATE.load().settings.action_aliases.clone(),
+        }
+    }
+
+    fn collect(
+        cmd: &Command,
+        parent: &[&str],
+        group: &str,
+        icon: &str,
+        actions: &mut Vec<NamedAction>,
+    ) {
+        let mut path = parent.to_vec();
+        path.push(cmd.get_name());
+
+        if cmd.has_subcommands() {
+            for sub in cmd.get_subcommands() {
+                if !EXCLUDED_SUBCOMMANDS.contains(&sub.get_name()) {
+                    Self::collect(sub, &path, group, icon, actions);
+                }
+            }
+            return;
+        }
+
+        let id = path.join(".");
+        let label = try_translate(&format!("actions.labels.{}", translation_key(&id)), &[])
+            .unwrap_or_else(|| Self::label(cmd.get_name()));
+        actions.push(NamedAction {
+            id,
+            group: group.to_owned(),
+            label,
+            description: cmd.get_about().map(|a| a.to_string()).unwrap_or_default(),
+            icon: icon.to_owned(),
+            params: cmd.get_positionals().map(Self::param).collect(),
+        });
+    }
+
+    /// `switch-workspace` -> `Switch Workspace`, used for the actions without translation
+    fn label(name: &str) -> String {
+        name.split('-')
+            .map(kebab_to_pascal)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn param(arg: &Arg) -> ActionParam {
+        let options: Vec<String> = arg
+            .get_possible_values()
+            .iter()
+            .map(|value| value.get_name().to_owned())
+            .collect();
+
+        let kind = if !options.is_empty() {
+            ActionParamKind::Choice
+        } else {
+            // clap doesn't expose the type of the value, but numbers are the only
+            // values that accept digits and reject letters
+            let accepts = |value: &str| {
+                Command::new("probe")
+                    .arg(arg.clone())
+                    .try_get_matches_from(["probe", value])
+                    .is_ok()
+            };
+            if accepts("0") && !accepts("a") {
+                ActionParamKind::Number
+            } else {
+                ActionParamKind::Text
+            }
+        };
+
+        ActionParam {
+            name: arg.get_id().to_string(),
+            description: arg.get_help().map(|h| h.to_string()).unwrap_or_default(),
+            kind,
+            options,
+        }
+    }
+
+    /// Whether `id` is an action or an alias
+    pub fn contains(&self, id: &str) -> bool {
+        self.actions.iter().any(|action| action.id == id)
+            || self.aliases.iter().any(|alias| alias.name == id)
+    }
+
+    /// Converts the action or alias and its params to the arguments of the CLI.
+    /// Actions take precedence over aliases with the same name.
+    pub fn to_cli_args(&self, id: &str, params: &HashMap<String, Value>) -> Result<Vec<String>> {
+        let mut params = params.clone();
+        let action = match self.actions.iter().find(|action| action.id == id) {
+            Some(action) => action,
+            None => {
+                let alias = self
+                    .aliases
+                    .iter()
+                    .find(|alias| alias.name == id)
+                    .ok_or_else(|| format!("Unknown action: {}", id))?;
+                for (name, value) in &alias.params {
+                    params.entry(name.clone()).or_insert_with(|| value.clone());
+                }
+                self.actions
+                    .iter()
+                    .find(|action| action.id == alias.id)
+                    .ok_or_else(|| format!("Unknown action: {} (alias {})", alias.id, id))?
+            }
+        };
+
+        let mut args: Vec<String> = action.id.split('.').map(String::from).collect();
+        for param in &action.params {
+            let value = match params.get(&param.name) {
+                Some(Value::String(value)) => value.clone(),
+                Some(Value::Number(value)) => value.to_string(),
+                Some(Value::Bool(value)) => value.to_string(),
+                _ => return Err(format!("Missing param: {}", param.name).into()),
+            };
+            args.push(value);
+        }
+        Ok(args)
+    }
+
+    /// Runs the action or alias on this instance
+    pub fn run(&self, id: &str, params: &HashMap<String, Value>) -> Result<()> {
+        run_cli(self.to_cli_args(id, params)?)
+    }
+}
+
+/// Runs the arguments as if they were given to `seelen-ui.exe`
+pub fn run_cli(args: Vec<String>) -> Result<()> {
+    let argv = std::iter::once("seelen-ui".to_string()).chain(args);
+    let command = trace_lock!(SEELEN_COMMAND_LINE).clone();
+    let matches = command
+        .try_get_matches_from(argv)
+        .map_err(|e| e.to_string())?;
+    handle_cli_events(&matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use seelen_core::state::ActionAlias;
+    use serde_json::json;
+
+    use super::*;
+
+    fn catalog() -> ActionCatalog {
+        ActionCatalog {
+            version: ActionCatalog::SCHEMA_VERSION,
+            actions: vec![NamedAction {
+                id: "virtual-desk.switch-workspace".into(),
+                group: "Workspaces".into(),
+                label: "Switch Workspace".into(),
+                description: String::new(),
+                icon: String::new(),
+                params: vec![ActionParam {
+                    name: "index".into(),
+                    description: String::new(),
+                    kind: ActionParamKind::Number,
+                    options: Vec::new(),
+                }],
+            }],
+            aliases: vec![
+                ActionAlias {
+                    name: "ws-2".into(),
+                    id: "virtual-desk.switch-workspace".into(),
+                    params: HashMap::from([("index".into(), json!(2))]),
+                },
+                ActionAlias {
+                    name: "broken".into(),
+                    id: "virtual-desk.removed".into(),
+                    params: HashMap::new(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_action_to_cli_args() {
+        let catalog = catalog();
+        let params = HashMap::from([("index".to_string(), json!(1))]);
+        assert_eq!(
+            catalog
+                .to_cli_args("virtual-desk.switch-workspace", &params)
+                .unwrap(),
+            ["virtual-desk", "switch-workspace", "1"]
+        );
+        assert!(catalog
+            .to_cli_args("virtual-desk.switch-workspace", &HashMap::new())
+            .is_err());
+        assert!(catalog.to_cli_args("unknown", &params).is_err());
+    }
+
+    #[test]
+    fn test_alias_to_cli_args() {
+        let catalog = catalog();
+        assert_eq!(
+            catalog.to_cli_args("ws-2", &HashMap::new()).unwrap(),
+            ["virtual-desk", "switch-workspace", "2"]
+        );
+        // given params take precedence over the ones of the alias
+        let params = HashMap::from([("index".to_string(), json!("3"))]);
+        assert_eq!(
+            catalog.to_cli_args("ws-2", &params).unwrap(),
+            ["virtual-desk", "switch-workspace", "3"]
+        );
+        assert!(catalog.contains("ws-2"));
+        assert!(catalog.to_cli_args("broken", &HashMap::new()).is_err());
+    }
+}
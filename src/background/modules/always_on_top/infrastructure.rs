@@ -0,0 +1,17 @@
+use std::sync::atomic::Ordering;
+
+use crate::{error_handler::Result, hook::LAST_ACTIVE_NOT_SEELEN, windows_api::window::Window};
+
+use super::AlwaysOnTop;
+
+/// Toggles the always on top state of the given window or the last focused one
+#[tauri::command(async)]
+pub fn toggle_always_on_top(hwnd: Option<isize>) -> Result<()> {
+    let hwnd = hwnd.unwrap_or_else(|| LAST_ACTIVE_NOT_SEELEN.load(Ordering::Acquire));
+    AlwaysOnTop::toggle(&Window::from(hwnd))
+}
+
+#[tauri::command(async)]
+pub fn get_always_on_top_windows() -> Vec<isize> {
+    AlwaysOnTop::get_all()
+}
@@ -0,0 +1,145 @@
+pub mod infrastructure;
+
+use std::collections::HashSet;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{handlers::SeelenEvent, state::AppExtraFlag};
+use windows::Win32::UI::WindowsAndMessaging::{
+    HWND_NOTOPMOST, HWND_TOPMOST, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE,
+};
+
+use crate::{
+    error_handler::Result,
+    hook::FocusedApp,
+    modules::{emit_scheduler::EmitScheduler, event_registry::EventRegistry},
+    seelen_weg::SeelenWeg,
+    state::application::FULL_STATE,
+    trace_lock,
+    windows_api::{window::Window, WindowsApi},
+    winevent::WinEvent,
+};
+
+lazy_static! {
+    /// app windows that are currently always on top
+    static ref TOPMOST: Mutex<HashSet<isize>> = Mutex::new(HashSet::new());
+    /// windows where the rule was already applied, so the user can turn it off
+    static ref RULE_APPLIED: Mutex<HashSet<isize>> = Mutex::new(HashSet::new());
+}
+
+/// Keeps track of the app windows that are always on top, the state is checked on every z-order
+/// change so it stays accurate even if the topmost flag is changed by other tools.
+pub struct AlwaysOnTop;
+
+impl AlwaysOnTop {
+    pub fn get_all() -> Vec<isize> {
+        trace_lock!(TOPMOST).iter().copied().collect()
+    }
+
+    fn emit() -> Result<()> {
+        EventRegistry::emit(SeelenEvent::GlobalAlwaysOnTopChanged, Self::get_all())?;
+        Ok(())
+    }
+
+    fn set(window: &Window, enabled: bool) -> Result<()> {
+        let order = if enabled {
+            HWND_TOPMOST
+        } else {
+            HWND_NOTOPMOST
+        };
+        WindowsApi::set_position(
+            window.hwnd(),
+            Some(order),
+            &Default::default(),
+            SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+        )?;
+        Self::check(window)
+    }
+
+    pub fn toggle(window: &Window) -> Result<()> {
+        Self::set(window, !window.is_always_on_top())
+    }
+
+    /// Syncs the tracked state with the real state of the window
+    fn check(window: &Window) -> Result<()> {
+        let address = window.address();
+        let is_topmost = window.is_always_on_top() && SeelenWeg::should_be_added(window.hwnd());
+        let changed = {
+            let mut topmost = trace_lock!(TOPMOST);
+            match is_topmost {
+                true => topmost.insert(address),
+                false => topmost.remove(&address),
+            }
+        };
+        if changed {
+            if window.is_foreground() {
+                EmitScheduler::schedule(SeelenEvent::GlobalFocusChanged, FocusedApp::from(window));
+            }
+            Self::emit()?;
+        }
+        Ok(())
+    }
+
+    fn check_all() -> Result<()> {
+        for address in Self::get_all() {
+            let window = Window::from(address);
+            match window.is_window() {
+                true => Self::check(&window)?,
+                false => Self::forget(address)?,
+            }
+        }
+        Self::check(&Window::from(WindowsApi::get_foreground_window()))
+    }
+
+    fn forget(address: isize) -> Result<()> {
+        trace_lock!(RULE_APPLIED).remove(&address);
+        if trace_lock!(TOPMOST).remove(&address) {
+            Self::emit()?;
+        }
+        Ok(())
+    }
+
+    /// Applies the rule once per window, later changes are left to the user
+    fn apply_rule(window: &Window) -> Result<()> {
+        let address = window.address();
+        if trace_lock!(RULE_APPLIED).contains(&address)
+            || !SeelenWeg::should_be_added(window.hwnd())
+        {
+            return Ok(());
+        }
+        let has_rule = FULL_STATE
+            .load()
+            .get_app_config_by_window(window.hwnd())
+            .is_some_and(|config| config.options.contains(&AppExtraFlag::AlwaysOnTop));
+        if has_rule {
+            trace_lock!(RULE_APPLIED).insert(address);
+            Self::set(window, true)?;
+        }
+        Ok(())
+    }
+
+    pub fn wants_win_event(event: &WinEvent) -> bool {
+        matches!(
+            event,
+            WinEvent::ObjectCreate
+                | WinEvent::ObjectShow
+                | WinEvent::ObjectDestroy
+                | WinEvent::SystemForeground
+                | WinEvent::ObjectReorder
+        )
+    }
+
+    pub fn process_win_event(event: WinEvent, window: &Window) -> Result<()> {
+        match event {
+            WinEvent::ObjectCreate | WinEvent::ObjectShow => {
+                Self::apply_rule(window)?;
+                Self::check(window)?;
+            }
+            WinEvent::ObjectDestroy => Self::forget(window.address())?,
+            // the origin of a reorder is the parent of the reordered windows
+            WinEvent::SystemForeground | WinEvent::ObjectReorder => Self::check_all()?,
+            _ => {}
+        }
+        Ok(())
+    }
+}
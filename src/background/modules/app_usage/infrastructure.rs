@@ -0,0 +1,22 @@
+use seelen_core::system_state::AppUsageReport;
+
+use crate::error_handler::Result;
+
+use super::AppUsageTracker;
+
+/// Foreground time of each app on the given date (`YYYY-MM-DD`), today by default
+#[tauri::command(async)]
+pub fn app_usage_get_report(date: Option<String>) -> Result<AppUsageReport> {
+    Ok(AppUsageTracker::report(date))
+}
+
+/// Daily reports of the last `days` days, oldest first
+#[tauri::command(async)]
+pub fn app_usage_get_history(days: u32) -> Result<Vec<AppUsageReport>> {
+    Ok(AppUsageTracker::history(days))
+}
+
+#[tauri::command(async)]
+pub fn app_usage_clear() -> Result<()> {
+    AppUsageTracker::clear()
+}
@@ -0,0 +1,360 @@
+pub mod infrastructure;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::system_state::{AppUsage, AppUsageReport};
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use windows::Win32::System::SystemInformation::GetLocalTime;
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::{
+        notifications::application::show_toast, private_session::PrivateSession,
+        session::SessionManager,
+    },
+    seelen::get_app_handle,
+    state::application::FULL_STATE,
+    t, trace_lock,
+    utils::spawn_named_thread,
+    windows_api::{window::Window, WindowsApi},
+};
+
+/// how often the foreground time is added to the database and saved
+const TICK_INTERVAL: Duration = Duration::from_secs(60);
+
+lazy_static! {
+    static ref DATABASE: Mutex<AppUsageDatabase> = Mutex::new(AppUsageDatabase::load());
+    static ref CURRENT: Mutex<Option<ForegroundSession>> = Mutex::new(None);
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct UsageEntry {
+    name: String,
+    seconds: u64,
+}
+
+/// local date (`YYYY-MM-DD`) -> app -> usage of that day
+type DailyUsage = BTreeMap<String, HashMap<String, UsageEntry>>;
+
+#[derive(Debug, Default)]
+struct AppUsageDatabase {
+    days: DailyUsage,
+    dirty: bool,
+}
+
+impl AppUsageDatabase {
+    fn path() -> Result<PathBuf> {
+        Ok(get_app_handle()
+            .path()
+            .app_data_dir()?
+            .join("app_usage.json"))
+    }
+
+    fn load() -> Self {
+        let days = Self::path()
+            .ok()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { days, dirty: false }
+    }
+
+    fn save(&mut self) -> Result<()> {
+        if self.dirty {
+            std::fs::write(Self::path()?, serde_json::to_string(&self.days)?)?;
+            self.dirty = false;
+        }
+        Ok(())
+    }
+
+    fn add(&mut self, date: String, app: &str, name: &str, seconds: u64) {
+        let entry = self
+            .days
+            .entry(date)
+            .or_default()
+            .entry(app.to_string())
+            .or_insert_with(|| UsageEntry {
+                name: name.to_string(),
+                seconds: 0,
+            });
+        entry.seconds += seconds;
+        self.dirty = true;
+    }
+
+    /// Removes the days before `oldest`
+    fn prune(&mut self, oldest: &str) {
+        let kept = self.days.split_off(oldest);
+        if !self.days.is_empty() {
+            self.dirty = true;
+        }
+        self.days = kept;
+    }
+}
+
+#[derive(Debug)]
+struct ForegroundSession {
+    app: String,
+    name: String,
+    /// time until which the usage was already added to the database
+    counted_until: Instant,
+    /// time on the app without switching to another one
+    continuous: Duration,
+    nudged: bool,
+}
+
+/// Foreground time of each app, aggregated by day
+pub struct AppUsageTracker;
+
+impl AppUsageTracker {
+    /// Same identity used by the position memory, so all the windows of an app are counted together
+    fn session_of(window: &Window) -> Option<ForegroundSession> {
+        if window.is_desktop() {
+            return None;
+        }
+        let app = match window.app_user_model_id() {
+            Some(umid) => umid,
+            None => window.exe().ok()?.to_string_lossy().to_lowercase(),
+        };
+        let name = window.app_display_name().unwrap_or_else(|_| app.clone());
+        Some(ForegroundSession {
+            app,
+            name,
+            counted_until: Instant::now(),
+            continuous: Duration::ZERO,
+            nudged: false,
+        })
+    }
+
+    /// Adds the time since the last flush to the usage of the current app
+    fn flush(current: &mut Option<ForegroundSession>) {
+        let Some(session) = current.as_mut() else {
+            return;
+        };
+        let elapsed = session.counted_until.elapsed();
+        // whole seconds are counted, the remaining is counted on the next flush
+        let seconds = elapsed.as_secs();
+        session.counted_until += Duration::from_secs(seconds);
+        // the foreground window doesn't change while the session is locked
+        if SessionManager::is_locked() {
+            session.continuous = Duration::ZERO;
+            return;
+        }
+        session.continuous += Duration::from_secs(seconds);
+        // the time on private sessions is discarded, the break nudge still works
+        if seconds > 0 && !PrivateSession::is_active() {
+            trace_lock!(DATABASE).add(today(), &session.app, &session.name, seconds);
+        }
+    }
+
+    pub fn on_foreground(window: &Window) {
+        if !FULL_STATE.load().settings.app_usage.enabled {
+            return;
+        }
+        let mut current = trace_lock!(CURRENT);
+        Self::flush(&mut current);
+        let next = Self::session_of(window);
+        // focusing another window of the same app doesn't reset the continuous time
+        if let (Some(session), Some(next)) = (current.as_ref(), next.as_ref()) {
+            if session.app == next.app {
+                return;
+            }
+        }
+        *current = next;
+    }
+
+    /// Counts the time until the session was locked
+    pub fn on_session_locked() {
+        Self::flush(&mut trace_lock!(CURRENT));
+    }
+
+    /// Discards the time while the session was locked
+    pub fn on_session_unlocked() {
+        if let Some(session) = trace_lock!(CURRENT).as_mut() {
+            session.counted_until = Instant::now();
+            session.continuous = Duration::ZERO;
+        }
+    }
+
+    fn tick() -> Result<()> {
+        let settings = FULL_STATE.load().settings.app_usage.clone();
+        let mut current = trace_lock!(CURRENT);
+        if !settings.enabled {
+            *current = None;
+            return Ok(());
+        }
+        if current.is_none() {
+            *current = Self::session_of(&Window::from(WindowsApi::get_foreground_window()));
+        }
+        Self::flush(&mut current);
+
+        if let Some(session) = current.as_mut() {
+            let nudge_after = Duration::from_secs(settings.nudge_after as u64 * 60);
+            if settings.nudge && !session.nudged && session.continuous >= nudge_after {
+                session.nudged = true;
+                let title = t!("app_usage.nudge_title");
+                let body = t!(
+                    "app_usage.nudge_body",
+                    app = session.name,
+                    time = format_duration(session.continuous.as_secs()),
+                );
+                std::thread::spawn(move || {
+                    log_error!(show_toast(&title, &body));
+                });
+            }
+        }
+        drop(current);
+
+        let mut database = trace_lock!(DATABASE);
+        database.prune(&date_key(today_days() - settings.retention_days as i64 + 1));
+        database.save()
+    }
+
+    pub fn start() -> Result<()> {
+        spawn_named_thread("App Usage", || loop {
+            std::thread::sleep(TICK_INTERVAL);
+            log_error!(Self::tick());
+        })?;
+        Ok(())
+    }
+
+    /// Report of the given date or today, includes the time of the current app until now
+    pub fn report(date: Option<String>) -> AppUsageReport {
+        Self::flush(&mut trace_lock!(CURRENT));
+        report_of(&trace_lock!(DATABASE).days, &date.unwrap_or_else(today))
+    }
+
+    /// Reports of the last `days` days, oldest first, days without usage are included
+    pub fn history(days: u32) -> Vec<AppUsageReport> {
+        Self::flush(&mut trace_lock!(CURRENT));
+        let database = trace_lock!(DATABASE);
+        let today = today_days();
+        (0..days as i64)
+            .rev()
+            .map(|ago| report_of(&database.days, &date_key(today - ago)))
+            .collect()
+    }
+
+    pub fn clear() -> Result<()> {
+        let mut database = trace_lock!(DATABASE);
+        database.days.clear();
+        database.dirty = true;
+        database.save()
+    }
+}
+
+fn report_of(days: &DailyUsage, date: &str) -> AppUsageReport {
+    let mut apps: Vec<AppUsage> = days
+        .get(date)
+        .into_iter()
+        .flatten()
+        .map(|(app, entry)| AppUsage {
+            app: app.clone(),
+            name: entry.name.clone(),
+            seconds: entry.seconds,
+        })
+        .collect();
+    apps.sort_by(|a, b| b.seconds.cmp(&a.seconds).then_with(|| a.name.cmp(&b.name)));
+    AppUsageReport {
+        date: date.to_string(),
+        total: apps.iter().map(|app| app.seconds).sum(),
+        apps,
+    }
+}
+
+fn format_duration(seconds: u64) -> String {
+    let (hours, minutes) = (seconds / 3600, seconds % 3600 / 60);
+    match (hours, minutes) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h {}m", hours, minutes),
+    }
+}
+
+/// Days since 1970-01-01 of the local date
+fn today_days() -> i64 {
+    let now = unsafe { GetLocalTime() };
+    days_from_civil(now.wYear as i64, now.wMonth as i64, now.wDay as i64)
+}
+
+fn today() -> String {
+    date_key(today_days())
+}
+
+/// Days since 1970-01-01 of a date on the gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Inverse of `days_from_civil`, formatted as `YYYY-MM-DD`
+fn date_key(days: i64) -> String {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = (shifted_month + 2) % 12 + 1;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_key() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(date_key(0), "1970-01-01");
+        assert_eq!(date_key(days_from_civil(2024, 3, 1) - 1), "2024-02-29");
+        assert_eq!(date_key(days_from_civil(2023, 3, 1) - 1), "2023-02-28");
+        assert_eq!(date_key(days_from_civil(2025, 1, 1) - 1), "2024-12-31");
+        for days in 19000..20000 {
+            let key = date_key(days);
+            let parts: Vec<i64> = key.split('-').map(|p| p.parse().unwrap()).collect();
+            assert_eq!(days_from_civil(parts[0], parts[1], parts[2]), days);
+        }
+    }
+
+    #[test]
+    fn test_report_of() {
+        let mut database = AppUsageDatabase::default();
+        database.add("2024-05-01".into(), "code.exe", "Code", 60);
+        database.add("2024-05-01".into(), "firefox.exe", "Firefox", 120);
+        database.add("2024-05-01".into(), "code.exe", "Code", 90);
+        database.add("2024-05-02".into(), "code.exe", "Code", 30);
+
+        let report = report_of(&database.days, "2024-05-01");
+        assert_eq!(report.total, 270);
+        assert_eq!(report.apps[0].name, "Code");
+        assert_eq!(report.apps[0].seconds, 150);
+        assert_eq!(report.apps[1].name, "Firefox");
+        assert!(report_of(&database.days, "2024-05-03").apps.is_empty());
+
+        database.prune("2024-05-02");
+        assert!(report_of(&database.days, "2024-05-01").apps.is_empty());
+        assert_eq!(report_of(&database.days, "2024-05-02").total, 30);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(45 * 60), "45m");
+        assert_eq!(format_duration(2 * 3600 + 59), "2h");
+        assert_eq!(format_duration(3600 + 30 * 60), "1h 30m");
+    }
+}
@@ -0,0 +1,225 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crossbeam_channel::{Receiver, Sender};
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::state::AppAudioRules;
+use windows::{
+    core::GUID,
+    Win32::{
+        Foundation::HWND,
+        Media::Audio::{
+            eRender, IAudioSessionControl2, IAudioSessionManager2, IMMDeviceEnumerator,
+            ISimpleAudioVolume, MMDeviceEnumerator, DEVICE_STATE_ACTIVE,
+        },
+        System::Com::CLSCTX_ALL,
+    },
+};
+use windows_core::Interface;
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    state::application::{FullState, FULL_STATE},
+    trace_lock,
+    utils::spawn_named_thread,
+    windows_api::{window::Window, Com, WindowEnumerator, WindowsApi},
+    winevent::WinEvent,
+};
+
+lazy_static! {
+    /// exe of the focused window
+    static ref FOCUSED_EXE: Mutex<Option<PathBuf>> = Mutex::new(None);
+    /// instance ids of the sessions muted by a rule, only those are unmuted on focus
+    static ref MUTED_BY_RULE: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+    static ref REQUESTS: (Sender<Option<PathBuf>>, Receiver<Option<PathBuf>>) =
+        crossbeam_channel::unbounded();
+}
+
+static WORKER_STARTED: AtomicBool = AtomicBool::new(false);
+
+struct AudioSession {
+    instance_id: String,
+    process_id: u32,
+    exe: PathBuf,
+    volume: ISimpleAudioVolume,
+}
+
+/// Applies the audio rules of the apps configs to their audio sessions, reacting to the focus
+pub struct AudioPolicy;
+
+impl AudioPolicy {
+    fn has_rules(state: &FullState) -> bool {
+        state
+            .settings_by_app
+            .iter()
+            .any(|config| config.audio.is_some())
+    }
+
+    /// Sessions of all the output devices, system sounds are excluded
+    fn sessions() -> Result<Vec<AudioSession>> {
+        let mut sessions = Vec::new();
+        unsafe {
+            let enumerator: IMMDeviceEnumerator = Com::create_instance(&MMDeviceEnumerator)?;
+            let devices = enumerator.EnumAudioEndpoints(eRender, DEVICE_STATE_ACTIVE)?;
+            for device_idx in 0..devices.GetCount()? {
+                let device = devices.Item(device_idx)?;
+                let manager: IAudioSessionManager2 = device.Activate(CLSCTX_ALL, None)?;
+                let list = manager.GetSessionEnumerator()?;
+                for session_idx in 0..list.GetCount()? {
+                    let session: IAudioSessionControl2 = list.GetSession(session_idx)?.cast()?;
+                    let process_id = session.GetProcessId()?;
+                    if process_id == 0 || session.IsSystemSoundsSession().0 == 0 {
+                        continue;
+                    }
+                    let Ok(exe) = WindowsApi::exe_path_by_process(process_id) else {
+                        continue;
+                    };
+                    sessions.push(AudioSession {
+                        instance_id: session.GetSessionInstanceIdentifier()?.to_string()?,
+                        process_id,
+                        exe: PathBuf::from(exe),
+                        volume: session.cast()?,
+                    });
+                }
+            }
+        }
+        Ok(sessions)
+    }
+
+    /// Rules of the app playing the session, matched by a window of the same process or
+    /// by the exe for the processes without windows (like the audio process of browsers).
+    fn rules_of(
+        state: &FullState,
+        session: &AudioSession,
+        windows: &HashMap<u32, HWND>,
+    ) -> Option<AppAudioRules> {
+        let config = match windows.get(&session.process_id) {
+            Some(hwnd) => state.get_app_config_by_window(*hwnd),
+            None => {
+                let exe = session.exe.file_name()?.to_string_lossy();
+                let path = session.exe.to_string_lossy();
+                state
+                    .settings_by_app
+                    .iter()
+                    .find(|config| config.identifier.validate("", "", &exe, &path))
+            }
+        };
+        config.and_then(|config| config.audio.clone())
+    }
+
+    fn enforce(focused: Option<&Path>) -> Result<()> {
+        let state = FULL_STATE.load();
+        let sessions = Self::sessions()?;
+
+        let process_ids: HashSet<u32> = sessions.iter().map(|s| s.process_id).collect();
+        let mut windows = HashMap::new();
+        WindowEnumerator::new().for_each(|hwnd| {
+            let (process_id, _) = WindowsApi::window_thread_process_id(hwnd);
+            if process_ids.contains(&process_id) && WindowsApi::is_window_visible(hwnd) {
+                windows.entry(process_id).or_insert(hwnd);
+            }
+        })?;
+
+        let mut muted = trace_lock!(MUTED_BY_RULE);
+        // the ids of the closed sessions are not reused
+        muted.retain(|id| sessions.iter().any(|s| &s.instance_id == id));
+
+        for session in &sessions {
+            let rules = Self::rules_of(&state, session, &windows).unwrap_or_default();
+            let is_focused = focused == Some(session.exe.as_path());
+            unsafe {
+                if rules.mute_unfocused && !is_focused {
+                    // sessions muted by the user are left as they are
+                    if !session.volume.GetMute()?.as_bool() {
+                        session.volume.SetMute(true, &GUID::zeroed())?;
+                        muted.insert(session.instance_id.clone());
+                    }
+                } else if muted.remove(&session.instance_id) {
+                    session.volume.SetMute(false, &GUID::zeroed())?;
+                }
+
+                if let Some(max_volume) = rules.max_volume {
+                    let max_volume = max_volume.clamp(0.0, 1.0);
+                    if session.volume.GetMasterVolume()? > max_volume {
+                        session
+                            .volume
+                            .SetMasterVolume(max_volume, &GUID::zeroed())?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The sessions are handled on its own thread, so the hook is not blocked by the audio apis
+    fn start_worker() -> Result<()> {
+        spawn_named_thread("Audio Policy", || {
+            log_error!(Com::run_with_context(|| {
+                while let Ok(mut focused) = REQUESTS.1.recv() {
+                    // only the last focus matters
+                    while let Ok(next) = REQUESTS.1.try_recv() {
+                        focused = next;
+                    }
+                    log_error!(Self::enforce(focused.as_deref()));
+                }
+                Ok(())
+            }));
+        })?;
+        Ok(())
+    }
+
+    /// Applies the rules again, should be called when the apps configs change
+    pub fn refresh() -> Result<()> {
+        let has_rules = Self::has_rules(&FULL_STATE.load());
+        // removed rules still need to unmute their sessions
+        if !has_rules && trace_lock!(MUTED_BY_RULE).is_empty() {
+            return Ok(());
+        }
+        if !WORKER_STARTED.swap(true, Ordering::SeqCst) {
+            Self::start_worker()?;
+        }
+        let focused = trace_lock!(FOCUSED_EXE).clone();
+        let _ = REQUESTS.0.send(focused);
+        Ok(())
+    }
+
+    /// Unmutes the sessions muted by the rules
+    pub fn release() -> Result<()> {
+        let muted = std::mem::take(&mut *trace_lock!(MUTED_BY_RULE));
+        if muted.is_empty() {
+            return Ok(());
+        }
+        Com::run_with_context(|| {
+            for session in Self::sessions()? {
+                if muted.contains(&session.instance_id) {
+                    unsafe { session.volume.SetMute(false, &GUID::zeroed())? };
+                }
+            }
+            Ok(())
+        })
+    }
+
+    pub fn wants_win_event(event: &WinEvent) -> bool {
+        matches!(event, WinEvent::SystemForeground)
+    }
+
+    pub fn process_win_event(event: WinEvent, window: &Window) -> Result<()> {
+        if event != WinEvent::SystemForeground || window.is_seelen_overlay() {
+            return Ok(());
+        }
+        let exe = window.exe().ok();
+        {
+            let mut focused = trace_lock!(FOCUSED_EXE);
+            if *focused == exe {
+                return Ok(());
+            }
+            *focused = exe;
+        }
+        Self::refresh()
+    }
+}
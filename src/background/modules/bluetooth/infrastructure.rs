@@ -0,0 +1,30 @@
+use seelen_core::system_state::BluetoothState;
+
+use crate::error_handler::Result;
+
+use super::BluetoothManager;
+
+#[tauri::command(async)]
+pub fn bluetooth_get_state() -> Result<BluetoothState> {
+    BluetoothManager::get_state()
+}
+
+#[tauri::command(async)]
+pub fn bluetooth_set_enabled(enabled: bool) -> Result<()> {
+    BluetoothManager::set_enabled(enabled)
+}
+
+#[tauri::command(async)]
+pub fn bluetooth_toggle() -> Result<()> {
+    BluetoothManager::toggle()
+}
+
+#[tauri::command(async)]
+pub fn bluetooth_connect(address: u64) -> Result<()> {
+    BluetoothManager::connect(address)
+}
+
+#[tauri::command(async)]
+pub fn bluetooth_disconnect(address: u64) -> Result<()> {
+    BluetoothManager::disconnect(address)
+}
@@ -0,0 +1,385 @@
+pub mod infrastructure;
+
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{
+    handlers::SeelenEvent,
+    system_state::{BluetoothDevice, BluetoothState},
+};
+use windows::{
+    core::{Interface, RuntimeType, GUID, HSTRING},
+    Devices::{
+        Bluetooth::{BluetoothDevice as WinBluetoothDevice, BluetoothLEDevice},
+        Enumeration::{
+            DeviceInformation, DeviceInformationKind, DeviceInformationUpdate, DeviceWatcher,
+        },
+        Radios::{Radio, RadioAccessStatus, RadioKind, RadioState},
+    },
+    Foundation::{Collections::IIterable, EventRegistrationToken, IReference, TypedEventHandler},
+    Win32::{
+        Devices::Bluetooth::{
+            BluetoothEnumerateInstalledServices, BluetoothFindFirstRadio, BluetoothFindRadioClose,
+            BluetoothGetDeviceInfo, BluetoothSetServiceState, BLUETOOTH_ADDRESS,
+            BLUETOOTH_ADDRESS_0, BLUETOOTH_DEVICE_INFO, BLUETOOTH_FIND_RADIO_PARAMS,
+            BLUETOOTH_SERVICE_DISABLE, BLUETOOTH_SERVICE_ENABLE,
+        },
+        Foundation::{CloseHandle, ERROR_SUCCESS, HANDLE},
+    },
+};
+use windows_core::IInspectable;
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::event_registry::EventRegistry,
+    trace_lock,
+    utils::event_queue::{EventQueue, QueueSender},
+};
+
+const IS_CONNECTED: &str = "System.Devices.Aep.IsConnected";
+const DEVICE_ADDRESS: &str = "System.Devices.Aep.DeviceAddress";
+const CONTAINER_ID: &str = "System.Devices.Aep.ContainerId";
+/// DEVPKEY_Bluetooth_Battery, reported by the PnP devices of the container
+const BATTERY: &str = "{104EA319-6EE2-4701-BD47-8DDBF425BBE5} 2";
+
+lazy_static! {
+    /// last emitted state, so only changes are emitted
+    static ref LAST: Mutex<Option<BluetoothState>> = Mutex::new(None);
+    /// services disabled on disconnect, they are not listed as installed until enabled again
+    static ref DISABLED_SERVICES: Mutex<HashMap<u64, Vec<GUID>>> = Mutex::new(HashMap::new());
+    static ref EVENTS: Mutex<Option<BluetoothEvents>> = Mutex::new(None);
+    /// device watchers fire a lot of events at once, so they are collapsed into a single emit
+    static ref EMIT_QUEUE: Result<EventQueue<()>> = EventQueue::new("Bluetooth", |_| Some(0), |_| {
+        log_error!(BluetoothManager::emit_changes());
+    });
+}
+
+struct BluetoothEvents {
+    radio: Option<(Radio, EventRegistrationToken)>,
+    watchers: Vec<DeviceWatcher>,
+}
+
+unsafe impl Send for BluetoothEvents {}
+
+/// Radio state and paired devices
+pub struct BluetoothManager;
+
+impl BluetoothManager {
+    fn radio() -> Result<Option<Radio>> {
+        let radios = Radio::GetRadiosAsync()?.get()?;
+        Ok(radios
+            .into_iter()
+            .find(|radio| radio.Kind().is_ok_and(|kind| kind == RadioKind::Bluetooth)))
+    }
+
+    fn additional_properties(keys: &[&str]) -> IIterable<HSTRING> {
+        keys.iter()
+            .map(|key| HSTRING::from(*key))
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /// Selectors of the paired classic and low energy devices
+    fn paired_selectors() -> Result<[(HSTRING, bool); 2]> {
+        Ok([
+            (
+                WinBluetoothDevice::GetDeviceSelectorFromPairingState(true)?,
+                false,
+            ),
+            (
+                BluetoothLEDevice::GetDeviceSelectorFromPairingState(true)?,
+                true,
+            ),
+        ])
+    }
+
+    fn property<T: RuntimeType + 'static>(info: &DeviceInformation, key: &str) -> Option<T> {
+        let value = info.Properties().ok()?.Lookup(&HSTRING::from(key)).ok()?;
+        value.cast::<IReference<T>>().ok()?.Value().ok()
+    }
+
+    /// The battery is not a property of the bluetooth device but of the PnP devices created for it
+    fn battery_of(container_id: GUID) -> Option<u8> {
+        let filter = HSTRING::from(format!(
+            "System.Devices.ContainerId:=\"{{{:?}}}\"",
+            container_id
+        ));
+        let devices = DeviceInformation::FindAllAsyncWithKindAqsFilterAndAdditionalProperties(
+            &filter,
+            &Self::additional_properties(&[BATTERY]),
+            DeviceInformationKind::Device,
+        )
+        .ok()?
+        .get()
+        .ok()?;
+        devices
+            .into_iter()
+            .find_map(|device| Self::property::<u8>(&device, BATTERY))
+    }
+
+    fn device_of(info: &DeviceInformation, low_energy: bool) -> Option<BluetoothDevice> {
+        let address = Self::property::<HSTRING>(info, DEVICE_ADDRESS)
+            .and_then(|address| parse_address(&address.to_string()))?;
+        Some(BluetoothDevice {
+            address,
+            name: info.Name().ok()?.to_string(),
+            connected: Self::property::<bool>(info, IS_CONNECTED).unwrap_or(false),
+            low_energy,
+            battery: Self::property::<GUID>(info, CONTAINER_ID).and_then(Self::battery_of),
+        })
+    }
+
+    fn get_devices() -> Result<Vec<BluetoothDevice>> {
+        let properties = Self::additional_properties(&[IS_CONNECTED, DEVICE_ADDRESS, CONTAINER_ID]);
+        let mut devices: Vec<BluetoothDevice> = Vec::new();
+        for (selector, low_energy) in Self::paired_selectors()? {
+            let infos = DeviceInformation::FindAllAsyncAqsFilterAndAdditionalProperties(
+                &selector,
+                &properties,
+            )?
+            .get()?;
+            for info in infos {
+                let Some(device) = Self::device_of(&info, low_energy) else {
+                    continue;
+                };
+                if !devices.iter().any(|d| d.address == device.address) {
+                    devices.push(device);
+                }
+            }
+        }
+        devices.sort_by(|a, b| b.connected.cmp(&a.connected).then(a.name.cmp(&b.name)));
+        Ok(devices)
+    }
+
+    pub fn get_state() -> Result<BluetoothState> {
+        let Some(radio) = Self::radio()? else {
+            return Ok(BluetoothState::default());
+        };
+        let enabled = radio.State()? == RadioState::On;
+        Ok(BluetoothState {
+            available: true,
+            enabled,
+            devices: if enabled {
+                Self::get_devices()?
+            } else {
+                Vec::new()
+            },
+        })
+    }
+
+    pub fn set_enabled(enabled: bool) -> Result<()> {
+        if Radio::RequestAccessAsync()?.get()? != RadioAccessStatus::Allowed {
+            return Err("Access to the bluetooth radio was denied".into());
+        }
+        let radio = Self::radio()?.ok_or("No bluetooth adapter found")?;
+        let state = if enabled {
+            RadioState::On
+        } else {
+            RadioState::Off
+        };
+        if radio.SetStateAsync(state)?.get()? != RadioAccessStatus::Allowed {
+            return Err("Failed to change the state of the bluetooth radio".into());
+        }
+        Self::emit_changes()
+    }
+
+    pub fn toggle() -> Result<()> {
+        let radio = Self::radio()?.ok_or("No bluetooth adapter found")?;
+        Self::set_enabled(radio.State()? != RadioState::On)
+    }
+
+    fn with_device_info<T>(
+        address: u64,
+        f: impl FnOnce(HANDLE, &BLUETOOTH_DEVICE_INFO) -> Result<T>,
+    ) -> Result<T> {
+        let params = BLUETOOTH_FIND_RADIO_PARAMS {
+            dwSize: std::mem::size_of::<BLUETOOTH_FIND_RADIO_PARAMS>() as u32,
+        };
+        let mut radio = HANDLE::default();
+        let find = unsafe { BluetoothFindFirstRadio(&params, &mut radio)? };
+
+        let mut info = BLUETOOTH_DEVICE_INFO {
+            dwSize: std::mem::size_of::<BLUETOOTH_DEVICE_INFO>() as u32,
+            Address: BLUETOOTH_ADDRESS {
+                Anonymous: BLUETOOTH_ADDRESS_0 { ullLong: address },
+            },
+            ..Default::default()
+        };
+        let result = match unsafe { BluetoothGetDeviceInfo(radio, &mut info) } {
+            code if code == ERROR_SUCCESS.0 => f(radio, &info),
+            _ => Err("Bluetooth device not found".into()),
+        };
+
+        unsafe {
+            let _ = BluetoothFindRadioClose(find);
+            let _ = CloseHandle(radio);
+        }
+        result
+    }
+
+    fn installed_services(radio: HANDLE, info: &BLUETOOTH_DEVICE_INFO) -> Vec<GUID> {
+        let mut count = 0;
+        unsafe { BluetoothEnumerateInstalledServices(radio, info, &mut count, None) };
+        let mut services = vec![GUID::zeroed(); count as usize];
+        unsafe {
+            BluetoothEnumerateInstalledServices(
+                radio,
+                info,
+                &mut count,
+                Some(services.as_mut_ptr()),
+            )
+        };
+        services.truncate(count as usize);
+        services
+    }
+
+    fn set_service_state(
+        radio: HANDLE,
+        info: &BLUETOOTH_DEVICE_INFO,
+        service: &GUID,
+        flags: u32,
+    ) -> bool {
+        unsafe { BluetoothSetServiceState(radio, info, service, flags) == ERROR_SUCCESS.0 }
+    }
+
+    /// Restarting the services of the device forces windows to connect to it again
+    pub fn connect(address: u64) -> Result<()> {
+        Self::with_device_info(address, |radio, info| {
+            let services = trace_lock!(DISABLED_SERVICES)
+                .remove(&address)
+                .unwrap_or_else(|| Self::installed_services(radio, info));
+            let mut enabled = false;
+            for service in &services {
+                Self::set_service_state(radio, info, service, BLUETOOTH_SERVICE_DISABLE);
+                enabled |= Self::set_service_state(radio, info, service, BLUETOOTH_SERVICE_ENABLE);
+            }
+            if !enabled {
+                return Err("Failed to connect the bluetooth device".into());
+            }
+            Ok(())
+        })?;
+        Self::emit_changes()
+    }
+
+    pub fn disconnect(address: u64) -> Result<()> {
+        Self::with_device_info(address, |radio, info| {
+            let services = Self::installed_services(radio, info);
+            let disabled: Vec<GUID> = services
+                .into_iter()
+                .filter(|service| {
+                    Self::set_service_state(radio, info, service, BLUETOOTH_SERVICE_DISABLE)
+                })
+                .collect();
+            if disabled.is_empty() {
+                return Err("Failed to disconnect the bluetooth device".into());
+            }
+            trace_lock!(DISABLED_SERVICES)
+                .entry(address)
+                .or_default()
+                .extend(disabled);
+            Ok(())
+        })?;
+        Self::emit_changes()
+    }
+
+    pub fn emit_changes() -> Result<()> {
+        let state = Self::get_state()?;
+        let mut last = trace_lock!(LAST);
+        if last.as_ref() != Some(&state) {
+            EventRegistry::emit(SeelenEvent::BluetoothChanged, &state)?;
+            *last = Some(state);
+        }
+        Ok(())
+    }
+
+    fn request_emit() {
+        EMIT_QUEUE.send(());
+    }
+
+    fn create_watcher(selector: &HSTRING) -> Result<DeviceWatcher> {
+        let properties = Self::additional_properties(&[IS_CONNECTED, DEVICE_ADDRESS, CONTAINER_ID]);
+        let watcher = DeviceInformation::CreateWatcherAqsFilterAndAdditionalProperties(
+            selector,
+            &properties,
+        )?;
+        watcher.Added(&TypedEventHandler::<DeviceWatcher, DeviceInformation>::new(
+            |_, _| {
+                Self::request_emit();
+                Ok(())
+            },
+        ))?;
+        let on_update = TypedEventHandler::<DeviceWatcher, DeviceInformationUpdate>::new(|_, _| {
+            Self::request_emit();
+            Ok(())
+        });
+        watcher.Updated(&on_update)?;
+        watcher.Removed(&on_update)?;
+        watcher.Start()?;
+        Ok(watcher)
+    }
+
+    pub fn register_events() -> Result<()> {
+        let mut events = trace_lock!(EVENTS);
+        if events.is_some() {
+            return Ok(());
+        }
+
+        let radio = match Self::radio()? {
+            Some(radio) => {
+                let token = radio.StateChanged(&TypedEventHandler::<Radio, IInspectable>::new(
+                    |_, _| {
+                        Self::request_emit();
+                        Ok(())
+                    },
+                ))?;
+                Some((radio, token))
+            }
+            None => None,
+        };
+
+        let mut watchers = Vec::new();
+        for (selector, _) in Self::paired_selectors()? {
+            watchers.push(Self::create_watcher(&selector)?);
+        }
+
+        *events = Some(BluetoothEvents { radio, watchers });
+        Self::request_emit();
+        Ok(())
+    }
+
+    pub fn release_events() {
+        let Some(events) = trace_lock!(EVENTS).take() else {
+            return;
+        };
+        if let Some((radio, token)) = events.radio {
+            log_error!(radio.RemoveStateChanged(token));
+        }
+        for watcher in events.watchers {
+            log_error!(watcher.Stop());
+        }
+    }
+}
+
+/// `a0:b1:c2:d3:e4:f5` -> `0xA0B1C2D3E4F5`
+fn parse_address(address: &str) -> Option<u64> {
+    let hex: String = address.split(':').collect();
+    if hex.len() != 12 {
+        return None;
+    }
+    u64::from_str_radix(&hex, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_address() {
+        assert_eq!(parse_address("a0:b1:c2:d3:e4:f5"), Some(0xA0B1C2D3E4F5));
+        assert_eq!(parse_address("00:00:00:00:00:01"), Some(1));
+        assert_eq!(parse_address("a0:b1:c2"), None);
+        assert_eq!(parse_address("zz:b1:c2:d3:e4:f5"), None);
+    }
+}
@@ -0,0 +1,27 @@
+use seelen_core::system_state::BreakReminderState;
+
+use crate::error_handler::Result;
+
+use super::BreakReminder;
+
+#[tauri::command(async)]
+pub fn break_reminder_get_state() -> BreakReminderState {
+    BreakReminder::get_state()
+}
+
+#[tauri::command(async)]
+pub fn break_reminder_take_break() -> Result<()> {
+    BreakReminder::take_break()
+}
+
+/// Postpones the current or the next break by the snooze time
+#[tauri::command(async)]
+pub fn break_reminder_snooze() -> Result<()> {
+    BreakReminder::snooze()
+}
+
+/// Ends the current break or restarts the interval to the next one
+#[tauri::command(async)]
+pub fn break_reminder_skip() -> Result<()> {
+    BreakReminder::skip()
+}
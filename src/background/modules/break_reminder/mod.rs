@@ -0,0 +1,296 @@
+pub mod infrastructure;
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{
+    handlers::SeelenEvent,
+    state::BreakReminderSettings,
+    system_state::{BreakPhase, BreakReminderState, MonitorBrightness},
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::{
+        brightness::BrightnessManager, event_registry::EventRegistry,
+        notifications::application::show_toast, session::SessionManager,
+    },
+    state::application::FULL_STATE,
+    t, trace_lock,
+    utils::spawn_named_thread,
+    windows_api::WindowsApi,
+};
+
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+/// time without input to consider the user away, the activity is not counted meanwhile
+const AWAY_AFTER: Duration = Duration::from_secs(60);
+
+lazy_static! {
+    static ref REMINDER: Mutex<Reminder> = Mutex::new(Reminder::default());
+}
+
+fn minutes(value: u32) -> Duration {
+    Duration::from_secs(value as u64 * 60)
+}
+
+#[derive(Debug, Default)]
+struct Reminder {
+    phase: BreakPhase,
+    paused: bool,
+    /// activity counted since the last break
+    worked: Duration,
+    /// activity until the next break, the interval or the snooze time
+    due: Duration,
+    /// time since the current break started
+    on_break: Duration,
+    /// time since the session was locked
+    locked_for: Duration,
+    /// brightness before dimming, restored when the break ends
+    dimmed: Option<Vec<MonitorBrightness>>,
+}
+
+impl Reminder {
+    fn start_working(&mut self, phase: BreakPhase, due: Duration) {
+        self.phase = phase;
+        self.worked = Duration::ZERO;
+        self.due = due;
+    }
+
+    fn start_break(&mut self) {
+        self.phase = BreakPhase::OnBreak;
+        self.paused = false;
+        self.on_break = Duration::ZERO;
+    }
+
+    /// Advances the reminder by the elapsed time, returns true if the state changed
+    fn update(
+        &mut self,
+        settings: &BreakReminderSettings,
+        elapsed: Duration,
+        idle: Duration,
+        locked: bool,
+    ) -> bool {
+        let before = (self.phase, self.paused);
+        if !settings.enabled {
+            self.phase = BreakPhase::Disabled;
+            self.paused = false;
+            return before != (self.phase, self.paused);
+        }
+
+        self.locked_for = match locked {
+            true => self.locked_for + elapsed,
+            false => Duration::ZERO,
+        };
+        let away = idle.max(self.locked_for);
+
+        match self.phase {
+            BreakPhase::Disabled => {
+                self.start_working(BreakPhase::Working, minutes(settings.interval))
+            }
+            BreakPhase::Working | BreakPhase::Snoozed => {
+                self.paused = locked || away >= AWAY_AFTER;
+                if away >= minutes(settings.duration) {
+                    // being away for the duration of a break is already a break
+                    self.start_working(BreakPhase::Working, minutes(settings.interval));
+                } else if !self.paused {
+                    self.worked += elapsed;
+                }
+                if self.worked >= self.due {
+                    self.start_break();
+                }
+            }
+            BreakPhase::OnBreak => {
+                self.on_break += elapsed;
+                if self.on_break >= minutes(settings.duration) {
+                    self.start_working(BreakPhase::Working, minutes(settings.interval));
+                }
+            }
+        }
+        before != (self.phase, self.paused)
+    }
+
+    fn remaining(&self, settings: &BreakReminderSettings) -> Duration {
+        match self.phase {
+            BreakPhase::Disabled => Duration::ZERO,
+            BreakPhase::Working | BreakPhase::Snoozed => self.due.saturating_sub(self.worked),
+            BreakPhase::OnBreak => minutes(settings.duration).saturating_sub(self.on_break),
+        }
+    }
+
+    fn state(&self, settings: &BreakReminderSettings) -> BreakReminderState {
+        let remaining = self.remaining(settings);
+        let running = self.phase != BreakPhase::Disabled && !self.paused;
+        BreakReminderState {
+            phase: self.phase,
+            paused: self.paused,
+            remaining: remaining.as_secs(),
+            ends_at: running.then(|| {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default();
+                (now + remaining).as_millis() as u64
+            }),
+        }
+    }
+}
+
+/// Reminds to take breaks after a time of activity, optionally dimming the monitors meanwhile
+pub struct BreakReminder;
+
+impl BreakReminder {
+    /// Dims the monitors when a break starts and restores them when it ends
+    fn on_phase_changed(reminder: &mut Reminder, previous: BreakPhase) {
+        let settings = &FULL_STATE.load().settings.break_reminder;
+        if reminder.phase == BreakPhase::OnBreak && previous != BreakPhase::OnBreak {
+            if settings.dim {
+                match BrightnessManager::dim(settings.dim_level.min(100) as f32 / 100.0) {
+                    Ok(previous) => reminder.dimmed = Some(previous),
+                    Err(err) => log::error!("Failed to dim the monitors: {:?}", err),
+                }
+            }
+            let title = t!("break_reminder.title");
+            let body = t!("break_reminder.body", minutes = settings.duration);
+            std::thread::spawn(move || {
+                log_error!(show_toast(&title, &body));
+            });
+        }
+        if reminder.phase != BreakPhase::OnBreak {
+            if let Some(previous) = reminder.dimmed.take() {
+                log_error!(BrightnessManager::restore(&previous));
+            }
+        }
+    }
+
+    fn emit(reminder: &Reminder) -> Result<()> {
+        let state = reminder.state(&FULL_STATE.load().settings.break_reminder);
+        EventRegistry::emit(SeelenEvent::BreakReminderChanged, state)?;
+        Ok(())
+    }
+
+    /// Applies a change made by the user
+    fn change<F: FnOnce(&mut Reminder, &BreakReminderSettings)>(f: F) -> Result<()> {
+        let settings = FULL_STATE.load().settings.break_reminder.clone();
+        if !settings.enabled {
+            return Err("Break reminders are disabled".into());
+        }
+        let mut reminder = trace_lock!(REMINDER);
+        let previous = reminder.phase;
+        f(&mut reminder, &settings);
+        Self::on_phase_changed(&mut reminder, previous);
+        Self::emit(&reminder)
+    }
+
+    fn tick(elapsed: Duration) -> Result<()> {
+        let settings = FULL_STATE.load().settings.break_reminder.clone();
+        let idle = WindowsApi::get_idle_time().unwrap_or_default();
+        let mut reminder = trace_lock!(REMINDER);
+        let previous = reminder.phase;
+        if reminder.update(&settings, elapsed, idle, SessionManager::is_locked()) {
+            Self::on_phase_changed(&mut reminder, previous);
+            Self::emit(&reminder)?;
+        }
+        Ok(())
+    }
+
+    pub fn start() -> Result<()> {
+        spawn_named_thread("Break Reminder", || {
+            let mut last_tick = Instant::now();
+            loop {
+                std::thread::sleep(TICK_INTERVAL);
+                // after the system is suspended the elapsed time is not activity
+                let elapsed = last_tick.elapsed().min(TICK_INTERVAL * 5);
+                last_tick = Instant::now();
+                log_error!(Self::tick(elapsed));
+            }
+        })?;
+        Ok(())
+    }
+
+    pub fn get_state() -> BreakReminderState {
+        trace_lock!(REMINDER).state(&FULL_STATE.load().settings.break_reminder)
+    }
+
+    pub fn take_break() -> Result<()> {
+        Self::change(|reminder, _| reminder.start_break())
+    }
+
+    /// Postpones the current or the next break by the snooze time
+    pub fn snooze() -> Result<()> {
+        Self::change(|reminder, settings| {
+            reminder.start_working(BreakPhase::Snoozed, minutes(settings.snooze))
+        })
+    }
+
+    /// Ends the current break or restarts the interval to the next one
+    pub fn skip() -> Result<()> {
+        Self::change(|reminder, settings| {
+            reminder.start_working(BreakPhase::Working, minutes(settings.interval))
+        })
+    }
+
+    /// Leaves the monitors with the brightness before the break, used on exit
+    pub fn restore() -> Result<()> {
+        if let Some(previous) = trace_lock!(REMINDER).dimmed.take() {
+            BrightnessManager::restore(&previous)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> BreakReminderSettings {
+        BreakReminderSettings {
+            enabled: true,
+            interval: 50,
+            duration: 5,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_break_cycle() {
+        let settings = settings();
+        let mut reminder = Reminder::default();
+        let active = Duration::ZERO;
+
+        assert!(reminder.update(&settings, TICK_INTERVAL, active, false));
+        assert_eq!(reminder.phase, BreakPhase::Working);
+
+        assert!(!reminder.update(&settings, minutes(49), active, false));
+        assert!(reminder.update(&settings, minutes(1), active, false));
+        assert_eq!(reminder.phase, BreakPhase::OnBreak);
+
+        assert!(!reminder.update(&settings, minutes(4), active, false));
+        assert!(reminder.update(&settings, minutes(1), active, false));
+        assert_eq!(reminder.phase, BreakPhase::Working);
+        assert_eq!(reminder.remaining(&settings), minutes(50));
+    }
+
+    #[test]
+    fn test_break_while_away() {
+        let settings = settings();
+        let mut reminder = Reminder::default();
+        reminder.update(&settings, TICK_INTERVAL, Duration::ZERO, false);
+        reminder.update(&settings, minutes(40), Duration::ZERO, false);
+
+        // away but not for long enough to count as a break, the activity is paused
+        assert!(reminder.update(&settings, minutes(2), minutes(2), false));
+        assert!(reminder.paused);
+        assert_eq!(reminder.remaining(&settings), minutes(10));
+
+        // away for the duration of a break, the next break is postponed
+        reminder.update(&settings, minutes(3), minutes(5), false);
+        assert_eq!(reminder.phase, BreakPhase::Working);
+        assert_eq!(reminder.remaining(&settings), minutes(50));
+
+        // a locked session is away even if the input timer was reset
+        reminder.update(&settings, minutes(20), Duration::ZERO, false);
+        reminder.update(&settings, minutes(5), Duration::ZERO, true);
+        assert_eq!(reminder.remaining(&settings), minutes(50));
+    }
+}
@@ -0,0 +1,33 @@
+use clap::Command;
+
+use crate::{error_handler::Result, get_subcommands, state::application::FULL_STATE};
+
+use super::BrightnessManager;
+
+get_subcommands![
+    /** Increases the brightness of all the monitors by the configured step. */
+    Up,
+    /** Decreases the brightness of all the monitors by the configured step. */
+    Down,
+];
+
+impl BrightnessManager {
+    pub const CLI_IDENTIFIER: &'static str = "brightness";
+
+    pub fn get_cli() -> Command {
+        Command::new(Self::CLI_IDENTIFIER)
+            .about("Changes the brightness of the monitors, useful to be bound to keys.")
+            .arg_required_else_help(true)
+            .subcommands(SubCommand::commands())
+    }
+
+    pub fn process(matches: &clap::ArgMatches) -> Result<()> {
+        let step = FULL_STATE.load().settings.hardware_keys.brightness_step as i32;
+        let subcommand = SubCommand::try_from(matches)?;
+        match subcommand {
+            SubCommand::Up => Self::step(step)?,
+            SubCommand::Down => Self::step(-step)?,
+        };
+        Ok(())
+    }
+}
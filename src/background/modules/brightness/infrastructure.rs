@@ -0,0 +1,33 @@
+use seelen_core::system_state::MonitorBrightness;
+
+use crate::{error_handler::Result, windows_api::WindowsApi};
+
+use super::BrightnessManager;
+
+fn main_monitor_id() -> Result<String> {
+    WindowsApi::monitor_name(WindowsApi::primary_monitor())
+}
+
+#[tauri::command(async)]
+pub fn get_main_monitor_brightness() -> Result<MonitorBrightness> {
+    let id = main_monitor_id()?;
+    BrightnessManager::get_all()?
+        .into_iter()
+        .find(|m| m.id == id)
+        .ok_or_else(|| "The main monitor has no brightness control".into())
+}
+
+#[tauri::command(async)]
+pub fn set_main_monitor_brightness(brightness: u32) -> Result<()> {
+    BrightnessManager::set(&main_monitor_id()?, brightness)
+}
+
+#[tauri::command(async)]
+pub fn brightness_get_all() -> Result<Vec<MonitorBrightness>> {
+    BrightnessManager::get_all()
+}
+
+#[tauri::command(async)]
+pub fn brightness_set(id: String, brightness: u32) -> Result<()> {
+    BrightnessManager::set(&id, brightness)
+}
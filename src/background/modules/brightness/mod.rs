@@ -0,0 +1,289 @@
+mod cli;
+pub mod infrastructure;
+mod wmi;
+
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{
+    handlers::SeelenEvent,
+    system_state::{BrightnessSource, MonitorBrightness, OsdEvent, OsdKind},
+};
+use widestring::U16CStr;
+use windows::{
+    core::HSTRING,
+    Win32::{
+        Devices::Display::{DestroyPhysicalMonitors, GetMonitorBrightness, SetMonitorBrightness},
+        Foundation::HANDLE,
+        Graphics::Gdi::{EnumDisplayDevicesW, DISPLAY_DEVICEW, HMONITOR},
+        UI::WindowsAndMessaging::EDD_GET_DEVICE_INTERFACE_NAME,
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::{
+        event_registry::EventRegistry, hardware_keys::HardwareKeys, power::profile::PowerProfile,
+    },
+    state::application::FULL_STATE,
+    trace_lock,
+    utils::spawn_named_thread,
+    windows_api::{MonitorEnumerator, WindowsApi},
+};
+
+lazy_static! {
+    /// last emitted state, so only changes are emitted
+    static ref LAST: Mutex<Vec<MonitorBrightness>> = Mutex::new(Vec::new());
+}
+
+static POLLING: AtomicBool = AtomicBool::new(false);
+
+/// Brightness of the internal panels (WMI) and the external monitors (DDC/CI)
+pub struct BrightnessManager;
+
+impl BrightnessManager {
+    /// `\\?\DISPLAY#BOE0867#4&2d6a1d0&0&UID8388688#{e6f07b5f-...}` -> `display\boe0867\4&2d6a1d0&0&uid8388688`
+    /// which is the start of the instance name used by WMI for the same panel
+    fn device_instance_of(monitor_id: &str) -> Option<String> {
+        let mut device = DISPLAY_DEVICEW {
+            cb: std::mem::size_of::<DISPLAY_DEVICEW>() as u32,
+            ..Default::default()
+        };
+        let adapter = HSTRING::from(format!(r"\\.\{}", monitor_id));
+        let found =
+            unsafe { EnumDisplayDevicesW(&adapter, 0, &mut device, EDD_GET_DEVICE_INTERFACE_NAME) };
+        if !found.as_bool() {
+            return None;
+        }
+        let interface = U16CStr::from_slice_truncate(&device.DeviceID)
+            .ok()?
+            .to_string_lossy();
+        let path = interface.trim_start_matches(r"\\?\").split("#{").next()?;
+        Some(path.replace('#', "\\").to_lowercase())
+    }
+
+    fn with_physical_monitor<T>(
+        hmonitor: HMONITOR,
+        f: impl FnOnce(HANDLE) -> Result<T>,
+    ) -> Result<T> {
+        let monitors = WindowsApi::physical_monitors(hmonitor)?;
+        let result = match monitors.first() {
+            Some(monitor) => f(monitor.hPhysicalMonitor),
+            None => Err("No physical monitor found".into()),
+        };
+        unsafe { DestroyPhysicalMonitors(&monitors)? };
+        result
+    }
+
+    fn ddc_get(hmonitor: HMONITOR) -> Result<(u32, u32, u32)> {
+        Self::with_physical_monitor(hmonitor, |handle| {
+            let (mut min, mut current, mut max) = (0, 0, 0);
+            if unsafe { GetMonitorBrightness(handle, &mut min, &mut current, &mut max) } == 0 {
+                return Err("GetMonitorBrightness failed".into());
+            }
+            Ok((min, current, max))
+        })
+    }
+
+    fn ddc_set(monitor_id: &str, value: u32) -> Result<()> {
+        let hmonitor = MonitorEnumerator::get_all()?
+            .into_iter()
+            .find(|m| WindowsApi::monitor_name(*m).is_ok_and(|name| name == monitor_id))
+            .ok_or("Monitor not found")?;
+        Self::with_physical_monitor(hmonitor, |handle| {
+            if unsafe { SetMonitorBrightness(handle, value) } == 0 {
+                return Err("SetMonitorBrightness failed".into());
+            }
+            Ok(())
+        })
+    }
+
+    /// Monitors without brightness control are not included
+    pub fn get_all() -> Result<Vec<MonitorBrightness>> {
+        // desktops have no internal panels, so failing here is not an error
+        let panels = wmi::get_panels().unwrap_or_default();
+        let mut monitors = Vec::new();
+        for hmonitor in MonitorEnumerator::get_all()? {
+            let id = WindowsApi::monitor_name(hmonitor)?;
+            let panel = Self::device_instance_of(&id).and_then(|instance| {
+                panels.iter().find(|p| {
+                    p.instance_name
+                        .to_lowercase()
+                        .starts_with(instance.as_str())
+                })
+            });
+            let brightness = match panel {
+                Some(panel) => MonitorBrightness {
+                    id,
+                    source: BrightnessSource::Wmi,
+                    min: 0,
+                    max: 100,
+                    current: panel.current,
+                },
+                None => match Self::ddc_get(hmonitor) {
+                    Ok((min, current, max)) => MonitorBrightness {
+                        id,
+                        source: BrightnessSource::Ddc,
+                        min,
+                        max,
+                        current,
+                    },
+                    // the monitor doesn't support DDC/CI
+                    Err(_) => continue,
+                },
+            };
+            monitors.push(brightness);
+        }
+        Ok(monitors)
+    }
+
+    fn set_one(brightness: &MonitorBrightness, value: u32) -> Result<()> {
+        let value = value.clamp(brightness.min, brightness.max);
+        match brightness.source {
+            BrightnessSource::Wmi => {
+                let instance = Self::device_instance_of(&brightness.id)
+                    .ok_or("Device instance of the panel not found")?;
+                wmi::set_panel_brightness(&instance, value as u8)
+            }
+            BrightnessSource::Ddc => Self::ddc_set(&brightness.id, value),
+        }
+    }
+
+    /// If the monitors are linked, the others are changed by the same proportion
+    pub fn set(monitor_id: &str, value: u32) -> Result<()> {
+        let monitors = Self::get_all()?;
+        let target = monitors
+            .iter()
+            .find(|m| m.id == monitor_id)
+            .ok_or("The monitor has no brightness control")?;
+        Self::set_one(target, value)?;
+
+        if FULL_STATE.load().settings.brightness.linked {
+            let ratio = proportion_of(target, value);
+            for other in monitors.iter().filter(|m| m.id != monitor_id) {
+                log_error!(Self::set_one(other, value_at(other, ratio)));
+            }
+        }
+        Self::emit_changes()
+    }
+
+    /// Dims all the monitors to the proportion of their range, returns the previous brightness
+    pub fn dim(proportion: f32) -> Result<Vec<MonitorBrightness>> {
+        let monitors = Self::get_all()?;
+        for monitor in &monitors {
+            if proportion_of(monitor, monitor.current) > proportion {
+                log_error!(Self::set_one(monitor, value_at(monitor, proportion)));
+            }
+        }
+        Self::emit_changes()?;
+        Ok(monitors)
+    }
+
+    /// Changes all the monitors by the percent of their range, used by the brightness keys
+    pub fn step(percent: i32) -> Result<()> {
+        for monitor in Self::get_all()? {
+            let current = proportion_of(&monitor, monitor.current);
+            let proportion = (current + percent as f32 / 100.0).clamp(0.0, 1.0);
+            log_error!(Self::set_one(&monitor, value_at(&monitor, proportion)));
+        }
+        Self::emit_changes()
+    }
+
+    /// Sets back the brightness returned by `dim`
+    pub fn restore(previous: &[MonitorBrightness]) -> Result<()> {
+        for monitor in previous {
+            log_error!(Self::set_one(monitor, monitor.current));
+        }
+        Self::emit_changes()
+    }
+
+    pub fn emit_changes() -> Result<()> {
+        let monitors = Self::get_all()?;
+        let mut last = trace_lock!(LAST);
+        if *last != monitors {
+            EventRegistry::emit(SeelenEvent::BrightnessChanged, &monitors)?;
+            // the brightness keys of laptops are handled by the firmware, so the popup is
+            // shown when the change is noticed instead of on the key press
+            let changed = monitors.iter().find(|m| {
+                last.iter()
+                    .any(|prev| prev.id == m.id && prev.current != m.current)
+            });
+            if let Some(monitor) = changed {
+                HardwareKeys::emit_osd(OsdEvent {
+                    kind: OsdKind::Brightness,
+                    value: Some(proportion_of(monitor, monitor.current)),
+                    muted: false,
+                    label: Some(monitor.id.clone()),
+                })?;
+            }
+            *last = monitors;
+        }
+        Ok(())
+    }
+
+    /// Brightness can be changed by the keyboard or the buttons of the monitor, so it is polled
+    pub fn register_events() -> Result<()> {
+        if POLLING.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+        spawn_named_thread("Brightness", || loop {
+            log_error!(Self::emit_changes());
+            // faster while the popups of the brightness keys are shown by seelen
+            let interval = match HardwareKeys::is_enabled() {
+                true => Duration::from_secs(1),
+                false => Duration::from_secs(5),
+            };
+            std::thread::sleep(PowerProfile::sampling_interval(interval));
+        })?;
+        Ok(())
+    }
+}
+
+/// Position of the value between min and max, from 0 to 1
+fn proportion_of(brightness: &MonitorBrightness, value: u32) -> f32 {
+    let range = brightness.max.saturating_sub(brightness.min);
+    if range == 0 {
+        return 1.0;
+    }
+    let value = value.clamp(brightness.min, brightness.max);
+    (value - brightness.min) as f32 / range as f32
+}
+
+fn value_at(brightness: &MonitorBrightness, proportion: f32) -> u32 {
+    let range = brightness.max.saturating_sub(brightness.min) as f32;
+    brightness.min + (range * proportion).round() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brightness(min: u32, max: u32) -> MonitorBrightness {
+        MonitorBrightness {
+            id: "DISPLAY1".to_string(),
+            source: BrightnessSource::Ddc,
+            min,
+            max,
+            current: min,
+        }
+    }
+
+    #[test]
+    fn test_linked_brightness() {
+        let panel = brightness(0, 100);
+        let external = brightness(20, 70);
+
+        assert_eq!(proportion_of(&panel, 40), 0.4);
+        assert_eq!(value_at(&external, proportion_of(&panel, 40)), 40);
+        assert_eq!(value_at(&external, proportion_of(&panel, 100)), 70);
+        assert_eq!(value_at(&panel, proportion_of(&external, 20)), 0);
+        // out of range values are clamped
+        assert_eq!(proportion_of(&external, 90), 1.0);
+        assert_eq!(proportion_of(&brightness(50, 50), 50), 1.0);
+    }
+}
@@ -0,0 +1,146 @@
+use windows::{
+    core::{w, BSTR, PCWSTR, VARIANT},
+    Win32::System::Wmi::{
+        IWbemClassObject, IWbemLocator, IWbemServices, WbemLocator, WBEM_FLAG_FORWARD_ONLY,
+        WBEM_FLAG_RETURN_IMMEDIATELY, WBEM_GENERIC_FLAG_TYPE, WBEM_INFINITE,
+    },
+};
+
+use crate::{error_handler::Result, windows_api::Com};
+
+/// Internal panel exposed on the `root\WMI` namespace
+pub struct WmiPanel {
+    /// e.g. `DISPLAY\BOE0867\4&2d6a1d0&0&UID8388688_0`
+    pub instance_name: String,
+    /// from 0 to 100
+    pub current: u32,
+}
+
+fn connect() -> Result<IWbemServices> {
+    let locator: IWbemLocator = Com::create_instance(&WbemLocator)?;
+    let server = unsafe {
+        locator.ConnectServer(
+            &BSTR::from(r"ROOT\WMI"),
+            &BSTR::new(),
+            &BSTR::new(),
+            &BSTR::new(),
+            0,
+            &BSTR::new(),
+            None,
+        )?
+    };
+    Ok(server)
+}
+
+fn query(server: &IWbemServices, query: &str) -> Result<Vec<IWbemClassObject>> {
+    let enumerator = unsafe {
+        server.ExecQuery(
+            &BSTR::from("WQL"),
+            &BSTR::from(query),
+            WBEM_FLAG_FORWARD_ONLY | WBEM_FLAG_RETURN_IMMEDIATELY,
+            None,
+        )?
+    };
+    let mut rows = Vec::new();
+    loop {
+        let mut row = [None; 1];
+        let mut returned = 0;
+        unsafe { enumerator.Next(WBEM_INFINITE, &mut row, &mut returned) }.ok()?;
+        match row[0].take() {
+            Some(row) if returned > 0 => rows.push(row),
+            _ => break,
+        }
+    }
+    Ok(rows)
+}
+
+fn get(object: &IWbemClassObject, name: PCWSTR) -> Result<VARIANT> {
+    let mut value = VARIANT::default();
+    unsafe { object.Get(name, 0, &mut value, None, None)? };
+    Ok(value)
+}
+
+fn instance_name_of(object: &IWbemClassObject) -> Result<String> {
+    Ok(BSTR::try_from(&get(object, w!("InstanceName"))?)?.to_string())
+}
+
+/// Desktops without internal panels return an empty list
+pub fn get_panels() -> Result<Vec<WmiPanel>> {
+    Com::run_with_context(|| {
+        let server = connect()?;
+        let rows = query(
+            &server,
+            "SELECT InstanceName, CurrentBrightness FROM WmiMonitorBrightness WHERE Active = TRUE",
+        )?;
+        let mut panels = Vec::new();
+        for row in rows {
+            panels.push(WmiPanel {
+                instance_name: instance_name_of(&row)?,
+                current: u8::try_from(&get(&row, w!("CurrentBrightness"))?)? as u32,
+            });
+        }
+        Ok(panels)
+    })
+}
+
+/// `instance_prefix` is the lowercased device instance without the `_0` suffix of WMI
+pub fn set_panel_brightness(instance_prefix: &str, brightness: u8) -> Result<()> {
+    Com::run_with_context(|| {
+        let server = connect()?;
+        let rows = query(
+            &server,
+            "SELECT __PATH, InstanceName FROM WmiMonitorBrightnessMethods",
+        )?;
+        let mut instance = None;
+        for row in rows {
+            if instance_name_of(&row)?
+                .to_lowercase()
+                .starts_with(instance_prefix)
+            {
+                instance = Some(row);
+                break;
+            }
+        }
+        let instance = instance.ok_or("WMI brightness methods not found for the panel")?;
+        let path = BSTR::try_from(&get(&instance, w!("__PATH"))?)?;
+
+        let mut class = None;
+        unsafe {
+            server.GetObject(
+                &BSTR::from("WmiMonitorBrightnessMethods"),
+                WBEM_GENERIC_FLAG_TYPE(0),
+                None,
+                Some(&mut class),
+                None,
+            )?
+        };
+        let class = class.ok_or("WmiMonitorBrightnessMethods class not found")?;
+
+        let mut signature = None;
+        unsafe {
+            class.GetMethod(
+                w!("WmiSetBrightness"),
+                0,
+                &mut signature,
+                std::ptr::null_mut(),
+            )?
+        };
+        let signature = signature.ok_or("WmiSetBrightness method not found")?;
+
+        unsafe {
+            let params = signature.SpawnInstance(0)?;
+            params.Put(w!("Timeout"), 0, &VARIANT::from(0i32), 0)?;
+            params.Put(w!("Brightness"), 0, &VARIANT::from(brightness), 0)?;
+            server.ExecMethod(
+                &path,
+                &BSTR::from("WmiSetBrightness"),
+                WBEM_GENERIC_FLAG_TYPE(0),
+                None,
+                &params,
+                None,
+                None,
+            )?;
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,25 @@
+use std::sync::atomic::Ordering;
+
+use seelen_core::{rect::Rect, system_state::CaptureResult};
+
+use crate::{error_handler::Result, hook::LAST_ACTIVE_NOT_SEELEN};
+
+use super::ScreenCapture;
+
+#[tauri::command(async)]
+pub fn capture_screen() -> Result<CaptureResult> {
+    ScreenCapture::screen()
+}
+
+/// Captures the given window or the last focused one
+#[tauri::command(async)]
+pub fn capture_window(hwnd: Option<isize>) -> Result<CaptureResult> {
+    let hwnd = hwnd.unwrap_or_else(|| LAST_ACTIVE_NOT_SEELEN.load(Ordering::Acquire));
+    ScreenCapture::window(hwnd)
+}
+
+/// The region is in physical pixels relative to the primary monitor
+#[tauri::command(async)]
+pub fn capture_region(rect: Rect) -> Result<CaptureResult> {
+    ScreenCapture::region(rect)
+}
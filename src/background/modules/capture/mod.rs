@@ -0,0 +1,237 @@
+pub mod infrastructure;
+
+use std::{ffi::c_void, path::PathBuf};
+
+use image::RgbaImage;
+use seelen_core::{
+    handlers::SeelenEvent,
+    rect::Rect,
+    system_state::{CaptureKind, CaptureResult},
+};
+use tauri::Manager;
+use windows::Win32::{
+    Foundation::{HWND, RECT, SYSTEMTIME},
+    Graphics::Gdi::{
+        BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject, GetDC,
+        GetDIBits, ReleaseDC, SelectObject, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, CAPTUREBLT,
+        DIB_RGB_COLORS, SRCCOPY,
+    },
+    System::SystemInformation::GetLocalTime,
+    UI::WindowsAndMessaging::{
+        GetSystemMetrics, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN,
+        SM_YVIRTUALSCREEN,
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    modules::event_registry::EventRegistry,
+    seelen::get_app_handle,
+    state::application::FULL_STATE,
+    windows_api::{Clipboard, WindowsApi},
+};
+
+/// Full screen, window and region screenshots
+pub struct ScreenCapture;
+
+impl ScreenCapture {
+    /// Copies the pixels of the rect as they are shown on the screen, overlapping windows included
+    pub fn capture_rect(rect: &RECT) -> Result<RgbaImage> {
+        let width = rect.right - rect.left;
+        let height = rect.bottom - rect.top;
+        if width <= 0 || height <= 0 {
+            return Err("The capture region is empty".into());
+        }
+
+        let mut info = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: width,
+                // negative height means top-down rows
+                biHeight: -height,
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut pixels = vec![0u8; width as usize * height as usize * 4];
+
+        let (copied, lines) = unsafe {
+            let screen = GetDC(HWND::default());
+            let memory = CreateCompatibleDC(screen);
+            let bitmap = CreateCompatibleBitmap(screen, width, height);
+            let previous = SelectObject(memory, bitmap);
+
+            let copied = BitBlt(
+                memory,
+                0,
+                0,
+                width,
+                height,
+                screen,
+                rect.left,
+                rect.top,
+                SRCCOPY | CAPTUREBLT,
+            );
+            let lines = GetDIBits(
+                memory,
+                bitmap,
+                0,
+                height as u32,
+                Some(pixels.as_mut_ptr() as *mut c_void),
+                &mut info,
+                DIB_RGB_COLORS,
+            );
+
+            SelectObject(memory, previous);
+            let _ = DeleteObject(bitmap);
+            let _ = DeleteDC(memory);
+            ReleaseDC(HWND::default(), screen);
+            (copied, lines)
+        };
+
+        copied?;
+        if lines == 0 {
+            return Err("Failed to read the captured pixels".into());
+        }
+
+        // GDI gives BGRA pixels without alpha
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+            pixel[3] = 255;
+        }
+        RgbaImage::from_raw(width as u32, height as u32, pixels)
+            .ok_or_else(|| "Invalid captured image".into())
+    }
+
+    /// Clipboard images are stored as a device independent bitmap with bottom-up BGRA rows
+    fn copy_to_clipboard(image: &RgbaImage) -> Result<()> {
+        let (width, height) = image.dimensions();
+        let header = BITMAPINFOHEADER {
+            biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: width as i32,
+            biHeight: height as i32,
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB.0,
+            biSizeImage: width * height * 4,
+            ..Default::default()
+        };
+
+        let header_size = std::mem::size_of::<BITMAPINFOHEADER>();
+        let mut data = Vec::with_capacity(header_size + header.biSizeImage as usize);
+        data.extend_from_slice(unsafe {
+            std::slice::from_raw_parts(&header as *const _ as *const u8, header_size)
+        });
+        for row in image.rows().rev() {
+            for pixel in row {
+                let [r, g, b, a] = pixel.0;
+                data.extend_from_slice(&[b, g, r, a]);
+            }
+        }
+
+        Clipboard::set_dib(&data)
+    }
+
+    fn folder() -> Result<PathBuf> {
+        match FULL_STATE.load().settings.capture.folder.clone() {
+            Some(folder) => Ok(folder),
+            None => Ok(get_app_handle().path().picture_dir()?.join("Screenshots")),
+        }
+    }
+
+    fn save(image: &RgbaImage) -> Result<PathBuf> {
+        let folder = Self::folder()?;
+        std::fs::create_dir_all(&folder)?;
+        let path = folder.join(capture_file_name(&unsafe { GetLocalTime() }));
+        image.save(&path)?;
+        Ok(path)
+    }
+
+    fn process(kind: CaptureKind, rect: &RECT) -> Result<CaptureResult> {
+        let image = Self::capture_rect(rect)?;
+        let settings = FULL_STATE.load().settings.capture.clone();
+
+        let path = match settings.save_to_folder {
+            true => Some(Self::save(&image)?),
+            false => None,
+        };
+        if settings.copy_to_clipboard {
+            Self::copy_to_clipboard(&image)?;
+        }
+
+        let result = CaptureResult {
+            kind,
+            path,
+            copied: settings.copy_to_clipboard,
+            width: image.width(),
+            height: image.height(),
+        };
+        EventRegistry::emit(SeelenEvent::CaptureTaken, &result)?;
+        Ok(result)
+    }
+
+    /// Captures all the monitors as a single image
+    pub fn screen() -> Result<CaptureResult> {
+        let rect = unsafe {
+            let left = GetSystemMetrics(SM_XVIRTUALSCREEN);
+            let top = GetSystemMetrics(SM_YVIRTUALSCREEN);
+            RECT {
+                left,
+                top,
+                right: left + GetSystemMetrics(SM_CXVIRTUALSCREEN),
+                bottom: top + GetSystemMetrics(SM_CYVIRTUALSCREEN),
+            }
+        };
+        Self::process(CaptureKind::Screen, &rect)
+    }
+
+    /// The drop shadow and invisible borders of the window are not included
+    pub fn window(hwnd: isize) -> Result<CaptureResult> {
+        let rect = WindowsApi::get_inner_window_rect(HWND(hwnd as _))?;
+        Self::process(CaptureKind::Window, &rect)
+    }
+
+    pub fn region(rect: Rect) -> Result<CaptureResult> {
+        Self::process(CaptureKind::Region, &RECT::from(rect))
+    }
+}
+
+/// `Screenshot 2024-08-01 15-30-05-042.png`, milliseconds avoid collisions on fast captures
+fn capture_file_name(time: &SYSTEMTIME) -> String {
+    format!(
+        "Screenshot {:04}-{:02}-{:02} {:02}-{:02}-{:02}-{:03}.png",
+        time.wYear,
+        time.wMonth,
+        time.wDay,
+        time.wHour,
+        time.wMinute,
+        time.wSecond,
+        time.wMilliseconds
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_file_name() {
+        let time = SYSTEMTIME {
+            wYear: 2024,
+            wMonth: 8,
+            wDay: 1,
+            wHour: 15,
+            wMinute: 30,
+            wSecond: 5,
+            wMilliseconds: 42,
+            ..Default::default()
+        };
+        assert_eq!(
+            capture_file_name(&time),
+            "Screenshot 2024-08-01 15-30-05-042.png"
+        );
+    }
+}
@@ -3,7 +3,11 @@ use std::sync::atomic::Ordering;
 use clap::Command;
 
 use crate::{
-    error_handler::Result, get_subcommands, hook::LOG_WIN_EVENTS, utils::TRACE_LOCK_ENABLED,
+    error_handler::Result,
+    get_subcommands,
+    hook::{get_event_queues_metrics, LOG_WIN_EVENTS},
+    modules::diagnostics::latency::InputLatency,
+    utils::{lock_stats::log_lock_stats, logger::SeelenLogger, TRACE_LOCK_ENABLED},
 };
 
 get_subcommands![
@@ -11,6 +15,21 @@ get_subcommands![
     ToggleWinEvents,
     /** Toggles the tracing of mutex lock */
     ToggleTraceLock,
+    /** Prints the depth and counters of the win event queues */
+    EventQueues,
+    /** Prints the wait and hold times recorded for each lock site */
+    LockStats,
+    /** Toggles the measurement of the window manager latency, from win event to SetWindowPos */
+    ToggleInputLatency,
+    /** Writes the input latency report into the diagnostics folder */
+    InputLatencyReport,
+    /** Changes the default log level until the next restart */
+    SetLogLevel(level: String => "off, error, warn, info, debug or trace"),
+    /** Changes the log level of a module until the next restart */
+    SetModuleLogLevel(
+        module: String => "Module path, ex: seelen_ui::hook",
+        level: String => "off, error, warn, info, debug or trace"
+    ),
 ];
 
 pub struct CliDebugger;
@@ -20,6 +39,7 @@ impl CliDebugger {
     pub fn get_cli() -> Command {
         Command::new(Self::CLI_IDENTIFIER)
             .about("Debugger cli")
+            .visible_alias("debug")
             .arg_required_else_help(true)
             .subcommands(SubCommand::commands())
     }
@@ -36,6 +56,30 @@ impl CliDebugger {
                     Ordering::Release,
                 );
             }
+            SubCommand::EventQueues => {
+                for metrics in get_event_queues_metrics() {
+                    log::info!("{:?}", metrics);
+                }
+            }
+            SubCommand::LockStats => log_lock_stats(),
+            SubCommand::ToggleInputLatency => {
+                log::info!(
+                    "Input latency measurement enabled: {}",
+                    InputLatency::toggle()
+                );
+            }
+            SubCommand::InputLatencyReport => {
+                log::info!(
+                    "Input latency report written to {:?}",
+                    InputLatency::dump()?
+                );
+            }
+            SubCommand::SetLogLevel(level) => {
+                SeelenLogger::set_level(None, SeelenLogger::parse_level(&level)?);
+            }
+            SubCommand::SetModuleLogLevel(module, level) => {
+                SeelenLogger::set_level(Some(module), SeelenLogger::parse_level(&level)?);
+            }
         };
         Ok(())
     }
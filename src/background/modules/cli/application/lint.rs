@@ -0,0 +1,110 @@
+use std::path::PathBuf;
+
+use clap::{Arg, ArgAction, ArgGroup, Command};
+use itertools::Itertools;
+
+use crate::{
+    error_handler::Result,
+    state::application::{
+        lint::{LintReport, LintSeverity},
+        FullState,
+    },
+};
+
+use super::{attach_console, detach_console};
+
+/// Validation of resources executed by the invoking process, useful on CI of resource repositories
+pub struct CliLint;
+impl CliLint {
+    pub const CLI_IDENTIFIER: &'static str = "lint";
+
+    pub fn get_cli() -> Command {
+        Command::new(Self::CLI_IDENTIFIER)
+            .about("Validates themes, placeholders and layouts using the same loaders of Seelen.")
+            .args([
+                Arg::new("path")
+                    .help("Resource file, theme folder or folder containing themes, placeholders or layouts folders.")
+                    .value_parser(clap::value_parser!(PathBuf))
+                    .action(ArgAction::Set),
+                Arg::new("all")
+                    .long("all")
+                    .action(ArgAction::SetTrue)
+                    .help("Validates all the resources installed by the user."),
+                Arg::new("json")
+                    .long("json")
+                    .action(ArgAction::SetTrue)
+                    .help("Prints the output as json, useful for scripts."),
+            ])
+            .group(
+                ArgGroup::new("target")
+                    .args(["path", "all"])
+                    .required(true),
+            )
+    }
+
+    /// The app is not running here, so the data folder is resolved like tauri does
+    fn user_data_dir() -> Result<PathBuf> {
+        let app_data = std::env::var("APPDATA").map_err(|_| "APPDATA is not defined")?;
+        Ok(PathBuf::from(app_data).join("com.seelen.seelen-ui"))
+    }
+
+    fn format(reports: &[LintReport]) -> String {
+        let mut output = String::new();
+        for report in reports.iter().filter(|r| !r.issues.is_empty()) {
+            output.push_str(&format!("{}\n", report.file.display()));
+            for issue in &report.issues {
+                let severity = match issue.severity {
+                    LintSeverity::Error => "error",
+                    LintSeverity::Warning => "warning",
+                };
+                let path = if issue.path.is_empty() {
+                    String::new()
+                } else {
+                    format!("{}: ", issue.path)
+                };
+                output.push_str(&format!("  {:<8} {}{}\n", severity, path, issue.message));
+            }
+        }
+
+        let (errors, warnings) = reports
+            .iter()
+            .flat_map(|r| &r.issues)
+            .partition::<Vec<_>, _>(|issue| issue.severity == LintSeverity::Error);
+        output.push_str(&format!(
+            "{} resources checked, {} errors, {} warnings",
+            reports.len(),
+            errors.len(),
+            warnings.len()
+        ));
+        output
+    }
+
+    pub fn process(matches: &clap::ArgMatches) -> Result<()> {
+        let path = match matches.get_one::<PathBuf>("path") {
+            Some(path) => Ok(path.clone()),
+            None => Self::user_data_dir(),
+        };
+        let reports = path.and_then(|path| FullState::lint_resources(&path));
+
+        attach_console()?;
+        match &reports {
+            Ok(reports) if matches.get_flag("json") => {
+                println!("{}", serde_json::to_string(reports)?)
+            }
+            Ok(reports) => println!("{}", Self::format(reports)),
+            Err(err) => eprintln!("{}", err.to_string().lines().next().unwrap_or_default()),
+        }
+        detach_console()?;
+
+        let failed = reports?
+            .iter()
+            .filter(|r| r.has_errors())
+            .map(|r| r.file.display().to_string())
+            .collect_vec();
+        if !failed.is_empty() {
+            // non zero exit code for CI
+            return Err(format!("Invalid resources: {}", failed.join(", ")).into());
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,7 @@
 mod debugger;
+mod lint;
+mod query;
+mod schema;
 
 use std::ffi::OsStr;
 use std::path::PathBuf;
@@ -8,17 +11,34 @@ use base64::Engine;
 use clap::{Arg, ArgAction, Command};
 use debugger::CliDebugger;
 use lazy_static::lazy_static;
+use lint::CliLint;
 use parking_lot::Mutex;
+use query::CliQuery;
+use schema::CliSchema;
+use seelen_core::system_state::RestorePointReason;
 use windows::Win32::System::Console::{AttachConsole, FreeConsole, ATTACH_PARENT_PROCESS};
 
 use crate::error_handler::Result;
+use crate::modules::actions::domain::ActionCatalog;
+use crate::modules::brightness::BrightnessManager;
+use crate::modules::cli::domain::Resource;
+use crate::modules::focus_history::FocusHistory;
+use crate::modules::hardware_keys::HardwareKeys;
+use crate::modules::live_theme::LiveTheme;
+use crate::modules::perf::{bench::StateBench, PerfMonitor};
+use crate::modules::picture_in_picture::PictureInPicture;
+use crate::modules::power::infrastructure::PowerManager;
+use crate::modules::private_session::PrivateSession;
+use crate::modules::restore_points::RestorePoints;
 use crate::modules::virtual_desk::{VirtualDesktopManager, VIRTUAL_DESKTOP_MANAGER};
-use crate::seelen::{Seelen, SEELEN};
+use crate::modules::window_effects::WindowEffects;
+use crate::modules::window_tags::WindowTags;
+use crate::seelen::{get_app_handle, Seelen, SEELEN};
 use crate::seelen_bar::FancyToolbar;
 use crate::seelen_rofi::SeelenRofi;
 use crate::seelen_weg::SeelenWeg;
 use crate::seelen_wm_v2::instance::WindowManagerV2;
-use crate::state::application::FULL_STATE;
+use crate::state::application::{FileListener, FULL_STATE};
 use crate::trace_lock;
 
 #[macro_export]
@@ -91,11 +111,31 @@ lazy_static! {
                     .long("silent")
                     .action(ArgAction::SetTrue)
                     .help("Start only background processes."),
+                Arg::new("replace")
+                    .long("replace")
+                    .action(ArgAction::SetTrue)
+                    .help("Asks the running instance to exit and takes its place."),
+                Arg::new("safe-mode")
+                    .long("safe-mode")
+                    .action(ArgAction::SetTrue)
+                    .help("Start only the settings, without window manager, dock, toolbar or hooks. Useful to fix a broken config."),
+                Arg::new("headless")
+                    .long("headless")
+                    .action(ArgAction::SetTrue)
+                    .help("Start the hooks, window manager and automation server without webviews (no dock, toolbar or launcher). Windows are tiled natively."),
                 Arg::new("verbose")
                     .short('V')
                     .long("verbose")
                     .action(ArgAction::SetTrue)
                     .help("Prints some extra process on the console."),
+                Arg::new("perf")
+                    .long("perf")
+                    .action(ArgAction::SetTrue)
+                    .help("Dumps the performance metrics of the running instance to the logs folder."),
+                Arg::new("bench-state")
+                    .long("bench-state")
+                    .action(ArgAction::SetTrue)
+                    .help("Benchmarks the state reload, theme parsing and layout computation on the running instance, results are written to the logs folder."),
                 Arg::new("version")
                     .short('v')
                     .long("version")
@@ -111,10 +151,24 @@ lazy_static! {
                 Command::new("settings").about("Opens the Seelen settings gui."),
                 VirtualDesktopManager::get_cli(),
                 CliDebugger::get_cli(),
+                CliQuery::get_cli(),
+                CliLint::get_cli(),
+                CliSchema::get_cli(),
                 FancyToolbar::get_cli(),
                 WindowManagerV2::get_cli(),
                 SeelenWeg::get_cli(),
                 SeelenRofi::get_cli(),
+                LiveTheme::get_cli(),
+                FocusHistory::get_cli(),
+                FileListener::get_cli(),
+                PictureInPicture::get_cli(),
+                BrightnessManager::get_cli(),
+                PrivateSession::get_cli(),
+                WindowTags::get_cli(),
+                WindowEffects::get_cli(),
+                HardwareKeys::get_cli(),
+                PowerManager::get_cli(),
+                ActionCatalog::get_cli(),
             ])
     ));
 }
@@ -149,6 +203,21 @@ pub fn is_just_getting_info(matches: &clap::ArgMatches) -> Result<bool> {
         r = true;
     }
 
+    if let Some((CliQuery::CLI_IDENTIFIER, sub_matches)) = matches.subcommand() {
+        CliQuery::process(sub_matches)?;
+        r = true;
+    }
+
+    if let Some((CliLint::CLI_IDENTIFIER, sub_matches)) = matches.subcommand() {
+        CliLint::process(sub_matches)?;
+        r = true;
+    }
+
+    if let Some((CliSchema::CLI_IDENTIFIER, sub_matches)) = matches.subcommand() {
+        CliSchema::process(sub_matches)?;
+        r = true;
+    }
+
     Ok(r)
 }
 
@@ -171,16 +240,36 @@ pub fn process_uri(uri: &str) -> Result<()> {
         }
     };
 
-    let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
-    let decoded = engine.decode(contents.as_bytes())?;
-
+    let resource = decode_slu(contents.as_bytes())?;
+    RestorePoints::create_before(RestorePointReason::ResourceImport, &resource.id);
     let mut state = FULL_STATE.load().cloned();
-    state.load_resource(serde_yaml::from_slice(&decoded)?)?;
+    state.load_resource(resource)?;
     state.store();
     Ok(())
 }
 
+/// Decodes the content of a `.slu` file (url safe base64 encoded yaml)
+pub fn decode_slu(contents: &[u8]) -> Result<Resource> {
+    let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    let decoded = engine.decode(contents.trim_ascii())?;
+    Ok(serde_yaml::from_slice(&decoded)?)
+}
+
 pub fn handle_cli_events(matches: &clap::ArgMatches) -> Result<()> {
+    if matches.get_flag("replace") {
+        log::info!("Exiting to be replaced by a new instance");
+        get_app_handle().exit(0);
+        return Ok(());
+    }
+
+    if matches.get_flag("perf") {
+        return PerfMonitor::dump();
+    }
+
+    if matches.get_flag("bench-state") {
+        return StateBench::dump();
+    }
+
     if let Some(uri) = matches.get_one::<String>("uri") {
         return process_uri(uri).map_err(|e| format!("Corrupted SLU file: {}", e).into());
     }
@@ -208,18 +297,46 @@ pub fn handle_cli_events(matches: &clap::ArgMatches) -> Result<()> {
                 }
             }
             SeelenWeg::CLI_IDENTIFIER => {
-                let mut seelen = trace_lock!(SEELEN);
-                for monitor in seelen.monitors_mut() {
-                    if let Some(weg) = monitor.weg_mut() {
-                        weg.process(matches)?;
-                    }
-                }
+                SeelenWeg::process(matches)?;
             }
             SeelenRofi::CLI_IDENTIFIER => {
                 if let Some(rofi) = trace_lock!(SEELEN).rofi_mut() {
                     rofi.process(matches)?;
                 }
             }
+            LiveTheme::CLI_IDENTIFIER => {
+                LiveTheme::process(matches)?;
+            }
+            FocusHistory::CLI_IDENTIFIER => {
+                FocusHistory::process(matches)?;
+            }
+            FileListener::CLI_IDENTIFIER => {
+                FileListener::process(matches)?;
+            }
+            PictureInPicture::CLI_IDENTIFIER => {
+                PictureInPicture::process(matches)?;
+            }
+            BrightnessManager::CLI_IDENTIFIER => {
+                BrightnessManager::process(matches)?;
+            }
+            PrivateSession::CLI_IDENTIFIER => {
+                PrivateSession::process(matches)?;
+            }
+            WindowTags::CLI_IDENTIFIER => {
+                WindowTags::process(matches)?;
+            }
+            WindowEffects::CLI_IDENTIFIER => {
+                WindowEffects::process(matches)?;
+            }
+            HardwareKeys::CLI_IDENTIFIER => {
+                HardwareKeys::process(matches)?;
+            }
+            PowerManager::CLI_IDENTIFIER => {
+                PowerManager::process(matches)?;
+            }
+            ActionCatalog::CLI_IDENTIFIER => {
+                ActionCatalog::process(matches)?;
+            }
             _ => {}
         }
         return Ok(());
@@ -0,0 +1,70 @@
+use clap::{Arg, ArgAction, Command};
+use serde_json::{json, Value};
+
+use crate::{error_handler::Result, modules::ipc::application::IpcClient};
+
+use super::{attach_console, detach_console};
+
+/// Queries executed by the invoking process against the running instance
+pub struct CliQuery;
+impl CliQuery {
+    pub const CLI_IDENTIFIER: &'static str = "get";
+
+    pub fn get_cli() -> Command {
+        Command::new(Self::CLI_IDENTIFIER)
+            .about("Prints the state of the running instance.")
+            .arg_required_else_help(true)
+            .args([
+                Arg::new("resource")
+                    .help("What to print.")
+                    .value_parser([
+                        "settings",
+                        "themes",
+                        "workspaces",
+                        "monitors",
+                        "windows",
+                        "window-filters",
+                        "window-at-cursor",
+                        "actions",
+                    ])
+                    .action(ArgAction::Set)
+                    .required(true),
+                Arg::new("json")
+                    .long("json")
+                    .action(ArgAction::SetTrue)
+                    .help("Prints the output as json, useful for scripts."),
+            ])
+    }
+
+    fn query(matches: &clap::ArgMatches) -> Result<String> {
+        let (method, params) = match matches.get_one::<String>("resource").map(|r| r.as_str()) {
+            Some("settings") => ("getSettings", Value::Null),
+            Some("themes") => ("getThemes", Value::Null),
+            Some("workspaces") => ("getWorkspaces", Value::Null),
+            Some("monitors") => ("getMonitors", Value::Null),
+            Some("windows") => ("getWindows", Value::Null),
+            Some("window-filters") => ("explainWindows", Value::Null),
+            Some("window-at-cursor") => ("inspectWindow", json!({ "flash": true })),
+            Some("actions") => ("listActions", Value::Null),
+            _ => return Err("Unknown resource".into()),
+        };
+
+        let result = IpcClient::connect()?.request(method, params)?;
+        if matches.get_flag("json") {
+            Ok(serde_json::to_string(&result)?)
+        } else {
+            Ok(serde_yaml::to_string(&result)?)
+        }
+    }
+
+    pub fn process(matches: &clap::ArgMatches) -> Result<()> {
+        let result = Self::query(matches);
+        attach_console()?;
+        match &result {
+            Ok(output) => println!("{}", output.trim_end()),
+            Err(err) => eprintln!("{}", err.to_string().lines().next().unwrap_or_default()),
+        }
+        detach_console()?;
+        result.map(|_| ())
+    }
+}
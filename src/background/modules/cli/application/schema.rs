@@ -0,0 +1,66 @@
+use std::path::{Path, PathBuf};
+
+use clap::{Arg, ArgAction, Command};
+use seelen_core::state::ConfigSchema;
+
+use crate::error_handler::Result;
+
+use super::{attach_console, detach_console};
+
+/// Generation of the config files reference executed by the invoking process, useful while developing
+pub struct CliSchema;
+impl CliSchema {
+    pub const CLI_IDENTIFIER: &'static str = "schema";
+
+    pub fn get_cli() -> Command {
+        Command::new(Self::CLI_IDENTIFIER)
+            .about("Generates the JSON schemas of the config files, used by editors to validate and autocomplete them.")
+            .args([
+                Arg::new("path")
+                    .help("Folder where the schemas will be written, the current one by default.")
+                    .value_parser(clap::value_parser!(PathBuf))
+                    .action(ArgAction::Set),
+                Arg::new("markdown")
+                    .long("markdown")
+                    .action(ArgAction::SetTrue)
+                    .help("Also writes a markdown reference of each config file."),
+            ])
+    }
+
+    fn write(folder: &Path, markdown: bool) -> Result<Vec<PathBuf>> {
+        std::fs::create_dir_all(folder)?;
+        let mut written = Vec::new();
+        for kind in ConfigSchema::ALL {
+            let path = folder.join(format!("{}.schema.json", kind.file_stem()));
+            std::fs::write(&path, kind.json())?;
+            written.push(path);
+
+            if markdown {
+                let path = folder.join(format!("{}.md", kind.file_stem()));
+                std::fs::write(&path, kind.markdown())?;
+                written.push(path);
+            }
+        }
+        Ok(written)
+    }
+
+    pub fn process(matches: &clap::ArgMatches) -> Result<()> {
+        let folder = match matches.get_one::<PathBuf>("path") {
+            Some(path) => path.clone(),
+            None => std::env::current_dir()?,
+        };
+        let written = Self::write(&folder, matches.get_flag("markdown"));
+
+        attach_console()?;
+        match &written {
+            Ok(files) => {
+                for file in files {
+                    println!("{}", file.display());
+                }
+            }
+            Err(err) => eprintln!("{}", err),
+        }
+        detach_console()?;
+        written.map(|_| ())
+    }
+}
@@ -0,0 +1,175 @@
+/// Fields of a date on the gregorian calendar, as seen on a time zone
+#[derive(Debug, Clone, Default)]
+pub struct ClockFields {
+    pub year: i32,
+    /// 1-12
+    pub month: u32,
+    /// 1-31
+    pub day: u32,
+    /// 0-23
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    /// 0-999
+    pub millisecond: u32,
+    /// localized names
+    pub month_name: String,
+    pub month_abbr: String,
+    pub weekday_name: String,
+    pub weekday_abbr: String,
+    /// abbreviation of the time zone, like `CET`
+    pub zone: String,
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// 1-366
+fn day_of_year(year: i32, month: u32, day: u32) -> u32 {
+    const CUMULATIVE: [u32; 12] = [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
+    let leap = (month > 2 && is_leap_year(year)) as u32;
+    CUMULATIVE[(month.clamp(1, 12) - 1) as usize] + day + leap
+}
+
+/// 1 = Monday ... 7 = Sunday
+fn iso_weekday(year: i32, month: u32, day: u32) -> u32 {
+    // Sakamoto's method, 0 = Sunday
+    const OFFSETS: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let year = if month < 3 { year - 1 } else { year };
+    let weekday = (year + year.div_euclid(4) - year.div_euclid(100)
+        + year.div_euclid(400)
+        + OFFSETS[(month.clamp(1, 12) - 1) as usize]
+        + day as i32)
+        .rem_euclid(7);
+    if weekday == 0 {
+        7
+    } else {
+        weekday as u32
+    }
+}
+
+fn iso_weeks_in_year(year: i32) -> u32 {
+    let p = |y: i32| (y + y.div_euclid(4) - y.div_euclid(100) + y.div_euclid(400)).rem_euclid(7);
+    if p(year) == 4 || p(year - 1) == 3 {
+        53
+    } else {
+        52
+    }
+}
+
+/// ISO 8601 week number, weeks start on Monday and the first one contains a Thursday
+pub fn iso_week(year: i32, month: u32, day: u32) -> u32 {
+    let ordinal = day_of_year(year, month, day) as i32;
+    let weekday = iso_weekday(year, month, day) as i32;
+    let week = (ordinal - weekday + 10) / 7;
+    if week < 1 {
+        iso_weeks_in_year(year - 1)
+    } else if week as u32 > iso_weeks_in_year(year) {
+        1
+    } else {
+        week as u32
+    }
+}
+
+/// The format shows the seconds, so the clock needs to be updated each second
+pub fn needs_seconds(format: &str) -> bool {
+    format.contains("%S") || format.contains("%T")
+}
+
+/// The format shows the tenths of second, so the clock needs to be updated each 100ms
+pub fn needs_fraction(format: &str) -> bool {
+    format.contains("%f")
+}
+
+/// strftime-like formatting, the unknown specifiers are kept as they are.
+///
+/// Supported: `%Y %y %m %d %e %H %I %M %S %f %p %a %A %b %B %j %V %u %Z %T %R %%`,
+/// `%f` being the tenths of second.
+pub fn format_clock(format: &str, fields: &ClockFields) -> String {
+    let mut result = String::with_capacity(format.len() * 2);
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            result.push(c);
+            continue;
+        }
+        let Some(specifier) = chars.next() else {
+            result.push('%');
+            break;
+        };
+        let hour12 = match fields.hour % 12 {
+            0 => 12,
+            hour => hour,
+        };
+        let text = match specifier {
+            'Y' => fields.year.to_string(),
+            'y' => format!("{:02}", fields.year.rem_euclid(100)),
+            'm' => format!("{:02}", fields.month),
+            'd' => format!("{:02}", fields.day),
+            'e' => format!("{:>2}", fields.day),
+            'H' => format!("{:02}", fields.hour),
+            'I' => format!("{:02}", hour12),
+            'M' => format!("{:02}", fields.minute),
+            'S' => format!("{:02}", fields.second),
+            'f' => (fields.millisecond / 100).to_string(),
+            'p' => if fields.hour < 12 { "AM" } else { "PM" }.to_owned(),
+            'a' => fields.weekday_abbr.clone(),
+            'A' => fields.weekday_name.clone(),
+            'b' => fields.month_abbr.clone(),
+            'B' => fields.month_name.clone(),
+            'j' => format!("{:03}", day_of_year(fields.year, fields.month, fields.day)),
+            'V' => format!("{:02}", iso_week(fields.year, fields.month, fields.day)),
+            'u' => iso_weekday(fields.year, fields.month, fields.day).to_string(),
+            'Z' => fields.zone.clone(),
+            'T' => format!(
+                "{:02}:{:02}:{:02}",
+                fields.hour, fields.minute, fields.second
+            ),
+            'R' => format!("{:02}:{:02}", fields.hour, fields.minute),
+            '%' => "%".to_owned(),
+            other => format!("%{}", other),
+        };
+        result.push_str(&text);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iso_week() {
+        assert_eq!(iso_week(2024, 1, 1), 1);
+        assert_eq!(iso_week(2024, 12, 30), 1);
+        assert_eq!(iso_week(2021, 1, 3), 53);
+        assert_eq!(iso_week(2020, 12, 31), 53);
+        assert_eq!(iso_week(2023, 1, 1), 52);
+        assert_eq!(iso_week(2026, 10, 16), 42);
+    }
+
+    #[test]
+    fn test_format_clock() {
+        let fields = ClockFields {
+            year: 2024,
+            month: 3,
+            day: 5,
+            hour: 0,
+            minute: 7,
+            second: 9,
+            millisecond: 450,
+            month_name: "March".to_owned(),
+            month_abbr: "Mar".to_owned(),
+            weekday_name: "Tuesday".to_owned(),
+            weekday_abbr: "Tue".to_owned(),
+            zone: "UTC".to_owned(),
+        };
+        assert_eq!(format_clock("%H:%M:%S", &fields), "00:07:09");
+        assert_eq!(format_clock("%T.%f", &fields), "00:07:09.4");
+        assert_eq!(format_clock("%I:%M %p", &fields), "12:07 AM");
+        assert_eq!(format_clock("%a %e %b %Y", &fields), "Tue  5 Mar 2024");
+        assert_eq!(format_clock("W%V %j %u", &fields), "W10 065 2");
+        assert_eq!(format_clock("100%% %q %", &fields), "100% %q %");
+    }
+}
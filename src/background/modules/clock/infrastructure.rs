@@ -0,0 +1,8 @@
+use seelen_core::system_state::ClockState;
+
+use super::Clock;
+
+#[tauri::command(async)]
+pub fn clock_get_state() -> ClockState {
+    Clock::get_state()
+}
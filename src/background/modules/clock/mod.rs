@@ -0,0 +1,251 @@
+mod format;
+pub mod infrastructure;
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{
+    handlers::SeelenEvent,
+    state::{ClockConfig, ClockTickMode},
+    system_state::{ClockEntry, ClockState},
+};
+use windows::{
+    core::HSTRING,
+    Foundation::DateTime,
+    Globalization::{Calendar, CalendarIdentifiers, ClockIdentifiers},
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::{event_registry::EventRegistry, resource_governor::ResourceGovernor},
+    seelen::Seelen,
+    state::application::FULL_STATE,
+    trace_lock,
+    utils::spawn_named_thread,
+    windows_api::Com,
+};
+
+use format::{format_clock, iso_week, needs_fraction, needs_seconds, ClockFields};
+
+/// 100ns intervals between 1601-01-01 (windows epoch) and 1970-01-01
+const UNIX_EPOCH_AS_WINDOWS_TICKS: i64 = 116_444_736_000_000_000;
+
+const PRECISE: u64 = 100;
+const SECOND: u64 = 1_000;
+const MINUTE: u64 = 60_000;
+/// the timers can wake up a bit before the requested time
+const EARLY_WAKE_TOLERANCE: u64 = 20;
+
+/// Time of the tick woken at `now`, the ticks woken just before a boundary are shown as the
+/// boundary, otherwise the previous second would be emitted twice.
+fn aligned_tick(now: u64, precision: u64) -> u64 {
+    let next = now - now % precision + precision;
+    if next - now <= EARLY_WAKE_TOLERANCE.min(precision / 10) {
+        next
+    } else {
+        now
+    }
+}
+
+lazy_static! {
+    static ref LAST: Mutex<ClockState> = Mutex::new(ClockState::default());
+    /// wakes up the ticker before the next tick, used when the clocks are changed
+    static ref WAKE_UP: (Sender<()>, Receiver<()>) = crossbeam_channel::unbounded();
+}
+
+struct ClockSource {
+    config: ClockConfig,
+    calendar: Calendar,
+}
+
+impl ClockSource {
+    fn new(config: &ClockConfig) -> Result<Self> {
+        let calendar = Calendar::new()?;
+        calendar.ChangeCalendarSystem(&CalendarIdentifiers::Gregorian()?)?;
+        calendar.ChangeClock(&ClockIdentifiers::TwentyFourHour()?)?;
+        if let Some(timezone) = &config.timezone {
+            calendar.ChangeTimeZone(&HSTRING::from(timezone))?;
+        }
+        Ok(Self {
+            config: config.clone(),
+            calendar,
+        })
+    }
+
+    fn entry(&self, time: u64) -> Result<ClockEntry> {
+        let calendar = &self.calendar;
+        calendar.SetDateTime(DateTime {
+            UniversalTime: UNIX_EPOCH_AS_WINDOWS_TICKS + time as i64 * 10_000,
+        })?;
+        let fields = ClockFields {
+            year: calendar.Year()?,
+            month: calendar.Month()? as u32,
+            day: calendar.Day()? as u32,
+            hour: calendar.Hour()? as u32,
+            minute: calendar.Minute()? as u32,
+            second: calendar.Second()? as u32,
+            millisecond: (time % 1000) as u32,
+            month_name: calendar.MonthAsFullString()?.to_string(),
+            month_abbr: calendar.MonthAsString(3)?.to_string(),
+            weekday_name: calendar.DayOfWeekAsFullString()?.to_string(),
+            weekday_abbr: calendar.DayOfWeekAsString(3)?.to_string(),
+            zone: calendar.TimeZoneAsString(3)?.to_string(),
+        };
+        Ok(ClockEntry {
+            id: self.config.id.clone(),
+            label: self.config.label.clone(),
+            timezone: calendar.GetTimeZone()?.to_string(),
+            text: format_clock(&self.config.format, &fields),
+            week: iso_week(fields.year, fields.month, fields.day),
+        })
+    }
+}
+
+/// Provider of the time for the clock widgets, emitted on ticks aligned to the tenth of
+/// second, the second or the minute depending on the formats in use and the tick mode.
+pub struct Clock;
+
+impl Clock {
+    pub fn start() -> Result<()> {
+        spawn_named_thread("Clock", || {
+            log_error!(Com::run_with_context(Self::ticker));
+        })?;
+        Ok(())
+    }
+
+    fn ticker() -> Result<()> {
+        let mut sources: Vec<ClockSource> = Vec::new();
+        let mut configs: Vec<ClockConfig> = Vec::new();
+        while Seelen::is_running() {
+            let state = FULL_STATE.load();
+            if state.settings.clock.clocks != configs {
+                configs = state.settings.clock.clocks.clone();
+                sources = Self::create_sources(&configs);
+            }
+
+            let mut precision = Self::precision(
+                state.settings.clock.tick_mode,
+                &configs,
+                &state.settings.date_format,
+            );
+            // nobody can see the seconds, the minutes are kept for the ones asking the state
+            if precision < MINUTE && !ResourceGovernor::is_toolbar_visible() {
+                precision = MINUTE;
+            }
+
+            let time = aligned_tick(Self::now(), precision);
+            log_error!(Self::emit(time, &sources));
+
+            // sleeps until the next boundary
+            let next = time - time % precision + precision;
+            let wait = Duration::from_millis(next.saturating_sub(Self::now()));
+            if let Err(RecvTimeoutError::Disconnected) = WAKE_UP.1.recv_timeout(wait) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Invalid time zones are logged and the clock is skipped
+    fn create_sources(configs: &[ClockConfig]) -> Vec<ClockSource> {
+        configs
+            .iter()
+            .filter_map(|config| match ClockSource::new(config) {
+                Ok(source) => Some(source),
+                Err(err) => {
+                    log::error!("Invalid clock {}: {:?}", config.id, err);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Milliseconds between ticks, `date_format` is the moment.js format of the toolbar date item
+    fn precision(mode: ClockTickMode, configs: &[ClockConfig], date_format: &str) -> u64 {
+        let any_format = |needs: fn(&str) -> bool| configs.iter().any(|c| needs(&c.format));
+        // `S` is the token of moment.js for the tenths of second
+        if mode == ClockTickMode::Precise || date_format.contains('S') || any_format(needs_fraction)
+        {
+            PRECISE
+        } else if mode == ClockTickMode::Seconds
+            || date_format.contains("ss")
+            || any_format(needs_seconds)
+        {
+            SECOND
+        } else {
+            MINUTE
+        }
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default()
+    }
+
+    fn emit(time: u64, sources: &[ClockSource]) -> Result<()> {
+        let mut clocks = Vec::with_capacity(sources.len());
+        for source in sources {
+            clocks.push(source.entry(time)?);
+        }
+        let state = ClockState { time, clocks };
+        EventRegistry::emit(SeelenEvent::ClockTick, &state)?;
+        *trace_lock!(LAST) = state;
+        Ok(())
+    }
+
+    pub fn get_state() -> ClockState {
+        trace_lock!(LAST).clone()
+    }
+
+    /// Emits a tick now, should be called when the settings change
+    pub fn refresh() {
+        let _ = WAKE_UP.0.send(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aligned_tick() {
+        assert_eq!(aligned_tick(10_995, SECOND), 11_000);
+        assert_eq!(aligned_tick(11_004, SECOND), 11_004);
+        assert_eq!(aligned_tick(10_950, SECOND), 10_950);
+        assert_eq!(aligned_tick(10_192, PRECISE), 10_200);
+        assert_eq!(aligned_tick(10_185, PRECISE), 10_185);
+        assert_eq!(aligned_tick(119_990, MINUTE), 120_000);
+    }
+
+    #[test]
+    fn test_precision() {
+        let clock = |format: &str| ClockConfig {
+            format: format.to_owned(),
+            ..Default::default()
+        };
+        let date = "ddd D MMM, hh:mm A";
+        assert_eq!(
+            Clock::precision(ClockTickMode::Auto, &[clock("%H:%M")], date),
+            MINUTE
+        );
+        assert_eq!(
+            Clock::precision(ClockTickMode::Auto, &[clock("%T")], date),
+            SECOND
+        );
+        assert_eq!(
+            Clock::precision(ClockTickMode::Auto, &[clock("%T.%f")], date),
+            PRECISE
+        );
+        assert_eq!(
+            Clock::precision(ClockTickMode::Auto, &[], "hh:mm:ss"),
+            SECOND
+        );
+        assert_eq!(Clock::precision(ClockTickMode::Seconds, &[], date), SECOND);
+        assert_eq!(Clock::precision(ClockTickMode::Precise, &[], date), PRECISE);
+    }
+}
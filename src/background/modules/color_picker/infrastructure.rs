@@ -0,0 +1,11 @@
+use seelen_core::system_state::{ColorFormat, PickedColor};
+
+use crate::error_handler::Result;
+
+use super::ColorPicker;
+
+/// Samples the color under the cursor and copies it to the clipboard
+#[tauri::command(async)]
+pub fn color_picker_pick(format: Option<ColorFormat>) -> Result<PickedColor> {
+    ColorPicker::pick(format.unwrap_or_default())
+}
@@ -0,0 +1,96 @@
+pub mod infrastructure;
+
+use seelen_core::{
+    handlers::SeelenEvent,
+    system_state::{ColorFormat, ColorPickerLoupe, PickedColor},
+};
+use windows::Win32::Foundation::RECT;
+
+use crate::{
+    error_handler::Result,
+    modules::{
+        capture::ScreenCapture,
+        emit_scheduler::EmitScheduler,
+        input::{domain::Point, Mouse},
+    },
+    windows_api::Clipboard,
+};
+
+/// pixels on each side of the cursor included on the loupe
+const LOUPE_RADIUS: i32 = 5;
+
+/// Pixel color under the cursor, with a zoomed area for previews
+pub struct ColorPicker;
+
+impl ColorPicker {
+    pub fn pick(format: ColorFormat) -> Result<PickedColor> {
+        let pos = Mouse::get_cursor_pos()?;
+        let image = ScreenCapture::capture_rect(&RECT {
+            left: pos.get_x(),
+            top: pos.get_y(),
+            right: pos.get_x() + 1,
+            bottom: pos.get_y() + 1,
+        })?;
+        let [r, g, b, _] = image.get_pixel(0, 0).0;
+        let color = PickedColor {
+            r,
+            g,
+            b,
+            text: format_color(format, r, g, b),
+        };
+        Clipboard::set_text(&color.text)?;
+        Ok(color)
+    }
+
+    pub fn wants_loupe() -> bool {
+        EmitScheduler::has_subscribers(SeelenEvent::ColorPickerLoupe)
+    }
+
+    /// Called by the mouse loop on each frame while a webview is subscribed to the loupe
+    pub fn emit_loupe(pos: &Point) -> Result<()> {
+        let image = ScreenCapture::capture_rect(&RECT {
+            left: pos.get_x() - LOUPE_RADIUS,
+            top: pos.get_y() - LOUPE_RADIUS,
+            right: pos.get_x() + LOUPE_RADIUS + 1,
+            bottom: pos.get_y() + LOUPE_RADIUS + 1,
+        })?;
+        EmitScheduler::schedule(
+            SeelenEvent::ColorPickerLoupe,
+            ColorPickerLoupe {
+                x: pos.get_x(),
+                y: pos.get_y(),
+                size: image.width(),
+                pixels: image
+                    .pixels()
+                    .map(|pixel| {
+                        let [r, g, b, _] = pixel.0;
+                        u32::from_be_bytes([0, r, g, b])
+                    })
+                    .collect(),
+            },
+        );
+        Ok(())
+    }
+}
+
+fn format_color(format: ColorFormat, r: u8, g: u8, b: u8) -> String {
+    match format {
+        ColorFormat::Hex => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        ColorFormat::Rgb => format!("rgb({}, {}, {})", r, g, b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_color() {
+        assert_eq!(format_color(ColorFormat::Hex, 26, 43, 60), "#1a2b3c");
+        assert_eq!(format_color(ColorFormat::Hex, 0, 0, 0), "#000000");
+        assert_eq!(
+            format_color(ColorFormat::Rgb, 26, 43, 255),
+            "rgb(26, 43, 255)"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error_handler::Result, log_error, modules::notifications::application::show_toast, t};
+
+const LAUNCH_FILE: &str = "launch.json";
+/// abnormal exits in a row before starting on safe mode with the default settings
+const MAX_RAPID_CRASHES: u32 = 3;
+/// runs longer than this are not considered part of a crash loop
+const STABLE_AFTER: Duration = Duration::from_secs(60);
+
+static RECOVERING: AtomicBool = AtomicBool::new(false);
+static LAUNCH_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct LaunchRecord {
+    /// set on launch and cleared on a clean exit
+    running: bool,
+    /// the launch ran long enough to not be part of a crash loop
+    stable: bool,
+    /// abnormal exits in a row that happened before being stable
+    rapid_crashes: u32,
+}
+
+/// Record of the launch starting now and whether it should recover using the defaults
+fn next_launch(previous: &LaunchRecord) -> (LaunchRecord, bool) {
+    let rapid_crashes = match previous.running && !previous.stable {
+        true => previous.rapid_crashes + 1,
+        false => 0,
+    };
+    let recover = rapid_crashes >= MAX_RAPID_CRASHES;
+    let record = LaunchRecord {
+        running: true,
+        stable: false,
+        // the launch after the recovery tries the user settings again
+        rapid_crashes: if recover { 0 } else { rapid_crashes },
+    };
+    (record, recover)
+}
+
+/// Detects the "app flashes and dies" loops, caused most of the time by a setting or resource
+/// breaking the startup. The user files are never modified, only ignored for one launch.
+pub struct CrashGuard;
+
+impl CrashGuard {
+    fn path() -> Result<&'static PathBuf> {
+        LAUNCH_PATH
+            .get()
+            .ok_or_else(|| "Crash guard is not initialized".into())
+    }
+
+    fn read() -> LaunchRecord {
+        Self::path()
+            .ok()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn write(record: &LaunchRecord) -> Result<()> {
+        std::fs::write(Self::path()?, serde_json::to_string_pretty(record)?)?;
+        Ok(())
+    }
+
+    /// Should be called before the state is loaded, so the defaults are used while recovering
+    pub fn on_launch(data_dir: &Path) -> Result<()> {
+        LAUNCH_PATH
+            .set(data_dir.join(LAUNCH_FILE))
+            .map_err(|_| "Crash guard already initialized")?;
+
+        let previous = Self::read();
+        let (record, recover) = next_launch(&previous);
+        Self::write(&record)?;
+
+        if recover {
+            log::warn!(
+                "Seelen UI exited unexpectedly {} times in a row, starting on safe mode with the default settings",
+                previous.rapid_crashes + 1
+            );
+            RECOVERING.store(true, Ordering::Release);
+            let title = t!("crash_guard.title");
+            let body = t!("crash_guard.body", count = previous.rapid_crashes + 1);
+            std::thread::spawn(move || {
+                log_error!(show_toast(&title, &body));
+            });
+        }
+
+        std::thread::spawn(|| {
+            std::thread::sleep(STABLE_AFTER);
+            let mut record = Self::read();
+            if record.running {
+                record.stable = true;
+                log_error!(Self::write(&record));
+            }
+        });
+        Ok(())
+    }
+
+    /// Should be called on every intended exit or restart
+    pub fn on_exit() -> Result<()> {
+        Self::write(&LaunchRecord::default())
+    }
+
+    /// Started after repeated crashes, the user settings are ignored until the next launch
+    pub fn is_recovering() -> bool {
+        RECOVERING.load(Ordering::Acquire)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crash_loop_detection() {
+        let mut record = LaunchRecord::default();
+        for _ in 0..MAX_RAPID_CRASHES {
+            let (next, recover) = next_launch(&record);
+            assert!(!recover);
+            record = next;
+        }
+        // the last launch crashed too
+        let (next, recover) = next_launch(&record);
+        assert!(recover);
+        assert_eq!(next.rapid_crashes, 0);
+
+        // crashes after being stable are not part of a loop
+        let stable = LaunchRecord {
+            running: true,
+            stable: true,
+            rapid_crashes: MAX_RAPID_CRASHES - 1,
+        };
+        assert_eq!(next_launch(&stable), (next.clone(), false));
+
+        // clean exits reset the count
+        assert_eq!(next_launch(&LaunchRecord::default()), (next, false));
+    }
+}
@@ -0,0 +1,8 @@
+use seelen_core::system_state::DailyWallpaper;
+
+use super::DailyWallpapers;
+
+#[tauri::command(async)]
+pub fn daily_wallpaper_get() -> Option<DailyWallpaper> {
+    DailyWallpapers::current()
+}
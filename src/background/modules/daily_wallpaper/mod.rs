@@ -0,0 +1,323 @@
+pub mod infrastructure;
+
+use std::{
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{
+    handlers::SeelenEvent,
+    state::{Settings, WallpaperSource},
+    system_state::DailyWallpaper,
+};
+use serde::Deserialize;
+use tauri::Manager;
+use tauri_plugin_http::reqwest;
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::event_registry::EventRegistry,
+    seelen::{get_app_handle, Seelen},
+    state::application::FULL_STATE,
+    trace_lock,
+    utils::spawn_named_thread,
+};
+
+lazy_static! {
+    static ref CACHE_DIR: PathBuf = get_app_handle()
+        .path()
+        .app_cache_dir()
+        .expect("Failed to get app cache dir")
+        .join("daily_wallpapers");
+    static ref CURRENT: Mutex<Option<DailyWallpaper>> = Mutex::new(None);
+    /// wakes up the refresher before the next check, used when the source is changed
+    static ref WAKE_UP: (Sender<()>, Receiver<()>) = crossbeam_channel::unbounded();
+}
+
+/// Image and metadata given by a provider, before being downloaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RemoteWallpaper {
+    url: String,
+    title: Option<String>,
+    copyright: Option<String>,
+    link: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct BingResponse {
+    images: Vec<BingImage>,
+}
+
+#[derive(Deserialize)]
+struct BingImage {
+    /// `/th?id=OHR.Name_EN-US0123456789`, the resolution is added as suffix
+    urlbase: String,
+    title: Option<String>,
+    copyright: Option<String>,
+    copyrightlink: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SpotlightResponse {
+    batchrsp: SpotlightBatch,
+}
+
+#[derive(Deserialize)]
+struct SpotlightBatch {
+    items: Vec<SpotlightItem>,
+}
+
+#[derive(Deserialize)]
+struct SpotlightItem {
+    /// json encoded `SpotlightContent`
+    item: String,
+}
+
+#[derive(Deserialize)]
+struct SpotlightContent {
+    ad: SpotlightAd,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpotlightAd {
+    landscape_image: SpotlightImage,
+    title: Option<String>,
+    copyright: Option<String>,
+    cta_uri: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SpotlightImage {
+    asset: String,
+}
+
+fn parse_bing(body: &str) -> Result<RemoteWallpaper> {
+    let response: BingResponse = serde_json::from_str(body)?;
+    let image = response
+        .images
+        .into_iter()
+        .next()
+        .ok_or("Bing sent no images")?;
+    Ok(RemoteWallpaper {
+        url: format!("https://www.bing.com{}_UHD.jpg", image.urlbase),
+        title: image.title,
+        copyright: image.copyright,
+        link: image.copyrightlink.filter(|link| link.starts_with("http")),
+    })
+}
+
+fn parse_spotlight(body: &str) -> Result<RemoteWallpaper> {
+    let response: SpotlightResponse = serde_json::from_str(body)?;
+    let item = response
+        .batchrsp
+        .items
+        .into_iter()
+        .next()
+        .ok_or("Spotlight sent no images")?;
+    let content: SpotlightContent = serde_json::from_str(&item.item)?;
+    let ad = content.ad;
+    Ok(RemoteWallpaper {
+        url: ad.landscape_image.asset,
+        title: ad.title,
+        copyright: ad.copyright,
+        // links are meant to be opened by edge, e.g. `microsoft-edge:https://www.bing.com/...`
+        link: ad
+            .cta_uri
+            .map(|uri| uri.trim_start_matches("microsoft-edge:").to_string())
+            .filter(|link| link.starts_with("http")),
+    })
+}
+
+/// Daily images of Bing and Windows Spotlight used as wallpaper. The last image of each
+/// source is cached, so it is still shown while offline.
+pub struct DailyWallpapers;
+
+impl DailyWallpapers {
+    /// the providers change the image once a day, a few hours of delay is fine
+    const MAX_AGE: Duration = Duration::from_secs(6 * 60 * 60);
+    const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+    pub fn start() -> Result<()> {
+        spawn_named_thread("Daily Wallpaper", || {
+            while Seelen::is_running() {
+                log_error!(Self::refresh());
+                if let Err(RecvTimeoutError::Disconnected) =
+                    WAKE_UP.1.recv_timeout(Self::CHECK_INTERVAL)
+                {
+                    break;
+                }
+            }
+        })?;
+        Ok(())
+    }
+
+    /// Checks the source again, should be called when the settings change
+    pub fn wake_up() {
+        let _ = WAKE_UP.0.send(());
+    }
+
+    /// The cached image of the selected source
+    pub fn current() -> Option<DailyWallpaper> {
+        let source = FULL_STATE.load().settings.wall.source;
+        trace_lock!(CURRENT)
+            .clone()
+            .filter(|current| current.source == source)
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default()
+    }
+
+    fn metadata_path(source: WallpaperSource) -> PathBuf {
+        CACHE_DIR.join(format!("{:?}.json", source).to_lowercase())
+    }
+
+    fn read_cache(source: WallpaperSource) -> Option<DailyWallpaper> {
+        let content = std::fs::read_to_string(Self::metadata_path(source)).ok()?;
+        let cached: DailyWallpaper = serde_json::from_str(&content).ok()?;
+        cached.path.exists().then_some(cached)
+    }
+
+    fn is_fresh(wallpaper: &DailyWallpaper) -> bool {
+        Self::now().saturating_sub(wallpaper.fetched_at) < Self::MAX_AGE.as_secs()
+    }
+
+    fn refresh() -> Result<()> {
+        let state = FULL_STATE.load();
+        let source = state.settings.wall.source;
+        if !state.is_wall_enabled() || source == WallpaperSource::Files {
+            return Ok(());
+        }
+
+        let current = Self::current().or_else(|| Self::read_cache(source));
+        if let Some(current) = &current {
+            if Self::is_fresh(current) {
+                return Self::set_current(current.clone());
+            }
+        }
+
+        match Self::fetch(source) {
+            Ok(wallpaper) => Self::set_current(wallpaper),
+            Err(err) => {
+                // the old image is better than nothing
+                if let Some(current) = current {
+                    Self::set_current(current)?;
+                }
+                Err(err)
+            }
+        }
+    }
+
+    fn set_current(wallpaper: DailyWallpaper) -> Result<()> {
+        let mut current = trace_lock!(CURRENT);
+        if current.as_ref() != Some(&wallpaper) {
+            EventRegistry::emit(SeelenEvent::WallDailyWallpaperChanged, &wallpaper)?;
+            *current = Some(wallpaper);
+        }
+        Ok(())
+    }
+
+    async fn get(url: &str) -> Result<reqwest::Response> {
+        Ok(reqwest::Client::new()
+            .get(url)
+            .timeout(Self::REQUEST_TIMEOUT)
+            .send()
+            .await?
+            .error_for_status()?)
+    }
+
+    fn fetch(source: WallpaperSource) -> Result<DailyWallpaper> {
+        let locale = Settings::get_locale().unwrap_or_else(|| "en-US".to_string());
+        let country = locale.rsplit('-').next().unwrap_or("US").to_string();
+        let url = match source {
+            WallpaperSource::Bing => format!(
+                "https://www.bing.com/HPImageArchive.aspx?format=js&idx=0&n=1&mkt={}",
+                locale
+            ),
+            WallpaperSource::Spotlight => format!(
+                "https://fd.api.iris.microsoft.com/v4/api/selection?placement=88000820&bcnt=1&country={}&locale={}&fmt=json",
+                country, locale
+            ),
+            WallpaperSource::Files => return Err("Files is not a daily source".into()),
+        };
+
+        let (remote, image) = tauri::async_runtime::block_on(async move {
+            let body = Self::get(&url).await?.text().await?;
+            let remote = match source {
+                WallpaperSource::Spotlight => parse_spotlight(&body)?,
+                _ => parse_bing(&body)?,
+            };
+            let image = Self::get(&remote.url).await?.bytes().await?;
+            Result::Ok((remote, image))
+        })?;
+
+        let fetched_at = Self::now();
+        let prefix = format!("{:?}-", source).to_lowercase();
+        std::fs::create_dir_all(CACHE_DIR.as_path())?;
+        // only the last image of each source is kept
+        for entry in std::fs::read_dir(CACHE_DIR.as_path())?.flatten() {
+            if entry.file_name().to_string_lossy().starts_with(&prefix) {
+                log_error!(std::fs::remove_file(entry.path()));
+            }
+        }
+
+        let path = CACHE_DIR.join(format!("{}{}.jpg", prefix, fetched_at));
+        std::fs::write(&path, &image)?;
+        let wallpaper = DailyWallpaper {
+            source,
+            path,
+            title: remote.title,
+            copyright: remote.copyright,
+            link: remote.link,
+            fetched_at,
+        };
+        std::fs::write(
+            Self::metadata_path(source),
+            serde_json::to_string_pretty(&wallpaper)?,
+        )?;
+        log::info!("Daily wallpaper downloaded from {:?}", source);
+        Ok(wallpaper)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bing() {
+        let body = r#"{"images":[{"url":"/th?id=OHR.Lake_EN-US123_1920x1080.jpg","urlbase":"/th?id=OHR.Lake_EN-US123","title":"A lake","copyright":"Lake (© Someone)","copyrightlink":"https://www.bing.com/search?q=lake"}]}"#;
+        let wallpaper = parse_bing(body).unwrap();
+        assert_eq!(
+            wallpaper.url,
+            "https://www.bing.com/th?id=OHR.Lake_EN-US123_UHD.jpg"
+        );
+        assert_eq!(wallpaper.title.as_deref(), Some("A lake"));
+        assert_eq!(
+            wallpaper.link.as_deref(),
+            Some("https://www.bing.com/search?q=lake")
+        );
+    }
+
+    #[test]
+    fn test_parse_spotlight() {
+        let item = r#"{"ad":{"landscapeImage":{"asset":"https://img.example.com/a.jpg"},"portraitImage":{"asset":"https://img.example.com/b.jpg"},"title":"Mountains","copyright":"© Someone","ctaUri":"microsoft-edge:https://www.bing.com/search?q=mountains"}}"#;
+        let body = serde_json::json!({ "batchrsp": { "ver": "1.0", "items": [{ "item": item }] } });
+        let wallpaper = parse_spotlight(&body.to_string()).unwrap();
+        assert_eq!(wallpaper.url, "https://img.example.com/a.jpg");
+        assert_eq!(wallpaper.copyright.as_deref(), Some("© Someone"));
+        assert_eq!(
+            wallpaper.link.as_deref(),
+            Some("https://www.bing.com/search?q=mountains")
+        );
+    }
+}
@@ -0,0 +1,6 @@
+param (
+  [string]$Source,
+  [string]$Destination
+)
+
+Compress-Archive -Path "$Source\*" -DestinationPath $Destination -Force
@@ -0,0 +1,23 @@
+use std::path::PathBuf;
+
+use seelen_core::system_state::CrashReport;
+
+use crate::error_handler::Result;
+
+use super::Diagnostics;
+
+#[tauri::command(async)]
+pub fn diagnostics_get_reports() -> Result<Vec<CrashReport>> {
+    Diagnostics::reports()
+}
+
+#[tauri::command(async)]
+pub fn diagnostics_clear() -> Result<()> {
+    Diagnostics::clear()
+}
+
+/// Returns the path of the exported zip
+#[tauri::command(async)]
+pub async fn diagnostics_export() -> Result<PathBuf> {
+    Diagnostics::export().await
+}
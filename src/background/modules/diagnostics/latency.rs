@@ -0,0 +1,289 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+    time::{Duration, Instant},
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde::Serialize;
+use windows::{
+    core::{GUID, HSTRING},
+    Win32::{
+        Foundation::HWND,
+        System::Diagnostics::Etw::{EventRegister, EventWriteString, REGHANDLE},
+    },
+};
+
+use crate::{error_handler::Result, trace_lock, winevent::WinEvent};
+
+use super::Diagnostics;
+
+/// `Seelen-UI-InputLatency` provider, it can be recorded along with the system providers using:
+/// `logman start seelen -p {5e8d1f36-94f2-4c1b-a7e3-2b6f0c9d4a81} -o latency.etl -ets`
+const PROVIDER: GUID = GUID::from_u128(0x5e8d1f36_94f2_4c1b_a7e3_2b6f0c9d4a81);
+/// TRACE_LEVEL_INFORMATION
+const LEVEL: u8 = 4;
+/// samples kept for the report
+const MAX_SAMPLES: usize = 2000;
+/// window positions requested after this time are not attributed to the event
+const OPERATION_TIMEOUT: Duration = Duration::from_secs(2);
+/// arrivals not picked by the window manager are discarded after this time
+const ARRIVAL_TIMEOUT: Duration = Duration::from_secs(5);
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static HANDLE: OnceLock<REGHANDLE> = OnceLock::new();
+
+lazy_static! {
+    /// hwnd -> first event not yet processed by the window manager
+    static ref ARRIVALS: Mutex<HashMap<isize, (WinEvent, Instant)>> = Mutex::new(HashMap::new());
+    static ref CURRENT: Mutex<Option<Operation>> = Mutex::new(None);
+    static ref SAMPLES: Mutex<VecDeque<LatencySample>> = Mutex::new(VecDeque::new());
+}
+
+/// A win event handled by the window manager and the window positions it caused
+struct Operation {
+    event: WinEvent,
+    arrived_at: Instant,
+    dequeued: Duration,
+    first_position: Option<Duration>,
+    last_position: Option<Duration>,
+    positions: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencySample {
+    pub event: String,
+    /// from the arrival of the event until the window manager picked it from its queue
+    pub dequeued_ms: f64,
+    /// from the arrival of the event until the first `SetWindowPos` returned
+    pub first_position_ms: f64,
+    /// from the arrival of the event until the last `SetWindowPos` returned
+    pub last_position_ms: f64,
+    pub positions: u32,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyStats {
+    pub count: usize,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub max_ms: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyReport {
+    pub version: String,
+    /// stats of `lastPositionMs` by event
+    pub events: BTreeMap<String, LatencyStats>,
+    pub dequeued: LatencyStats,
+    pub first_position: LatencyStats,
+    pub last_position: LatencyStats,
+    pub samples: Vec<LatencySample>,
+}
+
+fn ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn stats_of(mut values: Vec<f64>) -> LatencyStats {
+    if values.is_empty() {
+        return LatencyStats::default();
+    }
+    values.sort_by(f64::total_cmp);
+    let percentile = |p: usize| values[(values.len() - 1) * p / 100];
+    LatencyStats {
+        count: values.len(),
+        p50_ms: percentile(50),
+        p95_ms: percentile(95),
+        max_ms: values[values.len() - 1],
+    }
+}
+
+fn report_of(samples: Vec<LatencySample>) -> LatencyReport {
+    let mut by_event: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    for sample in &samples {
+        by_event
+            .entry(sample.event.clone())
+            .or_default()
+            .push(sample.last_position_ms);
+    }
+    LatencyReport {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        events: by_event
+            .into_iter()
+            .map(|(event, values)| (event, stats_of(values)))
+            .collect(),
+        dequeued: stats_of(samples.iter().map(|s| s.dequeued_ms).collect()),
+        first_position: stats_of(samples.iter().map(|s| s.first_position_ms).collect()),
+        last_position: stats_of(samples.iter().map(|s| s.last_position_ms).collect()),
+        samples,
+    }
+}
+
+/// Measures the time from the arrival of a win event until the window manager finishes
+/// repositioning the windows, the measurements are also written as ETW events.
+pub struct InputLatency;
+
+impl InputLatency {
+    pub fn is_enabled() -> bool {
+        ENABLED.load(Ordering::Acquire)
+    }
+
+    /// Returns true if the measurements were enabled, enabling them discards the previous samples
+    pub fn toggle() -> bool {
+        let enabled = !Self::is_enabled();
+        if enabled {
+            trace_lock!(SAMPLES).clear();
+            HANDLE.get_or_init(|| {
+                let mut handle = REGHANDLE::default();
+                let status = unsafe { EventRegister(&PROVIDER, None, None, &mut handle) };
+                if status != 0 {
+                    log::warn!("Failed to register the ETW provider: {}", status);
+                }
+                handle
+            });
+        } else {
+            Self::finish();
+            trace_lock!(ARRIVALS).clear();
+        }
+        ENABLED.store(enabled, Ordering::Release);
+        enabled
+    }
+
+    fn write_etw(message: &str) {
+        if let Some(handle) = HANDLE.get() {
+            unsafe { EventWriteString(*handle, LEVEL, 0, &HSTRING::from(message)) };
+        }
+    }
+
+    /// Called by the win event hook as soon as the event is received
+    pub fn on_arrival(event: WinEvent, origin: HWND) {
+        if !Self::is_enabled() {
+            return;
+        }
+        let now = Instant::now();
+        let mut arrivals = trace_lock!(ARRIVALS);
+        arrivals.retain(|_, (_, arrived_at)| now - *arrived_at < ARRIVAL_TIMEOUT);
+        // coalesced events are measured from the first arrival
+        arrivals.entry(origin.0 as isize).or_insert((event, now));
+    }
+
+    /// Called by the window manager queue before processing the event
+    pub fn on_dequeued(origin: HWND) {
+        if !Self::is_enabled() {
+            return;
+        }
+        let Some((event, arrived_at)) = trace_lock!(ARRIVALS).remove(&(origin.0 as isize)) else {
+            return;
+        };
+        Self::finish();
+        *trace_lock!(CURRENT) = Some(Operation {
+            event,
+            arrived_at,
+            dequeued: arrived_at.elapsed(),
+            first_position: None,
+            last_position: None,
+            positions: 0,
+        });
+    }
+
+    /// Called after `SetWindowPos` returns for a window managed by the window manager
+    pub fn on_position_set() {
+        if !Self::is_enabled() {
+            return;
+        }
+        let mut current = trace_lock!(CURRENT);
+        let Some(operation) = current.as_mut() else {
+            return;
+        };
+        let elapsed = operation.arrived_at.elapsed();
+        if elapsed > OPERATION_TIMEOUT {
+            return;
+        }
+        operation.first_position.get_or_insert(elapsed);
+        operation.last_position = Some(elapsed);
+        operation.positions += 1;
+    }
+
+    /// Stores the current operation, operations without window positions are not measured
+    fn finish() {
+        let Some(operation) = trace_lock!(CURRENT).take() else {
+            return;
+        };
+        let (Some(first), Some(last)) = (operation.first_position, operation.last_position) else {
+            return;
+        };
+        let sample = LatencySample {
+            event: format!("{:?}", operation.event),
+            dequeued_ms: ms(operation.dequeued),
+            first_position_ms: ms(first),
+            last_position_ms: ms(last),
+            positions: operation.positions,
+        };
+        Self::write_etw(&format!(
+            "{} dequeued={:.3}ms first={:.3}ms last={:.3}ms positions={}",
+            sample.event,
+            sample.dequeued_ms,
+            sample.first_position_ms,
+            sample.last_position_ms,
+            sample.positions
+        ));
+        let mut samples = trace_lock!(SAMPLES);
+        if samples.len() == MAX_SAMPLES {
+            samples.pop_front();
+        }
+        samples.push_back(sample);
+    }
+
+    /// Writes the report into the diagnostics folder, so it is included in the exported bundle
+    pub fn dump() -> Result<PathBuf> {
+        Self::finish();
+        let samples = trace_lock!(SAMPLES).iter().cloned().collect();
+        let report = report_of(samples);
+        let path =
+            Diagnostics::folder()?.join(format!("input-latency-{}.json", Diagnostics::now()));
+        std::fs::write(&path, serde_json::to_string_pretty(&report)?)?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(event: &str, last_position_ms: f64) -> LatencySample {
+        LatencySample {
+            event: event.to_string(),
+            dequeued_ms: 1.0,
+            first_position_ms: last_position_ms / 2.0,
+            last_position_ms,
+            positions: 2,
+        }
+    }
+
+    #[test]
+    fn test_report_of() {
+        let mut samples: Vec<_> = (1..=100).map(|i| sample("ObjectShow", i as f64)).collect();
+        samples.push(sample("SystemMinimizeStart", 40.0));
+
+        let report = report_of(samples);
+        let show = &report.events["ObjectShow"];
+        assert_eq!(show.count, 100);
+        assert_eq!(show.p50_ms, 50.0);
+        assert_eq!(show.p95_ms, 95.0);
+        assert_eq!(show.max_ms, 100.0);
+        assert_eq!(report.events["SystemMinimizeStart"].count, 1);
+        assert_eq!(report.last_position.count, 101);
+        assert_eq!(report.dequeued.max_ms, 1.0);
+
+        assert_eq!(stats_of(Vec::new()).count, 0);
+    }
+}
@@ -0,0 +1,219 @@
+pub mod infrastructure;
+pub mod latency;
+
+use std::{
+    os::windows::io::AsRawHandle,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        OnceLock,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use seelen_core::{state::DiagnosticsSettings, system_state::CrashReport};
+use tauri::Manager;
+use windows::Win32::{
+    Foundation::HANDLE,
+    System::{
+        Diagnostics::Debug::{
+            MiniDumpWithIndirectlyReferencedMemory, MiniDumpWithThreadInfo, MiniDumpWriteDump,
+            SetUnhandledExceptionFilter, EXCEPTION_POINTERS, MINIDUMP_EXCEPTION_INFORMATION,
+            MINIDUMP_TYPE,
+        },
+        Threading::{GetCurrentProcess, GetCurrentProcessId, GetCurrentThreadId},
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    seelen::get_app_handle,
+    utils::{copy_dir, logger::SeelenLogger, pwsh::PwshScript},
+};
+
+/// amount of log lines attached to each crash report
+const LOG_LINES: usize = 500;
+const REPORT_FILE: &str = "report.json";
+const MINIDUMP_FILE: &str = "seelen-ui.dmp";
+
+/// atomics instead of the settings state as they are read while crashing
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static MAX_REPORTS: AtomicUsize = AtomicUsize::new(10);
+static FOLDER: OnceLock<PathBuf> = OnceLock::new();
+
+/// Local crash reports, they are only shared if the user exports the diagnostics bundle
+pub struct Diagnostics;
+
+impl Diagnostics {
+    fn folder() -> Result<&'static PathBuf> {
+        FOLDER
+            .get()
+            .ok_or_else(|| "Diagnostics are not initialized".into())
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64
+    }
+
+    /// Catches the crashes that are not panics, like access violations on ffi calls
+    pub fn init(folder: PathBuf) -> Result<()> {
+        std::fs::create_dir_all(&folder)?;
+        FOLDER
+            .set(folder)
+            .map_err(|_| "Diagnostics already initialized")?;
+        unsafe { SetUnhandledExceptionFilter(Some(on_unhandled_exception)) };
+        Ok(())
+    }
+
+    /// Applies the settings, can be called at any time
+    pub fn set_config(config: &DiagnosticsSettings) {
+        ENABLED.store(config.crash_reports, Ordering::Release);
+        MAX_REPORTS.store(config.max_reports.max(1), Ordering::Release);
+    }
+
+    fn write_minidump(path: &Path, exception: Option<*const EXCEPTION_POINTERS>) -> Result<()> {
+        let file = std::fs::File::create(path)?;
+        let exception_info = exception.map(|pointers| MINIDUMP_EXCEPTION_INFORMATION {
+            ThreadId: unsafe { GetCurrentThreadId() },
+            ExceptionPointers: pointers as *mut _,
+            ClientPointers: false.into(),
+        });
+        unsafe {
+            MiniDumpWriteDump(
+                GetCurrentProcess(),
+                GetCurrentProcessId(),
+                HANDLE(file.as_raw_handle() as _),
+                MINIDUMP_TYPE(MiniDumpWithThreadInfo.0 | MiniDumpWithIndirectlyReferencedMemory.0),
+                exception_info.as_ref().map(|info| info as *const _),
+                None,
+                None,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn write_report(
+        cause: String,
+        location: Option<String>,
+        exception: Option<*const EXCEPTION_POINTERS>,
+    ) -> Result<()> {
+        let timestamp = Self::now();
+        let id = timestamp.to_string();
+        let folder = Self::folder()?.join(&id);
+        std::fs::create_dir_all(&folder)?;
+
+        let minidump = Self::write_minidump(&folder.join(MINIDUMP_FILE), exception);
+        let report = CrashReport {
+            id,
+            timestamp,
+            cause,
+            location,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            has_minidump: minidump.is_ok(),
+        };
+        std::fs::write(
+            folder.join(REPORT_FILE),
+            serde_json::to_string_pretty(&report)?,
+        )?;
+        std::fs::write(
+            folder.join("backtrace.txt"),
+            format!("{:?}", backtrace::Backtrace::new()),
+        )?;
+        std::fs::write(
+            folder.join("logs.jsonl"),
+            SeelenLogger::recent_lines(LOG_LINES).join("\n"),
+        )?;
+
+        Self::prune(MAX_REPORTS.load(Ordering::Acquire))
+    }
+
+    /// Called by the panic hook
+    pub fn on_panic(cause: &str, location: &str) {
+        if ENABLED.load(Ordering::Acquire) {
+            log_error!(Self::write_report(
+                cause.to_string(),
+                Some(location.to_string()),
+                None
+            ));
+        }
+    }
+
+    /// Newest first
+    pub fn reports() -> Result<Vec<CrashReport>> {
+        let mut reports = Vec::new();
+        for entry in std::fs::read_dir(Self::folder()?)?.flatten() {
+            let path = entry.path().join(REPORT_FILE);
+            if let Ok(content) = std::fs::read_to_string(path) {
+                if let Ok(report) = serde_json::from_str::<CrashReport>(&content) {
+                    reports.push(report);
+                }
+            }
+        }
+        reports.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        Ok(reports)
+    }
+
+    fn prune(max: usize) -> Result<()> {
+        for report in Self::reports()?.into_iter().skip(max) {
+            std::fs::remove_dir_all(Self::folder()?.join(report.id))?;
+        }
+        Ok(())
+    }
+
+    pub fn clear() -> Result<()> {
+        Self::prune(0)
+    }
+
+    /// Zips the crash reports and the logs into the downloads folder, returns the zip path
+    pub async fn export() -> Result<PathBuf> {
+        let handle = get_app_handle();
+        let name = format!("seelen-ui-diagnostics-{}", Self::now());
+        let staging = std::env::temp_dir().join(&name);
+        std::fs::create_dir_all(&staging)?;
+
+        copy_dir(Self::folder()?, &staging.join("crashes"))?;
+        copy_dir(
+            &handle.path().app_data_dir()?.join("logs"),
+            &staging.join("logs"),
+        )?;
+        std::fs::write(
+            staging.join("system.txt"),
+            format!(
+                "Seelen UI: v{}\nOperating System: {}\nWebView2 Runtime: {:?}\n",
+                env!("CARGO_PKG_VERSION"),
+                os_info::get(),
+                tauri::webview_version(),
+            ),
+        )?;
+
+        let destination = handle.path().download_dir()?.join(format!("{}.zip", name));
+        let mut script = PwshScript::new(include_str!("export.ps1"));
+        script.with_args([
+            "-Source".to_string(),
+            staging.to_string_lossy().to_string(),
+            "-Destination".to_string(),
+            destination.to_string_lossy().to_string(),
+        ]);
+        let result = script.execute().await;
+        std::fs::remove_dir_all(&staging)?;
+        result?;
+        Ok(destination)
+    }
+}
+
+unsafe extern "system" fn on_unhandled_exception(info: *const EXCEPTION_POINTERS) -> i32 {
+    if ENABLED.load(Ordering::Acquire) {
+        let code = (*(*info).ExceptionRecord).ExceptionCode;
+        log_error!(Diagnostics::write_report(
+            format!("Unhandled exception 0x{:08X}", code.0),
+            None,
+            Some(info),
+        ));
+    }
+    // EXCEPTION_CONTINUE_SEARCH, let the system terminate the process as usual
+    0
+}
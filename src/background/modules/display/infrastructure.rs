@@ -0,0 +1,25 @@
+use seelen_core::system_state::{DisplayDevice, DisplayMode, DisplayOrientation};
+
+use crate::error_handler::Result;
+
+use super::DisplayManager;
+
+#[tauri::command(async)]
+pub fn display_get_all() -> Result<Vec<DisplayDevice>> {
+    DisplayManager::enumerate()
+}
+
+#[tauri::command(async)]
+pub fn display_set_mode(id: String, mode: DisplayMode) -> Result<()> {
+    DisplayManager::set_mode(&id, &mode)
+}
+
+#[tauri::command(async)]
+pub fn display_set_orientation(id: String, orientation: DisplayOrientation) -> Result<()> {
+    DisplayManager::set_orientation(&id, orientation)
+}
+
+#[tauri::command(async)]
+pub fn display_set_primary(id: String) -> Result<()> {
+    DisplayManager::set_primary(&id)
+}
@@ -0,0 +1,245 @@
+pub mod infrastructure;
+
+use std::collections::HashSet;
+
+use seelen_core::system_state::{DisplayDevice, DisplayMode, DisplayOrientation};
+use widestring::U16CStr;
+use windows::{
+    core::{HSTRING, PCWSTR},
+    Win32::{
+        Foundation::HWND,
+        Graphics::Gdi::{
+            ChangeDisplaySettingsExW, EnumDisplayDevicesW, EnumDisplaySettingsW, CDS_NORESET,
+            CDS_SET_PRIMARY, CDS_TEST, CDS_TYPE, CDS_UPDATEREGISTRY, DEVMODEW,
+            DEVMODE_DISPLAY_ORIENTATION, DISPLAY_DEVICEW, DISPLAY_DEVICE_ATTACHED_TO_DESKTOP,
+            DISPLAY_DEVICE_PRIMARY_DEVICE, DISP_CHANGE_SUCCESSFUL, DMDO_180, DMDO_270, DMDO_90,
+            DMDO_DEFAULT, DM_BITSPERPEL, DM_DISPLAYFREQUENCY, DM_DISPLAYORIENTATION, DM_PELSHEIGHT,
+            DM_PELSWIDTH, DM_POSITION, ENUM_CURRENT_SETTINGS, ENUM_DISPLAY_SETTINGS_MODE,
+        },
+    },
+};
+
+use crate::error_handler::Result;
+
+/// Resolution, refresh rate, orientation and primary monitor changes
+pub struct DisplayManager;
+
+impl DisplayManager {
+    /// monitor ids don't include the `\\.\` prefix of the GDI device names
+    fn device_name(id: &str) -> HSTRING {
+        HSTRING::from(format!(r"\\.\{}", id))
+    }
+
+    fn attached_devices() -> Vec<DISPLAY_DEVICEW> {
+        let mut devices = Vec::new();
+        let mut index = 0;
+        loop {
+            let mut device = DISPLAY_DEVICEW {
+                cb: std::mem::size_of::<DISPLAY_DEVICEW>() as u32,
+                ..Default::default()
+            };
+            if !unsafe { EnumDisplayDevicesW(PCWSTR::null(), index, &mut device, 0) }.as_bool() {
+                break;
+            }
+            if device.StateFlags & DISPLAY_DEVICE_ATTACHED_TO_DESKTOP != 0 {
+                devices.push(device);
+            }
+            index += 1;
+        }
+        devices
+    }
+
+    fn settings(device_name: &HSTRING, mode: ENUM_DISPLAY_SETTINGS_MODE) -> Option<DEVMODEW> {
+        let mut devmode = DEVMODEW {
+            dmSize: std::mem::size_of::<DEVMODEW>() as u16,
+            ..Default::default()
+        };
+        unsafe { EnumDisplaySettingsW(device_name, mode, &mut devmode) }
+            .as_bool()
+            .then_some(devmode)
+    }
+
+    fn current_settings(device_name: &HSTRING) -> Result<DEVMODEW> {
+        Self::settings(device_name, ENUM_CURRENT_SETTINGS)
+            .ok_or_else(|| format!("Failed to read the settings of {}", device_name).into())
+    }
+
+    fn modes_of(device_name: &HSTRING) -> Vec<DisplayMode> {
+        let mut modes = HashSet::new();
+        let mut index = 0;
+        while let Some(devmode) = Self::settings(device_name, ENUM_DISPLAY_SETTINGS_MODE(index)) {
+            modes.insert(mode_of(&devmode));
+            index += 1;
+        }
+        let mut modes: Vec<DisplayMode> = modes.into_iter().collect();
+        modes.sort_by_key(|m| {
+            std::cmp::Reverse((m.width * m.height, m.refresh_rate, m.bits_per_pixel))
+        });
+        modes
+    }
+
+    fn monitor_label(device: &DISPLAY_DEVICEW) -> String {
+        let mut monitor = DISPLAY_DEVICEW {
+            cb: std::mem::size_of::<DISPLAY_DEVICEW>() as u32,
+            ..Default::default()
+        };
+        let found =
+            unsafe { EnumDisplayDevicesW(PCWSTR(device.DeviceName.as_ptr()), 0, &mut monitor, 0) };
+        let label = match found.as_bool() {
+            true => monitor.DeviceString,
+            false => device.DeviceString,
+        };
+        wide_to_string(&label)
+    }
+
+    pub fn enumerate() -> Result<Vec<DisplayDevice>> {
+        let mut displays = Vec::new();
+        for device in Self::attached_devices() {
+            let name = wide_to_string(&device.DeviceName);
+            let device_name = HSTRING::from(name.as_str());
+            let current = Self::current_settings(&device_name)?;
+            let position = unsafe { current.Anonymous1.Anonymous2.dmPosition };
+            displays.push(DisplayDevice {
+                id: name.trim_start_matches(r"\\.\").to_string(),
+                label: Self::monitor_label(&device),
+                is_primary: device.StateFlags & DISPLAY_DEVICE_PRIMARY_DEVICE != 0,
+                x: position.x,
+                y: position.y,
+                orientation: orientation_of(&current),
+                current_mode: mode_of(&current),
+                modes: Self::modes_of(&device_name),
+            });
+        }
+        Ok(displays)
+    }
+
+    /// Tests the change before applying it, so unsupported modes are never applied
+    fn change(device_name: Option<&HSTRING>, devmode: &DEVMODEW, flags: CDS_TYPE) -> Result<()> {
+        let device = device_name.map_or(PCWSTR::null(), |name| PCWSTR(name.as_ptr()));
+        for flags in [CDS_TEST, flags] {
+            let result = unsafe {
+                ChangeDisplaySettingsExW(
+                    device,
+                    Some(devmode as *const _),
+                    HWND::default(),
+                    flags,
+                    None,
+                )
+            };
+            if result != DISP_CHANGE_SUCCESSFUL {
+                return Err(format!("Failed to change display settings: {:?}", result).into());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn set_mode(id: &str, mode: &DisplayMode) -> Result<()> {
+        let device_name = Self::device_name(id);
+        if !Self::modes_of(&device_name).contains(mode) {
+            return Err(format!("Display mode not supported by {}: {:?}", id, mode).into());
+        }
+        let mut devmode = Self::current_settings(&device_name)?;
+        // modes are listed in landscape, so these are swapped if the display is rotated
+        let (width, height) = match is_portrait(orientation_of(&devmode)) {
+            true => (mode.height, mode.width),
+            false => (mode.width, mode.height),
+        };
+        devmode.dmPelsWidth = width;
+        devmode.dmPelsHeight = height;
+        devmode.dmDisplayFrequency = mode.refresh_rate;
+        devmode.dmBitsPerPel = mode.bits_per_pixel;
+        devmode.dmFields = DM_PELSWIDTH | DM_PELSHEIGHT | DM_DISPLAYFREQUENCY | DM_BITSPERPEL;
+        Self::change(Some(&device_name), &devmode, CDS_UPDATEREGISTRY)
+    }
+
+    pub fn set_orientation(id: &str, orientation: DisplayOrientation) -> Result<()> {
+        let device_name = Self::device_name(id);
+        let mut devmode = Self::current_settings(&device_name)?;
+        if is_portrait(orientation_of(&devmode)) != is_portrait(orientation) {
+            std::mem::swap(&mut devmode.dmPelsWidth, &mut devmode.dmPelsHeight);
+        }
+        devmode.Anonymous1.Anonymous2.dmDisplayOrientation = match orientation {
+            DisplayOrientation::Landscape => DMDO_DEFAULT,
+            DisplayOrientation::Portrait => DMDO_90,
+            DisplayOrientation::LandscapeFlipped => DMDO_180,
+            DisplayOrientation::PortraitFlipped => DMDO_270,
+        };
+        devmode.dmFields = DM_DISPLAYORIENTATION | DM_PELSWIDTH | DM_PELSHEIGHT;
+        Self::change(Some(&device_name), &devmode, CDS_UPDATEREGISTRY)
+    }
+
+    /// The primary display is always at the origin of the virtual screen,
+    /// so all the displays are moved to keep the same arrangement.
+    pub fn set_primary(id: &str) -> Result<()> {
+        let target = Self::device_name(id);
+        let origin = unsafe {
+            Self::current_settings(&target)?
+                .Anonymous1
+                .Anonymous2
+                .dmPosition
+        };
+
+        for device in Self::attached_devices() {
+            let device_name = HSTRING::from(wide_to_string(&device.DeviceName));
+            let mut devmode = Self::current_settings(&device_name)?;
+            unsafe {
+                let position = &mut devmode.Anonymous1.Anonymous2.dmPosition;
+                position.x -= origin.x;
+                position.y -= origin.y;
+            }
+            devmode.dmFields = DM_POSITION;
+
+            let mut flags = CDS_UPDATEREGISTRY | CDS_NORESET;
+            if device_name == target {
+                flags |= CDS_SET_PRIMARY;
+            }
+            Self::change(Some(&device_name), &devmode, flags)?;
+        }
+
+        // applies all the pending changes at once
+        let result = unsafe {
+            ChangeDisplaySettingsExW(PCWSTR::null(), None, HWND::default(), CDS_TYPE(0), None)
+        };
+        if result != DISP_CHANGE_SUCCESSFUL {
+            return Err(format!("Failed to apply display settings: {:?}", result).into());
+        }
+        Ok(())
+    }
+}
+
+fn wide_to_string(buffer: &[u16]) -> String {
+    U16CStr::from_slice_truncate(buffer)
+        .map(|s| s.to_string_lossy())
+        .unwrap_or_default()
+}
+
+fn mode_of(devmode: &DEVMODEW) -> DisplayMode {
+    let (width, height) = match is_portrait(orientation_of(devmode)) {
+        true => (devmode.dmPelsHeight, devmode.dmPelsWidth),
+        false => (devmode.dmPelsWidth, devmode.dmPelsHeight),
+    };
+    DisplayMode {
+        width,
+        height,
+        refresh_rate: devmode.dmDisplayFrequency,
+        bits_per_pixel: devmode.dmBitsPerPel,
+    }
+}
+
+fn orientation_of(devmode: &DEVMODEW) -> DisplayOrientation {
+    let orientation: DEVMODE_DISPLAY_ORIENTATION =
+        unsafe { devmode.Anonymous1.Anonymous2.dmDisplayOrientation };
+    match orientation {
+        DMDO_90 => DisplayOrientation::Portrait,
+        DMDO_180 => DisplayOrientation::LandscapeFlipped,
+        DMDO_270 => DisplayOrientation::PortraitFlipped,
+        _ => DisplayOrientation::Landscape,
+    }
+}
+
+fn is_portrait(orientation: DisplayOrientation) -> bool {
+    matches!(
+        orientation,
+        DisplayOrientation::Portrait | DisplayOrientation::PortraitFlipped
+    )
+}
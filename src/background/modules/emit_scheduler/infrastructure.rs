@@ -0,0 +1,13 @@
+use tauri::WebviewWindow;
+
+use super::EmitScheduler;
+
+#[tauri::command(async)]
+pub fn subscribe_scheduled_event(window: WebviewWindow, event: String) {
+    EmitScheduler::subscribe(window.label(), &event);
+}
+
+#[tauri::command(async)]
+pub fn unsubscribe_scheduled_event(window: WebviewWindow, event: String) {
+    EmitScheduler::unsubscribe(window.label(), &event);
+}
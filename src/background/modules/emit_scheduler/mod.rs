@@ -0,0 +1,220 @@
+pub mod infrastructure;
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{Emitter, EventId, EventTarget, Listener};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::perf::PerfMonitor,
+    seelen::{get_app_handle, Seelen},
+    trace_lock,
+    utils::spawn_named_thread,
+};
+
+lazy_static! {
+    /// only the latest payload of each event is kept, intermediate values are dropped
+    static ref PENDING: Mutex<HashMap<&'static str, serde_json::Value>> = Mutex::new(HashMap::new());
+    /// event -> labels of the webviews listening it
+    static ref SUBSCRIBERS: Mutex<HashMap<String, HashSet<String>>> = Mutex::new(HashMap::new());
+    /// event -> count of the rust listeners attached to it
+    static ref BACKEND_SUBSCRIBERS: Mutex<HashMap<String, usize>> = Mutex::new(HashMap::new());
+}
+
+static FRAME_MILLIS: AtomicU64 = AtomicU64::new(1000 / 30);
+/// frame set by the power saving profile, 0 if not throttled
+static THROTTLE_MILLIS: AtomicU64 = AtomicU64::new(0);
+
+/// Scheduled event ready to be sent, `labels` are the webviews receiving it
+struct Delivery {
+    event: &'static str,
+    payload: serde_json::Value,
+    labels: HashSet<String>,
+}
+
+/// Coalesces high frequency events and sends them once per frame only to the webviews
+/// subscribed to them, the rust listeners attached with `EmitScheduler::listen_any` too.
+pub struct EmitScheduler;
+
+impl EmitScheduler {
+    pub fn set_max_rate(rate: u32) {
+        let millis = 1000 / rate.clamp(1, 120) as u64;
+        FRAME_MILLIS.store(millis, Ordering::Relaxed);
+    }
+
+    /// Lowers the rate below the configured one until it is called with `None`
+    pub fn set_throttle(rate: Option<u32>) {
+        let millis = rate.map_or(0, |rate| 1000 / rate.clamp(1, 120) as u64);
+        THROTTLE_MILLIS.store(millis, Ordering::Relaxed);
+    }
+
+    pub fn frame_duration() -> Duration {
+        let millis = FRAME_MILLIS
+            .load(Ordering::Relaxed)
+            .max(THROTTLE_MILLIS.load(Ordering::Relaxed));
+        Duration::from_millis(millis)
+    }
+
+    pub fn subscribe(label: &str, event: &str) {
+        trace_lock!(SUBSCRIBERS)
+            .entry(event.to_string())
+            .or_default()
+            .insert(label.to_string());
+    }
+
+    pub fn unsubscribe(label: &str, event: &str) {
+        if let Some(labels) = trace_lock!(SUBSCRIBERS).get_mut(event) {
+            labels.remove(label);
+        }
+    }
+
+    /// Drops the subscriptions of a closed webview
+    pub fn forget(label: &str) {
+        for labels in trace_lock!(SUBSCRIBERS).values_mut() {
+            labels.remove(label);
+        }
+    }
+
+    /// Counts a rust listener of the event, so it is scheduled even without webviews listening
+    pub fn subscribe_backend(event: &str) {
+        *trace_lock!(BACKEND_SUBSCRIBERS)
+            .entry(event.to_string())
+            .or_default() += 1;
+    }
+
+    pub fn unsubscribe_backend(event: &str) {
+        if let Some(count) = trace_lock!(BACKEND_SUBSCRIBERS).get_mut(event) {
+            *count = count.saturating_sub(1);
+        }
+    }
+
+    fn has_backend_subscribers(event: &str) -> bool {
+        trace_lock!(BACKEND_SUBSCRIBERS)
+            .get(event)
+            .is_some_and(|count| *count > 0)
+    }
+
+    /// Same as `AppHandle::listen_any` but the scheduled events are also sent while
+    /// no webview is listening them, should be used by all the rust listeners.
+    pub fn listen_any<F>(event: &str, handler: F) -> EventId
+    where
+        F: Fn(tauri::Event) + Send + 'static,
+    {
+        Self::subscribe_backend(event);
+        get_app_handle().listen_any(event.to_string(), handler)
+    }
+
+    /// Removes a listener attached with `EmitScheduler::listen_any`
+    pub fn unlisten(event: &str, id: EventId) {
+        Self::unsubscribe_backend(event);
+        get_app_handle().unlisten(id);
+    }
+
+    pub fn has_subscribers(event: &str) -> bool {
+        Self::has_backend_subscribers(event)
+            || trace_lock!(SUBSCRIBERS)
+                .get(event)
+                .is_some_and(|labels| !labels.is_empty())
+    }
+
+    /// Queues the event to be sent on the next frame, replacing any pending value of the same event
+    pub fn schedule<S: Serialize>(event: &'static str, payload: S) {
+        if !Self::has_subscribers(event) {
+            return;
+        }
+        match serde_json::to_value(payload) {
+            Ok(value) => {
+                trace_lock!(PENDING).insert(event, value);
+            }
+            Err(err) => log::error!("Failed to serialize {}: {}", event, err),
+        }
+    }
+
+    /// Takes the pending events that still have someone to receive them
+    fn take_deliveries() -> Vec<Delivery> {
+        let pending = std::mem::take(&mut *trace_lock!(PENDING));
+        if pending.is_empty() {
+            return Vec::new();
+        }
+
+        let subscribers = trace_lock!(SUBSCRIBERS).clone();
+        pending
+            .into_iter()
+            .filter_map(|(event, payload)| {
+                let labels = subscribers.get(event).cloned().unwrap_or_default();
+                if labels.is_empty() && !Self::has_backend_subscribers(event) {
+                    return None;
+                }
+                Some(Delivery {
+                    event,
+                    payload,
+                    labels,
+                })
+            })
+            .collect()
+    }
+
+    fn flush() {
+        let handle = get_app_handle();
+        for Delivery {
+            event,
+            payload,
+            labels,
+        } in Self::take_deliveries()
+        {
+            PerfMonitor::record_emit(event);
+            // a single emit, so the rust listeners (any target) receive it once
+            log_error!(handle.emit_filter(event, &payload, |target| match target {
+                EventTarget::AnyLabel { label }
+                | EventTarget::Window { label }
+                | EventTarget::Webview { label }
+                | EventTarget::WebviewWindow { label } => labels.contains(label),
+                _ => false,
+            }));
+        }
+    }
+
+    pub fn start() -> Result<()> {
+        spawn_named_thread("Emit Scheduler", || loop {
+            std::thread::sleep(Self::frame_duration());
+            if !Seelen::is_running() {
+                break;
+            }
+            Self::flush();
+        })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backend_subscribers_without_webviews() {
+        let event = "test::backend-only";
+        EmitScheduler::schedule(event, 1);
+        assert!(EmitScheduler::take_deliveries()
+            .iter()
+            .all(|delivery| delivery.event != event));
+
+        EmitScheduler::subscribe_backend(event);
+        assert!(EmitScheduler::has_subscribers(event));
+        EmitScheduler::schedule(event, 2);
+        let deliveries = EmitScheduler::take_deliveries();
+        let delivery = deliveries.iter().find(|d| d.event == event).unwrap();
+        assert_eq!(delivery.payload, serde_json::json!(2));
+        assert!(delivery.labels.is_empty());
+
+        EmitScheduler::unsubscribe_backend(event);
+        assert!(!EmitScheduler::has_subscribers(event));
+    }
+}
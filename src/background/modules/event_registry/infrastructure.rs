@@ -0,0 +1,13 @@
+use tauri::WebviewWindow;
+
+use super::EventRegistry;
+
+#[tauri::command(async)]
+pub fn subscribe_event(window: WebviewWindow, event: String) {
+    EventRegistry::subscribe(window.label(), &event);
+}
+
+#[tauri::command(async)]
+pub fn unsubscribe_event(window: WebviewWindow, event: String) {
+    EventRegistry::unsubscribe(window.label(), &event);
+}
@@ -0,0 +1,85 @@
+pub mod infrastructure;
+
+use std::collections::{HashMap, HashSet};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{Emitter, EventTarget};
+
+use crate::{
+    error_handler::Result,
+    modules::{emit_scheduler::EmitScheduler, perf::PerfMonitor},
+    seelen::get_app_handle,
+    trace_lock,
+};
+
+lazy_static! {
+    /// webview label -> events it subscribed to, webviews not present here receive all the events
+    static ref SUBSCRIPTIONS: Mutex<HashMap<String, HashSet<String>>> = Mutex::new(HashMap::new());
+}
+
+/// Whether the webview should receive the event, only the webviews that made the
+/// handshake (subscribed to any event) are filtered.
+fn wants(subscriptions: &HashMap<String, HashSet<String>>, label: &str, event: &str) -> bool {
+    subscriptions
+        .get(label)
+        .map_or(true, |events| events.contains(event))
+}
+
+/// Routes the global events only to the webviews interested on them.
+pub struct EventRegistry;
+
+impl EventRegistry {
+    pub fn subscribe(label: &str, event: &str) {
+        trace_lock!(SUBSCRIPTIONS)
+            .entry(label.to_string())
+            .or_default()
+            .insert(event.to_string());
+    }
+
+    /// The webview keeps registered, so it doesn't go back to receive all the events
+    pub fn unsubscribe(label: &str, event: &str) {
+        if let Some(events) = trace_lock!(SUBSCRIPTIONS).get_mut(label) {
+            events.remove(event);
+        }
+    }
+
+    /// Drops the subscriptions of a closed webview
+    pub fn forget(label: &str) {
+        trace_lock!(SUBSCRIPTIONS).remove(label);
+        EmitScheduler::forget(label);
+    }
+
+    pub fn emit<S: Serialize + Clone>(event: &str, payload: S) -> Result<()> {
+        PerfMonitor::record_emit(event);
+        // cloned as the rust listeners are called while emitting and could subscribe
+        let subscriptions = trace_lock!(SUBSCRIPTIONS).clone();
+        get_app_handle().emit_filter(event, payload, |target| match target {
+            EventTarget::AnyLabel { label }
+            | EventTarget::Window { label }
+            | EventTarget::Webview { label }
+            | EventTarget::WebviewWindow { label } => wants(&subscriptions, label, event),
+            _ => true,
+        })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wants_event() {
+        let mut subscriptions = HashMap::new();
+        subscriptions.insert("weg".to_string(), HashSet::from(["a".to_string()]));
+        subscriptions.insert("wall".to_string(), HashSet::new());
+
+        assert!(wants(&subscriptions, "weg", "a"));
+        assert!(!wants(&subscriptions, "weg", "b"));
+        assert!(!wants(&subscriptions, "wall", "a"));
+        // without handshake everything is received
+        assert!(wants(&subscriptions, "toolbar", "b"));
+    }
+}
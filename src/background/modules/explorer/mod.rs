@@ -0,0 +1,138 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+use windows::{
+    core::w,
+    Win32::{
+        Foundation::{HWND, LPARAM, LRESULT, WPARAM},
+        UI::WindowsAndMessaging::{
+            ChangeWindowMessageFilterEx, CreateWindowExW, DefWindowProcW, DispatchMessageW,
+            GetMessageW, RegisterClassW, RegisterWindowMessageW, TranslateMessage, MSG,
+            MSGFLT_ALLOW, WINDOW_EX_STYLE, WINDOW_STYLE, WNDCLASSW,
+        },
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::{
+        taskbar::NativeTaskbar,
+        tray::{application::ensure_tray_overflow_creation, infrastructure::register_tray_events},
+    },
+    seelen::{Seelen, SEELEN},
+    seelen_weg::SeelenWeg,
+    state::application::FULL_STATE,
+    trace_lock,
+    utils::{sleep_millis, spawn_named_thread},
+    windows_api::{RegisteredBars, WindowsApi},
+};
+
+/// registered message broadcasted to all the top level windows when the taskbar is created
+static TASKBAR_CREATED: AtomicU32 = AtomicU32::new(0);
+static HEALING: AtomicBool = AtomicBool::new(false);
+
+/// Detects Explorer restarts and restores the state of the subsystems that depend on it
+pub struct ExplorerWatcher;
+
+impl ExplorerWatcher {
+    unsafe extern "system" fn window_proc(
+        window: HWND,
+        message: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        let taskbar_created = TASKBAR_CREATED.load(Ordering::Relaxed);
+        if taskbar_created != 0 && message == taskbar_created {
+            Self::on_explorer_restart();
+            return LRESULT(0);
+        }
+        DefWindowProcW(window, message, wparam, lparam)
+    }
+
+    fn on_explorer_restart() {
+        // explorer can broadcast the message multiple times while starting
+        if HEALING.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        std::thread::spawn(|| {
+            log::info!("Explorer restart detected, restoring state");
+            // give some time to explorer to finish its initialization
+            sleep_millis(1000);
+            if Seelen::is_running() {
+                log_error!(Self::heal());
+            }
+            HEALING.store(false, Ordering::SeqCst);
+        });
+    }
+
+    fn heal() -> Result<()> {
+        // app bars registered on the old explorer instance are lost, so the work area is not reserved anymore
+        trace_lock!(RegisteredBars).clear();
+        trace_lock!(SEELEN).refresh_windows_positions()?;
+
+        NativeTaskbar::apply();
+
+        log_error!(ensure_tray_overflow_creation());
+        register_tray_events();
+
+        let state = FULL_STATE.load();
+        if state.is_weg_enabled() {
+            // UWP frames and explorer windows are recreated with new handles
+            SeelenWeg::refresh_open_apps()?;
+        }
+        state.emit_all()?;
+        Ok(())
+    }
+
+    unsafe fn create_background_window() -> Result<()> {
+        let wide_class = w!("SeelenExplorerWatcher");
+        let h_module = WindowsApi::module_handle_w()?;
+
+        let wnd_class = WNDCLASSW {
+            lpfnWndProc: Some(Self::window_proc),
+            hInstance: h_module.into(),
+            lpszClassName: wide_class,
+            ..Default::default()
+        };
+
+        RegisterClassW(&wnd_class);
+
+        // should be a top level window (not message only) to receive broadcasted messages
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            wide_class,
+            w!("Seelen Explorer Watcher"),
+            WINDOW_STYLE::default(),
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            h_module,
+            None,
+        )?;
+
+        let taskbar_created = RegisterWindowMessageW(w!("TaskbarCreated"));
+        if taskbar_created == 0 {
+            return Err("Failed to register TaskbarCreated message".into());
+        }
+        TASKBAR_CREATED.store(taskbar_created, Ordering::SeqCst);
+        // if Seelen is running as admin, messages from explorer are blocked by UIPI
+        ChangeWindowMessageFilterEx(hwnd, taskbar_created, MSGFLT_ALLOW, None)?;
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, hwnd, 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+        Ok(())
+    }
+
+    pub fn start() -> Result<()> {
+        spawn_named_thread("Explorer Watcher", || unsafe {
+            log_error!(Self::create_background_window());
+        })?;
+        Ok(())
+    }
+}
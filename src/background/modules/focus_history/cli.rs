@@ -0,0 +1,32 @@
+use clap::Command;
+
+use crate::{error_handler::Result, get_subcommands};
+
+use super::FocusHistory;
+
+get_subcommands![
+    /** Focuses the previously focused window, even if it is on another workspace. */
+    Previous,
+    /** Focuses the window focused n times before the current one, 1 is the previous window. */
+    NthPrevious(nth: usize => "Places to go back on the focus history."),
+];
+
+impl FocusHistory {
+    pub const CLI_IDENTIFIER: &'static str = "focus";
+
+    pub fn get_cli() -> Command {
+        Command::new(Self::CLI_IDENTIFIER)
+            .about("Navigate the history of the focused windows.")
+            .arg_required_else_help(true)
+            .subcommands(SubCommand::commands())
+    }
+
+    pub fn process(matches: &clap::ArgMatches) -> Result<()> {
+        let subcommand = SubCommand::try_from(matches)?;
+        match subcommand {
+            SubCommand::Previous => Self::focus_previous(1)?,
+            SubCommand::NthPrevious(nth) => Self::focus_previous(nth)?,
+        };
+        Ok(())
+    }
+}
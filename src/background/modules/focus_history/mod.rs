@@ -0,0 +1,92 @@
+mod cli;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use windows::Win32::UI::WindowsAndMessaging::SW_RESTORE;
+
+use crate::{
+    error_handler::Result,
+    modules::virtual_desk::get_vd_manager,
+    seelen_weg::SeelenWeg,
+    trace_lock,
+    windows_api::{window::Window, WindowsApi},
+    winevent::WinEvent,
+};
+
+lazy_static! {
+    /// most recently focused windows first
+    static ref STACK: Mutex<Vec<isize>> = Mutex::new(Vec::new());
+}
+
+/// Windows in the order they were focused, across apps and workspaces
+pub struct FocusHistory;
+
+impl FocusHistory {
+    fn is_listed(window: &Window) -> bool {
+        SeelenWeg::should_be_added(window.hwnd())
+    }
+
+    /// Existing windows of the history, the first one is the focused window
+    pub fn windows() -> Vec<Window> {
+        trace_lock!(STACK)
+            .iter()
+            .map(|addr| Window::from(*addr))
+            .filter(|w| w.is_window() && Self::is_listed(w))
+            .collect()
+    }
+
+    /// Switches to the workspace of the window if needed, restores it and focuses it
+    pub fn focus(window: &Window) -> Result<()> {
+        let vd = get_vd_manager();
+        if let Ok(workspace) = window.workspace() {
+            let id = workspace.id();
+            if vd.get_current()?.id() != id {
+                if let Some(idx) = vd.get_all()?.iter().position(|w| w.id() == id) {
+                    vd.switch_to(idx)?;
+                }
+            }
+        }
+
+        let hwnd = window.hwnd();
+        if window.is_minimized() {
+            WindowsApi::show_window_async(hwnd, SW_RESTORE)?;
+        }
+        if WindowsApi::set_foreground(hwnd).is_err() {
+            WindowsApi::async_force_set_foreground(hwnd);
+        }
+        Ok(())
+    }
+
+    /// Focuses the window focused `nth` times before the current one, 1 is the previous window
+    pub fn focus_previous(nth: usize) -> Result<()> {
+        let windows = Self::windows();
+        let window = windows.get(nth).ok_or(format!(
+            "There is no window {} places back on the history",
+            nth
+        ))?;
+        Self::focus(window)
+    }
+
+    pub fn wants_win_event(event: &WinEvent) -> bool {
+        matches!(event, WinEvent::SystemForeground | WinEvent::ObjectDestroy)
+    }
+
+    pub fn process_win_event(event: WinEvent, window: &Window) -> Result<()> {
+        let address = window.address();
+        match event {
+            WinEvent::SystemForeground => {
+                if !Self::is_listed(window) {
+                    return Ok(());
+                }
+                let mut stack = trace_lock!(STACK);
+                stack.retain(|addr| *addr != address);
+                stack.insert(0, address);
+            }
+            WinEvent::ObjectDestroy => {
+                trace_lock!(STACK).retain(|addr| *addr != address);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
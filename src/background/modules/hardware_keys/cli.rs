@@ -0,0 +1,43 @@
+use clap::Command;
+
+use crate::{error_handler::Result, get_subcommands};
+
+use super::{HardwareKey, HardwareKeys};
+
+get_subcommands![
+    /** Plays or pauses the recommended media session, same as the play/pause key. */
+    PlayPause,
+    /** Skips to the next track of the recommended media session. */
+    Next,
+    /** Goes back to the previous track of the recommended media session. */
+    Prev,
+    /** Raises the volume of the default output device by the configured step. */
+    VolumeUp,
+    /** Lowers the volume of the default output device by the configured step. */
+    VolumeDown,
+    /** Mutes or unmutes the default output device. */
+    Mute,
+];
+
+impl HardwareKeys {
+    pub const CLI_IDENTIFIER: &'static str = "media";
+
+    pub fn get_cli() -> Command {
+        Command::new(Self::CLI_IDENTIFIER)
+            .about("Controls the media playback and the volume, same as the media keys.")
+            .arg_required_else_help(true)
+            .subcommands(SubCommand::commands())
+    }
+
+    pub fn process(matches: &clap::ArgMatches) -> Result<()> {
+        let key = match SubCommand::try_from(matches)? {
+            SubCommand::PlayPause => HardwareKey::PlayPause,
+            SubCommand::Next => HardwareKey::NextTrack,
+            SubCommand::Prev => HardwareKey::PrevTrack,
+            SubCommand::VolumeUp => HardwareKey::VolumeUp,
+            SubCommand::VolumeDown => HardwareKey::VolumeDown,
+            SubCommand::Mute => HardwareKey::VolumeMute,
+        };
+        Self::press(key)
+    }
+}
@@ -0,0 +1,201 @@
+pub mod cli;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crossbeam_channel::{Receiver, Sender};
+use lazy_static::lazy_static;
+use seelen_core::{
+    handlers::SeelenEvent,
+    system_state::{OsdEvent, OsdKind},
+};
+use windows::{
+    core::GUID,
+    Win32::UI::Input::KeyboardAndMouse::{
+        VIRTUAL_KEY, VK_MEDIA_NEXT_TRACK, VK_MEDIA_PLAY_PAUSE, VK_MEDIA_PREV_TRACK, VK_VOLUME_DOWN,
+        VK_VOLUME_MUTE, VK_VOLUME_UP,
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::{
+        event_registry::EventRegistry, keybindings::application::KeybindingsManager,
+        media::application::MEDIA_MANAGER,
+    },
+    state::application::FULL_STATE,
+    trace_lock,
+    utils::spawn_named_thread,
+    windows_api::Com,
+};
+
+lazy_static! {
+    static ref REQUESTS: (Sender<HardwareKey>, Receiver<HardwareKey>) =
+        crossbeam_channel::unbounded();
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static WORKER_STARTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HardwareKey {
+    VolumeUp,
+    VolumeDown,
+    VolumeMute,
+    PlayPause,
+    NextTrack,
+    PrevTrack,
+}
+
+impl HardwareKey {
+    fn from_vk(vk: u16) -> Option<Self> {
+        let key = match VIRTUAL_KEY(vk) {
+            VK_VOLUME_UP => Self::VolumeUp,
+            VK_VOLUME_DOWN => Self::VolumeDown,
+            VK_VOLUME_MUTE => Self::VolumeMute,
+            VK_MEDIA_PLAY_PAUSE => Self::PlayPause,
+            VK_MEDIA_NEXT_TRACK => Self::NextTrack,
+            VK_MEDIA_PREV_TRACK => Self::PrevTrack,
+            _ => return None,
+        };
+        Some(key)
+    }
+}
+
+/// Handling of the volume and media keys, replacing the native flyouts by OSD events.
+/// Brightness keys are handled by the firmware of the laptops, so their OSD events are
+/// emitted by the brightness module when the change is noticed.
+pub struct HardwareKeys;
+
+impl HardwareKeys {
+    pub fn is_enabled() -> bool {
+        ENABLED.load(Ordering::SeqCst)
+    }
+
+    /// Should be called when the settings change
+    pub fn refresh() -> Result<()> {
+        let enabled = FULL_STATE.load().settings.hardware_keys.enabled;
+        ENABLED.store(enabled, Ordering::SeqCst);
+        if enabled {
+            Self::ensure_worker()?;
+        }
+        KeybindingsManager::set_hardware_keys_grab(enabled)
+    }
+
+    /// Called from the keyboard hook, returns true if the key should be swallowed.
+    /// The key is processed on the worker thread to keep the keyboard responsive.
+    pub fn on_key_event(vk: u16, down: bool) -> bool {
+        if !Self::is_enabled() {
+            return false;
+        }
+        let Some(key) = HardwareKey::from_vk(vk) else {
+            return false;
+        };
+        if down {
+            let _ = REQUESTS.0.send(key);
+        }
+        true
+    }
+
+    /// Processes the key as if it was pressed, even if the handling of the keys is disabled
+    fn press(key: HardwareKey) -> Result<()> {
+        Self::ensure_worker()?;
+        REQUESTS.0.send(key).map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    fn ensure_worker() -> Result<()> {
+        if WORKER_STARTED.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+        spawn_named_thread("Hardware Keys", || {
+            log_error!(Com::run_with_context(|| {
+                while let Ok(key) = REQUESTS.1.recv() {
+                    log_error!(Self::handle(key));
+                }
+                Ok(())
+            }));
+        })?;
+        Ok(())
+    }
+
+    fn handle(key: HardwareKey) -> Result<()> {
+        let step = FULL_STATE.load().settings.hardware_keys.volume_step as f32 / 100.0;
+        let event = match key {
+            HardwareKey::VolumeUp => Self::change_volume(step, false)?,
+            HardwareKey::VolumeDown => Self::change_volume(-step, false)?,
+            HardwareKey::VolumeMute => Self::change_volume(0.0, true)?,
+            HardwareKey::PlayPause | HardwareKey::NextTrack | HardwareKey::PrevTrack => {
+                Self::control_media(key)?
+            }
+        };
+        Self::emit_osd(event)
+    }
+
+    /// Changes the volume of the default output device, raising the volume also unmutes it
+    /// as the native handling does.
+    fn change_volume(delta: f32, toggle_mute: bool) -> Result<OsdEvent> {
+        let manager = trace_lock!(MEDIA_MANAGER);
+        let device = manager
+            .outputs()
+            .iter()
+            .find(|d| d.is_default_multimedia)
+            .ok_or("No default output device")?;
+        let (endpoint, _) = manager
+            .devices_audio_endpoint()
+            .get(&device.id)
+            .ok_or("No endpoint for the default output device")?;
+
+        unsafe {
+            let mut muted = endpoint.GetMute()?.as_bool();
+            if toggle_mute || (muted && delta > 0.0) {
+                muted = !muted;
+                endpoint.SetMute(muted, &GUID::zeroed())?;
+            }
+            let mut volume = endpoint.GetMasterVolumeLevelScalar()?;
+            if delta != 0.0 {
+                volume = (volume + delta).clamp(0.0, 1.0);
+                endpoint.SetMasterVolumeLevelScalar(volume, &GUID::zeroed())?;
+            }
+            Ok(OsdEvent {
+                kind: OsdKind::Volume,
+                value: Some(volume),
+                muted,
+                label: Some(device.name.clone()),
+            })
+        }
+    }
+
+    /// Controls the session that Windows would control with the same key
+    fn control_media(key: HardwareKey) -> Result<OsdEvent> {
+        let manager = trace_lock!(MEDIA_MANAGER);
+        let id = manager.get_recommended_player_id()?;
+        let session = manager.session_by_id(&id).ok_or("No media session")?;
+        let success = match key {
+            HardwareKey::NextTrack => session.TrySkipNextAsync()?.get()?,
+            HardwareKey::PrevTrack => session.TrySkipPreviousAsync()?.get()?,
+            _ => session.TryTogglePlayPauseAsync()?.get()?,
+        };
+        if !success {
+            return Err(format!("The media session refused the {:?} key", key).into());
+        }
+        let title = manager
+            .playing()
+            .iter()
+            .find(|player| player.id == id)
+            .map(|player| player.title.clone());
+        Ok(OsdEvent {
+            kind: OsdKind::Media,
+            value: None,
+            muted: false,
+            label: title,
+        })
+    }
+
+    pub fn emit_osd(event: OsdEvent) -> Result<()> {
+        if Self::is_enabled() {
+            EventRegistry::emit(SeelenEvent::OsdShow, event)?;
+        }
+        Ok(())
+    }
+}
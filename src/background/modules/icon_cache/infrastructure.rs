@@ -0,0 +1,9 @@
+use crate::error_handler::Result;
+
+use super::IconCache;
+
+/// Extracts the icons of the path, or of all the files and apps if `None`, again
+#[tauri::command(async)]
+pub fn invalidate_icon_cache(path: Option<String>) -> Result<()> {
+    IconCache::invalidate(path.as_deref())
+}
@@ -0,0 +1,164 @@
+pub mod infrastructure;
+
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use image::RgbaImage;
+
+use crate::{
+    error_handler::Result,
+    modules::{shortcuts::ShortcutResolver, uwp::UwpManager},
+    seelen_weg::icon_extractor::get_icon_from_file,
+    state::application::{FullState, FULL_STATE},
+};
+
+/// Modification time of the file in seconds, 0 if it can't be read
+fn modified_secs(path: &Path) -> u64 {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+/// Version of an extracted icon, saved as suffix of the file name: `{uuid}-{version}.png`
+fn icon_version(icon: &Path) -> Option<u64> {
+    let stem = icon.file_stem()?.to_str()?;
+    stem.rsplit_once('-')?.1.parse().ok()
+}
+
+/// Icons extracted from files and apps, shared by the dock, launcher and window lists.
+///
+/// Icons are saved on the system icon pack and are extracted again when the source file
+/// (or the target of the shortcut) is modified, e.g. after updating a program.
+pub struct IconCache;
+
+impl IconCache {
+    /// Latest modification time of the file and the files it points to
+    fn version_of(path: &Path) -> u64 {
+        let mut version = modified_secs(path);
+        if ShortcutResolver::is_shortcut(path) {
+            if let Ok(shortcut) = ShortcutResolver::resolve(path) {
+                for file in shortcut.target.iter().chain(shortcut.icon_file.iter()) {
+                    version = version.max(modified_secs(file));
+                }
+            }
+        }
+        version
+    }
+
+    /// Cached icon of the key, icons of other packs are set by the user so they are never outdated
+    fn cached(state: &FullState, key: &str, version: Option<u64>) -> Option<PathBuf> {
+        let (icon_pack, icon) = state.find_icon_by_key(key)?;
+        if icon_pack != "system" {
+            return Some(icon);
+        }
+        let up_to_date = version.is_none() || icon_version(&icon) == version;
+        (up_to_date && icon.exists()).then_some(icon)
+    }
+
+    fn save(state: &FullState, key: &str, image: &RgbaImage, version: u64) -> Result<PathBuf> {
+        let filename = PathBuf::from(format!("{}-{}.png", uuid::Uuid::new_v4(), version));
+        let icon_path = state.icon_packs_folder().join("system").join(&filename);
+        image.save(&icon_path)?;
+        state.push_and_save_system_icon(key, &filename)?;
+        Ok(icon_path)
+    }
+
+    /// Returns the path of the icon extracted from the file, if the file is a shortcut without icon
+    /// the icon of its target is used.
+    pub fn of_file<T: AsRef<Path>>(path: T) -> Result<PathBuf> {
+        let path = path.as_ref();
+        if !path.exists() || path.is_dir() {
+            return Err("Path is not a file".into());
+        }
+
+        let state = FULL_STATE.load();
+        let key = path.to_string_lossy();
+        let version = Self::version_of(path);
+        if let Some(icon) = Self::cached(&state, &key, Some(version)) {
+            return Ok(icon);
+        }
+
+        log::trace!("Extracting icon for \"{}\"", path.display());
+        let shortcut = match ShortcutResolver::is_shortcut(path) {
+            true => Some(ShortcutResolver::resolve(path)?),
+            false => None,
+        };
+
+        // internet shortcuts only have the icon set on the file
+        if path.extension() == Some(OsStr::new("url")) {
+            let icon_file = shortcut
+                .and_then(|shortcut| shortcut.icon_file)
+                .ok_or("Failed to get icon")?;
+            return Self::save(&state, &key, &get_icon_from_file(&icon_file)?, version);
+        }
+
+        if let Ok(icon) = get_icon_from_file(path) {
+            return Self::save(&state, &key, &icon, version);
+        }
+
+        // if the lnk don't have an icon, try to extract it from the target
+        match shortcut.and_then(|shortcut| shortcut.target) {
+            Some(target) => Self::of_file(target),
+            None => Err("Failed to extract icon".into()),
+        }
+    }
+
+    /// Returns the path of the icon of the app with the specified user model id
+    pub fn of_umid<T: AsRef<str>>(app_umid: T) -> Result<PathBuf> {
+        let app_umid = app_umid.as_ref();
+
+        let state = FULL_STATE.load();
+        if let Some(icon) = Self::cached(&state, app_umid, None) {
+            return Ok(icon);
+        }
+
+        let app_icon = UwpManager::get_high_quality_icon_path(app_umid)?;
+        let filename = PathBuf::from(format!(
+            "{}-{}.png",
+            uuid::Uuid::new_v4(),
+            modified_secs(&app_icon)
+        ));
+        let icon_path = state.icon_packs_folder().join("system").join(&filename);
+        std::fs::copy(app_icon, &icon_path)?;
+        state.push_and_save_system_icon(app_umid, &filename)?;
+        Ok(icon_path)
+    }
+
+    /// Returns the icon of a file or of an app if the path is `shell:AppsFolder\{umid}`
+    pub fn of_path_or_umid(path: &str) -> Result<PathBuf> {
+        match path.strip_prefix("shell:AppsFolder\\") {
+            Some(umid) => Self::of_umid(umid),
+            None => Self::of_file(path),
+        }
+    }
+
+    /// Removes the extracted icon of the path or app user model id, or all of them if `None`.
+    /// The icons will be extracted again the next time they are requested.
+    pub fn invalidate(key: Option<&str>) -> Result<()> {
+        let state = FULL_STATE.load();
+        state.remove_system_icons(key)?;
+        ShortcutResolver::invalidate(key.map(Path::new));
+        state.emit_icon_packs()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_icon_version() {
+        let icon =
+            PathBuf::from("C:\\icons\\system\\0f8e4a3c-1b2d-4c5e-9f6a-7b8c9d0e1f2a-1717171717.png");
+        assert_eq!(icon_version(&icon), Some(1717171717));
+        // icons extracted before the versioning are outdated
+        let legacy = PathBuf::from("C:\\icons\\system\\0f8e4a3c-1b2d-4c5e-9f6a-7b8c9d0e1f2a.png");
+        assert_eq!(icon_version(&legacy), None);
+    }
+}
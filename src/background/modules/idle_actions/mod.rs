@@ -0,0 +1,274 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::state::AppIdleRules;
+use windows::Win32::{
+    System::SystemInformation::GetLocalTime,
+    UI::WindowsAndMessaging::{SW_MINIMIZE, WM_CLOSE},
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::{notifications::application::show_toast, session::SessionManager},
+    seelen_weg::SeelenWeg,
+    state::application::FULL_STATE,
+    t, trace_lock,
+    utils::spawn_named_thread,
+    windows_api::{window::Window, WindowEnumerator, WindowsApi},
+};
+
+const TICK_INTERVAL: Duration = Duration::from_secs(5);
+/// time without input after which the user is considered away from the computer
+const AWAY_AFTER: Duration = Duration::from_secs(60);
+const DAY: u32 = 24 * 60 * 60;
+
+lazy_static! {
+    static ref WINDOWS: Mutex<HashMap<isize, IdleWindow>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Debug, Default)]
+struct IdleWindow {
+    /// time without focus while the user was on the computer
+    unfocused: Duration,
+    minimized: bool,
+    /// the notification before closing was already shown
+    warned: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum IdleAction {
+    None,
+    Minimize,
+    /// seconds left until the window is closed
+    Warn(u64),
+    Close,
+}
+
+impl IdleWindow {
+    fn next_action(&self, rules: &AppIdleRules) -> IdleAction {
+        let unfocused = self.unfocused.as_secs();
+        if let Some(minutes) = rules.close_after {
+            let limit = minutes as u64 * 60;
+            if unfocused >= limit {
+                return IdleAction::Close;
+            }
+            let left = limit - unfocused;
+            if !self.warned && left <= rules.warn_before as u64 {
+                return IdleAction::Warn(left);
+            }
+        }
+        if let Some(minutes) = rules.minimize_after {
+            if !self.minimized && unfocused >= minutes as u64 * 60 {
+                return IdleAction::Minimize;
+            }
+        }
+        IdleAction::None
+    }
+}
+
+/// `HH:MM` to seconds of the day
+fn parse_time(time: &str) -> Option<u32> {
+    let (hours, minutes) = time.trim().split_once(':')?;
+    let hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+    (hours < 24 && minutes < 60).then_some((hours * 60 + minutes) * 60)
+}
+
+/// True if `at` is after `last` and until `now` (seconds of the day), going through midnight
+fn crossed(last: u32, now: u32, at: u32) -> bool {
+    match last <= now {
+        true => last < at && at <= now,
+        false => at > last || at <= now,
+    }
+}
+
+fn seconds_of_day() -> u32 {
+    let now = unsafe { GetLocalTime() };
+    now.wHour as u32 * 3600 + now.wMinute as u32 * 60 + now.wSecond as u32
+}
+
+/// Minimizes or closes the windows of the apps with idle rules, using the time they spent
+/// without focus or a daily schedule. A notification is shown before closing them.
+pub struct IdleActions;
+
+impl IdleActions {
+    fn tick(elapsed: Duration, last: u32, now: u32) -> Result<()> {
+        let state = FULL_STATE.load();
+        let mut windows = trace_lock!(WINDOWS);
+        if !state.settings_by_app.iter().any(|c| c.idle.is_some()) {
+            windows.clear();
+            return Ok(());
+        }
+
+        let away = SessionManager::is_locked()
+            || WindowsApi::get_idle_time().unwrap_or_default() >= AWAY_AFTER;
+        let foreground = WindowsApi::get_foreground_window().0 as isize;
+
+        let mut listed = Vec::new();
+        WindowEnumerator::new().for_each(|hwnd| {
+            if SeelenWeg::should_be_added(hwnd) {
+                listed.push(hwnd);
+            }
+        })?;
+        windows.retain(|address, _| listed.iter().any(|hwnd| hwnd.0 as isize == *address));
+
+        // app name -> seconds left, so apps with many windows show a single notification
+        let mut warnings: HashMap<String, u64> = HashMap::new();
+        let mut scheduled: HashMap<String, String> = HashMap::new();
+        for hwnd in listed {
+            let address = hwnd.0 as isize;
+            let Some(config) = state.get_app_config_by_window(hwnd) else {
+                windows.remove(&address);
+                continue;
+            };
+            let Some(rules) = &config.idle else {
+                windows.remove(&address);
+                continue;
+            };
+
+            if let Some(at) = rules.close_at.as_deref().and_then(parse_time) {
+                if crossed(last, now, at) {
+                    windows.remove(&address);
+                    WindowsApi::post_message(hwnd, WM_CLOSE, 0, 0)?;
+                    continue;
+                }
+                let warn_at = (at + DAY - rules.warn_before.min(DAY - 1)) % DAY;
+                if rules.warn_before > 0 && crossed(last, now, warn_at) {
+                    let time = rules.close_at.clone().unwrap_or_default();
+                    scheduled.insert(config.name.clone(), time);
+                }
+            }
+
+            let window = windows.entry(address).or_default();
+            // focusing the window starts everything again
+            if address == foreground {
+                *window = IdleWindow::default();
+                continue;
+            }
+            if !away {
+                window.unfocused += elapsed;
+            }
+
+            match window.next_action(rules) {
+                IdleAction::None => {}
+                IdleAction::Minimize => {
+                    window.minimized = true;
+                    if !Window::from(hwnd).is_minimized() {
+                        WindowsApi::show_window_async(hwnd, SW_MINIMIZE)?;
+                    }
+                }
+                IdleAction::Warn(left) => {
+                    window.warned = true;
+                    warnings.insert(config.name.clone(), left);
+                }
+                IdleAction::Close => {
+                    windows.remove(&address);
+                    WindowsApi::post_message(hwnd, WM_CLOSE, 0, 0)?;
+                }
+            }
+        }
+        drop(windows);
+
+        if warnings.is_empty() && scheduled.is_empty() {
+            return Ok(());
+        }
+        let title = t!("idle_actions.close_title");
+        let mut bodies = Vec::new();
+        for (app, seconds) in warnings {
+            bodies.push(t!(
+                "idle_actions.close_unfocused",
+                app = app,
+                seconds = seconds
+            ));
+        }
+        for (app, time) in scheduled {
+            bodies.push(t!("idle_actions.close_scheduled", app = app, time = time));
+        }
+        std::thread::spawn(move || {
+            for body in bodies {
+                log_error!(show_toast(&title, &body));
+            }
+        });
+        Ok(())
+    }
+
+    pub fn start() -> Result<()> {
+        spawn_named_thread("Idle Actions", || {
+            let mut last_tick = Instant::now();
+            let mut last_time = seconds_of_day();
+            loop {
+                std::thread::sleep(TICK_INTERVAL);
+                // after the system is suspended the elapsed time is not time without focus
+                let elapsed = last_tick.elapsed().min(TICK_INTERVAL * 5);
+                last_tick = Instant::now();
+                let now = seconds_of_day();
+                log_error!(Self::tick(elapsed, last_time, now));
+                last_time = now;
+            }
+        })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> AppIdleRules {
+        AppIdleRules {
+            minimize_after: Some(10),
+            close_after: Some(30),
+            close_at: None,
+            warn_before: 60,
+        }
+    }
+
+    fn window(minutes: u64) -> IdleWindow {
+        IdleWindow {
+            unfocused: Duration::from_secs(minutes * 60),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_next_action() {
+        let rules = rules();
+        assert_eq!(window(5).next_action(&rules), IdleAction::None);
+        assert_eq!(window(10).next_action(&rules), IdleAction::Minimize);
+
+        let mut minimized = window(20);
+        minimized.minimized = true;
+        assert_eq!(minimized.next_action(&rules), IdleAction::None);
+
+        minimized.unfocused = Duration::from_secs(29 * 60 + 30);
+        assert_eq!(minimized.next_action(&rules), IdleAction::Warn(30));
+        minimized.warned = true;
+        assert_eq!(minimized.next_action(&rules), IdleAction::None);
+        assert_eq!(window(30).next_action(&rules), IdleAction::Close);
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(parse_time("18:00"), Some(18 * 3600));
+        assert_eq!(parse_time(" 7:30 "), Some(7 * 3600 + 30 * 60));
+        assert_eq!(parse_time("24:00"), None);
+        assert_eq!(parse_time("6pm"), None);
+    }
+
+    #[test]
+    fn test_crossed() {
+        let at = 18 * 3600;
+        assert!(crossed(at - 5, at, at));
+        assert!(!crossed(at, at + 5, at));
+        assert!(!crossed(at - 10, at - 5, at));
+        // through midnight
+        assert!(crossed(DAY - 3, 2, 0));
+        assert!(crossed(DAY - 3, 2, DAY - 1));
+        assert!(!crossed(DAY - 3, 2, 3));
+    }
+}
@@ -0,0 +1,10 @@
+use seelen_core::system_state::ImeState;
+
+use crate::error_handler::Result;
+
+use super::Ime;
+
+#[tauri::command(async)]
+pub fn ime_get_state() -> Result<ImeState> {
+    Ok(Ime::get_state())
+}
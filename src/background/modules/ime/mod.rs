@@ -0,0 +1,133 @@
+pub mod infrastructure;
+
+use std::{collections::HashSet, time::Duration};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{handlers::SeelenEvent, system_state::ImeState};
+use windows::Win32::{
+    Foundation::{HWND, LPARAM, WPARAM},
+    UI::{
+        Input::Ime::{
+            ImmGetDefaultIMEWnd, IMC_GETCONVERSIONMODE, IMC_GETOPENSTATUS, IME_CMODE_NATIVE,
+        },
+        WindowsAndMessaging::{SendMessageTimeoutW, SMTO_ABORTIFHUNG, WM_IME_CONTROL},
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::event_registry::EventRegistry,
+    seelen::Seelen,
+    state::application::FULL_STATE,
+    trace_lock,
+    utils::spawn_named_thread,
+    windows_api::{window::Window, WindowsApi},
+    winevent::WinEvent,
+};
+
+/// toggling the input method with the keyboard doesn't produce any win event
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const QUERY_TIMEOUT_MS: u32 = 100;
+
+lazy_static! {
+    /// composition and candidate windows currently shown
+    static ref COMPOSING: Mutex<HashSet<isize>> = Mutex::new(HashSet::new());
+    /// last emitted state, so only changes are emitted
+    static ref LAST: Mutex<Option<ImeState>> = Mutex::new(None);
+}
+
+/// On/off state of the input method (IME) of the focused window
+pub struct Ime;
+
+impl Ime {
+    pub fn start() -> Result<()> {
+        spawn_named_thread("IME State", || {
+            while Seelen::is_running() {
+                if FULL_STATE.load().is_bar_enabled() {
+                    log_error!(Self::emit_changes());
+                }
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        })?;
+        Ok(())
+    }
+
+    pub fn wants_win_event(event: &WinEvent) -> bool {
+        matches!(
+            event,
+            WinEvent::ObjectShow
+                | WinEvent::ObjectHide
+                | WinEvent::ObjectDestroy
+                | WinEvent::ObjectImeShow
+                | WinEvent::ObjectImeHide
+                | WinEvent::ObjectImeChange
+                | WinEvent::SystemImeKeyNotification
+                | WinEvent::SystemForeground
+                | WinEvent::ObjectFocus
+        )
+    }
+
+    pub fn process_win_event(event: WinEvent, window: &Window) -> Result<()> {
+        if window.is_ime() {
+            let mut composing = trace_lock!(COMPOSING);
+            match event {
+                WinEvent::ObjectShow | WinEvent::ObjectImeShow => {
+                    composing.insert(window.address());
+                }
+                WinEvent::ObjectHide | WinEvent::ObjectImeHide | WinEvent::ObjectDestroy => {
+                    composing.remove(&window.address());
+                }
+                _ => {}
+            }
+        }
+        Self::emit_changes()
+    }
+
+    /// Sends `WM_IME_CONTROL` to the default IME window, 0 if the window doesn't respond
+    fn query(ime: HWND, command: u32) -> usize {
+        let mut result = 0;
+        unsafe {
+            SendMessageTimeoutW(
+                ime,
+                WM_IME_CONTROL,
+                WPARAM(command as usize),
+                LPARAM(0),
+                SMTO_ABORTIFHUNG,
+                QUERY_TIMEOUT_MS,
+                Some(&mut result),
+            )
+        };
+        result
+    }
+
+    pub fn get_state() -> ImeState {
+        let composing = !trace_lock!(COMPOSING).is_empty();
+        let ime = unsafe { ImmGetDefaultIMEWnd(WindowsApi::get_foreground_window()) };
+        if ime.is_invalid() {
+            return ImeState {
+                composing,
+                ..Default::default()
+            };
+        }
+        let open = Self::query(ime, IMC_GETOPENSTATUS) != 0;
+        // chinese input methods are always open and switch between native and alphanumeric
+        let conversion = Self::query(ime, IMC_GETCONVERSIONMODE) as u32;
+        ImeState {
+            available: true,
+            enabled: open && conversion & IME_CMODE_NATIVE.0 != 0,
+            composing,
+        }
+    }
+
+    pub fn emit_changes() -> Result<()> {
+        let state = Self::get_state();
+        let mut last = trace_lock!(LAST);
+        if *last != Some(state) {
+            EventRegistry::emit(SeelenEvent::ImeChanged, state)?;
+            *last = Some(state);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,466 @@
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    sync::Arc,
+    time::Duration,
+};
+
+use crossbeam_channel::{Sender, TrySendError};
+use itertools::Itertools;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::EventId;
+use windows::Win32::{
+    Foundation::{HWND, RECT},
+    Storage::FileSystem::PIPE_ACCESS_DUPLEX,
+};
+
+use crate::{
+    error_handler::Result,
+    hook::FocusedApp,
+    log_error,
+    modules::{
+        actions::domain::ActionCatalog,
+        cli::application::{handle_cli_events, SEELEN_COMMAND_LINE},
+        emit_scheduler::EmitScheduler,
+        focus_history::FocusHistory,
+        storage::Storage,
+        virtual_desk::get_vd_manager,
+        window_filter::WindowFilter,
+    },
+    seelen_weg::SeelenWeg,
+    state::application::{FullState, FULL_STATE},
+    trace_lock,
+    utils::spawn_named_thread,
+    windows_api::{window::Window, MonitorEnumerator, WindowEnumerator, WindowsApi},
+};
+
+use super::{
+    domain::{
+        parse_params, parse_request, CliParams, EventNotification, EventsParams,
+        FocusPreviousParams, InspectWindowParams, IpcWindow, MoveWindowParams, RpcError,
+        RpcNotification, RpcReply, RpcResponse, RpcRoute, RunActionParams, SetSettingParams,
+        WorkspaceParams, IPC_METHODS, JSON_RPC_VERSION,
+    },
+    pipe::{PipeConnection, PipeListener},
+};
+
+/// Messages waiting to be written to a client, a client that doesn't read them is dropped
+const SEND_QUEUE_SIZE: usize = 256;
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+/// requests are small JSON lines, a client sending more without a newline is dropped
+const MAX_REQUEST_SIZE: usize = 1024 * 1024;
+
+/// Queues the messages of a client, they are written by its own thread so the
+/// listeners of the events never wait for the pipe.
+#[derive(Clone)]
+struct ClientSender {
+    pipe: Arc<PipeConnection>,
+    queue: Sender<Vec<u8>>,
+}
+
+impl ClientSender {
+    fn new(pipe: Arc<PipeConnection>) -> Result<Self> {
+        let (queue, receiver) = crossbeam_channel::bounded::<Vec<u8>>(SEND_QUEUE_SIZE);
+        let writer = pipe.clone();
+        spawn_named_thread("IPC Writer", move || {
+            for line in receiver {
+                if let Err(err) = writer.write_all(&line, WRITE_TIMEOUT) {
+                    log::warn!("Dropping IPC client: {:?}", err);
+                    writer.disconnect();
+                    break;
+                }
+            }
+        })?;
+        Ok(Self { pipe, queue })
+    }
+
+    fn send<T: Serialize>(&self, message: &T) {
+        let mut line = match serde_json::to_vec(message) {
+            Ok(line) => line,
+            Err(err) => {
+                log::error!("Failed to serialize IPC message: {:?}", err);
+                return;
+            }
+        };
+        line.push(b'\n');
+        if let Err(TrySendError::Full(_)) = self.queue.try_send(line) {
+            log::warn!("Dropping IPC client: too many pending messages");
+            self.pipe.disconnect();
+        }
+    }
+}
+
+/// Subscriptions of a single client, removed when it disconnects
+struct Connection {
+    sender: ClientSender,
+    subscriptions: HashMap<String, EventId>,
+}
+
+pub struct IpcServer;
+
+impl IpcServer {
+    pub const PIPE_PATH: &str = r"\\.\pipe\seelen-ui";
+
+    pub fn start() -> Result<()> {
+        let mut listener = PipeListener::bind(Self::PIPE_PATH, PIPE_ACCESS_DUPLEX)?;
+        log::info!("IPC server listening on {}", Self::PIPE_PATH);
+        spawn_named_thread("IPC Server", move || loop {
+            match listener.accept() {
+                Ok(Some(pipe)) => match Connection::new(pipe) {
+                    Ok(connection) => {
+                        log_error!(spawn_named_thread("IPC Connection", move || {
+                            connection.serve()
+                        }));
+                    }
+                    Err(err) => log::error!("Failed to open IPC connection: {:?}", err),
+                },
+                Ok(None) => {
+                    log::trace!("Exiting IPC Server");
+                    break;
+                }
+                Err(err) => {
+                    log::error!("Failed to accept IPC connection: {:?}", err);
+                    std::thread::sleep(Duration::from_secs(1));
+                }
+            }
+        })?;
+        Ok(())
+    }
+
+    fn to_value<T: Serialize>(value: T) -> std::result::Result<Value, RpcError> {
+        serde_json::to_value(value)
+            .map_err(|e| RpcError::new(RpcError::INTERNAL_ERROR, e.to_string()))
+    }
+
+    /// Runs the arguments as if they were passed to `seelen-ui.exe`
+    fn run_cli(args: Vec<String>) -> std::result::Result<Value, RpcError> {
+        let command = trace_lock!(SEELEN_COMMAND_LINE).clone();
+        let argv = std::iter::once("seelen-ui".to_owned()).chain(args);
+        let matches = command
+            .try_get_matches_from(argv)
+            .map_err(|e| RpcError::new(RpcError::INVALID_PARAMS, e.to_string()))?;
+        handle_cli_events(&matches)?;
+        Ok(Value::Null)
+    }
+
+    fn target_window(hwnd: Option<isize>) -> isize {
+        hwnd.unwrap_or_else(|| WindowsApi::get_foreground_window().0 as isize)
+    }
+
+    fn ipc_window(window: &Window) -> IpcWindow {
+        IpcWindow {
+            hwnd: window.address(),
+            title: window.title(),
+            class: window.class(),
+            exe: window.exe().ok(),
+            workspace: window.workspace().ok().map(|d| d.id()),
+        }
+    }
+
+    /// Executes the methods that don't depend on the connection
+    pub fn handle(method: &str, params: Value) -> std::result::Result<Value, RpcError> {
+        let state = FULL_STATE.load();
+        match method {
+            "listMethods" => Self::to_value(IPC_METHODS),
+            "getSettings" => {
+                let mut settings = Self::to_value(&state.settings)?;
+                // gives access to the HTTP API, it is never handed out
+                if let Some(token) = settings.pointer_mut("/remoteControl/token") {
+                    *token = Value::String(String::new());
+                }
+                Ok(settings)
+            }
+            "getThemes" => Self::to_value(state.themes().values().collect_vec()),
+            "getWegItems" => Self::to_value(&*trace_lock!(state.weg_items)),
+            "getWorkspaces" => {
+                let vd = get_vd_manager();
+                let workspaces = vd
+                    .get_all()?
+                    .iter()
+                    .map(|d| d.as_serializable())
+                    .collect_vec();
+                Ok(json!({
+                    "active": vd.get_current_idx()?,
+                    "workspaces": Self::to_value(workspaces)?,
+                }))
+            }
+            "getMonitors" => {
+                let primary = WindowsApi::primary_monitor();
+                let mut monitors = Vec::new();
+                for (index, hmonitor) in MonitorEnumerator::get_all()?.into_iter().enumerate() {
+                    monitors.push(json!({
+                        "id": WindowsApi::monitor_name(hmonitor)?,
+                        "index": index,
+                        "primary": hmonitor == primary,
+                    }));
+                }
+                Ok(Value::Array(monitors))
+            }
+            "getFocusedWindow" => {
+                let window = Window::from(WindowsApi::get_foreground_window());
+                Self::to_value(FocusedApp::from(&window))
+            }
+            "getWindows" => {
+                let windows = WindowEnumerator::new()
+                    .map(Window::from)?
+                    .into_iter()
+                    .filter(|w| SeelenWeg::should_be_added(w.hwnd()))
+                    .map(|w| Self::ipc_window(&w))
+                    .collect_vec();
+                Self::to_value(windows)
+            }
+            "getFocusHistory" => {
+                let windows = FocusHistory::windows()
+                    .iter()
+                    .map(Self::ipc_window)
+                    .collect_vec();
+                Self::to_value(windows)
+            }
+            "getStorage" => Self::to_value(Storage::get_state()),
+            "explainWindows" => Self::to_value(WindowFilter::explain_all()?),
+            "inspectWindow" => {
+                let params: InspectWindowParams = match params {
+                    Value::Null => InspectWindowParams::default(),
+                    params => parse_params(params)?,
+                };
+                let inspection = match params.hwnd {
+                    Some(hwnd) => WindowFilter::inspect(&Window::from(hwnd), params.flash)?,
+                    None => WindowFilter::inspect_at_cursor(params.flash)?,
+                };
+                Self::to_value(inspection)
+            }
+            "listActions" => Self::to_value(ActionCatalog::build()),
+            "switchWorkspace" => {
+                let params: WorkspaceParams = parse_params(params)?;
+                get_vd_manager().switch_to(params.index)?;
+                Ok(Value::Null)
+            }
+            "sendToWorkspace" => {
+                let params: WorkspaceParams = parse_params(params)?;
+                get_vd_manager().send_to(params.index, Self::target_window(params.hwnd))?;
+                Ok(Value::Null)
+            }
+            "moveWindow" => {
+                let params: MoveWindowParams = parse_params(params)?;
+                let hwnd = HWND(Self::target_window(params.hwnd) as _);
+                let rect = RECT {
+                    left: params.x,
+                    top: params.y,
+                    ..Default::default()
+                };
+                WindowsApi::move_window(hwnd, &rect)?;
+                Ok(Value::Null)
+            }
+            "focusPrevious" => {
+                let params: FocusPreviousParams = match params {
+                    Value::Null => FocusPreviousParams::default(),
+                    params => parse_params(params)?,
+                };
+                FocusHistory::focus_previous(params.nth.unwrap_or(1))?;
+                Ok(Value::Null)
+            }
+            "setSetting" => {
+                let params: SetSettingParams = parse_params(params)?;
+                let pointer = format!("/{}", params.path.replace('.', "/"));
+                if Self::to_value(&state.settings)?.pointer(&pointer).is_none() {
+                    let msg = format!("Unknown setting: {}", params.path);
+                    return Err(RpcError::new(RpcError::INVALID_PARAMS, msg));
+                }
+                // the file watcher will reload and apply the changes
+                FullState::patch_settings(&pointer, params.value)?;
+                Ok(Value::Null)
+            }
+            "runAction" => {
+                let params: RunActionParams = parse_params(params)?;
+                let args = ActionCatalog::build()
+                    .to_cli_args(&params.id, &params.params)
+                    .map_err(|e| {
+                        RpcError::new(RpcError::INVALID_PARAMS, RpcError::from(e).message)
+                    })?;
+                Self::run_cli(args)
+            }
+            "cli" => {
+                let params: CliParams = parse_params(params)?;
+                Self::run_cli(params.args)
+            }
+            _ => Err(RpcError::new(
+                RpcError::METHOD_NOT_FOUND,
+                format!("Method not found: {}", method),
+            )),
+        }
+    }
+}
+
+impl Connection {
+    fn new(pipe: PipeConnection) -> Result<Self> {
+        Ok(Self {
+            sender: ClientSender::new(Arc::new(pipe))?,
+            subscriptions: HashMap::new(),
+        })
+    }
+
+    /// Reads newline delimited requests until the client disconnects
+    fn serve(mut self) {
+        let mut buffer = Vec::new();
+        let mut chunk = vec![0u8; 4096];
+        loop {
+            let read = match self.sender.pipe.read(&mut chunk) {
+                Ok(0) => break, // disconnected
+                Ok(read) => read,
+                Err(err) => {
+                    log::error!("Failed to read IPC request: {:?}", err);
+                    break;
+                }
+            };
+            buffer.extend_from_slice(&chunk[..read]);
+
+            while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+                let line = buffer.drain(..=pos).collect_vec();
+                if let Some(response) = self.process_line(&line) {
+                    self.sender.send(&response);
+                }
+            }
+
+            if buffer.len() > MAX_REQUEST_SIZE {
+                log::warn!("Dropping IPC client: request too large");
+                break;
+            }
+        }
+
+        for (event, id) in self.subscriptions.drain() {
+            EmitScheduler::unlisten(&event, id);
+        }
+        self.sender.pipe.disconnect();
+    }
+
+    fn process_line(&mut self, line: &[u8]) -> Option<RpcResponse> {
+        let request = match parse_request(line) {
+            Ok(Some(request)) => request,
+            Ok(None) => return None,
+            Err(response) => return Some(response),
+        };
+
+        log::trace!(target: "slu::ipc", "{}", request.method);
+        let result = self.dispatch(&request.method, request.params);
+        request.id.map(|id| RpcResponse::new(id, result))
+    }
+
+    fn dispatch(&mut self, method: &str, params: Value) -> std::result::Result<Value, RpcError> {
+        match RpcRoute::of(method)? {
+            RpcRoute::Subscribe => {
+                let params: EventsParams = parse_params(params)?;
+                for event in params.events {
+                    if self.subscriptions.contains_key(&event) {
+                        continue;
+                    }
+                    let sender = self.sender.clone();
+                    let name = event.clone();
+                    let id = EmitScheduler::listen_any(&event, move |e| {
+                        sender.send(&RpcNotification {
+                            jsonrpc: JSON_RPC_VERSION,
+                            method: "event",
+                            params: EventNotification {
+                                event: name.clone(),
+                                payload: serde_json::from_str(e.payload()).unwrap_or_default(),
+                            },
+                        });
+                    });
+                    self.subscriptions.insert(event, id);
+                }
+                Ok(Value::Null)
+            }
+            RpcRoute::Unsubscribe => {
+                let params: EventsParams = parse_params(params)?;
+                for event in params.events {
+                    if let Some(id) = self.subscriptions.remove(&event) {
+                        EmitScheduler::unlisten(&event, id);
+                    }
+                }
+                Ok(Value::Null)
+            }
+            RpcRoute::Server => IpcServer::handle(method, params),
+        }
+    }
+}
+
+/// Used by the command line to talk with the running instance
+pub struct IpcClient {
+    pipe: File,
+    reader: BufReader<File>,
+    last_id: u64,
+}
+
+impl IpcClient {
+    pub fn connect() -> Result<Self> {
+        let mut attempts = 0;
+        loop {
+            match OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open(IpcServer::PIPE_PATH)
+            {
+                Ok(pipe) => {
+                    let reader = BufReader::new(pipe.try_clone()?);
+                    return Ok(Self {
+                        pipe,
+                        reader,
+                        last_id: 0,
+                    });
+                }
+                // the pipe is busy while the server creates the next instance
+                Err(_) if attempts < 10 => {
+                    attempts += 1;
+                    std::thread::sleep(Duration::from_millis(100));
+                }
+                Err(err) => return Err(format!("Seelen UI is not running: {}", err).into()),
+            }
+        }
+    }
+
+    /// Sends a request without id, the server will not answer it
+    pub fn notify(&mut self, method: &str, params: Value) -> Result<()> {
+        let request = json!({
+            "jsonrpc": JSON_RPC_VERSION,
+            "method": method,
+            "params": params,
+        });
+        let mut line = serde_json::to_vec(&request)?;
+        line.push(b'\n');
+        self.pipe.write_all(&line)?;
+        self.pipe.flush()?;
+        Ok(())
+    }
+
+    pub fn request(&mut self, method: &str, params: Value) -> Result<Value> {
+        self.last_id += 1;
+        let request = json!({
+            "jsonrpc": JSON_RPC_VERSION,
+            "id": self.last_id,
+            "method": method,
+            "params": params,
+        });
+        let mut line = serde_json::to_vec(&request)?;
+        line.push(b'\n');
+        self.pipe.write_all(&line)?;
+        self.pipe.flush()?;
+
+        let expected_id = json!(self.last_id);
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Err("IPC connection closed".into());
+            }
+            let reply: RpcReply = serde_json::from_str(&line)?;
+            // skip event notifications
+            if reply.id.as_ref() != Some(&expected_id) {
+                continue;
+            }
+            return match reply.error {
+                Some(error) => Err(error.message.into()),
+                None => Ok(reply.result.unwrap_or_default()),
+            };
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use std::collections::HashMap;
+
+use seelen_core::state::WmNode;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{hook::FocusedApp, modules::virtual_desk::SerializableVirtualDesktop};
+
+pub const JSON_RPC_VERSION: &str = "2.0";
+
+/// Methods exposed through the pipe, documented in `documentation/ipc.md`
+pub const IPC_METHODS: &[&str] = &[
+    "listMethods",
+    "getSettings",
+    "getThemes",
+    "getWegItems",
+    "getWorkspaces",
+    "getMonitors",
+    "getFocusedWindow",
+    "getWindows",
+    "getFocusHistory",
+    "getStorage",
+    "explainWindows",
+    "inspectWindow",
+    "listActions",
+    "switchWorkspace",
+    "sendToWorkspace",
+    "moveWindow",
+    "focusPrevious",
+    "setSetting",
+    "runAction",
+    "cli",
+    "subscribe",
+    "unsubscribe",
+];
+
+/// Settings able to run programs or to give access to Seelen UI, they can't be changed by
+/// the HTTP API nor the scripts
+const PROTECTED_SETTINGS: &[&str] = &[
+    "remoteControl",
+    "triggers",
+    "keybindings",
+    "actionAliases",
+    "ahkEnabled",
+    "ahkVariables",
+    "launcher",
+    "updater",
+];
+
+/// Whether the dot separated `path` of `setSetting` is or is inside a protected setting
+pub fn is_protected_setting(path: &str) -> bool {
+    // same separators accepted by `setSetting`
+    let root = path.split(['.', '/']).next().unwrap_or_default();
+    PROTECTED_SETTINGS.contains(&root)
+}
+
+/// https://www.jsonrpc.org/specification#request_object
+#[derive(Debug, Deserialize)]
+pub struct RpcRequest {
+    /// notifications (requests without id) are processed but not answered
+    #[serde(default)]
+    pub id: Option<Value>,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RpcError {
+    pub code: i32,
+    pub message: String,
+}
+
+impl RpcError {
+    pub const PARSE_ERROR: i32 = -32700;
+    pub const METHOD_NOT_FOUND: i32 = -32601;
+    pub const INVALID_PARAMS: i32 = -32602;
+    pub const INTERNAL_ERROR: i32 = -32603;
+
+    pub fn new(code: i32, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<crate::error_handler::AppError> for RpcError {
+    fn from(err: crate::error_handler::AppError) -> Self {
+        // only the first line, the rest is the backtrace
+        let message = err
+            .to_string()
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_owned();
+        Self::new(Self::INTERNAL_ERROR, message)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct RpcResponse {
+    pub jsonrpc: &'static str,
+    pub id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+impl RpcResponse {
+    pub fn new(id: Value, result: Result<Value, RpcError>) -> Self {
+        let (result, error) = match result {
+            Ok(value) => (Some(value), None),
+            Err(err) => (None, Some(err)),
+        };
+        Self {
+            jsonrpc: JSON_RPC_VERSION,
+            id,
+            result,
+            error,
+        }
+    }
+}
+
+/// Parses a line sent by a client, empty lines are ignored.
+/// Invalid lines are answered with a parse error, as the id can't be known.
+pub fn parse_request(line: &[u8]) -> Result<Option<RpcRequest>, RpcResponse> {
+    let line = String::from_utf8_lossy(line);
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+    serde_json::from_str(line).map(Some).map_err(|err| {
+        let error = RpcError::new(RpcError::PARSE_ERROR, err.to_string());
+        RpcResponse::new(Value::Null, Err(error))
+    })
+}
+
+pub fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params)
+        .map_err(|e| RpcError::new(RpcError::INVALID_PARAMS, e.to_string()))
+}
+
+/// Where a request is executed, the subscriptions belong to the connection
+#[derive(Debug, PartialEq, Eq)]
+pub enum RpcRoute {
+    Subscribe,
+    Unsubscribe,
+    Server,
+}
+
+impl RpcRoute {
+    pub fn of(method: &str) -> Result<Self, RpcError> {
+        match method {
+            "subscribe" => Ok(Self::Subscribe),
+            "unsubscribe" => Ok(Self::Unsubscribe),
+            method if IPC_METHODS.contains(&method) => Ok(Self::Server),
+            _ => Err(RpcError::new(
+                RpcError::METHOD_NOT_FOUND,
+                format!("Method not found: {}", method),
+            )),
+        }
+    }
+}
+
+/// Response as read by the clients, it can also be a notification
+#[derive(Debug, Deserialize)]
+pub struct RpcReply {
+    #[serde(default)]
+    pub id: Option<Value>,
+    pub result: Option<Value>,
+    pub error: Option<RpcError>,
+}
+
+/// Sent to the clients subscribed to an event
+#[derive(Debug, Serialize)]
+pub struct RpcNotification {
+    pub jsonrpc: &'static str,
+    pub method: &'static str,
+    pub params: EventNotification,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EventNotification {
+    pub event: String,
+    pub payload: Value,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EventsParams {
+    pub events: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WorkspaceParams {
+    pub index: usize,
+    /// defaults to the foreground window
+    pub hwnd: Option<isize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MoveWindowParams {
+    pub hwnd: Option<isize>,
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct FocusPreviousParams {
+    /// places to go back on the focus history, defaults to the previous window
+    pub nth: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct InspectWindowParams {
+    /// defaults to the window under the cursor
+    pub hwnd: Option<isize>,
+    /// highlights the window for a moment
+    #[serde(default)]
+    pub flash: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CliParams {
+    pub args: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RunActionParams {
+    /// id of the action on the catalog or name of an alias, ex: `virtual-desk.switch-workspace`
+    pub id: String,
+    /// values of the params of the action by name
+    #[serde(default)]
+    pub params: HashMap<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetSettingParams {
+    /// dot separated path, ex: `seelenweg.enabled`
+    pub path: String,
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IpcWindow {
+    pub hwnd: isize,
+    pub title: String,
+    pub class: String,
+    pub exe: Option<std::path::PathBuf>,
+    pub workspace: Option<String>,
+}
+
+/// Published by the status feed on every change, documented in `documentation/ipc.md`
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusSnapshot {
+    /// incremented on breaking changes of the schema
+    pub version: u32,
+    pub active_workspace: usize,
+    pub workspaces: Vec<SerializableVirtualDesktop>,
+    pub focused_window: FocusedApp,
+    pub monitors: Vec<StatusMonitor>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusMonitor {
+    pub id: String,
+    /// layout of the active workspace, `null` if the window manager is disabled
+    pub layout: Option<WmNode>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_request() {
+        let request = parse_request(br#"{"jsonrpc":"2.0","id":1,"method":"getSettings"}"#)
+            .unwrap()
+            .unwrap();
+        assert_eq!(request.id, Some(json!(1)));
+        assert_eq!(request.method, "getSettings");
+        assert_eq!(request.params, Value::Null);
+
+        // notifications have no id
+        let request = parse_request(b"{\"method\":\"cli\",\"params\":{\"args\":[]}}\r\n")
+            .unwrap()
+            .unwrap();
+        assert_eq!(request.id, None);
+        assert_eq!(request.params, json!({ "args": [] }));
+
+        assert!(parse_request(b"  \n").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let response = parse_request(b"{not json}\n").unwrap_err();
+        assert_eq!(response.id, Value::Null);
+        assert!(response.result.is_none());
+        assert_eq!(response.error.unwrap().code, RpcError::PARSE_ERROR);
+    }
+
+    #[test]
+    fn test_route() {
+        assert_eq!(RpcRoute::of("subscribe").unwrap(), RpcRoute::Subscribe);
+        assert_eq!(RpcRoute::of("unsubscribe").unwrap(), RpcRoute::Unsubscribe);
+        for method in IPC_METHODS {
+            assert!(RpcRoute::of(method).is_ok());
+        }
+        let error = RpcRoute::of("deleteEverything").unwrap_err();
+        assert_eq!(error.code, RpcError::METHOD_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_invalid_params() {
+        let params: WorkspaceParams = parse_params(json!({ "index": 2 })).unwrap();
+        assert_eq!(params.index, 2);
+        assert_eq!(params.hwnd, None);
+
+        let error = parse_params::<WorkspaceParams>(json!({ "index": "2" })).unwrap_err();
+        assert_eq!(error.code, RpcError::INVALID_PARAMS);
+        let error = parse_params::<EventsParams>(Value::Null).unwrap_err();
+        assert_eq!(error.code, RpcError::INVALID_PARAMS);
+    }
+
+    #[test]
+    fn test_response_has_result_or_error() {
+        let ok = serde_json::to_value(RpcResponse::new(json!(7), Ok(json!([1, 2])))).unwrap();
+        assert_eq!(ok, json!({ "jsonrpc": "2.0", "id": 7, "result": [1, 2] }));
+
+        let error = RpcError::new(RpcError::INTERNAL_ERROR, "boom");
+        let err = serde_json::to_value(RpcResponse::new(json!("a"), Err(error))).unwrap();
+        assert_eq!(
+            err,
+            json!({ "jsonrpc": "2.0", "id": "a", "error": { "code": -32603, "message": "boom" } })
+        );
+    }
+
+    #[test]
+    fn test_protected_settings() {
+        assert!(is_protected_setting("remoteControl.token"));
+        assert!(is_protected_setting("triggers"));
+        assert!(is_protected_setting("keybindings/bindings"));
+        assert!(!is_protected_setting("fancyToolbar.enabled"));
+        assert!(!is_protected_setting("triggersX"));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod application;
+pub mod domain;
+pub mod pipe;
+pub mod remote;
+pub mod status_feed;
@@ -0,0 +1,332 @@
+use std::{
+    ffi::c_void,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use lazy_static::lazy_static;
+use windows::{
+    core::{HSTRING, PCWSTR, PWSTR},
+    Win32::{
+        Foundation::{
+            CloseHandle, LocalFree, ERROR_BROKEN_PIPE, ERROR_IO_PENDING, ERROR_NO_DATA,
+            ERROR_OPERATION_ABORTED, ERROR_PIPE_CONNECTED, ERROR_PIPE_NOT_CONNECTED, HANDLE,
+            HLOCAL, WAIT_OBJECT_0,
+        },
+        Security::{
+            Authorization::{
+                ConvertSidToStringSidW, ConvertStringSecurityDescriptorToSecurityDescriptorW,
+                SDDL_REVISION_1,
+            },
+            GetTokenInformation, TokenUser, PSECURITY_DESCRIPTOR, SECURITY_ATTRIBUTES, TOKEN_USER,
+        },
+        Storage::FileSystem::{
+            ReadFile, WriteFile, FILE_FLAGS_AND_ATTRIBUTES, FILE_FLAG_FIRST_PIPE_INSTANCE,
+            FILE_FLAG_OVERLAPPED,
+        },
+        System::{
+            Pipes::{
+                ConnectNamedPipe, CreateNamedPipeW, DisconnectNamedPipe, PIPE_READMODE_BYTE,
+                PIPE_REJECT_REMOTE_CLIENTS, PIPE_TYPE_BYTE, PIPE_UNLIMITED_INSTANCES, PIPE_WAIT,
+            },
+            Threading::{CreateEventW, WaitForSingleObject},
+            IO::{CancelIoEx, GetOverlappedResult, OVERLAPPED},
+        },
+    },
+};
+
+use crate::{error_handler::Result, seelen::Seelen, utils::sleep_millis, windows_api::WindowsApi};
+
+lazy_static! {
+    /// only the user running Seelen UI can open the pipes, they expose settings and the cli
+    static ref SECURITY_DESCRIPTOR: Option<String> = match current_user_sid() {
+        Ok(sid) => Some(format!("D:P(A;;GA;;;{})", sid)),
+        Err(err) => {
+            log::error!("Failed to get the user SID for the pipes: {:?}", err);
+            None
+        }
+    };
+}
+
+fn current_user_sid() -> Result<String> {
+    let token = WindowsApi::open_current_process_token()?;
+    let mut size = 0u32;
+    // first call to get the size
+    let _ = unsafe { GetTokenInformation(token, TokenUser, None, 0, &mut size) };
+    // u64 items so the buffer is aligned for `TOKEN_USER`
+    let mut buffer = vec![0u64; (size as usize).div_ceil(8)];
+    let result = unsafe {
+        GetTokenInformation(
+            token,
+            TokenUser,
+            Some(buffer.as_mut_ptr() as *mut c_void),
+            size,
+            &mut size,
+        )
+    };
+    WindowsApi::close_handle(token)?;
+    result?;
+
+    let user = unsafe { &*(buffer.as_ptr() as *const TOKEN_USER) };
+    let mut sid = PWSTR::null();
+    unsafe { ConvertSidToStringSidW(user.User.Sid, &mut sid)? };
+    let result = unsafe { sid.to_string() };
+    unsafe { LocalFree(HLOCAL(sid.0 as _)) };
+    Ok(result?)
+}
+
+/// Manual reset event used to wait an overlapped operation
+struct Overlapped {
+    inner: OVERLAPPED,
+}
+
+impl Overlapped {
+    fn new() -> Result<Self> {
+        let mut inner = OVERLAPPED::default();
+        inner.hEvent = unsafe { CreateEventW(None, true, false, PCWSTR::null())? };
+        Ok(Self { inner })
+    }
+
+    fn is_done(&self, timeout: Duration) -> bool {
+        unsafe {
+            WaitForSingleObject(self.inner.hEvent, timeout.as_millis() as u32) == WAIT_OBJECT_0
+        }
+    }
+
+    /// Bytes transferred, waits for the completion if `wait`
+    fn result(&self, pipe: HANDLE, wait: bool) -> windows::core::Result<u32> {
+        let mut transferred = 0u32;
+        unsafe { GetOverlappedResult(pipe, &self.inner, &mut transferred, wait)? };
+        Ok(transferred)
+    }
+
+    /// The operation should end before releasing the struct, the system writes on it
+    fn cancel(&self, pipe: HANDLE) {
+        unsafe {
+            let _ = CancelIoEx(pipe, Some(&self.inner as *const OVERLAPPED));
+        }
+        let _ = self.result(pipe, true);
+    }
+
+    fn as_ptr(&mut self) -> *mut OVERLAPPED {
+        &mut self.inner
+    }
+}
+
+impl Drop for Overlapped {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = CloseHandle(self.inner.hEvent);
+        }
+    }
+}
+
+fn is_disconnection(err: &windows::core::Error) -> bool {
+    [
+        ERROR_BROKEN_PIPE,
+        ERROR_NO_DATA,
+        ERROR_PIPE_NOT_CONNECTED,
+        ERROR_OPERATION_ABORTED,
+    ]
+    .iter()
+    .any(|code| err.code() == code.to_hresult())
+}
+
+/// Server end of a named pipe connection using overlapped I/O, so it can be read and written
+/// from different threads at the same time and the waits can be cancelled.
+pub struct PipeConnection {
+    handle: HANDLE,
+    closed: AtomicBool,
+}
+
+// the handle is only used through the overlapped functions, that are thread safe
+unsafe impl Send for PipeConnection {}
+unsafe impl Sync for PipeConnection {}
+
+impl PipeConnection {
+    const BUFFER_SIZE: u32 = 64 * 1024;
+    /// how often the exit of the app is checked while waiting for clients
+    const ACCEPT_POLL: Duration = Duration::from_millis(500);
+
+    fn create(path: &HSTRING, access: FILE_FLAGS_AND_ATTRIBUTES) -> Result<HANDLE> {
+        let descriptor = SECURITY_DESCRIPTOR
+            .as_ref()
+            .ok_or("The pipe can't be secured to the current user")?;
+        let mut security = PSECURITY_DESCRIPTOR::default();
+        unsafe {
+            ConvertStringSecurityDescriptorToSecurityDescriptorW(
+                &HSTRING::from(descriptor),
+                SDDL_REVISION_1,
+                &mut security,
+                None,
+            )?
+        };
+        let attributes = SECURITY_ATTRIBUTES {
+            nLength: std::mem::size_of::<SECURITY_ATTRIBUTES>() as u32,
+            lpSecurityDescriptor: security.0,
+            bInheritHandle: false.into(),
+        };
+
+        let handle = unsafe {
+            CreateNamedPipeW(
+                path,
+                access | FILE_FLAG_OVERLAPPED,
+                PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+                PIPE_UNLIMITED_INSTANCES,
+                Self::BUFFER_SIZE,
+                Self::BUFFER_SIZE,
+                0,
+                Some(&attributes),
+            )
+        };
+        unsafe { LocalFree(HLOCAL(security.0)) };
+        if handle.is_invalid() {
+            return Err(windows::core::Error::from_win32().into());
+        }
+        Ok(handle)
+    }
+
+    fn new(handle: HANDLE) -> Self {
+        Self {
+            handle,
+            closed: AtomicBool::new(false),
+        }
+    }
+
+    /// Waits until a client connects to this instance, returns `false` if the app is
+    /// closed while waiting.
+    fn wait_client(&self) -> Result<bool> {
+        let mut overlapped = Overlapped::new()?;
+        if let Err(err) = unsafe { ConnectNamedPipe(self.handle, Some(overlapped.as_ptr())) } {
+            // the client connected between the creation and the connect call
+            if err.code() == ERROR_PIPE_CONNECTED.to_hresult() {
+                return Ok(true);
+            }
+            if err.code() != ERROR_IO_PENDING.to_hresult() {
+                return Err(err.into());
+            }
+        }
+
+        while !overlapped.is_done(Self::ACCEPT_POLL) {
+            if !Seelen::is_running() {
+                overlapped.cancel(self.handle);
+                return Ok(false);
+            }
+        }
+        overlapped.result(self.handle, false)?;
+        Ok(true)
+    }
+
+    /// Blocks until data is available, returns 0 when the client is disconnected
+    pub fn read(&self, buffer: &mut [u8]) -> Result<usize> {
+        if self.closed.load(Ordering::Acquire) {
+            return Ok(0);
+        }
+        let mut overlapped = Overlapped::new()?;
+        if let Err(err) =
+            unsafe { ReadFile(self.handle, Some(buffer), None, Some(overlapped.as_ptr())) }
+        {
+            if is_disconnection(&err) {
+                return Ok(0);
+            }
+            if err.code() != ERROR_IO_PENDING.to_hresult() {
+                return Err(err.into());
+            }
+        }
+        match overlapped.result(self.handle, true) {
+            Ok(read) => Ok(read as usize),
+            Err(err) if is_disconnection(&err) => Ok(0),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Writes all the data, failing if the client doesn't read it before `timeout`
+    pub fn write_all(&self, mut data: &[u8], timeout: Duration) -> Result<()> {
+        while !data.is_empty() {
+            if self.closed.load(Ordering::Acquire) {
+                return Err("Pipe client disconnected".into());
+            }
+            let mut overlapped = Overlapped::new()?;
+            if let Err(err) =
+                unsafe { WriteFile(self.handle, Some(data), None, Some(overlapped.as_ptr())) }
+            {
+                if err.code() != ERROR_IO_PENDING.to_hresult() {
+                    return Err(err.into());
+                }
+            }
+            if !overlapped.is_done(timeout) {
+                overlapped.cancel(self.handle);
+                return Err("Pipe client is not reading".into());
+            }
+            let written = overlapped.result(self.handle, false)?;
+            data = &data[written as usize..];
+        }
+        Ok(())
+    }
+
+    /// Ends the connection, the pending reads and writes of other threads return
+    pub fn disconnect(&self) {
+        if self.closed.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        unsafe {
+            let _ = CancelIoEx(self.handle, None);
+            let _ = DisconnectNamedPipe(self.handle);
+        }
+    }
+}
+
+/// Server of a named pipe, an instance is always kept waiting for clients so no other
+/// process can take the name while Seelen UI is running.
+pub struct PipeListener {
+    path: HSTRING,
+    access: FILE_FLAGS_AND_ATTRIBUTES,
+    next: PipeConnection,
+}
+
+impl PipeListener {
+    /// attempts to create the first instance, an instance being replaced could still be exiting
+    const BIND_ATTEMPTS: u32 = 10;
+
+    /// Fails if the pipe already exists, another process could be impersonating the server
+    pub fn bind(path: &str, access: FILE_FLAGS_AND_ATTRIBUTES) -> Result<Self> {
+        let path = HSTRING::from(path);
+        let mut attempts = 0;
+        let handle = loop {
+            match PipeConnection::create(&path, access | FILE_FLAG_FIRST_PIPE_INSTANCE) {
+                Ok(handle) => break handle,
+                Err(_) if attempts < Self::BIND_ATTEMPTS => {
+                    attempts += 1;
+                    sleep_millis(200);
+                }
+                Err(err) => {
+                    return Err(format!("The pipe {} is already in use: {:?}", path, err).into())
+                }
+            }
+        };
+        Ok(Self {
+            path,
+            access,
+            next: PipeConnection::new(handle),
+        })
+    }
+
+    /// Waits until a client connects, returns `None` if the app is closed while waiting.
+    pub fn accept(&mut self) -> Result<Option<PipeConnection>> {
+        if !self.next.wait_client()? {
+            return Ok(None);
+        }
+        // the replacement is created before handing out the connected instance
+        let next = PipeConnection::new(PipeConnection::create(&self.path, self.access)?);
+        Ok(Some(std::mem::replace(&mut self.next, next)))
+    }
+}
+
+impl Drop for PipeConnection {
+    fn drop(&mut self) {
+        self.disconnect();
+        unsafe {
+            let _ = CloseHandle(self.handle);
+        }
+    }
+}
@@ -0,0 +1,358 @@
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::state::RemoteControlSettings;
+use serde_json::{json, Value};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    seelen::Seelen,
+    trace_lock,
+    utils::{sleep_millis, spawn_named_thread},
+};
+
+use super::{
+    application::IpcServer,
+    domain::{is_protected_setting, RpcError},
+};
+
+/// bodies bigger than this are rejected, requests are small JSON objects
+const MAX_BODY_SIZE: usize = 1024 * 1024;
+const MAX_HEADERS: usize = 64;
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// shorter tokens can be guessed, the server is not started with them
+pub const MIN_TOKEN_LENGTH: usize = 24;
+
+/// the port can be reached by any local user, so only the reads and window management are
+/// exposed, the methods able to run programs (`cli`, `runAction`) are only available on the pipe
+const REMOTE_METHODS: &[&str] = &[
+    "listMethods",
+    "getSettings",
+    "getThemes",
+    "getWegItems",
+    "getWorkspaces",
+    "getMonitors",
+    "getFocusedWindow",
+    "getWindows",
+    "getFocusHistory",
+    "getStorage",
+    "explainWindows",
+    "inspectWindow",
+    "listActions",
+    "switchWorkspace",
+    "sendToWorkspace",
+    "moveWindow",
+    "focusPrevious",
+    "setSetting",
+];
+
+lazy_static! {
+    /// (port, token) of the running server
+    static ref CURRENT: Mutex<Option<(u16, String)>> = Mutex::new(None);
+}
+
+/// incremented to stop the running listener
+static GENERATION: AtomicU32 = AtomicU32::new(0);
+
+struct HttpRequest {
+    method: String,
+    path: String,
+    /// lowercased names
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+struct HttpResponse {
+    status: u16,
+    body: Value,
+}
+
+impl HttpResponse {
+    fn new(status: u16, body: Value) -> Self {
+        Self { status, body }
+    }
+
+    fn error(status: u16, error: RpcError) -> Self {
+        Self::new(status, json!({ "error": error }))
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            403 => "Forbidden",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+/// Optional HTTP API on localhost exposing the same methods of the IPC pipe,
+/// used by companions that can't open named pipes (Stream Deck plugins, phones, etc.)
+pub struct RemoteControl;
+
+impl RemoteControl {
+    /// Starts, stops or restarts the server to match the settings
+    pub fn apply(settings: &RemoteControlSettings) -> Result<()> {
+        let mut current = trace_lock!(CURRENT);
+
+        let valid_token = settings.token.chars().count() >= MIN_TOKEN_LENGTH;
+        if settings.enabled && !valid_token {
+            log::warn!(
+                "Remote control is enabled but the token has less than {} characters, it will not be started",
+                MIN_TOKEN_LENGTH
+            );
+        }
+
+        let desired =
+            (settings.enabled && valid_token).then(|| (settings.port, settings.token.clone()));
+
+        let running_port = current.as_ref().map(|(port, _)| *port);
+        let desired_port = desired.as_ref().map(|(port, _)| *port);
+        // the token is read on each request, so changing it doesn't need a restart
+        if running_port == desired_port {
+            *current = desired;
+            return Ok(());
+        }
+
+        let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+        *current = desired;
+        if let Some(port) = desired_port {
+            spawn_named_thread("Remote Control", move || {
+                log_error!(Self::listen(port, generation));
+            })?;
+        }
+        Ok(())
+    }
+
+    fn is_current(generation: u32) -> bool {
+        Seelen::is_running() && GENERATION.load(Ordering::SeqCst) == generation
+    }
+
+    fn bind(port: u16) -> Result<TcpListener> {
+        let mut attempts = 0;
+        loop {
+            match TcpListener::bind((Ipv4Addr::LOCALHOST, port)) {
+                Ok(listener) => return Ok(listener),
+                // the previous listener could still be releasing the port
+                Err(_) if attempts < 10 => {
+                    attempts += 1;
+                    sleep_millis(200);
+                }
+                Err(err) => {
+                    return Err(format!("Failed to listen on port {}: {}", port, err).into())
+                }
+            }
+        }
+    }
+
+    fn listen(port: u16, generation: u32) -> Result<()> {
+        let listener = Self::bind(port)?;
+        // non blocking to be able to stop the server when the settings change
+        listener.set_nonblocking(true)?;
+        log::info!("Remote control listening on http://127.0.0.1:{}", port);
+
+        while Self::is_current(generation) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    log_error!(spawn_named_thread("Remote Control Connection", move || {
+                        log_error!(Self::serve(stream));
+                    }));
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => sleep_millis(100),
+                Err(err) => {
+                    log::error!("Failed to accept remote control connection: {:?}", err);
+                    sleep_millis(1000);
+                }
+            }
+        }
+
+        log::info!("Remote control stopped on port {}", port);
+        Ok(())
+    }
+
+    fn serve(mut stream: TcpStream) -> Result<()> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+
+        let response = match Self::read_request(&stream) {
+            Ok(request) => Self::respond(request),
+            Err(response) => response,
+        };
+
+        let body = serde_json::to_vec(&response.body)?;
+        let head = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            response.status,
+            response.reason(),
+            body.len()
+        );
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(&body)?;
+        stream.flush()?;
+        Ok(())
+    }
+
+    fn read_request(stream: &TcpStream) -> std::result::Result<HttpRequest, HttpResponse> {
+        let bad_request = |msg: &str| {
+            HttpResponse::error(400, RpcError::new(RpcError::PARSE_ERROR, msg.to_owned()))
+        };
+
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .map_err(|_| bad_request("Invalid request line"))?;
+
+        let mut parts = line.split_whitespace();
+        let (method, path) = match (parts.next(), parts.next()) {
+            (Some(method), Some(path)) => (method.to_owned(), path.to_owned()),
+            _ => return Err(bad_request("Invalid request line")),
+        };
+
+        let mut headers = HashMap::new();
+        loop {
+            line.clear();
+            reader
+                .read_line(&mut line)
+                .map_err(|_| bad_request("Invalid header"))?;
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if headers.len() >= MAX_HEADERS {
+                return Err(bad_request("Too many headers"));
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                headers.insert(name.trim().to_lowercase(), value.trim().to_owned());
+            }
+        }
+
+        let length = headers
+            .get("content-length")
+            .map(|value| value.parse::<usize>())
+            .transpose()
+            .map_err(|_| bad_request("Invalid Content-Length"))?
+            .unwrap_or(0);
+        if length > MAX_BODY_SIZE {
+            let error = RpcError::new(RpcError::INVALID_PARAMS, "Body too large");
+            return Err(HttpResponse::error(413, error));
+        }
+
+        let mut body = vec![0u8; length];
+        reader
+            .read_exact(&mut body)
+            .map_err(|_| bad_request("Incomplete body"))?;
+
+        Ok(HttpRequest {
+            method,
+            path,
+            headers,
+            body,
+        })
+    }
+
+    /// Compares the whole token to not leak how many characters matched
+    fn is_authorized(request: &HttpRequest) -> bool {
+        let expected = match &*trace_lock!(CURRENT) {
+            Some((_, token)) => token.clone(),
+            None => return false,
+        };
+        let given = match request
+            .headers
+            .get("authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+        {
+            Some(token) => token.trim(),
+            None => return false,
+        };
+        given.len() == expected.len()
+            && given
+                .bytes()
+                .zip(expected.bytes())
+                .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+                == 0
+    }
+
+    fn respond(request: HttpRequest) -> HttpResponse {
+        if !Self::is_authorized(&request) {
+            let error = RpcError::new(RpcError::INVALID_PARAMS, "Missing or invalid token");
+            return HttpResponse::error(401, error);
+        }
+
+        let method = match request
+            .path
+            .split('?')
+            .next()
+            .and_then(|p| p.strip_prefix("/api/"))
+        {
+            Some(method) if !method.is_empty() => method,
+            _ => {
+                let error = RpcError::new(RpcError::METHOD_NOT_FOUND, "Expected /api/<method>");
+                return HttpResponse::error(404, error);
+            }
+        };
+
+        if !REMOTE_METHODS.contains(&method) {
+            let error = RpcError::new(
+                RpcError::METHOD_NOT_FOUND,
+                format!("{} is not available on the HTTP API", method),
+            );
+            return HttpResponse::error(404, error);
+        }
+
+        let params = match request.method.as_str() {
+            "GET" => Value::Null,
+            "POST" if request.body.iter().all(|b| b.is_ascii_whitespace()) => Value::Null,
+            "POST" => match serde_json::from_slice(&request.body) {
+                Ok(params) => params,
+                Err(err) => {
+                    let error = RpcError::new(RpcError::PARSE_ERROR, err.to_string());
+                    return HttpResponse::error(400, error);
+                }
+            },
+            _ => {
+                let error = RpcError::new(RpcError::METHOD_NOT_FOUND, "Use GET or POST");
+                return HttpResponse::error(405, error);
+            }
+        };
+
+        if method == "setSetting"
+            && params
+                .get("path")
+                .and_then(Value::as_str)
+                .is_some_and(is_protected_setting)
+        {
+            let error = RpcError::new(
+                RpcError::INVALID_PARAMS,
+                "This setting can't be changed remotely",
+            );
+            return HttpResponse::error(403, error);
+        }
+
+        log::trace!(target: "slu::ipc", "http {}", method);
+        match IpcServer::handle(method, params) {
+            Ok(result) => HttpResponse::new(200, json!({ "result": result })),
+            Err(error) => {
+                let status = match error.code {
+                    RpcError::PARSE_ERROR | RpcError::INVALID_PARAMS => 400,
+                    RpcError::METHOD_NOT_FOUND => 404,
+                    _ => 500,
+                };
+                HttpResponse::error(status, error)
+            }
+        }
+    }
+}
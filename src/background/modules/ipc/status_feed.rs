@@ -0,0 +1,161 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use crossbeam_channel::{Receiver, Sender};
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{handlers::SeelenEvent, state::WmNode};
+use windows::Win32::Storage::FileSystem::PIPE_ACCESS_OUTBOUND;
+
+use crate::{
+    error_handler::Result,
+    hook::FocusedApp,
+    log_error,
+    modules::{emit_scheduler::EmitScheduler, virtual_desk::get_vd_manager},
+    trace_lock,
+    utils::spawn_named_thread,
+    windows_api::{window::Window, WindowsApi},
+};
+
+use super::{
+    domain::{StatusMonitor, StatusSnapshot},
+    pipe::{PipeConnection, PipeListener},
+};
+
+/// Changes closer than this are published together
+const DEBOUNCE: Duration = Duration::from_millis(50);
+/// Clients that don't read the snapshot in this time are dropped
+const WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+lazy_static! {
+    static ref CLIENTS: Mutex<Vec<PipeConnection>> = Mutex::new(Vec::new());
+    /// monitor id -> layout of the active workspace
+    static ref LAYOUTS: Mutex<BTreeMap<String, Option<WmNode>>> = Mutex::new(BTreeMap::new());
+    static ref LAST: Mutex<String> = Mutex::new(String::new());
+    static ref CHANGES: (Sender<()>, Receiver<()>) = crossbeam_channel::unbounded();
+}
+
+/// Read only pipe publishing the workspaces, focused window and layouts on every change,
+/// so external status bars can show the state of the window manager without JSON-RPC.
+pub struct StatusFeed;
+
+impl StatusFeed {
+    pub const PIPE_PATH: &str = r"\\.\pipe\seelen-ui-status";
+    const SCHEMA_VERSION: u32 = 1;
+    /// listened through the scheduler, so the focus changes arrive without webviews open
+    const EVENTS: [&'static str; 3] = [
+        SeelenEvent::GlobalFocusChanged,
+        SeelenEvent::WorkspacesChanged,
+        SeelenEvent::ActiveWorkspaceChanged,
+    ];
+
+    pub fn start() -> Result<()> {
+        let mut listener = PipeListener::bind(Self::PIPE_PATH, PIPE_ACCESS_OUTBOUND)?;
+        spawn_named_thread("Status Feed Server", move || loop {
+            match listener.accept() {
+                Ok(Some(pipe)) => {
+                    trace_lock!(CLIENTS).push(pipe);
+                    // new clients receive the current state right away
+                    trace_lock!(LAST).clear();
+                    Self::notify();
+                }
+                Ok(None) => break,
+                Err(err) => {
+                    log::error!("Failed to accept status feed connection: {:?}", err);
+                    std::thread::sleep(Duration::from_secs(1));
+                }
+            }
+        })?;
+
+        spawn_named_thread("Status Feed", || {
+            while CHANGES.1.recv().is_ok() {
+                std::thread::sleep(DEBOUNCE);
+                CHANGES.1.try_iter().for_each(drop);
+                log_error!(Self::publish());
+            }
+        })?;
+
+        for event in Self::EVENTS {
+            EmitScheduler::listen_any(event, |_| Self::notify());
+        }
+        Ok(())
+    }
+
+    fn notify() {
+        let _ = CHANGES.0.send(());
+    }
+
+    /// Called by the window manager each time a layout is rendered
+    pub fn on_layout(monitor_id: &str, root: Option<&WmNode>) {
+        trace_lock!(LAYOUTS).insert(monitor_id.to_string(), root.cloned());
+        Self::notify();
+    }
+
+    /// Called when the window manager is disabled
+    pub fn clear_layouts() {
+        trace_lock!(LAYOUTS).clear();
+        Self::notify();
+    }
+
+    fn snapshot() -> Result<StatusSnapshot> {
+        let vd = get_vd_manager();
+        let layouts = trace_lock!(LAYOUTS);
+        let monitors = layouts
+            .iter()
+            .map(|(id, layout)| StatusMonitor {
+                id: id.clone(),
+                layout: layout.clone(),
+            })
+            .collect_vec();
+        Ok(StatusSnapshot {
+            version: Self::SCHEMA_VERSION,
+            active_workspace: vd.get_current_idx()?,
+            workspaces: vd.get_all()?.iter().map(|d| d.as_serializable()).collect(),
+            focused_window: FocusedApp::from(&Window::from(WindowsApi::get_foreground_window())),
+            monitors,
+        })
+    }
+
+    /// Writes the snapshot to the connected clients if it changed, disconnected clients are dropped
+    fn publish() -> Result<()> {
+        if trace_lock!(CLIENTS).is_empty() {
+            return Ok(());
+        }
+
+        let mut line = serde_json::to_string(&Self::snapshot()?)?;
+        {
+            let mut last = trace_lock!(LAST);
+            if *last == line {
+                return Ok(());
+            }
+            *last = line.clone();
+        }
+        line.push('\n');
+
+        // written without the lock, so slow clients don't block the new connections
+        let mut clients = std::mem::take(&mut *trace_lock!(CLIENTS));
+        clients.retain(|pipe| pipe.write_all(line.as_bytes(), WRITE_TIMEOUT).is_ok());
+        trace_lock!(CLIENTS).extend(clients);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_focus_changes_without_webviews() {
+        // headless or without toolbar and weg, no webview subscribes to the scheduled events
+        assert!(StatusFeed::EVENTS.contains(&SeelenEvent::GlobalFocusChanged));
+        for event in StatusFeed::EVENTS {
+            EmitScheduler::subscribe_backend(event);
+        }
+        assert!(EmitScheduler::has_subscribers(
+            SeelenEvent::GlobalFocusChanged
+        ));
+        for event in StatusFeed::EVENTS {
+            EmitScheduler::unsubscribe_backend(event);
+        }
+    }
+}
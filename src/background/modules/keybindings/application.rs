@@ -0,0 +1,570 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc::{channel, Sender},
+        Arc,
+    },
+    time::Duration,
+};
+
+use arc_swap::ArcSwap;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{
+    handlers::SeelenEvent,
+    state::{KeybindingAction, KeybindingsSettings},
+};
+use tauri_plugin_shell::ShellExt;
+use windows::Win32::{
+    Foundation::{HINSTANCE, HWND, LPARAM, LRESULT, WPARAM},
+    UI::{
+        Input::KeyboardAndMouse::{
+            SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYBD_EVENT_FLAGS,
+            KEYEVENTF_KEYUP, VIRTUAL_KEY, VK_DOWN, VK_ESCAPE, VK_LEFT, VK_LMENU, VK_LWIN, VK_MENU,
+            VK_RETURN, VK_RIGHT, VK_RMENU, VK_RWIN, VK_TAB, VK_UP,
+        },
+        WindowsAndMessaging::{
+            CallNextHookEx, DispatchMessageW, GetMessageW, SetWindowsHookExW, TranslateMessage,
+            UnhookWindowsHookEx, HC_ACTION, HHOOK, KBDLLHOOKSTRUCT, LLKHF_EXTENDED, LLKHF_INJECTED,
+            MSG, WH_KEYBOARD_LL, WM_KEYDOWN, WM_KEYUP, WM_SYSKEYDOWN, WM_SYSKEYUP,
+        },
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::{
+        actions::domain::ActionCatalog,
+        cli::application::{handle_cli_events, SEELEN_COMMAND_LINE},
+        event_registry::EventRegistry,
+        hardware_keys::HardwareKeys,
+        window_switcher::{SwitcherInput, WindowSwitcher},
+    },
+    seelen::{get_app_handle, Seelen},
+    trace_lock,
+    utils::spawn_named_thread,
+};
+
+use super::domain::{
+    Hotkey, KeyChordDiagnostic, KeyPress, KeybindingConflict, KeybindingHint, KeybindingsHints,
+    Modifiers, SCAN_CODE_EXTENDED,
+};
+
+lazy_static! {
+    static ref KEYMAP: ArcSwap<Keymap> = ArcSwap::from_pointee(Keymap::default());
+    static ref PENDING: Arc<Mutex<Vec<KeyPress>>> = Arc::new(Mutex::new(Vec::new()));
+    pub static ref KEYBINDINGS_CONFLICTS: Arc<Mutex<Vec<KeybindingConflict>>> =
+        Arc::new(Mutex::new(Vec::new()));
+    /// set while waiting for a chord to be diagnosed, the next pressed chord is sent here
+    static ref DIAGNOSE: Mutex<Option<Sender<KeyPress>>> = Mutex::new(None);
+}
+
+static HOOK_INSTALLED: AtomicBool = AtomicBool::new(false);
+static SUPPRESS_START_MENU: AtomicBool = AtomicBool::new(false);
+static GRAB_ALT_TAB: AtomicBool = AtomicBool::new(false);
+static GRAB_HARDWARE_KEYS: AtomicBool = AtomicBool::new(false);
+/// true while the window switcher is open by Alt + Tab, until Alt is released
+static ALT_TAB_ACTIVE: AtomicBool = AtomicBool::new(false);
+/// true while the Windows key is held down without pressing any other key
+static WIN_KEY_ALONE: AtomicBool = AtomicBool::new(false);
+/// incremented each time the pending sequence changes, used to expire it after the timeout
+static PENDING_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Unassigned virtual key used to prevent the start menu (or the menu bar for Alt) from
+/// opening when a combination including the Windows key is swallowed by the hook.
+const VK_NONE: VIRTUAL_KEY = VIRTUAL_KEY(0xE8);
+
+struct RegisteredBinding {
+    sequence: Vec<Hotkey>,
+    labels: Vec<String>,
+    action: KeybindingAction,
+}
+
+#[derive(Default)]
+struct Keymap {
+    bindings: Vec<RegisteredBinding>,
+    timeout: Duration,
+}
+
+impl RegisteredBinding {
+    fn starts_with(&self, pressed: &[KeyPress]) -> bool {
+        self.sequence.len() >= pressed.len()
+            && self
+                .sequence
+                .iter()
+                .zip(pressed)
+                .all(|(hotkey, press)| hotkey.matches(press))
+    }
+}
+
+impl Keymap {
+    fn find(&self, pressed: &[KeyPress]) -> Option<&RegisteredBinding> {
+        self.bindings
+            .iter()
+            .find(|b| b.sequence.len() == pressed.len() && b.starts_with(pressed))
+    }
+
+    fn is_prefix(&self, pressed: &[KeyPress]) -> bool {
+        self.bindings
+            .iter()
+            .any(|b| b.sequence.len() > pressed.len() && b.starts_with(pressed))
+    }
+
+    fn hints(&self, pressed: &[KeyPress]) -> KeybindingsHints {
+        let mut hints = KeybindingsHints::default();
+        let step = pressed.len();
+        for binding in &self.bindings {
+            if binding.sequence.len() <= step || !binding.starts_with(pressed) {
+                continue;
+            }
+            if hints.pressed.is_empty() {
+                hints.pressed = binding.labels[..step].to_vec();
+            }
+            let keys = &binding.labels[step];
+            if hints.next.iter().any(|hint| &hint.keys == keys) {
+                continue;
+            }
+            hints.next.push(KeybindingHint {
+                keys: keys.clone(),
+                action: (binding.sequence.len() == step + 1).then(|| binding.action.clone()),
+            });
+        }
+        hints
+    }
+}
+
+pub struct KeybindingsManager;
+
+impl KeybindingsManager {
+    /// Loads (or reloads) the keybindings from the settings, installing the keyboard hook if needed
+    pub fn load(settings: &KeybindingsSettings) -> Result<()> {
+        let mut keymap = Keymap {
+            bindings: Vec::new(),
+            timeout: Duration::from_millis(settings.sequence_timeout),
+        };
+        let mut conflicts: HashMap<Vec<Hotkey>, KeybindingConflict> = HashMap::new();
+
+        if settings.enabled {
+            for binding in &settings.bindings {
+                let labels = binding.sequence();
+                let mode = binding.match_mode.unwrap_or(settings.match_mode);
+                let sequence = match Hotkey::parse_sequence(&labels, mode) {
+                    Ok(sequence) => sequence,
+                    Err(err) => {
+                        log::error!("Invalid keybinding: {:?}", err);
+                        continue;
+                    }
+                };
+
+                // a binding is unreachable if it is equal to or starts with another one and vice versa
+                let registered = keymap.bindings.iter().find(|b| {
+                    b.sequence.starts_with(&sequence) || sequence.starts_with(&b.sequence)
+                });
+
+                match registered {
+                    Some(registered) => {
+                        log::warn!(
+                            "Keybinding conflict on {}, {:?} will be ignored",
+                            labels.join(" > "),
+                            binding.action
+                        );
+                        conflicts
+                            .entry(registered.sequence.clone())
+                            .or_insert_with(|| KeybindingConflict {
+                                keys: registered.labels.join(" > "),
+                                actions: vec![registered.action.clone()],
+                            })
+                            .actions
+                            .push(binding.action.clone());
+                    }
+                    None => keymap.bindings.push(RegisteredBinding {
+                        sequence,
+                        labels: labels.iter().map(|l| l.to_string()).collect(),
+                        action: binding.action.clone(),
+                    }),
+                }
+            }
+        }
+
+        let should_hook = !keymap.bindings.is_empty();
+        KEYMAP.store(Arc::new(keymap));
+        Self::cancel_sequence();
+
+        let conflicts: Vec<KeybindingConflict> = conflicts.into_values().collect();
+        EventRegistry::emit(SeelenEvent::KeybindingsConflicts, &conflicts)?;
+        *trace_lock!(KEYBINDINGS_CONFLICTS) = conflicts;
+
+        if should_hook {
+            Self::ensure_hook()?;
+        }
+        Ok(())
+    }
+
+    /// If enabled, pressing the Windows key alone toggles the launcher instead of opening the start menu
+    pub fn set_start_menu_suppression(enabled: bool) -> Result<()> {
+        SUPPRESS_START_MENU.store(enabled, Ordering::SeqCst);
+        if enabled {
+            Self::ensure_hook()?;
+        }
+        Ok(())
+    }
+
+    /// If enabled, Alt + Tab opens the seelen window switcher instead of the native one
+    pub fn set_window_switcher_grab(enabled: bool) -> Result<()> {
+        GRAB_ALT_TAB.store(enabled, Ordering::SeqCst);
+        if enabled {
+            Self::ensure_hook()?;
+        }
+        Ok(())
+    }
+
+    /// If enabled, the volume and media keys are handled by seelen instead of the system
+    pub fn set_hardware_keys_grab(enabled: bool) -> Result<()> {
+        GRAB_HARDWARE_KEYS.store(enabled, Ordering::SeqCst);
+        if enabled {
+            Self::ensure_hook()?;
+        }
+        Ok(())
+    }
+
+    fn ensure_hook() -> Result<()> {
+        if !HOOK_INSTALLED.swap(true, Ordering::SeqCst) {
+            Self::register_hook()?;
+        }
+        Ok(())
+    }
+
+    fn register_hook() -> Result<()> {
+        log::trace!("Registering Keyboard Hook");
+        spawn_named_thread("KeyboardHook", || unsafe {
+            let hook = match SetWindowsHookExW(
+                WH_KEYBOARD_LL,
+                Some(Self::keyboard_hook),
+                HINSTANCE::default(),
+                0,
+            ) {
+                Ok(hook) => hook,
+                Err(err) => {
+                    log::error!("Failed to register keyboard hook: {:?}", err);
+                    HOOK_INSTALLED.store(false, Ordering::SeqCst);
+                    return;
+                }
+            };
+
+            let mut msg: MSG = MSG::default();
+            loop {
+                if !GetMessageW(&mut msg, HWND::default(), 0, 0).as_bool() {
+                    break;
+                };
+                let _ = TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+
+            log_error!(UnhookWindowsHookEx(hook));
+            HOOK_INSTALLED.store(false, Ordering::SeqCst);
+        })?;
+        Ok(())
+    }
+
+    unsafe extern "system" fn keyboard_hook(
+        code: i32,
+        w_param: WPARAM,
+        l_param: LPARAM,
+    ) -> LRESULT {
+        if code == HC_ACTION as i32 && Seelen::is_running() {
+            let message = w_param.0 as u32;
+            let info = &*(l_param.0 as *const KBDLLHOOKSTRUCT);
+            let injected = info.flags.0 & LLKHF_INJECTED.0 != 0;
+
+            if !injected && Self::on_alt_tab_event(message, info.vkCode as u16) {
+                return LRESULT(1);
+            }
+
+            if !injected && Self::on_win_key_event(message, info.vkCode as u16) {
+                return LRESULT(1);
+            }
+
+            if !injected && Self::on_hardware_key_event(message, info.vkCode as u16) {
+                return LRESULT(1);
+            }
+
+            if !injected && (message == WM_KEYDOWN || message == WM_SYSKEYDOWN) {
+                let vk = info.vkCode as u16;
+                let extended = info.flags.0 & LLKHF_EXTENDED.0 != 0;
+                let press = KeyPress {
+                    modifiers: Modifiers::current(),
+                    vk,
+                    scan: info.scanCode as u16 | if extended { SCAN_CODE_EXTENDED } else { 0 },
+                };
+                if !Modifiers::is_modifier_key(vk)
+                    && (Self::on_diagnose(press) || Self::on_key_down(press))
+                {
+                    // swallow the key so the focused app doesn't receive it
+                    return LRESULT(1);
+                }
+            }
+        }
+        CallNextHookEx(HHOOK::default(), code, w_param, l_param)
+    }
+
+    /// Returns true if the key was consumed by the window switcher
+    fn on_alt_tab_event(message: u32, vk: u16) -> bool {
+        if !GRAB_ALT_TAB.load(Ordering::SeqCst) {
+            return false;
+        }
+        let active = ALT_TAB_ACTIVE.load(Ordering::SeqCst);
+        let vk = VIRTUAL_KEY(vk);
+        match message {
+            WM_KEYDOWN | WM_SYSKEYDOWN => {
+                let modifiers = Modifiers::current();
+                if vk == VK_TAB && (active || (modifiers.alt && !modifiers.ctrl && !modifiers.win))
+                {
+                    let first = !ALT_TAB_ACTIVE.swap(true, Ordering::SeqCst);
+                    let reverse = modifiers.shift;
+                    if first {
+                        // the focused app only saw Alt, releasing it alone would open the menu bar
+                        std::thread::spawn(Self::mask_modifier_key);
+                        WindowSwitcher::send(SwitcherInput::Open { reverse });
+                    } else {
+                        WindowSwitcher::send(SwitcherInput::Step(if reverse { -1 } else { 1 }));
+                    }
+                    return true;
+                }
+                if !active {
+                    return false;
+                }
+                let input = match vk {
+                    VK_ESCAPE => SwitcherInput::Cancel,
+                    VK_RETURN => SwitcherInput::Commit,
+                    VK_LEFT | VK_UP => SwitcherInput::Step(-1),
+                    VK_RIGHT | VK_DOWN => SwitcherInput::Step(1),
+                    _ => return false,
+                };
+                if matches!(input, SwitcherInput::Cancel | SwitcherInput::Commit) {
+                    ALT_TAB_ACTIVE.store(false, Ordering::SeqCst);
+                }
+                WindowSwitcher::send(input);
+                true
+            }
+            WM_KEYUP | WM_SYSKEYUP if active && matches!(vk, VK_MENU | VK_LMENU | VK_RMENU) => {
+                ALT_TAB_ACTIVE.store(false, Ordering::SeqCst);
+                WindowSwitcher::send(SwitcherInput::Commit);
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns true if the Windows key release was swallowed to replace the start menu
+    fn on_win_key_event(message: u32, vk: u16) -> bool {
+        let is_win = vk == VK_LWIN.0 || vk == VK_RWIN.0;
+        match message {
+            WM_KEYDOWN | WM_SYSKEYDOWN => {
+                // the key being processed is not yet reflected on the async key state,
+                // so on key repeat the Windows key will appear as already pressed.
+                if !is_win {
+                    WIN_KEY_ALONE.store(false, Ordering::SeqCst);
+                } else if Modifiers::current() == Modifiers::default() {
+                    WIN_KEY_ALONE.store(true, Ordering::SeqCst);
+                }
+                false
+            }
+            WM_KEYUP | WM_SYSKEYUP if is_win => {
+                let alone = WIN_KEY_ALONE.swap(false, Ordering::SeqCst);
+                if !alone || !SUPPRESS_START_MENU.load(Ordering::SeqCst) {
+                    return false;
+                }
+                std::thread::spawn(move || {
+                    // the system still sees the Windows key as pressed so it is released after
+                    // a masking key, this way the start menu is not opened.
+                    Self::mask_modifier_key();
+                    Self::send_key_up(VIRTUAL_KEY(vk));
+                    log_error!(Self::execute(&KeybindingAction::ToggleLauncher));
+                });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns true if the volume or media key was handled by seelen
+    fn on_hardware_key_event(message: u32, vk: u16) -> bool {
+        if !GRAB_HARDWARE_KEYS.load(Ordering::SeqCst) {
+            return false;
+        }
+        match message {
+            WM_KEYDOWN | WM_SYSKEYDOWN => HardwareKeys::on_key_event(vk, true),
+            WM_KEYUP | WM_SYSKEYUP => HardwareKeys::on_key_event(vk, false),
+            _ => false,
+        }
+    }
+
+    /// Returns true if the chord was being waited to be diagnosed
+    fn on_diagnose(press: KeyPress) -> bool {
+        match trace_lock!(DIAGNOSE).take() {
+            Some(sender) => sender.send(press).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Waits for the next pressed chord and reports what it resolves to, the chord is swallowed
+    /// so its binding is not executed.
+    pub fn diagnose(timeout: Duration) -> Result<KeyChordDiagnostic> {
+        Self::ensure_hook()?;
+        let (sender, receiver) = channel();
+        *trace_lock!(DIAGNOSE) = Some(sender);
+        let received = receiver.recv_timeout(timeout);
+        trace_lock!(DIAGNOSE).take();
+        let press = received.map_err(|_| "No keys were pressed to be diagnosed")?;
+
+        let action = KEYMAP.load().find(&[press]).map(|b| b.action.clone());
+        let diagnostic = KeyChordDiagnostic::new(&press, action);
+        log::info!("Keys chord diagnostic: {:?}", diagnostic);
+        Ok(diagnostic)
+    }
+
+    /// Returns true if the key was consumed by a binding or by a pending sequence
+    fn on_key_down(press: KeyPress) -> bool {
+        let keymap = KEYMAP.load();
+        let mut pending = trace_lock!(PENDING);
+
+        let was_pending = !pending.is_empty();
+        let mut sequence = pending.clone();
+        sequence.push(press);
+
+        if let Some(binding) = keymap.find(&sequence) {
+            let action = binding.action.clone();
+            pending.clear();
+            PENDING_GENERATION.fetch_add(1, Ordering::SeqCst);
+            if was_pending {
+                Self::emit_hints(KeybindingsHints::default());
+            }
+            std::thread::spawn(move || {
+                if press.modifiers.win {
+                    Self::mask_modifier_key();
+                }
+                log_error!(Self::execute(&action));
+            });
+            return true;
+        }
+
+        if keymap.is_prefix(&sequence) {
+            Self::emit_hints(keymap.hints(&sequence));
+            *pending = sequence;
+
+            let generation = PENDING_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+            let timeout = keymap.timeout;
+            std::thread::spawn(move || {
+                if press.modifiers.win {
+                    Self::mask_modifier_key();
+                }
+                std::thread::sleep(timeout);
+                if PENDING_GENERATION.load(Ordering::SeqCst) == generation {
+                    Self::cancel_sequence();
+                }
+            });
+            return true;
+        }
+
+        // any other key cancels the pending sequence
+        if was_pending {
+            pending.clear();
+            PENDING_GENERATION.fetch_add(1, Ordering::SeqCst);
+            Self::emit_hints(KeybindingsHints::default());
+            return true;
+        }
+        false
+    }
+
+    fn cancel_sequence() {
+        let mut pending = trace_lock!(PENDING);
+        if !pending.is_empty() {
+            pending.clear();
+            PENDING_GENERATION.fetch_add(1, Ordering::SeqCst);
+            Self::emit_hints(KeybindingsHints::default());
+        }
+    }
+
+    fn emit_hints(hints: KeybindingsHints) {
+        // emitting is done outside the hook thread to keep the keyboard responsive
+        std::thread::spawn(move || {
+            log_error!(EventRegistry::emit(SeelenEvent::KeybindingsHints, hints));
+        });
+    }
+
+    /// Sends an unassigned key so releasing the Windows key doesn't open the start menu,
+    /// same for Alt and the menu bar of the focused app
+    fn mask_modifier_key() {
+        let input = |flags: KEYBD_EVENT_FLAGS| INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 {
+                ki: KEYBDINPUT {
+                    wVk: VK_NONE,
+                    dwFlags: flags,
+                    ..Default::default()
+                },
+            },
+        };
+        let inputs = [input(KEYBD_EVENT_FLAGS(0)), input(KEYEVENTF_KEYUP)];
+        unsafe { SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) };
+    }
+
+    fn send_key_up(vk: VIRTUAL_KEY) {
+        let inputs = [INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 {
+                ki: KEYBDINPUT {
+                    wVk: vk,
+                    dwFlags: KEYEVENTF_KEYUP,
+                    ..Default::default()
+                },
+            },
+        }];
+        unsafe { SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) };
+    }
+
+    pub fn execute(action: &KeybindingAction) -> Result<()> {
+        log::trace!("Executing keybinding action: {:?}", action);
+        let args: Vec<String> = match action {
+            KeybindingAction::ToggleLauncher => vec!["launcher".into(), "toggle".into()],
+            KeybindingAction::OpenSettings => vec!["settings".into()],
+            KeybindingAction::SwitchWorkspace(idx) => {
+                vec!["vd".into(), "switch-workspace".into(), idx.to_string()]
+            }
+            KeybindingAction::MoveToWorkspace(idx) => {
+                vec!["vd".into(), "move-to-workspace".into(), idx.to_string()]
+            }
+            KeybindingAction::SendToWorkspace(idx) => {
+                vec!["vd".into(), "send-to-workspace".into(), idx.to_string()]
+            }
+            KeybindingAction::SwitchNextWorkspace => vec!["vd".into(), "switch-next".into()],
+            KeybindingAction::SwitchPrevWorkspace => vec!["vd".into(), "switch-prev".into()],
+            KeybindingAction::WindowManager(args) => {
+                let mut cli = vec!["wm".to_string()];
+                cli.extend(args.iter().cloned());
+                cli
+            }
+            KeybindingAction::Cli(args) => args.clone(),
+            KeybindingAction::Action { id, params } => {
+                ActionCatalog::build().to_cli_args(id, params)?
+            }
+            KeybindingAction::Run { program, args } => {
+                get_app_handle()
+                    .shell()
+                    .command(program)
+                    .args(args)
+                    .spawn()?;
+                return Ok(());
+            }
+        };
+
+        let argv = std::iter::once("seelen-ui".to_string()).chain(args);
+        let command = trace_lock!(SEELEN_COMMAND_LINE).clone();
+        let matches = command
+            .try_get_matches_from(argv)
+            .map_err(|e| e.to_string())?;
+        handle_cli_events(&matches)
+    }
+}
@@ -0,0 +1,337 @@
+use serde::Serialize;
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    GetAsyncKeyState, VIRTUAL_KEY, VK_CONTROL, VK_LWIN, VK_MENU, VK_OEM_1, VK_OEM_2, VK_OEM_3,
+    VK_OEM_4, VK_OEM_5, VK_OEM_6, VK_OEM_7, VK_OEM_COMMA, VK_OEM_MINUS, VK_OEM_PERIOD, VK_OEM_PLUS,
+    VK_RWIN, VK_SHIFT,
+};
+
+use seelen_core::state::{KeyMatchMode, KeybindingAction};
+
+use crate::{error_handler::Result, modules::input::VIRTUAL_KEYS};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+    pub win: bool,
+}
+
+impl Modifiers {
+    /// Reads the modifiers currently held down on the keyboard
+    pub fn current() -> Self {
+        let is_down =
+            |vk: VIRTUAL_KEY| unsafe { (GetAsyncKeyState(vk.0 as i32) as u16 & 0x8000) != 0 };
+        Self {
+            ctrl: is_down(VK_CONTROL),
+            alt: is_down(VK_MENU),
+            shift: is_down(VK_SHIFT),
+            win: is_down(VK_LWIN) || is_down(VK_RWIN),
+        }
+    }
+
+    /// returns true if the key was a modifier and was applied
+    fn apply(&mut self, token: &str) -> bool {
+        match token {
+            "CTRL" | "CONTROL" | "LCTRL" | "LCONTROL" | "RCTRL" | "RCONTROL" => self.ctrl = true,
+            "ALT" | "MENU" | "LALT" | "LMENU" | "RALT" | "RMENU" => self.alt = true,
+            "SHIFT" | "LSHIFT" | "RSHIFT" => self.shift = true,
+            "WIN" | "WINDOWS" | "LWIN" | "LWINDOWS" | "RWIN" | "RWINDOWS" => self.win = true,
+            _ => return false,
+        }
+        true
+    }
+
+    pub fn is_modifier_key(vk: u16) -> bool {
+        // VK_SHIFT..=VK_MENU are 0x10..=0x12, VK_LSHIFT..=VK_RMENU are 0xA0..=0xA5
+        matches!(vk, 0x10..=0x12 | 0xA0..=0xA5) || vk == VK_LWIN.0 || vk == VK_RWIN.0
+    }
+
+    fn labels(&self) -> Vec<&'static str> {
+        [
+            (self.win, "Win"),
+            (self.ctrl, "Ctrl"),
+            (self.alt, "Alt"),
+            (self.shift, "Shift"),
+        ]
+        .into_iter()
+        .filter_map(|(held, label)| held.then_some(label))
+        .collect()
+    }
+}
+
+/// Set 1 scan codes of the character keys, named as on a US QWERTY keyboard
+const SCAN_CODE_ROWS: [(&str, u16); 4] = [
+    ("1234567890-=", 0x02),
+    ("QWERTYUIOP[]", 0x10),
+    ("ASDFGHJKL;'`", 0x1E),
+    ("ZXCVBNM,./", 0x2C),
+];
+const SCAN_CODE_BACKSLASH: u16 = 0x2B;
+/// flag added to the scan code of the extended keys, so they don't collide with the character keys
+pub const SCAN_CODE_EXTENDED: u16 = 0xE000;
+
+fn scan_code_of(c: char) -> Option<u16> {
+    if c == '\\' {
+        return Some(SCAN_CODE_BACKSLASH);
+    }
+    SCAN_CODE_ROWS.iter().find_map(|(row, first)| {
+        row.chars()
+            .position(|key| key == c)
+            .map(|idx| first + idx as u16)
+    })
+}
+
+/// Character of the physical key on a US QWERTY keyboard
+fn char_of_scan_code(scan: u16) -> Option<char> {
+    if scan == SCAN_CODE_BACKSLASH {
+        return Some('\\');
+    }
+    SCAN_CODE_ROWS.iter().find_map(|(row, first)| {
+        scan.checked_sub(*first)
+            .and_then(|idx| row.chars().nth(idx as usize))
+    })
+}
+
+/// A key pressed on the keyboard, as reported by the keyboard hook
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyPress {
+    pub modifiers: Modifiers,
+    pub vk: u16,
+    pub scan: u16,
+}
+
+/// A single keys combination like `Win + Shift + 1`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hotkey {
+    pub modifiers: Modifiers,
+    pub vk: u16,
+    /// if set the key is matched by its physical position instead of the virtual key
+    pub scan: Option<u16>,
+}
+
+impl Hotkey {
+    pub fn new(modifiers: Modifiers, vk: u16) -> Self {
+        Self {
+            modifiers,
+            vk,
+            scan: None,
+        }
+    }
+
+    pub fn matches(&self, press: &KeyPress) -> bool {
+        self.modifiers == press.modifiers
+            && match self.scan {
+                Some(scan) => scan == press.scan,
+                None => self.vk == press.vk,
+            }
+    }
+
+    fn key_from_token(token: &str) -> Option<u16> {
+        let mut chars = token.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            let vk = match c {
+                'A'..='Z' | '0'..='9' => VIRTUAL_KEY(c as u16),
+                '=' => VK_OEM_PLUS,
+                '-' => VK_OEM_MINUS,
+                ',' => VK_OEM_COMMA,
+                '.' => VK_OEM_PERIOD,
+                ';' => VK_OEM_1,
+                '/' => VK_OEM_2,
+                '`' => VK_OEM_3,
+                '[' => VK_OEM_4,
+                '\\' => VK_OEM_5,
+                ']' => VK_OEM_6,
+                '\'' => VK_OEM_7,
+                _ => return None,
+            };
+            return Some(vk.0);
+        }
+        VIRTUAL_KEYS.get(token).map(|vk| vk.0)
+    }
+
+    /// Inverse of `key_from_token`, used to show the pressed keys
+    fn key_name(vk: u16) -> String {
+        if let Some(c) = char::from_u32(vk as u32).filter(|c| c.is_ascii_alphanumeric()) {
+            return c.to_string();
+        }
+        if let Some(c) = "=-,.;/`[\\]'"
+            .chars()
+            .find(|c| Self::key_from_token(&c.to_string()) == Some(vk))
+        {
+            return c.to_string();
+        }
+        VIRTUAL_KEYS
+            .entries()
+            .find(|(_, key)| key.0 == vk)
+            .map(|(name, _)| name.to_string())
+            .unwrap_or_else(|| format!("0x{:02X}", vk))
+    }
+
+    /// Parses every step of a multi-step binding
+    pub fn parse_sequence(steps: &[&str], mode: KeyMatchMode) -> Result<Vec<Self>> {
+        steps
+            .iter()
+            .map(|step| Self::parse_as(step, mode))
+            .collect()
+    }
+
+    /// Parses a combination with the same format used on the settings, example: `Win + Shift + 1`
+    pub fn parse(keys: &str) -> Result<Self> {
+        Self::parse_as(keys, KeyMatchMode::VirtualKey)
+    }
+
+    /// On scan code mode only the character keys are matched by position, named keys like
+    /// `Space` or `F1` are the same on every layout.
+    pub fn parse_as(keys: &str, mode: KeyMatchMode) -> Result<Self> {
+        let mut modifiers = Modifiers::default();
+        let mut key = None;
+        let mut scan = None;
+
+        for token in keys.split('+').map(|t| t.trim().to_uppercase()) {
+            if token.is_empty() {
+                return Err(format!("Invalid keys combination: {}", keys).into());
+            }
+            if modifiers.apply(&token) {
+                continue;
+            }
+            if key.is_some() {
+                return Err(format!("Only one non-modifier key is allowed: {}", keys).into());
+            }
+            if mode == KeyMatchMode::ScanCode {
+                let mut chars = token.chars();
+                if let (Some(c), None) = (chars.next(), chars.next()) {
+                    scan = scan_code_of(c);
+                }
+            }
+            key = Some(
+                Self::key_from_token(&token)
+                    .ok_or_else(|| format!("Unknown key \"{}\" in: {}", token, keys))?,
+            );
+        }
+
+        match key {
+            Some(vk) => Ok(Self {
+                scan,
+                ..Self::new(modifiers, vk)
+            }),
+            None => Err(format!("Missing non-modifier key in: {}", keys).into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeybindingConflict {
+    pub keys: String,
+    pub actions: Vec<KeybindingAction>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeybindingHint {
+    pub keys: String,
+    /// None if the key leads to another step of the sequence
+    pub action: Option<KeybindingAction>,
+}
+
+/// Payload used by the frontend to show which keys are available next on a multi-step binding
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeybindingsHints {
+    /// already pressed steps, empty when the sequence was completed or cancelled
+    pub pressed: Vec<String>,
+    pub next: Vec<KeybindingHint>,
+}
+
+/// What a pressed chord resolves to, used to debug bindings on non US layouts
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyChordDiagnostic {
+    /// keys as named on the current keyboard layout, to be used on virtual key bindings
+    pub keys: String,
+    /// keys as named on a US QWERTY keyboard, to be used on scan code bindings
+    pub physical_keys: Option<String>,
+    pub vk: u16,
+    pub scan_code: u16,
+    /// action of the binding matching the chord, if any
+    pub action: Option<KeybindingAction>,
+}
+
+impl KeyChordDiagnostic {
+    pub fn new(press: &KeyPress, action: Option<KeybindingAction>) -> Self {
+        let join = |key: String| {
+            let mut labels: Vec<String> = press
+                .modifiers
+                .labels()
+                .into_iter()
+                .map(String::from)
+                .collect();
+            labels.push(key);
+            labels.join(" + ")
+        };
+        Self {
+            keys: join(Hotkey::key_name(press.vk)),
+            physical_keys: char_of_scan_code(press.scan).map(|c| join(c.to_string())),
+            vk: press.vk,
+            scan_code: press.scan,
+            action,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hotkey() -> Result<()> {
+        let hotkey = Hotkey::parse("Win + Shift + 1")?;
+        assert!(hotkey.modifiers.win && hotkey.modifiers.shift);
+        assert!(!hotkey.modifiers.ctrl && !hotkey.modifiers.alt);
+        assert_eq!(hotkey.vk, '1' as u16);
+
+        assert_eq!(Hotkey::parse("alt+=")?, Hotkey::parse("Alt + =")?);
+        assert_eq!(Hotkey::parse("Win + Space")?.vk, 0x20);
+
+        assert!(Hotkey::parse("Win + Shift").is_err());
+        assert!(Hotkey::parse("Win + A + B").is_err());
+        assert!(Hotkey::parse("Win + Unknown").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_scan_codes() {
+        assert_eq!(scan_code_of('Q'), Some(0x10));
+        assert_eq!(scan_code_of('A'), Some(0x1E));
+        assert_eq!(scan_code_of('/'), Some(0x35));
+        assert_eq!(scan_code_of('\\'), Some(0x2B));
+        for c in "1234567890-=QWERTYUIOP[]ASDFGHJKL;'`ZXCVBNM,./\\".chars() {
+            assert_eq!(scan_code_of(c).and_then(char_of_scan_code), Some(c));
+        }
+        assert_eq!(char_of_scan_code(0x39), None);
+        assert_eq!(char_of_scan_code(0x1E | SCAN_CODE_EXTENDED), None);
+    }
+
+    #[test]
+    fn test_parse_hotkey_as_scan_code() -> Result<()> {
+        let hotkey = Hotkey::parse_as("Win + Q", KeyMatchMode::ScanCode)?;
+        assert_eq!(hotkey.scan, Some(0x10));
+        assert_eq!(
+            Hotkey::parse_as("Win + Space", KeyMatchMode::ScanCode)?.scan,
+            None
+        );
+        assert_eq!(Hotkey::parse("Win + Q")?.scan, None);
+
+        // Q is labeled as A on AZERTY keyboards, the key at the same position matches
+        let press = |vk: char, scan| KeyPress {
+            modifiers: hotkey.modifiers,
+            vk: vk as u16,
+            scan,
+        };
+        assert!(hotkey.matches(&press('A', 0x10)));
+        assert!(!hotkey.matches(&press('Q', 0x1E)));
+        assert!(Hotkey::parse("Win + Q")?.matches(&press('Q', 0x1E)));
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+use crate::{error_handler::Result, trace_lock};
+
+use super::{
+    application::{KeybindingsManager, KEYBINDINGS_CONFLICTS},
+    domain::{KeyChordDiagnostic, KeybindingConflict},
+};
+
+/// max time to wait for the chord to be pressed
+const DIAGNOSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[tauri::command(async)]
+pub fn get_keybindings_conflicts() -> Result<Vec<KeybindingConflict>> {
+    Ok(trace_lock!(KEYBINDINGS_CONFLICTS).clone())
+}
+
+#[tauri::command(async)]
+pub fn keybindings_diagnose() -> Result<KeyChordDiagnostic> {
+    KeybindingsManager::diagnose(DIAGNOSE_TIMEOUT)
+}
@@ -0,0 +1,8 @@
+use seelen_core::system_state::LayoutDirectionState;
+
+use super::LayoutDirection;
+
+#[tauri::command(async)]
+pub fn get_layout_direction() -> LayoutDirectionState {
+    LayoutDirection::state()
+}
@@ -0,0 +1,43 @@
+pub mod infrastructure;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use seelen_core::{handlers::SeelenEvent, system_state::LayoutDirectionState};
+
+use crate::{
+    error_handler::Result, modules::event_registry::EventRegistry, state::application::FULL_STATE,
+};
+
+static RTL: AtomicBool = AtomicBool::new(false);
+
+/// Resolves the direction of the layout from the settings, the surfaces follow it and the
+/// window manager uses it to mirror the directional commands if the user asked for it.
+pub struct LayoutDirection;
+
+impl LayoutDirection {
+    pub fn state() -> LayoutDirectionState {
+        LayoutDirectionState {
+            rtl: RTL.load(Ordering::Acquire),
+        }
+    }
+
+    /// Should be called when the settings change, only emits if the direction changed
+    pub fn refresh() -> Result<()> {
+        let rtl = FULL_STATE.load().settings.is_rtl();
+        if RTL.swap(rtl, Ordering::AcqRel) == rtl {
+            return Ok(());
+        }
+        log::trace!("Layout direction changed, rtl: {}", rtl);
+        EventRegistry::emit(SeelenEvent::LayoutDirectionChanged, Self::state())
+    }
+
+    /// Left and right are swapped on the directional commands of the window manager
+    pub fn mirror_wm_directions() -> bool {
+        RTL.load(Ordering::Acquire)
+            && FULL_STATE
+                .load()
+                .settings
+                .window_manager
+                .mirror_directions_on_rtl
+    }
+}
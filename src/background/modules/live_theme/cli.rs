@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+use clap::Command;
+
+use crate::{error_handler::Result, get_subcommands};
+
+use super::{LiveTheme, TARGETS};
+
+get_subcommands![
+    /** Injects css over the loaded themes on the target (weg, toolbar, wm, launcher or wall). */
+    Inject(
+        target: String => "Target where the css will be injected.",
+        css: String => "Css to inject, empty to remove it."
+    ),
+    /** Injects the theme.<target>.css files of the folder and again each time they change. */
+    Watch(folder: PathBuf => "Absolute path of the folder to watch."),
+    /** Stops watching the folder, the injected css is kept. */
+    Unwatch,
+    /** Removes all the injected css and stops watching. */
+    Clear,
+];
+
+impl LiveTheme {
+    pub const CLI_IDENTIFIER: &'static str = "theme";
+
+    pub fn get_cli() -> Command {
+        Command::new(Self::CLI_IDENTIFIER)
+            .about(
+                "Live css injection for theme development, requires the developer tools enabled.",
+            )
+            .after_help(format!("Targets: {}", TARGETS.join(", ")))
+            .arg_required_else_help(true)
+            .subcommands(SubCommand::commands())
+    }
+
+    pub fn process(matches: &clap::ArgMatches) -> Result<()> {
+        let subcommand = SubCommand::try_from(matches)?;
+        match subcommand {
+            SubCommand::Inject(target, css) => Self::inject(&target, css)?,
+            SubCommand::Watch(folder) => Self::watch(folder)?,
+            SubCommand::Unwatch => Self::unwatch(),
+            SubCommand::Clear => Self::clear()?,
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,16 @@
+use seelen_core::state::ThemeCss;
+
+use crate::error_handler::Result;
+
+use super::LiveTheme;
+
+#[tauri::command(async)]
+pub fn live_theme_get_css() -> ThemeCss {
+    LiveTheme::css()
+}
+
+/// Used by the developer tools to preview css without writing theme files
+#[tauri::command(async)]
+pub fn live_theme_inject_css(target: String, css: String) -> Result<()> {
+    LiveTheme::inject(&target, css)
+}
@@ -0,0 +1,133 @@
+mod cli;
+pub mod infrastructure;
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use lazy_static::lazy_static;
+use notify_debouncer_full::{
+    new_debouncer,
+    notify::{ReadDirectoryChangesWatcher, RecursiveMode, Watcher},
+    DebounceEventResult, Debouncer, FileIdMap,
+};
+use parking_lot::Mutex;
+use seelen_core::{handlers::SeelenEvent, state::ThemeCss};
+
+use crate::{
+    error_handler::Result, log_error, modules::event_registry::EventRegistry,
+    state::application::FULL_STATE, trace_lock,
+};
+
+/// editors write files in several steps, so changes are grouped
+const DEBOUNCE_TIME: Duration = Duration::from_millis(100);
+
+/// surfaces that can be styled, same names used by `ThemeCss` and the theme folders
+pub const TARGETS: [&str; 5] = ["weg", "toolbar", "wm", "launcher", "wall"];
+
+lazy_static! {
+    static ref CSS: Mutex<ThemeCss> = Mutex::new(ThemeCss::default());
+    static ref WATCHER: Mutex<Option<Debouncer<ReadDirectoryChangesWatcher, FileIdMap>>> =
+        Mutex::new(None);
+}
+
+/// Css injected over the loaded themes without writing files, a fast feedback loop
+/// for theme developers. Only available while the developer tools are enabled.
+pub struct LiveTheme;
+
+impl LiveTheme {
+    fn ensure_enabled() -> Result<()> {
+        if !FULL_STATE.load().settings.dev_tools {
+            return Err("Live theme css requires the developer tools to be enabled".into());
+        }
+        Ok(())
+    }
+
+    fn target_mut<'a>(css: &'a mut ThemeCss, target: &str) -> Result<&'a mut String> {
+        Ok(match target {
+            "weg" => &mut css.weg,
+            "toolbar" => &mut css.toolbar,
+            "wm" => &mut css.wm,
+            "launcher" => &mut css.launcher,
+            "wall" => &mut css.wall,
+            _ => {
+                return Err(format!(
+                    "Unknown target: {}, expected one of {}",
+                    target,
+                    TARGETS.join(", ")
+                )
+                .into())
+            }
+        })
+    }
+
+    pub fn css() -> ThemeCss {
+        trace_lock!(CSS).clone()
+    }
+
+    fn set_css(css: ThemeCss) -> Result<()> {
+        *trace_lock!(CSS) = css.clone();
+        EventRegistry::emit(SeelenEvent::LiveThemeCssChanged, css)?;
+        Ok(())
+    }
+
+    /// Replaces the live css of the target, an empty css removes it
+    pub fn inject(target: &str, css: String) -> Result<()> {
+        Self::ensure_enabled()?;
+        let mut current = Self::css();
+        *Self::target_mut(&mut current, target)? = css;
+        Self::set_css(current)
+    }
+
+    /// Removes the css of all the targets and stops watching
+    pub fn clear() -> Result<()> {
+        trace_lock!(WATCHER).take();
+        Self::set_css(ThemeCss::default())
+    }
+
+    /// Reads `theme.<target>.css` files of the folder, the same layout of the theme folders
+    fn load_folder(folder: &Path) -> Result<()> {
+        let mut css = ThemeCss::default();
+        for target in TARGETS {
+            let path = folder.join(format!("theme.{}.css", target));
+            if path.exists() {
+                *Self::target_mut(&mut css, target)? = std::fs::read_to_string(path)?;
+            }
+        }
+        Self::set_css(css)
+    }
+
+    /// Injects the css files of the folder and again each time they change
+    pub fn watch(folder: PathBuf) -> Result<()> {
+        Self::ensure_enabled()?;
+        // the command can come from another process, so relative paths are ambiguous
+        if !folder.is_absolute() || !folder.is_dir() {
+            return Err(format!("{} is not an absolute path to a folder", folder.display()).into());
+        }
+
+        Self::load_folder(&folder)?;
+        let watched = folder.clone();
+        let mut debouncer = new_debouncer(
+            DEBOUNCE_TIME,
+            None,
+            move |result: DebounceEventResult| match result {
+                Ok(_) => log_error!(Self::load_folder(&watched)),
+                Err(errors) => errors
+                    .iter()
+                    .for_each(|e| log::error!("Live Theme Watcher Error: {:?}", e)),
+            },
+        )?;
+        debouncer
+            .watcher()
+            .watch(&folder, RecursiveMode::NonRecursive)?;
+        // replacing the previous debouncer stops it
+        *trace_lock!(WATCHER) = Some(debouncer);
+        log::info!("Live theme watching {}", folder.display());
+        Ok(())
+    }
+
+    pub fn unwatch() {
+        trace_lock!(WATCHER).take();
+    }
+}
@@ -0,0 +1,247 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use base64::Engine;
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use minisign_verify::{PublicKey, Signature};
+use seelen_core::{
+    state::{InstalledResources, MarketplaceEntry, MarketplaceIndex, MarketplaceResourceKind},
+    system_state::RestorePointReason,
+};
+use sha2::{Digest, Sha256};
+use tauri::Manager;
+use tauri_plugin_http::reqwest;
+
+use crate::{
+    error_handler::Result,
+    modules::{cli::application::decode_slu, restore_points::RestorePoints},
+    seelen::get_app_handle,
+    state::application::FULL_STATE,
+};
+
+lazy_static! {
+    static ref MARKETPLACE_DIR: PathBuf = get_app_handle()
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data dir")
+        .join("marketplace");
+    static ref INDEX_PATH: PathBuf = MARKETPLACE_DIR.join("index.json");
+    static ref INDEX_SIGNATURE_PATH: PathBuf = MARKETPLACE_DIR.join("index.json.sig");
+    static ref INSTALLED_PATH: PathBuf = MARKETPLACE_DIR.join("installed.json");
+}
+
+/// Client of the remote resources index, the index is cached on disk and used as
+/// fallback when offline. The index is signed and the downloaded resources are verified
+/// against its checksums before being installed.
+pub struct Marketplace;
+
+impl Marketplace {
+    const INDEX_URL: &'static str =
+        "https://raw.githubusercontent.com/Seelen-Inc/slu-marketplace/refs/heads/main/index.json";
+    const INDEX_SIGNATURE_URL: &'static str =
+        "https://raw.githubusercontent.com/Seelen-Inc/slu-marketplace/refs/heads/main/index.json.sig";
+    /// Same key used to sign the app updates, the index is signed with `tauri signer sign`
+    const PUBLIC_KEY: &'static str = "dW50cnVzdGVkIGNvbW1lbnQ6IG1pbmlzaWduIHB1YmxpYyBrZXk6IDQ4QjU1RUI0NEM0NzBERUIKUldUckRVZE10RjYxU0lpaERvdklYL05DVlg0Sk9EVngvaEgzZjMvU1NNemJTZXZ1K0dNVXU3ZkQK";
+    const CACHE_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+    fn is_cache_fresh() -> bool {
+        std::fs::metadata(INDEX_PATH.as_path())
+            .and_then(|meta| meta.modified())
+            .is_ok_and(|modified| {
+                SystemTime::now()
+                    .duration_since(modified)
+                    .is_ok_and(|age| age < Self::CACHE_TTL)
+            })
+    }
+
+    /// Keys and signatures are base64 encoded minisign files, like the ones of the updater
+    fn decode_minisign(encoded: &[u8]) -> Result<String> {
+        let decoded = base64::engine::general_purpose::STANDARD.decode(encoded.trim_ascii())?;
+        Ok(String::from_utf8(decoded)?)
+    }
+
+    /// Fails if the index was not signed by the Seelen key
+    fn verify_index(index: &[u8], signature: &[u8]) -> Result<()> {
+        let public_key = PublicKey::decode(&Self::decode_minisign(Self::PUBLIC_KEY.as_bytes())?)?;
+        let signature = Signature::decode(&Self::decode_minisign(signature)?)?;
+        public_key.verify(index, &signature, true)?;
+        Ok(())
+    }
+
+    /// The cache is verified too, it is only a copy of the downloaded index
+    fn read_cache() -> Result<MarketplaceIndex> {
+        let bytes = std::fs::read(INDEX_PATH.as_path())?;
+        Self::verify_index(&bytes, &std::fs::read(INDEX_SIGNATURE_PATH.as_path())?)?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    async fn download_index() -> Result<(Vec<u8>, Vec<u8>)> {
+        let bytes = Self::download(Self::INDEX_URL).await?;
+        let signature = Self::download(Self::INDEX_SIGNATURE_URL).await?;
+        Self::verify_index(&bytes, &signature)?;
+        Ok((bytes, signature))
+    }
+
+    async fn download(url: &str) -> Result<Vec<u8>> {
+        let response = reqwest::Client::new()
+            .get(url)
+            .timeout(Self::REQUEST_TIMEOUT)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Returns the cached index if fresh enough, otherwise fetches it
+    pub async fn index(force_refresh: bool) -> Result<MarketplaceIndex> {
+        if !force_refresh && Self::is_cache_fresh() {
+            if let Ok(index) = Self::read_cache() {
+                return Ok(index);
+            }
+        }
+
+        match Self::download_index().await {
+            Ok((bytes, signature)) => {
+                let index: MarketplaceIndex = serde_json::from_slice(&bytes)?;
+                std::fs::create_dir_all(MARKETPLACE_DIR.as_path())?;
+                std::fs::write(INDEX_PATH.as_path(), &bytes)?;
+                std::fs::write(INDEX_SIGNATURE_PATH.as_path(), &signature)?;
+                Ok(index)
+            }
+            Err(err) => {
+                log::warn!("Failed to fetch marketplace index, using cache: {}", err);
+                Self::read_cache().map_err(|_| err)
+            }
+        }
+    }
+
+    pub fn installed() -> Result<InstalledResources> {
+        if !INSTALLED_PATH.exists() {
+            return Ok(InstalledResources::new());
+        }
+        Ok(serde_json::from_str(&std::fs::read_to_string(
+            INSTALLED_PATH.as_path(),
+        )?)?)
+    }
+
+    fn save_installed(installed: &InstalledResources) -> Result<()> {
+        std::fs::create_dir_all(MARKETPLACE_DIR.as_path())?;
+        std::fs::write(
+            INSTALLED_PATH.as_path(),
+            serde_json::to_string_pretty(installed)?,
+        )?;
+        Ok(())
+    }
+
+    /// ids are used as filenames so only a safe subset of characters is allowed
+    fn is_valid_id(id: &str) -> bool {
+        !id.is_empty()
+            && !id.starts_with('.')
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    }
+
+    pub async fn search(
+        query: &str,
+        kind: Option<MarketplaceResourceKind>,
+    ) -> Result<Vec<MarketplaceEntry>> {
+        let query = query.trim().to_lowercase();
+        let index = Self::index(false).await?;
+        Ok(index
+            .resources
+            .into_iter()
+            .filter(|entry| kind.is_none() || kind == Some(entry.kind))
+            .filter(|entry| {
+                query.is_empty()
+                    || entry.info.display_name.to_lowercase().contains(&query)
+                    || entry.info.description.to_lowercase().contains(&query)
+                    || entry.info.author.to_lowercase().contains(&query)
+                    || entry.info.tags.iter().any(|t| t.to_lowercase() == query)
+            })
+            .sorted_by(|a, b| {
+                b.rating
+                    .total_cmp(&a.rating)
+                    .then(b.downloads.cmp(&a.downloads))
+            })
+            .collect_vec())
+    }
+
+    pub async fn install(id: &str) -> Result<()> {
+        let index = Self::index(false).await?;
+        let entry = index
+            .resources
+            .into_iter()
+            .find(|entry| entry.id == id)
+            .ok_or(format!("Resource not found on marketplace: {}", id))?;
+        Self::install_entry(entry).await
+    }
+
+    async fn install_entry(entry: MarketplaceEntry) -> Result<()> {
+        if !Self::is_valid_id(&entry.id) {
+            return Err(format!("Invalid resource id: {}", entry.id).into());
+        }
+
+        log::trace!(
+            "Installing {} v{} from marketplace",
+            entry.id,
+            entry.version
+        );
+        let bytes = Self::download(&entry.url).await?;
+        // the entry comes from the signed index, so the checksum can be trusted
+        let checksum = format!("{:x}", Sha256::digest(&bytes));
+        if !checksum.eq_ignore_ascii_case(&entry.sha256) {
+            return Err(format!("Checksum mismatch for {}, download discarded", entry.id).into());
+        }
+
+        let mut resource = decode_slu(&bytes)?;
+        // the index id is the trusted one
+        resource.id = entry.id.clone();
+
+        RestorePoints::create_before(RestorePointReason::Marketplace, &entry.id);
+        let mut state = FULL_STATE.load().cloned();
+        state.load_resource(resource)?;
+        state.store();
+
+        let mut installed = Self::installed()?;
+        installed.insert(entry.id, entry.version);
+        Self::save_installed(&installed)?;
+        Ok(())
+    }
+
+    /// Installed resources with a different version available on the marketplace
+    pub async fn check_updates() -> Result<Vec<MarketplaceEntry>> {
+        let installed = Self::installed()?;
+        if installed.is_empty() {
+            return Ok(Vec::new());
+        }
+        let index = Self::index(true).await?;
+        Ok(index
+            .resources
+            .into_iter()
+            .filter(|entry| {
+                installed
+                    .get(&entry.id)
+                    .is_some_and(|version| *version != entry.version)
+            })
+            .collect_vec())
+    }
+
+    /// Updates the given resource or all of them if `None`, returns the updated ids
+    pub async fn update(id: Option<&str>) -> Result<Vec<String>> {
+        let mut updated = Vec::new();
+        for entry in Self::check_updates().await? {
+            if id.is_some_and(|id| id != entry.id) {
+                continue;
+            }
+            let entry_id = entry.id.clone();
+            Self::install_entry(entry).await?;
+            updated.push(entry_id);
+        }
+        Ok(updated)
+    }
+}
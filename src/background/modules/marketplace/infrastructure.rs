@@ -0,0 +1,34 @@
+use seelen_core::state::{MarketplaceEntry, MarketplaceResourceKind};
+
+use crate::error_handler::Result;
+
+use super::application::Marketplace;
+
+#[tauri::command(async)]
+pub async fn marketplace_search(
+    query: Option<String>,
+    kind: Option<MarketplaceResourceKind>,
+) -> Result<Vec<MarketplaceEntry>> {
+    Marketplace::search(query.as_deref().unwrap_or_default(), kind).await
+}
+
+#[tauri::command(async)]
+pub async fn marketplace_refresh() -> Result<()> {
+    Marketplace::index(true).await?;
+    Ok(())
+}
+
+#[tauri::command(async)]
+pub async fn marketplace_install(id: String) -> Result<()> {
+    Marketplace::install(&id).await
+}
+
+#[tauri::command(async)]
+pub async fn marketplace_check_updates() -> Result<Vec<MarketplaceEntry>> {
+    Marketplace::check_updates().await
+}
+
+#[tauri::command(async)]
+pub async fn marketplace_update(id: Option<String>) -> Result<Vec<String>> {
+    Marketplace::update(id.as_deref()).await
+}
@@ -35,7 +35,7 @@ use windows_core::Interface;
 use crate::{
     error_handler::Result,
     log_error,
-    seelen_weg::icon_extractor::{extract_and_save_icon_from_file, extract_and_save_icon_umid},
+    modules::icon_cache::IconCache,
     trace_lock,
     utils::pcwstr,
     windows_api::{Com, WindowEnumerator, WindowsApi},
@@ -476,7 +476,7 @@ impl MediaManager {
                             .to_string()?,
                     }
                     .replace(".exe", "");
-                    icon_path = extract_and_save_icon_from_file(&path)
+                    icon_path = IconCache::of_file(&path)
                         .ok()
                         .map(|p| p.to_string_lossy().to_string());
                 }
@@ -581,8 +581,8 @@ impl MediaManager {
                     .unwrap_or_else(|_| "Unknown App".to_string()),
                 icon_path: w
                     .app_user_model_id()
-                    .and_then(|umid| extract_and_save_icon_umid(&umid).ok())
-                    .or_else(|| w.exe().and_then(extract_and_save_icon_from_file).ok()),
+                    .and_then(|umid| IconCache::of_umid(&umid).ok())
+                    .or_else(|| w.exe().and_then(IconCache::of_file).ok()),
             }),
             thumbnail: properties
                 .Thumbnail()
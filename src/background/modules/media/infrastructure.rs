@@ -1,27 +1,22 @@
 use seelen_core::handlers::SeelenEvent;
 use std::sync::atomic::{AtomicBool, Ordering};
-use tauri::Emitter;
 use windows::core::GUID;
 
 use crate::{
-    error_handler::Result, modules::media::application::MEDIA_MANAGER, seelen::get_app_handle,
+    error_handler::Result,
+    modules::{event_registry::EventRegistry, media::application::MEDIA_MANAGER},
     trace_lock,
 };
 
 use super::domain::{Device, MediaPlayer};
 
 fn emit_media_sessions(playing: &Vec<MediaPlayer>) {
-    let app = get_app_handle();
-    app.emit(SeelenEvent::MediaSessions, playing)
-        .expect("failed to emit");
+    EventRegistry::emit(SeelenEvent::MediaSessions, playing).expect("failed to emit");
 }
 
 fn emit_media_devices(inputs: &Vec<Device>, outputs: &Vec<Device>) {
-    let app = get_app_handle();
-    app.emit(SeelenEvent::MediaInputs, inputs)
-        .expect("failed to emit");
-    app.emit(SeelenEvent::MediaOutputs, outputs)
-        .expect("failed to emit");
+    EventRegistry::emit(SeelenEvent::MediaInputs, inputs).expect("failed to emit");
+    EventRegistry::emit(SeelenEvent::MediaOutputs, outputs).expect("failed to emit");
 }
 
 static REGISTERED: AtomicBool = AtomicBool::new(false);
@@ -48,6 +43,15 @@ pub fn release_media_events() {
     }
 }
 
+/// Id, volume and mute state of the default multimedia output device
+pub fn default_output() -> Option<(String, f32, bool)> {
+    trace_lock!(MEDIA_MANAGER)
+        .outputs()
+        .iter()
+        .find(|device| device.is_default_multimedia)
+        .map(|device| (device.id.clone(), device.volume, device.muted))
+}
+
 #[tauri::command(async)]
 pub fn media_set_default_device(id: String, role: String) -> Result<()> {
     trace_lock!(MEDIA_MANAGER).set_default_device(&id, &role)?;
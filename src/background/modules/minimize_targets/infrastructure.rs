@@ -0,0 +1,17 @@
+use std::collections::HashMap;
+
+use seelen_core::rect::Rect;
+use tauri::WebviewWindow;
+use windows::Win32::Foundation::RECT;
+
+use super::MinimizeTargets;
+
+/// Receives the rects of the dock items by window handle, in physical pixels
+#[tauri::command(async)]
+pub fn weg_set_minimize_targets(window: WebviewWindow, targets: HashMap<isize, Rect>) {
+    let targets = targets
+        .into_iter()
+        .map(|(hwnd, rect)| (hwnd, RECT::from(rect)))
+        .collect();
+    MinimizeTargets::set(window.label(), targets);
+}
@@ -0,0 +1,148 @@
+pub mod infrastructure;
+
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use windows::{
+    core::w,
+    Win32::{
+        Foundation::{HWND, LPARAM, LRESULT, RECT, WPARAM},
+        UI::{
+            Shell::SHELLHOOKINFO,
+            WindowsAndMessaging::{
+                CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW,
+                RegisterShellHookWindow, RegisterWindowMessageW, TranslateMessage,
+                HSHELL_GETMINRECT, MSG, WINDOW_EX_STYLE, WINDOW_STYLE, WNDCLASSW,
+            },
+        },
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    seelen_wm_v2::geometry::{monitor_for_rect, MonitorGeometry},
+    state::application::FULL_STATE,
+    trace_lock,
+    utils::spawn_named_thread,
+    windows_api::WindowsApi,
+};
+
+/// registered message used by the system to notify the shell hook windows
+static SHELLHOOK: AtomicU32 = AtomicU32::new(0);
+
+lazy_static! {
+    /// webview label -> window handle -> rect of its dock item in physical pixels
+    static ref TARGETS: Mutex<HashMap<String, HashMap<isize, RECT>>> = Mutex::new(HashMap::new());
+}
+
+/// Makes the minimize animations go to the dock items instead of the hidden native taskbar
+pub struct MinimizeTargets;
+
+impl MinimizeTargets {
+    pub fn set(label: &str, targets: HashMap<isize, RECT>) {
+        trace_lock!(TARGETS).insert(label.to_string(), targets);
+    }
+
+    /// The dock on the same monitor of the window is preferred if the app is on many docks
+    fn target_of(hwnd: HWND) -> Option<RECT> {
+        let candidates: Vec<RECT> = trace_lock!(TARGETS)
+            .values()
+            .filter_map(|targets| targets.get(&(hwnd.0 as isize)).copied())
+            .collect();
+        let monitor = WindowsApi::monitor_from_window(hwnd).0 as isize;
+        let monitors = MonitorGeometry::all().unwrap_or_default();
+        candidates
+            .iter()
+            .find(|rect| monitor_for_rect(rect, &monitors).is_some_and(|m| m.handle == monitor))
+            .or(candidates.first())
+            .copied()
+    }
+
+    /// The native taskbar keeps answering on the monitors where it is visible
+    fn should_answer(hwnd: HWND) -> bool {
+        let monitor = WindowsApi::monitor_from_window(hwnd);
+        match WindowsApi::monitor_index(monitor) {
+            Ok(idx) => FULL_STATE.load().should_hide_native_taskbar_on_monitor(idx),
+            Err(_) => false,
+        }
+    }
+
+    unsafe extern "system" fn window_proc(
+        window: HWND,
+        message: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        let shellhook = SHELLHOOK.load(Ordering::Relaxed);
+        if shellhook != 0
+            && message == shellhook
+            && wparam.0 as u32 == HSHELL_GETMINRECT
+            && lparam.0 != 0
+        {
+            let info = &mut *(lparam.0 as *mut SHELLHOOKINFO);
+            if Self::should_answer(info.hwnd) {
+                if let Some(rect) = Self::target_of(info.hwnd) {
+                    info.rc = rect;
+                    return LRESULT(1);
+                }
+            }
+            return LRESULT(0);
+        }
+        DefWindowProcW(window, message, wparam, lparam)
+    }
+
+    unsafe fn create_background_window() -> Result<()> {
+        let wide_class = w!("SeelenMinimizeTargets");
+        let h_module = WindowsApi::module_handle_w()?;
+
+        let wnd_class = WNDCLASSW {
+            lpfnWndProc: Some(Self::window_proc),
+            hInstance: h_module.into(),
+            lpszClassName: wide_class,
+            ..Default::default()
+        };
+
+        RegisterClassW(&wnd_class);
+
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            wide_class,
+            w!("Seelen Minimize Targets"),
+            WINDOW_STYLE::default(),
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            h_module,
+            None,
+        )?;
+
+        let shellhook = RegisterWindowMessageW(w!("SHELLHOOK"));
+        if shellhook == 0 {
+            return Err("Failed to register SHELLHOOK message".into());
+        }
+        SHELLHOOK.store(shellhook, Ordering::SeqCst);
+        RegisterShellHookWindow(hwnd).ok()?;
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, hwnd, 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+        Ok(())
+    }
+
+    pub fn start() -> Result<()> {
+        spawn_named_thread("Minimize Targets", || unsafe {
+            log_error!(Self::create_background_window());
+        })?;
+        Ok(())
+    }
+}
@@ -1,11 +1,72 @@
+pub mod accessibility;
+pub mod actions;
+pub mod always_on_top;
+pub mod app_usage;
+pub mod audio_policy;
+pub mod bluetooth;
+pub mod break_reminder;
+pub mod brightness;
+pub mod capture;
 pub mod cli;
+pub mod clock;
+pub mod color_picker;
+pub mod crash_guard;
+pub mod daily_wallpaper;
+pub mod diagnostics;
+pub mod display;
+pub mod emit_scheduler;
+pub mod event_registry;
+pub mod explorer;
+pub mod focus_history;
+pub mod hardware_keys;
+pub mod icon_cache;
+pub mod idle_actions;
+pub mod ime;
 pub mod input;
+pub mod ipc;
+pub mod keybindings;
+pub mod layout_direction;
+pub mod live_theme;
+pub mod marketplace;
 pub mod media;
+pub mod minimize_targets;
 pub mod monitors;
 pub mod network;
+pub mod night_light;
+pub mod notes;
 pub mod notifications;
+pub mod onboarding;
+pub mod perf;
+pub mod picture_in_picture;
 pub mod power;
+pub mod printers;
+pub mod private_session;
+pub mod processes;
+pub mod quick_settings;
+pub mod recycle_bin;
+pub mod removable_drives;
+pub mod resource_governor;
+pub mod restore_points;
+pub mod scripting;
+pub mod session;
+pub mod settings_search;
+pub mod shortcuts;
+pub mod start_menu;
+pub mod startup;
+pub mod storage;
+pub mod supervisor;
 pub mod system_settings;
+pub mod taskbar;
+pub mod titlebar;
 pub mod tray;
+pub mod triggers;
+pub mod updater;
+pub mod urgency;
 pub mod uwp;
+pub mod version_upgrade;
 pub mod virtual_desk;
+pub mod widgets;
+pub mod window_effects;
+pub mod window_filter;
+pub mod window_switcher;
+pub mod window_tags;
@@ -1,6 +1,13 @@
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use windows::{
     core::PCWSTR,
     Win32::{
@@ -10,8 +17,9 @@ use windows::{
         UI::WindowsAndMessaging::{
             CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW,
             RegisterDeviceNotificationW, TranslateMessage, DBT_DEVTYP_DEVICEINTERFACE,
-            DEVICE_NOTIFY_WINDOW_HANDLE, DEV_BROADCAST_DEVICEINTERFACE_W, MSG, WINDOW_EX_STYLE,
-            WINDOW_STYLE, WM_DEVICECHANGE, WM_DISPLAYCHANGE, WM_SETTINGCHANGE, WNDCLASSW,
+            DEVICE_NOTIFY_WINDOW_HANDLE, DEV_BROADCAST_DEVICEINTERFACE_W, MSG, SPI_SETWORKAREA,
+            WINDOW_EX_STYLE, WINDOW_STYLE, WM_DEVICECHANGE, WM_DISPLAYCHANGE, WM_DPICHANGED,
+            WM_SETTINGCHANGE, WNDCLASSW,
         },
     },
 };
@@ -34,12 +42,34 @@ pub enum MonitorManagerEvent {
     Added(String, HMONITOR),
     Removed(String, HMONITOR),
     Updated(String, HMONITOR),
+    /// the space reserved by the appbars changed on any monitor
+    WorkAreaChanged,
+    /// the scale (DPI) of the monitor changed
+    ScaleChanged(String, HMONITOR),
+    /// the displays were turned on again, emitted after the monitors were synced
+    Woke,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WakeReason {
+    DisplaysOn,
+    LidOpened,
+    ResumedFromSleep,
 }
 
+/// time given to the displays to be enumerated again after waking up
+const WAKE_SETTLE_DELAY: Duration = Duration::from_secs(2);
+
+static WAKE_SCHEDULED: AtomicBool = AtomicBool::new(false);
+static DISPLAYS_OFF: AtomicBool = AtomicBool::new(false);
+static LID_CLOSED: AtomicBool = AtomicBool::new(false);
+
 type OnMonitorsChange = Box<dyn Fn(MonitorManagerEvent) + Send + Sync>;
 
 pub struct MonitorManager {
     pub monitors: Vec<(String, HMONITOR)>,
+    /// last known dpi of each monitor
+    dpis: HashMap<String, u32>,
     callbacks: Vec<OnMonitorsChange>,
 }
 
@@ -54,40 +84,19 @@ impl MonitorManager {
     ) -> LRESULT {
         match message {
             // Added based on this https://stackoverflow.com/a/33762334
-            WM_DISPLAYCHANGE | WM_SETTINGCHANGE | WM_DEVICECHANGE => {
+            // changing the scale of a monitor sends WM_SETTINGCHANGE/WM_DISPLAYCHANGE,
+            // WM_DPICHANGED is only received if the window is on the monitor
+            WM_DISPLAYCHANGE | WM_SETTINGCHANGE | WM_DEVICECHANGE | WM_DPICHANGED => {
                 // log::debug!("Dispatching {}, {:?}, {:?}", message, wparam, lparam);
                 std::thread::spawn(move || {
                     let mut manager = trace_lock!(MONITOR_MANAGER);
-
-                    let mut old_list = manager.monitors.clone();
-                    let new_list = match Self::get_monitors() {
-                        Ok(monitors) => monitors,
-                        Err(_) => return,
-                    };
-
-                    for (name, id) in &new_list {
-                        match old_list.iter().position(|x| x.0 == *name) {
-                            Some(idx) => {
-                                let (_, old_id) = old_list.remove(idx);
-                                if old_id != *id {
-                                    manager.notify_changes(MonitorManagerEvent::Updated(
-                                        name.clone(),
-                                        *id,
-                                    ));
-                                }
-                            }
-                            None => {
-                                manager
-                                    .notify_changes(MonitorManagerEvent::Added(name.clone(), *id));
-                            }
-                        }
+                    if manager.sync().is_err() {
+                        return;
                     }
 
-                    for (name, id) in old_list {
-                        manager.notify_changes(MonitorManagerEvent::Removed(name, id));
+                    if message == WM_SETTINGCHANGE && wparam.0 == SPI_SETWORKAREA.0 as usize {
+                        manager.notify_changes(MonitorManagerEvent::WorkAreaChanged);
                     }
-
-                    manager.monitors = new_list.into_iter().collect();
                 });
                 LRESULT(0)
             }
@@ -161,12 +170,88 @@ impl MonitorManager {
             log_error!(Self::create_background_window());
         })?;
 
+        let monitors = Self::get_monitors()?;
+        let dpis = monitors
+            .iter()
+            .filter_map(|(name, id)| Some((name.clone(), WindowsApi::get_monitor_dpi(*id).ok()?)))
+            .collect();
         Ok(Self {
             callbacks: Vec::new(),
-            monitors: Self::get_monitors()?,
+            monitors,
+            dpis,
         })
     }
 
+    /// Compares the connected monitors with the last known ones, notifying the differences
+    fn sync(&mut self) -> Result<()> {
+        let mut old_list = self.monitors.clone();
+        let new_list = Self::get_monitors()?;
+
+        for (name, id) in &new_list {
+            match old_list.iter().position(|x| x.0 == *name) {
+                Some(idx) => {
+                    let (_, old_id) = old_list.remove(idx);
+                    if old_id != *id {
+                        self.notify_changes(MonitorManagerEvent::Updated(name.clone(), *id));
+                    }
+                }
+                None => {
+                    self.notify_changes(MonitorManagerEvent::Added(name.clone(), *id));
+                }
+            }
+        }
+
+        for (name, id) in old_list {
+            self.notify_changes(MonitorManagerEvent::Removed(name, id));
+        }
+
+        self.dpis
+            .retain(|name, _| new_list.iter().any(|(n, _)| n == name));
+        for (name, id) in &new_list {
+            let Ok(dpi) = WindowsApi::get_monitor_dpi(*id) else {
+                continue;
+            };
+            let previous = self.dpis.insert(name.clone(), dpi);
+            if previous.is_some_and(|previous| previous != dpi) {
+                self.notify_changes(MonitorManagerEvent::ScaleChanged(name.clone(), *id));
+            }
+        }
+
+        self.monitors = new_list;
+        Ok(())
+    }
+
+    /// Called by the power events, the displays are enumerated again once they settle
+    /// and the surfaces are placed again as they are usually misplaced after sleeping.
+    pub fn on_wake(reason: WakeReason) {
+        if WAKE_SCHEDULED.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        log::info!("Displays woke up ({:?}), revalidating positions", reason);
+        log_error!(spawn_named_thread("Monitor Manager Wake", || {
+            std::thread::sleep(WAKE_SETTLE_DELAY);
+            WAKE_SCHEDULED.store(false, Ordering::Release);
+            let mut manager = trace_lock!(MONITOR_MANAGER);
+            log_error!(manager.sync());
+            manager.notify_changes(MonitorManagerEvent::Woke);
+        }));
+    }
+
+    /// Windows only notifies the state of the console display, so all the monitors are
+    /// considered turned off or on together.
+    pub fn on_display_state(on: bool) {
+        if DISPLAYS_OFF.swap(!on, Ordering::AcqRel) && on {
+            Self::on_wake(WakeReason::DisplaysOn);
+        }
+    }
+
+    /// `open` is false when the lid is closed
+    pub fn on_lid_state(open: bool) {
+        if LID_CLOSED.swap(!open, Ordering::AcqRel) && open {
+            Self::on_wake(WakeReason::LidOpened);
+        }
+    }
+
     fn get_monitors() -> Result<Vec<(String, HMONITOR)>> {
         let mut monitors = Vec::new();
         for m in MonitorEnumerator::get_all()? {
@@ -18,7 +18,10 @@ use windows::{
     },
 };
 
-use crate::{error_handler::Result, modules::network::domain::WlanBssEntry};
+use crate::{
+    error_handler::Result,
+    modules::{network::domain::WlanBssEntry, power::profile::PowerProfile},
+};
 
 use super::NetworkManager;
 
@@ -246,7 +249,7 @@ impl NetworkManager {
                     }
                 }
 
-                std::thread::sleep(Duration::from_secs(3));
+                std::thread::sleep(PowerProfile::sampling_interval(Duration::from_secs(3)));
             }
         });
     }
@@ -1,7 +1,6 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 
 use seelen_core::handlers::SeelenEvent;
-use tauri::Emitter;
 use tauri_plugin_shell::ShellExt;
 use windows::Win32::Networking::NetworkListManager::{
     INetworkListManager, NetworkListManager, NLM_CONNECTIVITY_IPV4_INTERNET,
@@ -9,7 +8,8 @@ use windows::Win32::Networking::NetworkListManager::{
 };
 
 use crate::{
-    error_handler::Result, log_error, seelen::get_app_handle, utils::sleep_millis, windows_api::Com,
+    error_handler::Result, log_error, modules::event_registry::EventRegistry,
+    seelen::get_app_handle, utils::sleep_millis, windows_api::Com,
 };
 
 use super::{
@@ -18,10 +18,12 @@ use super::{
 };
 
 fn emit_networks(ip: String, adapters: Vec<NetworkAdapter>, has_internet: bool) {
-    let handle = get_app_handle();
-    log_error!(handle.emit(SeelenEvent::NetworkDefaultLocalIp, ip));
-    log_error!(handle.emit(SeelenEvent::NetworkAdapters, adapters));
-    log_error!(handle.emit(SeelenEvent::NetworkInternetConnection, has_internet));
+    log_error!(EventRegistry::emit(SeelenEvent::NetworkDefaultLocalIp, ip));
+    log_error!(EventRegistry::emit(SeelenEvent::NetworkAdapters, adapters));
+    log_error!(EventRegistry::emit(
+        SeelenEvent::NetworkInternetConnection,
+        has_internet
+    ));
 }
 
 static REGISTERED: AtomicBool = AtomicBool::new(false);
@@ -86,8 +88,7 @@ async fn try_connect_to_profile(ssid: &str) -> Result<bool> {
 pub fn wlan_start_scanning() {
     log::trace!("Start scanning networks");
     NetworkManager::start_scanning(|list| {
-        let app = get_app_handle();
-        log_error!(app.emit(SeelenEvent::NetworkWlanScanned, &list));
+        log_error!(EventRegistry::emit(SeelenEvent::NetworkWlanScanned, &list));
     });
 }
 
@@ -0,0 +1,21 @@
+use seelen_core::system_state::NightLightState;
+
+use crate::error_handler::Result;
+
+use super::NightLight;
+
+#[tauri::command(async)]
+pub fn night_light_get_state() -> NightLightState {
+    NightLight::get_state()
+}
+
+/// Overrides the schedule until its next change
+#[tauri::command(async)]
+pub fn night_light_set_active(active: bool) -> Result<()> {
+    NightLight::set_active(active)
+}
+
+#[tauri::command(async)]
+pub fn night_light_toggle() -> Result<()> {
+    NightLight::toggle()
+}
@@ -0,0 +1,313 @@
+pub mod infrastructure;
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use crossbeam_channel::{Receiver, Sender};
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{
+    handlers::SeelenEvent, state::NightLightSettings, system_state::NightLightState,
+};
+use windows::{
+    core::{w, HSTRING, PCWSTR},
+    Win32::{
+        Graphics::Gdi::{CreateDCW, DeleteDC},
+        System::SystemInformation::GetLocalTime,
+        UI::ColorSystem::SetDeviceGammaRamp,
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::event_registry::EventRegistry,
+    seelen::Seelen,
+    state::application::FULL_STATE,
+    trace_lock,
+    utils::spawn_named_thread,
+    windows_api::{MonitorEnumerator, WindowsApi},
+};
+
+/// color temperature that leaves the gamma ramp untouched
+const NEUTRAL: f32 = 6500.0;
+/// warmer ramps are rejected by most drivers
+const MIN_TEMPERATURE: f32 = 1200.0;
+
+lazy_static! {
+    static ref SCHEDULE: Mutex<Schedule> = Mutex::new(Schedule::default());
+    /// monitor id -> current transition of the temperature
+    static ref TRANSITIONS: Mutex<HashMap<String, Transition>> = Mutex::new(HashMap::new());
+    /// wakes up the worker before the next tick
+    static ref WAKE: (Sender<()>, Receiver<()>) = crossbeam_channel::unbounded();
+}
+
+#[derive(Debug, Default)]
+struct Schedule {
+    state: NightLightState,
+    /// state of the schedule when the user toggled the night light, the override ends when it changes
+    overridden_at: Option<bool>,
+}
+
+#[derive(Debug)]
+struct Transition {
+    from: f32,
+    to: f32,
+    started: Instant,
+    /// last temperature set on the gamma ramp
+    applied: f32,
+}
+
+impl Transition {
+    fn value(&self, duration: Duration) -> f32 {
+        if duration.is_zero() {
+            return self.to;
+        }
+        let progress = (self.started.elapsed().as_secs_f32() / duration.as_secs_f32()).min(1.0);
+        self.from + (self.to - self.from) * progress
+    }
+}
+
+/// Color temperature by monitor using the gamma ramps, so it works without the Windows night light
+pub struct NightLight;
+
+impl NightLight {
+    pub fn get_state() -> NightLightState {
+        trace_lock!(SCHEDULE).state.clone()
+    }
+
+    fn emit(state: &NightLightState) -> Result<()> {
+        EventRegistry::emit(SeelenEvent::NightLightChanged, state)?;
+        Ok(())
+    }
+
+    fn is_scheduled_now(settings: &NightLightSettings) -> bool {
+        if !settings.scheduled {
+            return false;
+        }
+        let (Some(start), Some(end)) = (parse_time(&settings.start), parse_time(&settings.end))
+        else {
+            return false;
+        };
+        let now = unsafe { GetLocalTime() };
+        is_within(now.wHour as u32 * 60 + now.wMinute as u32, start, end)
+    }
+
+    pub fn set_active(active: bool) -> Result<()> {
+        let scheduled = Self::is_scheduled_now(&FULL_STATE.load().settings.night_light);
+        let mut schedule = trace_lock!(SCHEDULE);
+        schedule.overridden_at = (active != scheduled).then_some(scheduled);
+        schedule.state = NightLightState {
+            active,
+            manual: schedule.overridden_at.is_some(),
+        };
+        Self::emit(&schedule.state)?;
+        let _ = WAKE.0.send(());
+        Ok(())
+    }
+
+    pub fn toggle() -> Result<()> {
+        Self::set_active(!Self::get_state().active)
+    }
+
+    /// Updates the state following the schedule and returns if the night light should be active
+    fn refresh_state(scheduled: bool) -> Result<bool> {
+        let mut schedule = trace_lock!(SCHEDULE);
+        if schedule.overridden_at.is_some_and(|at| at != scheduled) {
+            schedule.overridden_at = None;
+        }
+        let state = NightLightState {
+            active: match schedule.overridden_at {
+                Some(_) => schedule.state.active,
+                None => scheduled,
+            },
+            manual: schedule.overridden_at.is_some(),
+        };
+        if state.active != schedule.state.active || state.manual != schedule.state.manual {
+            Self::emit(&state)?;
+            schedule.state = state;
+        }
+        Ok(schedule.state.active)
+    }
+
+    /// Moves the temperature of each monitor to its target, returns true while transitioning
+    fn tick() -> Result<bool> {
+        if !Seelen::is_running() {
+            return Ok(false);
+        }
+        let full_state = FULL_STATE.load();
+        let settings = &full_state.settings.night_light;
+        let duration = Duration::from_millis(settings.transition_duration);
+        let active = Self::refresh_state(Self::is_scheduled_now(settings))?;
+
+        let mut transitions = trace_lock!(TRANSITIONS);
+        let mut transitioning = false;
+        for (idx, hmonitor) in MonitorEnumerator::get_all()?.into_iter().enumerate() {
+            let id = WindowsApi::monitor_name(hmonitor)?;
+            let target = match active {
+                true => full_state
+                    .get_night_light_temperature(idx)
+                    .map_or(NEUTRAL, |t| (t as f32).clamp(MIN_TEMPERATURE, NEUTRAL)),
+                false => NEUTRAL,
+            };
+
+            let transition = transitions.entry(id.clone()).or_insert_with(|| Transition {
+                from: NEUTRAL,
+                to: NEUTRAL,
+                started: Instant::now(),
+                applied: NEUTRAL,
+            });
+            if transition.to != target {
+                transition.from = transition.value(duration);
+                transition.to = target;
+                transition.started = Instant::now();
+            }
+
+            let value = transition.value(duration);
+            // the system resets the ramps on some display changes, so warm ramps are set on each tick
+            if value != transition.applied || value != NEUTRAL {
+                log_error!(set_gamma_ramp(&id, value));
+                transition.applied = value;
+            }
+            transitioning |= value != transition.to;
+        }
+        Ok(transitioning)
+    }
+
+    pub fn start() -> Result<()> {
+        spawn_named_thread("Night Light", || loop {
+            let transitioning = match Self::tick() {
+                Ok(transitioning) => transitioning,
+                Err(err) => {
+                    log::error!("Night light tick failed: {:?}", err);
+                    false
+                }
+            };
+            let interval = match transitioning {
+                true => Duration::from_millis(33),
+                false => Duration::from_secs(1),
+            };
+            let _ = WAKE.1.recv_timeout(interval);
+        })?;
+        Ok(())
+    }
+
+    /// Leaves the monitors with the neutral ramp, used on exit
+    pub fn restore() -> Result<()> {
+        let mut transitions = trace_lock!(TRANSITIONS);
+        for (id, transition) in transitions.iter_mut() {
+            if transition.applied != NEUTRAL {
+                set_gamma_ramp(id, NEUTRAL)?;
+            }
+        }
+        transitions.clear();
+        Ok(())
+    }
+}
+
+fn set_gamma_ramp(monitor_id: &str, temperature: f32) -> Result<()> {
+    let (red, green, blue) = kelvin_to_rgb(temperature);
+    let mut ramp = [[0u16; 256]; 3];
+    for i in 0..256 {
+        let value = (i * 257) as f32;
+        ramp[0][i] = (value * red) as u16;
+        ramp[1][i] = (value * green) as u16;
+        ramp[2][i] = (value * blue) as u16;
+    }
+
+    let device = HSTRING::from(format!(r"\\.\{}", monitor_id));
+    unsafe {
+        let hdc = CreateDCW(w!("DISPLAY"), &device, PCWSTR::null(), None);
+        if hdc.is_invalid() {
+            return Err(format!("Failed to create device context for {}", monitor_id).into());
+        }
+        let success = SetDeviceGammaRamp(hdc, ramp.as_ptr() as _).as_bool();
+        let _ = DeleteDC(hdc);
+        if !success {
+            return Err(format!("Gamma ramp rejected by {}", monitor_id).into());
+        }
+    }
+    Ok(())
+}
+
+/// Multipliers of each channel for the temperature, normalized so 6500K is (1, 1, 1).
+/// https://tannerhelland.com/2012/09/18/convert-temperature-rgb-algorithm-code.html
+fn kelvin_to_rgb(kelvin: f32) -> (f32, f32, f32) {
+    fn raw(kelvin: f32) -> (f32, f32, f32) {
+        let temp = kelvin / 100.0;
+        let red = match temp <= 66.0 {
+            true => 255.0,
+            false => 329.698_73 * (temp - 60.0).powf(-0.133_204_76),
+        };
+        let green = match temp <= 66.0 {
+            true => 99.470_8 * temp.ln() - 161.119_57,
+            false => 288.122_17 * (temp - 60.0).powf(-0.075_514_85),
+        };
+        let blue = match temp {
+            t if t >= 66.0 => 255.0,
+            t if t <= 19.0 => 0.0,
+            t => 138.517_73 * (t - 10.0).ln() - 305.044_8,
+        };
+        (
+            red.clamp(0.0, 255.0),
+            green.clamp(0.0, 255.0),
+            blue.clamp(0.0, 255.0),
+        )
+    }
+
+    let (red, green, blue) = raw(kelvin.min(NEUTRAL));
+    let (n_red, n_green, n_blue) = raw(NEUTRAL);
+    (
+        (red / n_red).min(1.0),
+        (green / n_green).min(1.0),
+        (blue / n_blue).min(1.0),
+    )
+}
+
+/// Minutes since midnight of a time with format `HH:MM`
+fn parse_time(time: &str) -> Option<u32> {
+    let (hours, minutes) = time.trim().split_once(':')?;
+    let hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+/// Ranges where the end is before the start go through midnight
+fn is_within(now: u32, start: u32, end: u32) -> bool {
+    match start <= end {
+        true => now >= start && now < end,
+        false => now >= start || now < end,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kelvin_to_rgb() {
+        assert_eq!(kelvin_to_rgb(NEUTRAL), (1.0, 1.0, 1.0));
+        assert_eq!(kelvin_to_rgb(9000.0), (1.0, 1.0, 1.0));
+
+        let (red, green, blue) = kelvin_to_rgb(3400.0);
+        assert_eq!(red, 1.0);
+        assert!(blue < green && green < 1.0);
+    }
+
+    #[test]
+    fn test_schedule() {
+        assert_eq!(parse_time("07:30"), Some(450));
+        assert_eq!(parse_time("24:00"), None);
+        assert_eq!(parse_time("7"), None);
+
+        // 20:00 - 07:00 goes through midnight
+        assert!(is_within(1380, 1200, 420));
+        assert!(is_within(60, 1200, 420));
+        assert!(!is_within(720, 1200, 420));
+        // 09:00 - 17:00
+        assert!(is_within(600, 540, 1020));
+        assert!(!is_within(1020, 540, 1020));
+    }
+}
@@ -0,0 +1,25 @@
+use seelen_core::system_state::Note;
+
+use crate::error_handler::Result;
+
+use super::StickyNotes;
+
+#[tauri::command(async)]
+pub fn notes_get_all() -> Result<Vec<Note>> {
+    StickyNotes::list()
+}
+
+#[tauri::command(async)]
+pub fn notes_create(content: String) -> Result<Note> {
+    StickyNotes::create(content)
+}
+
+#[tauri::command(async)]
+pub fn notes_update(id: String, content: String) -> Result<Note> {
+    StickyNotes::update(&id, content)
+}
+
+#[tauri::command(async)]
+pub fn notes_delete(id: String) -> Result<()> {
+    StickyNotes::delete(&id)
+}
@@ -0,0 +1,158 @@
+pub mod infrastructure;
+
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use lazy_static::lazy_static;
+use notify_debouncer_full::{
+    new_debouncer,
+    notify::{ReadDirectoryChangesWatcher, RecursiveMode, Watcher},
+    DebounceEventResult, Debouncer, FileIdMap,
+};
+use parking_lot::Mutex;
+use seelen_core::{handlers::SeelenEvent, system_state::Note};
+use tauri::Manager;
+
+use crate::{
+    error_handler::Result, log_error, modules::event_registry::EventRegistry,
+    seelen::get_app_handle, trace_lock,
+};
+
+/// editors write files in several steps, so changes are grouped
+const DEBOUNCE_TIME: Duration = Duration::from_millis(300);
+const EXTENSION: &str = "md";
+
+lazy_static! {
+    static ref WATCHER: Mutex<Option<Debouncer<ReadDirectoryChangesWatcher, FileIdMap>>> =
+        Mutex::new(None);
+}
+
+/// Notes kept as markdown files, so they can also be edited with any text editor
+pub struct StickyNotes;
+
+impl StickyNotes {
+    fn folder() -> Result<PathBuf> {
+        Ok(get_app_handle().path().app_data_dir()?.join("notes"))
+    }
+
+    /// ids are file names, so anything that could point outside of the folder is rejected
+    fn path_of(id: &str) -> Result<PathBuf> {
+        let is_file_name = Path::new(id).file_name().is_some_and(|name| name == id);
+        if id.is_empty() || !is_file_name || id.starts_with('.') {
+            return Err(format!("Invalid note id: {}", id).into());
+        }
+        Ok(Self::folder()?.join(format!("{}.{}", id, EXTENSION)))
+    }
+
+    fn title_of(content: &str) -> String {
+        content
+            .lines()
+            .map(|line| line.trim_start_matches('#').trim())
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    fn read(path: &Path) -> Result<Note> {
+        let content = std::fs::read_to_string(path)?;
+        let modified = std::fs::metadata(path)?
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        Ok(Note {
+            id: path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            path: path.to_path_buf(),
+            title: Self::title_of(&content),
+            content,
+            modified,
+        })
+    }
+
+    /// Notes sorted by the last change, newest first
+    pub fn list() -> Result<Vec<Note>> {
+        let folder = Self::folder()?;
+        if !folder.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut notes = Vec::new();
+        for entry in std::fs::read_dir(folder)?.flatten() {
+            let path = entry.path();
+            if path.is_file() && path.extension().is_some_and(|ext| ext == EXTENSION) {
+                match Self::read(&path) {
+                    Ok(note) => notes.push(note),
+                    Err(err) => log::error!("Failed to read note {}: {}", path.display(), err),
+                }
+            }
+        }
+        notes.sort_by(|a, b| b.modified.cmp(&a.modified));
+        Ok(notes)
+    }
+
+    pub fn create(content: String) -> Result<Note> {
+        let mut timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        while Self::path_of(&timestamp.to_string())?.exists() {
+            timestamp += 1;
+        }
+
+        let path = Self::path_of(&timestamp.to_string())?;
+        std::fs::create_dir_all(Self::folder()?)?;
+        std::fs::write(&path, content)?;
+        Self::read(&path)
+    }
+
+    pub fn update(id: &str, content: String) -> Result<Note> {
+        let path = Self::path_of(id)?;
+        if !path.exists() {
+            return Err(format!("Note not found: {}", id).into());
+        }
+        std::fs::write(&path, content)?;
+        Self::read(&path)
+    }
+
+    pub fn delete(id: &str) -> Result<()> {
+        let path = Self::path_of(id)?;
+        if !path.exists() {
+            return Err(format!("Note not found: {}", id).into());
+        }
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+
+    fn emit() -> Result<()> {
+        EventRegistry::emit(SeelenEvent::NotesChanged, Self::list()?)?;
+        Ok(())
+    }
+
+    /// Changes made by the commands and by external editors are emitted by the watcher
+    pub fn start() -> Result<()> {
+        let folder = Self::folder()?;
+        std::fs::create_dir_all(&folder)?;
+
+        let mut debouncer =
+            new_debouncer(
+                DEBOUNCE_TIME,
+                None,
+                |result: DebounceEventResult| match result {
+                    Ok(_) => log_error!(Self::emit()),
+                    Err(errors) => errors
+                        .iter()
+                        .for_each(|e| log::error!("Notes Watcher Error: {:?}", e)),
+                },
+            )?;
+        debouncer
+            .watcher()
+            .watch(&folder, RecursiveMode::NonRecursive)?;
+        *trace_lock!(WATCHER) = Some(debouncer);
+        Ok(())
+    }
+}
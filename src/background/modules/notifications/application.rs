@@ -11,15 +11,17 @@ use lazy_static::lazy_static;
 use parking_lot::Mutex;
 use serde::Serialize;
 use windows::{
+    core::HSTRING,
     Foundation::{EventRegistrationToken, TypedEventHandler},
     UI::Notifications::{
         KnownNotificationBindings,
         Management::{UserNotificationListener, UserNotificationListenerAccessStatus},
-        NotificationKinds, UserNotification, UserNotificationChangedEventArgs,
+        NotificationKinds, ToastNotification, ToastNotificationManager, ToastTemplateType,
+        UserNotification, UserNotificationChangedEventArgs,
     },
 };
 
-use crate::{error_handler::Result, log_error, utils::spawn_named_thread};
+use crate::{error_handler::Result, log_error, seelen::get_app_handle, utils::spawn_named_thread};
 
 lazy_static! {
     pub static ref NOTIFICATION_MANAGER: Arc<Mutex<NotificationManager>> = Arc::new(Mutex::new(
@@ -223,3 +225,18 @@ impl NotificationManager {
         Ok(())
     }
 }
+
+/// Shows a toast from Seelen UI, it will be listed with the other notifications
+pub fn show_toast(title: &str, body: &str) -> Result<()> {
+    let content = ToastNotificationManager::GetTemplateContent(ToastTemplateType::ToastText02)?;
+    let texts = content.GetElementsByTagName(&HSTRING::from("text"))?;
+    for (idx, text) in [title, body].into_iter().enumerate() {
+        texts
+            .Item(idx as u32)?
+            .AppendChild(&content.CreateTextNode(&HSTRING::from(text))?)?;
+    }
+    let toast = ToastNotification::CreateToastNotification(&content)?;
+    let app_id = HSTRING::from(get_app_handle().config().identifier.as_str());
+    ToastNotificationManager::CreateToastNotifierWithId(&app_id)?.Show(&toast)?;
+    Ok(())
+}
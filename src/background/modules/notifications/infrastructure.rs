@@ -1,16 +1,13 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 
 use seelen_core::handlers::SeelenEvent;
-use tauri::Emitter;
 
-use crate::{error_handler::Result, log_error, seelen::get_app_handle, trace_lock};
+use crate::{error_handler::Result, log_error, modules::event_registry::EventRegistry, trace_lock};
 
 use super::application::{AppNotification, NOTIFICATION_MANAGER};
 
 fn emit_notifications(notifications: &Vec<AppNotification>) {
-    get_app_handle()
-        .emit(SeelenEvent::Notifications, notifications)
-        .expect("failed to emit");
+    EventRegistry::emit(SeelenEvent::Notifications, notifications).expect("failed to emit");
 }
 
 static REGISTERED: AtomicBool = AtomicBool::new(false);
@@ -0,0 +1,15 @@
+use seelen_core::system_state::{OnboardingPreset, SystemCapabilities};
+
+use crate::error_handler::Result;
+
+use super::Onboarding;
+
+#[tauri::command(async)]
+pub fn onboarding_detect_capabilities() -> SystemCapabilities {
+    Onboarding::detect_capabilities()
+}
+
+#[tauri::command(async)]
+pub fn onboarding_apply_preset(preset: OnboardingPreset) -> Result<()> {
+    Onboarding::apply_preset(preset)
+}
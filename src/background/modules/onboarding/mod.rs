@@ -0,0 +1,96 @@
+pub mod infrastructure;
+
+use itertools::Itertools;
+use seelen_core::{
+    state::VirtualDesktopStrategy,
+    system_state::{ConflictingSoftware, OnboardingPreset, SeelenFeature, SystemCapabilities},
+};
+
+use crate::{
+    error_handler::Result, state::application::FULL_STATE, utils::is_virtual_desktop_supported,
+    windows_api::WindowsApi,
+};
+
+/// Guides the first run, detecting what is supported on the system and applying a preset
+pub struct Onboarding;
+
+impl Onboarding {
+    /// (executable in lowercase, display name, conflicting feature)
+    const KNOWN_CONFLICTS: &'static [(&'static str, &'static str, SeelenFeature)] = &[
+        ("komorebi.exe", "Komorebi", SeelenFeature::WindowManager),
+        ("glazewm.exe", "GlazeWM", SeelenFeature::WindowManager),
+        ("fancywm.exe", "FancyWM", SeelenFeature::WindowManager),
+        ("workspacer.exe", "Workspacer", SeelenFeature::WindowManager),
+        ("whim.exe", "Whim", SeelenFeature::WindowManager),
+        (
+            "powertoys.fancyzones.exe",
+            "PowerToys FancyZones",
+            SeelenFeature::WindowManager,
+        ),
+        ("nexus.exe", "Winstep Nexus", SeelenFeature::Dock),
+        ("rocketdock.exe", "RocketDock", SeelenFeature::Dock),
+        ("objectdock.exe", "ObjectDock", SeelenFeature::Dock),
+        ("zebar.exe", "Zebar", SeelenFeature::Toolbar),
+    ];
+
+    fn detect_conflicts() -> Vec<ConflictingSoftware> {
+        let mut sys = sysinfo::System::new();
+        sys.refresh_processes();
+        let running = sys
+            .processes()
+            .values()
+            .map(|p| p.name().to_lowercase())
+            .collect_vec();
+
+        Self::KNOWN_CONFLICTS
+            .iter()
+            .filter(|(exe, _, _)| running.iter().any(|name| name == exe))
+            .map(|(exe, name, feature)| ConflictingSoftware {
+                name: name.to_string(),
+                exe: exe.to_string(),
+                conflicts_with: *feature,
+            })
+            .collect_vec()
+    }
+
+    pub fn detect_capabilities() -> SystemCapabilities {
+        SystemCapabilities {
+            native_virtual_desktops: is_virtual_desktop_supported(),
+            is_elevated: WindowsApi::is_elevated().unwrap_or(false),
+            conflicting_software: Self::detect_conflicts(),
+        }
+    }
+
+    /// Writes the preset on the settings file, the file watcher will load and apply it
+    pub fn apply_preset(preset: OnboardingPreset) -> Result<()> {
+        let mut state = FULL_STATE.load().cloned();
+        let settings = &mut state.settings;
+
+        match preset {
+            OnboardingPreset::DockOnly => {
+                settings.seelenweg.enabled = true;
+                settings.fancy_toolbar.enabled = false;
+                settings.window_manager.enabled = false;
+                settings.launcher.enabled = false;
+            }
+            OnboardingPreset::FullTiling => {
+                settings.seelenweg.enabled = true;
+                settings.fancy_toolbar.enabled = true;
+                settings.window_manager.enabled = true;
+                settings.launcher.enabled = true;
+            }
+            OnboardingPreset::Minimal => {
+                settings.seelenweg.enabled = false;
+                settings.fancy_toolbar.enabled = true;
+                settings.window_manager.enabled = false;
+                settings.launcher.enabled = false;
+            }
+        }
+
+        if !is_virtual_desktop_supported() {
+            settings.virtual_desktop_strategy = VirtualDesktopStrategy::Seelen;
+        }
+
+        state.save_settings()
+    }
+}
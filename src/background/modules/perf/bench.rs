@@ -0,0 +1,152 @@
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tauri::Manager;
+
+use crate::{
+    error_handler::Result,
+    seelen::get_app_handle,
+    seelen_wm_v2::preview::preview_layout,
+    state::application::{FullState, FULL_STATE},
+};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchResult {
+    pub name: String,
+    pub iterations: usize,
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub max_ms: f64,
+}
+
+impl BenchResult {
+    fn from_samples(name: String, mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let to_ms = |duration: Option<&Duration>| {
+            duration.map_or(0.0, |duration| duration.as_secs_f64() * 1000.0)
+        };
+        Self {
+            name,
+            iterations: samples.len(),
+            min_ms: to_ms(samples.first()),
+            median_ms: to_ms(samples.get(samples.len() / 2)),
+            max_ms: to_ms(samples.last()),
+        }
+    }
+}
+
+/// Developer benchmarks of the state and window manager modules, run via `--bench-state`
+pub struct StateBench;
+
+impl StateBench {
+    const ITERATIONS: usize = 10;
+    const THEME_FILES: [usize; 3] = [1, 10, 50];
+    const LAYOUT_WINDOWS: [usize; 3] = [4, 16, 64];
+
+    fn measure(name: impl Into<String>, mut f: impl FnMut() -> Result<()>) -> Result<BenchResult> {
+        let mut samples = Vec::with_capacity(Self::ITERATIONS);
+        for _ in 0..Self::ITERATIONS {
+            let start = Instant::now();
+            f()?;
+            samples.push(start.elapsed());
+        }
+        Ok(BenchResult::from_samples(name.into(), samples))
+    }
+
+    fn bench_load_all() -> Result<BenchResult> {
+        let state = FULL_STATE.load_full();
+        Self::measure("state/load_all", || state.cloned().load_all())
+    }
+
+    /// Parses copies of the installed themes written on a temporary folder
+    fn bench_themes(files: usize) -> Result<BenchResult> {
+        let folder = std::env::temp_dir().join("seelen-bench-themes");
+        std::fs::create_dir_all(&folder)?;
+        let themes: Vec<String> = FULL_STATE
+            .load()
+            .themes
+            .values()
+            .map(serde_yaml::to_string)
+            .collect::<std::result::Result<_, _>>()?;
+        if themes.is_empty() {
+            return Err("no themes to benchmark".into());
+        }
+
+        let mut paths = Vec::with_capacity(files);
+        for (idx, content) in themes.iter().cycle().take(files).enumerate() {
+            let path = folder.join(format!("theme-{}.yml", idx));
+            std::fs::write(&path, content)?;
+            paths.push(path);
+        }
+
+        let result = Self::measure(format!("themes/parse/{}", files), || {
+            for path in &paths {
+                FullState::load_theme_from_file(path.clone())?;
+            }
+            Ok(())
+        });
+        std::fs::remove_dir_all(&folder)?;
+        result
+    }
+
+    fn bench_layouts(windows: usize) -> Result<Vec<BenchResult>> {
+        let state = FULL_STATE.load();
+        let mut results = Vec::new();
+        for (name, layout) in &state.layouts {
+            results.push(Self::measure(
+                format!("layouts/{}/{}", name, windows),
+                || {
+                    preview_layout(layout, windows);
+                    Ok(())
+                },
+            )?);
+        }
+        Ok(results)
+    }
+
+    pub fn run() -> Result<Vec<BenchResult>> {
+        let mut results = vec![Self::bench_load_all()?];
+        for files in Self::THEME_FILES {
+            results.push(Self::bench_themes(files)?);
+        }
+        for windows in Self::LAYOUT_WINDOWS {
+            results.extend(Self::bench_layouts(windows)?);
+        }
+        Ok(results)
+    }
+
+    /// Runs the benchmarks and writes the results as json on the logs folder
+    pub fn dump() -> Result<()> {
+        let results = Self::run()?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let folder = get_app_handle().path().app_data_dir()?.join("logs");
+        std::fs::create_dir_all(&folder)?;
+        let path = folder.join(format!("bench-{}.json", timestamp));
+        std::fs::write(&path, serde_json::to_string_pretty(&results)?)?;
+        log::info!("State benchmarks written to {}", path.display());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bench_result_from_samples() {
+        let samples = [3, 1, 2, 5, 4].map(Duration::from_millis).to_vec();
+        let result = BenchResult::from_samples("test".to_string(), samples);
+        assert_eq!(result.iterations, 5);
+        assert_eq!(result.min_ms, 1.0);
+        assert_eq!(result.median_ms, 3.0);
+        assert_eq!(result.max_ms, 5.0);
+
+        let empty = BenchResult::from_samples("empty".to_string(), Vec::new());
+        assert_eq!(empty.iterations, 0);
+        assert_eq!(empty.median_ms, 0.0);
+    }
+}
@@ -0,0 +1,6 @@
+use super::{PerfMetrics, PerfMonitor};
+
+#[tauri::command(async)]
+pub fn get_perf_metrics() -> PerfMetrics {
+    PerfMonitor::snapshot()
+}
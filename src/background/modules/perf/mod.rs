@@ -0,0 +1,173 @@
+pub mod bench;
+pub mod infrastructure;
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::handlers::SeelenEvent;
+use serde::Serialize;
+use sysinfo::{Pid, System};
+use tauri::Manager;
+
+use crate::{
+    error_handler::Result,
+    hook::get_event_queues_metrics,
+    log_error,
+    modules::{event_registry::EventRegistry, power::profile::PowerProfile},
+    seelen::{get_app_handle, Seelen},
+    state::application::FULL_STATE,
+    trace_lock,
+    utils::{event_queue::EventQueueMetrics, spawn_named_thread},
+};
+
+lazy_static! {
+    static ref STARTED_AT: Instant = Instant::now();
+    static ref DURATIONS: Mutex<HashMap<&'static str, DurationMetrics>> =
+        Mutex::new(HashMap::new());
+    static ref EMITS: Mutex<HashMap<String, u64>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DurationMetrics {
+    pub count: u64,
+    pub total_ms: f64,
+    pub max_ms: f64,
+    pub last_ms: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessMemory {
+    pub pid: u32,
+    pub name: String,
+    /// resident memory in bytes
+    pub memory: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfMetrics {
+    pub uptime_secs: u64,
+    pub event_queues: Vec<EventQueueMetrics>,
+    pub durations: HashMap<&'static str, DurationMetrics>,
+    pub emits: HashMap<String, u64>,
+    pub background: Option<ProcessMemory>,
+    /// webview2 processes spawned by this instance
+    pub webviews: Vec<ProcessMemory>,
+}
+
+pub struct PerfMonitor;
+
+impl PerfMonitor {
+    const STREAM_INTERVAL: Duration = Duration::from_secs(5);
+
+    pub fn record_duration(name: &'static str, duration: Duration) {
+        let ms = duration.as_secs_f64() * 1000.0;
+        let mut durations = trace_lock!(DURATIONS);
+        let entry = durations.entry(name).or_default();
+        entry.count += 1;
+        entry.total_ms += ms;
+        entry.max_ms = entry.max_ms.max(ms);
+        entry.last_ms = ms;
+    }
+
+    pub fn measure<T>(name: &'static str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        Self::record_duration(name, start.elapsed());
+        result
+    }
+
+    pub fn record_emit(event: &str) {
+        *trace_lock!(EMITS).entry(event.to_string()).or_default() += 1;
+    }
+
+    fn processes_memory() -> (Option<ProcessMemory>, Vec<ProcessMemory>) {
+        let mut sys = System::new();
+        sys.refresh_processes();
+
+        let to_memory = |pid: &Pid| {
+            sys.process(*pid).map(|p| ProcessMemory {
+                pid: pid.as_u32(),
+                name: p.name().to_string(),
+                memory: p.memory(),
+            })
+        };
+
+        let current = match sysinfo::get_current_pid() {
+            Ok(pid) => pid,
+            Err(_) => return (None, Vec::new()),
+        };
+
+        // webview2 spawns a browser process as child of us and renderers as children of it
+        let is_descendant = |mut pid: Pid| {
+            // depth limit to avoid looping on reused pids
+            for _ in 0..8 {
+                match sys.process(pid).and_then(|p| p.parent()) {
+                    Some(parent) if parent == current => return true,
+                    Some(parent) => pid = parent,
+                    None => return false,
+                }
+            }
+            false
+        };
+
+        let webviews = sys
+            .processes()
+            .keys()
+            .filter(|pid| is_descendant(**pid))
+            .filter_map(&to_memory)
+            .collect();
+        (to_memory(&current), webviews)
+    }
+
+    pub fn snapshot() -> PerfMetrics {
+        let (background, webviews) = Self::processes_memory();
+        PerfMetrics {
+            uptime_secs: STARTED_AT.elapsed().as_secs(),
+            event_queues: get_event_queues_metrics(),
+            durations: trace_lock!(DURATIONS).clone(),
+            emits: trace_lock!(EMITS).clone(),
+            background,
+            webviews,
+        }
+    }
+
+    /// Writes the current metrics as json on the logs folder
+    pub fn dump() -> Result<()> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let folder = get_app_handle().path().app_data_dir()?.join("logs");
+        std::fs::create_dir_all(&folder)?;
+        let path = folder.join(format!("perf-{}.json", timestamp));
+        std::fs::write(&path, serde_json::to_string_pretty(&Self::snapshot())?)?;
+        log::info!("Performance metrics written to {}", path.display());
+        Ok(())
+    }
+
+    /// Publishes the metrics periodically while the dev tools are enabled
+    pub fn start_stream() -> Result<()> {
+        // ensure the uptime is measured from here
+        lazy_static::initialize(&STARTED_AT);
+        spawn_named_thread("Perf Metrics", || loop {
+            std::thread::sleep(PowerProfile::sampling_interval(Self::STREAM_INTERVAL));
+            if !Seelen::is_running() {
+                break;
+            }
+            if FULL_STATE.load().settings.dev_tools {
+                log_error!(EventRegistry::emit(
+                    SeelenEvent::PerfMetrics,
+                    Self::snapshot()
+                ));
+            }
+        })?;
+        Ok(())
+    }
+}
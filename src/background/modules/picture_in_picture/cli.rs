@@ -0,0 +1,46 @@
+use clap::Command;
+use seelen_core::state::PipCorner;
+
+use crate::{error_handler::Result, get_subcommands};
+
+use super::PictureInPicture;
+
+get_subcommands![
+    /** Moves the picture-in-picture window to a corner (top-left, top-right, bottom-left or bottom-right). */
+    Snap(corner: String => "Corner of the monitor."),
+    /** Resizes the picture-in-picture window to the next preset size. */
+    CycleSize,
+    /** Turns the focused window into a picture-in-picture window, or releases it. */
+    Toggle,
+];
+
+impl PictureInPicture {
+    pub const CLI_IDENTIFIER: &'static str = "pip";
+
+    pub fn get_cli() -> Command {
+        Command::new(Self::CLI_IDENTIFIER)
+            .about("Handles the picture-in-picture windows, the focused one or the last opened.")
+            .arg_required_else_help(true)
+            .subcommands(SubCommand::commands())
+    }
+
+    fn parse_corner(corner: &str) -> Result<PipCorner> {
+        match corner {
+            "top-left" => Ok(PipCorner::TopLeft),
+            "top-right" => Ok(PipCorner::TopRight),
+            "bottom-left" => Ok(PipCorner::BottomLeft),
+            "bottom-right" => Ok(PipCorner::BottomRight),
+            _ => Err(format!("Invalid corner: {}", corner).into()),
+        }
+    }
+
+    pub fn process(matches: &clap::ArgMatches) -> Result<()> {
+        let subcommand = SubCommand::try_from(matches)?;
+        match subcommand {
+            SubCommand::Snap(corner) => Self::snap_to(Self::parse_corner(&corner)?)?,
+            SubCommand::CycleSize => Self::cycle_size()?,
+            SubCommand::Toggle => Self::toggle_focused()?,
+        };
+        Ok(())
+    }
+}
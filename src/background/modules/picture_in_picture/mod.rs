@@ -0,0 +1,225 @@
+mod cli;
+
+use std::collections::HashSet;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::state::{AppExtraFlag, PipCorner};
+use windows::Win32::{
+    Foundation::RECT,
+    UI::WindowsAndMessaging::{HWND_TOPMOST, SWP_NOACTIVATE},
+};
+
+use crate::{
+    error_handler::Result,
+    modules::virtual_desk::get_vd_manager,
+    state::application::FULL_STATE,
+    trace_lock,
+    windows_api::{window::Window, WindowsApi},
+    winevent::WinEvent,
+};
+
+lazy_static! {
+    /// picture-in-picture windows in order of appearance
+    static ref WINDOWS: Mutex<Vec<PipWindow>> = Mutex::new(Vec::new());
+    /// windows released by the user, the rule is not applied to them again
+    static ref RELEASED: Mutex<HashSet<isize>> = Mutex::new(HashSet::new());
+}
+
+#[derive(Debug, Clone)]
+struct PipWindow {
+    address: isize,
+    corner: PipCorner,
+    /// index on the preset sizes
+    size: usize,
+}
+
+/// Rect of the given width on the corner of the work area, keeping the aspect ratio (w / h)
+fn corner_rect(area: &RECT, corner: PipCorner, margin: i32, width: i32, aspect: f32) -> RECT {
+    let area_width = area.right - area.left - margin * 2;
+    let area_height = area.bottom - area.top - margin * 2;
+    let mut width = width.clamp(1, area_width.max(1));
+    let mut height = (width as f32 / aspect).round() as i32;
+    if height > area_height {
+        height = area_height.max(1);
+        width = (height as f32 * aspect).round() as i32;
+    }
+
+    let left = match corner {
+        PipCorner::TopLeft | PipCorner::BottomLeft => area.left + margin,
+        PipCorner::TopRight | PipCorner::BottomRight => area.right - margin - width,
+    };
+    let top = match corner {
+        PipCorner::TopLeft | PipCorner::TopRight => area.top + margin,
+        PipCorner::BottomLeft | PipCorner::BottomRight => area.bottom - margin - height,
+    };
+    RECT {
+        left,
+        top,
+        right: left + width,
+        bottom: top + height,
+    }
+}
+
+/// Coordinated handling of the picture-in-picture windows, they are placed on a corner,
+/// pinned to all the workspaces and kept on top of the other windows.
+pub struct PictureInPicture;
+
+impl PictureInPicture {
+    fn is_tracked(address: isize) -> bool {
+        trace_lock!(WINDOWS).iter().any(|w| w.address == address)
+    }
+
+    fn has_rule(window: &Window) -> bool {
+        FULL_STATE
+            .load()
+            .get_app_config_by_window(window.hwnd())
+            .is_some_and(|config| config.options.contains(&AppExtraFlag::PictureInPicture))
+    }
+
+    /// The focused window if it is a picture-in-picture one, else the last that appeared
+    fn target() -> Option<PipWindow> {
+        let windows = trace_lock!(WINDOWS);
+        let foreground = WindowsApi::get_foreground_window().0 as isize;
+        windows
+            .iter()
+            .find(|w| w.address == foreground)
+            .or_else(|| windows.last())
+            .cloned()
+    }
+
+    fn update(pip: PipWindow) {
+        let mut windows = trace_lock!(WINDOWS);
+        if let Some(current) = windows.iter_mut().find(|w| w.address == pip.address) {
+            *current = pip;
+        }
+    }
+
+    fn place(pip: &PipWindow) -> Result<()> {
+        let settings = FULL_STATE.load().settings.picture_in_picture.clone();
+        let window = Window::from(pip.address);
+        let hwnd = window.hwnd();
+
+        let area = window.monitor().work_area()?;
+        let current = WindowsApi::get_outer_window_rect(hwnd)?;
+        let current_height = (current.bottom - current.top).max(1);
+        let aspect = (current.right - current.left).max(1) as f32 / current_height as f32;
+
+        let percent = settings.sizes.get(pip.size).copied().unwrap_or(30);
+        let width = (area.right - area.left) * percent as i32 / 100;
+        let rect = corner_rect(&area, pip.corner, settings.margin as i32, width, aspect);
+        WindowsApi::set_position(hwnd, Some(HWND_TOPMOST), &rect, SWP_NOACTIVATE)
+    }
+
+    fn adopt(window: &Window) -> Result<()> {
+        let pip = PipWindow {
+            address: window.address(),
+            corner: FULL_STATE.load().settings.picture_in_picture.corner,
+            size: 0,
+        };
+        trace_lock!(WINDOWS).push(pip.clone());
+
+        let vd = get_vd_manager();
+        if !vd.is_pinned_window(pip.address)? {
+            vd.pin_window(pip.address)?;
+        }
+        Self::place(&pip)
+    }
+
+    fn release(address: isize) -> Result<()> {
+        trace_lock!(WINDOWS).retain(|w| w.address != address);
+        trace_lock!(RELEASED).insert(address);
+        let vd = get_vd_manager();
+        if vd.is_pinned_window(address)? {
+            vd.unpin_window(address)?;
+        }
+        Ok(())
+    }
+
+    /// Moves the picture-in-picture window to the corner
+    pub fn snap_to(corner: PipCorner) -> Result<()> {
+        if let Some(mut pip) = Self::target() {
+            pip.corner = corner;
+            Self::place(&pip)?;
+            Self::update(pip);
+        }
+        Ok(())
+    }
+
+    /// Resizes the picture-in-picture window to the next preset size
+    pub fn cycle_size() -> Result<()> {
+        let count = FULL_STATE.load().settings.picture_in_picture.sizes.len();
+        if let Some(mut pip) = Self::target() {
+            pip.size = (pip.size + 1) % count.max(1);
+            Self::place(&pip)?;
+            Self::update(pip);
+        }
+        Ok(())
+    }
+
+    /// Turns the focused window into a picture-in-picture one, or releases it if it already is
+    pub fn toggle_focused() -> Result<()> {
+        let window = Window::from(WindowsApi::get_foreground_window());
+        let address = window.address();
+        if Self::is_tracked(address) {
+            return Self::release(address);
+        }
+        trace_lock!(RELEASED).remove(&address);
+        Self::adopt(&window)
+    }
+
+    pub fn wants_win_event(event: &WinEvent) -> bool {
+        matches!(event, WinEvent::ObjectShow | WinEvent::ObjectDestroy)
+    }
+
+    pub fn process_win_event(event: WinEvent, window: &Window) -> Result<()> {
+        let address = window.address();
+        match event {
+            WinEvent::ObjectShow => {
+                if !Self::is_tracked(address)
+                    && !trace_lock!(RELEASED).contains(&address)
+                    && Self::has_rule(window)
+                {
+                    Self::adopt(window)?;
+                }
+            }
+            WinEvent::ObjectDestroy => {
+                trace_lock!(WINDOWS).retain(|w| w.address != address);
+                trace_lock!(RELEASED).remove(&address);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AREA: RECT = RECT {
+        left: 0,
+        top: 0,
+        right: 1920,
+        bottom: 1040,
+    };
+
+    #[test]
+    fn test_corner_rect() {
+        let rect = corner_rect(&AREA, PipCorner::BottomRight, 16, 480, 16.0 / 9.0);
+        assert_eq!((rect.right, rect.bottom), (1904, 1024));
+        assert_eq!((rect.right - rect.left, rect.bottom - rect.top), (480, 270));
+
+        let rect = corner_rect(&AREA, PipCorner::TopLeft, 16, 480, 16.0 / 9.0);
+        assert_eq!((rect.left, rect.top), (16, 16));
+    }
+
+    #[test]
+    fn test_corner_rect_fits_the_area() {
+        // a tall window can't be taller than the work area
+        let rect = corner_rect(&AREA, PipCorner::TopRight, 0, 1920, 0.5);
+        assert_eq!(rect.bottom - rect.top, 1040);
+        assert_eq!(rect.right - rect.left, 520);
+        assert_eq!(rect.right, 1920);
+    }
+}
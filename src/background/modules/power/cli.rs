@@ -0,0 +1,44 @@
+use clap::Command;
+
+use crate::{error_handler::Result, get_subcommands};
+
+use super::infrastructure::{self, PowerManager};
+
+get_subcommands![
+    /** Locks the session. */
+    Lock,
+    /** Closes the session of the current user. */
+    LogOut,
+    /** Shows the lock screen to sign in with another user, keeping the current session open. */
+    SwitchUser,
+    /** Suspends the computer. */
+    Suspend,
+    /** Restarts the computer. */
+    Restart,
+    /** Shuts down the computer. */
+    Shutdown,
+];
+
+impl PowerManager {
+    pub const CLI_IDENTIFIER: &'static str = "power";
+
+    pub fn get_cli() -> Command {
+        Command::new(Self::CLI_IDENTIFIER)
+            .about("Session and power actions of the computer.")
+            .arg_required_else_help(true)
+            .subcommands(SubCommand::commands())
+    }
+
+    pub fn process(matches: &clap::ArgMatches) -> Result<()> {
+        let subcommand = SubCommand::try_from(matches)?;
+        match subcommand {
+            SubCommand::Lock => infrastructure::lock()?,
+            SubCommand::LogOut => infrastructure::log_out(),
+            SubCommand::SwitchUser => infrastructure::switch_user()?,
+            SubCommand::Suspend => infrastructure::suspend(),
+            SubCommand::Restart => infrastructure::restart()?,
+            SubCommand::Shutdown => infrastructure::shutdown()?,
+        };
+        Ok(())
+    }
+}
@@ -1,26 +1,49 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 
-use seelen_core::handlers::SeelenEvent;
-use tauri::Emitter;
+use seelen_core::{handlers::SeelenEvent, system_state::PowerProfileState};
 use windows::{
-    core::PCWSTR,
+    core::{GUID, PCWSTR},
     Win32::{
-        Foundation::{HWND, LPARAM, LRESULT, WPARAM},
-        System::Shutdown::{EWX_LOGOFF, EWX_REBOOT, EWX_SHUTDOWN, SHTDN_REASON_NONE},
+        Foundation::{HANDLE, HWND, LPARAM, LRESULT, WPARAM},
+        System::{
+            Power::{RegisterPowerSettingNotification, POWERBROADCAST_SETTING},
+            Shutdown::{EWX_LOGOFF, EWX_REBOOT, EWX_SHUTDOWN, SHTDN_REASON_NONE},
+        },
         UI::WindowsAndMessaging::{
             CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, PostQuitMessage,
-            RegisterClassW, TranslateMessage, MSG, PBT_APMPOWERSTATUSCHANGE, WINDOW_EX_STYLE,
-            WINDOW_STYLE, WM_DESTROY, WM_POWERBROADCAST, WNDCLASSW,
+            RegisterClassW, TranslateMessage, DEVICE_NOTIFY_WINDOW_HANDLE, MSG,
+            PBT_APMPOWERSTATUSCHANGE, PBT_APMRESUMEAUTOMATIC, PBT_POWERSETTINGCHANGE,
+            WINDOW_EX_STYLE, WINDOW_STYLE, WM_DESTROY, WM_POWERBROADCAST, WNDCLASSW,
         },
     },
 };
 
 use crate::{
-    error_handler::Result, log_error, modules::power::domain::Battery, seelen::get_app_handle,
-    utils::spawn_named_thread, windows_api::WindowsApi,
+    error_handler::Result,
+    log_error,
+    modules::{
+        event_registry::EventRegistry,
+        monitors::{MonitorManager, WakeReason},
+        power::domain::Battery,
+        resource_governor::ResourceGovernor,
+        session::SessionManager,
+        triggers::TriggerRunner,
+    },
+    utils::spawn_named_thread,
+    windows_api::WindowsApi,
 };
 
-use super::domain::PowerStatus;
+use super::{domain::PowerStatus, profile::PowerProfile};
+
+/// `GUID_POWER_SAVING_STATUS`, notified when the battery saver is turned on or off
+const GUID_POWER_SAVING_STATUS: GUID = GUID::from_u128(0xe00958c0_c213_4ace_ac77_fecced2eeea5);
+/// `GUID_CONSOLE_DISPLAY_STATE`, notified when the displays are turned off, on or dimmed
+const GUID_CONSOLE_DISPLAY_STATE: GUID = GUID::from_u128(0x6fe69556_704a_47a0_8f24_c28d936fda47);
+/// value of `GUID_CONSOLE_DISPLAY_STATE` when the displays are off
+const DISPLAY_STATE_OFF: u8 = 0;
+/// `GUID_LIDSWITCH_STATE_CHANGE`, notified when the lid is opened (1) or closed (0)
+const GUID_LIDSWITCH_STATE_CHANGE: GUID = GUID::from_u128(0xba3e0f4d_b817_4094_a2d1_d56379e6a0f3);
+const LID_STATE_CLOSED: u8 = 0;
 
 static REGISTERED: AtomicBool = AtomicBool::new(false);
 
@@ -34,7 +57,24 @@ impl PowerManager {
     ) -> LRESULT {
         match msg {
             WM_POWERBROADCAST => {
-                if PBT_APMPOWERSTATUSCHANGE == w_param.0 as u32 {
+                let event = w_param.0 as u32;
+                if event == PBT_POWERSETTINGCHANGE {
+                    let setting = &*(l_param.0 as *const POWERBROADCAST_SETTING);
+                    if setting.PowerSetting == GUID_CONSOLE_DISPLAY_STATE {
+                        let on = setting.Data[0] != DISPLAY_STATE_OFF;
+                        ResourceGovernor::on_display_state(on);
+                        MonitorManager::on_display_state(on);
+                        return LRESULT(1);
+                    }
+                    if setting.PowerSetting == GUID_LIDSWITCH_STATE_CHANGE {
+                        MonitorManager::on_lid_state(setting.Data[0] != LID_STATE_CLOSED);
+                        return LRESULT(1);
+                    }
+                }
+                if event == PBT_APMRESUMEAUTOMATIC {
+                    MonitorManager::on_wake(WakeReason::ResumedFromSleep);
+                }
+                if event == PBT_APMPOWERSTATUSCHANGE || event == PBT_POWERSETTINGCHANGE {
                     log_error!(PowerManager::emit_system_power_info());
                 }
                 LRESULT(1)
@@ -90,6 +130,20 @@ impl PowerManager {
             )?
         };
 
+        for setting in [
+            GUID_POWER_SAVING_STATUS,
+            GUID_CONSOLE_DISPLAY_STATE,
+            GUID_LIDSWITCH_STATE_CHANGE,
+        ] {
+            unsafe {
+                RegisterPowerSettingNotification(
+                    HANDLE(hwnd.0),
+                    &setting,
+                    DEVICE_NOTIFY_WINDOW_HANDLE,
+                )?
+            };
+        }
+
         let addr = hwnd.0 as isize;
         spawn_named_thread("Power Manager Message Loop", move || unsafe {
             let hwnd = HWND(addr as _);
@@ -110,10 +164,10 @@ impl PowerManager {
     }
 
     pub fn emit_system_power_info() -> Result<()> {
-        let handle = get_app_handle();
-
         let power_status: PowerStatus = WindowsApi::get_system_power_status()?.into();
-        handle.emit(SeelenEvent::PowerStatus, power_status)?;
+        TriggerRunner::on_power_status(&power_status);
+        log_error!(PowerProfile::on_power_status(&power_status));
+        EventRegistry::emit(SeelenEvent::PowerStatus, power_status)?;
 
         let mut batteries: Vec<Battery> = Vec::new();
         let manager = battery::Manager::new()?;
@@ -121,17 +175,32 @@ impl PowerManager {
             batteries.push(battery.try_into()?);
         }
 
-        handle.emit(SeelenEvent::BatteriesStatus, batteries)?;
+        EventRegistry::emit(SeelenEvent::BatteriesStatus, batteries)?;
 
         Ok(())
     }
 }
 
+#[tauri::command(async)]
+pub fn get_power_profile() -> PowerProfileState {
+    PowerProfile::state()
+}
+
 #[tauri::command(async)]
 pub fn log_out() {
     log_error!(WindowsApi::exit_windows(EWX_LOGOFF, SHTDN_REASON_NONE));
 }
 
+#[tauri::command(async)]
+pub fn lock() -> Result<()> {
+    SessionManager::lock()
+}
+
+#[tauri::command(async)]
+pub fn switch_user() -> Result<()> {
+    SessionManager::switch_user()
+}
+
 #[tauri::command(async)]
 pub fn suspend() {
     log_error!(WindowsApi::set_suspend_state());
@@ -1,2 +1,4 @@
+pub mod cli;
 pub mod domain;
 pub mod infrastructure;
+pub mod profile;
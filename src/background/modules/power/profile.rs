@@ -0,0 +1,90 @@
+use std::{
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
+    time::Duration,
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{handlers::SeelenEvent, system_state::PowerProfileState};
+
+use crate::{
+    error_handler::Result,
+    modules::{emit_scheduler::EmitScheduler, event_registry::EventRegistry},
+    state::application::FULL_STATE,
+    trace_lock,
+};
+
+use super::domain::PowerStatus;
+
+/// `SystemStatusFlag` of `SYSTEM_POWER_STATUS` when the battery saver is on
+const BATTERY_SAVER_ON: u8 = 1;
+/// `ACLineStatus` of `SYSTEM_POWER_STATUS` when running on battery
+const AC_OFFLINE: u8 = 0;
+/// polling loops wait this many times longer while saving
+const SAMPLING_FACTOR: u32 = 4;
+
+lazy_static! {
+    static ref STATE: Mutex<PowerProfileState> = Mutex::new(PowerProfileState::default());
+}
+
+/// read on hot paths, so these are kept apart from the state
+static ANIMATIONS_DISABLED: AtomicBool = AtomicBool::new(false);
+static SAMPLING: AtomicU32 = AtomicU32::new(1);
+
+/// Coordinates the adaptations applied while the battery saver is on, every subsystem reads
+/// its part of the profile from here instead of checking the power status by itself.
+pub struct PowerProfile;
+
+impl PowerProfile {
+    pub fn state() -> PowerProfileState {
+        *trace_lock!(STATE)
+    }
+
+    pub fn animations_disabled() -> bool {
+        ANIMATIONS_DISABLED.load(Ordering::Relaxed)
+    }
+
+    /// Interval of a polling loop, longer while saving
+    pub fn sampling_interval(interval: Duration) -> Duration {
+        interval * SAMPLING.load(Ordering::Relaxed)
+    }
+
+    pub fn on_power_status(status: &PowerStatus) -> Result<()> {
+        let mut state = Self::state();
+        state.battery_saver = status.system_status_flag == BATTERY_SAVER_ON;
+        state.on_battery = status.ac_line_status == AC_OFFLINE;
+        Self::update(state)
+    }
+
+    /// Applies the settings to the current power status
+    pub fn refresh() -> Result<()> {
+        Self::update(Self::state())
+    }
+
+    fn update(mut state: PowerProfileState) -> Result<()> {
+        let settings = &FULL_STATE.load().settings.power_saving;
+        state.saving = settings.enabled && state.battery_saver && state.on_battery;
+        state.pause_wallpaper = state.saving && settings.pause_wallpaper;
+        state.disable_animations = state.saving && settings.disable_animations;
+
+        EmitScheduler::set_throttle(state.saving.then_some(settings.max_emit_rate));
+        ANIMATIONS_DISABLED.store(state.disable_animations, Ordering::Relaxed);
+        let sampling = match state.saving && settings.reduce_sampling {
+            true => SAMPLING_FACTOR,
+            false => 1,
+        };
+        SAMPLING.store(sampling, Ordering::Relaxed);
+
+        let mut current = trace_lock!(STATE);
+        if *current == state {
+            return Ok(());
+        }
+        if current.saving != state.saving {
+            log::info!("Power saving profile active: {}", state.saving);
+        }
+        *current = state;
+        drop(current);
+        EventRegistry::emit(SeelenEvent::PowerProfileChanged, state)?;
+        Ok(())
+    }
+}
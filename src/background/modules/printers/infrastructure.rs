@@ -0,0 +1,35 @@
+use seelen_core::system_state::Printer;
+
+use crate::error_handler::Result;
+
+use super::Printers;
+
+#[tauri::command(async)]
+pub fn printers_get_all() -> Result<Vec<Printer>> {
+    Printers::get_all()
+}
+
+#[tauri::command(async)]
+pub fn printer_pause(printer: String) -> Result<()> {
+    Printers::set_paused(&printer, true)
+}
+
+#[tauri::command(async)]
+pub fn printer_resume(printer: String) -> Result<()> {
+    Printers::set_paused(&printer, false)
+}
+
+#[tauri::command(async)]
+pub fn print_job_pause(printer: String, job_id: u32) -> Result<()> {
+    Printers::pause_job(&printer, job_id)
+}
+
+#[tauri::command(async)]
+pub fn print_job_resume(printer: String, job_id: u32) -> Result<()> {
+    Printers::resume_job(&printer, job_id)
+}
+
+#[tauri::command(async)]
+pub fn print_job_cancel(printer: String, job_id: u32) -> Result<()> {
+    Printers::cancel_job(&printer, job_id)
+}
@@ -0,0 +1,352 @@
+pub mod infrastructure;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{
+    handlers::SeelenEvent,
+    system_state::{PrintJob, PrintJobStatus, Printer, PrinterProblem},
+};
+use windows::{
+    core::{HSTRING, PCWSTR, PWSTR},
+    Win32::{
+        Graphics::Printing::{
+            ClosePrinter, EnumJobsW, EnumPrintersW, FindClosePrinterChangeNotification,
+            FindFirstPrinterChangeNotification, FindNextPrinterChangeNotification,
+            GetDefaultPrinterW, OpenPrinterW, SetJobW, SetPrinterW, JOB_CONTROL_DELETE,
+            JOB_CONTROL_PAUSE, JOB_CONTROL_RESUME, JOB_INFO_1W, PRINTER_ACCESS_ADMINISTER,
+            PRINTER_ACCESS_RIGHTS, PRINTER_ACCESS_USE, PRINTER_CHANGE_JOB, PRINTER_CHANGE_PRINTER,
+            PRINTER_CONTROL_PAUSE, PRINTER_CONTROL_RESUME, PRINTER_DEFAULTSW,
+            PRINTER_ENUM_CONNECTIONS, PRINTER_ENUM_LOCAL, PRINTER_HANDLE, PRINTER_INFO_2W,
+        },
+        System::Threading::{WaitForSingleObject, INFINITE},
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::event_registry::EventRegistry,
+    trace_lock,
+    utils::{
+        event_queue::{EventQueue, QueueSender},
+        spawn_named_thread,
+    },
+};
+
+// https://learn.microsoft.com/en-us/windows/win32/printdocs/printer-info-2
+const PRINTER_STATUS_PAUSED: u32 = 0x1;
+const PRINTER_STATUS_ERROR: u32 = 0x2;
+const PRINTER_STATUS_PAPER_JAM: u32 = 0x8;
+const PRINTER_STATUS_PAPER_OUT: u32 = 0x10;
+const PRINTER_STATUS_PAPER_PROBLEM: u32 = 0x40;
+const PRINTER_STATUS_OFFLINE: u32 = 0x80;
+const PRINTER_STATUS_TONER_LOW: u32 = 0x20000;
+const PRINTER_STATUS_NO_TONER: u32 = 0x40000;
+const PRINTER_STATUS_USER_INTERVENTION: u32 = 0x100000;
+const PRINTER_STATUS_DOOR_OPEN: u32 = 0x400000;
+const PRINTER_ATTRIBUTE_WORK_OFFLINE: u32 = 0x400;
+
+// https://learn.microsoft.com/en-us/windows/win32/printdocs/job-info-1
+const JOB_STATUS_PAUSED: u32 = 0x1;
+const JOB_STATUS_ERROR: u32 = 0x2;
+const JOB_STATUS_DELETING: u32 = 0x4;
+const JOB_STATUS_SPOOLING: u32 = 0x8;
+const JOB_STATUS_PRINTING: u32 = 0x10;
+const JOB_STATUS_OFFLINE: u32 = 0x20;
+const JOB_STATUS_PAPEROUT: u32 = 0x40;
+const JOB_STATUS_BLOCKED_DEVQ: u32 = 0x200;
+const JOB_STATUS_USER_INTERVENTION: u32 = 0x400;
+
+lazy_static! {
+    /// last emitted printers, so only changes are emitted
+    static ref LAST: Mutex<Option<Vec<Printer>>> = Mutex::new(None);
+    /// the spooler notifies each step of a job, so they are collapsed into a single emit
+    static ref EMIT_QUEUE: Result<EventQueue<()>> = EventQueue::new("Printers", |_| Some(0), |_| {
+        log_error!(Printers::emit_changes());
+    });
+}
+
+/// Print queues of the local and connected printers
+pub struct Printers;
+
+impl Printers {
+    fn open(name: &str, access: PRINTER_ACCESS_RIGHTS) -> Result<PRINTER_HANDLE> {
+        let defaults = PRINTER_DEFAULTSW {
+            DesiredAccess: access,
+            ..Default::default()
+        };
+        let mut handle = PRINTER_HANDLE::default();
+        unsafe { OpenPrinterW(&HSTRING::from(name), &mut handle, Some(&defaults))? };
+        Ok(handle)
+    }
+
+    fn with_printer<T>(
+        name: &str,
+        access: PRINTER_ACCESS_RIGHTS,
+        f: impl FnOnce(PRINTER_HANDLE) -> Result<T>,
+    ) -> Result<T> {
+        let handle = Self::open(name, access)?;
+        let result = f(handle);
+        unsafe { log_error!(ClosePrinter(handle)) };
+        result
+    }
+
+    fn default_printer() -> Option<String> {
+        let mut size = 0;
+        unsafe { GetDefaultPrinterW(PWSTR::null(), &mut size) };
+        if size == 0 {
+            return None;
+        }
+        let mut buffer = vec![0u16; size as usize];
+        if !unsafe { GetDefaultPrinterW(PWSTR(buffer.as_mut_ptr()), &mut size) }.as_bool() {
+            return None;
+        }
+        Some(String::from_utf16_lossy(
+            &buffer[..size.saturating_sub(1) as usize],
+        ))
+    }
+
+    fn get_jobs(handle: PRINTER_HANDLE, count: u32) -> Result<Vec<PrintJob>> {
+        unsafe {
+            enumerate(
+                |buffer, needed, returned| EnumJobsW(handle, 0, count, 1, buffer, needed, returned),
+                |job: &JOB_INFO_1W| PrintJob {
+                    id: job.JobId,
+                    document: pwstr_to_string(job.pDocument),
+                    user: pwstr_to_string(job.pUserName),
+                    status: job_status_of(job.Status),
+                    total_pages: job.TotalPages,
+                    pages_printed: job.PagesPrinted,
+                },
+            )
+        }
+    }
+
+    pub fn get_all() -> Result<Vec<Printer>> {
+        let default = Self::default_printer();
+        let printers = unsafe {
+            enumerate(
+                |buffer, needed, returned| {
+                    EnumPrintersW(
+                        PRINTER_ENUM_LOCAL | PRINTER_ENUM_CONNECTIONS,
+                        PCWSTR::null(),
+                        2,
+                        buffer,
+                        needed,
+                        returned,
+                    )
+                },
+                |info: &PRINTER_INFO_2W| {
+                    (
+                        pwstr_to_string(info.pPrinterName),
+                        info.Status,
+                        info.Attributes,
+                        info.cJobs,
+                    )
+                },
+            )?
+        };
+
+        let mut result = Vec::new();
+        for (name, status, attributes, jobs_count) in printers {
+            let jobs = match jobs_count {
+                0 => Vec::new(),
+                count => Self::with_printer(&name, PRINTER_ACCESS_USE, |handle| {
+                    Self::get_jobs(handle, count)
+                })
+                .unwrap_or_default(),
+            };
+            result.push(Printer {
+                is_default: default.as_ref() == Some(&name),
+                name,
+                paused: status & PRINTER_STATUS_PAUSED != 0,
+                offline: status & PRINTER_STATUS_OFFLINE != 0
+                    || attributes & PRINTER_ATTRIBUTE_WORK_OFFLINE != 0,
+                problems: printer_problems_of(status),
+                jobs,
+            });
+        }
+        Ok(result)
+    }
+
+    /// Needs administrator rights for most printers
+    pub fn set_paused(name: &str, paused: bool) -> Result<()> {
+        let command = if paused {
+            PRINTER_CONTROL_PAUSE
+        } else {
+            PRINTER_CONTROL_RESUME
+        };
+        Self::with_printer(name, PRINTER_ACCESS_ADMINISTER, |handle| {
+            unsafe { SetPrinterW(handle, 0, None, command)? };
+            Ok(())
+        })
+    }
+
+    /// Users can control their own jobs without administrator rights
+    fn control_job(name: &str, job_id: u32, command: u32) -> Result<()> {
+        Self::with_printer(name, PRINTER_ACCESS_USE, |handle| {
+            unsafe { SetJobW(handle, job_id, 0, None, command)? };
+            Ok(())
+        })
+    }
+
+    pub fn pause_job(name: &str, job_id: u32) -> Result<()> {
+        Self::control_job(name, job_id, JOB_CONTROL_PAUSE)
+    }
+
+    pub fn resume_job(name: &str, job_id: u32) -> Result<()> {
+        Self::control_job(name, job_id, JOB_CONTROL_RESUME)
+    }
+
+    pub fn cancel_job(name: &str, job_id: u32) -> Result<()> {
+        Self::control_job(name, job_id, JOB_CONTROL_DELETE)
+    }
+
+    pub fn emit_changes() -> Result<()> {
+        let printers = Self::get_all()?;
+        let mut last = trace_lock!(LAST);
+        if last.as_ref() != Some(&printers) {
+            EventRegistry::emit(SeelenEvent::PrintersChanged, &printers)?;
+            *last = Some(printers);
+        }
+        Ok(())
+    }
+
+    /// Waits for changes on any printer of the local print server
+    fn watch() -> Result<()> {
+        // a null name opens the local print server
+        let mut server = PRINTER_HANDLE::default();
+        unsafe { OpenPrinterW(PCWSTR::null(), &mut server, None)? };
+        let change = unsafe {
+            FindFirstPrinterChangeNotification(
+                server,
+                PRINTER_CHANGE_JOB | PRINTER_CHANGE_PRINTER,
+                0,
+                None,
+            )?
+        };
+        loop {
+            unsafe { WaitForSingleObject(change, INFINITE) };
+            let mut changes = 0;
+            if !unsafe { FindNextPrinterChangeNotification(change, Some(&mut changes), None, None) }
+                .as_bool()
+            {
+                break;
+            }
+            EMIT_QUEUE.send(());
+        }
+        unsafe {
+            let _ = FindClosePrinterChangeNotification(change);
+            let _ = ClosePrinter(server);
+        }
+        Err("Printer change notifications stopped".into())
+    }
+
+    pub fn start() -> Result<()> {
+        spawn_named_thread("Printers", || {
+            log_error!(Self::watch());
+        })?;
+        Ok(())
+    }
+}
+
+/// The spooler APIs first return the size needed, then fill a buffer with the items
+/// followed by the strings they point to
+unsafe fn enumerate<T, R>(
+    call: impl Fn(Option<&mut [u8]>, *mut u32, *mut u32) -> windows::core::Result<()>,
+    map: impl Fn(&T) -> R,
+) -> Result<Vec<R>> {
+    let (mut needed, mut returned) = (0, 0);
+    // fails with ERROR_INSUFFICIENT_BUFFER, only the size is wanted
+    let _ = call(None, &mut needed, &mut returned);
+    if needed == 0 {
+        return Ok(Vec::new());
+    }
+    // u64 to keep the structs aligned
+    let mut buffer = vec![0u64; (needed as usize).div_ceil(8)];
+    let bytes = std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, needed as usize);
+    call(Some(bytes), &mut needed, &mut returned)?;
+    let items = std::slice::from_raw_parts(buffer.as_ptr() as *const T, returned as usize);
+    Ok(items.iter().map(map).collect())
+}
+
+unsafe fn pwstr_to_string(value: PWSTR) -> String {
+    if value.is_null() {
+        return String::new();
+    }
+    value.to_string().unwrap_or_default()
+}
+
+fn printer_problems_of(status: u32) -> Vec<PrinterProblem> {
+    [
+        (PRINTER_STATUS_ERROR, PrinterProblem::Error),
+        (PRINTER_STATUS_PAPER_OUT, PrinterProblem::PaperOut),
+        (PRINTER_STATUS_PAPER_JAM, PrinterProblem::PaperJam),
+        (PRINTER_STATUS_PAPER_PROBLEM, PrinterProblem::PaperProblem),
+        (PRINTER_STATUS_TONER_LOW, PrinterProblem::TonerLow),
+        (PRINTER_STATUS_NO_TONER, PrinterProblem::NoToner),
+        (PRINTER_STATUS_DOOR_OPEN, PrinterProblem::DoorOpen),
+        (
+            PRINTER_STATUS_USER_INTERVENTION,
+            PrinterProblem::UserIntervention,
+        ),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| status & flag != 0)
+    .map(|(_, problem)| problem)
+    .collect()
+}
+
+/// A job can have several flags at once, the most relevant one wins
+fn job_status_of(status: u32) -> PrintJobStatus {
+    let error = JOB_STATUS_ERROR
+        | JOB_STATUS_OFFLINE
+        | JOB_STATUS_PAPEROUT
+        | JOB_STATUS_BLOCKED_DEVQ
+        | JOB_STATUS_USER_INTERVENTION;
+    if status & JOB_STATUS_DELETING != 0 {
+        PrintJobStatus::Deleting
+    } else if status & error != 0 {
+        PrintJobStatus::Error
+    } else if status & JOB_STATUS_PAUSED != 0 {
+        PrintJobStatus::Paused
+    } else if status & JOB_STATUS_PRINTING != 0 {
+        PrintJobStatus::Printing
+    } else if status & JOB_STATUS_SPOOLING != 0 {
+        PrintJobStatus::Spooling
+    } else {
+        PrintJobStatus::Queued
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_printer_problems_of() {
+        assert!(printer_problems_of(0).is_empty());
+        assert!(printer_problems_of(PRINTER_STATUS_PAUSED).is_empty());
+        assert_eq!(
+            printer_problems_of(PRINTER_STATUS_PAPER_OUT | PRINTER_STATUS_TONER_LOW),
+            vec![PrinterProblem::PaperOut, PrinterProblem::TonerLow]
+        );
+    }
+
+    #[test]
+    fn test_job_status_of() {
+        assert_eq!(job_status_of(0), PrintJobStatus::Queued);
+        assert_eq!(
+            job_status_of(JOB_STATUS_SPOOLING | JOB_STATUS_PRINTING),
+            PrintJobStatus::Printing
+        );
+        assert_eq!(
+            job_status_of(JOB_STATUS_PRINTING | JOB_STATUS_PAPEROUT),
+            PrintJobStatus::Error
+        );
+        assert_eq!(
+            job_status_of(JOB_STATUS_PAUSED | JOB_STATUS_DELETING),
+            PrintJobStatus::Deleting
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use clap::Command;
+
+use crate::{error_handler::Result, get_subcommands};
+
+use super::PrivateSession;
+
+get_subcommands![
+    /** Stops saving the launcher history and the apps usage. */
+    On,
+    /** Saves the launcher history and the apps usage again. */
+    Off,
+    /** Starts or ends the private session. */
+    Toggle,
+];
+
+impl PrivateSession {
+    pub const CLI_IDENTIFIER: &'static str = "private";
+
+    pub fn get_cli() -> Command {
+        Command::new(Self::CLI_IDENTIFIER)
+            .about("Handles the private session, while active no history is saved.")
+            .arg_required_else_help(true)
+            .subcommands(SubCommand::commands())
+    }
+
+    pub fn process(matches: &clap::ArgMatches) -> Result<()> {
+        let subcommand = SubCommand::try_from(matches)?;
+        match subcommand {
+            SubCommand::On => Self::set_active(true)?,
+            SubCommand::Off => Self::set_active(false)?,
+            SubCommand::Toggle => Self::toggle()?,
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,18 @@
+use crate::error_handler::Result;
+
+use super::PrivateSession;
+
+#[tauri::command(async)]
+pub fn private_session_get() -> bool {
+    PrivateSession::is_active()
+}
+
+#[tauri::command(async)]
+pub fn private_session_set(active: bool) -> Result<()> {
+    PrivateSession::set_active(active)
+}
+
+#[tauri::command(async)]
+pub fn private_session_toggle() -> Result<()> {
+    PrivateSession::toggle()
+}
@@ -0,0 +1,36 @@
+mod cli;
+pub mod infrastructure;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use seelen_core::handlers::SeelenEvent;
+
+use crate::{error_handler::Result, modules::event_registry::EventRegistry};
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// While active nothing is written to the launcher history nor to the app usage database,
+/// useful while sharing the screen or on shared machines. It is not persisted, so a
+/// restart always ends the private session.
+pub struct PrivateSession;
+
+impl PrivateSession {
+    pub fn is_active() -> bool {
+        ACTIVE.load(Ordering::SeqCst)
+    }
+
+    pub fn set_active(active: bool) -> Result<()> {
+        if ACTIVE.swap(active, Ordering::SeqCst) != active {
+            log::info!(
+                "Private session {}",
+                if active { "started" } else { "ended" }
+            );
+            EventRegistry::emit(SeelenEvent::PrivateSessionChanged, active)?;
+        }
+        Ok(())
+    }
+
+    pub fn toggle() -> Result<()> {
+        Self::set_active(!Self::is_active())
+    }
+}
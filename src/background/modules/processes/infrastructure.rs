@@ -0,0 +1,11 @@
+use seelen_core::system_state::ProcessInfo;
+
+use crate::error_handler::Result;
+
+use super::ProcessWatcher;
+
+/// Running processes, used along with the `processes-changed` event to track background processes
+#[tauri::command(async)]
+pub fn processes_get_running() -> Result<Vec<ProcessInfo>> {
+    ProcessWatcher::running()
+}
@@ -0,0 +1,156 @@
+pub mod infrastructure;
+
+use std::path::PathBuf;
+
+use seelen_core::{
+    handlers::SeelenEvent,
+    system_state::{ProcessEvent, ProcessEventKind, ProcessInfo},
+};
+use windows::{
+    core::{w, IUnknown, Interface, BSTR, PCWSTR, VARIANT},
+    Win32::System::Wmi::{
+        IEnumWbemClassObject, IWbemClassObject, IWbemLocator, IWbemServices, WbemLocator,
+        WBEM_FLAG_FORWARD_ONLY, WBEM_FLAG_RETURN_IMMEDIATELY, WBEM_INFINITE, WBEM_TIMEOUT_TYPE,
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::{event_registry::EventRegistry, triggers::TriggerRunner},
+    seelen::Seelen,
+    utils::spawn_named_thread,
+    windows_api::Com,
+};
+
+/// time to wait for an event before checking if the app is still running
+const NEXT_TIMEOUT: WBEM_TIMEOUT_TYPE = WBEM_TIMEOUT_TYPE(1000);
+
+/// WMI checks the process list once per `WITHIN` seconds, this doesn't require admin rights
+/// as the `Win32_ProcessStartTrace` (ETW) events do.
+const EVENTS_QUERY: &str = "SELECT * FROM __InstanceOperationEvent WITHIN 1 \
+    WHERE TargetInstance ISA 'Win32_Process' \
+    AND (__CLASS = '__InstanceCreationEvent' OR __CLASS = '__InstanceDeletionEvent')";
+
+const PROCESSES_QUERY: &str =
+    "SELECT ProcessId, ParentProcessId, Name, ExecutablePath FROM Win32_Process";
+
+fn connect() -> Result<IWbemServices> {
+    let locator: IWbemLocator = Com::create_instance(&WbemLocator)?;
+    let server = unsafe {
+        locator.ConnectServer(
+            &BSTR::from(r"ROOT\CIMV2"),
+            &BSTR::new(),
+            &BSTR::new(),
+            &BSTR::new(),
+            0,
+            &BSTR::new(),
+            None,
+        )?
+    };
+    Ok(server)
+}
+
+fn get(object: &IWbemClassObject, name: PCWSTR) -> Result<VARIANT> {
+    let mut value = VARIANT::default();
+    unsafe { object.Get(name, 0, &mut value, None, None)? };
+    Ok(value)
+}
+
+/// Next object of the enumerator, `None` if the timeout expires or there are no more objects
+fn next(
+    enumerator: &IEnumWbemClassObject,
+    timeout: WBEM_TIMEOUT_TYPE,
+) -> Result<Option<IWbemClassObject>> {
+    let mut row = [None; 1];
+    let mut returned = 0;
+    unsafe { enumerator.Next(timeout, &mut row, &mut returned) }.ok()?;
+    Ok(row[0].take().filter(|_| returned > 0))
+}
+
+fn process_of(object: &IWbemClassObject) -> Result<ProcessInfo> {
+    let exe = BSTR::try_from(&get(object, w!("ExecutablePath"))?)
+        .ok()
+        .map(|path| path.to_string())
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from);
+    Ok(ProcessInfo {
+        pid: u32::try_from(&get(object, w!("ProcessId"))?)?,
+        parent_pid: u32::try_from(&get(object, w!("ParentProcessId"))?)?,
+        name: BSTR::try_from(&get(object, w!("Name"))?)?.to_string(),
+        exe,
+    })
+}
+
+fn event_of(object: &IWbemClassObject) -> Result<ProcessEvent> {
+    let kind = match BSTR::try_from(&get(object, w!("__CLASS"))?)?
+        .to_string()
+        .as_str()
+    {
+        "__InstanceCreationEvent" => ProcessEventKind::Started,
+        _ => ProcessEventKind::Stopped,
+    };
+    let target: IWbemClassObject =
+        IUnknown::try_from(&get(object, w!("TargetInstance"))?)?.cast()?;
+    Ok(ProcessEvent {
+        kind,
+        process: process_of(&target)?,
+    })
+}
+
+/// Creation and termination of all the processes, not only the ones with windows
+pub struct ProcessWatcher;
+
+impl ProcessWatcher {
+    fn on_event(event: ProcessEvent) -> Result<()> {
+        TriggerRunner::on_process_event(&event);
+        EventRegistry::emit(SeelenEvent::ProcessesChanged, &event)?;
+        Ok(())
+    }
+
+    fn listen() -> Result<()> {
+        Com::run_with_context(|| {
+            let server = connect()?;
+            let enumerator = unsafe {
+                server.ExecNotificationQuery(
+                    &BSTR::from("WQL"),
+                    &BSTR::from(EVENTS_QUERY),
+                    WBEM_FLAG_FORWARD_ONLY | WBEM_FLAG_RETURN_IMMEDIATELY,
+                    None,
+                )?
+            };
+            while Seelen::is_running() {
+                if let Some(object) = next(&enumerator, NEXT_TIMEOUT)? {
+                    log_error!(event_of(&object).and_then(Self::on_event));
+                }
+            }
+            Ok(())
+        })
+    }
+
+    pub fn start() -> Result<()> {
+        spawn_named_thread("Process Watcher", || {
+            log_error!(Self::listen());
+        })?;
+        Ok(())
+    }
+
+    pub fn running() -> Result<Vec<ProcessInfo>> {
+        Com::run_with_context(|| {
+            let server = connect()?;
+            let enumerator = unsafe {
+                server.ExecQuery(
+                    &BSTR::from("WQL"),
+                    &BSTR::from(PROCESSES_QUERY),
+                    WBEM_FLAG_FORWARD_ONLY | WBEM_FLAG_RETURN_IMMEDIATELY,
+                    None,
+                )?
+            };
+            let mut processes = Vec::new();
+            while let Some(object) = next(&enumerator, WBEM_INFINITE)? {
+                processes.push(process_of(&object)?);
+            }
+            Ok(processes)
+        })
+    }
+}
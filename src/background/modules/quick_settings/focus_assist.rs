@@ -0,0 +1,83 @@
+use std::ffi::c_void;
+
+use windows::Win32::Foundation::NTSTATUS;
+
+use crate::error_handler::Result;
+
+/// WNF_SHEL_QUIETHOURS_ACTIVE_PROFILE_CHANGED, published by the shell when focus assist changes
+const QUIET_HOURS_PROFILE: u64 = 0x0D83063EA3BF1C75;
+
+/// 0 is off, 1 is priority only and 2 is alarms only
+const PROFILE_OFF: u32 = 0;
+const PROFILE_PRIORITY_ONLY: u32 = 1;
+
+#[link(name = "ntdll")]
+extern "system" {
+    fn NtQueryWnfStateData(
+        state_name: *const u64,
+        type_id: *const c_void,
+        explicit_scope: *const c_void,
+        change_stamp: *mut u32,
+        buffer: *mut c_void,
+        buffer_size: *mut u32,
+    ) -> NTSTATUS;
+
+    fn NtUpdateWnfStateData(
+        state_name: *const u64,
+        buffer: *const c_void,
+        length: u32,
+        type_id: *const c_void,
+        explicit_scope: *const c_void,
+        matching_change_stamp: u32,
+        check_stamp: u32,
+    ) -> NTSTATUS;
+}
+
+/// Focus assist (do not disturb) has no public api, so the WNF state used by the shell is used
+pub struct FocusAssist;
+
+impl FocusAssist {
+    fn profile() -> Result<u32> {
+        let mut profile = 0u32;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        let mut stamp = 0u32;
+        unsafe {
+            NtQueryWnfStateData(
+                &QUIET_HOURS_PROFILE,
+                std::ptr::null(),
+                std::ptr::null(),
+                &mut stamp,
+                &mut profile as *mut u32 as *mut c_void,
+                &mut size,
+            )
+            .ok()?;
+        }
+        Ok(profile)
+    }
+
+    pub fn is_enabled() -> Result<bool> {
+        Ok(Self::profile()? != PROFILE_OFF)
+    }
+
+    /// Enabling uses the priority only profile, the same as the action center
+    pub fn set_enabled(enabled: bool) -> Result<()> {
+        let profile = if enabled {
+            PROFILE_PRIORITY_ONLY
+        } else {
+            PROFILE_OFF
+        };
+        unsafe {
+            NtUpdateWnfStateData(
+                &QUIET_HOURS_PROFILE,
+                &profile as *const u32 as *const c_void,
+                std::mem::size_of::<u32>() as u32,
+                std::ptr::null(),
+                std::ptr::null(),
+                0,
+                0,
+            )
+            .ok()?;
+        }
+        Ok(())
+    }
+}
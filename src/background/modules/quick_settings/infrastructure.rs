@@ -0,0 +1,25 @@
+use seelen_core::system_state::{QuickSettingsState, QuickSettingsToggle};
+
+use crate::error_handler::Result;
+
+use super::QuickSettings;
+
+#[tauri::command(async)]
+pub fn quick_settings_get_state() -> Result<QuickSettingsState> {
+    QuickSettings::get_state()
+}
+
+#[tauri::command(async)]
+pub fn quick_settings_toggle(toggle: QuickSettingsToggle) -> Result<()> {
+    QuickSettings::toggle(toggle)
+}
+
+#[tauri::command(async)]
+pub fn quick_settings_set_volume(level: f32) -> Result<()> {
+    QuickSettings::set_volume(level)
+}
+
+#[tauri::command(async)]
+pub fn quick_settings_set_brightness(value: u32) -> Result<()> {
+    QuickSettings::set_brightness(value)
+}
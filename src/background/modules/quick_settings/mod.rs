@@ -0,0 +1,220 @@
+mod focus_assist;
+pub mod infrastructure;
+
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use crossbeam_channel::{Receiver, Sender};
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{
+    handlers::SeelenEvent,
+    system_state::{QuickSettingsState, QuickSettingsToggle},
+};
+use windows::Devices::Radios::{Radio, RadioAccessStatus, RadioKind, RadioState};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::{
+        bluetooth::BluetoothManager,
+        brightness::BrightnessManager,
+        emit_scheduler::EmitScheduler,
+        event_registry::EventRegistry,
+        media::infrastructure::{
+            default_output, media_toggle_mute, register_media_events, set_volume_level,
+        },
+        network::infrastructure::register_network_events,
+        night_light::NightLight,
+        power::profile::PowerProfile,
+    },
+    trace_lock,
+    utils::spawn_named_thread,
+    windows_api::WindowsApi,
+};
+
+use focus_assist::FocusAssist;
+
+/// Changes closer than this are emitted together
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+lazy_static! {
+    /// last emitted state, so only changes are emitted
+    static ref LAST: Mutex<Option<QuickSettingsState>> = Mutex::new(None);
+    static ref CHANGES: (Sender<()>, Receiver<()>) = crossbeam_channel::unbounded();
+}
+
+static REGISTERED: AtomicBool = AtomicBool::new(false);
+
+/// Toggles and sliders of the quick settings panel on a single state, built over the
+/// modules owning each of them so a panel doesn't need to listen to all of them.
+pub struct QuickSettings;
+
+impl QuickSettings {
+    fn wifi_radio() -> Result<Option<Radio>> {
+        let radios = Radio::GetRadiosAsync()?.get()?;
+        Ok(radios
+            .into_iter()
+            .find(|radio| radio.Kind().is_ok_and(|kind| kind == RadioKind::WiFi)))
+    }
+
+    fn set_wifi(enabled: bool) -> Result<()> {
+        if Radio::RequestAccessAsync()?.get()? != RadioAccessStatus::Allowed {
+            return Err("Access to the wifi radio was denied".into());
+        }
+        let radio = Self::wifi_radio()?.ok_or("No wifi adapter found")?;
+        let state = if enabled {
+            RadioState::On
+        } else {
+            RadioState::Off
+        };
+        if radio.SetStateAsync(state)?.get()? != RadioAccessStatus::Allowed {
+            return Err("Failed to change the state of the wifi radio".into());
+        }
+        Ok(())
+    }
+
+    fn main_monitor_id() -> Result<String> {
+        WindowsApi::monitor_name(WindowsApi::primary_monitor())
+    }
+
+    /// Brightness of the main monitor as a percent of its range
+    fn brightness() -> Option<u32> {
+        let id = Self::main_monitor_id().ok()?;
+        let monitor = BrightnessManager::get_all()
+            .ok()?
+            .into_iter()
+            .find(|m| m.id == id)?;
+        let range = monitor.max.saturating_sub(monitor.min);
+        if range == 0 {
+            return Some(100);
+        }
+        let value = monitor.current.clamp(monitor.min, monitor.max) - monitor.min;
+        Some((value as f32 / range as f32 * 100.0).round() as u32)
+    }
+
+    pub fn get_state() -> Result<QuickSettingsState> {
+        let wifi = match Self::wifi_radio()? {
+            Some(radio) => Some(radio.State()? == RadioState::On),
+            None => None,
+        };
+        let bluetooth = BluetoothManager::get_state()?;
+
+        let (volume, muted) = match default_output() {
+            Some((_, volume, muted)) => (Some(volume), muted),
+            None => (None, false),
+        };
+
+        Ok(QuickSettingsState {
+            wifi,
+            bluetooth: bluetooth.available.then_some(bluetooth.enabled),
+            night_light: NightLight::get_state().active,
+            do_not_disturb: FocusAssist::is_enabled().ok(),
+            volume,
+            muted,
+            brightness: Self::brightness(),
+        })
+    }
+
+    pub fn toggle(toggle: QuickSettingsToggle) -> Result<()> {
+        let state = Self::get_state()?;
+        match toggle {
+            QuickSettingsToggle::Wifi => {
+                let enabled = state.wifi.ok_or("No wifi adapter found")?;
+                Self::set_wifi(!enabled)?;
+            }
+            QuickSettingsToggle::Bluetooth => BluetoothManager::toggle()?,
+            QuickSettingsToggle::NightLight => NightLight::toggle()?,
+            QuickSettingsToggle::DoNotDisturb => {
+                let enabled = state
+                    .do_not_disturb
+                    .ok_or("The state of focus assist can't be read")?;
+                FocusAssist::set_enabled(!enabled)?;
+            }
+            QuickSettingsToggle::Mute => {
+                let (id, _, _) = default_output().ok_or("No output device found")?;
+                media_toggle_mute(id, None)?;
+            }
+        }
+        Self::notify();
+        Ok(())
+    }
+
+    /// Volume of the default output device from 0 to 1
+    pub fn set_volume(level: f32) -> Result<()> {
+        let (id, _, _) = default_output().ok_or("No output device found")?;
+        set_volume_level(id, None, level.clamp(0.0, 1.0))?;
+        Self::notify();
+        Ok(())
+    }
+
+    /// Brightness of the main monitor from 0 to 100, the others follow if they are linked
+    pub fn set_brightness(percent: u32) -> Result<()> {
+        let id = Self::main_monitor_id()?;
+        let monitor = BrightnessManager::get_all()?
+            .into_iter()
+            .find(|m| m.id == id)
+            .ok_or("The main monitor has no brightness control")?;
+        let range = monitor.max.saturating_sub(monitor.min) as f32;
+        let value = monitor.min + (range * percent.min(100) as f32 / 100.0).round() as u32;
+        BrightnessManager::set(&id, value)?;
+        Self::notify();
+        Ok(())
+    }
+
+    fn notify() {
+        let _ = CHANGES.0.send(());
+    }
+
+    pub fn emit_changes() -> Result<()> {
+        let state = Self::get_state()?;
+        let mut last = trace_lock!(LAST);
+        if last.as_ref() != Some(&state) {
+            EventRegistry::emit(SeelenEvent::QuickSettingsChanged, &state)?;
+            *last = Some(state);
+        }
+        Ok(())
+    }
+
+    /// Follows the events of the modules behind the state, wifi and focus assist have no
+    /// events so they are polled.
+    pub fn register_events() -> Result<()> {
+        if REGISTERED.swap(true, Ordering::SeqCst) {
+            // new panels need the current state
+            trace_lock!(LAST).take();
+            Self::notify();
+            return Ok(());
+        }
+
+        log_error!(register_network_events());
+        log_error!(BrightnessManager::register_events());
+        log_error!(BluetoothManager::register_events());
+        register_media_events();
+
+        spawn_named_thread("Quick Settings", || {
+            while CHANGES.1.recv().is_ok() {
+                std::thread::sleep(DEBOUNCE);
+                CHANGES.1.try_iter().for_each(drop);
+                log_error!(Self::emit_changes());
+            }
+        })?;
+
+        spawn_named_thread("Quick Settings Polling", || loop {
+            Self::notify();
+            std::thread::sleep(PowerProfile::sampling_interval(Duration::from_secs(5)));
+        })?;
+
+        for event in [
+            SeelenEvent::BluetoothChanged,
+            SeelenEvent::NightLightChanged,
+            SeelenEvent::BrightnessChanged,
+            SeelenEvent::MediaOutputs,
+            SeelenEvent::NetworkAdapters,
+        ] {
+            EmitScheduler::listen_any(event, |_| Self::notify());
+        }
+        Ok(())
+    }
+}
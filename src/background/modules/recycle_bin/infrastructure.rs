@@ -0,0 +1,20 @@
+use seelen_core::system_state::RecycleBinState;
+
+use crate::error_handler::Result;
+
+use super::RecycleBin;
+
+#[tauri::command(async)]
+pub fn get_recycle_bin_state() -> Result<RecycleBinState> {
+    RecycleBin::get_state()
+}
+
+#[tauri::command(async)]
+pub fn empty_recycle_bin() -> Result<()> {
+    RecycleBin::empty()
+}
+
+#[tauri::command(async)]
+pub fn open_recycle_bin() -> Result<()> {
+    RecycleBin::open()
+}
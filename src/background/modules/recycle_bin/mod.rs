@@ -0,0 +1,188 @@
+pub mod infrastructure;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{handlers::SeelenEvent, system_state::RecycleBinState};
+use tauri_plugin_shell::ShellExt;
+use windows::{
+    core::{w, PCWSTR},
+    Win32::{
+        Foundation::{HANDLE, HWND, LPARAM, LRESULT, WPARAM},
+        System::Com::CoTaskMemFree,
+        UI::{
+            Shell::{
+                FOLDERID_RecycleBinFolder, SHCNRF_InterruptLevel, SHCNRF_ShellLevel,
+                SHChangeNotifyEntry, SHChangeNotifyRegister, SHEmptyRecycleBinW,
+                SHGetKnownFolderIDList, SHQueryRecycleBinW, KF_FLAG_DEFAULT, SHCNE_ALLEVENTS,
+                SHERB_NOCONFIRMATION, SHERB_NOPROGRESSUI, SHERB_NOSOUND, SHQUERYRBINFO,
+            },
+            WindowsAndMessaging::{
+                CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW,
+                TranslateMessage, MSG, WINDOW_EX_STYLE, WINDOW_STYLE, WM_APP, WNDCLASSW,
+            },
+        },
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::event_registry::EventRegistry,
+    seelen::get_app_handle,
+    trace_lock,
+    utils::{
+        event_queue::{EventQueue, QueueSender},
+        spawn_named_thread,
+    },
+    windows_api::WindowsApi,
+};
+
+const WM_RECYCLE_BIN_CHANGED: u32 = WM_APP + 1;
+
+lazy_static! {
+    /// last emitted state, so only changes are emitted
+    static ref LAST: Mutex<Option<RecycleBinState>> = Mutex::new(None);
+    /// deleting a folder sends a notification per item inside of it
+    static ref EMIT_QUEUE: Result<EventQueue<()>> = EventQueue::new("Recycle Bin", |_| Some(0), |_| {
+        log_error!(RecycleBin::emit_changes());
+    });
+}
+
+/// Items and size of the recycle bin of all the drives
+pub struct RecycleBin;
+
+impl RecycleBin {
+    unsafe extern "system" fn window_proc(
+        window: HWND,
+        message: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        match message {
+            WM_RECYCLE_BIN_CHANGED => {
+                EMIT_QUEUE.send(());
+                LRESULT(0)
+            }
+            _ => DefWindowProcW(window, message, wparam, lparam),
+        }
+    }
+
+    /// Shell operations over the recycle bin (delete, restore, empty) are notified to its folder
+    unsafe fn register_change_notify(hwnd: HWND) -> Result<()> {
+        let pidl = SHGetKnownFolderIDList(
+            &FOLDERID_RecycleBinFolder,
+            KF_FLAG_DEFAULT,
+            HANDLE::default(),
+        )?;
+        let entry = SHChangeNotifyEntry {
+            pidl,
+            fRecursive: true.into(),
+        };
+        let id = SHChangeNotifyRegister(
+            hwnd,
+            SHCNRF_ShellLevel | SHCNRF_InterruptLevel,
+            SHCNE_ALLEVENTS.0 as i32,
+            WM_RECYCLE_BIN_CHANGED,
+            1,
+            &entry,
+        );
+        // the registration keeps its own copy of the pidl
+        CoTaskMemFree(Some(pidl as _));
+        if id == 0 {
+            return Err("Failed to register the recycle bin change notifications".into());
+        }
+        Ok(())
+    }
+
+    unsafe fn create_background_window() -> Result<()> {
+        let wide_class = w!("SeelenRecycleBin");
+        let h_module = WindowsApi::module_handle_w()?;
+
+        let wnd_class = WNDCLASSW {
+            lpfnWndProc: Some(Self::window_proc),
+            hInstance: h_module.into(),
+            lpszClassName: wide_class,
+            ..Default::default()
+        };
+
+        RegisterClassW(&wnd_class);
+
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            wide_class,
+            w!("Seelen Recycle Bin"),
+            WINDOW_STYLE::default(),
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            h_module,
+            None,
+        )?;
+
+        Self::register_change_notify(hwnd)?;
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, hwnd, 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+        Ok(())
+    }
+
+    pub fn start() -> Result<()> {
+        spawn_named_thread("Recycle Bin", || unsafe {
+            log_error!(Self::create_background_window());
+        })?;
+        Ok(())
+    }
+
+    pub fn get_state() -> Result<RecycleBinState> {
+        let mut info = SHQUERYRBINFO {
+            cbSize: std::mem::size_of::<SHQUERYRBINFO>() as u32,
+            ..Default::default()
+        };
+        // a null root path queries the recycle bins of all the drives
+        unsafe { SHQueryRecycleBinW(PCWSTR::null(), &mut info)? };
+        Ok(RecycleBinState {
+            items: info.i64NumItems.max(0) as u64,
+            size: info.i64Size.max(0) as u64,
+        })
+    }
+
+    pub fn empty() -> Result<()> {
+        // emptying an already empty recycle bin fails
+        if Self::get_state()?.items == 0 {
+            return Ok(());
+        }
+        unsafe {
+            SHEmptyRecycleBinW(
+                HWND::default(),
+                PCWSTR::null(),
+                SHERB_NOCONFIRMATION | SHERB_NOPROGRESSUI | SHERB_NOSOUND,
+            )?
+        };
+        Self::emit_changes()
+    }
+
+    pub fn open() -> Result<()> {
+        get_app_handle()
+            .shell()
+            .command("cmd")
+            .args(["/c", "explorer", "shell:RecycleBinFolder"])
+            .spawn()?;
+        Ok(())
+    }
+
+    pub fn emit_changes() -> Result<()> {
+        let state = Self::get_state()?;
+        let mut last = trace_lock!(LAST);
+        if last.as_ref() != Some(&state) {
+            EventRegistry::emit(SeelenEvent::RecycleBinChanged, &state)?;
+            *last = Some(state);
+        }
+        Ok(())
+    }
+}
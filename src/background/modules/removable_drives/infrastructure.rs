@@ -0,0 +1,15 @@
+use seelen_core::system_state::RemovableDrive;
+
+use crate::error_handler::Result;
+
+use super::RemovableDrives;
+
+#[tauri::command(async)]
+pub fn removable_drives_get_all() -> Vec<RemovableDrive> {
+    RemovableDrives::get_all()
+}
+
+#[tauri::command(async)]
+pub fn removable_drive_eject(letter: char) -> Result<()> {
+    RemovableDrives::eject(letter)
+}
@@ -0,0 +1,310 @@
+pub mod infrastructure;
+
+use std::{ffi::c_void, time::Duration};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{
+    handlers::SeelenEvent,
+    system_state::{RemovableDrive, RemovableDriveKind},
+};
+use widestring::U16CStr;
+use windows::{
+    core::{w, HSTRING},
+    Win32::{
+        Foundation::{
+            CloseHandle, GENERIC_READ, GENERIC_WRITE, HANDLE, HWND, LPARAM, LRESULT, WPARAM,
+        },
+        Storage::FileSystem::{
+            BusTypeUsb, CreateFileW, GetDiskFreeSpaceExW, GetDriveTypeW, GetLogicalDrives,
+            GetVolumeInformationW, FILE_FLAGS_AND_ATTRIBUTES, FILE_SHARE_READ, FILE_SHARE_WRITE,
+            OPEN_EXISTING,
+        },
+        System::{
+            Ioctl::{
+                PropertyStandardQuery, StorageDeviceProperty, FSCTL_DISMOUNT_VOLUME,
+                FSCTL_LOCK_VOLUME, IOCTL_STORAGE_EJECT_MEDIA, IOCTL_STORAGE_MEDIA_REMOVAL,
+                IOCTL_STORAGE_QUERY_PROPERTY, PREVENT_MEDIA_REMOVAL, STORAGE_DEVICE_DESCRIPTOR,
+                STORAGE_PROPERTY_QUERY,
+            },
+            WindowsProgramming::{DRIVE_CDROM, DRIVE_FIXED, DRIVE_REMOVABLE},
+            IO::DeviceIoControl,
+        },
+        UI::WindowsAndMessaging::{
+            CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW,
+            TranslateMessage, DBT_DEVICEARRIVAL, DBT_DEVICEREMOVECOMPLETE, MSG, WINDOW_EX_STYLE,
+            WINDOW_STYLE, WM_DEVICECHANGE, WNDCLASSW,
+        },
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::event_registry::EventRegistry,
+    state::application::FULL_STATE,
+    trace_lock,
+    utils::{
+        event_queue::{EventQueue, QueueSender},
+        spawn_named_thread,
+    },
+    windows_api::WindowsApi,
+};
+
+/// the volume can't be locked while a file is open on it, so it is retried for a while
+const LOCK_RETRIES: u32 = 10;
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(300);
+
+lazy_static! {
+    /// last emitted drives, so only changes are emitted
+    static ref LAST: Mutex<Option<Vec<RemovableDrive>>> = Mutex::new(None);
+    /// a single device can send several notifications (one per volume/interface)
+    static ref EMIT_QUEUE: Result<EventQueue<()>> = EventQueue::new("Removable Drives", |_| Some(0), |_| {
+        log_error!(RemovableDrives::emit_changes());
+    });
+}
+
+/// USB, memory cards and optical drives, with safe eject
+pub struct RemovableDrives;
+
+impl RemovableDrives {
+    unsafe extern "system" fn window_proc(
+        window: HWND,
+        message: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        match message {
+            WM_DEVICECHANGE => {
+                if matches!(
+                    wparam.0 as u32,
+                    DBT_DEVICEARRIVAL | DBT_DEVICEREMOVECOMPLETE
+                ) {
+                    EMIT_QUEUE.send(());
+                }
+                LRESULT(1)
+            }
+            _ => DefWindowProcW(window, message, wparam, lparam),
+        }
+    }
+
+    /// Volume arrivals are broadcasted to all the top level windows, so no registration is needed
+    unsafe fn create_background_window() -> Result<()> {
+        let wide_class = w!("SeelenRemovableDrives");
+        let h_module = WindowsApi::module_handle_w()?;
+
+        let wnd_class = WNDCLASSW {
+            lpfnWndProc: Some(Self::window_proc),
+            hInstance: h_module.into(),
+            lpszClassName: wide_class,
+            ..Default::default()
+        };
+
+        RegisterClassW(&wnd_class);
+
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            wide_class,
+            w!("Seelen Removable Drives"),
+            WINDOW_STYLE::default(),
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            h_module,
+            None,
+        )?;
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, hwnd, 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+        Ok(())
+    }
+
+    pub fn start() -> Result<()> {
+        spawn_named_thread("Removable Drives", || unsafe {
+            log_error!(Self::create_background_window());
+        })?;
+        Ok(())
+    }
+
+    fn open_volume(letter: char, access: u32) -> Result<HANDLE> {
+        let path = HSTRING::from(format!(r"\\.\{}:", letter));
+        let handle = unsafe {
+            CreateFileW(
+                &path,
+                access,
+                FILE_SHARE_READ | FILE_SHARE_WRITE,
+                None,
+                OPEN_EXISTING,
+                FILE_FLAGS_AND_ATTRIBUTES(0),
+                HANDLE::default(),
+            )?
+        };
+        Ok(handle)
+    }
+
+    unsafe fn io_control(
+        handle: HANDLE,
+        code: u32,
+        input: Option<(*const c_void, u32)>,
+    ) -> Result<()> {
+        let mut returned = 0;
+        DeviceIoControl(
+            handle,
+            code,
+            input.map(|(ptr, _)| ptr),
+            input.map_or(0, |(_, size)| size),
+            None,
+            0,
+            Some(&mut returned),
+            None,
+        )?;
+        Ok(())
+    }
+
+    /// External disks are reported as fixed drives, so the bus is needed to tell them apart
+    fn is_usb(letter: char) -> bool {
+        let Ok(handle) = Self::open_volume(letter, 0) else {
+            return false;
+        };
+        let query = STORAGE_PROPERTY_QUERY {
+            PropertyId: StorageDeviceProperty,
+            QueryType: PropertyStandardQuery,
+            ..Default::default()
+        };
+        let mut descriptor = STORAGE_DEVICE_DESCRIPTOR::default();
+        let mut returned = 0;
+        let result = unsafe {
+            DeviceIoControl(
+                handle,
+                IOCTL_STORAGE_QUERY_PROPERTY,
+                Some(&query as *const _ as *const c_void),
+                std::mem::size_of::<STORAGE_PROPERTY_QUERY>() as u32,
+                Some(&mut descriptor as *mut _ as *mut c_void),
+                std::mem::size_of::<STORAGE_DEVICE_DESCRIPTOR>() as u32,
+                Some(&mut returned),
+                None,
+            )
+        };
+        unsafe {
+            let _ = CloseHandle(handle);
+        }
+        result.is_ok() && descriptor.BusType == BusTypeUsb
+    }
+
+    fn kind_of(letter: char, root: &HSTRING) -> Option<RemovableDriveKind> {
+        match unsafe { GetDriveTypeW(root) } {
+            DRIVE_CDROM => Some(RemovableDriveKind::Optical),
+            DRIVE_REMOVABLE if Self::is_usb(letter) => Some(RemovableDriveKind::Usb),
+            DRIVE_REMOVABLE => Some(RemovableDriveKind::Card),
+            DRIVE_FIXED if Self::is_usb(letter) => Some(RemovableDriveKind::Usb),
+            _ => None,
+        }
+    }
+
+    /// Drives without media (e.g. empty card readers) are not included
+    pub fn get_all() -> Vec<RemovableDrive> {
+        let mask = unsafe { GetLogicalDrives() };
+        drive_letters(mask)
+            .filter_map(|letter| {
+                let root = HSTRING::from(format!(r"{}:\", letter));
+                let kind = Self::kind_of(letter, &root)?;
+
+                let (mut free, mut total) = (0, 0);
+                unsafe { GetDiskFreeSpaceExW(&root, Some(&mut free), Some(&mut total), None) }
+                    .ok()?;
+
+                let mut label = [0u16; 261];
+                unsafe { GetVolumeInformationW(&root, Some(&mut label), None, None, None, None) }
+                    .ok()?;
+
+                Some(RemovableDrive {
+                    letter,
+                    label: U16CStr::from_slice_truncate(&label)
+                        .map(|label| label.to_string_lossy())
+                        .unwrap_or_default(),
+                    kind,
+                    total,
+                    free,
+                })
+            })
+            .collect()
+    }
+
+    unsafe fn eject_volume(handle: HANDLE) -> Result<()> {
+        let mut locked = false;
+        for _ in 0..LOCK_RETRIES {
+            if Self::io_control(handle, FSCTL_LOCK_VOLUME, None).is_ok() {
+                locked = true;
+                break;
+            }
+            std::thread::sleep(LOCK_RETRY_INTERVAL);
+        }
+        if !locked {
+            return Err("The drive is in use by another application".into());
+        }
+
+        Self::io_control(handle, FSCTL_DISMOUNT_VOLUME, None)?;
+        // allow the removal in case that it was prevented by some application
+        let removal = PREVENT_MEDIA_REMOVAL::default();
+        Self::io_control(
+            handle,
+            IOCTL_STORAGE_MEDIA_REMOVAL,
+            Some((
+                &removal as *const _ as *const c_void,
+                std::mem::size_of::<PREVENT_MEDIA_REMOVAL>() as u32,
+            )),
+        )?;
+        Self::io_control(handle, IOCTL_STORAGE_EJECT_MEDIA, None)
+    }
+
+    /// Flushes and dismounts the volume before ejecting it, so no data is lost
+    pub fn eject(letter: char) -> Result<()> {
+        let letter = letter.to_ascii_uppercase();
+        if !Self::get_all().iter().any(|drive| drive.letter == letter) {
+            return Err(format!("{}: is not a removable drive", letter).into());
+        }
+        let handle = Self::open_volume(letter, GENERIC_READ.0 | GENERIC_WRITE.0)?;
+        let result = unsafe { Self::eject_volume(handle) };
+        unsafe {
+            let _ = CloseHandle(handle);
+        }
+        result?;
+        Self::emit_changes()
+    }
+
+    pub fn emit_changes() -> Result<()> {
+        let drives = Self::get_all();
+        let mut last = trace_lock!(LAST);
+        if last.as_ref() != Some(&drives) {
+            EventRegistry::emit(SeelenEvent::RemovableDrivesChanged, &drives)?;
+            *last = Some(drives);
+        }
+        // pinned items on a connected drive are available again
+        FULL_STATE.load().refresh_weg_items()
+    }
+}
+
+/// Letters of the drives on the mask returned by `GetLogicalDrives`, bit 0 is `A`
+fn drive_letters(mask: u32) -> impl Iterator<Item = char> {
+    (0..26u8)
+        .filter(move |bit| mask & (1 << bit) != 0)
+        .map(|bit| (b'A' + bit) as char)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drive_letters() {
+        assert_eq!(drive_letters(0b10101).collect::<String>(), "ACE");
+        assert_eq!(drive_letters(1 << 25).collect::<String>(), "Z");
+        assert_eq!(drive_letters(0).count(), 0);
+    }
+}
@@ -0,0 +1,253 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::OsStr,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use sysinfo::System;
+use tauri::{Url, WebviewWindow};
+use windows::Win32::Foundation::HWND;
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::{
+        clock::Clock, power::profile::PowerProfile, shortcuts::ShortcutResolver,
+        supervisor::WebviewSupervisor,
+    },
+    seelen::{Seelen, SEELEN},
+    state::application::FULL_STATE,
+    trace_lock,
+    utils::spawn_named_thread,
+    windows_api::WindowsApi,
+};
+
+lazy_static! {
+    /// label of the suspended webviews -> url to load when resumed
+    static ref SUSPENDED: Mutex<HashMap<String, Url>> = Mutex::new(HashMap::new());
+    /// label of the hidden webviews -> since when
+    static ref HIDDEN_SINCE: Mutex<HashMap<String, Instant>> = Mutex::new(HashMap::new());
+    /// label of the toolbars alive
+    static ref TOOLBARS: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+static DISPLAY_OFF: AtomicBool = AtomicBool::new(false);
+/// the caches are trimmed once each time the memory usage crosses the threshold
+static UNDER_PRESSURE: AtomicBool = AtomicBool::new(false);
+/// cached on each visibility change, so the clock ticks don't have to look at the toolbars
+static TOOLBAR_VISIBLE: AtomicBool = AtomicBool::new(false);
+
+/// Keeps the idle footprint low, the webviews that can't be seen are unloaded into a blank
+/// page and the caches are trimmed when the system is running out of memory.
+pub struct ResourceGovernor;
+
+impl ResourceGovernor {
+    const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+    pub fn start() -> Result<()> {
+        spawn_named_thread("Resource Governor", || loop {
+            std::thread::sleep(PowerProfile::sampling_interval(Self::CHECK_INTERVAL));
+            if !Seelen::is_running() {
+                break;
+            }
+            log_error!(Self::check());
+        })?;
+        Ok(())
+    }
+
+    pub fn is_suspended(label: &str) -> bool {
+        trace_lock!(SUSPENDED).contains_key(label)
+    }
+
+    /// Called by the surfaces when they are hidden or shown, shown surfaces are loaded again
+    pub fn on_visibility_change(window: &WebviewWindow, visible: bool) -> Result<()> {
+        let label = window.label().to_string();
+        if !visible {
+            trace_lock!(HIDDEN_SINCE)
+                .entry(label)
+                .or_insert_with(Instant::now);
+            Self::update_toolbar_visibility();
+            return Ok(());
+        }
+        trace_lock!(HIDDEN_SINCE).remove(&label);
+        Self::update_toolbar_visibility();
+        // nothing can be seen while the displays are off
+        if !DISPLAY_OFF.load(Ordering::Acquire) {
+            Self::resume(window)?;
+        }
+        Ok(())
+    }
+
+    /// Whether any toolbar can be seen, the updates only shown on them can slow down otherwise
+    pub fn is_toolbar_visible() -> bool {
+        TOOLBAR_VISIBLE.load(Ordering::Acquire)
+    }
+
+    fn update_toolbar_visibility() {
+        // stored while locked, so concurrent updates can't leave an outdated value
+        let hidden = trace_lock!(HIDDEN_SINCE);
+        let visible = !DISPLAY_OFF.load(Ordering::Acquire)
+            && trace_lock!(TOOLBARS)
+                .iter()
+                .any(|label| !hidden.contains_key(label));
+        TOOLBAR_VISIBLE.store(visible, Ordering::Release);
+    }
+
+    /// Called by the toolbars on creation, they are considered visible until hidden
+    pub fn on_toolbar_created(window: &WebviewWindow) {
+        trace_lock!(TOOLBARS).insert(window.label().to_string());
+        Self::update_toolbar_visibility();
+    }
+
+    pub fn forget(window: &WebviewWindow) {
+        trace_lock!(SUSPENDED).remove(window.label());
+        trace_lock!(HIDDEN_SINCE).remove(window.label());
+        trace_lock!(TOOLBARS).remove(window.label());
+        Self::update_toolbar_visibility();
+    }
+
+    /// Windows only notifies the state of the console display, so all the monitors are
+    /// considered turned off together.
+    pub fn on_display_state(on: bool) {
+        if DISPLAY_OFF.swap(!on, Ordering::AcqRel) == !on {
+            return;
+        }
+        log::trace!("Displays turned {}", if on { "on" } else { "off" });
+        Self::update_toolbar_visibility();
+        if on {
+            Clock::refresh();
+        }
+        // called from the window procedure of the power events, so it is not blocked
+        log_error!(spawn_named_thread("Resource Governor Display", move || {
+            log_error!(Self::apply_display_state(on));
+        }));
+    }
+
+    /// Webviews of the toolbars and docks, the wallpaper only if `with_wall`
+    fn surfaces(with_wall: bool) -> Vec<WebviewWindow> {
+        let seelen = trace_lock!(SEELEN);
+        let mut windows = Vec::new();
+        for monitor in seelen.monitors() {
+            windows.extend(monitor.toolbar().as_ref().map(|t| t.window().clone()));
+            windows.extend(monitor.weg().as_ref().map(|w| w.window().clone()));
+        }
+        if with_wall {
+            windows.extend(seelen.wall().as_ref().map(|w| w.window().clone()));
+        }
+        windows
+    }
+
+    fn apply_display_state(on: bool) -> Result<()> {
+        let settings = FULL_STATE.load().settings.resource_governor.clone();
+        for window in Self::surfaces(true) {
+            if on {
+                // surfaces hidden before the displays turned off keep waiting their timeout
+                if !trace_lock!(HIDDEN_SINCE).contains_key(window.label()) {
+                    Self::resume(&window)?;
+                }
+            } else if settings.enabled && settings.suspend_on_display_off {
+                Self::suspend(&window)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the content of the webview with a blank page, releasing the renderer memory
+    fn suspend(window: &WebviewWindow) -> Result<()> {
+        let label = window.label();
+        if Self::is_suspended(label) {
+            return Ok(());
+        }
+        let url = window.url()?;
+        window.navigate(Url::parse("about:blank").expect("valid url"))?;
+        trace_lock!(SUSPENDED).insert(label.to_string(), url);
+        log::info!("Webview {} suspended", label);
+        Ok(())
+    }
+
+    fn resume(window: &WebviewWindow) -> Result<()> {
+        let label = window.label();
+        let Some(url) = trace_lock!(SUSPENDED).remove(label) else {
+            return Ok(());
+        };
+        window.navigate(url)?;
+        // the supervisor gives the grace period of a new window while it loads again
+        WebviewSupervisor::on_heartbeat(label);
+        log::info!("Webview {} resumed", label);
+        Ok(())
+    }
+
+    fn memory_usage_percent() -> u32 {
+        let mut sys = System::new();
+        sys.refresh_memory();
+        match sys.total_memory() {
+            0 => 0,
+            total => (sys.used_memory() * 100 / total) as u32,
+        }
+    }
+
+    /// Removes the window previews of the dock and the switcher of windows already closed
+    fn trim_thumbnails() {
+        let entries = std::fs::read_dir(std::env::temp_dir())
+            .into_iter()
+            .flatten();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension() != Some(OsStr::new("png")) {
+                continue;
+            }
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            let addr = stem.strip_prefix("switcher-").unwrap_or(&stem);
+            if let Ok(addr) = addr.parse::<isize>() {
+                if !WindowsApi::is_window(HWND(addr as _)) {
+                    let _ = std::fs::remove_file(path);
+                }
+            }
+        }
+    }
+
+    fn trim_caches() {
+        log::info!("Memory pressure, trimming caches");
+        ShortcutResolver::invalidate(None);
+        Self::trim_thumbnails();
+    }
+
+    fn check() -> Result<()> {
+        let settings = FULL_STATE.load().settings.resource_governor.clone();
+        if !settings.enabled {
+            return Ok(());
+        }
+
+        let pressure = Self::memory_usage_percent() >= settings.memory_pressure_threshold;
+        if pressure && !UNDER_PRESSURE.swap(true, Ordering::AcqRel) {
+            Self::trim_caches();
+        }
+        if !pressure {
+            UNDER_PRESSURE.store(false, Ordering::Release);
+        }
+
+        // hidden surfaces are unloaded right away while the memory is scarce
+        let suspend_after = match pressure {
+            true => Duration::ZERO,
+            false => Duration::from_secs(settings.suspend_after),
+        };
+        let expired: Vec<String> = trace_lock!(HIDDEN_SINCE)
+            .iter()
+            .filter(|(_, since)| since.elapsed() >= suspend_after)
+            .map(|(label, _)| label.clone())
+            .collect();
+        if expired.is_empty() {
+            return Ok(());
+        }
+
+        for window in Self::surfaces(false) {
+            if expired.iter().any(|label| label == window.label()) {
+                Self::suspend(&window)?;
+            }
+        }
+        Ok(())
+    }
+}
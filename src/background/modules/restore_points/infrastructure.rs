@@ -0,0 +1,25 @@
+use seelen_core::system_state::{RestorePoint, RestorePointReason};
+
+use crate::error_handler::Result;
+
+use super::RestorePoints;
+
+#[tauri::command(async)]
+pub fn restore_points_get_all() -> Result<Vec<RestorePoint>> {
+    RestorePoints::list()
+}
+
+#[tauri::command(async)]
+pub fn restore_points_create() -> Result<RestorePoint> {
+    RestorePoints::create(RestorePointReason::Manual, None)
+}
+
+#[tauri::command(async)]
+pub fn restore_points_apply(id: String) -> Result<()> {
+    RestorePoints::apply(&id)
+}
+
+#[tauri::command(async)]
+pub fn restore_points_delete(id: String) -> Result<()> {
+    RestorePoints::delete(&id)
+}
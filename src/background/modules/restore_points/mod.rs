@@ -0,0 +1,165 @@
+pub mod infrastructure;
+
+use std::{
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use seelen_core::system_state::{RestorePoint, RestorePointReason};
+
+use tauri::Manager;
+
+use crate::{error_handler::Result, seelen::get_app_handle, utils::copy_dir};
+
+const METADATA_FILE: &str = "restore_point.json";
+/// automatic restore points are removed when this amount is exceeded, oldest first
+const MAX_RESTORE_POINTS: usize = 15;
+
+/// User config files and folders inside the data dir, icon packs and wallpapers are not
+/// included as these are big and are not modified by the resources.
+const SNAPSHOT_FILES: [&str; 3] = ["settings.json", "seelenweg_items.yaml", "applications.yml"];
+const SNAPSHOT_FOLDERS: [&str; 5] = ["themes", "placeholders", "layouts", "scripts", "widgets"];
+
+/// Snapshots of the config files, taken before changes that could break the setup
+pub struct RestorePoints;
+
+impl RestorePoints {
+    fn data_dir() -> Result<PathBuf> {
+        Ok(get_app_handle().path().app_data_dir()?)
+    }
+
+    fn folder() -> Result<PathBuf> {
+        Ok(Self::data_dir()?.join("restore"))
+    }
+
+    /// ids are used as folder names so only the generated ones are allowed
+    fn path_of(id: &str) -> Result<PathBuf> {
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_digit()) {
+            return Err(format!("Invalid restore point id: {}", id).into());
+        }
+        let path = Self::folder()?.join(id);
+        if !path.join(METADATA_FILE).exists() {
+            return Err(format!("Restore point not found: {}", id).into());
+        }
+        Ok(path)
+    }
+
+    pub fn create(reason: RestorePointReason, source: Option<String>) -> Result<RestorePoint> {
+        let mut timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        // two snapshots on the same millisecond, e.g. a rollback right after an import
+        while Self::folder()?.join(timestamp.to_string()).exists() {
+            timestamp += 1;
+        }
+
+        let point = RestorePoint {
+            id: timestamp.to_string(),
+            timestamp,
+            reason,
+            source,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        };
+        let destination = Self::folder()?.join(&point.id);
+        std::fs::create_dir_all(&destination)?;
+        if let Err(err) = copy_config(&Self::data_dir()?, &destination) {
+            std::fs::remove_dir_all(&destination)?;
+            return Err(err);
+        }
+        std::fs::write(
+            destination.join(METADATA_FILE),
+            serde_json::to_string_pretty(&point)?,
+        )?;
+
+        log::info!("Restore point {} created ({:?})", point.id, point.reason);
+        Self::prune()?;
+        Ok(point)
+    }
+
+    /// Same as `create` but failing to snapshot doesn't block the change that follows
+    pub fn create_before(reason: RestorePointReason, source: &str) {
+        if let Err(err) = Self::create(reason, Some(source.to_string())) {
+            log::error!("Failed to create restore point: {:?}", err);
+        }
+    }
+
+    /// Newest first
+    pub fn list() -> Result<Vec<RestorePoint>> {
+        let folder = Self::folder()?;
+        if !folder.exists() {
+            return Ok(Vec::new());
+        }
+        let mut points = Vec::new();
+        for entry in std::fs::read_dir(folder)?.flatten() {
+            let path = entry.path().join(METADATA_FILE);
+            if let Ok(content) = std::fs::read_to_string(path) {
+                if let Ok(point) = serde_json::from_str::<RestorePoint>(&content) {
+                    points.push(point);
+                }
+            }
+        }
+        points.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        Ok(points)
+    }
+
+    /// Manual restore points are kept until the user deletes them
+    fn prune() -> Result<()> {
+        let automatic = Self::list()?
+            .into_iter()
+            .filter(|point| point.reason != RestorePointReason::Manual);
+        for point in automatic.skip(MAX_RESTORE_POINTS) {
+            std::fs::remove_dir_all(Self::folder()?.join(point.id))?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the current config with the snapshot, the file watcher reloads the state.
+    /// The current config is saved first, so applying a restore point can be reverted too.
+    pub fn apply(id: &str) -> Result<()> {
+        let source = Self::path_of(id)?;
+        Self::create(RestorePointReason::Rollback, Some(id.to_string()))?;
+
+        let data_dir = Self::data_dir()?;
+        for file in SNAPSHOT_FILES {
+            let snapshot = source.join(file);
+            if snapshot.exists() {
+                std::fs::write(data_dir.join(file), std::fs::read(snapshot)?)?;
+            }
+        }
+        for folder in SNAPSHOT_FOLDERS {
+            // the folder itself is kept as it is being watched
+            let current = data_dir.join(folder);
+            if current.exists() {
+                for entry in std::fs::read_dir(&current)?.flatten() {
+                    let path = entry.path();
+                    match path.is_dir() {
+                        true => std::fs::remove_dir_all(path)?,
+                        false => std::fs::remove_file(path)?,
+                    }
+                }
+            }
+            copy_dir(&source.join(folder), &current)?;
+        }
+        log::info!("Restore point {} applied", id);
+        Ok(())
+    }
+
+    pub fn delete(id: &str) -> Result<()> {
+        std::fs::remove_dir_all(Self::path_of(id)?)?;
+        Ok(())
+    }
+}
+
+fn copy_config(data_dir: &Path, destination: &Path) -> Result<()> {
+    for file in SNAPSHOT_FILES {
+        let path = data_dir.join(file);
+        if path.exists() {
+            std::fs::copy(path, destination.join(file))?;
+        }
+    }
+    for folder in SNAPSHOT_FOLDERS {
+        copy_dir(&data_dir.join(folder), &destination.join(folder))?;
+    }
+    Ok(())
+}
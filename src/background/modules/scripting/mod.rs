@@ -0,0 +1,412 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use rhai::{
+    module_resolvers::FileModuleResolver, Dynamic, Engine, EvalAltResult, FnPtr, Map, Module,
+    ModuleResolver, Position, AST,
+};
+use serde_json::{json, Value};
+use tauri::{EventId, Manager};
+
+use crate::{
+    error_handler::Result,
+    modules::{
+        emit_scheduler::EmitScheduler,
+        ipc::{
+            application::IpcServer,
+            domain::{is_protected_setting, RpcError},
+        },
+    },
+    seelen::{get_app_handle, Seelen},
+    state::application::FullState,
+    trace_lock,
+    utils::spawn_named_thread,
+};
+
+lazy_static! {
+    static ref COMMANDS: (Sender<ScriptCommand>, Receiver<ScriptCommand>) =
+        crossbeam_channel::unbounded();
+}
+
+type RhaiResult = std::result::Result<Dynamic, Box<rhai::EvalAltResult>>;
+
+enum ScriptCommand {
+    Reload,
+    Call {
+        script: String,
+        callback: FnPtr,
+        payload: Value,
+    },
+    /// function declared on the top level of a script
+    Invoke {
+        script: String,
+        function: String,
+    },
+}
+
+struct ScriptTimer {
+    script: String,
+    callback: FnPtr,
+    next: Instant,
+    /// `None` for one shot timers
+    interval: Option<Duration>,
+}
+
+/// Callbacks registered by the scripts, owned by the script that was running while registering them
+#[derive(Default)]
+struct Registry {
+    current: String,
+    listeners: Vec<(String, EventId)>,
+    timers: Vec<ScriptTimer>,
+}
+
+impl Registry {
+    fn listen(&mut self, event: &str, callback: FnPtr) {
+        let script = self.current.clone();
+        let id = EmitScheduler::listen_any(event, move |e| {
+            let _ = COMMANDS.0.send(ScriptCommand::Call {
+                script: script.clone(),
+                callback: callback.clone(),
+                payload: serde_json::from_str(e.payload()).unwrap_or_default(),
+            });
+        });
+        self.listeners.push((event.to_string(), id));
+    }
+
+    fn add_timer(&mut self, millis: i64, callback: FnPtr, repeat: bool) {
+        let interval = Duration::from_millis(millis.max(ScriptingEngine::MIN_INTERVAL) as u64);
+        self.timers.push(ScriptTimer {
+            script: self.current.clone(),
+            callback,
+            next: Instant::now() + interval,
+            interval: repeat.then_some(interval),
+        });
+    }
+
+    fn clear(&mut self) {
+        for (event, id) in self.listeners.drain(..) {
+            EmitScheduler::unlisten(&event, id);
+        }
+        self.timers.clear();
+    }
+}
+
+/// Resolves the `import` of the scripts, only the files inside the scripts folder can be imported
+struct ScriptsResolver {
+    folder: PathBuf,
+    files: FileModuleResolver,
+}
+
+impl ScriptsResolver {
+    fn new(folder: PathBuf) -> Self {
+        Self {
+            files: FileModuleResolver::new_with_path(folder.clone()),
+            folder,
+        }
+    }
+
+    fn is_inside(&self, file: &Path) -> bool {
+        match (file.canonicalize(), self.folder.canonicalize()) {
+            (Ok(file), Ok(folder)) => file.starts_with(folder),
+            _ => false,
+        }
+    }
+}
+
+impl ModuleResolver for ScriptsResolver {
+    fn resolve(
+        &self,
+        engine: &Engine,
+        source: Option<&str>,
+        path: &str,
+        pos: Position,
+    ) -> std::result::Result<Arc<Module>, Box<EvalAltResult>> {
+        // `..` and absolute paths could load any file
+        if !self.is_inside(&self.files.get_file_path(path, None)) {
+            return Err(EvalAltResult::ErrorModuleNotFound(path.to_string(), pos).into());
+        }
+        self.files.resolve(engine, source, path, pos)
+    }
+}
+
+/// Runs the user scripts (`data_dir/scripts/*.rhai`) on a dedicated thread.
+///
+/// Scripts only have access to the functions registered here, the top level code runs on load
+/// and should register the callbacks via `on`, `every` and `after`.
+pub struct ScriptingEngine {
+    engine: Engine,
+    registry: Arc<Mutex<Registry>>,
+    scripts: HashMap<String, AST>,
+    folder: PathBuf,
+}
+
+impl ScriptingEngine {
+    const EXTENSION: &'static str = "rhai";
+    /// min interval in milliseconds allowed for timers
+    const MIN_INTERVAL: i64 = 50;
+
+    pub fn start() -> Result<()> {
+        let folder = get_app_handle().path().app_data_dir()?.join("scripts");
+        spawn_named_thread("Scripting Engine", move || {
+            let mut scripting = Self::new(folder);
+            scripting.load_all();
+            scripting.run();
+        })?;
+        Ok(())
+    }
+
+    /// Reloads all the scripts, called by the file watcher
+    pub fn reload() {
+        let _ = COMMANDS.0.send(ScriptCommand::Reload);
+    }
+
+    /// Calls a function of a loaded script, used by the toolbar items
+    pub fn invoke(script: String, function: String) {
+        let _ = COMMANDS.0.send(ScriptCommand::Invoke { script, function });
+    }
+
+    fn ipc(method: &str, params: Value) -> RhaiResult {
+        let value = IpcServer::handle(method, params).map_err(|e| e.message)?;
+        rhai::serde::to_dynamic(value)
+    }
+
+    fn set_setting(path: &str, value: Dynamic) -> RhaiResult {
+        if is_protected_setting(path) {
+            return Err(format!("{} can't be changed by scripts", path).into());
+        }
+        let value: Value = rhai::serde::from_dynamic(&value)?;
+        let pointer = format!("/{}", path.replace('.', "/"));
+        FullState::patch_settings(&pointer, value).map_err(|e| RpcError::from(e).message)?;
+        Ok(Dynamic::UNIT)
+    }
+
+    fn run_action(id: &str, params: Map) -> RhaiResult {
+        let params: Value = rhai::serde::from_dynamic(&params.into())?;
+        Self::ipc("runAction", json!({ "id": id, "params": params }))
+    }
+
+    fn new(folder: PathBuf) -> Self {
+        let registry = Arc::new(Mutex::new(Registry::default()));
+        let mut engine = Engine::new();
+
+        // sandbox limits, a bad script should not freeze the thread or eat the memory
+        engine.set_max_operations(1_000_000);
+        engine.set_max_call_levels(32);
+        engine.set_max_expr_depths(64, 32);
+        engine.set_max_string_size(1024 * 1024);
+        engine.set_max_array_size(10_000);
+        engine.set_max_map_size(10_000);
+        engine.disable_symbol("eval");
+        engine.set_module_resolver(ScriptsResolver::new(folder.clone()));
+
+        engine.on_print(|text| log::info!(target: "slu::script", "{}", text));
+        engine.on_debug(|text, _, pos| log::debug!(target: "slu::script", "{:?} {}", pos, text));
+
+        // queries
+        engine.register_fn("get_settings", || Self::ipc("getSettings", Value::Null));
+        engine.register_fn("get_workspaces", || Self::ipc("getWorkspaces", Value::Null));
+        engine.register_fn("get_focused_window", || {
+            Self::ipc("getFocusedWindow", Value::Null)
+        });
+        engine.register_fn("get_windows", || Self::ipc("getWindows", Value::Null));
+
+        // actions
+        engine.register_fn("switch_workspace", |index: i64| {
+            Self::ipc("switchWorkspace", json!({ "index": index }))
+        });
+        engine.register_fn("send_to_workspace", |index: i64| {
+            Self::ipc("sendToWorkspace", json!({ "index": index }))
+        });
+        engine.register_fn("move_window", |x: i64, y: i64| {
+            Self::ipc("moveWindow", json!({ "x": x, "y": y }))
+        });
+        engine.register_fn("set_setting", Self::set_setting);
+        engine.register_fn("run_action", |id: &str| Self::run_action(id, Map::new()));
+        engine.register_fn("run_action", Self::run_action);
+
+        // events and timers
+        let r = registry.clone();
+        engine.register_fn("on", move |event: &str, callback: FnPtr| {
+            trace_lock!(r).listen(event, callback)
+        });
+        let r = registry.clone();
+        engine.register_fn("every", move |millis: i64, callback: FnPtr| {
+            trace_lock!(r).add_timer(millis, callback, true)
+        });
+        let r = registry.clone();
+        engine.register_fn("after", move |millis: i64, callback: FnPtr| {
+            trace_lock!(r).add_timer(millis, callback, false)
+        });
+
+        Self {
+            engine,
+            registry,
+            scripts: HashMap::new(),
+            folder,
+        }
+    }
+
+    fn load_all(&mut self) {
+        trace_lock!(self.registry).clear();
+        self.scripts.clear();
+
+        let entries = match std::fs::read_dir(&self.folder) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+
+        let paths = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == Self::EXTENSION))
+            .sorted();
+
+        for path in paths {
+            let name = path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            if let Err(err) = self.load(&name, &path) {
+                log::error!("Script {} failed to load: {}", name, err);
+            }
+        }
+        log::info!("{} scripts loaded", self.scripts.len());
+    }
+
+    fn load(&mut self, name: &str, path: &Path) -> std::result::Result<(), String> {
+        let source = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let ast = self.engine.compile(source).map_err(|e| e.to_string())?;
+        trace_lock!(self.registry).current = name.to_string();
+        // the registry lock is not held while running, the script can register callbacks
+        self.engine.run_ast(&ast).map_err(|e| e.to_string())?;
+        self.scripts.insert(name.to_string(), ast);
+        Ok(())
+    }
+
+    fn call(&self, script: &str, callback: &FnPtr, args: Vec<Dynamic>) {
+        if let Some(ast) = self.scripts.get(script) {
+            trace_lock!(self.registry).current = script.to_string();
+            if let Err(err) = callback.call::<Dynamic>(&self.engine, ast, args) {
+                log::error!("Script {} failed: {}", script, err);
+            }
+        }
+    }
+
+    fn call_fn(&self, script: &str, function: &str) {
+        let Some(ast) = self.scripts.get(script) else {
+            log::error!("Script {} is not loaded", script);
+            return;
+        };
+        trace_lock!(self.registry).current = script.to_string();
+        let result = self
+            .engine
+            .call_fn::<Dynamic>(&mut rhai::Scope::new(), ast, function, ());
+        if let Err(err) = result {
+            log::error!("Script {} failed calling {}: {}", script, function, err);
+        }
+    }
+
+    fn run_due_timers(&mut self) {
+        let now = Instant::now();
+        let due = {
+            let mut registry = trace_lock!(self.registry);
+            let mut due = Vec::new();
+            registry.timers.retain_mut(|timer| {
+                if timer.next > now {
+                    return true;
+                }
+                due.push((timer.script.clone(), timer.callback.clone()));
+                match timer.interval {
+                    Some(interval) => {
+                        timer.next = now + interval;
+                        true
+                    }
+                    None => false,
+                }
+            });
+            due
+        };
+
+        for (script, callback) in due {
+            self.call(&script, &callback, Vec::new());
+        }
+    }
+
+    fn next_timeout(&self) -> Duration {
+        trace_lock!(self.registry)
+            .timers
+            .iter()
+            .map(|timer| timer.next.saturating_duration_since(Instant::now()))
+            .min()
+            .unwrap_or(Duration::from_secs(1))
+    }
+
+    fn run(&mut self) {
+        loop {
+            match COMMANDS.1.recv_timeout(self.next_timeout()) {
+                Ok(ScriptCommand::Reload) => {
+                    log::info!("Scripts changed");
+                    self.load_all();
+                }
+                Ok(ScriptCommand::Call {
+                    script,
+                    callback,
+                    payload,
+                }) => match rhai::serde::to_dynamic(payload) {
+                    Ok(payload) => self.call(&script, &callback, vec![payload]),
+                    Err(err) => log::error!("Invalid event payload for {}: {}", script, err),
+                },
+                Ok(ScriptCommand::Invoke { script, function }) => self.call_fn(&script, &function),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            if !Seelen::is_running() {
+                break;
+            }
+            self.run_due_timers();
+        }
+        trace_lock!(self.registry).clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_imports_are_limited_to_the_scripts_folder() {
+        let root = std::env::temp_dir().join("slu-scripting-test");
+        let folder = root.join("scripts");
+        std::fs::create_dir_all(&folder).unwrap();
+        std::fs::write(folder.join("utils.rhai"), "export const X = 1;").unwrap();
+        std::fs::write(root.join("outside.rhai"), "export const X = 2;").unwrap();
+
+        let mut engine = Engine::new();
+        engine.set_module_resolver(ScriptsResolver::new(folder));
+        assert_eq!(
+            engine.eval::<i64>(r#"import "utils" as u; u::X"#).unwrap(),
+            1
+        );
+        assert!(engine
+            .eval::<i64>(r#"import "../outside" as u; u::X"#)
+            .is_err());
+        let absolute = format!(
+            r#"import "{}" as u; u::X"#,
+            root.join("outside")
+                .display()
+                .to_string()
+                .replace('\\', "/")
+        );
+        assert!(engine.eval::<i64>(&absolute).is_err());
+    }
+}
@@ -0,0 +1,8 @@
+use seelen_core::system_state::SessionState;
+
+use super::SessionManager;
+
+#[tauri::command(async)]
+pub fn session_get_state() -> SessionState {
+    SessionManager::get_state()
+}
@@ -0,0 +1,169 @@
+pub mod infrastructure;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use seelen_core::{
+    handlers::SeelenEvent,
+    system_state::{SessionChange, SessionState},
+};
+use windows::{
+    core::w,
+    Win32::{
+        Foundation::{HWND, LPARAM, LRESULT, WPARAM},
+        System::{
+            RemoteDesktop::{
+                WTSDisconnectSession, WTSRegisterSessionNotification, NOTIFY_FOR_THIS_SESSION,
+                WTS_CURRENT_SERVER_HANDLE, WTS_CURRENT_SESSION,
+            },
+            Shutdown::LockWorkStation,
+        },
+        UI::WindowsAndMessaging::{
+            CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, GetSystemMetrics,
+            RegisterClassW, TranslateMessage, MSG, SM_REMOTESESSION, WINDOW_EX_STYLE, WINDOW_STYLE,
+            WM_WTSSESSION_CHANGE, WNDCLASSW, WTS_CONSOLE_CONNECT, WTS_CONSOLE_DISCONNECT,
+            WTS_REMOTE_CONNECT, WTS_REMOTE_DISCONNECT, WTS_SESSION_LOCK, WTS_SESSION_UNLOCK,
+        },
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::{app_usage::AppUsageTracker, event_registry::EventRegistry},
+    seelen_wm_v2::instance::WindowManagerV2,
+    utils::spawn_named_thread,
+    windows_api::WindowsApi,
+};
+
+/// Seelen is started after logon so the session is unlocked at start
+static LOCKED: AtomicBool = AtomicBool::new(false);
+
+/// Lock, unlock and remote connection changes of the current session
+pub struct SessionManager;
+
+impl SessionManager {
+    unsafe extern "system" fn window_proc(
+        window: HWND,
+        message: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        match message {
+            WM_WTSSESSION_CHANGE => {
+                if let Some(change) = session_change_of(wparam.0 as u32) {
+                    log_error!(Self::on_change(change));
+                }
+                LRESULT(0)
+            }
+            _ => DefWindowProcW(window, message, wparam, lparam),
+        }
+    }
+
+    unsafe fn create_background_window() -> Result<()> {
+        let wide_class = w!("SeelenSession");
+        let h_module = WindowsApi::module_handle_w()?;
+
+        let wnd_class = WNDCLASSW {
+            lpfnWndProc: Some(Self::window_proc),
+            hInstance: h_module.into(),
+            lpszClassName: wide_class,
+            ..Default::default()
+        };
+
+        RegisterClassW(&wnd_class);
+
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            wide_class,
+            w!("Seelen Session"),
+            WINDOW_STYLE::default(),
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            h_module,
+            None,
+        )?;
+
+        WTSRegisterSessionNotification(hwnd, NOTIFY_FOR_THIS_SESSION)?;
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, hwnd, 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+        Ok(())
+    }
+
+    pub fn start() -> Result<()> {
+        spawn_named_thread("Session", || unsafe {
+            log_error!(Self::create_background_window());
+        })?;
+        Ok(())
+    }
+
+    pub fn is_locked() -> bool {
+        LOCKED.load(Ordering::Acquire)
+    }
+
+    fn is_remote() -> bool {
+        unsafe { GetSystemMetrics(SM_REMOTESESSION) != 0 }
+    }
+
+    pub fn get_state() -> SessionState {
+        SessionState {
+            locked: Self::is_locked(),
+            remote: Self::is_remote(),
+            change: None,
+        }
+    }
+
+    fn on_change(change: SessionChange) -> Result<()> {
+        match change {
+            SessionChange::Lock => {
+                AppUsageTracker::on_session_locked();
+                LOCKED.store(true, Ordering::Release);
+            }
+            SessionChange::Unlock => {
+                LOCKED.store(false, Ordering::Release);
+                AppUsageTracker::on_session_unlocked();
+                WindowManagerV2::on_session_unlocked()?;
+            }
+            _ => {}
+        }
+        EventRegistry::emit(
+            SeelenEvent::SessionChanged,
+            SessionState {
+                change: Some(change),
+                ..Self::get_state()
+            },
+        )?;
+        Ok(())
+    }
+
+    pub fn lock() -> Result<()> {
+        unsafe { LockWorkStation()? };
+        Ok(())
+    }
+
+    /// Disconnecting the session shows the lock screen with the user switcher, the apps keep running
+    pub fn switch_user() -> Result<()> {
+        unsafe { WTSDisconnectSession(WTS_CURRENT_SERVER_HANDLE, WTS_CURRENT_SESSION, false)? };
+        Ok(())
+    }
+}
+
+/// `wparam` of `WM_WTSSESSION_CHANGE`, logon and logoff are not received by the session itself
+fn session_change_of(code: u32) -> Option<SessionChange> {
+    match code {
+        WTS_SESSION_LOCK => Some(SessionChange::Lock),
+        WTS_SESSION_UNLOCK => Some(SessionChange::Unlock),
+        WTS_REMOTE_CONNECT => Some(SessionChange::RemoteConnect),
+        WTS_REMOTE_DISCONNECT => Some(SessionChange::RemoteDisconnect),
+        WTS_CONSOLE_CONNECT => Some(SessionChange::ConsoleConnect),
+        WTS_CONSOLE_DISCONNECT => Some(SessionChange::ConsoleDisconnect),
+        _ => None,
+    }
+}
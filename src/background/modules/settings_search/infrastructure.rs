@@ -0,0 +1,14 @@
+use seelen_core::state::{ConfigSchema, SettingsSearchResult};
+
+use super::SettingsSearch;
+
+#[tauri::command(async)]
+pub fn search_settings(query: String) -> Vec<SettingsSearchResult> {
+    SettingsSearch::search(&query)
+}
+
+/// JSON schema of a config file, used to validate and autocomplete the files of the user
+#[tauri::command(async)]
+pub fn get_config_schema(kind: ConfigSchema) -> serde_json::Value {
+    kind.schema()
+}
@@ -0,0 +1,406 @@
+pub mod infrastructure;
+
+use std::sync::Arc;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use phf::phf_map;
+use seelen_core::state::{Settings, SettingsSearchResult};
+use serde_json::Value;
+
+use crate::{state::application::FULL_STATE, trace_lock};
+
+/// max nesting of settings indexed, deeper objects are shown as a single entry
+const MAX_DEPTH: usize = 4;
+const MAX_RESULTS: usize = 20;
+
+macro_rules! translations {
+    ($($lang:literal),*) => {
+        phf_map! {
+            $($lang => include_str!(concat!("../../../apps/settings/i18n/translations/", $lang, ".yml")),)*
+        }
+    };
+}
+
+/// same translations used by the settings window
+static TRANSLATIONS: phf::Map<&'static str, &'static str> = translations!(
+    "en", "es", "de", "zh", "ko", "fr", "ar", "ru", "hi", "ja", "pt", "it", "nl", "tr", "pl", "uk",
+    "id", "cs", "th", "vi", "ms", "he", "ro", "el", "sv", "no", "fi", "da", "hu", "lt", "bg", "sk",
+    "hr", "lv", "et", "tl", "ca", "af", "bn", "fa", "pa", "sw", "ta", "ur", "cy", "am", "hy", "az",
+    "eu", "bs", "ka", "gu", "is", "km", "ku", "lo", "lb", "mk", "mt", "mn", "ne", "ps", "sr", "si",
+    "so", "tg", "te", "uz", "yo", "zu"
+);
+
+/// settings path -> translation key of the label shown on the settings window
+static LABELS: phf::Map<&'static str, &'static str> = phf_map! {
+    "fancyToolbar.enabled" => "toolbar.enable",
+    "fancyToolbar.height" => "toolbar.height",
+    "fancyToolbar.placeholder" => "toolbar.placeholder.select",
+    "fancyToolbar.hideMode" => "toolbar.auto_hide",
+    "seelenweg.enabled" => "weg.enable",
+    "seelenweg.hideMode" => "weg.auto_hide",
+    "seelenweg.position" => "weg.dock_side",
+    "seelenweg.padding" => "weg.padding",
+    "seelenweg.margin" => "weg.margin",
+    "seelenweg.size" => "weg.items.size",
+    "seelenweg.zoomSize" => "weg.items.zoom_size",
+    "seelenweg.spaceBetweenItems" => "weg.items.gap",
+    "seelenweg.visibleSeparators" => "weg.items.visible_separators",
+    "seelenweg.pinRemovableDrives" => "weg.items.pin_removable_drives",
+    "seelenweg.orphanedItems" => "weg.items.orphaned_items",
+    "nativeTaskbar.mode" => "native_taskbar.mode",
+    "nativeTaskbar.suppressStartMenu" => "native_taskbar.suppress_start_menu",
+    "windowManager.enabled" => "wm.enable",
+    "windowManager.defaultLayout" => "wm.layout",
+    "windowManager.workspaceGap" => "wm.space_between_containers",
+    "windowManager.workspacePadding" => "wm.workspace_padding",
+    "windowManager.workspaceMargin" => "wm.workspace_offset",
+    "windowManager.resizeDelta" => "wm.resize_delta",
+    "windowManager.zones.enabled" => "wm.zones.enable",
+    "windowManager.zones.modifier" => "wm.zones.modifier",
+    "windowManager.mirrorDirectionsOnRtl" => "wm.mirror_directions_on_rtl",
+    "windowManager.border.enabled" => "wm.border.enable",
+    "windowManager.border.width" => "wm.border.width",
+    "windowManager.border.offset" => "wm.border.offset",
+    "windowManager.border.color" => "wm.border.color",
+    "windowManager.border.radius" => "wm.border.radius",
+    "windowManager.border.animationDuration" => "wm.border.animation_duration",
+    "virtualDesktopStrategy" => "vd.strategy.label",
+    "wall.enabled" => "wall.enable",
+    "wall.interval" => "wall.interval",
+    "wall.backgrounds" => "wall.backgrounds",
+    "launcher.enabled" => "app_launcher.enable",
+    "launcher.monitor" => "app_launcher.monitor",
+    "launcher.runners" => "app_launcher.runners.label",
+    "ahkEnabled" => "shortcuts.enable",
+    "devTools" => "devtools.enable",
+    "language" => "general.language",
+    "textDirection" => "general.text_direction.label",
+    "dateFormat" => "general.date_format",
+    "selectedThemes" => "general.theme.label",
+    "iconPacks" => "general.icon_pack.label",
+    "updater.channel" => "update.channel",
+    "updater.autoDownload" => "update.auto_download",
+    "nightLight.scheduled" => "general.night_light.scheduled",
+    "nightLight.start" => "general.night_light.start",
+    "nightLight.end" => "general.night_light.end",
+    "nightLight.temperature" => "general.night_light.temperature",
+    "nightLight.transitionDuration" => "general.night_light.transition_duration",
+    "brightness.linked" => "general.brightness.linked",
+    "capture.saveToFolder" => "general.capture.save_to_folder",
+    "capture.folder" => "general.capture.folder",
+    "capture.copyToClipboard" => "general.capture.copy_to_clipboard",
+    "appUsage.enabled" => "general.app_usage.enabled",
+    "appUsage.retentionDays" => "general.app_usage.retention_days",
+    "appUsage.nudge" => "general.app_usage.nudge",
+    "appUsage.nudgeAfter" => "general.app_usage.nudge_after",
+    "breakReminder.enabled" => "general.break_reminder.enabled",
+    "breakReminder.interval" => "general.break_reminder.interval",
+    "breakReminder.duration" => "general.break_reminder.duration",
+    "breakReminder.snooze" => "general.break_reminder.snooze",
+    "breakReminder.dim" => "general.break_reminder.dim",
+    "breakReminder.dimLevel" => "general.break_reminder.dim_level",
+    "storage.alerts" => "general.storage.alerts",
+    "storage.lowSpaceThreshold" => "general.storage.low_space_threshold",
+    "storage.temperatureThreshold" => "general.storage.temperature_threshold",
+    "accessibility.announceWorkspaces" => "general.accessibility.announce_workspaces",
+    "accessibility.announceFocus" => "general.accessibility.announce_focus",
+    "diagnostics.crashReports" => "extras.diagnostics.crash_reports",
+    "windowOpacity.rememberPerApp" => "general.window_opacity.remember_per_app",
+    "clock.tickMode" => "general.clocks.tick_mode.label",
+};
+
+lazy_static! {
+    /// index of the last used language, rebuilt when the language changes
+    static ref INDEX: Mutex<Option<(String, Arc<Vec<IndexEntry>>)>> = Mutex::new(None);
+}
+
+#[derive(Debug, Default)]
+struct IndexEntry {
+    key: String,
+    route: &'static str,
+    label: Option<String>,
+    /// lowercase text matched against the query, label on the current language and in english
+    label_text: String,
+    /// lowercase words of the key, `workspaceGap` -> `workspace gap`
+    key_text: String,
+    description: Option<String>,
+}
+
+impl IndexEntry {
+    /// Higher is better, None if some word of the query is not found
+    fn score(&self, words: &[String]) -> Option<u32> {
+        let description = self.description.as_deref().unwrap_or("").to_lowercase();
+        words.iter().try_fold(0, |score, word| {
+            let found = if contains(&self.label_text, word) {
+                3
+            } else if contains(&self.key_text, word) {
+                2
+            } else if description.contains(word.as_str()) {
+                1
+            } else {
+                return None;
+            };
+            Some(score + found)
+        })
+    }
+}
+
+/// Also matches words written together, "autohide" matches "auto hide"
+fn contains(text: &str, word: &str) -> bool {
+    text.contains(word) || text.replace(' ', "").contains(word)
+}
+
+/// Page of the settings window where the setting is shown
+fn route_of(key: &str) -> &'static str {
+    let section = key.split('.').next().unwrap_or_default();
+    match section {
+        "fancyToolbar" => "seelen_bar",
+        "seelenweg" | "nativeTaskbar" => "seelen_weg",
+        "windowManager" | "virtualDesktopStrategy" => "seelen_wm",
+        "wall" => "seelen_wall",
+        "launcher" => "seelen_rofi",
+        "monitors" => "monitors",
+        "keybindings" => "shortcuts",
+        "devTools" => "developer",
+        "updater" | "diagnostics" => "info",
+        _ if section.starts_with("ahk") => "shortcuts",
+        _ => "general",
+    }
+}
+
+/// `workspaceGap` -> `workspace gap`
+fn split_words(key: &str) -> String {
+    let mut words = String::new();
+    for c in key.chars() {
+        match c {
+            '.' => words.push(' '),
+            c if c.is_uppercase() => {
+                words.push(' ');
+                words.extend(c.to_lowercase());
+            }
+            c => words.push(c),
+        }
+    }
+    words
+}
+
+fn translate(translations: &serde_yaml::Value, key: &str) -> Option<String> {
+    key.split('.')
+        .try_fold(translations, |value, part| value.get(part))?
+        .as_str()
+        .map(|s| s.to_string())
+}
+
+/// Resolves references and optional values to the schema of the value itself
+fn resolve<'a>(schema: &'a Value, definitions: &'a Value) -> &'a Value {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = reference.trim_start_matches("#/definitions/");
+        if let Some(definition) = definitions.get(name) {
+            return resolve(definition, definitions);
+        }
+    }
+    for combinator in ["allOf", "anyOf"] {
+        if let Some(Value::Array(options)) = schema.get(combinator) {
+            let not_null = options
+                .iter()
+                .filter(|option| option.get("type").and_then(Value::as_str) != Some("null"))
+                .collect::<Vec<_>>();
+            if let [option] = not_null.as_slice() {
+                return resolve(option, definitions);
+            }
+        }
+    }
+    schema
+}
+
+/// Collects the path and description of each setting on the schema
+fn collect_settings(
+    schema: &Value,
+    definitions: &Value,
+    path: &str,
+    depth: usize,
+    settings: &mut Vec<(String, Option<String>)>,
+) {
+    let resolved = resolve(schema, definitions);
+    let description = schema
+        .get("description")
+        .or_else(|| resolved.get("description"))
+        .and_then(Value::as_str)
+        .map(|s| s.to_string());
+
+    match resolved.get("properties").and_then(Value::as_object) {
+        Some(properties) if depth < MAX_DEPTH && !LABELS.contains_key(path) => {
+            for (name, property) in properties {
+                let child = match path.is_empty() {
+                    true => name.clone(),
+                    false => format!("{}.{}", path, name),
+                };
+                collect_settings(property, definitions, &child, depth + 1, settings);
+            }
+        }
+        _ if !path.is_empty() => settings.push((path.to_string(), description)),
+        _ => {}
+    }
+}
+
+fn build_index(lang: &str) -> Vec<IndexEntry> {
+    let load = |lang: &str| -> serde_yaml::Value {
+        TRANSLATIONS
+            .get(lang)
+            .and_then(|content| serde_yaml::from_str(content).ok())
+            .unwrap_or_default()
+    };
+    let english = load("en");
+    let current = load(lang);
+
+    let schema = Settings::schema();
+    let definitions = schema.get("definitions").cloned().unwrap_or_default();
+    let mut settings = Vec::new();
+    collect_settings(&schema, &definitions, "", 0, &mut settings);
+
+    settings
+        .into_iter()
+        .map(|(key, description)| {
+            let i18n_key = LABELS.get(key.as_str());
+            let english_label = i18n_key.and_then(|k| translate(&english, k));
+            let label = i18n_key
+                .and_then(|k| translate(&current, k))
+                .or_else(|| english_label.clone());
+            let label_text = format!(
+                "{} {}",
+                label.as_deref().unwrap_or_default(),
+                english_label.as_deref().unwrap_or_default()
+            )
+            .to_lowercase();
+            IndexEntry {
+                route: route_of(&key),
+                key_text: split_words(&key),
+                key,
+                label,
+                label_text,
+                description,
+            }
+        })
+        .collect()
+}
+
+fn search_in(index: &[IndexEntry], query: &str) -> Vec<SettingsSearchResult> {
+    let words: Vec<String> = query
+        .to_lowercase()
+        .split_whitespace()
+        .map(String::from)
+        .collect();
+    if words.is_empty() {
+        return Vec::new();
+    }
+
+    let mut found: Vec<(u32, &IndexEntry)> = index
+        .iter()
+        .filter_map(|entry| entry.score(&words).map(|score| (score, entry)))
+        .collect();
+    found
+        .sort_by(|(a_score, a), (b_score, b)| b_score.cmp(a_score).then_with(|| a.key.cmp(&b.key)));
+
+    found
+        .into_iter()
+        .take(MAX_RESULTS)
+        .map(|(_, entry)| SettingsSearchResult {
+            key: entry.key.clone(),
+            route: entry.route.to_string(),
+            label: entry.label.clone(),
+            description: entry.description.clone(),
+        })
+        .collect()
+}
+
+/// Finds settings by their label on the current language, key or description
+pub struct SettingsSearch;
+
+impl SettingsSearch {
+    fn index() -> Arc<Vec<IndexEntry>> {
+        let lang = FULL_STATE
+            .load()
+            .settings
+            .language
+            .clone()
+            .filter(|lang| TRANSLATIONS.contains_key(lang.as_str()))
+            .unwrap_or_else(|| "en".to_string());
+
+        let mut cached = trace_lock!(INDEX);
+        match cached.as_ref() {
+            Some((cached_lang, index)) if *cached_lang == lang => index.clone(),
+            _ => {
+                let index = Arc::new(build_index(&lang));
+                *cached = Some((lang, index.clone()));
+                index
+            }
+        }
+    }
+
+    pub fn search(query: &str) -> Vec<SettingsSearchResult> {
+        search_in(&Self::index(), query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn entry(key: &str, label: &str, description: &str) -> IndexEntry {
+        IndexEntry {
+            key: key.to_string(),
+            route: route_of(key),
+            label: Some(label.to_string()),
+            label_text: label.to_lowercase(),
+            key_text: split_words(key),
+            description: Some(description.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_search_in() {
+        let index = vec![
+            entry("seelenweg.hideMode", "Auto Hide", "when to hide the dock"),
+            entry(
+                "seelenweg.spaceBetweenItems",
+                "Space Between Items",
+                "gap between items",
+            ),
+            entry("windowManager.workspaceGap", "Space Between Containers", ""),
+        ];
+
+        let results = search_in(&index, "gap");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].key, "windowManager.workspaceGap");
+        assert_eq!(results[0].route, "seelen_wm");
+        assert_eq!(results[1].key, "seelenweg.spaceBetweenItems");
+
+        let results = search_in(&index, "AutoHide");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].route, "seelen_weg");
+
+        assert!(search_in(&index, "space dock").is_empty());
+        assert!(search_in(&index, "  ").is_empty());
+    }
+
+    #[test]
+    fn test_labels_are_valid() {
+        let english: serde_yaml::Value = serde_yaml::from_str(TRANSLATIONS["en"]).unwrap();
+        let index = build_index("en");
+        let keys: HashMap<&str, &IndexEntry> = index
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry))
+            .collect();
+        for (key, i18n_key) in LABELS.entries() {
+            assert!(
+                translate(&english, i18n_key).is_some(),
+                "missing translation {}",
+                i18n_key
+            );
+            assert!(keys.contains_key(key), "missing setting {}", key);
+        }
+    }
+}
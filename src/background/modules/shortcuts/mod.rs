@@ -0,0 +1,124 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+
+use crate::{error_handler::Result, trace_lock, windows_api::WindowsApi};
+
+lazy_static! {
+    /// shortcut path -> last modified time and resolved shortcut
+    static ref CACHE: Mutex<HashMap<PathBuf, (SystemTime, Shortcut)>> = Mutex::new(HashMap::new());
+}
+
+/// Resolved `.lnk` or `.url` file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Shortcut {
+    /// program, file or folder the shortcut points to
+    pub target: Option<PathBuf>,
+    pub arguments: Option<String>,
+    /// address of internet shortcuts (`.url`)
+    pub url: Option<String>,
+    /// custom icon of internet shortcuts (`.url`)
+    pub icon_file: Option<PathBuf>,
+}
+
+/// Resolves shortcuts once, they are resolved again only if the file was modified
+pub struct ShortcutResolver;
+
+impl ShortcutResolver {
+    fn extension(path: &Path) -> Option<String> {
+        path.extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+    }
+
+    pub fn is_shortcut(path: &Path) -> bool {
+        matches!(Self::extension(path).as_deref(), Some("lnk" | "url"))
+    }
+
+    pub fn resolve(path: &Path) -> Result<Shortcut> {
+        let modified = std::fs::metadata(path)?.modified()?;
+        if let Some((cached_at, shortcut)) = trace_lock!(CACHE).get(path) {
+            if *cached_at == modified {
+                return Ok(shortcut.clone());
+            }
+        }
+
+        let shortcut = match Self::extension(path).as_deref() {
+            Some("lnk") => {
+                let (target, arguments) = WindowsApi::resolve_lnk_target(path)?;
+                Shortcut {
+                    target: Some(target).filter(|t| !t.as_os_str().is_empty()),
+                    arguments: Some(arguments)
+                        .filter(|a| !a.is_empty())
+                        .map(|a| a.to_string_lossy().to_string()),
+                    ..Default::default()
+                }
+            }
+            // in theory .url files are encoded in UTF-8 so we don't need to use OsString
+            Some("url") => parse_url_file(&String::from_utf8_lossy(&std::fs::read(path)?)),
+            _ => return Err(format!("{} is not a shortcut", path.display()).into()),
+        };
+
+        trace_lock!(CACHE).insert(path.to_path_buf(), (modified, shortcut.clone()));
+        Ok(shortcut)
+    }
+
+    /// Forgets the resolved shortcut of the path or all of them if `None`
+    pub fn invalidate(path: Option<&Path>) {
+        let mut cache = trace_lock!(CACHE);
+        match path {
+            Some(path) => {
+                cache.remove(path);
+            }
+            None => cache.clear(),
+        }
+    }
+}
+
+fn parse_url_file(content: &str) -> Shortcut {
+    let mut shortcut = Shortcut::default();
+    for line in content.lines().map(str::trim) {
+        if let Some(url) = line.strip_prefix("URL=") {
+            shortcut.url = Some(url.to_string());
+        } else if let Some(icon_file) = line.strip_prefix("IconFile=") {
+            shortcut.icon_file = Some(PathBuf::from(icon_file));
+        }
+    }
+    // urls pointing to local files are opened as the file
+    shortcut.target = shortcut
+        .url
+        .as_deref()
+        .and_then(|url| url.strip_prefix("file:///"))
+        .map(|file| PathBuf::from(file.replace('/', "\\")));
+    shortcut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_url_file() {
+        let shortcut = parse_url_file(
+            "[InternetShortcut]\r\nURL=steam://rungameid/570\r\nIconIndex=0\r\nIconFile=C:\\Steam\\steam\\games\\dota.ico\r\n",
+        );
+        assert_eq!(shortcut.url.as_deref(), Some("steam://rungameid/570"));
+        assert_eq!(
+            shortcut.icon_file,
+            Some(PathBuf::from("C:\\Steam\\steam\\games\\dota.ico"))
+        );
+        assert_eq!(shortcut.target, None);
+
+        let shortcut =
+            parse_url_file("[InternetShortcut]\nURL=file:///C:/Users/Public/notes.txt\n");
+        assert_eq!(
+            shortcut.target,
+            Some(PathBuf::from("C:\\Users\\Public\\notes.txt"))
+        );
+        assert_eq!(shortcut.icon_file, None);
+    }
+}
@@ -0,0 +1,10 @@
+use seelen_core::system_state::StartMenuItem;
+
+use crate::error_handler::Result;
+
+use super::StartMenuManager;
+
+#[tauri::command(async)]
+pub fn get_start_menu_items() -> Result<Vec<StartMenuItem>> {
+    StartMenuManager::items()
+}
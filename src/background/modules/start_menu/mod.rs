@@ -0,0 +1,186 @@
+pub mod infrastructure;
+
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use lazy_static::lazy_static;
+use notify_debouncer_full::{
+    new_debouncer,
+    notify::{ReadDirectoryChangesWatcher, RecursiveMode, Watcher},
+    DebounceEventResult, Debouncer, FileIdMap,
+};
+use parking_lot::Mutex;
+use seelen_core::{handlers::SeelenEvent, system_state::StartMenuItem};
+use windows::{
+    core::GUID,
+    Win32::{
+        Foundation::HANDLE,
+        System::Com::CoTaskMemFree,
+        UI::Shell::{
+            FOLDERID_CommonPrograms, FOLDERID_Programs, SHGetKnownFolderPath, KF_FLAG_DEFAULT,
+        },
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::{event_registry::EventRegistry, icon_cache::IconCache, shortcuts::ShortcutResolver},
+    trace_lock,
+    utils::spawn_named_thread,
+};
+
+/// installers create and remove several files at once, so changes are grouped
+const DEBOUNCE_TIME: Duration = Duration::from_millis(500);
+
+lazy_static! {
+    static ref CACHE: Mutex<StartMenuCache> = Mutex::new(StartMenuCache::default());
+    static ref WATCHER: Mutex<Option<Debouncer<ReadDirectoryChangesWatcher, FileIdMap>>> =
+        Mutex::new(None);
+}
+
+#[derive(Debug, Default)]
+struct StartMenuCache {
+    /// shortcut path -> last modified time and parsed item, so unchanged shortcuts are not parsed again
+    entries: HashMap<PathBuf, (SystemTime, StartMenuItem)>,
+    items: Vec<StartMenuItem>,
+    loaded: bool,
+}
+
+impl StartMenuCache {
+    fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+        for entry in std::fs::read_dir(dir)?.flatten() {
+            let file_type = entry.file_type()?;
+            let path = entry.path();
+            if file_type.is_dir() {
+                log_error!(Self::collect_files(&path, files));
+            } else if file_type.is_file() && path.extension() != Some(OsStr::new("ini")) {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    fn parse(path: &Path) -> StartMenuItem {
+        let shortcut = match ShortcutResolver::is_shortcut(path) {
+            true => ShortcutResolver::resolve(path).unwrap_or_default(),
+            false => Default::default(),
+        };
+        StartMenuItem {
+            label: path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            icon: IconCache::of_file(path).ok(),
+            target: shortcut.target,
+            arguments: shortcut.arguments,
+            path: path.to_path_buf(),
+        }
+    }
+
+    /// Scans the folders again, returns true if the items changed
+    fn refresh(&mut self, folders: &[PathBuf]) -> bool {
+        let mut files = Vec::new();
+        for folder in folders {
+            log_error!(Self::collect_files(folder, &mut files));
+        }
+
+        let mut entries = HashMap::new();
+        for path in files {
+            let modified = std::fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            let item = match self.entries.remove(&path) {
+                Some((cached_at, item)) if cached_at == modified => item,
+                _ => Self::parse(&path),
+            };
+            entries.insert(path, (modified, item));
+        }
+
+        let mut items: Vec<StartMenuItem> =
+            entries.values().map(|(_, item)| item.clone()).collect();
+        items.sort_by(|a, b| {
+            a.label
+                .to_lowercase()
+                .cmp(&b.label.to_lowercase())
+                .then_with(|| a.path.cmp(&b.path))
+        });
+
+        self.entries = entries;
+        self.loaded = true;
+        let changed = self.items != items;
+        self.items = items;
+        changed
+    }
+}
+
+/// Shortcuts of the Start Menu, watched to update the launcher when apps are installed or uninstalled
+pub struct StartMenuManager;
+
+impl StartMenuManager {
+    fn known_folder(id: &GUID) -> Result<PathBuf> {
+        unsafe {
+            let path = SHGetKnownFolderPath(id, KF_FLAG_DEFAULT, HANDLE::default())?;
+            let result = path.to_string();
+            CoTaskMemFree(Some(path.0 as _));
+            Ok(PathBuf::from(result?))
+        }
+    }
+
+    /// User and common (all users) programs folders
+    fn folders() -> Result<Vec<PathBuf>> {
+        Ok(vec![
+            Self::known_folder(&FOLDERID_Programs)?,
+            Self::known_folder(&FOLDERID_CommonPrograms)?,
+        ])
+    }
+
+    fn refresh() -> Result<()> {
+        let folders = Self::folders()?;
+        let mut cache = trace_lock!(CACHE);
+        if cache.refresh(&folders) {
+            EventRegistry::emit(SeelenEvent::StartMenuItemsChanged, &cache.items)?;
+        }
+        Ok(())
+    }
+
+    pub fn start() -> Result<()> {
+        // loading the icons of all the shortcuts takes a while on the first run
+        spawn_named_thread("Start Menu", || {
+            log_error!(Self::refresh());
+        })?;
+
+        let mut debouncer =
+            new_debouncer(
+                DEBOUNCE_TIME,
+                None,
+                |result: DebounceEventResult| match result {
+                    Ok(_) => log_error!(Self::refresh()),
+                    Err(errors) => errors
+                        .iter()
+                        .for_each(|e| log::error!("Start Menu Watcher Error: {:?}", e)),
+                },
+            )?;
+        for folder in Self::folders()? {
+            debouncer
+                .watcher()
+                .watch(&folder, RecursiveMode::Recursive)?;
+        }
+        *trace_lock!(WATCHER) = Some(debouncer);
+        Ok(())
+    }
+
+    /// Cached items, sorted by label. Waits for the first scan if it is not finished.
+    pub fn items() -> Result<Vec<StartMenuItem>> {
+        let mut cache = trace_lock!(CACHE);
+        if !cache.loaded {
+            cache.refresh(&Self::folders()?);
+        }
+        Ok(cache.items.clone())
+    }
+}
@@ -0,0 +1,40 @@
+use seelen_core::system_state::{StartupTaskOptions, StartupTaskStatus};
+
+use crate::error_handler::Result;
+
+use super::StartupTask;
+
+#[tauri::command(async)]
+pub async fn set_auto_start(enabled: bool) -> Result<()> {
+    if enabled {
+        let status = StartupTask::status().await?;
+        let options = if status.registered {
+            status.options()
+        } else {
+            StartupTaskOptions::default()
+        };
+        StartupTask::register(&options).await
+    } else {
+        StartupTask::unregister().await
+    }
+}
+
+#[tauri::command(async)]
+pub async fn get_auto_start_status() -> Result<bool> {
+    Ok(StartupTask::status().await?.registered)
+}
+
+#[tauri::command(async)]
+pub async fn startup_get_status() -> Result<StartupTaskStatus> {
+    StartupTask::status().await
+}
+
+#[tauri::command(async)]
+pub async fn startup_register(options: StartupTaskOptions) -> Result<()> {
+    StartupTask::register(&options).await
+}
+
+#[tauri::command(async)]
+pub async fn startup_unregister() -> Result<()> {
+    StartupTask::unregister().await
+}
@@ -0,0 +1,58 @@
+pub mod infrastructure;
+
+use seelen_core::system_state::{StartupTaskOptions, StartupTaskStatus};
+
+use crate::{error_handler::Result, utils::pwsh::PwshScript, windows_api::WindowsApi};
+
+/// Manages the login startup task on the Task Scheduler (`\Seelen\Seelen-UI`)
+pub struct StartupTask;
+
+impl StartupTask {
+    pub async fn status() -> Result<StartupTaskStatus> {
+        let stdout = PwshScript::new(include_str!("status.ps1"))
+            .execute()
+            .await?;
+        Ok(serde_json::from_str(&stdout)?)
+    }
+
+    async fn schedule(enabled: bool, options: &StartupTaskOptions) -> Result<()> {
+        let exe_path = std::env::current_exe()?;
+        let mut script = PwshScript::new(include_str!("schedule.ps1"));
+        script.with_args([
+            "-ExeRoute".to_string(),
+            exe_path.to_string_lossy().to_string(),
+            "-Enabled".to_string(),
+            enabled.to_string(),
+            "-Elevated".to_string(),
+            options.elevated.to_string(),
+            "-Delay".to_string(),
+            options.delay.to_string(),
+        ]);
+        script.execute().await?;
+        Ok(())
+    }
+
+    /// Registers or overrides the task, the user will be asked for admin rights if needed
+    pub async fn register(options: &StartupTaskOptions) -> Result<()> {
+        log::trace!("Registering startup task: {:?}", options);
+        Self::schedule(true, options).await
+    }
+
+    pub async fn unregister() -> Result<()> {
+        log::trace!("Unregistering startup task");
+        Self::schedule(false, &StartupTaskOptions::default()).await
+    }
+
+    /// Override the task in case of location change, normally this happen on MSIX update
+    pub async fn refresh_path() -> Result<()> {
+        if !WindowsApi::is_elevated()? {
+            return Ok(());
+        }
+        let status = Self::status().await?;
+        let current = std::env::current_exe()?.to_string_lossy().to_string();
+        if status.registered && status.exe.as_ref() != Some(&current) {
+            Self::register(&status.options()).await?;
+        }
+        Ok(())
+    }
+}
@@ -1,35 +1,43 @@
-param (
-  [string]$ExeRoute,
-  [string]$Enabled
-)
-
-$isAdmin = ([Security.Principal.WindowsPrincipal] [Security.Principal.WindowsIdentity]::GetCurrent()).IsInRole([Security.Principal.WindowsBuiltInRole]::Administrator)
-if (-not $isAdmin) {
-  $ownRoute = $MyInvocation.MyCommand.Definition
-  $arguments = @(
-    "-NoProfile"
-    "-ExecutionPolicy Bypass"
-    "-File `"$ownRoute`""
-    "-ExeRoute `"$ExeRoute`""
-    "-Enabled `"$Enabled`""
-  )
-  Start-Process powershell -ArgumentList $arguments -Verb RunAs -WindowStyle Hidden
-  Exit
-}
-
-$taskName = "Seelen-UI"
-$taskPath = "\Seelen\$taskName"
-
-if ($Enabled -eq "true") {
-  $action = New-ScheduledTaskAction -Execute "$ExeRoute" -Argument "--silent"
-  $trigger = New-ScheduledTaskTrigger -AtLogon
-  $settings = New-ScheduledTaskSettingsSet -Priority 4 -AllowStartIfOnBatteries -DontStopIfGoingOnBatteries -Hidden
-
-  Register-ScheduledTask -Force -Action $action -Trigger $trigger -Settings $settings -TaskName $taskPath -User $env:USERNAME -RunLevel Highest
-}
-else {
-  $existingTask = Get-ScheduledTask -TaskName $taskName -ErrorAction SilentlyContinue
-  if ($null -ne $existingTask) {
-    Unregister-ScheduledTask -TaskName $taskName -Confirm:$false
-  }
-}
+param (
+  [string]$ExeRoute,
+  [string]$Enabled,
+  [string]$Elevated = "true",
+  [int]$Delay = 0
+)
+
+$isAdmin = ([Security.Principal.WindowsPrincipal] [Security.Principal.WindowsIdentity]::GetCurrent()).IsInRole([Security.Principal.WindowsBuiltInRole]::Administrator)
+if (-not $isAdmin) {
+  $ownRoute = $MyInvocation.MyCommand.Definition
+  $arguments = @(
+    "-NoProfile"
+    "-ExecutionPolicy Bypass"
+    "-File `"$ownRoute`""
+    "-ExeRoute `"$ExeRoute`""
+    "-Enabled `"$Enabled`""
+    "-Elevated `"$Elevated`""
+    "-Delay $Delay"
+  )
+  Start-Process powershell -ArgumentList $arguments -Verb RunAs -WindowStyle Hidden -Wait
+  Exit
+}
+
+$taskName = "Seelen-UI"
+$taskPath = "\Seelen\$taskName"
+
+if ($Enabled -eq "true") {
+  $action = New-ScheduledTaskAction -Execute "$ExeRoute" -Argument "--silent"
+  $trigger = New-ScheduledTaskTrigger -AtLogon
+  if ($Delay -gt 0) {
+    $trigger.Delay = "PT$($Delay)S"
+  }
+  $settings = New-ScheduledTaskSettingsSet -Priority 4 -AllowStartIfOnBatteries -DontStopIfGoingOnBatteries -Hidden
+  $runLevel = if ($Elevated -eq "true") { "Highest" } else { "Limited" }
+
+  Register-ScheduledTask -Force -Action $action -Trigger $trigger -Settings $settings -TaskName $taskPath -User $env:USERNAME -RunLevel $runLevel
+}
+else {
+  $existingTask = Get-ScheduledTask -TaskName $taskName -ErrorAction SilentlyContinue
+  if ($null -ne $existingTask) {
+    Unregister-ScheduledTask -TaskName $taskName -Confirm:$false
+  }
+}
@@ -0,0 +1,15 @@
+$task = Get-ScheduledTask -TaskName Seelen-UI -ErrorAction SilentlyContinue
+if ($null -eq $task) {
+  Write-Output '{"registered":false}'
+  Exit
+}
+
+$delay = $task.Triggers[0].Delay
+$seconds = if ($delay) { [int][System.Xml.XmlConvert]::ToTimeSpan($delay).TotalSeconds } else { 0 }
+
+@{
+  registered = $true
+  elevated   = ($task.Principal.RunLevel -eq "Highest")
+  delay      = $seconds
+  exe        = $task.Actions[0].Execute
+} | ConvertTo-Json -Compress
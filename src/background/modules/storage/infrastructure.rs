@@ -0,0 +1,8 @@
+use seelen_core::system_state::StorageState;
+
+use super::Storage;
+
+#[tauri::command(async)]
+pub fn storage_get_state() -> StorageState {
+    Storage::get_state()
+}
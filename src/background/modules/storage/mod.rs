@@ -0,0 +1,408 @@
+pub mod infrastructure;
+
+use std::{collections::HashSet, ffi::c_void, time::Duration};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{
+    handlers::SeelenEvent,
+    state::StorageSettings,
+    system_state::{DiskHealth, StorageState, StorageVolume},
+};
+use widestring::U16CStr;
+use windows::{
+    core::HSTRING,
+    Win32::{
+        Foundation::{CloseHandle, HANDLE},
+        Storage::FileSystem::{
+            CreateFileW, GetDiskFreeSpaceExW, GetDriveTypeW, GetLogicalDrives,
+            GetVolumeInformationW, FILE_FLAGS_AND_ATTRIBUTES, FILE_SHARE_READ, FILE_SHARE_WRITE,
+            OPEN_EXISTING,
+        },
+        System::{
+            Ioctl::{
+                PropertyStandardQuery, StorageDeviceProperty, StorageDeviceTemperatureProperty,
+                IOCTL_STORAGE_GET_DEVICE_NUMBER, IOCTL_STORAGE_PREDICT_FAILURE,
+                IOCTL_STORAGE_QUERY_PROPERTY, STORAGE_DEVICE_DESCRIPTOR, STORAGE_DEVICE_NUMBER,
+                STORAGE_PREDICT_FAILURE, STORAGE_PROPERTY_ID, STORAGE_PROPERTY_QUERY,
+                STORAGE_TEMPERATURE_DATA_DESCRIPTOR,
+            },
+            WindowsProgramming::DRIVE_FIXED,
+            IO::DeviceIoControl,
+        },
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::{
+        event_registry::EventRegistry, notifications::application::show_toast,
+        power::profile::PowerProfile,
+    },
+    state::application::FULL_STATE,
+    t, trace_lock,
+    utils::spawn_named_thread,
+};
+
+/// space and health change slowly, so there is no need to check them often
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// SMART attribute ids
+const REALLOCATED_SECTORS: u8 = 5;
+const TEMPERATURE: u8 = 194;
+
+lazy_static! {
+    /// last emitted state, so only changes are emitted
+    static ref LAST: Mutex<Option<StorageState>> = Mutex::new(None);
+    /// alerts already shown, they are shown again only after the problem is gone
+    static ref ALERTED: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+/// Space of the fixed volumes and health of their disks, with alerts when they need attention
+pub struct Storage;
+
+impl Storage {
+    /// Opens without access rights, enough to query the properties of the device
+    fn open(path: &str) -> Result<HANDLE> {
+        let handle = unsafe {
+            CreateFileW(
+                &HSTRING::from(path),
+                0,
+                FILE_SHARE_READ | FILE_SHARE_WRITE,
+                None,
+                OPEN_EXISTING,
+                FILE_FLAGS_AND_ATTRIBUTES(0),
+                HANDLE::default(),
+            )?
+        };
+        Ok(handle)
+    }
+
+    unsafe fn io_control<T>(
+        handle: HANDLE,
+        code: u32,
+        input: Option<&T>,
+        output: &mut [u8],
+    ) -> bool {
+        let mut returned = 0;
+        DeviceIoControl(
+            handle,
+            code,
+            input.map(|input| input as *const T as *const c_void),
+            input.map_or(0, |_| std::mem::size_of::<T>() as u32),
+            Some(output.as_mut_ptr() as *mut c_void),
+            output.len() as u32,
+            Some(&mut returned),
+            None,
+        )
+        .is_ok()
+    }
+
+    unsafe fn query_property(
+        handle: HANDLE,
+        property: STORAGE_PROPERTY_ID,
+        output: &mut [u8],
+    ) -> bool {
+        let query = STORAGE_PROPERTY_QUERY {
+            PropertyId: property,
+            QueryType: PropertyStandardQuery,
+            ..Default::default()
+        };
+        Self::io_control(handle, IOCTL_STORAGE_QUERY_PROPERTY, Some(&query), output)
+    }
+
+    fn disk_number_of(letter: char) -> Option<u32> {
+        let handle = Self::open(&format!(r"\\.\{}:", letter)).ok()?;
+        let mut output = [0u8; std::mem::size_of::<STORAGE_DEVICE_NUMBER>()];
+        // fails for volumes spanning several disks
+        let found = unsafe {
+            Self::io_control::<()>(handle, IOCTL_STORAGE_GET_DEVICE_NUMBER, None, &mut output)
+        };
+        unsafe {
+            let _ = CloseHandle(handle);
+        }
+        if !found {
+            return None;
+        }
+        let number: STORAGE_DEVICE_NUMBER =
+            unsafe { std::ptr::read_unaligned(output.as_ptr() as _) };
+        Some(number.DeviceNumber)
+    }
+
+    fn get_volumes() -> Vec<StorageVolume> {
+        let mask = unsafe { GetLogicalDrives() };
+        (0..26u8)
+            .filter(|bit| mask & (1 << bit) != 0)
+            .map(|bit| (b'A' + bit) as char)
+            .filter_map(|letter| {
+                let root = HSTRING::from(format!(r"{}:\", letter));
+                if unsafe { GetDriveTypeW(&root) } != DRIVE_FIXED {
+                    return None;
+                }
+
+                let (mut free, mut total) = (0, 0);
+                unsafe { GetDiskFreeSpaceExW(&root, Some(&mut free), Some(&mut total), None) }
+                    .ok()?;
+
+                let mut label = [0u16; 261];
+                let mut file_system = [0u16; 261];
+                unsafe {
+                    GetVolumeInformationW(
+                        &root,
+                        Some(&mut label),
+                        None,
+                        None,
+                        None,
+                        Some(&mut file_system),
+                    )
+                }
+                .ok()?;
+
+                let to_string = |buffer: &[u16]| {
+                    U16CStr::from_slice_truncate(buffer)
+                        .map(|s| s.to_string_lossy())
+                        .unwrap_or_default()
+                };
+                Some(StorageVolume {
+                    letter,
+                    label: to_string(&label),
+                    file_system: to_string(&file_system),
+                    total,
+                    free,
+                    disk: Self::disk_number_of(letter),
+                })
+            })
+            .collect()
+    }
+
+    fn get_disk(number: u32) -> Option<DiskHealth> {
+        let handle = Self::open(&format!(r"\\.\PhysicalDrive{}", number)).ok()?;
+
+        let mut descriptor = [0u8; 1024];
+        let model =
+            if unsafe { Self::query_property(handle, StorageDeviceProperty, &mut descriptor) } {
+                let header: STORAGE_DEVICE_DESCRIPTOR =
+                    unsafe { std::ptr::read_unaligned(descriptor.as_ptr() as _) };
+                ascii_at(&descriptor, header.ProductIdOffset as usize)
+            } else {
+                String::new()
+            };
+
+        let mut prediction = [0u8; std::mem::size_of::<STORAGE_PREDICT_FAILURE>()];
+        // the vendor data of ATA disks is the SMART attributes table
+        let (failure_predicted, smart) = if unsafe {
+            Self::io_control::<()>(handle, IOCTL_STORAGE_PREDICT_FAILURE, None, &mut prediction)
+        } {
+            let prediction: STORAGE_PREDICT_FAILURE =
+                unsafe { std::ptr::read_unaligned(prediction.as_ptr() as _) };
+            (
+                Some(prediction.PredictFailure != 0),
+                Some(prediction.VendorSpecific),
+            )
+        } else {
+            (None, None)
+        };
+
+        let mut temperature = [0u8; 256];
+        let reported_temperature = if unsafe {
+            Self::query_property(handle, StorageDeviceTemperatureProperty, &mut temperature)
+        } {
+            let data: STORAGE_TEMPERATURE_DATA_DESCRIPTOR =
+                unsafe { std::ptr::read_unaligned(temperature.as_ptr() as _) };
+            let current = data.TemperatureInfo[0].Temperature;
+            (data.InfoCount > 0 && current > 0).then_some(current as u32)
+        } else {
+            None
+        };
+
+        unsafe {
+            let _ = CloseHandle(handle);
+        }
+
+        Some(DiskHealth {
+            number,
+            model,
+            temperature: reported_temperature.or_else(|| {
+                // only the lowest byte of the raw value is the current temperature
+                smart
+                    .as_ref()
+                    .and_then(|smart| smart_attribute(smart, TEMPERATURE))
+                    .map(|raw| (raw & 0xFF) as u32)
+                    .filter(|celsius| *celsius > 0)
+            }),
+            reallocated_sectors: smart
+                .as_ref()
+                .and_then(|smart| smart_attribute(smart, REALLOCATED_SECTORS)),
+            failure_predicted,
+        })
+    }
+
+    pub fn get_state() -> StorageState {
+        let volumes = Self::get_volumes();
+        let mut numbers: Vec<u32> = volumes.iter().filter_map(|v| v.disk).collect();
+        numbers.sort();
+        numbers.dedup();
+        let disks = numbers.into_iter().filter_map(Self::get_disk).collect();
+        StorageState { volumes, disks }
+    }
+
+    fn alerts_of(
+        state: &StorageState,
+        settings: &StorageSettings,
+    ) -> Vec<(String, String, String)> {
+        let mut alerts = Vec::new();
+        for volume in &state.volumes {
+            let percent = free_percent(volume);
+            if volume.total > 0 && percent < settings.low_space_threshold {
+                alerts.push((
+                    format!("space:{}", volume.letter),
+                    t!("storage.low_space_title"),
+                    t!(
+                        "storage.low_space_body",
+                        drive = format!("{}:", volume.letter),
+                        percent = percent
+                    ),
+                ));
+            }
+        }
+        for disk in &state.disks {
+            if disk
+                .temperature
+                .is_some_and(|celsius| celsius >= settings.temperature_threshold)
+            {
+                alerts.push((
+                    format!("temperature:{}", disk.number),
+                    t!("storage.temperature_title"),
+                    t!(
+                        "storage.temperature_body",
+                        disk = disk.model,
+                        temperature = disk.temperature.unwrap_or_default()
+                    ),
+                ));
+            }
+            if disk.failure_predicted == Some(true) {
+                alerts.push((
+                    format!("health:{}", disk.number),
+                    t!("storage.health_title"),
+                    t!("storage.health_body", disk = disk.model),
+                ));
+            }
+        }
+        alerts
+    }
+
+    /// Each problem is notified once, until it is gone
+    fn notify_alerts(state: &StorageState) {
+        let settings = FULL_STATE.load().settings.storage.clone();
+        let alerts = if settings.alerts {
+            Self::alerts_of(state, &settings)
+        } else {
+            Vec::new()
+        };
+
+        let mut alerted = trace_lock!(ALERTED);
+        let current: HashSet<String> = alerts.iter().map(|(key, _, _)| key.clone()).collect();
+        let new: Vec<(String, String)> = alerts
+            .into_iter()
+            .filter(|(key, _, _)| !alerted.contains(key))
+            .map(|(_, title, body)| (title, body))
+            .collect();
+        *alerted = current;
+        drop(alerted);
+
+        for (title, body) in new {
+            log_error!(show_toast(&title, &body));
+        }
+    }
+
+    pub fn emit_changes() -> Result<()> {
+        let state = Self::get_state();
+        Self::notify_alerts(&state);
+        let mut last = trace_lock!(LAST);
+        if last.as_ref() != Some(&state) {
+            EventRegistry::emit(SeelenEvent::StorageChanged, &state)?;
+            *last = Some(state);
+        }
+        Ok(())
+    }
+
+    pub fn start() -> Result<()> {
+        spawn_named_thread("Storage", || loop {
+            log_error!(Self::emit_changes());
+            std::thread::sleep(PowerProfile::sampling_interval(CHECK_INTERVAL));
+        })?;
+        Ok(())
+    }
+}
+
+fn free_percent(volume: &StorageVolume) -> u32 {
+    if volume.total == 0 {
+        return 0;
+    }
+    (volume.free as f64 / volume.total as f64 * 100.0).round() as u32
+}
+
+/// Null terminated ascii string at the offset of the buffer, 0 is used as no string
+fn ascii_at(buffer: &[u8], offset: usize) -> String {
+    if offset == 0 || offset >= buffer.len() {
+        return String::new();
+    }
+    let bytes = &buffer[offset..];
+    let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).trim().to_string()
+}
+
+/// Raw value of an attribute on the SMART attributes table, a 2 bytes revision followed
+/// by 30 entries of 12 bytes: id, flags (2), current, worst, raw value (6) and reserved.
+fn smart_attribute(table: &[u8], id: u8) -> Option<u64> {
+    table
+        .get(2..2 + 30 * 12)?
+        .chunks_exact(12)
+        .find(|entry| entry[0] == id)
+        .map(|entry| {
+            entry[5..11]
+                .iter()
+                .rev()
+                .fold(0u64, |value, byte| (value << 8) | *byte as u64)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(entries: &[(u8, [u8; 6])]) -> Vec<u8> {
+        let mut table = vec![0u8; 512];
+        for (idx, (id, raw)) in entries.iter().enumerate() {
+            let start = 2 + idx * 12;
+            table[start] = *id;
+            table[start + 5..start + 11].copy_from_slice(raw);
+        }
+        table
+    }
+
+    #[test]
+    fn test_smart_attribute() {
+        let table = table(&[
+            (9, [0x10, 0x27, 0, 0, 0, 0]),
+            (REALLOCATED_SECTORS, [0x08, 0x01, 0, 0, 0, 0]),
+            (TEMPERATURE, [38, 0, 20, 0, 45, 0]),
+        ]);
+        assert_eq!(smart_attribute(&table, REALLOCATED_SECTORS), Some(0x108));
+        assert_eq!(
+            smart_attribute(&table, TEMPERATURE).map(|raw| raw & 0xFF),
+            Some(38)
+        );
+        assert_eq!(smart_attribute(&table, 1), None);
+        assert_eq!(smart_attribute(&[0u8; 10], REALLOCATED_SECTORS), None);
+    }
+
+    #[test]
+    fn test_ascii_at() {
+        let buffer = b"\0\0\0\0Samsung SSD 980  \0rest";
+        assert_eq!(ascii_at(buffer, 4), "Samsung SSD 980");
+        assert_eq!(ascii_at(buffer, 0), "");
+        assert_eq!(ascii_at(buffer, 100), "");
+    }
+}
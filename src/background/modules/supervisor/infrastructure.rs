@@ -0,0 +1,8 @@
+use tauri::WebviewWindow;
+
+use super::WebviewSupervisor;
+
+#[tauri::command(async)]
+pub fn webview_heartbeat(window: WebviewWindow) {
+    WebviewSupervisor::on_heartbeat(window.label());
+}
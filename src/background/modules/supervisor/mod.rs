@@ -0,0 +1,272 @@
+pub mod infrastructure;
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::handlers::SeelenEvent;
+use tauri::{Emitter, Manager, WebviewWindow};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::resource_governor::ResourceGovernor,
+    seelen::{get_app_handle, Seelen, SEELEN},
+    seelen_bar::FancyToolbar,
+    seelen_wall::SeelenWall,
+    seelen_weg::SeelenWeg,
+    trace_lock,
+    utils::spawn_named_thread,
+    windows_api::WindowsApi,
+};
+
+lazy_static! {
+    static ref LAST_HEARTBEATS: Mutex<HashMap<String, Instant>> = Mutex::new(HashMap::new());
+    /// restarts waiting for the previous webview to be destroyed
+    static ref PENDING_RESTARTS: Mutex<Vec<Restart>> = Mutex::new(Vec::new());
+    /// label -> state applied when the recreated webview answers its first heartbeat
+    static ref PENDING_RESTORES: Mutex<HashMap<String, WebviewState>> = Mutex::new(HashMap::new());
+}
+
+/// State of a toolbar or weg that their webview can't recover by itself
+#[derive(Debug, Clone, Copy)]
+pub struct WebviewState {
+    pub visible: bool,
+    pub overlaped: bool,
+}
+
+/// Webview removed from its container to be recreated
+enum Restart {
+    Toolbar {
+        monitor: String,
+        label: String,
+        state: WebviewState,
+    },
+    Weg {
+        monitor: String,
+        label: String,
+        state: WebviewState,
+    },
+    Wall {
+        label: String,
+    },
+}
+
+impl Restart {
+    fn label(&self) -> &str {
+        match self {
+            Self::Toolbar { label, .. } | Self::Weg { label, .. } | Self::Wall { label } => label,
+        }
+    }
+}
+
+/// Keeps the toolbar, weg and wall webviews alive, recreating them when they die or stop
+/// answering the heartbeat pings.
+pub struct WebviewSupervisor;
+
+impl WebviewSupervisor {
+    const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+    /// Time without answering a heartbeat before considering the webview as hung,
+    /// also used as grace period for new windows while they load.
+    const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(30);
+    /// Max wait for the destruction of a webview, the restart is retried on the next check
+    const DESTROY_TIMEOUT: Duration = Duration::from_secs(3);
+
+    pub fn start() -> Result<()> {
+        spawn_named_thread("Webview Supervisor", || loop {
+            std::thread::sleep(Self::HEARTBEAT_INTERVAL);
+            if !Seelen::is_running() {
+                log::trace!("Exiting Webview Supervisor");
+                break;
+            }
+            Self::check();
+        })?;
+        Ok(())
+    }
+
+    pub fn on_heartbeat(label: &str) {
+        trace_lock!(LAST_HEARTBEATS).insert(label.to_string(), Instant::now());
+        let restore = trace_lock!(PENDING_RESTORES).remove(label);
+        if let Some(state) = restore {
+            log_error!(Self::restore(label, state));
+        }
+    }
+
+    fn forget(label: &str) {
+        trace_lock!(LAST_HEARTBEATS).remove(label);
+    }
+    /// Returns true if the webview should be recreated
+    fn is_unhealthy(window: &WebviewWindow) -> bool {
+        let label = window.label();
+        let alive = window.hwnd().is_ok_and(WindowsApi::is_window);
+        if !alive {
+            log::warn!("Webview {} was destroyed", label);
+            return true;
+        }
+
+        // the blank page of a suspended webview doesn't answer the pings
+        if ResourceGovernor::is_suspended(label) {
+            return false;
+        }
+
+        let mut heartbeats = trace_lock!(LAST_HEARTBEATS);
+        // first time seen, start the grace period
+        let last = *heartbeats
+            .entry(label.to_string())
+            .or_insert_with(Instant::now);
+        if last.elapsed() > Self::HEARTBEAT_TIMEOUT {
+            log::warn!(
+                "Webview {} is not responding since {:.2?}",
+                label,
+                last.elapsed()
+            );
+            return true;
+        }
+
+        log_error!(window.emit_to(label, SeelenEvent::WebviewHeartbeat, ()));
+        false
+    }
+
+    /// Removes the unhealthy webviews from their containers, dropping them destroys the windows
+    fn take_unhealthy() -> Vec<Restart> {
+        let mut restarts = Vec::new();
+        let mut seelen = trace_lock!(SEELEN);
+
+        for monitor in seelen.monitors_mut() {
+            let name = monitor.name().clone();
+            if monitor
+                .toolbar()
+                .as_ref()
+                .is_some_and(|t| Self::is_unhealthy(t.window()))
+            {
+                if let Some(toolbar) = monitor.toolbar_mut().take() {
+                    let label = toolbar.window().label().to_string();
+                    Self::forget(&label);
+                    restarts.push(Restart::Toolbar {
+                        monitor: name.clone(),
+                        label,
+                        state: toolbar.webview_state(),
+                    });
+                }
+            }
+
+            if monitor
+                .weg()
+                .as_ref()
+                .is_some_and(|w| Self::is_unhealthy(w.window()))
+            {
+                if let Some(weg) = monitor.weg_mut().take() {
+                    let label = weg.window().label().to_string();
+                    Self::forget(&label);
+                    restarts.push(Restart::Weg {
+                        monitor: name,
+                        label,
+                        state: weg.webview_state(),
+                    });
+                }
+            }
+        }
+
+        if seelen
+            .wall()
+            .as_ref()
+            .is_some_and(|w| Self::is_unhealthy(w.window()))
+        {
+            if let Some(wall) = seelen.wall_mut().take() {
+                let label = wall.window().label().to_string();
+                Self::forget(&label);
+                restarts.push(Restart::Wall { label });
+            }
+        }
+        restarts
+    }
+
+    /// The label is released when tauri handles the `Destroyed` event of the previous window
+    fn wait_destroyed(label: &str) -> bool {
+        let handle = get_app_handle();
+        let start = Instant::now();
+        while handle.get_webview_window(label).is_some() {
+            if start.elapsed() > Self::DESTROY_TIMEOUT {
+                return false;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        true
+    }
+
+    /// Webviews are built without holding the lock, the creation waits for the main thread
+    fn recreate(restart: Restart) -> Result<()> {
+        match restart {
+            Restart::Toolbar {
+                monitor,
+                label,
+                state,
+            } => {
+                let toolbar = FancyToolbar::new(&monitor)?;
+                let mut seelen = trace_lock!(SEELEN);
+                let settings = seelen.state();
+                if let Some(container) = seelen.monitor_by_name_mut(&monitor) {
+                    container.replace_toolbar(toolbar, &settings)?;
+                    trace_lock!(PENDING_RESTORES).insert(label, state);
+                }
+            }
+            Restart::Weg {
+                monitor,
+                label,
+                state,
+            } => {
+                let weg = SeelenWeg::new(&monitor)?;
+                let mut seelen = trace_lock!(SEELEN);
+                let settings = seelen.state();
+                if let Some(container) = seelen.monitor_by_name_mut(&monitor) {
+                    container.replace_weg(weg, &settings)?;
+                    trace_lock!(PENDING_RESTORES).insert(label, state);
+                }
+            }
+            Restart::Wall { .. } => {
+                let wall = SeelenWall::new()?;
+                trace_lock!(SEELEN).replace_wall(wall)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn restore(label: &str, state: WebviewState) -> Result<()> {
+        let mut seelen = trace_lock!(SEELEN);
+        for monitor in seelen.monitors_mut() {
+            if let Some(toolbar) = monitor
+                .toolbar_mut()
+                .as_mut()
+                .filter(|t| t.window().label() == label)
+            {
+                return toolbar.restore_webview_state(state);
+            }
+            if let Some(weg) = monitor
+                .weg_mut()
+                .as_mut()
+                .filter(|w| w.window().label() == label)
+            {
+                return weg.restore_webview_state(state);
+            }
+        }
+        Ok(())
+    }
+
+    fn check() {
+        let mut restarts = std::mem::take(&mut *trace_lock!(PENDING_RESTARTS));
+        restarts.extend(Self::take_unhealthy());
+
+        for restart in restarts {
+            if !Self::wait_destroyed(restart.label()) {
+                log::warn!("Webview {} is still being destroyed", restart.label());
+                trace_lock!(PENDING_RESTARTS).push(restart);
+                continue;
+            }
+            log::info!("Recreating webview {}", restart.label());
+            log_error!(Self::recreate(restart));
+        }
+    }
+}
@@ -1,20 +1,31 @@
 use seelen_core::{handlers::SeelenEvent, system_state::UIColors};
-use tauri::Emitter;
 
-use crate::{error_handler::Result, log_error, seelen::get_app_handle, trace_lock};
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::{event_registry::EventRegistry, titlebar::Titlebar},
+    seelen_wm_v2::border::FocusBorder,
+    trace_lock,
+};
 
 use super::application::SYSTEM_SETTINGS;
 
 fn emit_colors(colors: &UIColors) {
-    get_app_handle()
-        .emit(SeelenEvent::ColorsChanged, colors)
-        .expect("failed to emit");
+    EventRegistry::emit(SeelenEvent::ColorsChanged, colors).expect("failed to emit");
 }
 
 pub fn register_colors_events() {
     std::thread::spawn(move || {
         let mut manager = trace_lock!(SYSTEM_SETTINGS);
         manager.on_colors_change(Box::new(emit_colors));
+        manager.on_colors_change(Box::new(|colors| {
+            FocusBorder::set_accent_color(&colors.accent)
+        }));
+        manager.on_colors_change(Box::new(Titlebar::set_system_colors));
+        match manager.get_colors() {
+            Ok(colors) => Titlebar::set_system_colors(&colors),
+            Err(err) => log::error!("Failed to get the system colors: {:?}", err),
+        }
     });
 }
 
@@ -0,0 +1,224 @@
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use tauri::Manager;
+use windows::Win32::{
+    Foundation::HWND,
+    UI::WindowsAndMessaging::{EVENT_OBJECT_CREATE, EVENT_OBJECT_SHOW, SW_HIDE, SW_SHOWNORMAL},
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::keybindings::application::KeybindingsManager,
+    seelen::{get_app_handle, Seelen},
+    state::application::{FullState, FULL_STATE},
+    trace_lock,
+    utils::{sleep_millis, spawn_named_thread},
+    windows_api::{AppBarData, AppBarDataState, WindowEnumerator, WindowsApi},
+};
+
+lazy_static! {
+    /// taskbar state before being modified by Seelen, `None` if the taskbar was never touched
+    static ref INITIAL_STATE: Mutex<Option<AppBarDataState>> = Mutex::new(None);
+    pub static ref TASKBAR_CLASS: Vec<&'static str> =
+        Vec::from(["Shell_TrayWnd", "Shell_SecondaryTrayWnd",]);
+}
+
+static APPLYING: AtomicBool = AtomicBool::new(false);
+static WATCHDOG_RUNNING: AtomicBool = AtomicBool::new(false);
+
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Controls the visibility of the native taskbars (one per monitor) and the start menu
+pub struct NativeTaskbar;
+
+impl NativeTaskbar {
+    pub fn get_handles() -> Result<Vec<HWND>> {
+        let mut founds = Vec::new();
+        WindowEnumerator::new().for_each(|hwnd| {
+            let class = WindowsApi::get_class(hwnd).unwrap_or_default();
+            if TASKBAR_CLASS.contains(&class.as_str()) {
+                founds.push(hwnd);
+            }
+        })?;
+        Ok(founds)
+    }
+
+    fn should_hide(hwnd: HWND, state: &FullState) -> bool {
+        let monitor = WindowsApi::monitor_from_window(hwnd);
+        match WindowsApi::monitor_index(monitor) {
+            Ok(idx) => state.should_hide_native_taskbar_on_monitor(idx),
+            Err(_) => false,
+        }
+    }
+
+    /// The initial state is persisted so it can be restored on next run if Seelen crashes
+    fn initial_state_path() -> Result<PathBuf> {
+        Ok(get_app_handle()
+            .path()
+            .app_data_dir()?
+            .join("native_taskbar_state"))
+    }
+
+    fn capture_initial_state(hwnd: HWND) -> Result<()> {
+        let mut initial = trace_lock!(INITIAL_STATE);
+        if initial.is_some() {
+            return Ok(());
+        }
+        let path = Self::initial_state_path()?;
+        let persisted = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| s.trim().parse::<u32>().ok())
+            .filter(|s| *s <= 3);
+        let state = match persisted {
+            Some(state) => AppBarDataState::from(state),
+            None => {
+                let state = AppBarData::from_handle(hwnd).state();
+                std::fs::write(&path, (state as u32).to_string())?;
+                state
+            }
+        };
+        *initial = Some(state);
+        Ok(())
+    }
+
+    /// Returns true if all the taskbars have the expected visibility
+    fn is_applied() -> Result<bool> {
+        let state = FULL_STATE.load();
+        let touched = trace_lock!(INITIAL_STATE).is_some();
+        for hwnd in Self::get_handles()? {
+            let visible = WindowsApi::is_window_visible(hwnd);
+            let hide = Self::should_hide(hwnd, &state);
+            if (hide && visible) || (!hide && !visible && touched) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn apply_once() -> Result<()> {
+        let state = FULL_STATE.load();
+        let targets: Vec<(HWND, bool)> = Self::get_handles()?
+            .into_iter()
+            .map(|hwnd| (hwnd, Self::should_hide(hwnd, &state)))
+            .collect();
+
+        let hide_all = !targets.is_empty() && targets.iter().all(|(_, hide)| *hide);
+        if let Some((hwnd, _)) = targets.iter().find(|(_, hide)| *hide) {
+            Self::capture_initial_state(*hwnd)?;
+        }
+
+        let initial = *trace_lock!(INITIAL_STATE);
+        let Some(initial) = initial else {
+            // nothing to hide and nothing to restore
+            return Ok(());
+        };
+
+        // the auto-hide state is shared by all the taskbars so it is only enabled if all of them
+        // are hidden (to release the work area), otherwise the visible ones would be auto-hidden too.
+        if let Some((hwnd, _)) = targets.first() {
+            let app_bar = AppBarData::from_handle(*hwnd);
+            app_bar.set_state(match hide_all {
+                true => AppBarDataState::AutoHide,
+                false => initial,
+            });
+        }
+
+        for (hwnd, hide) in targets {
+            if hide {
+                WindowsApi::show_window(hwnd, SW_HIDE)?;
+            } else if !WindowsApi::is_window_visible(hwnd) {
+                WindowsApi::show_window(hwnd, SW_SHOWNORMAL)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the visibility configured for each monitor. Explorer can show the taskbar again
+    /// while it is starting so this is retried a few times.
+    pub fn apply() {
+        if APPLYING.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let result = spawn_named_thread("NativeTaskbar", || {
+            for _ in 0..10 {
+                if let Err(err) = Self::apply_once() {
+                    log::error!("Failed to apply native taskbar state: {:?}", err);
+                    break;
+                }
+                sleep_millis(50);
+            }
+            APPLYING.store(false, Ordering::SeqCst);
+        });
+        if let Err(err) = result {
+            log::error!("Failed to spawn native taskbar thread: {:?}", err);
+            APPLYING.store(false, Ordering::SeqCst);
+        }
+        log_error!(Self::start_watchdog());
+        log_error!(KeybindingsManager::set_start_menu_suppression(
+            FULL_STATE.load().should_suppress_start_menu()
+        ));
+    }
+
+    /// Restores the taskbars to the state before Seelen was started
+    pub fn restore() -> Result<()> {
+        let Some(initial) = trace_lock!(INITIAL_STATE).take() else {
+            return Ok(());
+        };
+        for hwnd in Self::get_handles()? {
+            AppBarData::from_handle(hwnd).set_state(initial);
+            WindowsApi::show_window(hwnd, SW_SHOWNORMAL)?;
+        }
+        let path = Self::initial_state_path()?;
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// Re-applies the state if something else changed the taskbars, like an Explorer restart
+    fn start_watchdog() -> Result<()> {
+        if WATCHDOG_RUNNING.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+        spawn_named_thread("NativeTaskbarWatchdog", || {
+            while Seelen::is_running() {
+                std::thread::sleep(WATCHDOG_INTERVAL);
+                match Self::is_applied() {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        log::debug!("Native taskbar state was changed externally, re-applying");
+                        Self::apply();
+                    }
+                    Err(err) => log::error!("Native taskbar watchdog: {:?}", err),
+                }
+            }
+            WATCHDOG_RUNNING.store(false, Ordering::SeqCst);
+        })?;
+        Ok(())
+    }
+
+    pub fn process_raw_win_event(event: u32, origin_hwnd: HWND) -> Result<()> {
+        if !matches!(event, EVENT_OBJECT_SHOW | EVENT_OBJECT_CREATE)
+            || trace_lock!(INITIAL_STATE).is_none()
+        {
+            return Ok(());
+        }
+        let class = WindowsApi::get_class(origin_hwnd)?;
+        let parent_class =
+            WindowsApi::get_class(WindowsApi::get_parent(origin_hwnd)).unwrap_or_default();
+        if TASKBAR_CLASS
+            .iter()
+            .any(|t| t == &class || t == &parent_class)
+        {
+            Self::apply();
+        }
+        Ok(())
+    }
+}
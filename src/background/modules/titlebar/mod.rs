@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{
+    state::{TitlebarMode, TitlebarSettings},
+    system_state::UIColors,
+};
+use windows::Win32::{
+    Foundation::{BOOL, COLORREF, HWND},
+    Graphics::Dwm::{DWMWA_CAPTION_COLOR, DWMWA_USE_IMMERSIVE_DARK_MODE},
+    UI::WindowsAndMessaging::WS_CHILD,
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::window_filter::WindowFilter,
+    seelen_wm_v2::border::parse_hex_color,
+    state::application::FULL_STATE,
+    trace_lock,
+    windows_api::{window::Window, WindowEnumerator, WindowsApi},
+    winevent::WinEvent,
+};
+
+/// lets the system decide the caption color
+const COLOR_DEFAULT: COLORREF = COLORREF(0xFFFFFFFF);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TitlebarStyle {
+    dark: bool,
+    caption: COLORREF,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SystemColors {
+    dark: bool,
+    accent: COLORREF,
+}
+
+lazy_static! {
+    /// windows modified and their dark mode before being modified
+    static ref APPLIED: Mutex<HashMap<isize, (TitlebarStyle, bool)>> = Mutex::new(HashMap::new());
+    static ref COLORS: Mutex<SystemColors> = Mutex::new(SystemColors {
+        dark: false,
+        accent: COLOR_DEFAULT,
+    });
+}
+
+/// Forces dark or light titlebars on the app windows so they match the theme
+pub struct Titlebar;
+
+impl Titlebar {
+    /// Style to apply according to the settings, `None` if the titlebars should be untouched
+    fn target(settings: &TitlebarSettings, original_dark: bool) -> Option<TitlebarStyle> {
+        if settings.mode == TitlebarMode::Untouched && !settings.accent_caption {
+            return None;
+        }
+        let colors = *trace_lock!(COLORS);
+        Some(TitlebarStyle {
+            dark: match settings.mode {
+                TitlebarMode::Untouched => original_dark,
+                TitlebarMode::System => colors.dark,
+                TitlebarMode::Dark => true,
+                TitlebarMode::Light => false,
+            },
+            caption: match settings.accent_caption {
+                true => colors.accent,
+                false => COLOR_DEFAULT,
+            },
+        })
+    }
+
+    fn set_style(hwnd: HWND, style: TitlebarStyle) -> Result<()> {
+        WindowsApi::dwm_set_window_attribute(
+            hwnd,
+            DWMWA_USE_IMMERSIVE_DARK_MODE,
+            &BOOL::from(style.dark),
+        )?;
+        // not supported before Windows 11
+        let _ = WindowsApi::dwm_set_window_attribute(hwnd, DWMWA_CAPTION_COLOR, &style.caption);
+        Ok(())
+    }
+
+    fn revert(hwnd: HWND) -> Result<()> {
+        if let Some((_, original_dark)) = trace_lock!(APPLIED).remove(&(hwnd.0 as isize)) {
+            Self::set_style(
+                hwnd,
+                TitlebarStyle {
+                    dark: original_dark,
+                    caption: COLOR_DEFAULT,
+                },
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn refresh(window: &Window) -> Result<()> {
+        let settings = &FULL_STATE.load().settings.titlebar;
+        let address = window.address();
+        let applied = trace_lock!(APPLIED).get(&address).copied();
+
+        let original_dark = match applied {
+            Some((_, original_dark)) => original_dark,
+            None => {
+                if settings.mode == TitlebarMode::Untouched && !settings.accent_caption {
+                    return Ok(());
+                }
+                if WindowsApi::get_styles(window.hwnd()).contains(WS_CHILD)
+                    || !WindowFilter::interactable(window, false).included
+                {
+                    return Ok(());
+                }
+                let mut dark = BOOL::default();
+                let _ = WindowsApi::dwm_get_window_attribute(
+                    window.hwnd(),
+                    DWMWA_USE_IMMERSIVE_DARK_MODE,
+                    &mut dark,
+                );
+                dark.as_bool()
+            }
+        };
+
+        match Self::target(settings, original_dark) {
+            Some(style) if applied.map(|(current, _)| current) != Some(style) => {
+                // saved before applying so a partial change can still be reverted
+                trace_lock!(APPLIED).insert(address, (style, original_dark));
+                Self::set_style(window.hwnd(), style)
+            }
+            Some(_) => Ok(()),
+            None => Self::revert(window.hwnd()),
+        }
+    }
+
+    /// Should be called when the settings or the system colors change
+    pub fn refresh_all() -> Result<()> {
+        let settings = &FULL_STATE.load().settings.titlebar;
+        if Self::target(settings, false).is_none() && trace_lock!(APPLIED).is_empty() {
+            return Ok(());
+        }
+        WindowEnumerator::new().for_each(|hwnd| {
+            log_error!(Self::refresh(&Window::from(hwnd)));
+        })
+    }
+
+    /// Restores the titlebars of all the windows
+    pub fn revert_all() {
+        let applied = std::mem::take(&mut *trace_lock!(APPLIED));
+        for (address, (_, original_dark)) in applied {
+            let hwnd = HWND(address as _);
+            if WindowsApi::is_window(hwnd) {
+                log_error!(Self::set_style(
+                    hwnd,
+                    TitlebarStyle {
+                        dark: original_dark,
+                        caption: COLOR_DEFAULT,
+                    }
+                ));
+            }
+        }
+    }
+
+    /// The system is in dark mode if the background color is dark
+    pub fn set_system_colors(colors: &UIColors) {
+        {
+            let mut current = trace_lock!(COLORS);
+            if let Some([r, g, b, _]) = parse_hex_color(&colors.background) {
+                let luminance = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+                current.dark = luminance < 128.0;
+            }
+            if let Some([r, g, b, _]) = parse_hex_color(&colors.accent) {
+                current.accent = COLORREF(u32::from_le_bytes([r, g, b, 0]));
+            }
+        }
+        log_error!(Self::refresh_all());
+    }
+
+    pub fn wants_win_event(event: &WinEvent) -> bool {
+        matches!(
+            event,
+            WinEvent::ObjectCreate | WinEvent::ObjectShow | WinEvent::ObjectDestroy
+        )
+    }
+
+    pub fn process_win_event(event: WinEvent, window: &Window) -> Result<()> {
+        match event {
+            WinEvent::ObjectDestroy => {
+                trace_lock!(APPLIED).remove(&window.address());
+            }
+            _ => Self::refresh(window)?,
+        }
+        Ok(())
+    }
+}
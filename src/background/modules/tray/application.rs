@@ -18,8 +18,8 @@ use winreg::{
 
 use crate::{
     error_handler::Result,
+    modules::icon_cache::IconCache,
     pcstr,
-    seelen_weg::icon_extractor::extract_and_save_icon_from_file,
     utils::{is_windows_10, is_windows_11, resolve_guid_path, sleep_millis},
     windows_api::{AppBarData, AppBarDataState, Com, WindowsApi},
 };
@@ -175,7 +175,7 @@ impl TrayIcon {
         }
 
         let path = self.registry.as_ref().unwrap().executable_path.clone();
-        let icon = extract_and_save_icon_from_file(&path)?;
+        let icon = IconCache::of_file(&path)?;
         Ok(icon
             .to_string_lossy()
             .trim_start_matches("\\\\?\\")
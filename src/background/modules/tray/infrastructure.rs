@@ -2,17 +2,16 @@ use std::sync::atomic::{AtomicBool, Ordering};
 
 use itertools::Itertools;
 use seelen_core::handlers::SeelenEvent;
-use tauri::Emitter;
 
 use crate::{
-    error_handler::Result, log_error, modules::tray::application::get_tray_icons,
-    seelen::get_app_handle,
+    error_handler::Result,
+    log_error,
+    modules::{event_registry::EventRegistry, tray::application::get_tray_icons},
 };
 
 fn emit_tray_info() -> Result<()> {
-    let handle = get_app_handle();
     let payload = get_tray_icons()?.iter().map(|t| t.info()).collect_vec();
-    handle.emit(SeelenEvent::TrayInfo, payload)?;
+    EventRegistry::emit(SeelenEvent::TrayInfo, payload)?;
     Ok(())
 }
 
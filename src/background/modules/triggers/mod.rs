@@ -0,0 +1,335 @@
+mod sandbox;
+
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use crossbeam_channel::{Receiver, Sender};
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use sandbox::SandboxedProcess;
+use seelen_core::{
+    state::{TriggerAction, TriggerEvent},
+    system_state::{ProcessEvent, ProcessEventKind},
+};
+use serde::Serialize;
+use tauri_plugin_http::reqwest;
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::{
+        actions::domain::ActionCatalog,
+        cli::application::{handle_cli_events, SEELEN_COMMAND_LINE},
+        power::domain::PowerStatus,
+    },
+    seelen::Seelen,
+    state::application::FULL_STATE,
+    trace_lock,
+    utils::spawn_named_thread,
+    windows_api::window::Window,
+};
+
+lazy_static! {
+    static ref QUEUE: (Sender<Job>, Receiver<Job>) = crossbeam_channel::bounded(32);
+    static ref LIMITER: Mutex<RateLimiter> = Mutex::new(RateLimiter::default());
+    static ref LAST_STATE: Mutex<LastState> = Mutex::new(LastState::default());
+}
+
+/// Event data, sent as json body to webhooks and as `SEELEN_TRIGGER` env var to programs
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TriggerSource {
+    FocusChanged { exe: String, title: String },
+    WorkspaceSwitched { index: usize },
+    BatteryChanged { percentage: u8, charging: bool },
+    ProcessStarted { exe: String, pid: u32 },
+    ProcessStopped { exe: String, pid: u32 },
+}
+
+struct Job {
+    action: TriggerAction,
+    source: TriggerSource,
+}
+
+#[derive(Default)]
+struct RateLimiter {
+    /// trigger index -> last execution
+    last_runs: HashMap<usize, Instant>,
+    /// executions of the last minute
+    window: VecDeque<Instant>,
+}
+
+impl RateLimiter {
+    fn allow(&mut self, index: usize, cooldown: Duration, max_per_minute: u32) -> bool {
+        self.allow_at(Instant::now(), index, cooldown, max_per_minute)
+    }
+
+    fn allow_at(
+        &mut self,
+        now: Instant,
+        index: usize,
+        cooldown: Duration,
+        max_per_minute: u32,
+    ) -> bool {
+        if self
+            .last_runs
+            .get(&index)
+            .is_some_and(|last| now.duration_since(*last) < cooldown)
+        {
+            return false;
+        }
+
+        while self
+            .window
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= Duration::from_secs(60))
+        {
+            self.window.pop_front();
+        }
+        if self.window.len() >= max_per_minute as usize {
+            return false;
+        }
+
+        self.window.push_back(now);
+        self.last_runs.insert(index, now);
+        true
+    }
+}
+
+/// Used to only fire on changes (edge triggered)
+#[derive(Default)]
+struct LastState {
+    focused_exe: Option<String>,
+    battery: Option<u8>,
+}
+
+/// Executes the user defined triggers on a pool of threads, a slow trigger doesn't delay the rest.
+///
+/// Programs are launched without a shell, with a restricted low integrity token inside a job
+/// object, and killed with their children if they exceed the timeout. Executions are rate
+/// limited by trigger cooldown and a global limit per minute.
+pub struct TriggerRunner;
+
+impl TriggerRunner {
+    const WORKERS: usize = 4;
+    const PROCESS_TIMEOUT: Duration = Duration::from_secs(30);
+    const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+    pub fn start() -> Result<()> {
+        for worker in 0..Self::WORKERS {
+            spawn_named_thread(&format!("Trigger Runner {}", worker), || {
+                for job in QUEUE.1.iter() {
+                    if !Seelen::is_running() {
+                        break;
+                    }
+                    log_error!(Self::execute(job));
+                }
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Forgets the rate limit history, the trigger indexes could change with the settings
+    pub fn reset() {
+        *trace_lock!(LIMITER) = RateLimiter::default();
+    }
+
+    fn dispatch(source: TriggerSource, matches: impl Fn(&TriggerEvent) -> bool) {
+        let state = FULL_STATE.load();
+        let config = &state.settings.triggers;
+        if !config.enabled {
+            return;
+        }
+
+        let cooldown = Duration::from_millis(config.cooldown);
+        for (index, trigger) in config.triggers.iter().enumerate() {
+            if !trigger.enabled || !matches(&trigger.on) {
+                continue;
+            }
+            if !trace_lock!(LIMITER).allow(index, cooldown, config.max_per_minute) {
+                log::debug!("Trigger {} skipped by rate limit", index);
+                continue;
+            }
+            let job = Job {
+                action: trigger.action.clone(),
+                source: source.clone(),
+            };
+            if QUEUE.0.try_send(job).is_err() {
+                log::warn!("Trigger queue is full, trigger {} dropped", index);
+            }
+        }
+    }
+
+    pub fn on_focus_changed(window: &Window) {
+        if !FULL_STATE.load().settings.triggers.enabled {
+            return;
+        }
+
+        let exe = match window.exe() {
+            Ok(path) => path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            Err(_) => return,
+        };
+
+        {
+            let mut last = trace_lock!(LAST_STATE);
+            if last.focused_exe.as_ref() == Some(&exe) {
+                return;
+            }
+            last.focused_exe = Some(exe.clone());
+        }
+
+        let source = TriggerSource::FocusChanged {
+            exe: exe.clone(),
+            title: window.title(),
+        };
+        Self::dispatch(source, |event| match event {
+            TriggerEvent::FocusChanged { exe: expected } => expected.eq_ignore_ascii_case(&exe),
+            _ => false,
+        });
+    }
+
+    pub fn on_workspace_switched(index: usize) {
+        Self::dispatch(
+            TriggerSource::WorkspaceSwitched { index },
+            |event| match event {
+                TriggerEvent::WorkspaceSwitched { index: expected } => {
+                    expected.is_none() || *expected == Some(index)
+                }
+                _ => false,
+            },
+        );
+    }
+
+    pub fn on_power_status(status: &PowerStatus) {
+        // 255 means unknown status
+        if status.battery_life_percent > 100 {
+            return;
+        }
+
+        let percentage = status.battery_life_percent;
+        let charging = status.ac_line_status == 1;
+        let previous = trace_lock!(LAST_STATE).battery.replace(percentage);
+        if charging {
+            return;
+        }
+
+        let source = TriggerSource::BatteryChanged {
+            percentage,
+            charging,
+        };
+        Self::dispatch(source, |event| match event {
+            TriggerEvent::BatteryBelow {
+                percentage: threshold,
+            } => percentage < *threshold && !previous.is_some_and(|p| p < *threshold),
+            _ => false,
+        });
+    }
+
+    pub fn on_process_event(event: &ProcessEvent) {
+        if !FULL_STATE.load().settings.triggers.enabled {
+            return;
+        }
+
+        let exe = event.process.name.clone();
+        let pid = event.process.pid;
+        let source = match event.kind {
+            ProcessEventKind::Started => TriggerSource::ProcessStarted {
+                exe: exe.clone(),
+                pid,
+            },
+            ProcessEventKind::Stopped => TriggerSource::ProcessStopped {
+                exe: exe.clone(),
+                pid,
+            },
+        };
+        Self::dispatch(source, |trigger| match (trigger, event.kind) {
+            (TriggerEvent::ProcessStarted { exe: expected }, ProcessEventKind::Started)
+            | (TriggerEvent::ProcessStopped { exe: expected }, ProcessEventKind::Stopped) => {
+                expected.eq_ignore_ascii_case(&exe)
+            }
+            _ => false,
+        });
+    }
+
+    fn execute(job: Job) -> Result<()> {
+        log::trace!("Executing trigger {:?} for {:?}", job.action, job.source);
+        match job.action {
+            TriggerAction::Run { program, args } => {
+                let env = [("SEELEN_TRIGGER", serde_json::to_string(&job.source)?)];
+                let process = SandboxedProcess::spawn(&program, &args, &env)?;
+                // waited apart, programs can take until the timeout and the workers are few
+                spawn_named_thread("Trigger Process", move || {
+                    log_error!(process.wait(Self::PROCESS_TIMEOUT));
+                })?;
+            }
+            TriggerAction::Cli(args) => {
+                let argv = std::iter::once("seelen-ui".to_string()).chain(args);
+                let command = trace_lock!(SEELEN_COMMAND_LINE).clone();
+                let matches = command
+                    .try_get_matches_from(argv)
+                    .map_err(|e| e.to_string())?;
+                handle_cli_events(&matches)?;
+            }
+            TriggerAction::Action { id, params } => ActionCatalog::build().run(&id, &params)?,
+            TriggerAction::Webhook { url } => {
+                let body = serde_json::to_string(&job.source)?;
+                tauri::async_runtime::block_on(async move {
+                    reqwest::Client::new()
+                        .post(url)
+                        .header(reqwest::header::CONTENT_TYPE, "application/json")
+                        .body(body)
+                        .timeout(Self::WEBHOOK_TIMEOUT)
+                        .send()
+                        .await?
+                        .error_for_status()?;
+                    Result::Ok(())
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COOLDOWN: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn test_cooldown_is_per_trigger() {
+        let mut limiter = RateLimiter::default();
+        let now = Instant::now();
+        assert!(limiter.allow_at(now, 0, COOLDOWN, 10));
+        assert!(!limiter.allow_at(now + Duration::from_secs(1), 0, COOLDOWN, 10));
+        assert!(limiter.allow_at(now + Duration::from_secs(1), 1, COOLDOWN, 10));
+        assert!(limiter.allow_at(now + COOLDOWN, 0, COOLDOWN, 10));
+    }
+
+    #[test]
+    fn test_global_limit_per_minute() {
+        let mut limiter = RateLimiter::default();
+        let now = Instant::now();
+        assert!(limiter.allow_at(now, 0, COOLDOWN, 2));
+        assert!(limiter.allow_at(now, 1, COOLDOWN, 2));
+        assert!(!limiter.allow_at(now, 2, COOLDOWN, 2));
+        // the executions older than a minute stop counting
+        assert!(limiter.allow_at(now + Duration::from_secs(60), 2, COOLDOWN, 2));
+    }
+
+    #[test]
+    fn test_rejected_executions_are_not_counted() {
+        let mut limiter = RateLimiter::default();
+        let now = Instant::now();
+        assert!(limiter.allow_at(now, 0, COOLDOWN, 2));
+        for _ in 0..5 {
+            assert!(!limiter.allow_at(now, 0, COOLDOWN, 2));
+        }
+        assert!(limiter.allow_at(now, 1, COOLDOWN, 2));
+    }
+}
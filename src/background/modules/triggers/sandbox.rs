@@ -0,0 +1,291 @@
+use std::{ffi::c_void, time::Duration};
+
+use windows::{
+    core::{w, PCWSTR, PWSTR},
+    Win32::{
+        Foundation::{LocalFree, HANDLE, HLOCAL, WAIT_TIMEOUT},
+        Security::{
+            Authorization::ConvertStringSidToSidW, CreateRestrictedToken, GetLengthSid,
+            SetTokenInformation, TokenIntegrityLevel, DISABLE_MAX_PRIVILEGE, LUA_TOKEN, PSID,
+            SID_AND_ATTRIBUTES, TOKEN_ADJUST_DEFAULT, TOKEN_ASSIGN_PRIMARY, TOKEN_DUPLICATE,
+            TOKEN_MANDATORY_LABEL, TOKEN_QUERY,
+        },
+        System::{
+            JobObjects::{
+                AssignProcessToJobObject, CreateJobObjectW, JobObjectBasicUIRestrictions,
+                JobObjectExtendedLimitInformation, SetInformationJobObject, TerminateJobObject,
+                JOBOBJECT_BASIC_UI_RESTRICTIONS, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+                JOB_OBJECT_LIMIT_ACTIVE_PROCESS, JOB_OBJECT_LIMIT_DIE_ON_UNHANDLED_EXCEPTION,
+                JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE, JOB_OBJECT_LIMIT_PROCESS_MEMORY,
+                JOB_OBJECT_UILIMIT_DESKTOP, JOB_OBJECT_UILIMIT_DISPLAYSETTINGS,
+                JOB_OBJECT_UILIMIT_EXITWINDOWS, JOB_OBJECT_UILIMIT_GLOBALATOMS,
+                JOB_OBJECT_UILIMIT_HANDLES, JOB_OBJECT_UILIMIT_READCLIPBOARD,
+                JOB_OBJECT_UILIMIT_SYSTEMPARAMETERS, JOB_OBJECT_UILIMIT_WRITECLIPBOARD,
+            },
+            Threading::{
+                CreateProcessAsUserW, GetCurrentProcess, OpenProcessToken, ResumeThread,
+                TerminateProcess, WaitForSingleObject, CREATE_NO_WINDOW, CREATE_SUSPENDED,
+                CREATE_UNICODE_ENVIRONMENT, PROCESS_INFORMATION, STARTUPINFOW,
+            },
+        },
+    },
+};
+
+use crate::{error_handler::Result, windows_api::WindowsApi};
+
+/// `SE_GROUP_INTEGRITY`, the integrity label attribute of the token groups
+const SE_GROUP_INTEGRITY: u32 = 0x20;
+/// Processes that the program and its children can have alive at the same time
+const ACTIVE_PROCESS_LIMIT: u32 = 8;
+/// Memory that each process of the job can commit
+const PROCESS_MEMORY_LIMIT: usize = 512 * 1024 * 1024;
+
+/// Quotes the argument following the rules of `CommandLineToArgvW`, so it is received as it is
+pub fn quote_arg(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains([' ', '\t', '\n', '\x0b', '"']) {
+        return arg.to_string();
+    }
+
+    let mut quoted = String::from('"');
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        match c {
+            '\\' => backslashes += 1,
+            '"' => {
+                // the backslashes before a quote are escaped and the quote too
+                quoted.push_str(&"\\".repeat(backslashes * 2 + 1));
+                quoted.push('"');
+                backslashes = 0;
+            }
+            c => {
+                quoted.push_str(&"\\".repeat(backslashes));
+                quoted.push(c);
+                backslashes = 0;
+            }
+        }
+    }
+    // the backslashes before the closing quote are escaped
+    quoted.push_str(&"\\".repeat(backslashes * 2));
+    quoted.push('"');
+    quoted
+}
+
+/// Copy of the token of Seelen UI without privileges, admin group and with low integrity,
+/// so the program can't write on the files, registry or windows of the user.
+fn restricted_token() -> Result<HANDLE> {
+    let mut token = HANDLE::default();
+    unsafe {
+        OpenProcessToken(
+            GetCurrentProcess(),
+            TOKEN_DUPLICATE | TOKEN_ASSIGN_PRIMARY | TOKEN_QUERY | TOKEN_ADJUST_DEFAULT,
+            &mut token,
+        )?
+    };
+
+    let mut restricted = HANDLE::default();
+    let result = unsafe {
+        CreateRestrictedToken(
+            token,
+            DISABLE_MAX_PRIVILEGE | LUA_TOKEN,
+            None,
+            None,
+            None,
+            &mut restricted,
+        )
+    };
+    WindowsApi::close_handle(token)?;
+    result?;
+
+    if let Err(err) = set_low_integrity(restricted) {
+        WindowsApi::close_handle(restricted)?;
+        return Err(err);
+    }
+    Ok(restricted)
+}
+
+fn set_low_integrity(token: HANDLE) -> Result<()> {
+    let mut sid = PSID::default();
+    unsafe { ConvertStringSidToSidW(w!("S-1-16-4096"), &mut sid)? };
+    let label = TOKEN_MANDATORY_LABEL {
+        Label: SID_AND_ATTRIBUTES {
+            Sid: sid,
+            Attributes: SE_GROUP_INTEGRITY,
+        },
+    };
+    let result = unsafe {
+        SetTokenInformation(
+            token,
+            TokenIntegrityLevel,
+            &label as *const _ as *const c_void,
+            std::mem::size_of::<TOKEN_MANDATORY_LABEL>() as u32 + GetLengthSid(sid),
+        )
+    };
+    unsafe { LocalFree(HLOCAL(sid.0)) };
+    Ok(result?)
+}
+
+/// Job that kills the program and its children when closed, limiting the amount of processes,
+/// the memory and the access to the clipboard, desktops and system settings.
+fn restricted_job() -> Result<HANDLE> {
+    let job = unsafe { CreateJobObjectW(None, PCWSTR::null())? };
+
+    let mut limits = JOBOBJECT_EXTENDED_LIMIT_INFORMATION::default();
+    limits.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE
+        | JOB_OBJECT_LIMIT_ACTIVE_PROCESS
+        | JOB_OBJECT_LIMIT_PROCESS_MEMORY
+        | JOB_OBJECT_LIMIT_DIE_ON_UNHANDLED_EXCEPTION;
+    limits.BasicLimitInformation.ActiveProcessLimit = ACTIVE_PROCESS_LIMIT;
+    limits.ProcessMemoryLimit = PROCESS_MEMORY_LIMIT;
+
+    let ui = JOBOBJECT_BASIC_UI_RESTRICTIONS {
+        UIRestrictionsClass: JOB_OBJECT_UILIMIT_DESKTOP
+            | JOB_OBJECT_UILIMIT_DISPLAYSETTINGS
+            | JOB_OBJECT_UILIMIT_EXITWINDOWS
+            | JOB_OBJECT_UILIMIT_GLOBALATOMS
+            | JOB_OBJECT_UILIMIT_HANDLES
+            | JOB_OBJECT_UILIMIT_READCLIPBOARD
+            | JOB_OBJECT_UILIMIT_SYSTEMPARAMETERS
+            | JOB_OBJECT_UILIMIT_WRITECLIPBOARD,
+    };
+
+    let result = unsafe {
+        SetInformationJobObject(
+            job,
+            JobObjectExtendedLimitInformation,
+            &limits as *const _ as *const c_void,
+            std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+        )
+        .and_then(|_| {
+            SetInformationJobObject(
+                job,
+                JobObjectBasicUIRestrictions,
+                &ui as *const _ as *const c_void,
+                std::mem::size_of::<JOBOBJECT_BASIC_UI_RESTRICTIONS>() as u32,
+            )
+        })
+    };
+    if let Err(err) = result {
+        WindowsApi::close_handle(job)?;
+        return Err(err.into());
+    }
+    Ok(job)
+}
+
+/// Environment of Seelen UI plus the given variables, as expected by `CreateProcessAsUserW`
+fn environment_block(extra: &[(&str, String)]) -> Vec<u16> {
+    let mut block = Vec::new();
+    let inherited = std::env::vars_os()
+        .filter(|(key, _)| !extra.iter().any(|(name, _)| key.eq_ignore_ascii_case(name)))
+        .map(|(key, value)| format!("{}={}", key.to_string_lossy(), value.to_string_lossy()));
+    let extra = extra
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value));
+    for var in inherited.chain(extra) {
+        block.extend(var.encode_utf16());
+        block.push(0);
+    }
+    block.push(0);
+    block
+}
+
+/// Program started by a trigger, running with a restricted token inside a job object
+pub struct SandboxedProcess {
+    job: HANDLE,
+    process: HANDLE,
+}
+
+// the handles are owned by the struct and only closed on drop
+unsafe impl Send for SandboxedProcess {}
+
+impl SandboxedProcess {
+    pub fn spawn(program: &str, args: &[String], env: &[(&str, String)]) -> Result<Self> {
+        let mut sandbox = Self {
+            job: restricted_job()?,
+            process: HANDLE::default(),
+        };
+
+        let command_line = std::iter::once(program)
+            .chain(args.iter().map(String::as_str))
+            .map(quote_arg)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut command_line: Vec<u16> = command_line.encode_utf16().chain(Some(0)).collect();
+        let environment = environment_block(env);
+
+        let token = restricted_token()?;
+        let startup = STARTUPINFOW {
+            cb: std::mem::size_of::<STARTUPINFOW>() as u32,
+            ..Default::default()
+        };
+        let mut info = PROCESS_INFORMATION::default();
+        let result = unsafe {
+            CreateProcessAsUserW(
+                token,
+                PCWSTR::null(),
+                PWSTR(command_line.as_mut_ptr()),
+                None,
+                None,
+                false,
+                CREATE_SUSPENDED | CREATE_NO_WINDOW | CREATE_UNICODE_ENVIRONMENT,
+                Some(environment.as_ptr() as *const c_void),
+                PCWSTR::null(),
+                &startup,
+                &mut info,
+            )
+        };
+        WindowsApi::close_handle(token)?;
+        result?;
+        sandbox.process = info.hProcess;
+
+        // the program only starts running once it is inside the job
+        let assigned = unsafe { AssignProcessToJobObject(sandbox.job, info.hProcess) };
+        match assigned {
+            Ok(_) => unsafe {
+                ResumeThread(info.hThread);
+            },
+            // outside of the job it would not be killed on drop
+            Err(_) => unsafe {
+                let _ = TerminateProcess(info.hProcess, 1);
+            },
+        }
+        WindowsApi::close_handle(info.hThread)?;
+        assigned?;
+        Ok(sandbox)
+    }
+
+    /// Waits the exit of the program, killing it and its children if it exceeds the timeout
+    pub fn wait(self, timeout: Duration) -> Result<()> {
+        let event = unsafe { WaitForSingleObject(self.process, timeout.as_millis() as u32) };
+        if event == WAIT_TIMEOUT {
+            log::warn!("Trigger process timed out, killing it");
+            unsafe { TerminateJobObject(self.job, 1)? };
+        }
+        Ok(())
+    }
+}
+
+impl Drop for SandboxedProcess {
+    fn drop(&mut self) {
+        for handle in [self.process, self.job] {
+            if !handle.is_invalid() {
+                let _ = WindowsApi::close_handle(handle);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_arg() {
+        assert_eq!(quote_arg("simple"), "simple");
+        assert_eq!(quote_arg(""), "\"\"");
+        assert_eq!(quote_arg("with space"), "\"with space\"");
+        assert_eq!(quote_arg("C:\\path\\"), "C:\\path\\");
+        assert_eq!(quote_arg("C:\\my path\\"), "\"C:\\my path\\\\\"");
+        assert_eq!(quote_arg("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(quote_arg("a\\\"b"), "\"a\\\\\\\"b\"");
+    }
+}
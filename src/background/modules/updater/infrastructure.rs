@@ -0,0 +1,33 @@
+use crate::{
+    error_handler::Result, log_error, modules::crash_guard::CrashGuard, seelen::get_app_handle,
+};
+
+use super::{SeelenUpdater, UpdaterStatus};
+
+#[tauri::command(async)]
+pub async fn check_for_updates() -> Result<bool> {
+    Ok(SeelenUpdater::check().await?.is_some())
+}
+
+#[tauri::command(async)]
+pub fn updater_get_status() -> UpdaterStatus {
+    SeelenUpdater::status()
+}
+
+/// Downloads the update to be installed on next restart
+#[tauri::command(async)]
+pub async fn updater_download() -> Result<bool> {
+    SeelenUpdater::download().await
+}
+
+#[tauri::command(async)]
+pub async fn install_last_available_update() -> Result<()> {
+    if !SeelenUpdater::status().ready && !SeelenUpdater::download().await? {
+        return Err("There is no update available".into());
+    }
+    SeelenUpdater::install_pending()?;
+    log_error!(CrashGuard::on_exit());
+    get_app_handle().restart();
+    #[allow(unreachable_code)]
+    Ok(())
+}
@@ -0,0 +1,305 @@
+pub mod infrastructure;
+
+use std::time::{Duration, Instant};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{handlers::SeelenEvent, state::UpdateChannel};
+use serde::{Deserialize, Serialize};
+use tauri::Url;
+use tauri_plugin_http::reqwest;
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::event_registry::EventRegistry,
+    seelen::{get_app_handle, Seelen},
+    state::application::FULL_STATE,
+    trace_lock,
+    utils::spawn_named_thread,
+};
+
+lazy_static! {
+    static ref PENDING_UPDATE: Mutex<Option<PendingUpdate>> = Mutex::new(None);
+    static ref STATUS: Mutex<UpdaterStatus> = Mutex::new(UpdaterStatus::default());
+}
+
+struct PendingUpdate {
+    update: Update,
+    bytes: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdaterStatus {
+    /// version of the available update
+    pub version: Option<String>,
+    pub downloading: bool,
+    /// downloaded bytes
+    pub downloaded: u64,
+    /// total bytes, if known
+    pub total: Option<u64>,
+    /// downloaded and verified, will be installed on next restart
+    pub ready: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    draft: bool,
+    assets: Vec<GithubAsset>,
+}
+
+/// Manifest of the newest release including pre-releases, the releases are sorted by date
+fn newest_manifest(releases: &[GithubRelease]) -> Option<&str> {
+    releases
+        .iter()
+        .filter(|release| !release.draft && release.tag_name != "nightly")
+        .find_map(|release| {
+            release
+                .assets
+                .iter()
+                .find(|asset| asset.name == "latest.json")
+                .map(|asset| asset.browser_download_url.as_str())
+        })
+}
+
+/// Checks for updates on the user channel and downloads them on background,
+/// the downloaded update is installed when the app exits (or restarts).
+///
+/// The release channel uses the endpoints of `tauri.conf.json`, the others replace them
+/// with their own manifest.
+pub struct SeelenUpdater;
+
+impl SeelenUpdater {
+    const STARTUP_DELAY: Duration = Duration::from_secs(60);
+    const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+    /// how often the exit of the app is checked while waiting for the next check
+    const EXIT_POLL: Duration = Duration::from_secs(1);
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+    /// `releases/latest` excludes pre-releases, so the beta manifest is searched on the list
+    const RELEASES_API: &'static str =
+        "https://api.github.com/repos/eythaann/Seelen-UI/releases?per_page=20";
+    const NIGHTLY_ENDPOINT: &'static str =
+        "https://github.com/eythaann/Seelen-UI/releases/download/nightly/latest.json";
+
+    fn is_update_valid_on_channel(update: &Update, channel: UpdateChannel) -> bool {
+        match channel {
+            // all updates are available on nightly
+            UpdateChannel::Nightly => true,
+            // version must not contain `+` that means <build-identifier> used for nightly in this project
+            UpdateChannel::Beta => !update.version.contains('+'),
+            // version must not contain `+` used for nightly or `-` used for <pre-releases> as `-beta` or `-alpha`
+            UpdateChannel::Release => {
+                !update.version.contains('+') && !update.version.contains('-')
+            }
+        }
+    }
+
+    async fn beta_endpoint() -> Result<String> {
+        let response = reqwest::Client::new()
+            .get(Self::RELEASES_API)
+            // required by the github api
+            .header(reqwest::header::USER_AGENT, "Seelen-UI")
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .timeout(Self::REQUEST_TIMEOUT)
+            .send()
+            .await?
+            .error_for_status()?;
+        let releases: Vec<GithubRelease> = serde_json::from_slice(&response.bytes().await?)?;
+        newest_manifest(&releases)
+            .map(ToString::to_string)
+            .ok_or("No release with an update manifest was found".into())
+    }
+
+    /// The endpoint of the channel, `None` to use the ones of `tauri.conf.json`
+    async fn channel_endpoint(channel: UpdateChannel) -> Result<Option<String>> {
+        Ok(match channel {
+            UpdateChannel::Release => None,
+            UpdateChannel::Beta => Some(Self::beta_endpoint().await?),
+            UpdateChannel::Nightly => Some(Self::NIGHTLY_ENDPOINT.to_string()),
+        })
+    }
+
+    pub async fn check() -> Result<Option<Update>> {
+        if tauri::is_dev() || std::env::current_exe()?.starts_with("C:\\Program Files\\WindowsApps")
+        {
+            return Ok(None);
+        }
+
+        let channel = FULL_STATE.load().settings.updater.channel;
+        let mut builder = get_app_handle().updater_builder();
+        if let Some(endpoint) = Self::channel_endpoint(channel).await? {
+            log::trace!(
+                "Update: using the {:?} channel endpoint {}",
+                channel,
+                endpoint
+            );
+            let url = Url::parse(&endpoint).map_err(|e| e.to_string())?;
+            builder = builder.endpoints(vec![url]);
+        }
+        let update = builder.build()?.check().await?;
+        Ok(update.filter(|update| Self::is_update_valid_on_channel(update, channel)))
+    }
+
+    pub fn status() -> UpdaterStatus {
+        trace_lock!(STATUS).clone()
+    }
+
+    fn set_status(f: impl FnOnce(&mut UpdaterStatus)) {
+        let status = {
+            let mut status = trace_lock!(STATUS);
+            f(&mut status);
+            status.clone()
+        };
+        log_error!(EventRegistry::emit(SeelenEvent::UpdaterStatus, status));
+    }
+
+    /// Downloads the available update, returns false if there is no update
+    pub async fn download() -> Result<bool> {
+        if Self::status().downloading {
+            return Err("The update is already being downloaded".into());
+        }
+
+        let update = match Self::check().await? {
+            Some(update) => update,
+            None => return Ok(false),
+        };
+
+        let already_downloaded = trace_lock!(PENDING_UPDATE)
+            .as_ref()
+            .is_some_and(|pending| pending.update.version == update.version);
+        if already_downloaded {
+            return Ok(true);
+        }
+
+        log::trace!("Update: downloading {}", update.version);
+        let version = update.version.clone();
+        Self::set_status(|status| {
+            *status = UpdaterStatus {
+                version: Some(version),
+                downloading: true,
+                ..Default::default()
+            }
+        });
+
+        let mut downloaded = 0;
+        let mut last_percent = 0;
+        // the signature is verified by the plugin using the configured public key
+        let result = update
+            .download(
+                |chunk_length, content_length| {
+                    downloaded += chunk_length as u64;
+                    let percent = content_length.map_or(0, |total| downloaded * 100 / total.max(1));
+                    // notify only on each percent to avoid flooding the webviews
+                    if percent != last_percent {
+                        last_percent = percent;
+                        Self::set_status(|status| {
+                            status.downloaded = downloaded;
+                            status.total = content_length;
+                        });
+                    }
+                },
+                || log::trace!("Update: download finished"),
+            )
+            .await;
+
+        match result {
+            Ok(bytes) => {
+                *trace_lock!(PENDING_UPDATE) = Some(PendingUpdate { update, bytes });
+                Self::set_status(|status| {
+                    status.downloading = false;
+                    status.ready = true;
+                });
+                Ok(true)
+            }
+            Err(err) => {
+                Self::set_status(|status| status.downloading = false);
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Runs the installer of the downloaded update, returns false if there is nothing to install
+    pub fn install_pending() -> Result<bool> {
+        let pending = trace_lock!(PENDING_UPDATE).take();
+        match pending {
+            Some(pending) => {
+                log::info!("Update: installing {}", pending.update.version);
+                pending.update.install(&pending.bytes)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Sleeps the given time, returns false if the app is closed meanwhile
+    fn wait(duration: Duration) -> bool {
+        let until = Instant::now() + duration;
+        loop {
+            if !Seelen::is_running() {
+                return false;
+            }
+            let now = Instant::now();
+            if now >= until {
+                return true;
+            }
+            std::thread::sleep(Self::EXIT_POLL.min(until - now));
+        }
+    }
+
+    pub fn start() -> Result<()> {
+        spawn_named_thread("Updater", || {
+            let mut delay = Self::STARTUP_DELAY;
+            while Self::wait(delay) {
+                if FULL_STATE.load().settings.updater.auto_download {
+                    log_error!(tauri::async_runtime::block_on(Self::download()));
+                }
+                delay = Self::CHECK_INTERVAL;
+            }
+        })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag: &str, draft: bool, assets: &[&str]) -> GithubRelease {
+        GithubRelease {
+            tag_name: tag.to_string(),
+            draft,
+            assets: assets
+                .iter()
+                .map(|name| GithubAsset {
+                    name: name.to_string(),
+                    browser_download_url: format!("https://example.com/{}/{}", tag, name),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_newest_manifest_includes_prereleases() {
+        let releases = vec![
+            release("nightly", false, &["latest.json"]),
+            release("v2.1.0-beta.2", true, &["latest.json"]),
+            release("v2.1.0-beta.1", false, &["Seelen.UI_2.1.0-beta.1_x64-setup.exe"]),
+            release("v2.1.0-beta.0", false, &["latest.json"]),
+            release("v2.0.4", false, &["latest.json"]),
+        ];
+        assert_eq!(
+            newest_manifest(&releases),
+            Some("https://example.com/v2.1.0-beta.0/latest.json")
+        );
+        assert_eq!(newest_manifest(&releases[..2]), None);
+    }
+}
@@ -0,0 +1,16 @@
+use seelen_core::system_state::UrgentWindow;
+
+use crate::error_handler::Result;
+
+use super::Urgency;
+
+#[tauri::command(async)]
+pub fn get_urgent_windows() -> Vec<UrgentWindow> {
+    Urgency::get_all()
+}
+
+/// Clears the urgency of the given window or of all the windows
+#[tauri::command(async)]
+pub fn clear_urgency(hwnd: Option<isize>) -> Result<()> {
+    Urgency::clear(hwnd)
+}
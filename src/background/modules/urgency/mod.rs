@@ -0,0 +1,182 @@
+pub mod infrastructure;
+
+use std::{
+    collections::HashSet,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{handlers::SeelenEvent, system_state::UrgentWindow};
+use windows::{
+    core::w,
+    Win32::{
+        Foundation::{HWND, LPARAM, LRESULT, WPARAM},
+        UI::WindowsAndMessaging::{
+            CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW,
+            RegisterShellHookWindow, RegisterWindowMessageW, TranslateMessage, HSHELL_FLASH, MSG,
+            WINDOW_EX_STYLE, WINDOW_STYLE, WNDCLASSW,
+        },
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    hook::{HookManager, WinEventConsumer, WinEventFilter},
+    log_error,
+    modules::{event_registry::EventRegistry, virtual_desk::get_vd_manager},
+    seelen_weg::SeelenWeg,
+    trace_lock,
+    utils::spawn_named_thread,
+    windows_api::{window::Window, WindowsApi},
+    winevent::WinEvent,
+};
+
+/// registered message used by the system to notify the shell hook windows
+static SHELLHOOK: AtomicU32 = AtomicU32::new(0);
+
+lazy_static! {
+    /// app windows that flashed and were not focused since then
+    static ref URGENT: Mutex<HashSet<isize>> = Mutex::new(HashSet::new());
+}
+
+/// Keeps track of the windows requesting attention (`FlashWindow`), the flashes are notified to the
+/// shell hook windows so they are received even if the native taskbar is hidden.
+pub struct Urgency;
+
+impl Urgency {
+    /// events that end the urgency of a window, used by the subscription and the processing
+    const CLEARED_BY: [WinEvent; 2] = [WinEvent::SystemForeground, WinEvent::ObjectDestroy];
+
+    pub fn get_all() -> Vec<UrgentWindow> {
+        let vd = get_vd_manager();
+        trace_lock!(URGENT)
+            .iter()
+            .map(|hwnd| UrgentWindow {
+                hwnd: *hwnd,
+                workspace: vd.get_by_window(*hwnd).ok().map(|workspace| workspace.id()),
+            })
+            .collect()
+    }
+
+    /// Only the events of the urgent windows are received, none while there are no urgent windows
+    pub fn win_event_filter() -> Option<WinEventFilter> {
+        let urgent = trace_lock!(URGENT);
+        (!urgent.is_empty()).then(|| {
+            WinEventFilter::events(Self::wants_win_event).only_windows(urgent.iter().copied())
+        })
+    }
+
+    fn emit() -> Result<()> {
+        HookManager::set_subscription(WinEventConsumer::Urgency, Self::win_event_filter());
+        EventRegistry::emit(SeelenEvent::GlobalUrgentChanged, Self::get_all())?;
+        Ok(())
+    }
+
+    fn on_flash(hwnd: HWND) -> Result<()> {
+        let window = Window::from(hwnd);
+        // the focused window flashing is only a visual cue, the user is already there
+        if window.is_foreground() || !SeelenWeg::should_be_added(hwnd) {
+            return Ok(());
+        }
+        if trace_lock!(URGENT).insert(window.address()) {
+            Self::emit()?;
+        }
+        Ok(())
+    }
+
+    pub fn clear(hwnd: Option<isize>) -> Result<()> {
+        let changed = {
+            let mut urgent = trace_lock!(URGENT);
+            match hwnd {
+                Some(hwnd) => urgent.remove(&hwnd),
+                None => {
+                    let changed = !urgent.is_empty();
+                    urgent.clear();
+                    changed
+                }
+            }
+        };
+        if changed {
+            Self::emit()?;
+        }
+        Ok(())
+    }
+
+    fn wants_win_event(event: &WinEvent) -> bool {
+        Self::CLEARED_BY.contains(event)
+    }
+
+    pub fn process_win_event(event: WinEvent, window: &Window) -> Result<()> {
+        if Self::wants_win_event(&event) {
+            return Self::clear(Some(window.address()));
+        }
+        Ok(())
+    }
+
+    unsafe extern "system" fn window_proc(
+        window: HWND,
+        message: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        let shellhook = SHELLHOOK.load(Ordering::Relaxed);
+        if shellhook != 0 && message == shellhook {
+            if wparam.0 as u32 == HSHELL_FLASH {
+                log_error!(Self::on_flash(HWND(lparam.0 as _)));
+            }
+            return LRESULT(0);
+        }
+        DefWindowProcW(window, message, wparam, lparam)
+    }
+
+    unsafe fn create_background_window() -> Result<()> {
+        let wide_class = w!("SeelenUrgency");
+        let h_module = WindowsApi::module_handle_w()?;
+
+        let wnd_class = WNDCLASSW {
+            lpfnWndProc: Some(Self::window_proc),
+            hInstance: h_module.into(),
+            lpszClassName: wide_class,
+            ..Default::default()
+        };
+
+        RegisterClassW(&wnd_class);
+
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            wide_class,
+            w!("Seelen Urgency"),
+            WINDOW_STYLE::default(),
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            h_module,
+            None,
+        )?;
+
+        let shellhook = RegisterWindowMessageW(w!("SHELLHOOK"));
+        if shellhook == 0 {
+            return Err("Failed to register SHELLHOOK message".into());
+        }
+        SHELLHOOK.store(shellhook, Ordering::SeqCst);
+        RegisterShellHookWindow(hwnd).ok()?;
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, hwnd, 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+        Ok(())
+    }
+
+    pub fn start() -> Result<()> {
+        spawn_named_thread("Urgency", || unsafe {
+            log_error!(Self::create_background_window());
+        })?;
+        Ok(())
+    }
+}
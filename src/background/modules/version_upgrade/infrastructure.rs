@@ -0,0 +1,14 @@
+use seelen_core::system_state::VersionUpgrade;
+
+use super::VersionUpgrades;
+
+/// Notes of the last upgrade, null if there is no upgrade or it was already dismissed
+#[tauri::command(async)]
+pub fn get_version_upgrade() -> Option<VersionUpgrade> {
+    VersionUpgrades::pending()
+}
+
+#[tauri::command(async)]
+pub fn dismiss_version_upgrade() {
+    VersionUpgrades::dismiss();
+}
@@ -0,0 +1,335 @@
+pub mod infrastructure;
+
+use std::path::Path;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{
+    handlers::SeelenEvent,
+    system_state::{ChangelogRelease, ChangelogSection, RestorePointReason, VersionUpgrade},
+};
+use serde_json::{Map, Value};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::{event_registry::EventRegistry, restore_points::RestorePoints},
+    trace_lock,
+};
+
+lazy_static! {
+    static ref PENDING_UPGRADE: Mutex<Option<VersionUpgrade>> = Mutex::new(None);
+}
+
+const CHANGELOG: &str = include_str!("../../../../changelog.md");
+/// last version that ran with this data dir
+const VERSION_FILE: &str = "last_version";
+const UNRELEASED: &str = "Unreleased";
+
+struct SettingsMigration {
+    /// applied when upgrading from a version below this one
+    version: &'static str,
+    description: &'static str,
+    /// returns true if the settings were changed
+    apply: fn(&mut Map<String, Value>) -> bool,
+}
+
+static MIGRATIONS: [SettingsMigration; 1] = [SettingsMigration {
+    version: "1.9.8",
+    description: "settings keys were renamed to camelCase",
+    apply: migrate_snake_case_keys,
+}];
+
+fn snake_to_camel(key: &str) -> String {
+    let mut result = String::with_capacity(key.len());
+    let mut upper = false;
+    for c in key.chars() {
+        match c {
+            '_' => upper = true,
+            c if upper => {
+                result.extend(c.to_uppercase());
+                upper = false;
+            }
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// The keys are read through serde aliases, this only cleans the file.
+/// Nested objects are not touched as some of them are maps with user defined keys.
+fn migrate_snake_case_keys(settings: &mut Map<String, Value>) -> bool {
+    let mut changed = false;
+    let snake_keys: Vec<String> = settings
+        .keys()
+        .filter(|k| k.contains('_'))
+        .cloned()
+        .collect();
+    for key in snake_keys {
+        let new_key = match key.as_str() {
+            "selected_theme" => "selectedThemes".to_string(),
+            key => snake_to_camel(key),
+        };
+        if let Some(value) = settings.remove(&key) {
+            // a value already using the new key wins, same as serde would do
+            settings.entry(new_key).or_insert(value);
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Parses `x.y.z`, a leading `v` and a pre-release suffix are ignored
+fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
+    let version = version.trim().trim_start_matches('v');
+    let version = version.split(['-', '+']).next()?;
+    let mut parts = version.split('.').map(|part| part.parse::<u32>().ok());
+    Some((parts.next()??, parts.next()??, parts.next()??))
+}
+
+/// Releases of a keep-a-changelog file, in the same order of the file
+fn parse_changelog(content: &str) -> Vec<ChangelogRelease> {
+    let mut releases: Vec<ChangelogRelease> = Vec::new();
+    for line in content.lines().map(str::trim) {
+        if let Some(title) = line.strip_prefix("## ") {
+            releases.push(ChangelogRelease {
+                version: title.trim_matches(['[', ']']).to_string(),
+                sections: Vec::new(),
+            });
+            continue;
+        }
+
+        let Some(release) = releases.last_mut() else {
+            continue;
+        };
+
+        if let Some(kind) = line.strip_prefix("### ") {
+            release.sections.push(ChangelogSection {
+                kind: kind.trim().to_string(),
+                entries: Vec::new(),
+            });
+        } else if let Some(entry) = line.strip_prefix("- ") {
+            if let Some(section) = release.sections.last_mut() {
+                section.entries.push(entry.to_string());
+            }
+        }
+    }
+    releases
+}
+
+/// Releases newer than `previous` up to `current`, the unreleased entries are only
+/// relevant for development builds (the current version is not on the changelog yet).
+fn releases_between(
+    releases: Vec<ChangelogRelease>,
+    previous: Option<&str>,
+    current: &str,
+) -> Vec<ChangelogRelease> {
+    let previous = previous.and_then(parse_version);
+    let Some(current_version) = parse_version(current) else {
+        return Vec::new();
+    };
+    let is_released = releases
+        .iter()
+        .any(|r| parse_version(&r.version) == Some(current_version));
+
+    releases
+        .into_iter()
+        .filter(|release| match parse_version(&release.version) {
+            Some(version) => {
+                version <= current_version
+                    && previous.map_or(version == current_version, |p| version > p)
+            }
+            None => release.version == UNRELEASED && !is_released,
+        })
+        .collect()
+}
+
+/// Migrations needed to go from `previous` to the current version, all if it is unknown
+fn pending_migrations(previous: Option<&str>) -> impl Iterator<Item = &'static SettingsMigration> {
+    let previous = previous.and_then(parse_version);
+    MIGRATIONS.iter().filter(move |migration| {
+        let version = parse_version(migration.version);
+        previous.map_or(true, |previous| version.is_some_and(|v| previous < v))
+    })
+}
+
+/// Detects the first run after a version change, the notes are kept until the user dismisses them
+pub struct VersionUpgrades;
+
+impl VersionUpgrades {
+    /// Should be called before the state is loaded, so the migrated settings are the ones read
+    pub fn detect(data_dir: &Path) -> Result<()> {
+        let current = env!("CARGO_PKG_VERSION");
+        let version_path = data_dir.join(VERSION_FILE);
+        let settings_path = data_dir.join("settings.json");
+
+        let previous = std::fs::read_to_string(&version_path)
+            .ok()
+            .map(|version| version.trim().to_string());
+        if previous.as_deref() == Some(current) {
+            return Ok(());
+        }
+
+        // fresh install, there is nothing new for the user
+        if previous.is_none() && !settings_path.exists() {
+            std::fs::write(&version_path, current)?;
+            return Ok(());
+        }
+
+        log::info!(
+            "Upgraded from {} to {}",
+            previous.as_deref().unwrap_or("unknown version"),
+            current
+        );
+        let migrations = Self::migrate_settings(&settings_path, previous.as_deref())?;
+        let notes = releases_between(parse_changelog(CHANGELOG), previous.as_deref(), current);
+        std::fs::write(&version_path, current)?;
+
+        *trace_lock!(PENDING_UPGRADE) = Some(VersionUpgrade {
+            previous,
+            current: current.to_string(),
+            notes,
+            migrations,
+        });
+        Ok(())
+    }
+
+    fn migrate_settings(path: &Path, previous: Option<&str>) -> Result<Vec<String>> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut settings: Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        let Some(map) = settings.as_object_mut() else {
+            return Ok(Vec::new());
+        };
+
+        let mut applied = Vec::new();
+        for migration in pending_migrations(previous) {
+            if (migration.apply)(map) {
+                applied.push(migration.description.to_string());
+            }
+        }
+
+        if !applied.is_empty() {
+            log_error!(RestorePoints::create(
+                RestorePointReason::VersionUpgrade,
+                previous.map(|v| v.to_string())
+            ));
+            std::fs::write(path, serde_json::to_string_pretty(&settings)?)?;
+            log::info!("Settings migrated: {}", applied.join(", "));
+        }
+        Ok(applied)
+    }
+
+    pub fn pending() -> Option<VersionUpgrade> {
+        trace_lock!(PENDING_UPGRADE).clone()
+    }
+
+    pub fn dismiss() {
+        trace_lock!(PENDING_UPGRADE).take();
+    }
+
+    /// Notifies the views already running, the ones created later ask for the pending upgrade
+    pub fn emit_pending() -> Result<()> {
+        if let Some(upgrade) = Self::pending() {
+            EventRegistry::emit(SeelenEvent::VersionUpgraded, upgrade)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const CHANGELOG_SAMPLE: &str = "# Changelog
+
+## [Unreleased]
+### features
+- new thing.
+
+## [2.0.4]
+### fix
+- fix a.
+- fix b.
+
+## [2.0.3]
+### fix
+- fix c.
+
+## [2.0.2]
+### enhancements
+- better d.
+";
+
+    fn versions(releases: &[ChangelogRelease]) -> Vec<&str> {
+        releases.iter().map(|r| r.version.as_str()).collect()
+    }
+
+    #[test]
+    fn test_parse_changelog() {
+        let releases = parse_changelog(CHANGELOG_SAMPLE);
+        assert_eq!(
+            versions(&releases),
+            ["Unreleased", "2.0.4", "2.0.3", "2.0.2"]
+        );
+        assert_eq!(releases[1].sections[0].kind, "fix");
+        assert_eq!(releases[1].sections[0].entries, ["fix a.", "fix b."]);
+    }
+
+    #[test]
+    fn test_releases_between() {
+        let releases = || parse_changelog(CHANGELOG_SAMPLE);
+        assert_eq!(
+            versions(&releases_between(releases(), Some("2.0.2"), "2.0.4")),
+            ["2.0.4", "2.0.3"]
+        );
+        // unknown previous version, only the notes of the current one
+        assert_eq!(
+            versions(&releases_between(releases(), None, "2.0.4")),
+            ["2.0.4"]
+        );
+        // development build, not released yet
+        assert_eq!(
+            versions(&releases_between(releases(), Some("2.0.3"), "2.0.5")),
+            ["Unreleased", "2.0.4"]
+        );
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("v2.0.4"), Some((2, 0, 4)));
+        assert_eq!(parse_version("2.1.0-beta.1"), Some((2, 1, 0)));
+        assert_eq!(parse_version("Unreleased"), None);
+        assert!(parse_version("1.10.0") > parse_version("1.9.8"));
+    }
+
+    #[test]
+    fn test_snake_case_migration() {
+        let mut settings = json!({
+            "ahk_enabled": true,
+            "selected_theme": ["default"],
+            "dateFormat": "YYYY",
+            "date_format": "old",
+            "fancyToolbar": { "some_key": 1 }
+        });
+        let map = settings.as_object_mut().unwrap();
+        assert!(migrate_snake_case_keys(map));
+        assert_eq!(
+            settings,
+            json!({
+                "ahkEnabled": true,
+                "selectedThemes": ["default"],
+                "dateFormat": "YYYY",
+                "fancyToolbar": { "some_key": 1 }
+            })
+        );
+
+        assert_eq!(pending_migrations(Some("1.9.7")).count(), 1);
+        assert_eq!(pending_migrations(Some("1.9.8")).count(), 0);
+        assert_eq!(pending_migrations(None).count(), 1);
+    }
+}
@@ -4,7 +4,12 @@ use clap::Command;
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
 
-use crate::{error_handler::Result, get_subcommands, windows_api::WindowsApi};
+use crate::{
+    error_handler::Result,
+    get_subcommands,
+    seelen_wm_v2::{history::WmCommand, instance::WindowManagerV2},
+    windows_api::WindowsApi,
+};
 
 use super::VirtualDesktopManager;
 
@@ -31,9 +36,24 @@ impl VirtualDesktopManager {
     pub fn get_cli() -> Command {
         Command::new(Self::CLI_IDENTIFIER)
             .about("Manage the Seelen Window Manager.")
-            .visible_alias("vd")
+            .visible_aliases(["vd", "workspace"])
             .arg_required_else_help(true)
             .subcommands(SubCommand::commands())
+            // short verbs, ex: `workspace switch 3`
+            .mut_subcommand("switch-workspace", |c| c.visible_alias("switch"))
+            .mut_subcommand("send-to-workspace", |c| c.visible_alias("send"))
+            .mut_subcommand("move-to-workspace", |c| c.visible_alias("move"))
+            .mut_subcommand("switch-next", |c| c.visible_alias("next"))
+            .mut_subcommand("switch-prev", |c| c.visible_alias("prev"))
+    }
+
+    /// Recorded on the window manager history so it can be reverted with `wm undo`
+    fn send_foreground_to(&self, index: usize) -> Result<()> {
+        WindowManagerV2::execute(WmCommand::SendToWorkspace {
+            window: WindowsApi::get_foreground_window().0 as isize,
+            from: self.get_current_idx()?,
+            to: index,
+        })
     }
 
     pub fn process(&self, matches: &clap::ArgMatches) -> Result<()> {
@@ -42,17 +62,27 @@ impl VirtualDesktopManager {
         let subcommand = SubCommand::try_from(matches)?;
         match subcommand {
             SubCommand::SendToWorkspace(index) => {
-                self.send_to(index, WindowsApi::get_foreground_window().0 as isize)?;
+                self.send_foreground_to(index)?;
             }
             SubCommand::SwitchWorkspace(index) => {
                 self.switch_to(index)?;
             }
             SubCommand::MoveToWorkspace(index) => {
-                self.send_to(index, WindowsApi::get_foreground_window().0 as isize)?;
+                self.send_foreground_to(index)?;
                 std::thread::sleep(std::time::Duration::from_millis(20));
                 self.switch_to(index)?;
             }
-            _ => log::warn!("Unimplemented command: {:?}", subcommand),
+            SubCommand::SwitchNext => {
+                let next = self.get_current_idx()? + 1;
+                if next < self.get_all()?.len() {
+                    self.switch_to(next)?;
+                }
+            }
+            SubCommand::SwitchPrev => {
+                if let Some(prev) = self.get_current_idx()?.checked_sub(1) {
+                    self.switch_to(prev)?;
+                }
+            }
         }
         Ok(())
     }
@@ -1,14 +1,17 @@
 mod cli;
+mod monitor_rules;
 mod native;
 mod workspaces;
 
+pub use monitor_rules::WorkspaceMonitorRules;
+
 use arc_swap::ArcSwap;
 use lazy_static::lazy_static;
 use seelen_core::state::VirtualDesktopStrategy;
 use serde::Serialize;
 use std::sync::Arc;
 
-use crate::{error_handler::Result, state::application::FULL_STATE};
+use crate::{error_handler::Result, hook::WinEventFilter, state::application::FULL_STATE};
 
 lazy_static! {
     pub static ref VIRTUAL_DESKTOP_MANAGER: Arc<ArcSwap<VirtualDesktopManager>> =
@@ -182,6 +185,16 @@ impl VirtualDesktopManager {
             VirtualDesktopManager::Seelen(m) => m.uses_cloak(),
         }
     }
+
+    /// Native virtual desktops are handled by Windows so only Seelen's implementation needs win events
+    pub fn win_event_filter(&self) -> Option<WinEventFilter> {
+        match self {
+            VirtualDesktopManager::Native(_) => None,
+            VirtualDesktopManager::Seelen(_) => Some(WinEventFilter::events(
+                workspaces::SeelenWorkspacesManager::wants_win_event,
+            )),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
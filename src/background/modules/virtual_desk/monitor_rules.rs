@@ -0,0 +1,86 @@
+use seelen_core::state::{WorkspaceIdentifierType, WorkspaceMonitorRule};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::window_filter::WindowFilter,
+    seelen_wm_v2::instance::WindowManagerV2,
+    state::application::FULL_STATE,
+    windows_api::{monitor::Monitor, window::Window, WindowEnumerator},
+};
+
+use super::{get_vd_manager, VirtualDesktop};
+
+/// Windows are moved to the remaining monitors by Windows when a monitor is disconnected,
+/// these rules bring the windows of the bound workspaces back when the monitor is connected again.
+pub struct WorkspaceMonitorRules;
+
+impl WorkspaceMonitorRules {
+    fn resolve(rule: &WorkspaceMonitorRule, workspaces: &[VirtualDesktop]) -> Option<String> {
+        let identifier = &rule.workspace;
+        let workspace = match identifier.kind {
+            WorkspaceIdentifierType::Name => workspaces
+                .iter()
+                .find(|w| w.name().as_deref() == Some(identifier.id.as_str())),
+            WorkspaceIdentifierType::Index => workspaces.get(identifier.id.parse::<usize>().ok()?),
+        };
+        workspace.map(|w| w.id())
+    }
+
+    /// Moves the windows of the workspaces bound to the monitor
+    pub fn on_monitor_connected(monitor_id: &str) -> Result<()> {
+        let state = FULL_STATE.load();
+        let rules = state
+            .settings
+            .workspace_monitor_rules
+            .iter()
+            .filter(|rule| rule.monitor == monitor_id)
+            .collect::<Vec<_>>();
+        if rules.is_empty() {
+            return Ok(());
+        }
+        let Some(target) = Monitor::by_id(monitor_id) else {
+            return Ok(());
+        };
+
+        let vd = get_vd_manager();
+        let workspaces = vd.get_all()?;
+        let bound = rules
+            .iter()
+            .filter_map(|rule| Self::resolve(rule, &workspaces))
+            .collect::<Vec<_>>();
+
+        if state.is_window_manager_enabled() {
+            // the layouts of the monitor are created here, so its tiled windows can be inserted
+            WindowManagerV2::init_state()?;
+        }
+
+        let mut windows = Vec::new();
+        WindowEnumerator::new().for_each(|hwnd| {
+            let window = Window::from(hwnd);
+            let address = window.address();
+            // pinned windows are on all the workspaces
+            if !WindowFilter::interactable(&window, false).included
+                || vd.is_pinned_window(address).unwrap_or(true)
+            {
+                return;
+            }
+            if vd
+                .get_by_window(address)
+                .is_ok_and(|workspace| bound.contains(&workspace.id()))
+            {
+                windows.push(window);
+            }
+        })?;
+
+        log::trace!(
+            "Moving {} windows to the connected monitor {}",
+            windows.len(),
+            monitor_id
+        );
+        for window in windows {
+            log_error!(WindowManagerV2::send_to_monitor(&window, target));
+        }
+        Ok(())
+    }
+}
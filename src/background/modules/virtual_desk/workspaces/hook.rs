@@ -43,6 +43,21 @@ impl SeelenWorkspacesManager {
         Ok(())
     }
 
+    pub fn wants_win_event(event: &WinEvent) -> bool {
+        matches!(
+            event,
+            WinEvent::ObjectCreate
+                | WinEvent::ObjectShow
+                | WinEvent::SystemMinimizeEnd
+                | WinEvent::ObjectDestroy
+                | WinEvent::ObjectHide
+                | WinEvent::SystemMinimizeStart
+                | WinEvent::SystemForeground
+                | WinEvent::ObjectFocus
+                | WinEvent::ObjectParentChange
+        )
+    }
+
     pub fn on_win_event(&self, event: WinEvent, window: &Window) -> Result<()> {
         let addr = window.address();
         match event {
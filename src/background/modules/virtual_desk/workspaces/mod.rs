@@ -16,6 +16,7 @@ use crate::{
     error_handler::{AppError, Result},
     hook::HookManager,
     log_error, trace_lock,
+    utils::lock_stats::TracedGuard,
     windows_api::WindowsApi,
     winevent::WinEvent,
 };
@@ -134,7 +135,7 @@ impl SeelenWorkspacesManager {
         self.current.load(Ordering::Relaxed)
     }
 
-    fn pinned(&self) -> MutexGuard<'_, Vec<isize>> {
+    fn pinned(&self) -> TracedGuard<MutexGuard<'_, Vec<isize>>> {
         trace_lock!(self.pinned)
     }
 
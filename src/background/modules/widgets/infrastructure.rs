@@ -0,0 +1,39 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    error_handler::Result, modules::ipc::application::IpcServer, state::application::FULL_STATE,
+};
+
+#[derive(Deserialize)]
+struct SubscribeParams {
+    events: Vec<String>,
+}
+
+/// Entry point of the widgets api, the toolbar forwards the widget requests here
+/// and they are only executed if allowed by the permissions declared on the manifest.
+#[tauri::command(async)]
+pub fn widget_call(widget_id: String, method: String, params: Option<Value>) -> Result<Value> {
+    let state = FULL_STATE.load();
+    let widget = state
+        .widgets()
+        .get(&widget_id)
+        .ok_or(format!("Widget not found: {}", widget_id))?;
+    let params = params.unwrap_or_default();
+
+    // events are forwarded by the toolbar, here we only validate them
+    if method == "subscribe" {
+        let SubscribeParams { events } = serde_json::from_value(params)?;
+        if let Some(event) = events.iter().find(|e| !widget.allows_event(e)) {
+            return Err(
+                format!("Widget {} has no permission to listen {}", widget_id, event).into(),
+            );
+        }
+        return Ok(Value::Null);
+    }
+
+    if !widget.allows_method(&method) {
+        return Err(format!("Widget {} has no permission to call {}", widget_id, method).into());
+    }
+    IpcServer::handle(&method, params).map_err(|e| e.message.into())
+}
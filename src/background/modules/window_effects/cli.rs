@@ -0,0 +1,52 @@
+use clap::Command;
+
+use crate::{
+    error_handler::Result,
+    get_subcommands,
+    windows_api::{window::Window, WindowsApi},
+};
+
+use super::WindowEffects;
+
+get_subcommands![
+    /** Changes the opacity of the focused window by the given percent, ex: 10 or -10. */
+    Step(delta: i32 => "Percent added to the current opacity, negative to decrease it."),
+    /** Sets the opacity of the focused window. */
+    Set(percent: u32 => "Opacity in percent, from 10 to 100."),
+    /** Restores the opacity of the focused window to the one of the apps rules. */
+    Reset,
+];
+
+impl WindowEffects {
+    pub const CLI_IDENTIFIER: &'static str = "window";
+
+    pub fn get_cli() -> Command {
+        Command::new(Self::CLI_IDENTIFIER)
+            .about("Changes the focused window.")
+            .arg_required_else_help(true)
+            .subcommand(
+                Command::new("opacity")
+                    .about("Changes the opacity of the focused window.")
+                    .arg_required_else_help(true)
+                    .subcommands(SubCommand::commands())
+                    .mut_subcommand("step", |c| {
+                        c.mut_arg("delta", |arg| arg.allow_negative_numbers(true))
+                    }),
+            )
+    }
+
+    pub fn process(matches: &clap::ArgMatches) -> Result<()> {
+        let Some(("opacity", matches)) = matches.subcommand() else {
+            return Err("Unknown subcommand.".into());
+        };
+        let window = Window::from(WindowsApi::get_foreground_window());
+        match SubCommand::try_from(matches)? {
+            SubCommand::Step(delta) => Self::step_opacity(&window, delta)?,
+            SubCommand::Set(percent) => {
+                Self::set_user_opacity(&window, Some(percent as f32 / 100.0))?
+            }
+            SubCommand::Reset => Self::set_user_opacity(&window, None)?,
+        };
+        Ok(())
+    }
+}
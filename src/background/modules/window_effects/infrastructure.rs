@@ -0,0 +1,12 @@
+use std::sync::atomic::Ordering;
+
+use crate::{error_handler::Result, hook::LAST_ACTIVE_NOT_SEELEN, windows_api::window::Window};
+
+use super::WindowEffects;
+
+/// Toggles the effects of the given window or the last focused one
+#[tauri::command(async)]
+pub fn toggle_window_effects(hwnd: Option<isize>) -> Result<()> {
+    let hwnd = hwnd.unwrap_or_else(|| LAST_ACTIVE_NOT_SEELEN.load(Ordering::Acquire));
+    WindowEffects::toggle(&Window::from(hwnd))
+}
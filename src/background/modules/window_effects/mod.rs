@@ -0,0 +1,357 @@
+mod cli;
+pub mod infrastructure;
+
+use std::collections::{HashMap, HashSet};
+
+use std::path::PathBuf;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::state::{AppWindowEffects, WindowBackdrop};
+use tauri::Manager;
+use windows::Win32::{
+    Foundation::HWND,
+    Graphics::Dwm::{
+        DwmExtendFrameIntoClientArea, DWMSBT_AUTO, DWMSBT_MAINWINDOW, DWMSBT_TABBEDWINDOW,
+        DWMSBT_TRANSIENTWINDOW, DWMWA_SYSTEMBACKDROP_TYPE, DWM_SYSTEMBACKDROP_TYPE,
+    },
+    UI::{
+        Controls::MARGINS,
+        WindowsAndMessaging::{WS_CHILD, WS_EX_LAYERED},
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::window_tags::WindowTags,
+    seelen::get_app_handle,
+    state::application::FULL_STATE,
+    trace_lock,
+    windows_api::{window::Window, WindowEnumerator, WindowsApi},
+    winevent::WinEvent,
+};
+
+lazy_static! {
+    /// windows modified by a rule and their state before being modified
+    static ref APPLIED: Mutex<HashMap<isize, AppliedEffects>> = Mutex::new(HashMap::new());
+    /// windows where the effects were turned off by the user
+    static ref DISABLED: Mutex<HashSet<isize>> = Mutex::new(HashSet::new());
+    /// opacity set by the user with the `window opacity` commands, by window
+    static ref USER_OPACITY: Mutex<HashMap<isize, f32>> = Mutex::new(HashMap::new());
+    /// last opacity set by the user by app, only used if `windowOpacity.rememberPerApp` is enabled
+    static ref APP_OPACITY: Mutex<HashMap<String, f32>> =
+        Mutex::new(WindowEffects::load_app_opacity());
+}
+
+/// lower opacity would make the window almost impossible to find
+const MIN_OPACITY: f32 = 0.1;
+
+/// `current` changed by `delta` percent, rounded to avoid the drift of repeated steps
+fn stepped_opacity(current: f32, delta: i32) -> f32 {
+    let percent = (current * 100.0).round() as i32 + delta;
+    (percent as f32 / 100.0).clamp(MIN_OPACITY, 1.0)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct OriginalState {
+    layered: bool,
+    /// alpha of the layered window, if it was already set by the app
+    alpha: Option<u8>,
+    backdrop: DWM_SYSTEMBACKDROP_TYPE,
+}
+
+#[derive(Debug, Clone)]
+struct AppliedEffects {
+    effects: AppWindowEffects,
+    original: OriginalState,
+}
+
+/// Applies the transparency and backdrop effects configured on the apps rules
+pub struct WindowEffects;
+
+impl WindowEffects {
+    fn capture(hwnd: HWND) -> OriginalState {
+        let mut backdrop = DWMSBT_AUTO;
+        // not supported before Windows 11 22H2
+        let _ =
+            WindowsApi::dwm_get_window_attribute(hwnd, DWMWA_SYSTEMBACKDROP_TYPE, &mut backdrop);
+        OriginalState {
+            layered: WindowsApi::get_ex_styles(hwnd).contains(WS_EX_LAYERED),
+            alpha: WindowsApi::get_layered_alpha(hwnd),
+            backdrop,
+        }
+    }
+
+    fn set_opacity(hwnd: HWND, opacity: Option<f32>, original: &OriginalState) -> Result<()> {
+        // windows drawn by `UpdateLayeredWindow` stop rendering if the alpha is set
+        if original.layered && original.alpha.is_none() {
+            return Ok(());
+        }
+        let styles = WindowsApi::get_ex_styles(hwnd);
+        match opacity {
+            Some(opacity) => {
+                WindowsApi::set_ex_styles(hwnd, styles | WS_EX_LAYERED);
+                let alpha = (opacity.clamp(MIN_OPACITY, 1.0) * 255.0).round() as u8;
+                WindowsApi::set_layered_alpha(hwnd, alpha)?;
+            }
+            None => match original.alpha {
+                Some(alpha) => WindowsApi::set_layered_alpha(hwnd, alpha)?,
+                None => WindowsApi::set_ex_styles(hwnd, styles & !WS_EX_LAYERED),
+            },
+        }
+        Ok(())
+    }
+
+    fn set_backdrop(
+        hwnd: HWND,
+        backdrop: Option<WindowBackdrop>,
+        original: &OriginalState,
+    ) -> Result<()> {
+        let value = match backdrop {
+            Some(WindowBackdrop::Mica) => DWMSBT_MAINWINDOW,
+            Some(WindowBackdrop::MicaAlt) => DWMSBT_TABBEDWINDOW,
+            Some(WindowBackdrop::Acrylic) => DWMSBT_TRANSIENTWINDOW,
+            None => original.backdrop,
+        };
+        // the material is only visible behind the client area if the frame is extended into it
+        let margins = match backdrop {
+            Some(_) => MARGINS {
+                cxLeftWidth: -1,
+                cxRightWidth: -1,
+                cyTopHeight: -1,
+                cyBottomHeight: -1,
+            },
+            None => MARGINS::default(),
+        };
+        unsafe { DwmExtendFrameIntoClientArea(hwnd, &margins)? };
+        WindowsApi::dwm_set_window_attribute(hwnd, DWMWA_SYSTEMBACKDROP_TYPE, &value)
+    }
+
+    fn update(
+        hwnd: HWND,
+        from: &AppWindowEffects,
+        to: &AppWindowEffects,
+        original: &OriginalState,
+    ) -> Result<()> {
+        if from.opacity != to.opacity {
+            Self::set_opacity(hwnd, to.opacity, original)?;
+        }
+        if from.backdrop != to.backdrop {
+            Self::set_backdrop(hwnd, to.backdrop, original)?;
+        }
+        Ok(())
+    }
+
+    fn revert(window: &Window) -> Result<()> {
+        if let Some(applied) = trace_lock!(APPLIED).remove(&window.address()) {
+            Self::update(
+                window.hwnd(),
+                &applied.effects,
+                &AppWindowEffects::default(),
+                &applied.original,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn app_opacity_path() -> Result<PathBuf> {
+        Ok(get_app_handle()
+            .path()
+            .app_data_dir()?
+            .join("window_opacity.json"))
+    }
+
+    fn load_app_opacity() -> HashMap<String, f32> {
+        Self::app_opacity_path()
+            .ok()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_app_opacity(apps: &HashMap<String, f32>) -> Result<()> {
+        std::fs::write(Self::app_opacity_path()?, serde_json::to_string(apps)?)?;
+        Ok(())
+    }
+
+    fn rule_effects(window: &Window) -> AppWindowEffects {
+        FULL_STATE
+            .load()
+            .get_app_config_by_window(window.hwnd())
+            .and_then(|config| config.effects.clone())
+            .unwrap_or_default()
+    }
+
+    /// Opacity set by the user on the window, or on another window of the same app if remembered
+    fn user_opacity(window: &Window) -> Option<f32> {
+        if let Some(opacity) = trace_lock!(USER_OPACITY).get(&window.address()) {
+            return Some(*opacity);
+        }
+        if !FULL_STATE.load().settings.window_opacity.remember_per_app {
+            return None;
+        }
+        let app = WindowTags::identity_of(window)?;
+        trace_lock!(APP_OPACITY).get(&app).copied()
+    }
+
+    /// Applies the effects of the rule matching the window, reverting them if the rule was removed
+    pub fn refresh(window: &Window) -> Result<()> {
+        let address = window.address();
+        if trace_lock!(DISABLED).contains(&address)
+            || WindowsApi::get_styles(window.hwnd()).contains(WS_CHILD)
+        {
+            return Ok(());
+        }
+
+        let mut effects = Self::rule_effects(window);
+        if let Some(opacity) = Self::user_opacity(window) {
+            effects.opacity = Some(opacity);
+        }
+
+        let mut applied = trace_lock!(APPLIED);
+        let (current, original) = match applied.get(&address) {
+            Some(applied) => (applied.effects.clone(), applied.original),
+            None if effects == AppWindowEffects::default() => return Ok(()),
+            None => (AppWindowEffects::default(), Self::capture(window.hwnd())),
+        };
+        if current == effects {
+            return Ok(());
+        }
+
+        // the state is saved even on failure so a partial change can still be reverted
+        let result = Self::update(window.hwnd(), &current, &effects, &original);
+        if effects == AppWindowEffects::default() {
+            applied.remove(&address);
+        } else {
+            applied.insert(address, AppliedEffects { effects, original });
+        }
+        result
+    }
+
+    /// Should be called when the apps rules change
+    pub fn refresh_all() -> Result<()> {
+        WindowEnumerator::new().for_each(|hwnd| {
+            log_error!(Self::refresh(&Window::from(hwnd)));
+        })
+    }
+
+    /// Restores all the windows to their original state
+    pub fn revert_all() {
+        let applied = std::mem::take(&mut *trace_lock!(APPLIED));
+        for (address, applied) in applied {
+            let hwnd = HWND(address as _);
+            if WindowsApi::is_window(hwnd) {
+                log_error!(Self::update(
+                    hwnd,
+                    &applied.effects,
+                    &AppWindowEffects::default(),
+                    &applied.original
+                ));
+            }
+        }
+    }
+
+    /// Turns off/on the effects of the rule matching the window
+    pub fn toggle(window: &Window) -> Result<()> {
+        let address = window.address();
+        let enable = {
+            let mut disabled = trace_lock!(DISABLED);
+            let was_disabled = disabled.remove(&address);
+            if !was_disabled {
+                disabled.insert(address);
+            }
+            was_disabled
+        };
+        match enable {
+            true => Self::refresh(window),
+            false => Self::revert(window),
+        }
+    }
+
+    /// Overrides the opacity of the rules on the window, `None` goes back to the one of the rules
+    pub fn set_user_opacity(window: &Window, opacity: Option<f32>) -> Result<()> {
+        let opacity = opacity.map(|opacity| opacity.clamp(MIN_OPACITY, 1.0));
+        {
+            let mut windows = trace_lock!(USER_OPACITY);
+            match opacity {
+                Some(opacity) => windows.insert(window.address(), opacity),
+                None => windows.remove(&window.address()),
+            };
+        }
+
+        if FULL_STATE.load().settings.window_opacity.remember_per_app {
+            if let Some(app) = WindowTags::identity_of(window) {
+                let mut apps = trace_lock!(APP_OPACITY);
+                match opacity {
+                    Some(opacity) => apps.insert(app, opacity),
+                    None => apps.remove(&app),
+                };
+                Self::save_app_opacity(&apps)?;
+            }
+        }
+        Self::refresh(window)
+    }
+
+    /// Changes the opacity of the window by `delta` percent, starting from the current one
+    pub fn step_opacity(window: &Window, delta: i32) -> Result<()> {
+        let current = trace_lock!(APPLIED)
+            .get(&window.address())
+            .and_then(|applied| applied.effects.opacity)
+            .or_else(|| {
+                WindowsApi::get_layered_alpha(window.hwnd()).map(|alpha| alpha as f32 / 255.0)
+            })
+            .unwrap_or(1.0);
+        let opacity = stepped_opacity(current, delta);
+        // fully opaque without a rule is the same as untouched, so the layered style is removed
+        if opacity >= 1.0 && Self::rule_effects(window).opacity.is_none() {
+            return Self::set_user_opacity(window, None);
+        }
+        Self::set_user_opacity(window, Some(opacity))
+    }
+
+    pub fn wants_win_event(event: &WinEvent) -> bool {
+        matches!(
+            event,
+            WinEvent::ObjectCreate
+                | WinEvent::ObjectShow
+                | WinEvent::SystemForeground
+                | WinEvent::ObjectDestroy
+        )
+    }
+
+    pub fn process_win_event(event: WinEvent, window: &Window) -> Result<()> {
+        match event {
+            WinEvent::ObjectDestroy => {
+                trace_lock!(APPLIED).remove(&window.address());
+                trace_lock!(DISABLED).remove(&window.address());
+                trace_lock!(USER_OPACITY).remove(&window.address());
+            }
+            _ => Self::refresh(window)?,
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stepped_opacity() {
+        assert_eq!(stepped_opacity(1.0, -10), 0.9);
+        // repeated steps don't drift
+        let mut opacity = 1.0;
+        for _ in 0..5 {
+            opacity = stepped_opacity(opacity, -10);
+        }
+        for _ in 0..5 {
+            opacity = stepped_opacity(opacity, 10);
+        }
+        assert_eq!(opacity, 1.0);
+        assert_eq!(stepped_opacity(0.2, -50), MIN_OPACITY);
+        assert_eq!(stepped_opacity(0.95, 10), 1.0);
+        // alpha of 204/255 read from a layered window
+        assert_eq!(stepped_opacity(204.0 / 255.0, 10), 0.9);
+    }
+}
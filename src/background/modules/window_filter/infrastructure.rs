@@ -0,0 +1,17 @@
+use seelen_core::system_state::{WindowFilterReport, WindowInspection};
+
+use crate::error_handler::Result;
+
+use super::WindowFilter;
+
+/// Explains why each visible window is or isn't shown on the dock and tiled
+#[tauri::command(async)]
+pub fn window_filter_explain() -> Result<Vec<WindowFilterReport>> {
+    WindowFilter::explain_all()
+}
+
+/// Details of the window under the cursor used to match it against the app configs
+#[tauri::command(async)]
+pub fn window_filter_inspect_at_cursor(flash: bool) -> Result<WindowInspection> {
+    WindowFilter::inspect_at_cursor(flash)
+}
@@ -0,0 +1,294 @@
+pub mod infrastructure;
+
+use std::time::Duration;
+
+use itertools::Itertools;
+use seelen_core::{
+    state::{AppExtraFlag, WindowFiltersSettings},
+    system_state::{
+        WindowFilterKind, WindowFilterOutcome, WindowFilterReport, WindowFilterStep,
+        WindowFilterVerdict, WindowInspection,
+    },
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    WS_CAPTION, WS_EX_APPWINDOW, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_EX_TOPMOST,
+};
+
+use crate::{
+    error_handler::Result,
+    modules::virtual_desk::get_vd_manager,
+    seelen_wm_v2::{border::FocusBorder, instance::WindowManagerV2},
+    state::application::FULL_STATE,
+    windows_api::{window::Window, WindowEnumerator, WindowsApi},
+};
+
+/// time that the inspected window is highlighted
+const FLASH_DURATION: Duration = Duration::from_millis(1500);
+
+/// titles of shell windows that have the styles of a normal window
+const TITLE_BLACKLIST: &[&str] = &[
+    "",
+    "Task Switching",
+    "DesktopWindowXamlSource",
+    "Program Manager",
+];
+
+type Check = (WindowFilterOutcome, String);
+
+fn reject_if(condition: bool, detail: impl ToString) -> Check {
+    let outcome = match condition {
+        true => WindowFilterOutcome::Rejected,
+        false => WindowFilterOutcome::Passed,
+    };
+    (outcome, detail.to_string())
+}
+
+/// Evaluates checks in order, the first one rejecting or forcing the window decides.
+/// On explain mode the remaining checks are still evaluated to be shown on the report.
+pub struct FilterChain {
+    explain: bool,
+    verdict: WindowFilterVerdict,
+}
+
+impl FilterChain {
+    pub fn new(explain: bool) -> Self {
+        Self {
+            explain,
+            verdict: WindowFilterVerdict {
+                included: true,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn should_evaluate(&self) -> bool {
+        self.explain || self.verdict.decided_by.is_none()
+    }
+
+    pub fn check(mut self, filter: WindowFilterKind, check: impl FnOnce() -> Check) -> Self {
+        if !self.should_evaluate() {
+            return self;
+        }
+        let (outcome, detail) = check();
+        if self.verdict.decided_by.is_none() && outcome != WindowFilterOutcome::Passed {
+            self.verdict.decided_by = Some(filter);
+            self.verdict.included = outcome == WindowFilterOutcome::Forced;
+        }
+        if self.explain {
+            self.verdict.steps.push(WindowFilterStep {
+                filter,
+                outcome,
+                detail,
+            });
+        }
+        self
+    }
+
+    /// Appends a nested chain, its decision is taken if this chain was not decided yet
+    pub fn chain(mut self, nested: impl FnOnce(bool) -> WindowFilterVerdict) -> Self {
+        if !self.should_evaluate() {
+            return self;
+        }
+        let nested = nested(self.explain);
+        if self.verdict.decided_by.is_none() && nested.decided_by.is_some() {
+            self.verdict.decided_by = nested.decided_by;
+            self.verdict.included = nested.included;
+        }
+        self.verdict.steps.extend(nested.steps);
+        self
+    }
+
+    pub fn verdict(self) -> WindowFilterVerdict {
+        self.verdict
+    }
+}
+
+/// Heuristics used to decide which windows are interactable (shown on the dock, switcher, etc)
+/// and which ones can be tiled, configured by `windowFilters` on the settings.
+pub struct WindowFilter;
+
+impl WindowFilter {
+    fn size(window: &Window, settings: &WindowFiltersSettings) -> Check {
+        if settings.min_width == 0 && settings.min_height == 0 {
+            return reject_if(false, "disabled");
+        }
+        let (Ok(rect), Ok(scale)) = (
+            window.inner_rect(),
+            WindowsApi::get_device_pixel_ratio(window.monitor().raw()),
+        ) else {
+            return reject_if(false, "size can't be read");
+        };
+        let width = ((rect.right - rect.left) as f32 / scale) as u32;
+        let height = ((rect.bottom - rect.top) as f32 / scale) as u32;
+        reject_if(
+            width < settings.min_width || height < settings.min_height,
+            format!("{}x{}", width, height),
+        )
+    }
+
+    /// Checks of the windows that the user can interact with, like the ones shown on the dock
+    pub fn interactable(window: &Window, explain: bool) -> WindowFilterVerdict {
+        let state = FULL_STATE.load();
+        let settings = &state.settings.window_filters;
+        let path = window.process().program_path();
+
+        FilterChain::new(explain)
+            .check(WindowFilterKind::Program, || match &path {
+                Ok(path) => reject_if(false, path.display()),
+                Err(err) => reject_if(true, err),
+            })
+            .check(WindowFilterKind::SystemApp, || {
+                let is_system = path
+                    .as_ref()
+                    .is_ok_and(|path| path.starts_with("C:\\Windows\\SystemApps"));
+                reject_if(is_system, is_system)
+            })
+            .check(WindowFilterKind::Visible, || {
+                let visible = window.is_visible();
+                reject_if(!visible, visible)
+            })
+            .check(WindowFilterKind::Owner, || match window.parent() {
+                Some(parent) => reject_if(settings.by_owner, format!("{:?}", parent)),
+                None => reject_if(false, "none"),
+            })
+            .check(WindowFilterKind::SeelenOverlay, || {
+                let is_overlay = window.is_seelen_overlay();
+                reject_if(is_overlay, is_overlay)
+            })
+            .check(WindowFilterKind::Class, || {
+                let class = window.class();
+                reject_if(settings.class_blacklist.contains(&class), class)
+            })
+            .check(WindowFilterKind::Styles, || {
+                let ex_style = WindowsApi::get_ex_styles(window.hwnd());
+                let is_tool = (ex_style.contains(WS_EX_TOOLWINDOW)
+                    || ex_style.contains(WS_EX_NOACTIVATE))
+                    && !ex_style.contains(WS_EX_APPWINDOW);
+                reject_if(settings.by_styles && is_tool, format!("{:?}", ex_style))
+            })
+            .check(WindowFilterKind::GhostFrame, || {
+                let is_ghost = window.is_ghost_frame();
+                reject_if(is_ghost, is_ghost)
+            })
+            .check(WindowFilterKind::SuspendedUwp, || {
+                let suspended =
+                    WindowsApi::window_is_uwp_suspended(window.hwnd()).unwrap_or_default();
+                reject_if(suspended, suspended)
+            })
+            .check(WindowFilterKind::AppConfig, || {
+                let hidden = state
+                    .get_app_config_by_window(window.hwnd())
+                    .is_some_and(|config| config.options.contains(&AppExtraFlag::Hidden));
+                reject_if(hidden, format!("hidden: {}", hidden))
+            })
+            .check(WindowFilterKind::Title, || {
+                let title = window.title();
+                reject_if(TITLE_BLACKLIST.contains(&title.as_str()), title)
+            })
+            .check(WindowFilterKind::Size, || Self::size(window, settings))
+            .verdict()
+    }
+
+    /// Checks of the windows that can be tiled, the state and the per app configuration of
+    /// the window manager are checked by [`WindowManagerV2`].
+    pub fn tileable(window: &Window, explain: bool) -> WindowFilterVerdict {
+        let hwnd = window.hwnd();
+        let is_frame_host = window
+            .exe()
+            .is_ok_and(|exe| exe.ends_with("ApplicationFrameHost.exe"));
+
+        FilterChain::new(explain)
+            .check(WindowFilterKind::Ime, || {
+                let is_ime = window.is_ime();
+                reject_if(is_ime, is_ime)
+            })
+            .chain(|explain| Self::interactable(window, explain))
+            .check(WindowFilterKind::Caption, || {
+                // frames of UWP apps draw their own title bar
+                if is_frame_host {
+                    return reject_if(false, "ApplicationFrameHost");
+                }
+                let has_caption = WindowsApi::get_styles(hwnd).contains(WS_CAPTION);
+                let is_topmost = WindowsApi::get_ex_styles(hwnd).contains(WS_EX_TOPMOST);
+                reject_if(
+                    !has_caption || is_topmost,
+                    format!("caption: {}, topmost: {}", has_caption, is_topmost),
+                )
+            })
+            .check(WindowFilterKind::SuspendedUwp, || {
+                // suspended apps are not rendered so these would be tiled as empty ghosts
+                let suspended = is_frame_host && window.is_frame_suspended();
+                reject_if(suspended, format!("frame suspended: {}", suspended))
+            })
+            .check(WindowFilterKind::Minimized, || {
+                let minimized = WindowsApi::is_iconic(hwnd);
+                reject_if(minimized, minimized)
+            })
+            .check(WindowFilterKind::Cloaked, || {
+                let is_cloak_allowed = get_vd_manager().uses_cloak()
+                    && !WindowsApi::is_cloaked_by_app(hwnd).unwrap_or(false);
+                let cloaked = WindowsApi::is_cloaked(hwnd).unwrap_or(false);
+                reject_if(
+                    cloaked && !is_cloak_allowed,
+                    format!("cloaked: {}, allowed: {}", cloaked, is_cloak_allowed),
+                )
+            })
+            .verdict()
+    }
+
+    pub fn explain(window: &Window) -> WindowFilterReport {
+        WindowFilterReport {
+            hwnd: window.address(),
+            title: window.title(),
+            class: window.class(),
+            exe: window.exe().ok(),
+            weg: Self::interactable(window, true),
+            wm: WindowManagerV2::management_verdict(window, true),
+        }
+    }
+
+    /// Details of the window used to match it against the app configs,
+    /// optionally highlighting it to be sure it is the expected one
+    pub fn inspect(window: &Window, flash: bool) -> Result<WindowInspection> {
+        if flash {
+            FocusBorder::flash(window, FLASH_DURATION)?;
+        }
+
+        let state = FULL_STATE.load();
+        let matched_configs = state
+            .get_app_configs_matching_window(window.hwnd())
+            .into_iter()
+            .cloned()
+            .collect_vec();
+        let applied_flags = matched_configs
+            .first()
+            .map(|config| config.options.clone())
+            .unwrap_or_default();
+
+        Ok(WindowInspection {
+            hwnd: window.address(),
+            title: window.title(),
+            class: window.class(),
+            exe: window.exe().ok(),
+            aumid: window.app_user_model_id(),
+            matched_configs,
+            applied_flags,
+            weg: Self::interactable(window, true),
+            wm: WindowManagerV2::management_verdict(window, true),
+        })
+    }
+
+    pub fn inspect_at_cursor(flash: bool) -> Result<WindowInspection> {
+        Self::inspect(&Window::from(WindowsApi::window_at_cursor()?), flash)
+    }
+
+    /// Reports of the visible top level windows
+    pub fn explain_all() -> Result<Vec<WindowFilterReport>> {
+        Ok(WindowEnumerator::new()
+            .map(Window::from)?
+            .into_iter()
+            .filter(|window| window.is_visible())
+            .map(|window| Self::explain(&window))
+            .collect_vec())
+    }
+}
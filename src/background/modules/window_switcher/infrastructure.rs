@@ -0,0 +1,31 @@
+use seelen_core::system_state::WindowSwitcherState;
+
+use crate::error_handler::Result;
+
+use super::WindowSwitcher;
+
+#[tauri::command(async)]
+pub fn window_switcher_get_state() -> WindowSwitcherState {
+    WindowSwitcher::get_state()
+}
+
+#[tauri::command(async)]
+pub fn window_switcher_open(reverse: Option<bool>) -> Result<()> {
+    WindowSwitcher::open(reverse.unwrap_or(false))
+}
+
+#[tauri::command(async)]
+pub fn window_switcher_select(index: usize) -> Result<()> {
+    WindowSwitcher::select(index)
+}
+
+/// Focuses the given window or the selected one and closes the switcher
+#[tauri::command(async)]
+pub fn window_switcher_commit(hwnd: Option<isize>) -> Result<()> {
+    WindowSwitcher::commit(hwnd)
+}
+
+#[tauri::command(async)]
+pub fn window_switcher_cancel() -> Result<()> {
+    WindowSwitcher::cancel()
+}
@@ -0,0 +1,286 @@
+pub mod infrastructure;
+
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use image::ImageFormat;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{
+    handlers::SeelenEvent,
+    system_state::{SwitcherWindow, WindowSwitcherState},
+};
+use windows::Win32::{Foundation::HWND, UI::WindowsAndMessaging::SW_RESTORE};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::{
+        event_registry::EventRegistry, focus_history::FocusHistory, icon_cache::IconCache,
+        window_tags::WindowTags,
+    },
+    seelen_weg::SeelenWeg,
+    state::application::FULL_STATE,
+    trace_lock,
+    utils::{
+        event_queue::{EventQueue, QueueSender},
+        spawn_named_thread,
+    },
+    windows_api::{window::Window, WindowEnumerator, WindowsApi},
+    winevent::WinEvent,
+};
+
+lazy_static! {
+    static ref STATE: Mutex<WindowSwitcherState> = Mutex::new(WindowSwitcherState::default());
+    /// inputs from the keyboard hook, processed in order by a single worker
+    static ref INPUT_QUEUE: Result<EventQueue<SwitcherInput>> =
+        EventQueue::new("Window Switcher", |_| None, |input| {
+            log_error!(WindowSwitcher::process_input(input));
+        });
+}
+
+/// incremented each time the switcher is opened, used to discard thumbnails of old snapshots
+static SNAPSHOT_ID: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Copy)]
+pub enum SwitcherInput {
+    Open { reverse: bool },
+    Step(isize),
+    Commit,
+    Cancel,
+}
+
+/// Backend of the Alt + Tab replacement
+pub struct WindowSwitcher;
+
+impl WindowSwitcher {
+    fn emit(state: &WindowSwitcherState) -> Result<()> {
+        EventRegistry::emit(SeelenEvent::WindowSwitcherChanged, state)?;
+        Ok(())
+    }
+
+    pub fn get_state() -> WindowSwitcherState {
+        trace_lock!(STATE).clone()
+    }
+
+    pub fn send(input: SwitcherInput) {
+        INPUT_QUEUE.send(input);
+    }
+
+    fn process_input(input: SwitcherInput) -> Result<()> {
+        match input {
+            SwitcherInput::Open { reverse } => Self::open(reverse),
+            SwitcherInput::Step(delta) => Self::step(delta),
+            SwitcherInput::Commit => Self::commit(None),
+            SwitcherInput::Cancel => Self::cancel(),
+        }
+    }
+
+    fn is_listed(window: &Window) -> bool {
+        SeelenWeg::should_be_added(window.hwnd())
+    }
+
+    /// Listed windows, the ones never focused since Seelen started are added at the end on z-order
+    fn windows_by_mru() -> Result<Vec<Window>> {
+        let mut windows = FocusHistory::windows();
+        WindowEnumerator::new().for_each(|hwnd| {
+            let window = Window::from(hwnd);
+            if !windows.contains(&window) && Self::is_listed(&window) {
+                windows.push(window);
+            }
+        })?;
+        Ok(windows)
+    }
+
+    fn icon_of(window: &Window) -> Option<PathBuf> {
+        match window.app_user_model_id().filter(|umid| umid.contains('!')) {
+            Some(umid) => IconCache::of_umid(&umid).ok(),
+            None => IconCache::of_file(window.exe().ok()?).ok(),
+        }
+    }
+
+    fn snapshot_of(window: &Window) -> SwitcherWindow {
+        SwitcherWindow {
+            hwnd: window.address(),
+            title: window.display_title(),
+            app_name: window.app_display_name().unwrap_or_default(),
+            exe: window.exe().ok(),
+            icon_path: Self::icon_of(window),
+            thumbnail_path: None,
+            is_minimized: window.is_minimized(),
+            tags: WindowTags::tags_of(window),
+        }
+    }
+
+    fn capture_thumbnail(hwnd: HWND) -> Result<Option<PathBuf>> {
+        let Some(image) = SeelenWeg::capture_window(hwnd) else {
+            return Ok(None);
+        };
+        let rect = WindowsApi::get_inner_window_rect(hwnd)?;
+        let shadow = WindowsApi::shadow_rect(hwnd)?;
+        let image = image.crop_imm(
+            shadow.left.unsigned_abs(),
+            shadow.top.unsigned_abs(),
+            (rect.right - rect.left) as u32,
+            (rect.bottom - rect.top) as u32,
+        );
+        let path = std::env::temp_dir().join(format!("switcher-{}.png", hwnd.0 as isize));
+        image.save_with_format(&path, ImageFormat::Png)?;
+        Ok(Some(path))
+    }
+
+    /// Thumbnails are slow to capture so they are added to the snapshot once ready
+    fn capture_thumbnails(snapshot_id: u64, handles: Vec<isize>) -> Result<()> {
+        spawn_named_thread("Window Switcher Thumbnails", move || {
+            for addr in handles {
+                if SNAPSHOT_ID.load(Ordering::SeqCst) != snapshot_id {
+                    return;
+                }
+                let path = match Self::capture_thumbnail(HWND(addr as _)) {
+                    Ok(Some(path)) => path,
+                    Ok(None) => continue,
+                    Err(err) => {
+                        log::error!("Failed to capture window thumbnail: {:?}", err);
+                        continue;
+                    }
+                };
+                let mut state = trace_lock!(STATE);
+                if !state.visible || SNAPSHOT_ID.load(Ordering::SeqCst) != snapshot_id {
+                    return;
+                }
+                if let Some(w) = state.windows.iter_mut().find(|w| w.hwnd == addr) {
+                    w.thumbnail_path = Some(path);
+                    log_error!(Self::emit(&state));
+                }
+            }
+        })?;
+        Ok(())
+    }
+
+    /// Shows the switcher with the next window selected, or moves the selection if already open
+    pub fn open(reverse: bool) -> Result<()> {
+        let delta = if reverse { -1 } else { 1 };
+        let mut state = trace_lock!(STATE);
+        if state.visible {
+            drop(state);
+            return Self::step(delta);
+        }
+
+        let tag = WindowTags::filter();
+        let windows: Vec<SwitcherWindow> = Self::windows_by_mru()?
+            .iter()
+            .map(Self::snapshot_of)
+            .filter(|w| tag.as_ref().map_or(true, |tag| w.tags.contains(tag)))
+            .collect();
+        // the first window is the current one, so the next one is selected
+        let selected = match (windows.len(), reverse) {
+            (0, _) => 0,
+            (len, true) => len - 1,
+            (len, false) => 1.min(len - 1),
+        };
+
+        *state = WindowSwitcherState {
+            visible: true,
+            windows,
+            selected,
+            tag,
+        };
+        Self::emit(&state)?;
+
+        let snapshot_id = SNAPSHOT_ID.fetch_add(1, Ordering::SeqCst) + 1;
+        if FULL_STATE.load().settings.window_switcher.thumbnails {
+            let handles = state
+                .windows
+                .iter()
+                .filter(|w| !w.is_minimized)
+                .map(|w| w.hwnd)
+                .collect();
+            Self::capture_thumbnails(snapshot_id, handles)?;
+        }
+        Ok(())
+    }
+
+    /// Moves the selection, wrapping around the ends. Opens the switcher if it is closed.
+    pub fn step(delta: isize) -> Result<()> {
+        let mut state = trace_lock!(STATE);
+        if !state.visible {
+            drop(state);
+            return Self::open(delta < 0);
+        }
+        let len = state.windows.len() as isize;
+        if len == 0 {
+            return Ok(());
+        }
+        state.selected = (state.selected as isize + delta).rem_euclid(len) as usize;
+        Self::emit(&state)
+    }
+
+    pub fn select(index: usize) -> Result<()> {
+        let mut state = trace_lock!(STATE);
+        if !state.visible || index >= state.windows.len() {
+            return Ok(());
+        }
+        state.selected = index;
+        Self::emit(&state)
+    }
+
+    fn close() -> Result<WindowSwitcherState> {
+        SNAPSHOT_ID.fetch_add(1, Ordering::SeqCst);
+        let mut state = trace_lock!(STATE);
+        let last = std::mem::take(&mut *state);
+        Self::emit(&state)?;
+        Ok(last)
+    }
+
+    /// Closes the switcher focusing the given window or the selected one
+    pub fn commit(hwnd: Option<isize>) -> Result<()> {
+        let last = Self::close()?;
+        let target = hwnd.or_else(|| last.windows.get(last.selected).map(|w| w.hwnd));
+        let Some(target) = target else {
+            return Ok(());
+        };
+
+        let hwnd = HWND(target as _);
+        if !WindowsApi::is_window(hwnd) {
+            return Ok(());
+        }
+        if WindowsApi::is_iconic(hwnd) {
+            WindowsApi::show_window_async(hwnd, SW_RESTORE)?;
+        }
+        if WindowsApi::set_foreground(hwnd).is_err() {
+            WindowsApi::async_force_set_foreground(hwnd);
+        }
+        Ok(())
+    }
+
+    /// Closes the switcher without changing the focus
+    pub fn cancel() -> Result<()> {
+        Self::close()?;
+        Ok(())
+    }
+
+    pub fn wants_win_event(event: &WinEvent) -> bool {
+        matches!(event, WinEvent::ObjectDestroy)
+    }
+
+    /// The history of the windows is kept by `FocusHistory`, only the open switcher is updated here
+    pub fn process_win_event(event: WinEvent, window: &Window) -> Result<()> {
+        if !matches!(event, WinEvent::ObjectDestroy) {
+            return Ok(());
+        }
+        let address = window.address();
+        let mut state = trace_lock!(STATE);
+        if let Some(idx) = state.windows.iter().position(|w| w.hwnd == address) {
+            state.windows.remove(idx);
+            if state.selected > idx
+                || (state.selected == idx && idx == state.windows.len() && idx > 0)
+            {
+                state.selected -= 1;
+            }
+            Self::emit(&state)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use clap::Command;
+
+use crate::{error_handler::Result, get_subcommands};
+
+use super::WindowTags;
+
+get_subcommands![
+    /** Adds the tag to the app of the focused window. */
+    Add(tag: String => "Name of the tag, the leading # is optional."),
+    /** Removes the tag from the app of the focused window. */
+    Remove(tag: String => "Name of the tag, the leading # is optional."),
+    /** Adds the tag to the app of the focused window, or removes it if it already has it. */
+    Toggle(tag: String => "Name of the tag, the leading # is optional."),
+    /** Removes all the tags of the app of the focused window. */
+    Clear,
+    /** Minimizes all the windows with the tag. */
+    Minimize(tag: String => "Name of the tag, the leading # is optional."),
+    /** Restores all the minimized windows with the tag. */
+    Restore(tag: String => "Name of the tag, the leading # is optional."),
+    /** Sends all the windows with the tag to the workspace. */
+    SendToWorkspace(tag: String => "Name of the tag, the leading # is optional.", index: usize => "The index of the workspace."),
+    /** Only shows the windows with the tag on the switcher and the dock. */
+    Filter(tag: String => "Name of the tag, the leading # is optional."),
+    /** Shows all the windows on the switcher and the dock again. */
+    ClearFilter,
+];
+
+impl WindowTags {
+    pub const CLI_IDENTIFIER: &'static str = "tag";
+
+    pub fn get_cli() -> Command {
+        Command::new(Self::CLI_IDENTIFIER)
+            .about("Tags the windows by app and acts on all the windows of a tag.")
+            .arg_required_else_help(true)
+            .subcommands(SubCommand::commands())
+            .mut_subcommand("send-to-workspace", |c| c.visible_alias("send"))
+    }
+
+    pub fn process(matches: &clap::ArgMatches) -> Result<()> {
+        let subcommand = SubCommand::try_from(matches)?;
+        match subcommand {
+            SubCommand::Add(tag) => Self::add_to_focused(&tag)?,
+            SubCommand::Remove(tag) => Self::remove_from_focused(&tag)?,
+            SubCommand::Toggle(tag) => Self::toggle_on_focused(&tag)?,
+            SubCommand::Clear => Self::clear_focused()?,
+            SubCommand::Minimize(tag) => Self::minimize(&tag)?,
+            SubCommand::Restore(tag) => Self::restore(&tag)?,
+            SubCommand::SendToWorkspace(tag, index) => Self::send_to_workspace(&tag, index)?,
+            SubCommand::Filter(tag) => Self::set_filter(Some(tag))?,
+            SubCommand::ClearFilter => Self::set_filter(None)?,
+        };
+        Ok(())
+    }
+}
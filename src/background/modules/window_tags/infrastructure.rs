@@ -0,0 +1,22 @@
+use seelen_core::system_state::WindowTagsState;
+
+use crate::{error_handler::Result, windows_api::window::Window};
+
+use super::WindowTags;
+
+#[tauri::command(async)]
+pub fn window_tags_get_state() -> WindowTagsState {
+    WindowTags::get_state()
+}
+
+/// Replaces the tags of the app of the window
+#[tauri::command(async)]
+pub fn window_tags_set(hwnd: isize, tags: Vec<String>) -> Result<()> {
+    WindowTags::set(&Window::from(hwnd), tags)
+}
+
+/// Only the windows with the tag are shown on the switcher and the dock, `None` to show all
+#[tauri::command(async)]
+pub fn window_tags_set_filter(tag: Option<String>) -> Result<()> {
+    WindowTags::set_filter(tag)
+}
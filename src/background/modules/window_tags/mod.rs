@@ -0,0 +1,210 @@
+mod cli;
+pub mod infrastructure;
+
+use std::path::PathBuf;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{handlers::SeelenEvent, system_state::WindowTagsState};
+use tauri::Manager;
+use windows::Win32::UI::WindowsAndMessaging::{SW_MINIMIZE, SW_RESTORE};
+
+use crate::{
+    error_handler::Result,
+    modules::{event_registry::EventRegistry, virtual_desk::get_vd_manager},
+    seelen::get_app_handle,
+    seelen_weg::SeelenWeg,
+    trace_lock,
+    windows_api::{window::Window, WindowEnumerator, WindowsApi},
+};
+
+lazy_static! {
+    static ref STATE: Mutex<WindowTagsState> = Mutex::new(WindowTags::load());
+}
+
+/// `#Docs ` -> `docs`, tags are compared ignoring the case
+fn normalize(tag: &str) -> Option<String> {
+    let tag = tag.trim().trim_start_matches('#').trim().to_lowercase();
+    (!tag.is_empty() && !tag.contains(char::is_whitespace)).then_some(tag)
+}
+
+/// Tags given by the user to the windows, stored by app so they are kept when the app is
+/// reopened. Used to filter the switcher and the dock, and to act on many windows at once.
+pub struct WindowTags;
+
+impl WindowTags {
+    fn path() -> Result<PathBuf> {
+        Ok(get_app_handle()
+            .path()
+            .app_data_dir()?
+            .join("window_tags.json"))
+    }
+
+    fn load() -> WindowTagsState {
+        let apps = Self::path()
+            .ok()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        WindowTagsState { apps, filter: None }
+    }
+
+    fn save(state: &WindowTagsState) -> Result<()> {
+        std::fs::write(Self::path()?, serde_json::to_string(&state.apps)?)?;
+        Ok(())
+    }
+
+    fn emit(state: &WindowTagsState) -> Result<()> {
+        EventRegistry::emit(SeelenEvent::WindowTagsChanged, state)?;
+        Ok(())
+    }
+
+    /// Same identity used by the position memory and the app usage
+    pub fn identity_of(window: &Window) -> Option<String> {
+        match window.app_user_model_id() {
+            Some(umid) => Some(umid),
+            None => Some(window.exe().ok()?.to_string_lossy().to_lowercase()),
+        }
+    }
+
+    pub fn get_state() -> WindowTagsState {
+        trace_lock!(STATE).clone()
+    }
+
+    pub fn tags_of(window: &Window) -> Vec<String> {
+        Self::identity_of(window)
+            .and_then(|app| trace_lock!(STATE).apps.get(&app).cloned())
+            .unwrap_or_default()
+    }
+
+    pub fn has_tag(window: &Window, tag: &str) -> bool {
+        Self::tags_of(window).iter().any(|t| t == tag)
+    }
+
+    /// Tag used to filter the switcher and the dock
+    pub fn filter() -> Option<String> {
+        trace_lock!(STATE).filter.clone()
+    }
+
+    /// Windows shown on the dock that have the tag
+    pub fn windows_with(tag: &str) -> Result<Vec<Window>> {
+        let tag = normalize(tag).ok_or("Invalid tag")?;
+        let mut windows = Vec::new();
+        WindowEnumerator::new().for_each(|hwnd| {
+            let window = Window::from(hwnd);
+            if SeelenWeg::should_be_added(hwnd) && Self::has_tag(&window, &tag) {
+                windows.push(window);
+            }
+        })?;
+        Ok(windows)
+    }
+
+    /// Replaces the tags of the app of the window
+    pub fn set(window: &Window, tags: Vec<String>) -> Result<()> {
+        let app = Self::identity_of(window).ok_or("The window has no app identity")?;
+        let mut tags: Vec<String> = tags.iter().filter_map(|t| normalize(t)).collect();
+        tags.sort();
+        tags.dedup();
+
+        let mut state = trace_lock!(STATE);
+        if tags.is_empty() {
+            state.apps.remove(&app);
+        } else {
+            state.apps.insert(app, tags);
+        }
+        Self::save(&state)?;
+        Self::emit(&state)?;
+        drop(state);
+        SeelenWeg::update_all_apps();
+        Ok(())
+    }
+
+    fn change<F: FnOnce(&mut Vec<String>)>(window: &Window, f: F) -> Result<()> {
+        let mut tags = Self::tags_of(window);
+        f(&mut tags);
+        Self::set(window, tags)
+    }
+
+    pub fn set_filter(tag: Option<String>) -> Result<()> {
+        let tag = match tag {
+            Some(tag) => Some(normalize(&tag).ok_or("Invalid tag")?),
+            None => None,
+        };
+        let mut state = trace_lock!(STATE);
+        if state.filter != tag {
+            state.filter = tag;
+            Self::emit(&state)?;
+        }
+        Ok(())
+    }
+
+    fn foreground() -> Window {
+        Window::from(WindowsApi::get_foreground_window())
+    }
+
+    pub fn add_to_focused(tag: &str) -> Result<()> {
+        let tag = normalize(tag).ok_or("Invalid tag")?;
+        Self::change(&Self::foreground(), |tags| tags.push(tag))
+    }
+
+    pub fn remove_from_focused(tag: &str) -> Result<()> {
+        let tag = normalize(tag).ok_or("Invalid tag")?;
+        Self::change(&Self::foreground(), |tags| tags.retain(|t| t != &tag))
+    }
+
+    pub fn toggle_on_focused(tag: &str) -> Result<()> {
+        let tag = normalize(tag).ok_or("Invalid tag")?;
+        Self::change(&Self::foreground(), |tags| {
+            match tags.iter().position(|t| t == &tag) {
+                Some(idx) => {
+                    tags.remove(idx);
+                }
+                None => tags.push(tag),
+            }
+        })
+    }
+
+    pub fn clear_focused() -> Result<()> {
+        Self::set(&Self::foreground(), Vec::new())
+    }
+
+    pub fn minimize(tag: &str) -> Result<()> {
+        for window in Self::windows_with(tag)? {
+            if !window.is_minimized() {
+                WindowsApi::show_window_async(window.hwnd(), SW_MINIMIZE)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn restore(tag: &str) -> Result<()> {
+        for window in Self::windows_with(tag)? {
+            if window.is_minimized() {
+                WindowsApi::show_window_async(window.hwnd(), SW_RESTORE)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn send_to_workspace(tag: &str, index: usize) -> Result<()> {
+        let vd = get_vd_manager();
+        for window in Self::windows_with(tag)? {
+            vd.send_to(index, window.address())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("#Docs").as_deref(), Some("docs"));
+        assert_eq!(normalize("  projA ").as_deref(), Some("proja"));
+        assert_eq!(normalize("# work").as_deref(), Some("work"));
+        assert_eq!(normalize("#"), None);
+        assert_eq!(normalize("two words"), None);
+    }
+}
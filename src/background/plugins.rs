@@ -1,60 +1,74 @@
 use tauri::{Builder, Wry};
 use tauri_plugin_autostart::MacosLauncher;
-use tauri_plugin_log::{Target, TargetKind};
+use tauri_plugin_log::{fern, Target, TargetKind};
+
+use crate::utils::logger::SeelenLogger;
+
+fn stdout_dispatch() -> fern::Dispatch {
+    let dispatch = fern::Dispatch::new();
+
+    #[cfg(not(dev))]
+    let dispatch = dispatch.format(move |out, message, record| {
+        out.finish(format_args!(
+            "[{}][{}] {}",
+            record.level(),
+            record.target(),
+            message
+        ))
+    });
+
+    #[cfg(dev)]
+    let dispatch = {
+        use owo_colors::OwoColorize;
+        dispatch.format(move |out, message, record| {
+            out.finish(format_args!(
+                "[{}][{}] {}",
+                match record.level() {
+                    log::Level::Trace => "TRACE".bright_black().to_string(),
+                    log::Level::Info => "INFO~".bright_blue().to_string(),
+                    log::Level::Warn => "WARN~".yellow().to_string(),
+                    log::Level::Error => "ERROR".red().to_string(),
+                    log::Level::Debug => "DEBUG".bright_green().to_string(),
+                },
+                if record.level() == log::Level::Error {
+                    record
+                        .file()
+                        .map(|file| {
+                            format!(
+                                "{}:{}",
+                                file.replace("\\", "/"),
+                                record.line().unwrap_or_default()
+                            )
+                        })
+                        .unwrap_or_else(|| record.target().to_owned())
+                        .bright_red()
+                        .to_string()
+                } else {
+                    record.target().bright_black().to_string()
+                },
+                message
+            ))
+        })
+    };
+
+    dispatch.chain(std::io::stdout())
+}
 
 pub fn register_plugins(app_builder: Builder<Wry>) -> Builder<Wry> {
-    let log_plugin_builder = tauri_plugin_log::Builder::new()
+    let log_plugin = tauri_plugin_log::Builder::new()
+        // each target formats the message by itself
+        .format(|out, message, _record| out.finish(format_args!("{}", message)))
         .targets([
-            Target::new(TargetKind::Stdout),
-            Target::new(TargetKind::LogDir { file_name: None }),
+            Target::new(TargetKind::Dispatch(stdout_dispatch())),
             Target::new(TargetKind::Webview),
+            Target::new(TargetKind::Dispatch(SeelenLogger::json_dispatch())),
         ])
+        .filter(SeelenLogger::is_enabled)
         .level_for("tao", log::LevelFilter::Off)
         .level_for("os_info", log::LevelFilter::Off)
         .level_for("notify", log::LevelFilter::Off)
-        .level_for("notify_debouncer_full", log::LevelFilter::Off);
-
-    let log_plugin = {
-        #[cfg(not(dev))]
-        {
-            log_plugin_builder.build()
-        }
-        #[cfg(dev)]
-        {
-            use owo_colors::OwoColorize;
-            log_plugin_builder
-                .format(move |out, message, record| {
-                    out.finish(format_args!(
-                        "[{}][{}] {}",
-                        match record.level() {
-                            log::Level::Trace => "TRACE".bright_black().to_string(),
-                            log::Level::Info => "INFO~".bright_blue().to_string(),
-                            log::Level::Warn => "WARN~".yellow().to_string(),
-                            log::Level::Error => "ERROR".red().to_string(),
-                            log::Level::Debug => "DEBUG".bright_green().to_string(),
-                        },
-                        if record.level() == log::Level::Error {
-                            record
-                                .file()
-                                .map(|file| {
-                                    format!(
-                                        "{}:{}",
-                                        file.replace("\\", "/"),
-                                        record.line().unwrap_or_default()
-                                    )
-                                })
-                                .unwrap_or_else(|| record.target().to_owned())
-                                .bright_red()
-                                .to_string()
-                        } else {
-                            record.target().bright_black().to_string()
-                        },
-                        message
-                    ))
-                })
-                .build()
-        }
-    };
+        .level_for("notify_debouncer_full", log::LevelFilter::Off)
+        .build();
 
     app_builder
         .plugin(tauri_plugin_fs::init())
@@ -1,31 +1,80 @@
-use std::{
-    env::temp_dir,
-    sync::{atomic::AtomicBool, Arc, OnceLock},
-};
+use std::sync::{atomic::AtomicBool, Arc, OnceLock};
 
 use getset::{Getters, MutGetters};
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
-use seelen_core::handlers::SeelenEvent;
-use tauri::{path::BaseDirectory, AppHandle, Emitter, Manager, Wry};
+use seelen_core::{handlers::SeelenEvent, state::TitlebarMode, system_state::MonitorScale};
+use tauri::{path::BaseDirectory, AppHandle, Manager, Wry};
 use tauri_plugin_shell::ShellExt;
 use windows::Win32::Graphics::Gdi::HMONITOR;
 
 use crate::{
     error_handler::Result,
-    hook::register_win_hook,
+    hook::{register_win_hook, HookManager, WinEventConsumer, WinEventFilter, WinEventPredicate},
     instance::SeelenInstanceContainer,
     log_error,
-    modules::monitors::{MonitorManagerEvent, MONITOR_MANAGER},
+    modules::{
+        always_on_top::AlwaysOnTop,
+        app_usage::AppUsageTracker,
+        audio_policy::AudioPolicy,
+        break_reminder::BreakReminder,
+        clock::Clock,
+        crash_guard::CrashGuard,
+        daily_wallpaper::DailyWallpapers,
+        diagnostics::Diagnostics,
+        emit_scheduler::EmitScheduler,
+        event_registry::EventRegistry,
+        explorer::ExplorerWatcher,
+        focus_history::FocusHistory,
+        hardware_keys::HardwareKeys,
+        idle_actions::IdleActions,
+        ime::Ime,
+        ipc::{application::IpcServer, remote::RemoteControl, status_feed::StatusFeed},
+        keybindings::application::KeybindingsManager,
+        layout_direction::LayoutDirection,
+        minimize_targets::MinimizeTargets,
+        monitors::{MonitorManagerEvent, MONITOR_MANAGER},
+        night_light::NightLight,
+        notes::StickyNotes,
+        perf::PerfMonitor,
+        picture_in_picture::PictureInPicture,
+        power::profile::PowerProfile,
+        printers::Printers,
+        processes::ProcessWatcher,
+        recycle_bin::RecycleBin,
+        removable_drives::RemovableDrives,
+        resource_governor::ResourceGovernor,
+        scripting::ScriptingEngine,
+        session::SessionManager,
+        start_menu::StartMenuManager,
+        startup::StartupTask,
+        storage::Storage,
+        supervisor::WebviewSupervisor,
+        taskbar::NativeTaskbar,
+        titlebar::Titlebar,
+        triggers::TriggerRunner,
+        updater::SeelenUpdater,
+        urgency::Urgency,
+        version_upgrade::VersionUpgrades,
+        virtual_desk::{get_vd_manager, WorkspaceMonitorRules},
+        window_effects::WindowEffects,
+        window_switcher::WindowSwitcher,
+    },
+    seelen_bar::FancyToolbar,
     seelen_rofi::SeelenRofi,
     seelen_wall::SeelenWall,
     seelen_weg::SeelenWeg,
-    seelen_wm_v2::instance::WindowManagerV2,
+    seelen_wm_v2::{
+        border::FocusBorder, corners::WmCorners, instance::WindowManagerV2, snap::WmSnap,
+    },
     state::application::{FullState, FULL_STATE},
     system::{declare_system_events_handlers, release_system_events_handlers},
     trace_lock,
-    utils::{ahk::AutoHotKey, PERFORMANCE_HELPER},
-    windows_api::WindowsApi,
+    tray::refresh_tray_menu,
+    utils::{
+        ahk::AutoHotKey, lock_stats::start_lock_watchdog, logger::SeelenLogger, PERFORMANCE_HELPER,
+    },
+    windows_api::{monitor::Monitor, WindowsApi},
 };
 
 lazy_static! {
@@ -34,6 +83,8 @@ lazy_static! {
 
 static APP_HANDLE: OnceLock<AppHandle<Wry>> = OnceLock::new();
 static SEELEN_IS_RUNNING: AtomicBool = AtomicBool::new(false);
+static SAFE_MODE: AtomicBool = AtomicBool::new(false);
+static HEADLESS: AtomicBool = AtomicBool::new(false);
 
 pub fn get_app_handle<'a>() -> &'a AppHandle<Wry> {
     APP_HANDLE
@@ -58,6 +109,25 @@ impl Seelen {
         SEELEN_IS_RUNNING.load(std::sync::atomic::Ordering::Relaxed)
     }
 
+    /// In safe mode only the settings management and the settings window are available
+    pub fn is_safe_mode() -> bool {
+        SAFE_MODE.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    pub fn set_safe_mode(value: bool) {
+        SAFE_MODE.store(value, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// In headless mode no webviews are created, the window manager tiles the windows natively
+    /// and everything is controlled via the cli and the automation server.
+    pub fn is_headless() -> bool {
+        HEADLESS.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    pub fn set_headless(value: bool) {
+        HEADLESS.store(value, std::sync::atomic::Ordering::Relaxed);
+    }
+
     pub fn focused_monitor(&self) -> Option<&SeelenInstanceContainer> {
         self.monitors.iter().find(|m| m.is_focused())
     }
@@ -99,7 +169,16 @@ impl Seelen {
         Ok(())
     }
 
-    fn refresh_windows_positions(&mut self) -> Result<()> {
+    /// Puts back a wall recreated by the supervisor, discarded if it was disabled meanwhile
+    pub fn replace_wall(&mut self, wall: SeelenWall) -> Result<()> {
+        if self.wall.is_none() && self.state().is_wall_enabled() {
+            wall.update_position()?;
+            self.wall = Some(wall);
+        }
+        Ok(())
+    }
+
+    pub fn refresh_windows_positions(&mut self) -> Result<()> {
         if let Some(wall) = &self.wall {
             wall.update_position()?;
         }
@@ -111,20 +190,148 @@ impl Seelen {
         Ok(())
     }
 
+    /// The toolbar, the dock or the native taskbar reserved or released space on a monitor,
+    /// only the window manager follows the work area so this can't be triggered again by it.
+    fn on_work_area_changed(&mut self) -> Result<()> {
+        for monitor in &self.monitors {
+            if let Some(wm) = monitor.wm() {
+                wm.set_position(*monitor.handle())?;
+            }
+        }
+        if self.state().is_window_manager_enabled() {
+            WindowManagerV2::force_retiling()?;
+        }
+        Ok(())
+    }
+
+    /// The surfaces of the monitor are placed again and notified of the new scale,
+    /// the window manager retiles only that monitor.
+    fn on_scale_changed(&mut self, name: &str, hmonitor: HMONITOR) -> Result<()> {
+        let scale = MonitorScale {
+            id: name.to_string(),
+            scale_factor: WindowsApi::get_device_pixel_ratio(hmonitor)?,
+            work_area: Monitor::from(hmonitor).work_area()?.into(),
+        };
+        log::info!("Monitor {} scale changed to {}", name, scale.scale_factor);
+        if let Some(monitor) = self.monitor_by_name_mut(name) {
+            monitor.update_handle(hmonitor);
+            monitor.ensure_positions()?;
+        }
+        EventRegistry::emit(SeelenEvent::MonitorScaleChanged, &scale)?;
+        if self.state().is_window_manager_enabled() {
+            WindowManagerV2::force_retiling_on(name)?;
+        }
+        Ok(())
+    }
+
+    fn refresh_win_event_subscriptions(state: &FullState) {
+        fn filter_if(enabled: bool, predicate: WinEventPredicate) -> Option<WinEventFilter> {
+            enabled.then(|| WinEventFilter::events(predicate))
+        }
+
+        HookManager::set_subscription(
+            WinEventConsumer::VirtualDesk,
+            get_vd_manager().win_event_filter(),
+        );
+        HookManager::set_subscription(
+            WinEventConsumer::WegGlobal,
+            filter_if(state.is_weg_enabled(), SeelenWeg::wants_global_win_event),
+        );
+        HookManager::set_subscription(
+            WinEventConsumer::WegInstance,
+            filter_if(
+                state.is_weg_enabled(),
+                SeelenWeg::wants_individual_win_event,
+            ),
+        );
+        HookManager::set_subscription(
+            WinEventConsumer::Toolbar,
+            filter_if(state.is_bar_enabled(), FancyToolbar::wants_win_event),
+        );
+        HookManager::set_subscription(
+            WinEventConsumer::WindowManager,
+            filter_if(
+                state.is_window_manager_enabled(),
+                WindowManagerV2::wants_win_event,
+            ),
+        );
+        HookManager::set_subscription(
+            WinEventConsumer::Wall,
+            filter_if(state.is_wall_enabled(), SeelenWall::wants_win_event),
+        );
+        HookManager::set_subscription(
+            WinEventConsumer::WindowEffects,
+            Some(WinEventFilter::events(WindowEffects::wants_win_event)),
+        );
+        HookManager::set_subscription(
+            WinEventConsumer::Titlebar,
+            filter_if(
+                state.settings.titlebar.mode != TitlebarMode::Untouched
+                    || state.settings.titlebar.accent_caption,
+                Titlebar::wants_win_event,
+            ),
+        );
+        HookManager::set_subscription(
+            WinEventConsumer::AlwaysOnTop,
+            Some(WinEventFilter::events(AlwaysOnTop::wants_win_event)),
+        );
+        HookManager::set_subscription(
+            WinEventConsumer::WindowSwitcher,
+            Some(WinEventFilter::events(WindowSwitcher::wants_win_event)),
+        );
+        HookManager::set_subscription(
+            WinEventConsumer::FocusHistory,
+            Some(WinEventFilter::events(FocusHistory::wants_win_event)),
+        );
+        HookManager::set_subscription(WinEventConsumer::Urgency, Urgency::win_event_filter());
+        HookManager::set_subscription(
+            WinEventConsumer::PictureInPicture,
+            filter_if(
+                state.settings.picture_in_picture.enabled,
+                PictureInPicture::wants_win_event,
+            ),
+        );
+        HookManager::set_subscription(
+            WinEventConsumer::AudioPolicy,
+            Some(WinEventFilter::events(AudioPolicy::wants_win_event)),
+        );
+        HookManager::set_subscription(
+            WinEventConsumer::Ime,
+            filter_if(state.is_bar_enabled(), Ime::wants_win_event),
+        );
+    }
+
     pub fn on_settings_change(&mut self) -> Result<()> {
+        if Self::is_safe_mode() {
+            return Ok(());
+        }
+
         let state = self.state();
+        SeelenLogger::set_config(&state.settings.logging);
+        Diagnostics::set_config(&state.settings.diagnostics);
+        EmitScheduler::set_max_rate(state.settings.max_emit_rate);
+        log_error!(PowerProfile::refresh());
+        log_error!(LayoutDirection::refresh());
+        Clock::refresh();
+        DailyWallpapers::wake_up();
+        log_error!(RemoteControl::apply(&state.settings.remote_control));
+        Self::refresh_win_event_subscriptions(&state);
 
         match state.is_ahk_enabled() {
             true => Self::start_ahk_shortcuts()?,
             false => Self::kill_ahk_shortcuts()?,
         }
 
-        if state.is_weg_enabled() {
-            SeelenWeg::hide_taskbar();
-        } else {
-            SeelenWeg::restore_taskbar()?;
-        }
+        KeybindingsManager::load(&state.settings.keybindings)?;
+        KeybindingsManager::set_window_switcher_grab(state.settings.window_switcher.enabled)?;
+        log_error!(HardwareKeys::refresh());
+        TriggerRunner::reset();
 
+        NativeTaskbar::apply();
+        log_error!(Titlebar::refresh_all());
+
+        FocusBorder::refresh()?;
+        log_error!(WmSnap::refresh());
         match state.is_window_manager_enabled() {
             true => {
                 WindowManagerV2::init_state()?;
@@ -148,6 +355,7 @@ impl Seelen {
         }
 
         self.refresh_windows_positions()?;
+        log_error!(refresh_tray_menu());
         Ok(())
     }
 
@@ -158,24 +366,54 @@ impl Seelen {
             .set(handle.to_owned())
             .map_err(|_| "Failed to set app handle")?;
         Self::ensure_folders(handle)?;
+        SeelenLogger::init_file(handle.path().app_data_dir()?.join("logs"))?;
+        Diagnostics::init(handle.path().app_data_dir()?.join("diagnostics"))?;
+        log_error!(CrashGuard::on_launch(&handle.path().app_data_dir()?));
+        if CrashGuard::is_recovering() {
+            Self::set_safe_mode(true);
+        }
+        log_error!(VersionUpgrades::detect(&handle.path().app_data_dir()?));
         Ok(())
     }
 
+    fn sync_wm_monitors() {
+        if FULL_STATE.load().is_window_manager_enabled() {
+            log_error!(WindowManagerV2::sync_monitors());
+        }
+    }
+
     fn on_monitor_event(event: MonitorManagerEvent) {
         match event {
-            MonitorManagerEvent::Added(_name, id) => {
+            MonitorManagerEvent::Added(name, id) => {
                 log_error!(trace_lock!(SEELEN).add_monitor(id));
+                log_error!(WorkspaceMonitorRules::on_monitor_connected(&name));
+                Self::sync_wm_monitors();
             }
             MonitorManagerEvent::Removed(_name, id) => {
                 log_error!(trace_lock!(SEELEN).remove_monitor(id));
+                Self::sync_wm_monitors();
             }
             MonitorManagerEvent::Updated(name, id) => {
                 if let Some(m) = trace_lock!(SEELEN).monitor_by_name_mut(&name) {
                     m.update_handle(id);
                 }
             }
+            MonitorManagerEvent::WorkAreaChanged => {
+                log_error!(trace_lock!(SEELEN).on_work_area_changed());
+                return;
+            }
+            MonitorManagerEvent::ScaleChanged(name, id) => {
+                log_error!(trace_lock!(SEELEN).on_scale_changed(&name, id));
+                return;
+            }
+            MonitorManagerEvent::Woke => {
+                log_error!(trace_lock!(SEELEN).refresh_windows_positions());
+                Self::sync_wm_monitors();
+                return;
+            }
         }
-        log_error!(get_app_handle().emit(SeelenEvent::GlobalMonitorsChanged, ()));
+        log_error!(refresh_tray_menu());
+        log_error!(EventRegistry::emit(SeelenEvent::GlobalMonitorsChanged, ()));
     }
 
     async fn start_async() -> Result<()> {
@@ -188,12 +426,17 @@ impl Seelen {
         }
 
         Self::start_ahk_shortcuts()?;
-        Self::refresh_auto_start_path().await?;
+        StartupTask::refresh_path().await?;
         Ok(())
     }
 
     pub fn start(&mut self) -> Result<()> {
         SEELEN_IS_RUNNING.store(true, std::sync::atomic::Ordering::SeqCst);
+        SeelenLogger::set_config(&self.state().settings.logging);
+        Diagnostics::set_config(&self.state().settings.diagnostics);
+        EmitScheduler::set_max_rate(self.state().settings.max_emit_rate);
+        log_error!(LayoutDirection::refresh());
+        start_lock_watchdog()?;
         declare_system_events_handlers()?;
 
         if self.state().is_rofi_enabled() {
@@ -204,9 +447,8 @@ impl Seelen {
             self.add_wall()?;
         }
 
-        if self.state().is_weg_enabled() {
-            SeelenWeg::hide_taskbar();
-        }
+        NativeTaskbar::apply();
+        log_error!(WmSnap::refresh());
 
         log::trace!("Enumerating Monitors & Creating Instances");
         let monitors = trace_lock!(MONITOR_MANAGER).monitors.clone();
@@ -222,7 +464,42 @@ impl Seelen {
         });
 
         self.refresh_windows_positions()?;
+        Self::refresh_win_event_subscriptions(&self.state());
         register_win_hook()?;
+        KeybindingsManager::load(&self.state().settings.keybindings)?;
+        KeybindingsManager::set_window_switcher_grab(
+            self.state().settings.window_switcher.enabled,
+        )?;
+        WebviewSupervisor::start()?;
+        ResourceGovernor::start()?;
+        PerfMonitor::start_stream()?;
+        EmitScheduler::start()?;
+        IpcServer::start()?;
+        StatusFeed::start()?;
+        log_error!(RemoteControl::apply(&self.state().settings.remote_control));
+        TriggerRunner::start()?;
+        ScriptingEngine::start()?;
+        SeelenUpdater::start()?;
+        ExplorerWatcher::start()?;
+        FocusBorder::start()?;
+        MinimizeTargets::start()?;
+        NightLight::start()?;
+        RemovableDrives::start()?;
+        Storage::start()?;
+        Printers::start()?;
+        RecycleBin::start()?;
+        StickyNotes::start()?;
+        Ime::start()?;
+        Clock::start()?;
+        DailyWallpapers::start()?;
+        Urgency::start()?;
+        SessionManager::start()?;
+        AppUsageTracker::start()?;
+        BreakReminder::start()?;
+        IdleActions::start()?;
+        StartMenuManager::start()?;
+        ProcessWatcher::start()?;
+        log_error!(VersionUpgrades::emit_pending());
         Ok(())
     }
 
@@ -231,9 +508,14 @@ impl Seelen {
         SEELEN_IS_RUNNING.store(false, std::sync::atomic::Ordering::SeqCst);
 
         release_system_events_handlers();
-        if self.state().is_weg_enabled() {
-            log_error!(SeelenWeg::restore_taskbar());
-        }
+        log_error!(NativeTaskbar::restore());
+        log_error!(NightLight::restore());
+        log_error!(WmSnap::restore());
+        log_error!(BreakReminder::restore());
+        WindowEffects::revert_all();
+        log_error!(AudioPolicy::release());
+        Titlebar::revert_all();
+        WmCorners::restore_all();
         if self.state().is_ahk_enabled() {
             log_error!(Self::kill_ahk_shortcuts());
         }
@@ -281,77 +563,23 @@ impl Seelen {
         create_if_needed("placeholders")?;
         create_if_needed("themes")?;
         create_if_needed("layouts")?;
+        create_if_needed("scripts")?;
+        create_if_needed("widgets")?;
+        create_if_needed("settings.d")?;
         create_if_needed("icons/system")?;
         create_if_needed("wallpapers")?;
 
         Ok(())
     }
 
-    pub async fn is_auto_start_enabled() -> Result<bool> {
-        let handle = get_app_handle();
-        let output = handle
-            .shell()
-            .command("powershell")
-            .args([
-                "-ExecutionPolicy",
-                "Bypass",
-                "-NoProfile",
-                "-Command",
-                "[bool](Get-ScheduledTask -TaskName Seelen-UI -ErrorAction SilentlyContinue)",
-            ])
-            .output()
-            .await?;
-
-        let (cow, _used, _has_errors) = encoding_rs::GBK.decode(&output.stdout);
-        let stdout = cow.to_string().trim().to_lowercase();
-        Ok(stdout == "true")
-    }
-
-    /// override auto-start task in case of location change, normally this happen on MSIX update
-    async fn refresh_auto_start_path() -> Result<()> {
-        if WindowsApi::is_elevated()? && Self::is_auto_start_enabled().await? {
-            Self::set_auto_start(true).await?;
-        }
-        Ok(())
-    }
-
-    pub async fn set_auto_start(enabled: bool) -> Result<()> {
-        let pwsh_script = include_str!("schedule.ps1");
-        let pwsh_script_path = temp_dir().join("schedule.ps1");
-        std::fs::write(&pwsh_script_path, pwsh_script).expect("Failed to write temp script file");
-
-        let exe_path = std::env::current_exe()?;
-
-        let output = get_app_handle()
-            .shell()
-            .command("powershell")
-            .args([
-                "-ExecutionPolicy",
-                "Bypass",
-                "-NoProfile",
-                "-File",
-                &pwsh_script_path.to_string_lossy(),
-                "-ExeRoute",
-                &exe_path.to_string_lossy(),
-                "-Enabled",
-                if enabled { "true" } else { "false" },
-            ])
-            .output()
-            .await?;
-
-        if !output.status.success() {
-            return Err(output.into());
-        }
-        Ok(())
-    }
-
     // TODO: split ahk logic into another file/module
     pub fn start_ahk_shortcuts() -> Result<()> {
         // kill all running shortcuts before starting again
         Self::kill_ahk_shortcuts()?;
 
         let state = FULL_STATE.load();
-        if state.is_ahk_enabled() {
+        // integrated keybindings replace the AHK shortcuts
+        if state.is_ahk_enabled() && !state.is_keybindings_enabled() {
             log::trace!("Creating AHK shortcuts");
             let vars = state.get_ahk_variables();
 
@@ -0,0 +1,23 @@
+use seelen_core::state::ToolbarItemAction;
+
+use crate::{
+    error_handler::Result,
+    modules::{
+        actions::{domain::ActionCatalog, run_cli},
+        scripting::ScriptingEngine,
+    },
+};
+
+/// Runs an action declared on `actions` of a toolbar item
+#[tauri::command(async)]
+pub fn toolbar_run_item_action(action: ToolbarItemAction) -> Result<()> {
+    log::trace!("Running toolbar action {:?}", action);
+    match action {
+        ToolbarItemAction::Action { id, params } => ActionCatalog::build().run(&id, &params),
+        ToolbarItemAction::Cli(args) => run_cli(args),
+        ToolbarItemAction::Script { file, function } => {
+            ScriptingEngine::invoke(file, function);
+            Ok(())
+        }
+    }
+}
@@ -9,6 +9,18 @@ use crate::{
 use super::FancyToolbar;
 
 impl FancyToolbar {
+    pub fn wants_win_event(event: &WinEvent) -> bool {
+        matches!(
+            event,
+            WinEvent::ObjectNameChange
+                | WinEvent::SystemForeground
+                | WinEvent::ObjectFocus
+                | WinEvent::ObjectLocationChange
+                | WinEvent::SyntheticFullscreenStart(_)
+                | WinEvent::SyntheticFullscreenEnd(_)
+        )
+    }
+
     pub fn process_win_event(&mut self, event: WinEvent, origin: HWND) -> Result<()> {
         let window = Window::from(origin);
         match event {
@@ -31,7 +43,7 @@ impl FancyToolbar {
             }
             WinEvent::SyntheticFullscreenStart(event_data) => {
                 let monitor = WindowsApi::monitor_from_window(self.window.hwnd()?);
-                if monitor == event_data.monitor {
+                if monitor == event_data.monitor && !event_data.policy().keep_toolbar {
                     self.hide()?;
                 }
             }
@@ -1,10 +1,15 @@
 pub mod cli;
+pub mod handler;
 pub mod hook;
 
 use crate::{
     error_handler::Result,
     log_error,
-    modules::virtual_desk::get_vd_manager,
+    modules::{
+        clock::Clock, event_registry::EventRegistry, perf::PerfMonitor,
+        resource_governor::ResourceGovernor, supervisor::WebviewState,
+        virtual_desk::get_vd_manager,
+    },
     seelen::get_app_handle,
     state::application::FULL_STATE,
     utils::{
@@ -37,6 +42,7 @@ impl Drop for FancyToolbar {
         if let Ok(hwnd) = self.window.hwnd() {
             AppBarData::from_handle(hwnd).unregister_bar();
         }
+        ResourceGovernor::forget(&self.window);
         log_error!(self.window.destroy());
     }
 }
@@ -44,15 +50,22 @@ impl Drop for FancyToolbar {
 impl FancyToolbar {
     pub fn new(postfix: &str) -> Result<Self> {
         log::info!("Creating {}/{}", Self::TARGET, postfix);
+        let window = Self::create_window(postfix)?;
+        ResourceGovernor::on_toolbar_created(&window);
         Ok(Self {
-            window: Self::create_window(postfix)?,
+            window,
             last_focus: None,
             theoretical_rect: RECT::default(),
             overlaped: false,
         })
     }
 
+    pub fn window(&self) -> &WebviewWindow {
+        &self.window
+    }
+
     pub fn emit<S: Serialize + Clone>(&self, event: &str, payload: S) -> Result<()> {
+        PerfMonitor::record_emit(event);
         self.window.emit_to(self.window.label(), event, payload)?;
         Ok(())
     }
@@ -84,6 +97,7 @@ impl FancyToolbar {
 
     pub fn hide(&mut self) -> Result<()> {
         WindowsApi::show_window_async(self.window.hwnd()?, SW_HIDE)?;
+        ResourceGovernor::on_visibility_change(&self.window, false)?;
         self.window.emit_to(
             self.window.label(),
             SeelenEvent::HandleLayeredHitboxes,
@@ -93,6 +107,9 @@ impl FancyToolbar {
     }
 
     pub fn show(&mut self) -> Result<()> {
+        ResourceGovernor::on_visibility_change(&self.window, true)?;
+        // the clock ticks slow down while the toolbars are hidden
+        Clock::refresh();
         WindowsApi::show_window_async(self.window.hwnd()?, SW_SHOWNOACTIVATE)?;
         self.window.emit_to(
             self.window.label(),
@@ -106,6 +123,23 @@ impl FancyToolbar {
         self.last_focus = Some(hwnd);
         Ok(())
     }
+
+    pub fn webview_state(&self) -> WebviewState {
+        WebviewState {
+            visible: self.window.is_visible().unwrap_or(true),
+            overlaped: self.overlaped,
+        }
+    }
+
+    /// Applies the state of a previous webview, called once the new one is loaded
+    pub fn restore_webview_state(&mut self, state: WebviewState) -> Result<()> {
+        self.overlaped = state.overlaped;
+        self.emit(SeelenEvent::ToolbarOverlaped, self.overlaped)?;
+        if !state.visible {
+            self.hide()?;
+        }
+        Ok(())
+    }
 }
 
 // statics
@@ -113,8 +147,8 @@ impl FancyToolbar {
     pub const TITLE: &'static str = "Seelen Fancy Toolbar";
     const TARGET: &'static str = "fancy-toolbar";
 
-    /// Work area no works fine on multiple monitors
-    /// so we use this functions that only takes the toolbar in account
+    /// Area of the monitor without the space of the toolbar, the dock is placed inside of it.
+    /// For the space not reserved by any appbar use `Monitor::work_area`.
     pub fn get_work_area_by_monitor(monitor: HMONITOR) -> Result<RECT> {
         let monitor_info = WindowsApi::monitor_info(monitor)?;
 
@@ -190,15 +224,14 @@ impl FancyToolbar {
     fn on_store_events_ready(_: tauri::Event) {
         // TODO refactor this implementation
         std::thread::spawn(|| -> Result<()> {
-            let handler = get_app_handle();
             let vd = get_vd_manager();
             let desktops = vd
                 .get_all()?
                 .iter()
                 .map(|d| d.as_serializable())
                 .collect_vec();
-            handler.emit(SeelenEvent::WorkspacesChanged, &desktops)?;
-            handler.emit(SeelenEvent::ActiveWorkspaceChanged, vd.get_current()?.id())?;
+            EventRegistry::emit(SeelenEvent::WorkspacesChanged, &desktops)?;
+            EventRegistry::emit(SeelenEvent::ActiveWorkspaceChanged, vd.get_current()?.id())?;
             Ok(())
         });
     }
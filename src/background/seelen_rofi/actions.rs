@@ -0,0 +1,304 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri_plugin_shell::ShellExt;
+use winreg::{
+    enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE},
+    RegKey, HKEY,
+};
+
+use crate::{
+    error_handler::Result,
+    modules::shortcuts::ShortcutResolver,
+    seelen::get_app_handle,
+    windows_api::{Clipboard, WindowsApi},
+};
+
+const UNINSTALL_KEYS: [(HKEY, &str); 3] = [
+    (
+        HKEY_LOCAL_MACHINE,
+        r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
+    ),
+    (
+        HKEY_LOCAL_MACHINE,
+        r"SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall",
+    ),
+    (
+        HKEY_CURRENT_USER,
+        r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
+    ),
+];
+
+const EXECUTABLE_EXTENSIONS: [&str; 5] = ["exe", "bat", "cmd", "com", "msc"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LauncherItemKind {
+    /// shortcut of the start menu
+    App,
+    /// text typed on the search input, a path or a command
+    Command,
+    Note,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LauncherItem {
+    pub kind: LauncherItemKind,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LauncherItemAction {
+    Open,
+    RunAsAdmin,
+    OpenLocation,
+    CopyPath,
+    Uninstall,
+}
+
+/// Entry of the installed programs list of the control panel
+#[derive(Debug)]
+struct UninstallEntry {
+    display_name: String,
+    install_location: Option<PathBuf>,
+    uninstall_string: String,
+}
+
+impl UninstallEntry {
+    fn all() -> Vec<Self> {
+        let mut entries = Vec::new();
+        for (hkey, path) in UNINSTALL_KEYS {
+            let Ok(key) = RegKey::predef(hkey).open_subkey(path) else {
+                continue;
+            };
+            for name in key.enum_keys().flatten() {
+                let Ok(app) = key.open_subkey(&name) else {
+                    continue;
+                };
+                // updates and runtimes installed as part of other programs
+                if app.get_value::<u32, _>("SystemComponent") == Ok(1) {
+                    continue;
+                }
+                let (Ok(display_name), Ok(uninstall_string)) = (
+                    app.get_value::<String, _>("DisplayName"),
+                    app.get_value::<String, _>("UninstallString"),
+                ) else {
+                    continue;
+                };
+                entries.push(Self {
+                    display_name,
+                    install_location: app
+                        .get_value::<String, _>("InstallLocation")
+                        .ok()
+                        .filter(|location| !location.trim().is_empty())
+                        .map(PathBuf::from),
+                    uninstall_string,
+                });
+            }
+        }
+        entries
+    }
+
+    /// Same display name as the shortcut, or else the deepest install folder containing the program
+    fn find(entries: Vec<Self>, label: &str, target: Option<&Path>) -> Option<Self> {
+        let target = target.map(|target| PathBuf::from(target.to_string_lossy().to_lowercase()));
+        let depth = |entry: &Self| {
+            let location = entry
+                .install_location
+                .as_ref()?
+                .to_string_lossy()
+                .to_lowercase();
+            let location = PathBuf::from(location.trim_matches('"'));
+            target
+                .as_ref()?
+                .starts_with(&location)
+                .then(|| location.components().count())
+        };
+
+        let index = entries
+            .iter()
+            .position(|entry| entry.display_name.eq_ignore_ascii_case(label))
+            .or_else(|| {
+                entries
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, entry)| Some((depth(entry)?, index)))
+                    .max()
+                    .map(|(_, index)| index)
+            })?;
+        entries.into_iter().nth(index)
+    }
+}
+
+/// `"C:\app\unins000.exe" /SILENT` -> (`C:\app\unins000.exe`, `/SILENT`)
+fn split_command_line(command: &str) -> (String, Option<String>) {
+    let command = command.trim();
+    let (file, args) = match command.strip_prefix('"') {
+        Some(rest) => rest.split_once('"').unwrap_or((rest, "")),
+        None => match command.to_lowercase().find(".exe") {
+            Some(idx) => command.split_at(idx + 4),
+            None => command.split_once(' ').unwrap_or((command, "")),
+        },
+    };
+    let args = args.trim();
+    (
+        file.to_string(),
+        (!args.is_empty()).then(|| args.to_string()),
+    )
+}
+
+/// Secondary actions of the launcher results, resolved by type of result
+pub struct LauncherActions;
+
+impl LauncherActions {
+    fn label(item: &LauncherItem) -> String {
+        Path::new(&item.path)
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Program opened by the item, shortcuts are resolved to their target
+    fn target(item: &LauncherItem) -> Option<PathBuf> {
+        let path = PathBuf::from(&item.path);
+        if ShortcutResolver::is_shortcut(&path) {
+            return ShortcutResolver::resolve(&path).ok()?.target;
+        }
+        Some(path)
+    }
+
+    fn is_executable(path: &Path) -> bool {
+        path.extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .is_some_and(|ext| EXECUTABLE_EXTENSIONS.contains(&ext.as_str()))
+    }
+
+    fn uninstall_entry(item: &LauncherItem) -> Option<UninstallEntry> {
+        let target = Self::target(item);
+        UninstallEntry::find(UninstallEntry::all(), &Self::label(item), target.as_deref())
+    }
+
+    /// Actions available for the item, the first one is the default (Enter)
+    /// and the second one the alternative (Shift+Enter)
+    pub fn resolve(item: &LauncherItem) -> Vec<LauncherItemAction> {
+        let mut actions = vec![LauncherItemAction::Open];
+        match item.kind {
+            LauncherItemKind::App => {
+                if Self::target(item).is_some_and(|target| Self::is_executable(&target)) {
+                    actions.push(LauncherItemAction::RunAsAdmin);
+                }
+                actions.push(LauncherItemAction::OpenLocation);
+                actions.push(LauncherItemAction::CopyPath);
+                if Self::uninstall_entry(item).is_some() {
+                    actions.push(LauncherItemAction::Uninstall);
+                }
+            }
+            LauncherItemKind::Command => {
+                actions.push(LauncherItemAction::RunAsAdmin);
+                if Path::new(&item.path).exists() {
+                    actions.push(LauncherItemAction::OpenLocation);
+                    actions.push(LauncherItemAction::CopyPath);
+                }
+            }
+            LauncherItemKind::Note => {
+                actions.push(LauncherItemAction::OpenLocation);
+                actions.push(LauncherItemAction::CopyPath);
+            }
+        }
+        actions
+    }
+
+    fn shell_open(path: &str) -> Result<()> {
+        get_app_handle()
+            .shell()
+            .command("cmd")
+            .args(["/c", "explorer", path])
+            .spawn()?;
+        Ok(())
+    }
+
+    pub fn run(item: &LauncherItem, action: LauncherItemAction) -> Result<()> {
+        log::trace!("Running launcher action {:?} on {:?}", action, item);
+        match action {
+            LauncherItemAction::Open => Self::shell_open(&item.path)?,
+            LauncherItemAction::RunAsAdmin => WindowsApi::run_as_admin(&item.path, None, None)?,
+            LauncherItemAction::OpenLocation => {
+                get_app_handle()
+                    .shell()
+                    .command("explorer")
+                    .args(["/select,", &item.path])
+                    .spawn()?;
+            }
+            LauncherItemAction::CopyPath => {
+                let path = match item.kind {
+                    LauncherItemKind::App => Self::target(item)
+                        .map(|target| target.to_string_lossy().to_string())
+                        .unwrap_or_else(|| item.path.clone()),
+                    _ => item.path.clone(),
+                };
+                Clipboard::set_text(&path)?;
+            }
+            LauncherItemAction::Uninstall => match Self::uninstall_entry(item) {
+                Some(entry) => {
+                    let (file, args) = split_command_line(&entry.uninstall_string);
+                    WindowsApi::run_as_admin(&file, args.as_deref(), None)?;
+                }
+                // the installer is unknown, let the user find the app on the system list
+                None => Self::shell_open("ms-settings:appsfeatures")?,
+            },
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_command_line() {
+        assert_eq!(
+            split_command_line(r#""C:\Program Files\App\unins000.exe" /SILENT"#),
+            (
+                r"C:\Program Files\App\unins000.exe".into(),
+                Some("/SILENT".into())
+            )
+        );
+        assert_eq!(
+            split_command_line("MsiExec.exe /X{1234}"),
+            ("MsiExec.exe".into(), Some("/X{1234}".into()))
+        );
+        assert_eq!(
+            split_command_line(r"C:\Program Files\App\uninstall.exe"),
+            (r"C:\Program Files\App\uninstall.exe".into(), None)
+        );
+    }
+
+    #[test]
+    fn test_find_uninstall_entry() {
+        let entry = |name: &str, location: Option<&str>| UninstallEntry {
+            display_name: name.into(),
+            install_location: location.map(PathBuf::from),
+            uninstall_string: String::new(),
+        };
+        let found = |label: &str, target: Option<&str>| {
+            let entries = vec![
+                entry("Tools", Some(r"C:\Program Files")),
+                entry("Editor 2.0", Some(r"C:\Program Files\Editor")),
+                entry("Player", None),
+            ];
+            UninstallEntry::find(entries, label, target.map(Path::new))
+                .map(|entry| entry.display_name)
+        };
+        assert_eq!(found("player", None).as_deref(), Some("Player"));
+        // the deepest folder wins over broad install locations
+        assert_eq!(
+            found("Editor", Some(r"C:\PROGRAM FILES\Editor\bin\editor.exe")).as_deref(),
+            Some("Editor 2.0")
+        );
+        assert_eq!(found("Other", Some(r"D:\other.exe")), None);
+    }
+}
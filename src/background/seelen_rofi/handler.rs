@@ -1,11 +1,31 @@
-use crate::{seelen::SEELEN, trace_lock};
+use crate::{
+    error_handler::Result, modules::start_menu::StartMenuManager, utils::constants::Icons,
+};
 
-use super::SeelenRofiApp;
+use super::{
+    actions::{LauncherActions, LauncherItem, LauncherItemAction},
+    SeelenRofiApp,
+};
 
 #[tauri::command(async)]
-pub fn launcher_get_apps() -> Vec<SeelenRofiApp> {
-    if let Some(rofi) = trace_lock!(SEELEN).rofi() {
-        return rofi.apps.clone();
-    }
-    Vec::new()
+pub fn launcher_get_apps() -> Result<Vec<SeelenRofiApp>> {
+    Ok(StartMenuManager::items()?
+        .into_iter()
+        .map(|item| SeelenRofiApp {
+            label: item.label,
+            icon: item.icon.unwrap_or_else(Icons::missing_app),
+            path: item.path,
+        })
+        .collect())
+}
+
+/// Actions of a result, the first one is run with Enter and the second one with Shift+Enter
+#[tauri::command(async)]
+pub fn launcher_get_item_actions(item: LauncherItem) -> Vec<LauncherItemAction> {
+    LauncherActions::resolve(&item)
+}
+
+#[tauri::command(async)]
+pub fn launcher_run_item_action(item: LauncherItem, action: LauncherItemAction) -> Result<()> {
+    LauncherActions::run(&item, action)
 }
@@ -0,0 +1,12 @@
+use seelen_core::rect::Rect;
+use windows::Win32::Foundation::RECT;
+
+use crate::error_handler::Result;
+
+use super::widgets::WallWidgets;
+
+/// Receives the rects of the widgets of the wall, in physical pixels
+#[tauri::command(async)]
+pub fn wall_set_widget_rects(rects: Vec<Rect>) -> Result<()> {
+    WallWidgets::set_rects(rects.into_iter().map(RECT::from).collect())
+}
@@ -6,6 +6,13 @@ use crate::{error_handler::Result, windows_api::window::Window, winevent::WinEve
 use super::SeelenWall;
 
 impl SeelenWall {
+    pub fn wants_win_event(event: &WinEvent) -> bool {
+        matches!(
+            event,
+            WinEvent::SyntheticFullscreenStart(_) | WinEvent::SyntheticFullscreenEnd(_)
+        )
+    }
+
     pub fn process_win_event(&mut self, event: WinEvent, _origin: &Window) -> Result<()> {
         match event {
             WinEvent::SyntheticFullscreenStart(_) => {
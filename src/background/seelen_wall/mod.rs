@@ -1,4 +1,6 @@
+pub mod handler;
 mod hook;
+mod widgets;
 
 use tauri::WebviewWindow;
 use windows::Win32::{
@@ -11,11 +13,15 @@ use windows::Win32::{
 
 use crate::{
     error_handler::Result,
-    log_error, pcstr,
+    log_error,
+    modules::resource_governor::ResourceGovernor,
+    pcstr,
     seelen::get_app_handle,
     windows_api::{WindowEnumerator, WindowsApi},
 };
 
+use widgets::WallWidgets;
+
 pub struct SeelenWall {
     window: WebviewWindow,
 }
@@ -23,6 +29,8 @@ pub struct SeelenWall {
 impl Drop for SeelenWall {
     fn drop(&mut self) {
         log::info!("Dropping {}", self.window.label());
+        WallWidgets::clear();
+        ResourceGovernor::forget(&self.window);
         log_error!(self.window.destroy());
     }
 }
@@ -38,6 +46,10 @@ impl SeelenWall {
         })
     }
 
+    pub fn window(&self) -> &WebviewWindow {
+        &self.window
+    }
+
     fn create_window() -> Result<WebviewWindow> {
         let handle = get_app_handle();
         let window = tauri::WebviewWindowBuilder::new(
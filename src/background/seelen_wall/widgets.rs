@@ -0,0 +1,148 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::handlers::SeelenEvent;
+use serde::Serialize;
+use tauri::Emitter;
+use windows::Win32::{
+    Foundation::{HINSTANCE, HWND, LPARAM, LRESULT, POINT, RECT, WPARAM},
+    UI::WindowsAndMessaging::{
+        CallNextHookEx, DispatchMessageW, GetAncestor, GetMessageW, SetWindowsHookExW,
+        TranslateMessage, UnhookWindowsHookEx, WindowFromPoint, GA_ROOT, HC_ACTION, HHOOK,
+        LLMHF_INJECTED, MSG, MSLLHOOKSTRUCT, WH_MOUSE_LL, WM_LBUTTONDOWN, WM_LBUTTONUP,
+    },
+};
+
+use crate::{
+    error_handler::Result, log_error, seelen::get_app_handle, trace_lock,
+    utils::spawn_named_thread, windows_api::WindowsApi,
+};
+
+use super::SeelenWall;
+
+lazy_static! {
+    /// rects of the widgets in physical pixels
+    static ref WIDGET_RECTS: Mutex<Vec<RECT>> = Mutex::new(Vec::new());
+}
+
+static HOOK_INSTALLED: AtomicBool = AtomicBool::new(false);
+/// a press inside a widget, its release is forwarded too even if outside
+static PRESSED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WallWidgetClick {
+    pressed: bool,
+    /// screen position in physical pixels
+    x: i32,
+    y: i32,
+}
+
+/// The wall lives behind the desktop icons so it never receives the mouse input,
+/// clicks inside the widgets are taken from the desktop and sent to the webview.
+pub struct WallWidgets;
+
+impl WallWidgets {
+    pub fn set_rects(rects: Vec<RECT>) -> Result<()> {
+        let is_empty = rects.is_empty();
+        *trace_lock!(WIDGET_RECTS) = rects;
+        if !is_empty && !HOOK_INSTALLED.swap(true, Ordering::SeqCst) {
+            Self::register_hook()?;
+        }
+        Ok(())
+    }
+
+    pub fn clear() {
+        trace_lock!(WIDGET_RECTS).clear();
+        PRESSED.store(false, Ordering::SeqCst);
+    }
+
+    fn register_hook() -> Result<()> {
+        log::trace!("Registering Wall Mouse Hook");
+        spawn_named_thread("WallMouseHook", || unsafe {
+            let hook = match SetWindowsHookExW(
+                WH_MOUSE_LL,
+                Some(Self::mouse_hook),
+                HINSTANCE::default(),
+                0,
+            ) {
+                Ok(hook) => hook,
+                Err(err) => {
+                    log::error!("Failed to register wall mouse hook: {:?}", err);
+                    HOOK_INSTALLED.store(false, Ordering::SeqCst);
+                    return;
+                }
+            };
+
+            let mut msg: MSG = MSG::default();
+            loop {
+                if !GetMessageW(&mut msg, HWND::default(), 0, 0).as_bool() {
+                    break;
+                };
+                let _ = TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+
+            log_error!(UnhookWindowsHookEx(hook));
+            HOOK_INSTALLED.store(false, Ordering::SeqCst);
+        })?;
+        Ok(())
+    }
+
+    /// Only the desktop is replaced, windows over the widgets keep their clicks
+    fn is_desktop_at(point: POINT) -> bool {
+        let root = unsafe { GetAncestor(WindowFromPoint(point), GA_ROOT) };
+        matches!(
+            WindowsApi::get_class(root).as_deref(),
+            Ok("Progman") | Ok("WorkerW")
+        ) || WindowsApi::get_window_text(root) == SeelenWall::TITLE
+    }
+
+    fn is_inside_widget(point: POINT) -> bool {
+        trace_lock!(WIDGET_RECTS).iter().any(|rect| {
+            point.x >= rect.left
+                && point.x < rect.right
+                && point.y >= rect.top
+                && point.y < rect.bottom
+        })
+    }
+
+    fn forward(pressed: bool, point: POINT) {
+        let click = WallWidgetClick {
+            pressed,
+            x: point.x,
+            y: point.y,
+        };
+        log_error!(get_app_handle().emit_to(
+            SeelenWall::TARGET,
+            SeelenEvent::WallWidgetClick,
+            click
+        ));
+    }
+
+    unsafe extern "system" fn mouse_hook(code: i32, w_param: WPARAM, l_param: LPARAM) -> LRESULT {
+        if code == HC_ACTION as i32 {
+            let message = w_param.0 as u32;
+            let info = &*(l_param.0 as *const MSLLHOOKSTRUCT);
+            let injected = info.flags & LLMHF_INJECTED != 0;
+
+            if !injected
+                && message == WM_LBUTTONDOWN
+                && Self::is_inside_widget(info.pt)
+                && Self::is_desktop_at(info.pt)
+            {
+                PRESSED.store(true, Ordering::SeqCst);
+                Self::forward(true, info.pt);
+                // swallowed so the desktop doesn't start a selection
+                return LRESULT(1);
+            }
+
+            if !injected && message == WM_LBUTTONUP && PRESSED.swap(false, Ordering::SeqCst) {
+                Self::forward(false, info.pt);
+                return LRESULT(1);
+            }
+        }
+        CallNextHookEx(HHOOK::default(), code, w_param, l_param)
+    }
+}
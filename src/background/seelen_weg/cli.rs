@@ -1,12 +1,17 @@
+use std::path::PathBuf;
+
 use clap::Command;
+use seelen_core::state::{PinnedWegItemData, WegItem};
 
-use crate::{error_handler::Result, get_subcommands};
+use crate::{error_handler::Result, get_subcommands, state::application::FULL_STATE, trace_lock};
 
 use super::SeelenWeg;
 
 get_subcommands![
     /** Open Dev Tools (only works if the app is running in dev mode) */
     Debug,
+    /** Pins a program, file or folder to the dock */
+    Pin(path: PathBuf => "Path of the item to pin."),
 ];
 
 impl SeelenWeg {
@@ -19,13 +24,63 @@ impl SeelenWeg {
             .subcommands(SubCommand::commands())
     }
 
-    pub fn process(&mut self, matches: &clap::ArgMatches) -> Result<()> {
+    /// Pins the item to the center of the dock, if it is already open it will be pinned in place
+    pub fn pin_item(path: PathBuf) -> Result<()> {
+        if !path.exists() {
+            return Err(format!("{} does not exist", path.display()).into());
+        }
+
+        let data = PinnedWegItemData {
+            execution_command: path.to_string_lossy().to_string(),
+            is_dir: path.is_dir(),
+            path,
+            orphaned: false,
+            arguments: None,
+            working_dir: None,
+            run_as_admin: false,
+            new_instance: false,
+        };
+
+        let state = FULL_STATE.load();
+        let mut items = trace_lock!(state.weg_items).clone();
+        let existing = items
+            .left
+            .iter_mut()
+            .chain(items.center.iter_mut())
+            .chain(items.right.iter_mut())
+            .find(|item| match item {
+                WegItem::Pinned(d) | WegItem::Temporal(d) => {
+                    d.execution_command == data.execution_command
+                }
+                _ => false,
+            });
+
+        match existing {
+            Some(item) => {
+                if let WegItem::Temporal(d) = item {
+                    *item = WegItem::Pinned(d.clone());
+                }
+            }
+            None => items.center.push(WegItem::Pinned(data)),
+        }
+
+        // the file watcher will reload and emit the new items
+        state.save_weg_items(&items)
+    }
+
+    pub fn process(matches: &clap::ArgMatches) -> Result<()> {
         let subcommand = SubCommand::try_from(matches)?;
         match subcommand {
-            SubCommand::Debug => {
+            SubCommand::Debug =>
+            {
                 #[cfg(any(debug_assertions, feature = "devtools"))]
-                self.window.open_devtools();
+                for monitor in trace_lock!(crate::seelen::SEELEN).monitors() {
+                    if let Some(weg) = monitor.weg() {
+                        weg.window.open_devtools();
+                    }
+                }
             }
+            SubCommand::Pin(path) => Self::pin_item(path)?,
         };
         Ok(())
     }
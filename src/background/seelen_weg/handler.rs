@@ -1,19 +1,26 @@
 use std::{ffi::OsStr, path::PathBuf, sync::atomic::Ordering};
 
 use image::ImageFormat;
-use seelen_core::state::{PinnedWegItemData, WegItem};
-use tauri::Emitter;
+use seelen_core::state::{PinnedWegItemData, WegItem, WegItemsSide};
 
 use crate::{
-    error_handler::Result, hook::LAST_ACTIVE_NOT_SEELEN, seelen::get_app_handle,
-    state::application::FULL_STATE, trace_lock, windows_api::WindowsApi,
+    error_handler::Result,
+    hook::LAST_ACTIVE_NOT_SEELEN,
+    log_error,
+    modules::{
+        event_registry::EventRegistry, notifications::application::show_toast,
+        shortcuts::ShortcutResolver,
+    },
+    state::application::FULL_STATE,
+    t, trace_lock,
+    windows_api::{monitor::Monitor, WindowsApi},
 };
 use windows::Win32::{
     Foundation::HWND,
     UI::WindowsAndMessaging::{SW_MINIMIZE, SW_RESTORE, WM_CLOSE},
 };
 
-use super::SeelenWeg;
+use super::{launcher::WegItemLauncher, SeelenWeg};
 
 #[tauri::command(async)]
 pub fn weg_request_update_previews(handles: Vec<isize>) -> Result<()> {
@@ -46,7 +53,7 @@ pub fn weg_request_update_previews(handles: Vec<isize>) -> Result<()> {
             );
 
             image.save_with_format(temp_dir.join(format!("{}.png", addr)), ImageFormat::Png)?;
-            get_app_handle().emit(format!("weg-preview-update-{}", addr).as_str(), ())?;
+            EventRegistry::emit(format!("weg-preview-update-{}", addr).as_str(), ())?;
         }
     }
     Ok(())
@@ -63,6 +70,13 @@ pub fn weg_close_app(hwnd: isize) -> Result<()> {
     Ok(())
 }
 
+/// Brings the windows of an app to the monitor of the dock that requested it
+#[tauri::command(async)]
+pub fn weg_gather_windows(handles: Vec<isize>, monitor: String) -> Result<()> {
+    let monitor = Monitor::by_id(&monitor).ok_or(format!("Monitor {} not found", monitor))?;
+    SeelenWeg::gather_windows(&handles, &monitor)
+}
+
 #[tauri::command(async)]
 pub fn weg_toggle_window_state(hwnd: isize) -> Result<()> {
     let hwnd = HWND(hwnd as _);
@@ -94,19 +108,64 @@ pub fn weg_pin_item(path: PathBuf) -> Result<()> {
         path: path.clone(),
         is_dir: path.is_dir(),
         execution_command: path.to_string_lossy().to_string(),
+        orphaned: false,
+        arguments: None,
+        working_dir: None,
+        run_as_admin: false,
+        new_instance: false,
     };
 
     if path.extension() == Some(OsStr::new("lnk")) {
-        let (program, _arguments) = WindowsApi::resolve_lnk_target(&path)?;
-        data.is_dir = program.is_dir();
-        data.execution_command = program.to_string_lossy().to_string();
+        if let Some(program) = ShortcutResolver::resolve(&path)?.target {
+            data.is_dir = program.is_dir();
+            data.execution_command = program.to_string_lossy().to_string();
+        }
     }
 
     let state = FULL_STATE.load();
     let mut weg_items = trace_lock!(state.weg_items);
     weg_items.center.insert(0, WegItem::Pinned(data));
-    weg_items.sanitize();
+    weg_items.sanitize(state.settings.seelenweg.orphaned_items);
     state.emit_weg_items(&weg_items)?;
     state.save_weg_items(&weg_items)?;
     Ok(())
 }
+
+/// Moves a saved item of the dock, the items are shared by the docks of all the monitors
+/// so the emitted list keeps all of them in the same order.
+#[tauri::command(async)]
+pub fn weg_move_item(id: String, side: WegItemsSide, index: usize) -> Result<()> {
+    let state = FULL_STATE.load();
+    let mut weg_items = trace_lock!(state.weg_items);
+
+    // applied over a copy so the items on memory only change if they were persisted
+    let mut items = weg_items.clone();
+    if !items.move_item(&id, side, index) {
+        return Err(format!("Weg item not found: {}", id).into());
+    }
+    state.save_weg_items(&items)?;
+    *weg_items = items;
+    state.emit_weg_items(&weg_items)?;
+    Ok(())
+}
+
+/// Opens the program, file or folder of the item with its launch options
+#[tauri::command(async)]
+pub fn weg_launch_item(item: PinnedWegItemData) -> Result<()> {
+    let result = WegItemLauncher::launch(&item);
+    if let Err(err) = &result {
+        let app = item
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| item.execution_command.clone());
+        // the error includes the backtrace, only the message is useful for the user
+        let error = err.to_string();
+        let error = error.lines().next().unwrap_or_default();
+        log_error!(show_toast(
+            &t!("weg.launch_failed_title"),
+            &t!("weg.launch_failed_body", app = app, error = error)
+        ));
+    }
+    result
+}
@@ -10,9 +10,37 @@ use crate::{
     winevent::WinEvent,
 };
 
-use super::{SeelenWeg, TASKBAR_CLASS};
+use super::SeelenWeg;
 
 impl SeelenWeg {
+    pub fn wants_global_win_event(event: &WinEvent) -> bool {
+        matches!(
+            event,
+            WinEvent::ObjectShow
+                | WinEvent::ObjectCreate
+                | WinEvent::ObjectParentChange
+                | WinEvent::ObjectDestroy
+                | WinEvent::ObjectHide
+                | WinEvent::ObjectNameChange
+                | WinEvent::ObjectCloaked
+                | WinEvent::ObjectUncloaked
+                | WinEvent::SystemForeground
+                | WinEvent::ObjectFocus
+                | WinEvent::ObjectLocationChange
+        )
+    }
+
+    pub fn wants_individual_win_event(event: &WinEvent) -> bool {
+        matches!(
+            event,
+            WinEvent::SystemForeground
+                | WinEvent::ObjectFocus
+                | WinEvent::ObjectLocationChange
+                | WinEvent::SyntheticFullscreenStart(_)
+                | WinEvent::SyntheticFullscreenEnd(_)
+        )
+    }
+
     pub fn process_global_win_event(event: WinEvent, window: &Window) -> Result<()> {
         let origin = window.hwnd();
         match event {
@@ -36,6 +64,17 @@ impl SeelenWeg {
                     Self::remove_hwnd(origin);
                 }
             }
+            // UWP frames are cloaked by the app when it is closed or suspended but the frame is kept alive
+            WinEvent::ObjectCloaked => {
+                if Self::contains_app(origin) && window.is_ghost_frame() {
+                    Self::remove_hwnd(origin);
+                }
+            }
+            WinEvent::ObjectUncloaked => {
+                if !Self::contains_app(origin) && Self::should_be_added(origin) {
+                    Self::add_hwnd(origin)?;
+                }
+            }
             WinEvent::ObjectNameChange => {
                 if Self::contains_app(origin) {
                     Self::update_app(origin);
@@ -46,6 +85,12 @@ impl SeelenWeg {
             WinEvent::SystemForeground | WinEvent::ObjectFocus => {
                 Self::set_active_window(origin)?;
             }
+            // the window could be moved to other monitor
+            WinEvent::ObjectLocationChange => {
+                if Self::contains_app(origin) {
+                    Self::update_location(origin);
+                }
+            }
             _ => {}
         }
         Ok(())
@@ -67,7 +112,7 @@ impl SeelenWeg {
             }
             WinEvent::SyntheticFullscreenStart(event_data) => {
                 let monitor = WindowsApi::monitor_from_window(self.window.hwnd()?);
-                if monitor == event_data.monitor {
+                if monitor == event_data.monitor && event_data.policy().hide_overlays {
                     self.hide()?;
                 }
             }
@@ -86,17 +131,6 @@ impl SeelenWeg {
         match event {
             EVENT_OBJECT_SHOW | EVENT_OBJECT_CREATE => {
                 let class = WindowsApi::get_class(origin_hwnd)?;
-                let parent_class =
-                    WindowsApi::get_class(WindowsApi::get_parent(origin_hwnd)).unwrap_or_default();
-
-                if TASKBAR_CLASS
-                    .iter()
-                    .any(|t| t == &class || t == &parent_class)
-                {
-                    Self::hide_taskbar();
-                    return Ok(());
-                }
-
                 if class.eq("XamlExplorerHostIslandWindow")
                     && WindowsApi::get_window_text(origin_hwnd).is_empty()
                 {
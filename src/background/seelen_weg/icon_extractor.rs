@@ -17,15 +17,10 @@ use windows::Win32::{
 use std::arch::x86_64::{
     __m128i, _mm_loadu_si128, _mm_setr_epi8, _mm_shuffle_epi8, _mm_storeu_si128,
 };
-use std::ffi::OsStr;
-use std::io::BufRead;
 use std::os::windows::ffi::OsStrExt;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
 use crate::error_handler::Result;
-use crate::modules::uwp::UwpManager;
-use crate::state::application::FULL_STATE;
-use crate::windows_api::WindowsApi;
 
 /// Convert BGRA to RGBA
 ///
@@ -214,97 +209,3 @@ pub fn get_icon_from_file(path: &Path) -> Result<RgbaImage> {
         Ok(image)
     }
 }
-
-pub fn get_icon_from_url_file(path: &Path) -> Result<RgbaImage> {
-    let file = std::fs::File::open(path)?;
-    let reader = std::io::BufReader::new(file);
-
-    let mut path = None;
-    // in theory .url files are encoded in UTF-8 so we don't need to use OsString
-    for line in reader.lines() {
-        if let Some(stripped) = line?.strip_prefix("IconFile=") {
-            path = Some(PathBuf::from(stripped));
-            break;
-        }
-    }
-
-    let path = match path {
-        Some(icon_file) => icon_file,
-        None => return Err("Failed to get icon".into()),
-    };
-
-    get_icon_from_file(&path)
-}
-
-/// returns the path of the icon extracted from the executable or copied if is an UWP app.
-///
-/// If the icon already exists, it returns the path instead overriding, this is needed for allow user custom icons.
-pub fn extract_and_save_icon_from_file<T: AsRef<Path>>(path: T) -> Result<PathBuf> {
-    let path = path.as_ref();
-    if !path.exists() || path.is_dir() {
-        return Err("Path is not a file".into());
-    }
-
-    let state = FULL_STATE.load();
-    if let Some(icon) = state.get_icon_by_key(&path.to_string_lossy()) {
-        return Ok(icon);
-    }
-
-    let icon_filename = PathBuf::from(format!("{}.png", uuid::Uuid::new_v4()));
-    let icon_path = state
-        .icon_packs_folder()
-        .join("system")
-        .join(&icon_filename);
-
-    let file_name = path.file_name().ok_or("Failed to get file name")?;
-    let ext = path.extension();
-
-    log::trace!(
-        "Extracting icon for \"{}\"",
-        file_name.to_string_lossy().to_string()
-    );
-
-    // try get icons for URLs
-    if ext == Some(OsStr::new("url")) {
-        let icon = get_icon_from_url_file(path)?;
-        icon.save(&icon_path)?;
-        state.push_and_save_system_icon(path.to_string_lossy().as_ref(), &icon_filename)?;
-        return Ok(icon_path);
-    }
-
-    // try get the icon directly from the file
-    if let Ok(icon) = get_icon_from_file(path) {
-        icon.save(&icon_path)?;
-        state.push_and_save_system_icon(path.to_string_lossy().as_ref(), &icon_filename)?;
-        return Ok(icon_path);
-    }
-
-    // if the lnk don't have an icon, try to extract it from the target
-    if ext == Some(OsStr::new("lnk")) {
-        let (target, _) = WindowsApi::resolve_lnk_target(path)?;
-        return extract_and_save_icon_from_file(&target);
-    }
-
-    Err("Failed to extract icon".into())
-}
-
-/// returns the path of the icon extracted from the app with the specified user model id.
-pub fn extract_and_save_icon_umid<T: AsRef<str>>(app_umid: T) -> Result<PathBuf> {
-    let app_umid = app_umid.as_ref();
-
-    let state = FULL_STATE.load();
-    if let Some(icon) = state.get_icon_by_key(app_umid) {
-        return Ok(icon);
-    }
-
-    let app_icon = UwpManager::get_high_quality_icon_path(app_umid)?;
-
-    let relative_path = PathBuf::from(format!("{}.png", uuid::Uuid::new_v4()));
-    let image_path = state
-        .icon_packs_folder()
-        .join("system")
-        .join(&relative_path);
-    std::fs::copy(app_icon, &image_path)?;
-    state.push_and_save_system_icon(app_umid, &relative_path)?;
-    Ok(image_path)
-}
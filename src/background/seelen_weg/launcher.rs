@@ -0,0 +1,91 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use seelen_core::state::PinnedWegItemData;
+use tauri_plugin_shell::ShellExt;
+
+use crate::{error_handler::Result, seelen::get_app_handle, windows_api::WindowsApi};
+
+/// Starts the programs, files and folders of the weg items with their launch options
+pub struct WegItemLauncher;
+
+impl WegItemLauncher {
+    fn is_lnk(path: &Path) -> bool {
+        path.extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("lnk"))
+    }
+
+    /// Explorer is used so the programs don't inherit the elevation of Seelen
+    fn open_with_explorer(target: &str) -> Result<()> {
+        get_app_handle()
+            .shell()
+            .command("cmd")
+            .args(["/c", "explorer", target])
+            .spawn()?;
+        Ok(())
+    }
+
+    /// Explorer can't pass arguments, so a shortcut with them is created and opened instead
+    fn temp_lnk(data: &PinnedWegItemData) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        data.execution_command.hash(&mut hasher);
+        std::env::temp_dir()
+            .join("seelen-weg-launch")
+            .join(format!("{:x}.lnk", hasher.finish()))
+    }
+
+    pub fn launch(data: &PinnedWegItemData) -> Result<()> {
+        let is_app_id = data.execution_command.starts_with("shell:");
+        if !is_app_id && !data.path.exists() {
+            return Err(format!("{} doesn't exist", data.path.display()).into());
+        }
+
+        let has_options = data.arguments.is_some() || data.working_dir.is_some();
+        let is_lnk = Self::is_lnk(&data.path);
+        let mut file = match is_lnk {
+            true => data.path.to_string_lossy().to_string(),
+            false => data.execution_command.clone(),
+        };
+        let mut arguments = data.arguments.clone();
+
+        // the link would override the options, so its target is used directly
+        if is_lnk && has_options {
+            let (program, link_arguments) = WindowsApi::resolve_lnk_target(&data.path)?;
+            file = program.to_string_lossy().to_string();
+            if arguments.is_none() && !link_arguments.is_empty() {
+                arguments = Some(link_arguments.to_string_lossy().to_string());
+            }
+        }
+
+        if data.run_as_admin {
+            return WindowsApi::run_as_admin(
+                &file,
+                arguments.as_deref(),
+                data.working_dir.as_deref(),
+            );
+        }
+
+        if !has_options {
+            return Self::open_with_explorer(&file);
+        }
+
+        if is_app_id {
+            return Err("Arguments and working directory are not supported by store apps".into());
+        }
+
+        let lnk = Self::temp_lnk(data);
+        if let Some(parent) = lnk.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        WindowsApi::create_lnk(
+            &lnk,
+            Path::new(&file),
+            arguments.as_deref(),
+            data.working_dir.as_deref(),
+        )?;
+        Self::open_with_explorer(&lnk.to_string_lossy())
+    }
+}
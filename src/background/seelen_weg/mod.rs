@@ -2,17 +2,18 @@ pub mod cli;
 pub mod handler;
 pub mod hook;
 pub mod icon_extractor;
+pub mod launcher;
+pub mod native_pins;
 
-use std::{collections::HashMap, path::PathBuf, thread::JoinHandle};
+use std::path::PathBuf;
 
 use getset::{Getters, MutGetters};
-use icon_extractor::{extract_and_save_icon_from_file, extract_and_save_icon_umid};
 use image::{DynamicImage, RgbaImage};
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
 use seelen_core::{
     handlers::SeelenEvent,
-    state::{AppExtraFlag, HideMode, SeelenWegSide},
+    state::{HideMode, SeelenWegSide},
 };
 use serde::Serialize;
 use tauri::{Emitter, Listener, WebviewWindow, Wry};
@@ -20,34 +21,30 @@ use win_screenshot::capture::capture_window;
 use windows::Win32::{
     Foundation::{HWND, RECT},
     Graphics::Gdi::HMONITOR,
-    UI::WindowsAndMessaging::{
-        SWP_NOACTIVATE, SW_HIDE, SW_SHOWNOACTIVATE, SW_SHOWNORMAL, WS_EX_APPWINDOW,
-        WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW,
-    },
+    UI::WindowsAndMessaging::{SWP_NOACTIVATE, SW_HIDE, SW_SHOWNOACTIVATE},
 };
 
 use crate::{
     error_handler::Result,
     log_error,
+    modules::{
+        event_registry::EventRegistry, icon_cache::IconCache, perf::PerfMonitor,
+        resource_governor::ResourceGovernor, supervisor::WebviewState,
+        virtual_desk::get_vd_manager, window_filter::WindowFilter, window_tags::WindowTags,
+    },
     seelen::get_app_handle,
     seelen_bar::FancyToolbar,
+    seelen_wm_v2::instance::WindowManagerV2,
     state::application::FULL_STATE,
     trace_lock,
     utils::{
         are_overlaped,
         constants::{Icons, NATIVE_UI_POPUP_CLASSES, OVERLAP_BLACK_LIST_BY_EXE},
-        sleep_millis,
     },
-    windows_api::{window::Window, AppBarData, AppBarDataState, WindowEnumerator, WindowsApi},
+    windows_api::{monitor::Monitor, window::Window, AppBarData, WindowEnumerator, WindowsApi},
 };
 
 lazy_static! {
-    static ref TITLE_BLACK_LIST: Vec<&'static str> = Vec::from([
-        "",
-        "Task Switching",
-        "DesktopWindowXamlSource",
-        "Program Manager",
-    ]);
     static ref OPEN_APPS: Mutex<Vec<SeelenWegApp>> = Mutex::new(Vec::new());
 }
 
@@ -59,6 +56,25 @@ pub struct SeelenWegApp {
     icon_path: PathBuf,
     execution_path: String,
     creator_hwnd: isize,
+    /// tags of the app, see `WindowTags`
+    tags: Vec<String>,
+    /// id of the monitor where the window is, see `Monitor::id`
+    monitor: Option<String>,
+    /// id of the workspace of the window, `None` if unknown
+    workspace: Option<String>,
+}
+
+impl SeelenWegApp {
+    /// Updates the monitor and workspace of the window, returns true if any changed
+    fn locate(&mut self) -> bool {
+        let window = Window::from(self.hwnd);
+        let monitor = window.monitor().id().ok();
+        let workspace = window.workspace().ok().map(|workspace| workspace.id());
+        let changed = self.monitor != monitor || self.workspace != workspace;
+        self.monitor = monitor;
+        self.workspace = workspace;
+        changed
+    }
 }
 
 #[derive(Getters, MutGetters)]
@@ -75,6 +91,7 @@ impl Drop for SeelenWeg {
         if let Ok(hwnd) = self.window.hwnd() {
             AppBarData::from_handle(hwnd).unregister_bar();
         }
+        ResourceGovernor::forget(&self.window);
         log_error!(self.window.destroy());
     }
 }
@@ -82,9 +99,8 @@ impl Drop for SeelenWeg {
 // SINGLETON
 impl SeelenWeg {
     pub fn set_active_window(hwnd: HWND) -> Result<()> {
-        let handle = get_app_handle();
-        handle.emit(SeelenEvent::WegSetFocusedHandle, hwnd.0 as isize)?;
-        handle.emit(
+        EventRegistry::emit(SeelenEvent::WegSetFocusedHandle, hwnd.0 as isize)?;
+        EventRegistry::emit(
             SeelenEvent::WegSetFocusedExecutable,
             WindowsApi::exe(hwnd).unwrap_or_default(),
         )?;
@@ -103,13 +119,61 @@ impl SeelenWeg {
         let mut apps = trace_lock!(OPEN_APPS);
         let app = apps.iter_mut().find(|app| app.hwnd == addr);
         if let Some(app) = app {
-            app.title = WindowsApi::get_window_text(hwnd);
-            get_app_handle()
-                .emit(SeelenEvent::WegUpdateOpenAppInfo, app.clone())
+            let window = Window::from(hwnd);
+            app.title = window.display_title();
+            app.tags = WindowTags::tags_of(&window);
+            app.locate();
+            EventRegistry::emit(SeelenEvent::WegUpdateOpenAppInfo, app.clone())
                 .expect("Failed to emit");
         }
     }
 
+    /// Should be called when a window is moved to other monitor or workspace,
+    /// only emits if the location of the window changed
+    pub fn update_location(hwnd: HWND) {
+        let addr = hwnd.0 as isize;
+        let mut apps = trace_lock!(OPEN_APPS);
+        if let Some(app) = apps.iter_mut().find(|app| app.hwnd == addr) {
+            if app.locate() {
+                log_error!(EventRegistry::emit(
+                    SeelenEvent::WegUpdateOpenAppInfo,
+                    app.clone()
+                ));
+            }
+        }
+    }
+
+    /// Moves the windows to the monitor and the current workspace, so the windows of an app
+    /// spread across monitors are brought together with a single click.
+    pub fn gather_windows(handles: &[isize], monitor: &Monitor) -> Result<()> {
+        let vd = get_vd_manager();
+        let current = vd.get_current()?.id();
+        let current_idx = vd.get_current_idx()?;
+        for &addr in handles {
+            let window = Window::from(addr);
+            if !window.is_window() {
+                continue;
+            }
+            let on_other_workspace = window
+                .workspace()
+                .is_ok_and(|workspace| workspace.id() != current);
+            if on_other_workspace && !vd.is_pinned_window(addr)? {
+                log_error!(vd.send_to(current_idx, addr));
+            }
+            log_error!(WindowManagerV2::send_to_monitor(&window, *monitor));
+            Self::update_location(window.hwnd());
+        }
+        Ok(())
+    }
+
+    /// Should be called when the title rules change
+    pub fn update_all_apps() {
+        let handles: Vec<isize> = trace_lock!(OPEN_APPS).iter().map(|app| app.hwnd).collect();
+        for hwnd in handles {
+            Self::update_app(HWND(hwnd as _));
+        }
+    }
+
     pub fn enumerate_all_windows() -> Result<()> {
         WindowEnumerator::new().for_each(|hwnd| {
             if Self::should_be_added(hwnd) {
@@ -118,6 +182,22 @@ impl SeelenWeg {
         })
     }
 
+    /// Removes the apps whose windows were destroyed or are no longer valid and adds the missing ones
+    pub fn refresh_open_apps() -> Result<()> {
+        let apps = trace_lock!(OPEN_APPS).clone();
+        for app in apps {
+            let hwnd = HWND(app.hwnd as _);
+            let creator = HWND(app.creator_hwnd as _);
+            if !WindowsApi::is_window(hwnd)
+                || !WindowsApi::is_window(creator)
+                || !Self::should_be_added(hwnd)
+            {
+                Self::remove_hwnd(hwnd);
+            }
+        }
+        Self::enumerate_all_windows()
+    }
+
     pub fn add_hwnd(hwnd: HWND) -> Result<()> {
         if Self::contains_app(hwnd) {
             return Ok(());
@@ -133,30 +213,38 @@ impl SeelenWeg {
         let program_path = creator.exe()?;
         let mut app = SeelenWegApp {
             hwnd: hwnd.0 as isize,
-            title: creator.title(),
+            title: creator.display_title(),
             exe: program_path.clone(),
             execution_path: program_path.to_string_lossy().to_string(),
             icon_path: Default::default(),
             creator_hwnd: creator.hwnd().0 as isize,
+            tags: WindowTags::tags_of(&window),
+            monitor: None,
+            workspace: None,
         };
+        app.locate();
+
+        // the frame has the package identity of the hosted app so both are grouped as the same app
+        let umid = match window.is_frame().unwrap_or(false) {
+            true => window.app_user_model_id(),
+            false => None,
+        }
+        .or_else(|| creator.app_user_model_id());
 
-        if let Some(umid) = creator.app_user_model_id() {
+        if let Some(umid) = umid {
             if umid.contains("!") {
                 app.execution_path = format!("shell:AppsFolder\\{umid}");
-                app.icon_path =
-                    extract_and_save_icon_umid(&umid).unwrap_or_else(|_| Icons::missing_app())
+                app.icon_path = IconCache::of_umid(&umid).unwrap_or_else(|_| Icons::missing_app())
             } else {
-                app.icon_path = extract_and_save_icon_from_file(program_path)
-                    .unwrap_or_else(|_| Icons::missing_app());
+                app.icon_path =
+                    IconCache::of_file(program_path).unwrap_or_else(|_| Icons::missing_app());
             }
         } else {
-            app.icon_path = extract_and_save_icon_from_file(program_path)
-                .unwrap_or_else(|_| Icons::missing_app());
+            app.icon_path =
+                IconCache::of_file(program_path).unwrap_or_else(|_| Icons::missing_app());
         }
 
-        get_app_handle()
-            .emit(SeelenEvent::WegAddOpenApp, app.clone())
-            .expect("Failed to emit");
+        EventRegistry::emit(SeelenEvent::WegAddOpenApp, app.clone()).expect("Failed to emit");
 
         trace_lock!(OPEN_APPS).push(app);
         Ok(())
@@ -165,58 +253,11 @@ impl SeelenWeg {
     pub fn remove_hwnd(hwnd: HWND) {
         let addr = hwnd.0 as isize;
         trace_lock!(OPEN_APPS).retain(|app| app.hwnd != addr);
-        get_app_handle()
-            .emit(SeelenEvent::WegRemoveOpenApp, addr)
-            .expect("Failed to emit");
+        EventRegistry::emit(SeelenEvent::WegRemoveOpenApp, addr).expect("Failed to emit");
     }
 
     pub fn should_be_added(hwnd: HWND) -> bool {
-        let window = Window::from(hwnd);
-        let path = match window.process().program_path() {
-            Ok(path) => path,
-            Err(_) => return false,
-        };
-
-        if path.starts_with("C:\\Windows\\SystemApps")
-            || !window.is_visible()
-            || window.parent().is_some()
-            || window.is_seelen_overlay()
-        {
-            return false;
-        }
-
-        // this class is used for edge tabs to be shown as independent windows on alt + tab
-        // this only applies when the new tab is created it is binded to explorer.exe for some reason
-        // maybe we can search/learn more about edge tabs later.
-        if window.class() == "Windows.Internal.Shell.TabProxyWindow" {
-            return false;
-        }
-
-        let ex_style = WindowsApi::get_ex_styles(hwnd);
-        if (ex_style.contains(WS_EX_TOOLWINDOW) || ex_style.contains(WS_EX_NOACTIVATE))
-            && !ex_style.contains(WS_EX_APPWINDOW)
-        {
-            return false;
-        }
-
-        if let Ok(frame_creator) = window.get_frame_creator() {
-            if frame_creator.is_none() {
-                return false;
-            }
-        }
-
-        if WindowsApi::window_is_uwp_suspended(hwnd).unwrap_or_default() {
-            return false;
-        }
-
-        if let Some(config) = FULL_STATE.load().get_app_config_by_window(hwnd) {
-            if config.options.contains(&AppExtraFlag::Hidden) {
-                log::trace!("Skipping by config: {:?}", window);
-                return false;
-            }
-        }
-
-        !TITLE_BLACK_LIST.contains(&window.title().as_str())
+        WindowFilter::interactable(&Window::from(hwnd), false).included
     }
 
     pub fn capture_window(hwnd: HWND) -> Option<DynamicImage> {
@@ -240,7 +281,12 @@ impl SeelenWeg {
         Ok(weg)
     }
 
+    pub fn window(&self) -> &WebviewWindow {
+        &self.window
+    }
+
     fn emit<S: Serialize + Clone>(&self, event: &str, payload: S) -> Result<()> {
+        PerfMonitor::record_emit(event);
         self.window.emit_to(self.window.label(), event, payload)?;
         Ok(())
     }
@@ -272,6 +318,7 @@ impl SeelenWeg {
 
     pub fn hide(&mut self) -> Result<()> {
         WindowsApi::show_window_async(self.window.hwnd()?, SW_HIDE)?;
+        ResourceGovernor::on_visibility_change(&self.window, false)?;
         self.window.emit_to(
             self.window.label(),
             SeelenEvent::HandleLayeredHitboxes,
@@ -281,6 +328,7 @@ impl SeelenWeg {
     }
 
     pub fn show(&mut self) -> Result<()> {
+        ResourceGovernor::on_visibility_change(&self.window, true)?;
         WindowsApi::show_window_async(self.window.hwnd()?, SW_SHOWNOACTIVATE)?;
         self.window.emit_to(
             self.window.label(),
@@ -290,6 +338,23 @@ impl SeelenWeg {
         Ok(())
     }
 
+    pub fn webview_state(&self) -> WebviewState {
+        WebviewState {
+            visible: self.window.is_visible().unwrap_or(true),
+            overlaped: self.overlaped,
+        }
+    }
+
+    /// Applies the state of a previous webview, called once the new one is loaded
+    pub fn restore_webview_state(&mut self, state: WebviewState) -> Result<()> {
+        self.overlaped = state.overlaped;
+        self.emit(SeelenEvent::WegOverlaped, self.overlaped)?;
+        if !state.visible {
+            self.hide()?;
+        }
+        Ok(())
+    }
+
     pub fn set_position(&mut self, monitor: HMONITOR) -> Result<()> {
         let rc_work = FancyToolbar::get_work_area_by_monitor(monitor)?;
         let hwnd = HWND(self.window.hwnd()?.0);
@@ -371,54 +436,4 @@ impl SeelenWeg {
         });
         Ok(window)
     }
-
-    pub fn hide_taskbar() -> JoinHandle<()> {
-        std::thread::spawn(move || match get_taskbars_handles() {
-            Ok(handles) => {
-                let mut attempts = 0;
-                while attempts < 10 && FULL_STATE.load().is_weg_enabled() {
-                    for handle in &handles {
-                        let app_bar = AppBarData::from_handle(*handle);
-                        trace_lock!(TASKBAR_STATE_ON_INIT)
-                            .insert(handle.0 as isize, app_bar.state());
-                        app_bar.set_state(AppBarDataState::AutoHide);
-                        let _ = WindowsApi::show_window(*handle, SW_HIDE);
-                    }
-                    attempts += 1;
-                    sleep_millis(50);
-                }
-            }
-            Err(err) => log::error!("Failed to get taskbars handles: {:?}", err),
-        })
-    }
-
-    pub fn restore_taskbar() -> Result<()> {
-        for hwnd in get_taskbars_handles()? {
-            AppBarData::from_handle(hwnd).set_state(
-                *trace_lock!(TASKBAR_STATE_ON_INIT)
-                    .get(&(hwnd.0 as isize))
-                    .unwrap_or(&AppBarDataState::AlwaysOnTop),
-            );
-            WindowsApi::show_window(hwnd, SW_SHOWNORMAL)?;
-        }
-        Ok(())
-    }
-}
-
-lazy_static! {
-    pub static ref TASKBAR_STATE_ON_INIT: Mutex<HashMap<isize, AppBarDataState>> =
-        Mutex::new(HashMap::new());
-    pub static ref TASKBAR_CLASS: Vec<&'static str> =
-        Vec::from(["Shell_TrayWnd", "Shell_SecondaryTrayWnd",]);
-}
-
-pub fn get_taskbars_handles() -> Result<Vec<HWND>> {
-    let mut founds = Vec::new();
-    WindowEnumerator::new().for_each(|hwnd| {
-        let class = WindowsApi::get_class(hwnd).unwrap_or_default();
-        if TASKBAR_CLASS.contains(&class.as_str()) {
-            founds.push(hwnd);
-        }
-    })?;
-    Ok(founds)
 }
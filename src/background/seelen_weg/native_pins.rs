@@ -0,0 +1,97 @@
+use std::path::PathBuf;
+
+use itertools::Itertools;
+use seelen_core::state::{PinnedWegItemData, WegItem};
+use tauri::Manager;
+use winreg::{enums::HKEY_CURRENT_USER, RegKey};
+
+use crate::{error_handler::Result, modules::shortcuts::ShortcutResolver, seelen::get_app_handle};
+
+const TASKBAND_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Explorer\Taskband";
+
+/// Apps pinned to the native taskbar, used to start the dock with the same apps
+pub struct NativeTaskbarPins;
+
+impl NativeTaskbarPins {
+    fn folder() -> Result<PathBuf> {
+        Ok(get_app_handle()
+            .path()
+            .data_dir()?
+            .join(r"Microsoft\Internet Explorer\Quick Launch\User Pinned\TaskBar"))
+    }
+
+    /// `Favorites` is an undocumented list of shell items in the order of the taskbar,
+    /// the only stable part on it are the names of the shortcuts in UTF-16
+    fn order_blob() -> Vec<u8> {
+        RegKey::predef(HKEY_CURRENT_USER)
+            .open_subkey(TASKBAND_KEY)
+            .and_then(|key| key.get_raw_value("Favorites"))
+            .map(|value| value.bytes)
+            .unwrap_or_default()
+    }
+
+    fn position_on(blob: &[u8], name: &str) -> Option<usize> {
+        let needle = name
+            .encode_utf16()
+            .flat_map(|c| c.to_le_bytes())
+            .collect_vec();
+        if needle.is_empty() {
+            return None;
+        }
+        blob.windows(needle.len()).position(|w| w == needle)
+    }
+
+    /// Shortcuts on the pinned folder in the order they are shown on the taskbar,
+    /// shortcuts not found on the registry are added at the end.
+    pub fn read() -> Result<Vec<WegItem>> {
+        let folder = Self::folder()?;
+        if !folder.exists() {
+            return Ok(Vec::new());
+        }
+
+        let blob = Self::order_blob();
+        let shortcuts = std::fs::read_dir(folder)?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| ShortcutResolver::is_shortcut(path))
+            .map(|path| {
+                let name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                (
+                    Self::position_on(&blob, &name).unwrap_or(usize::MAX),
+                    name,
+                    path,
+                )
+            })
+            .sorted()
+            .collect_vec();
+
+        let mut items = Vec::new();
+        for (_, _, path) in shortcuts {
+            // same as pinning a shortcut from the dock, the shortcut keeps arguments and icon
+            let target = match ShortcutResolver::resolve(&path) {
+                Ok(shortcut) => shortcut.target,
+                Err(err) => {
+                    log::warn!("Skipping taskbar pin {}: {:?}", path.display(), err);
+                    continue;
+                }
+            };
+            let Some(target) = target else {
+                continue;
+            };
+            items.push(WegItem::Pinned(PinnedWegItemData {
+                is_dir: target.is_dir(),
+                execution_command: target.to_string_lossy().to_string(),
+                path,
+                orphaned: false,
+                arguments: None,
+                working_dir: None,
+                run_as_admin: false,
+                new_instance: false,
+            }));
+        }
+        Ok(items)
+    }
+}
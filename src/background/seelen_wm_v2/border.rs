@@ -0,0 +1,628 @@
+use std::{
+    ffi::c_void,
+    sync::atomic::{AtomicIsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::state::Border;
+use windows::{
+    core::w,
+    Win32::{
+        Foundation::{COLORREF, HANDLE, HWND, LPARAM, LRESULT, POINT, RECT, SIZE, WPARAM},
+        Graphics::Gdi::{
+            CreateCompatibleDC, CreateDIBSection, DeleteDC, DeleteObject, GetDC, ReleaseDC,
+            SelectObject, AC_SRC_ALPHA, AC_SRC_OVER, BITMAPINFO, BITMAPINFOHEADER, BI_RGB,
+            BLENDFUNCTION, DIB_RGB_COLORS,
+        },
+        UI::WindowsAndMessaging::{
+            CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, KillTimer,
+            PostMessageW, RegisterClassW, SetTimer, SetWindowPos, ShowWindow, TranslateMessage,
+            UpdateLayeredWindow, EVENT_OBJECT_CLOAKED, EVENT_OBJECT_DESTROY, EVENT_OBJECT_HIDE,
+            EVENT_OBJECT_LOCATIONCHANGE, EVENT_OBJECT_SHOW, EVENT_OBJECT_UNCLOAKED,
+            EVENT_SYSTEM_MINIMIZEEND, EVENT_SYSTEM_MINIMIZESTART, HWND_TOP, MSG, SWP_NOACTIVATE,
+            SWP_NOSIZE, SWP_SHOWWINDOW, SW_HIDE, ULW_ALPHA, WM_APP, WM_TIMER, WNDCLASSW,
+            WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_EX_TRANSPARENT, WS_POPUP,
+        },
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::{power::profile::PowerProfile, system_settings::application::SYSTEM_SETTINGS},
+    state::application::FULL_STATE,
+    trace_lock,
+    utils::spawn_named_thread,
+    windows_api::{window::Window, WindowsApi},
+};
+
+use super::geometry::DEFAULT_DPI;
+
+/// wparam is 1 if the change should be animated
+const WM_UPDATE_BORDER: u32 = WM_APP + 1;
+const ANIMATION_TIMER_ID: usize = 1;
+/// ~60fps
+const ANIMATION_FRAME_INTERVAL: u32 = 16;
+/// used if the color is invalid and the system accent color can't be read
+const FALLBACK_COLOR: [u8; 4] = [0, 120, 215, 255];
+/// magenta, to not be confused with the focus border
+const FLASH_COLOR: [u8; 4] = [255, 0, 255, 255];
+/// in css pixels
+const FLASH_THICKNESS: f64 = 4.0;
+
+/// overlay window handle, 0 until the border thread creates it
+static BORDER_HWND: AtomicIsize = AtomicIsize::new(0);
+/// window being highlighted, read on every raw event so it lives outside of the state mutex
+static TRACKED: AtomicIsize = AtomicIsize::new(0);
+
+lazy_static! {
+    static ref STATE: Mutex<BorderState> = Mutex::new(BorderState::default());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Frame {
+    rect: RECT,
+    thickness: i32,
+    radius: i32,
+    color: [u8; 4],
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Animation {
+    from: RECT,
+    started: Instant,
+}
+
+/// Temporary highlight of a window, shown even if the border is disabled
+#[derive(Debug, Clone, Copy)]
+struct Flash {
+    window: isize,
+    until: Instant,
+}
+
+#[derive(Debug, Default)]
+struct BorderState {
+    enabled: bool,
+    config: Border,
+    /// system accent color, used when the border has no custom color
+    accent: Option<[u8; 4]>,
+    /// hidden while a fullscreen app is focused
+    suspended: bool,
+    /// frame currently on screen, `None` if the overlay is hidden
+    drawn: Option<Frame>,
+    animation: Option<Animation>,
+    flash: Option<Flash>,
+}
+
+impl BorderState {
+    fn color(&self) -> [u8; 4] {
+        self.config
+            .color
+            .as_deref()
+            .and_then(parse_hex_color)
+            .or(self.accent)
+            .unwrap_or(FALLBACK_COLOR)
+    }
+
+    /// Frame that should be drawn around the tracked window, `None` if the border should be hidden
+    fn target_frame(&self) -> Result<Option<Frame>> {
+        if let Some(flash) = self.flash.filter(|flash| flash.until > Instant::now()) {
+            return Self::flash_frame(flash.window);
+        }
+
+        let tracked = TRACKED.load(Ordering::Acquire);
+        if !self.enabled || self.suspended || tracked == 0 || self.config.width <= 0.0 {
+            return Ok(None);
+        }
+
+        let window = Window::from(tracked);
+        if !WindowsApi::is_window(window.hwnd())
+            || !window.is_visible()
+            || window.is_minimized()
+            || window.is_maximized()
+            || window.is_cloaked()
+        {
+            return Ok(None);
+        }
+
+        // sizes are in css pixels as the layout of the window manager
+        let dpi = WindowsApi::get_monitor_dpi(WindowsApi::monitor_from_window(window.hwnd()))?;
+        let scale = |value: f64| (value * dpi as f64 / DEFAULT_DPI as f64).round() as i32;
+        let thickness = scale(self.config.width).max(1);
+        let spread = thickness + scale(self.config.offset);
+
+        let rect = WindowsApi::get_inner_window_rect(window.hwnd())?;
+        Ok(Some(Frame {
+            rect: RECT {
+                left: rect.left - spread,
+                top: rect.top - spread,
+                right: rect.right + spread,
+                bottom: rect.bottom + spread,
+            },
+            thickness,
+            radius: scale(self.config.radius),
+            color: self.color(),
+        }))
+    }
+
+    fn flash_frame(window: isize) -> Result<Option<Frame>> {
+        let hwnd = Window::from(window).hwnd();
+        if !WindowsApi::is_window(hwnd) {
+            return Ok(None);
+        }
+        let dpi = WindowsApi::get_monitor_dpi(WindowsApi::monitor_from_window(hwnd))?;
+        let thickness = (FLASH_THICKNESS * dpi as f64 / DEFAULT_DPI as f64).round() as i32;
+        let rect = WindowsApi::get_inner_window_rect(hwnd)?;
+        Ok(Some(Frame {
+            rect: RECT {
+                left: rect.left - thickness,
+                top: rect.top - thickness,
+                right: rect.right + thickness,
+                bottom: rect.bottom + thickness,
+            },
+            thickness,
+            radius: 0,
+            color: FLASH_COLOR,
+        }))
+    }
+
+    unsafe fn hide(&mut self, border: HWND) {
+        self.animation = None;
+        let _ = KillTimer(border, ANIMATION_TIMER_ID);
+        if self.drawn.take().is_some() {
+            let _ = ShowWindow(border, SW_HIDE);
+        }
+    }
+
+    unsafe fn draw(&mut self, border: HWND, frame: Frame) -> Result<()> {
+        let needs_repaint = self.drawn.map_or(true, |drawn| {
+            let size = |r: &RECT| (r.right - r.left, r.bottom - r.top);
+            size(&drawn.rect) != size(&frame.rect)
+                || drawn.thickness != frame.thickness
+                || drawn.radius != frame.radius
+                || drawn.color != frame.color
+        });
+        if needs_repaint {
+            paint(border, &frame)?;
+        }
+        // keep it over the focused window but below the topmost ones
+        SetWindowPos(
+            border,
+            HWND_TOP,
+            frame.rect.left,
+            frame.rect.top,
+            0,
+            0,
+            SWP_NOSIZE | SWP_NOACTIVATE | SWP_SHOWWINDOW,
+        )?;
+        self.drawn = Some(frame);
+        Ok(())
+    }
+}
+
+/// Native overlay that highlights the focused window managed by the window manager
+pub struct FocusBorder;
+
+impl FocusBorder {
+    unsafe extern "system" fn window_proc(
+        window: HWND,
+        message: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        match message {
+            WM_UPDATE_BORDER => {
+                log_error!(Self::on_update(window, wparam.0 == 1));
+                LRESULT(0)
+            }
+            WM_TIMER if wparam.0 == ANIMATION_TIMER_ID => {
+                log_error!(Self::on_animation_frame(window));
+                LRESULT(0)
+            }
+            _ => DefWindowProcW(window, message, wparam, lparam),
+        }
+    }
+
+    unsafe fn on_update(border: HWND, animate: bool) -> Result<()> {
+        let mut state = trace_lock!(STATE);
+        let Some(target) = state.target_frame()? else {
+            state.hide(border);
+            return Ok(());
+        };
+
+        if animate && state.config.animation_duration > 0 && !PowerProfile::animations_disabled() {
+            if let Some(drawn) = state.drawn {
+                state.animation = Some(Animation {
+                    from: drawn.rect,
+                    started: Instant::now(),
+                });
+                SetTimer(border, ANIMATION_TIMER_ID, ANIMATION_FRAME_INTERVAL, None);
+                return Ok(());
+            }
+        }
+
+        // the running animation will reach the new location by itself
+        if state.animation.is_some() {
+            return Ok(());
+        }
+        state.draw(border, target)
+    }
+
+    unsafe fn on_animation_frame(border: HWND) -> Result<()> {
+        let mut state = trace_lock!(STATE);
+        let (Some(animation), Some(target)) = (state.animation, state.target_frame()?) else {
+            state.hide(border);
+            return Ok(());
+        };
+
+        let duration = Duration::from_millis(state.config.animation_duration);
+        let progress =
+            (animation.started.elapsed().as_secs_f32() / duration.as_secs_f32()).min(1.0);
+        if progress >= 1.0 {
+            state.animation = None;
+            let _ = KillTimer(border, ANIMATION_TIMER_ID);
+        }
+
+        // ease out cubic
+        let eased = 1.0 - (1.0 - progress).powi(3);
+        let frame = Frame {
+            rect: lerp_rect(&animation.from, &target.rect, eased),
+            ..target
+        };
+        state.draw(border, frame)
+    }
+
+    fn post_update(animate: bool) -> Result<()> {
+        let border = BORDER_HWND.load(Ordering::Acquire);
+        if border != 0 {
+            unsafe {
+                PostMessageW(
+                    HWND(border as _),
+                    WM_UPDATE_BORDER,
+                    WPARAM(animate as usize),
+                    LPARAM(0),
+                )?
+            };
+        }
+        Ok(())
+    }
+
+    /// Moves the border to the given window or hides it if `None`
+    pub fn track(window: Option<&Window>) -> Result<()> {
+        let address = window.map_or(0, |w| w.address());
+        let previous = TRACKED.swap(address, Ordering::AcqRel);
+        if previous != address {
+            Self::post_update(true)?;
+        }
+        Ok(())
+    }
+
+    pub fn untrack(window: &Window) -> Result<()> {
+        let address = window.address();
+        if TRACKED
+            .compare_exchange(address, 0, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+        {
+            Self::post_update(false)?;
+        }
+        Ok(())
+    }
+
+    /// Highlights the window for a while, used to identify the window being inspected
+    pub fn flash(window: &Window, duration: Duration) -> Result<()> {
+        trace_lock!(STATE).flash = Some(Flash {
+            window: window.address(),
+            until: Instant::now() + duration,
+        });
+        Self::post_update(false)?;
+        spawn_named_thread("Focus Border Flash", move || {
+            std::thread::sleep(duration);
+            log_error!(Self::post_update(false));
+        })?;
+        Ok(())
+    }
+
+    pub fn set_suspended(suspended: bool) -> Result<()> {
+        trace_lock!(STATE).suspended = suspended;
+        Self::post_update(false)
+    }
+
+    pub fn set_accent_color(accent: &str) {
+        trace_lock!(STATE).accent = parse_hex_color(accent);
+        log_error!(Self::post_update(false));
+    }
+
+    /// Reloads the border settings
+    pub fn refresh() -> Result<()> {
+        let full_state = FULL_STATE.load();
+        {
+            let mut state = trace_lock!(STATE);
+            state.enabled = full_state.is_window_manager_enabled()
+                && full_state.settings.window_manager.border.enabled;
+            state.config = full_state.settings.window_manager.border.clone();
+        }
+        Self::post_update(false)
+    }
+
+    pub fn process_raw_win_event(event: u32, origin: HWND) -> Result<()> {
+        let tracked = TRACKED.load(Ordering::Acquire);
+        if tracked == 0 || tracked != origin.0 as isize {
+            return Ok(());
+        }
+        match event {
+            EVENT_OBJECT_DESTROY => Self::untrack(&Window::from(origin))?,
+            EVENT_OBJECT_LOCATIONCHANGE
+            | EVENT_OBJECT_SHOW
+            | EVENT_OBJECT_HIDE
+            | EVENT_OBJECT_CLOAKED
+            | EVENT_OBJECT_UNCLOAKED
+            | EVENT_SYSTEM_MINIMIZESTART
+            | EVENT_SYSTEM_MINIMIZEEND => Self::post_update(false)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    unsafe fn create_border_window() -> Result<()> {
+        let wide_class = w!("SeelenFocusBorder");
+        let h_module = WindowsApi::module_handle_w()?;
+
+        let wnd_class = WNDCLASSW {
+            lpfnWndProc: Some(Self::window_proc),
+            hInstance: h_module.into(),
+            lpszClassName: wide_class,
+            ..Default::default()
+        };
+
+        RegisterClassW(&wnd_class);
+
+        // click through and never activated, so it is ignored by the hook consumers
+        let hwnd = CreateWindowExW(
+            WS_EX_LAYERED | WS_EX_TRANSPARENT | WS_EX_TOOLWINDOW | WS_EX_NOACTIVATE,
+            wide_class,
+            w!("Seelen Focus Border"),
+            WS_POPUP,
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            h_module,
+            None,
+        )?;
+        BORDER_HWND.store(hwnd.0 as isize, Ordering::Release);
+
+        if let Ok(colors) = trace_lock!(SYSTEM_SETTINGS).get_colors() {
+            trace_lock!(STATE).accent = parse_hex_color(&colors.accent);
+        }
+        Self::refresh()?;
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, hwnd, 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+        Ok(())
+    }
+
+    pub fn start() -> Result<()> {
+        spawn_named_thread("Focus Border", || unsafe {
+            log_error!(Self::create_border_window());
+        })?;
+        Ok(())
+    }
+}
+
+/// Uploads the frame to the layered window, this also moves and resizes it
+unsafe fn paint(border: HWND, frame: &Frame) -> Result<()> {
+    let width = frame.rect.right - frame.rect.left;
+    let height = frame.rect.bottom - frame.rect.top;
+    if width <= 0 || height <= 0 {
+        return Ok(());
+    }
+    let pixels = render_ring(width, height, frame.thickness, frame.radius, frame.color);
+
+    let screen_dc = GetDC(HWND::default());
+    let mem_dc = CreateCompatibleDC(screen_dc);
+    let info = BITMAPINFO {
+        bmiHeader: BITMAPINFOHEADER {
+            biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: width,
+            // negative height means top-down rows
+            biHeight: -height,
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let result = (|| -> Result<()> {
+        let mut bits: *mut c_void = std::ptr::null_mut();
+        let bitmap = CreateDIBSection(
+            mem_dc,
+            &info,
+            DIB_RGB_COLORS,
+            &mut bits,
+            HANDLE::default(),
+            0,
+        )?;
+        std::ptr::copy_nonoverlapping(pixels.as_ptr(), bits as *mut u32, pixels.len());
+
+        let previous = SelectObject(mem_dc, bitmap);
+        let blend = BLENDFUNCTION {
+            BlendOp: AC_SRC_OVER as u8,
+            BlendFlags: 0,
+            SourceConstantAlpha: 255,
+            AlphaFormat: AC_SRC_ALPHA as u8,
+        };
+        let position = POINT {
+            x: frame.rect.left,
+            y: frame.rect.top,
+        };
+        let size = SIZE {
+            cx: width,
+            cy: height,
+        };
+        let source = POINT::default();
+        let updated = UpdateLayeredWindow(
+            border,
+            screen_dc,
+            Some(&position as *const _),
+            Some(&size as *const _),
+            mem_dc,
+            Some(&source as *const _),
+            COLORREF(0),
+            Some(&blend as *const _),
+            ULW_ALPHA,
+        );
+        SelectObject(mem_dc, previous);
+        let _ = DeleteObject(bitmap);
+        updated?;
+        Ok(())
+    })();
+
+    let _ = DeleteDC(mem_dc);
+    ReleaseDC(HWND::default(), screen_dc);
+    result
+}
+
+fn lerp_rect(from: &RECT, to: &RECT, t: f32) -> RECT {
+    let lerp = |a: i32, b: i32| a + ((b - a) as f32 * t).round() as i32;
+    RECT {
+        left: lerp(from.left, to.left),
+        top: lerp(from.top, to.top),
+        right: lerp(from.right, to.right),
+        bottom: lerp(from.bottom, to.bottom),
+    }
+}
+
+/// Parses `#RRGGBB` and `#RRGGBBAA` colors into rgba
+pub fn parse_hex_color(value: &str) -> Option<[u8; 4]> {
+    let hex = value.trim().strip_prefix('#')?;
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    match hex.len() {
+        6 => Some([channel(0)?, channel(2)?, channel(4)?, 255]),
+        8 => Some([channel(0)?, channel(2)?, channel(4)?, channel(6)?]),
+        _ => None,
+    }
+}
+
+/// Signed distance from the point to a rounded rect centered at the origin
+fn rounded_rect_sdf(x: f32, y: f32, half_width: f32, half_height: f32, radius: f32) -> f32 {
+    let qx = x.abs() - half_width + radius;
+    let qy = y.abs() - half_height + radius;
+    qx.max(0.0).hypot(qy.max(0.0)) + qx.max(qy).min(0.0) - radius
+}
+
+/// Renders an antialiased rounded ring filling the surface, as premultiplied BGRA pixels
+fn render_ring(width: i32, height: i32, thickness: i32, radius: i32, color: [u8; 4]) -> Vec<u32> {
+    let (w, h) = (width.max(0) as usize, height.max(0) as usize);
+    let mut pixels = vec![0u32; w * h];
+    if w == 0 || h == 0 || thickness <= 0 {
+        return pixels;
+    }
+
+    let half_width = width as f32 / 2.0;
+    let half_height = height as f32 / 2.0;
+    let thickness_f = thickness as f32;
+    let outer_radius = (radius as f32).clamp(0.0, half_width.min(half_height));
+    let inner_radius = (outer_radius - thickness_f).max(0.0);
+    let inner_half_width = half_width - thickness_f;
+    let inner_half_height = half_height - thickness_f;
+
+    let coverage = |distance: f32| (0.5 - distance).clamp(0.0, 1.0);
+    let [r, g, b, a] = color;
+
+    // only the edges and the corners are painted, the middle of the surface stays transparent
+    let band = (outer_radius.ceil() as usize).max(thickness as usize) + 1;
+    for y in 0..h {
+        let is_edge_row = y < band || y + band >= h;
+        let (left_end, right_start) = match is_edge_row {
+            true => (w, w),
+            false => (band.min(w), w.saturating_sub(band).max(band.min(w))),
+        };
+        for x in (0..left_end).chain(right_start..w) {
+            let px = x as f32 + 0.5 - half_width;
+            let py = y as f32 + 0.5 - half_height;
+            let outer = coverage(rounded_rect_sdf(
+                px,
+                py,
+                half_width,
+                half_height,
+                outer_radius,
+            ));
+            let inner = if inner_half_width > 0.0 && inner_half_height > 0.0 {
+                coverage(rounded_rect_sdf(
+                    px,
+                    py,
+                    inner_half_width,
+                    inner_half_height,
+                    inner_radius,
+                ))
+            } else {
+                0.0
+            };
+
+            let alpha = (outer - inner).max(0.0) * a as f32 / 255.0;
+            if alpha > 0.0 {
+                let channel = |c: u8| (c as f32 * alpha).round() as u32;
+                pixels[y * w + x] = (((alpha * 255.0).round() as u32) << 24)
+                    | (channel(r) << 16)
+                    | (channel(g) << 8)
+                    | channel(b);
+            }
+        }
+    }
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(parse_hex_color("#FF8000"), Some([255, 128, 0, 255]));
+        assert_eq!(parse_hex_color("#ff800080"), Some([255, 128, 0, 128]));
+        assert_eq!(parse_hex_color(" #000000 "), Some([0, 0, 0, 255]));
+        assert_eq!(parse_hex_color("FF8000"), None);
+        assert_eq!(parse_hex_color("#FF80"), None);
+        assert_eq!(parse_hex_color("#GG8000"), None);
+    }
+
+    #[test]
+    fn test_render_ring() {
+        let (width, height) = (100, 60);
+        let pixels = render_ring(width, height, 4, 10, [255, 0, 0, 255]);
+        let at = |x: i32, y: i32| pixels[(y * width + x) as usize];
+
+        // middle of the edges are fully painted
+        assert_eq!(at(50, 0), 0xFFFF0000);
+        assert_eq!(at(0, 30), 0xFFFF0000);
+        assert_eq!(at(99, 30), 0xFFFF0000);
+        assert_eq!(at(50, 59), 0xFFFF0000);
+        // inside of the ring and outside of the rounded corners are transparent
+        assert_eq!(at(50, 30), 0);
+        assert_eq!(at(4, 30), 0);
+        assert_eq!(at(0, 0), 0);
+        assert_eq!(at(99, 59), 0);
+    }
+
+    #[test]
+    fn test_render_ring_without_radius() {
+        let (width, height) = (20, 20);
+        let pixels = render_ring(width, height, 2, 0, [0, 0, 255, 128]);
+        let at = |x: i32, y: i32| pixels[(y * width + x) as usize];
+
+        // color channels are premultiplied by the alpha
+        assert_eq!(at(0, 0), 0x80000080);
+        assert_eq!(at(1, 1), 0x80000080);
+        assert_eq!(at(2, 2), 0);
+        assert_eq!(at(10, 10), 0);
+    }
+}
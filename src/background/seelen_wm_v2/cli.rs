@@ -1,17 +1,17 @@
 use clap::{Command, ValueEnum};
-use seelen_core::handlers::SeelenEvent;
 use serde::{Deserialize, Serialize};
-use tauri::Emitter;
 
 use crate::error_handler::Result;
-use crate::seelen::{get_app_handle, SEELEN};
+use crate::modules::layout_direction::LayoutDirection;
+use crate::seelen::SEELEN;
 use crate::state::application::FULL_STATE;
+use crate::windows_api::monitor::Monitor;
 use crate::windows_api::window::Window;
 use crate::windows_api::WindowsApi;
 use crate::{get_subcommands, trace_lock};
 
+use super::history::WmCommand;
 use super::instance::WindowManagerV2;
-use super::state::WM_STATE;
 
 #[derive(Debug, Clone, Serialize, Deserialize, ValueEnum)]
 pub enum AllowedReservations {
@@ -32,6 +32,17 @@ pub enum AllowedFocus {
     Latest,
 }
 
+impl AllowedFocus {
+    /// Left and right swapped, used on right-to-left layouts
+    pub fn mirrored(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+            other => other,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
 pub enum Sizing {
     Increase,
@@ -67,6 +78,16 @@ get_subcommands![
     ResetWorkspaceSize,
     /** Focuses the window in the specified position. */
     Focus(side: AllowedFocus => "The position of the window to focus."),
+    /** Toggles the tiling of the focused window. */
+    ToggleFloat,
+    /** Moves the focused window to the monitor at the index (starting from 0). */
+    SendToMonitor(index: usize => "The index of the monitor."),
+    /** Moves the focused window to the monitor under the cursor. */
+    SendToCursor,
+    /** Reverts the last layout operation (toggle float, resize, send to workspace or monitor). */
+    Undo,
+    /** Applies again the last reverted layout operation. */
+    Redo,
 ];
 
 impl WindowManagerV2 {
@@ -122,13 +143,11 @@ impl WindowManagerV2 {
                     Sizing::Decrease => -FULL_STATE.load().settings.window_manager.resize_delta,
                 };
 
-                let state = trace_lock!(WM_STATE);
-                let (m, w) = state.update_size(&foreground, Axis::Horizontal, percentage, false)?;
-                get_app_handle().emit_to(
-                    format!("{}/{}", Self::TARGET, m.id),
-                    SeelenEvent::WMSetLayout,
-                    w.get_root_node(),
-                )?;
+                Self::execute(WmCommand::Resize {
+                    window: foreground.address(),
+                    changes: vec![(Axis::Horizontal, percentage)],
+                    relative: false,
+                })?;
             }
             SubCommand::Height(action) => {
                 let foreground = Window::from(WindowsApi::get_foreground_window());
@@ -137,20 +156,45 @@ impl WindowManagerV2 {
                     Sizing::Decrease => -FULL_STATE.load().settings.window_manager.resize_delta,
                 };
 
-                let state = trace_lock!(WM_STATE);
-                let (m, w) = state.update_size(&foreground, Axis::Vertical, percentage, false)?;
-                get_app_handle().emit_to(
-                    format!("{}/{}", Self::TARGET, m.id),
-                    SeelenEvent::WMSetLayout,
-                    w.get_root_node(),
-                )?;
+                Self::execute(WmCommand::Resize {
+                    window: foreground.address(),
+                    changes: vec![(Axis::Vertical, percentage)],
+                    relative: false,
+                })?;
             }
             SubCommand::ResetWorkspaceSize => {
                 // self.emit(SeelenEvent::WMResetWorkspaceSize, ())?;
             }
-            SubCommand::Focus(_side) => {
-                // self.emit(SeelenEvent::WMFocus, side)?;
+            SubCommand::Focus(side) => {
+                let side = match LayoutDirection::mirror_wm_directions() {
+                    true => side.mirrored(),
+                    false => side,
+                };
+                Self::focus(side)?;
+            }
+            SubCommand::ToggleFloat => {
+                let foreground = Window::from(WindowsApi::get_foreground_window());
+                Self::execute(WmCommand::ToggleFloat(foreground.address()))?;
+            }
+            SubCommand::SendToMonitor(index) => {
+                let foreground = Window::from(WindowsApi::get_foreground_window());
+                Self::execute(WmCommand::SendToMonitor {
+                    window: foreground.address(),
+                    from: foreground.monitor().index()?,
+                    to: index,
+                })?;
+            }
+            SubCommand::SendToCursor => {
+                let foreground = Window::from(WindowsApi::get_foreground_window());
+                let target = Monitor::from(WindowsApi::monitor_from_cursor_point());
+                Self::execute(WmCommand::SendToMonitor {
+                    window: foreground.address(),
+                    from: foreground.monitor().index()?,
+                    to: target.index()?,
+                })?;
             }
+            SubCommand::Undo => Self::undo()?,
+            SubCommand::Redo => Self::redo()?,
         };
         Ok(())
     }
@@ -0,0 +1,92 @@
+use std::collections::HashSet;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::state::WindowCorners;
+use windows::Win32::{
+    Foundation::HWND,
+    Graphics::Dwm::{
+        DWMWA_WINDOW_CORNER_PREFERENCE, DWMWCP_DEFAULT, DWMWCP_DONOTROUND, DWMWCP_ROUND,
+        DWMWCP_ROUNDSMALL, DWM_WINDOW_CORNER_PREFERENCE,
+    },
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::window_filter::WindowFilter,
+    state::application::FULL_STATE,
+    trace_lock,
+    windows_api::{window::Window, WindowsApi},
+};
+
+lazy_static! {
+    /// windows with a corner preference set by Seelen
+    static ref APPLIED: Mutex<HashSet<isize>> = Mutex::new(HashSet::new());
+}
+
+/// Corner preference of the windows, set by the app rules or the window manager settings
+pub struct WmCorners;
+
+impl WmCorners {
+    fn preference(corners: WindowCorners) -> DWM_WINDOW_CORNER_PREFERENCE {
+        match corners {
+            WindowCorners::Default => DWMWCP_DEFAULT,
+            WindowCorners::Round => DWMWCP_ROUND,
+            WindowCorners::RoundSmall => DWMWCP_ROUNDSMALL,
+            WindowCorners::Square => DWMWCP_DONOTROUND,
+        }
+    }
+
+    fn set(hwnd: HWND, corners: WindowCorners) -> Result<()> {
+        WindowsApi::dwm_set_window_attribute(
+            hwnd,
+            DWMWA_WINDOW_CORNER_PREFERENCE,
+            &Self::preference(corners),
+        )
+    }
+
+    /// Applies the corners of the app rule or the default of the tiled/floating windows
+    pub fn apply(window: &Window, tiled: bool) {
+        if !tiled && !WindowFilter::interactable(window, false).included {
+            return;
+        }
+
+        let state = FULL_STATE.load();
+        let defaults = &state.settings.window_manager.corners;
+        let corners = state
+            .get_app_config_by_window(window.hwnd())
+            .and_then(|config| config.corners)
+            .unwrap_or(match tiled {
+                true => defaults.tiled,
+                false => defaults.floating,
+            });
+
+        let address = window.address();
+        let mut applied = trace_lock!(APPLIED);
+        // windows never touched keep the preference chosen by the app
+        if corners == WindowCorners::Default && !applied.remove(&address) {
+            return;
+        }
+        if corners != WindowCorners::Default {
+            applied.insert(address);
+        }
+        // not supported before Windows 11
+        let _ = Self::set(window.hwnd(), corners);
+    }
+
+    pub fn forget(window: &Window) {
+        trace_lock!(APPLIED).remove(&window.address());
+    }
+
+    /// Gives back the control of the corners to the apps
+    pub fn restore_all() {
+        let applied = std::mem::take(&mut *trace_lock!(APPLIED));
+        for address in applied {
+            let hwnd = HWND(address as _);
+            if WindowsApi::is_window(hwnd) {
+                log_error!(Self::set(hwnd, WindowCorners::Default));
+            }
+        }
+    }
+}
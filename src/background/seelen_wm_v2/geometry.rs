@@ -0,0 +1,224 @@
+use windows::Win32::{Foundation::RECT, Graphics::Gdi::HMONITOR};
+
+use crate::{
+    error_handler::Result,
+    windows_api::{MonitorEnumerator, WindowsApi},
+};
+
+/// 96 is the default DPI value on Windows (100% scaling)
+pub const DEFAULT_DPI: u32 = 96;
+
+/// Monitor as used by the layout math, the rect is in physical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonitorGeometry {
+    pub handle: isize,
+    pub rect: RECT,
+    pub dpi: u32,
+}
+
+impl MonitorGeometry {
+    pub fn from_handle(hmonitor: HMONITOR) -> Result<Self> {
+        Ok(Self {
+            handle: hmonitor.0 as isize,
+            rect: WindowsApi::monitor_rect(hmonitor)?,
+            dpi: WindowsApi::get_monitor_dpi(hmonitor)?,
+        })
+    }
+
+    pub fn all() -> Result<Vec<Self>> {
+        MonitorEnumerator::get_all()?
+            .into_iter()
+            .map(Self::from_handle)
+            .collect()
+    }
+
+    pub fn scale_factor(&self) -> f32 {
+        self.dpi as f32 / DEFAULT_DPI as f32
+    }
+}
+
+fn intersection_area(a: &RECT, b: &RECT) -> i64 {
+    let width = a.right.min(b.right) - a.left.max(b.left);
+    let height = a.bottom.min(b.bottom) - a.top.max(b.top);
+    if width <= 0 || height <= 0 {
+        return 0;
+    }
+    width as i64 * height as i64
+}
+
+/// Returns the monitor that contains the biggest part of the rect, same as `MonitorFromRect`
+pub fn monitor_for_rect<'a>(
+    rect: &RECT,
+    monitors: &'a [MonitorGeometry],
+) -> Option<&'a MonitorGeometry> {
+    monitors
+        .iter()
+        .map(|m| (m, intersection_area(rect, &m.rect)))
+        .filter(|(_, area)| *area > 0)
+        .max_by_key(|(_, area)| *area)
+        .map(|(m, _)| m)
+}
+
+/// Converts a distance in physical pixels from a DPI to another one
+pub fn scale_length(value: i32, from_dpi: u32, to_dpi: u32) -> i32 {
+    if from_dpi == to_dpi || from_dpi == 0 {
+        return value;
+    }
+    (value as f64 * to_dpi as f64 / from_dpi as f64).round() as i32
+}
+
+/// The invisible borders (shadow) of a window are scaled by the window itself when it changes of DPI
+pub fn scale_shadow(shadow: &RECT, from_dpi: u32, to_dpi: u32) -> RECT {
+    RECT {
+        left: scale_length(shadow.left, from_dpi, to_dpi),
+        top: scale_length(shadow.top, from_dpi, to_dpi),
+        right: scale_length(shadow.right, from_dpi, to_dpi),
+        bottom: scale_length(shadow.bottom, from_dpi, to_dpi),
+    }
+}
+
+/// Places the rect on another work area keeping its relative position, the size is rescaled to
+/// the DPI of the target and shrunk if it doesn't fit on the target work area.
+pub fn teleport_rect(rect: &RECT, from: &RECT, from_dpi: u32, to: &RECT, to_dpi: u32) -> RECT {
+    let to_width = to.right - to.left;
+    let to_height = to.bottom - to.top;
+    let width = scale_length(rect.right - rect.left, from_dpi, to_dpi).min(to_width);
+    let height = scale_length(rect.bottom - rect.top, from_dpi, to_dpi).min(to_height);
+
+    // fraction of the free space at the left/top of the window
+    let offset = |start: i32, size: i32, area_start: i32, area_size: i32| {
+        let free = area_size - size;
+        match free > 0 {
+            true => ((start - area_start) as f64 / free as f64).clamp(0.0, 1.0),
+            false => 0.0,
+        }
+    };
+    let x = offset(
+        rect.left,
+        rect.right - rect.left,
+        from.left,
+        from.right - from.left,
+    );
+    let y = offset(
+        rect.top,
+        rect.bottom - rect.top,
+        from.top,
+        from.bottom - from.top,
+    );
+
+    let left = to.left + ((to_width - width) as f64 * x).round() as i32;
+    let top = to.top + ((to_height - height) as f64 * y).round() as i32;
+    RECT {
+        left,
+        top,
+        right: left + width,
+        bottom: top + height,
+    }
+}
+
+/// Expands the visible rect by the invisible borders so the visible frame fits the rect
+pub fn rect_with_shadow(rect: &RECT, shadow: &RECT) -> RECT {
+    RECT {
+        left: rect.left + shadow.left,
+        top: rect.top + shadow.top,
+        right: rect.right + shadow.right,
+        bottom: rect.bottom + shadow.bottom,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(left: i32, top: i32, right: i32, bottom: i32) -> RECT {
+        RECT {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    /// 1080p at 100% on the left and 4k at 150% on the right
+    fn mixed_scaling_monitors() -> Vec<MonitorGeometry> {
+        vec![
+            MonitorGeometry {
+                handle: 1,
+                rect: rect(0, 0, 1920, 1080),
+                dpi: 96,
+            },
+            MonitorGeometry {
+                handle: 2,
+                rect: rect(1920, 0, 5760, 2160),
+                dpi: 144,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_monitor_for_rect() {
+        let monitors = mixed_scaling_monitors();
+        let find = |r: RECT| monitor_for_rect(&r, &monitors).map(|m| m.handle);
+
+        assert_eq!(find(rect(100, 100, 900, 700)), Some(1));
+        assert_eq!(find(rect(2000, 100, 3000, 1000)), Some(2));
+        // spanning both monitors, most of the rect is on the second one
+        assert_eq!(find(rect(1800, 0, 2800, 1000)), Some(2));
+        assert_eq!(find(rect(1000, 0, 2000, 1000)), Some(1));
+        assert_eq!(find(rect(-500, -500, -100, -100)), None);
+    }
+
+    #[test]
+    fn test_scale_between_dpis() {
+        assert_eq!(scale_length(10, 96, 144), 15);
+        assert_eq!(scale_length(15, 144, 96), 10);
+        assert_eq!(scale_length(7, 96, 144), 11);
+        assert_eq!(scale_length(-7, 96, 144), -11);
+        assert_eq!(scale_length(7, 96, 96), 7);
+
+        let shadow = rect(-7, 0, 7, 7);
+        let scaled = scale_shadow(&shadow, 96, 144);
+        assert_eq!(scaled, rect(-11, 0, 11, 11));
+        assert_eq!(scale_shadow(&scaled, 144, 96), shadow);
+    }
+
+    #[test]
+    fn test_rect_with_shadow_keeps_visible_size() {
+        let monitors = mixed_scaling_monitors();
+        let target = rect(1930, 10, 3850, 2150);
+        let monitor = monitor_for_rect(&target, &monitors).unwrap();
+        assert_eq!(monitor.scale_factor(), 1.5);
+
+        // shadow measured while the window was on the 100% monitor
+        let shadow = scale_shadow(&rect(-7, 0, 7, 7), DEFAULT_DPI, monitor.dpi);
+        let result = rect_with_shadow(&target, &shadow);
+        assert_eq!(result, rect(1919, 10, 3861, 2161));
+
+        let visible_width = (result.right - shadow.right) - (result.left - shadow.left);
+        assert_eq!(visible_width, target.right - target.left);
+    }
+
+    #[test]
+    fn test_teleport_rect() {
+        let monitors = mixed_scaling_monitors();
+        let (small, big) = (monitors[0], monitors[1]);
+
+        // centered window keeps centered and grows with the scale factor
+        let centered = rect(560, 240, 1360, 840);
+        let result = teleport_rect(&centered, &small.rect, small.dpi, &big.rect, big.dpi);
+        assert_eq!(result, rect(3240, 630, 4440, 1530));
+        // and going back restores it
+        let back = teleport_rect(&result, &big.rect, big.dpi, &small.rect, small.dpi);
+        assert_eq!(back, centered);
+
+        // windows on the corner stay on the same corner
+        let corner = rect(1120, 480, 1920, 1080);
+        let result = teleport_rect(&corner, &small.rect, small.dpi, &big.rect, big.dpi);
+        assert_eq!(result, rect(4560, 1260, 5760, 2160));
+
+        // too big windows are shrunk to the work area
+        let huge = rect(1920, 0, 5760, 2160);
+        let result = teleport_rect(&huge, &big.rect, big.dpi, &small.rect, small.dpi);
+        assert_eq!(result, rect(0, 0, 1920, 1080));
+    }
+}
@@ -5,8 +5,66 @@ use windows::Win32::{
     },
 };
 
-use crate::{error_handler::Result, windows_api::WindowsApi};
-use seelen_core::rect::Rect;
+use crate::{
+    error_handler::Result,
+    log_error,
+    modules::diagnostics::latency::InputLatency,
+    state::application::FULL_STATE,
+    utils::spawn_named_thread,
+    windows_api::{window::Window, WindowsApi},
+};
+use seelen_core::{
+    rect::Rect,
+    state::{AppExtraFlag, WindowManagerLayout, WmLayoutPreview, WmLayoutValidation, WmSizeHints},
+};
+
+use super::{
+    geometry::{monitor_for_rect, rect_with_shadow, scale_shadow, MonitorGeometry},
+    instance::WindowManagerV2,
+    preview::{preview_layout, validate_layout},
+};
+
+/// Size limits of the window without the shadow, None if its app is configured to ignore them
+fn size_hints(hwnd: HWND) -> Option<WmSizeHints> {
+    let ignored = FULL_STATE
+        .load()
+        .get_app_config_by_window(hwnd)
+        .is_some_and(|config| config.options.contains(&AppExtraFlag::IgnoreSizeHints));
+    if ignored {
+        return None;
+    }
+
+    let (Ok(info), Ok(shadow)) = (
+        WindowsApi::get_min_max_info(hwnd),
+        WindowsApi::shadow_rect(hwnd),
+    ) else {
+        return Some(WmSizeHints::default());
+    };
+    let monitor = WindowsApi::monitor_rect(WindowsApi::monitor_from_window(hwnd)).ok()?;
+    let shadow_width = shadow.right - shadow.left;
+    let shadow_height = shadow.bottom - shadow.top;
+
+    let min = |size: i32, shadow: i32| (size - shadow).max(0);
+    // most windows report the size of the virtual screen as maximum, so it is not a real limit
+    let max = |size: i32, shadow: i32, monitor: i32| match size > 0 && size < monitor {
+        true => (size - shadow).max(1),
+        false => 0,
+    };
+    Some(WmSizeHints {
+        min_width: min(info.ptMinTrackSize.x, shadow_width),
+        min_height: min(info.ptMinTrackSize.y, shadow_height),
+        max_width: max(
+            info.ptMaxTrackSize.x,
+            shadow_width,
+            monitor.right - monitor.left,
+        ),
+        max_height: max(
+            info.ptMaxTrackSize.y,
+            shadow_height,
+            monitor.bottom - monitor.top,
+        ),
+    })
+}
 
 #[tauri::command(async)]
 pub fn set_window_position(hwnd: isize, rect: Rect) -> Result<()> {
@@ -18,13 +76,59 @@ pub fn set_window_position(hwnd: isize, rect: Rect) -> Result<()> {
 
     WindowsApi::unmaximize_window(hwnd)?;
 
-    let shadow = WindowsApi::shadow_rect(hwnd)?;
-    let rect = RECT {
-        top: rect.top + shadow.top,
-        left: rect.left + shadow.left,
-        right: rect.right + shadow.right,
-        bottom: rect.bottom + shadow.bottom,
-    };
+    // all the rects are in physical pixels
+    let mut rect = RECT::from(rect);
+
+    if let Some(hints) = size_hints(hwnd) {
+        let width = rect.right - rect.left;
+        let height = rect.bottom - rect.top;
+        // the window can't be shrunk to fit the tile, so it would overlap the other windows
+        if width < hints.min_width.max(1) || height < hints.min_height.max(1) {
+            log::debug!(
+                "Floating {:?}, the tile is smaller than its minimum size",
+                hwnd
+            );
+            let address = hwnd.0 as isize;
+            // on headless mode this is called while the state of the wm is locked
+            spawn_named_thread("WM Float", move || {
+                log_error!(WindowManagerV2::float_window(&Window::from(HWND(
+                    address as _
+                ))));
+            })?;
+            return Ok(());
+        }
+        // the window can't be grown to fill the tile, so it is centered on it
+        if hints.max_width > 0 && width > hints.max_width {
+            rect.left += (width - hints.max_width) / 2;
+            rect.right = rect.left + hints.max_width;
+        }
+        if hints.max_height > 0 && height > hints.max_height {
+            rect.top += (height - hints.max_height) / 2;
+            rect.bottom = rect.top + hints.max_height;
+        }
+    }
+
+    let current = MonitorGeometry::from_handle(WindowsApi::monitor_from_window(hwnd))?;
+    let monitors = MonitorGeometry::all()?;
+    let target = monitor_for_rect(&rect, &monitors)
+        .copied()
+        .unwrap_or(current);
+
+    // the window rescales itself on WM_DPICHANGED when moved to a monitor with another scale factor,
+    // so first the window is moved to the target monitor and then resized, this way the final size
+    // is not affected by the rescaling.
+    if current.dpi != target.dpi {
+        WindowsApi::move_window(hwnd, &rect)?;
+    }
+
+    let mut shadow = WindowsApi::shadow_rect(hwnd)?;
+    // the window is still on the old monitor, so the shadow is measured on the old scale
+    if current.dpi != target.dpi
+        && WindowsApi::monitor_from_window(hwnd).0 as isize != target.handle
+    {
+        shadow = scale_shadow(&shadow, current.dpi, target.dpi);
+    }
+    let rect = rect_with_shadow(&rect, &shadow);
 
     // WindowsApi::move_window(hwnd, &rect)?;
     WindowsApi::set_position(
@@ -33,6 +137,7 @@ pub fn set_window_position(hwnd: isize, rect: Rect) -> Result<()> {
         &rect,
         SWP_NOACTIVATE | SWP_NOCOPYBITS | SWP_ASYNCWINDOWPOS | SWP_NOSENDCHANGING,
     )?;
+    InputLatency::on_position_set();
     Ok(())
 }
 
@@ -53,3 +158,25 @@ pub fn request_focus(hwnd: isize) -> Result<()> {
     WindowsApi::async_force_set_foreground(hwnd);
     Ok(())
 }
+
+/// Size limits of the window used to give it a bigger share of the layout
+#[tauri::command(async)]
+pub fn wm_get_size_hints(hwnd: isize) -> Option<WmSizeHints> {
+    let hwnd = HWND(hwnd as _);
+    if !WindowsApi::is_window(hwnd) {
+        return None;
+    }
+    size_hints(hwnd)
+}
+
+/// Parses and validates the content of a layout file (json or yaml), used by the layout editor
+#[tauri::command(async)]
+pub fn wm_layout_validate(content: String) -> WmLayoutValidation {
+    validate_layout(&content)
+}
+
+/// Placement of the given amount of fake windows on the layout, used by the layout editor
+#[tauri::command(async)]
+pub fn wm_layout_preview(layout: WindowManagerLayout, windows: usize) -> WmLayoutPreview {
+    preview_layout(&layout, windows)
+}
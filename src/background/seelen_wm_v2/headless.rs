@@ -0,0 +1,57 @@
+use seelen_core::{
+    rect::Rect,
+    state::{WmArea, WmNode},
+};
+
+use crate::{
+    error_handler::Result,
+    log_error,
+    state::application::FULL_STATE,
+    windows_api::{monitor::Monitor, WindowsApi},
+};
+
+use super::{handler::set_window_position, instance::WindowManagerV2};
+
+impl WindowManagerV2 {
+    /// Positions the windows of the layout without the layout webview, used on headless mode.
+    /// Gaps, paddings and margins are in logical pixels like on the webview.
+    pub(super) fn tile_natively(monitor_id: &str, root: Option<&WmNode>) -> Result<()> {
+        let Some(root) = root else {
+            return Ok(());
+        };
+        let monitor = Monitor::by_id(monitor_id).ok_or("Monitor not found")?;
+        let work_area = monitor.work_area()?;
+        let scale = WindowsApi::get_device_pixel_ratio(monitor.raw())?;
+
+        let settings = &FULL_STATE.load().settings.window_manager;
+        let padding = settings.workspace_padding as f32;
+        let margin = &settings.workspace_margin;
+        let area = WmArea {
+            x: work_area.left as f32 + (margin.left as f32 + padding) * scale,
+            y: work_area.top as f32 + (margin.top as f32 + padding) * scale,
+            width: (work_area.right - work_area.left) as f32
+                - (margin.left as f32 + margin.right as f32 + padding * 2.0) * scale,
+            height: (work_area.bottom - work_area.top) as f32
+                - (margin.top as f32 + margin.bottom as f32 + padding * 2.0) * scale,
+        };
+        // the focus border is drawn inside the space of the window
+        let inset = match settings.border.enabled {
+            true => ((settings.border.width + settings.border.offset) as f32 * scale).max(0.0),
+            false => 0.0,
+        };
+
+        let gap = settings.workspace_gap as f32 * scale;
+        let mut placed = Vec::new();
+        root.place(area, gap, &mut placed);
+        for window in placed {
+            let rect = Rect {
+                left: (window.x + inset).round() as i32,
+                top: (window.y + inset).round() as i32,
+                right: (window.x + window.width - inset).round() as i32,
+                bottom: (window.y + window.height - inset).round() as i32,
+            };
+            log_error!(set_window_position(window.index as isize, rect));
+        }
+        Ok(())
+    }
+}
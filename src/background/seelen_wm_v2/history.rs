@@ -0,0 +1,202 @@
+use std::collections::VecDeque;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+
+use crate::{
+    error_handler::Result,
+    modules::virtual_desk::get_vd_manager,
+    trace_lock,
+    windows_api::{monitor::Monitor, window::Window},
+};
+
+use super::{cli::Axis, instance::WindowManagerV2, state::WM_STATE};
+
+/// amount of operations that can be undone
+const MAX_HISTORY: usize = 50;
+
+lazy_static! {
+    static ref HISTORY: Mutex<WmHistory> = Mutex::new(WmHistory::default());
+}
+
+/// Layout operations made by the user, windows being opened or closed are not recorded
+#[derive(Debug, Clone)]
+pub enum WmCommand {
+    ToggleFloat(isize),
+    /// resizes the window along each axis by the percentage
+    Resize {
+        window: isize,
+        changes: Vec<(Axis, f32)>,
+        relative: bool,
+    },
+    /// restores the grow factors of a workspace, see `WmV2StateWorkspace::sizes`
+    SetSizes {
+        monitor_id: String,
+        workspace_id: String,
+        sizes: Vec<f32>,
+    },
+    SendToWorkspace {
+        window: isize,
+        from: usize,
+        to: usize,
+    },
+    /// moves the window between monitors, indexes as returned by `Monitor::index`
+    SendToMonitor {
+        window: isize,
+        from: usize,
+        to: usize,
+    },
+}
+
+#[derive(Debug, Default)]
+struct WmHistory {
+    /// inverse of the executed commands, newest last
+    undo: VecDeque<WmCommand>,
+    redo: Vec<WmCommand>,
+}
+
+impl WmHistory {
+    fn push_undo(&mut self, inverse: WmCommand) {
+        if self.undo.len() == MAX_HISTORY {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(inverse);
+    }
+}
+
+impl WindowManagerV2 {
+    /// Applies the command and returns its inverse, `None` if nothing changed
+    fn apply(command: WmCommand) -> Result<Option<WmCommand>> {
+        match command {
+            WmCommand::ToggleFloat(address) => {
+                let window = Window::from(address);
+                if !window.is_window() {
+                    return Err("The window no longer exists".into());
+                }
+                Self::toggle_float(&window)?;
+                Ok(Some(WmCommand::ToggleFloat(address)))
+            }
+            WmCommand::Resize {
+                window,
+                changes,
+                relative,
+            } => {
+                let window = Window::from(window);
+                let state = trace_lock!(WM_STATE);
+                let (monitor_id, workspace_id) = state
+                    .locate(&window)
+                    .ok_or("Trying to change size of an unmanaged window")?;
+                let before = state
+                    .get_workspace(&monitor_id, &workspace_id)
+                    .map(|w| w.sizes())
+                    .unwrap_or_default();
+
+                for (axis, percentage) in changes {
+                    state.update_size(&window, axis, percentage, relative)?;
+                }
+
+                let Some(workspace) = state.get_workspace(&monitor_id, &workspace_id) else {
+                    return Ok(None);
+                };
+                if workspace.sizes() == before {
+                    return Ok(None);
+                }
+                Self::render_workspace(&monitor_id, workspace)?;
+                Ok(Some(WmCommand::SetSizes {
+                    monitor_id,
+                    workspace_id,
+                    sizes: before,
+                }))
+            }
+            WmCommand::SetSizes {
+                monitor_id,
+                workspace_id,
+                sizes,
+            } => {
+                let state = trace_lock!(WM_STATE);
+                let workspace = state
+                    .get_workspace(&monitor_id, &workspace_id)
+                    .ok_or("The workspace no longer exists")?;
+                let before = workspace.sizes();
+                workspace.set_sizes(&sizes)?;
+                if workspace_id == get_vd_manager().get_current()?.id() {
+                    Self::render_workspace(&monitor_id, workspace)?;
+                }
+                Ok(Some(WmCommand::SetSizes {
+                    monitor_id,
+                    workspace_id,
+                    sizes: before,
+                }))
+            }
+            WmCommand::SendToWorkspace { window, from, to } => {
+                if from == to {
+                    return Ok(None);
+                }
+                if !Window::from(window).is_window() {
+                    return Err("The window no longer exists".into());
+                }
+                // the layout is updated by the virtual desktop events
+                get_vd_manager().send_to(to, window)?;
+                Ok(Some(WmCommand::SendToWorkspace {
+                    window,
+                    from: to,
+                    to: from,
+                }))
+            }
+            WmCommand::SendToMonitor { window, from, to } => {
+                if from == to {
+                    return Ok(None);
+                }
+                let window = Window::from(window);
+                if !window.is_window() {
+                    return Err("The window no longer exists".into());
+                }
+                let target = Monitor::at(to).ok_or(format!("Monitor {} not found", to))?;
+                Self::send_to_monitor(&window, target)?;
+                Ok(Some(WmCommand::SendToMonitor {
+                    window: window.address(),
+                    from: to,
+                    to: from,
+                }))
+            }
+        }
+    }
+
+    /// Applies the command recording it on the history, discards the undone commands
+    pub fn execute(command: WmCommand) -> Result<()> {
+        if let Some(inverse) = Self::apply(command)? {
+            let mut history = trace_lock!(HISTORY);
+            history.push_undo(inverse);
+            history.redo.clear();
+        }
+        Ok(())
+    }
+
+    /// Reverts the last command, commands that can't be reverted anymore (e.g. the window was closed)
+    /// are discarded and the error is returned.
+    pub fn undo() -> Result<()> {
+        let Some(inverse) = trace_lock!(HISTORY).undo.pop_back() else {
+            return Ok(());
+        };
+        if let Some(command) = Self::apply(inverse)? {
+            trace_lock!(HISTORY).redo.push(command);
+        }
+        Ok(())
+    }
+
+    pub fn redo() -> Result<()> {
+        let Some(command) = trace_lock!(HISTORY).redo.pop() else {
+            return Ok(());
+        };
+        if let Some(inverse) = Self::apply(command)? {
+            trace_lock!(HISTORY).push_undo(inverse);
+        }
+        Ok(())
+    }
+
+    pub fn clear_history() {
+        let mut history = trace_lock!(HISTORY);
+        history.undo.clear();
+        history.redo.clear();
+    }
+}
@@ -11,7 +11,11 @@ use crate::{
     winevent::WinEvent,
 };
 
-use super::{cli::Axis, state::WM_STATE, WindowManagerV2};
+use super::{
+    border::FocusBorder, cli::Axis, corners::WmCorners, history::WmCommand,
+    position_memory::PositionMemory, snap::WmSnap, state::WM_STATE, zones::WmZones,
+    WindowManagerV2,
+};
 
 lazy_static! {
     static ref SystemMoveSizeStartRect: Arc<Mutex<Rect>> = Arc::new(Mutex::new(Rect::default()));
@@ -40,10 +44,21 @@ impl WindowManagerV2 {
     }
 
     fn system_move_size_end(window: &Window) -> Result<()> {
-        if !Self::is_managed(window) {
+        // snapped windows are floating now, so the overlay stays hidden as for any unmanaged window
+        if WmZones::end_drag(window)? {
+            return Ok(());
+        }
+
+        // the layout follows the native snap instead of moving the window back
+        if WmSnap::on_window_moved(window)? {
+            Self::set_overlay_visibility(true)?;
             return Ok(());
         }
 
+        if !Self::is_managed(window) {
+            return PositionMemory::record(window);
+        }
+
         if *SystemMoveSizeStartMonitor.lock() != window.monitor() {
             log::trace!("window moved of monitor");
             Self::remove(window)?;
@@ -60,6 +75,7 @@ impl WindowManagerV2 {
         let new_width = (rect.right - rect.left) as f32;
         let new_height = (rect.bottom - rect.top) as f32;
 
+        let mut changes = Vec::new();
         if initial_width != new_width {
             let percentage_diff = (new_width - initial_width) / initial_width * 100.0;
             let axis = if rect.left == initial_rect.left {
@@ -68,9 +84,7 @@ impl WindowManagerV2 {
                 Axis::Left
             };
             log::trace!("window width changed by: {}%", percentage_diff);
-            let state = trace_lock!(WM_STATE);
-            let (m, w) = state.update_size(window, axis, percentage_diff, true)?;
-            Self::render_workspace(&m.id, w)?;
+            changes.push((axis, percentage_diff));
         }
 
         if initial_height != new_height {
@@ -81,9 +95,16 @@ impl WindowManagerV2 {
                 Axis::Top
             };
             log::trace!("window height changed by: {}%", percentage_diff);
-            let state = trace_lock!(WM_STATE);
-            let (m, w) = state.update_size(window, axis, percentage_diff, true)?;
-            Self::render_workspace(&m.id, w)?;
+            changes.push((axis, percentage_diff));
+        }
+
+        if !changes.is_empty() {
+            // resizing both axes is a single operation for undo
+            Self::execute(WmCommand::Resize {
+                window: window.address(),
+                changes,
+                relative: true,
+            })?;
         }
 
         Self::force_retiling()?;
@@ -91,9 +112,32 @@ impl WindowManagerV2 {
         Ok(())
     }
 
+    pub fn wants_win_event(event: &WinEvent) -> bool {
+        matches!(
+            event,
+            WinEvent::SystemMoveSizeStart
+                | WinEvent::SystemMoveSizeEnd
+                | WinEvent::ObjectCreate
+                | WinEvent::ObjectShow
+                | WinEvent::SystemMinimizeEnd
+                | WinEvent::ObjectDestroy
+                | WinEvent::ObjectHide
+                | WinEvent::SystemMinimizeStart
+                | WinEvent::ObjectFocus
+                | WinEvent::SystemForeground
+                | WinEvent::ObjectNameChange
+                | WinEvent::ObjectLocationChange
+                | WinEvent::ObjectCloaked
+                | WinEvent::ObjectUncloaked
+                | WinEvent::SyntheticFullscreenStart(_)
+                | WinEvent::SyntheticFullscreenEnd(_)
+        )
+    }
+
     pub fn process_win_event(event: WinEvent, window: &Window) -> Result<()> {
         match event {
             WinEvent::SystemMoveSizeStart => {
+                WmZones::start_drag(window)?;
                 if Self::is_managed(window) {
                     Self::set_overlay_visibility(false)?;
                     *SystemMoveSizeStartRect.lock() = window.inner_rect()?;
@@ -105,16 +149,46 @@ impl WindowManagerV2 {
                 if !Self::is_managed(window) && Self::should_be_managed(window.hwnd()) {
                     Self::add(window)?;
                     Self::set_overlay_visibility(true)?;
+                } else if event == WinEvent::ObjectShow {
+                    PositionMemory::restore(window)?;
+                    WmCorners::apply(window, false);
                 }
             }
             WinEvent::ObjectDestroy | WinEvent::ObjectHide | WinEvent::SystemMinimizeStart => {
+                if event == WinEvent::ObjectDestroy {
+                    let mut state = trace_lock!(WM_STATE);
+                    state.floating.remove(&window.address());
+                    state.fullscreen.remove(&window.address());
+                    drop(state);
+                    PositionMemory::forget(window);
+                    WmSnap::forget(window);
+                    WmCorners::forget(window);
+                }
                 if Self::is_managed(window) {
                     Self::remove(window)?;
+                } else if event == WinEvent::ObjectHide {
+                    PositionMemory::record(window)?;
+                }
+            }
+            // cloaked by the shell means that it was moved to another virtual desktop and that is
+            // handled by the virtual desktop events, cloaked by the app means that it was closed
+            // or suspended (UWP frames), in that case the tile should be removed.
+            WinEvent::ObjectCloaked => {
+                if Self::is_managed(window) && window.is_cloaked_by_app() {
+                    Self::remove(window)?;
+                }
+            }
+            WinEvent::ObjectUncloaked => {
+                if !Self::is_managed(window) && Self::should_be_managed(window.hwnd()) {
+                    Self::add(window)?;
+                    Self::set_overlay_visibility(true)?;
                 }
             }
             WinEvent::ObjectFocus | WinEvent::SystemForeground => {
+                let is_managed = Self::is_managed(window);
                 Self::set_active_window(window)?;
-                Self::set_overlay_visibility(Self::is_managed(window))?;
+                Self::set_overlay_visibility(is_managed)?;
+                FocusBorder::track(is_managed.then_some(window))?;
             }
             // apps like firefox doesn't launch ObjectCreate
             WinEvent::ObjectNameChange => {
@@ -130,9 +204,32 @@ impl WindowManagerV2 {
                 if window.is_foreground() && window.is_maximized() {
                     Self::set_overlay_visibility(false)?;
                 }
+                // snaps made with the keyboard or the flyout don't start a drag
+                if !WmZones::is_dragging() {
+                    WmSnap::on_window_moved(window)?;
+                }
+            }
+            WinEvent::SyntheticFullscreenStart(data) => {
+                let policy = data.policy();
+                if policy.hide_overlays {
+                    Self::set_overlay_visibility(false)?;
+                    FocusBorder::set_suspended(true)?;
+                }
+                let window = Window::from(data.handle);
+                if policy.never_tile && Self::is_managed(&window) {
+                    trace_lock!(WM_STATE).fullscreen.insert(window.address());
+                    Self::remove(&window)?;
+                }
+            }
+            WinEvent::SyntheticFullscreenEnd(data) => {
+                Self::set_overlay_visibility(true)?;
+                FocusBorder::set_suspended(false)?;
+                let window = Window::from(data.handle);
+                let was_tiled = trace_lock!(WM_STATE).fullscreen.remove(&window.address());
+                if was_tiled && Self::should_be_managed(window.hwnd()) {
+                    Self::add(&window)?;
+                }
             }
-            WinEvent::SyntheticFullscreenStart(_) => Self::set_overlay_visibility(false)?,
-            WinEvent::SyntheticFullscreenEnd(_) => Self::set_overlay_visibility(true)?,
             _ => {}
         };
         Ok(())
@@ -4,9 +4,13 @@ use tauri::{Emitter, Listener, WebviewWindow};
 use windows::Win32::{Graphics::Gdi::HMONITOR, UI::WindowsAndMessaging::SWP_ASYNCWINDOWPOS};
 
 use crate::{
-    error_handler::Result, log_error, modules::virtual_desk::get_vd_manager,
-    seelen::get_app_handle, seelen_bar::FancyToolbar, seelen_wm_v2::state::WM_STATE, trace_lock,
-    windows_api::WindowsApi,
+    error_handler::Result,
+    log_error,
+    modules::{event_registry::EventRegistry, virtual_desk::get_vd_manager},
+    seelen::get_app_handle,
+    seelen_wm_v2::state::WM_STATE,
+    trace_lock,
+    windows_api::{monitor::Monitor, WindowsApi},
 };
 
 #[derive(Getters, MutGetters)]
@@ -71,7 +75,7 @@ impl WindowManagerV2 {
                     )?;
                 }
 
-                app.emit(
+                EventRegistry::emit(
                     SeelenEvent::WMSetActiveWindow,
                     WindowsApi::get_foreground_window().0 as isize,
                 )?;
@@ -83,7 +87,7 @@ impl WindowManagerV2 {
     }
 
     pub fn set_position(&self, monitor: HMONITOR) -> Result<()> {
-        let work_area = FancyToolbar::get_work_area_by_monitor(monitor)?;
+        let work_area = Monitor::from(monitor).work_area()?;
         let main_hwnd = self.window.hwnd()?;
         WindowsApi::move_window(main_hwnd, &work_area)?;
         WindowsApi::set_position(main_hwnd, None, &work_area, SWP_ASYNCWINDOWPOS)?;
@@ -1,89 +1,173 @@
+pub mod border;
 pub mod cli;
+pub mod corners;
+pub mod geometry;
 pub mod handler;
+pub mod headless;
+pub mod history;
 pub mod hook;
 pub mod instance;
 pub mod node_impl;
+pub mod position_memory;
+pub mod preview;
+pub mod snap;
 pub mod state;
+pub mod zones;
 
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use border::FocusBorder;
+use cli::AllowedFocus;
+use corners::WmCorners;
+use geometry::{teleport_rect, MonitorGeometry};
 use instance::WindowManagerV2;
-use seelen_core::{handlers::SeelenEvent, state::AppExtraFlag};
+use seelen_core::{
+    handlers::SeelenEvent,
+    rect::Rect,
+    state::{AppExtraFlag, WmNode},
+    system_state::{WindowFilterKind, WindowFilterOutcome, WindowFilterVerdict},
+};
 use state::{WmV2StateWorkspace, WM_STATE};
 use tauri::Emitter;
 use windows::Win32::{
-    Foundation::HWND,
-    UI::WindowsAndMessaging::{WS_CAPTION, WS_EX_TOPMOST},
+    Foundation::{HWND, RECT},
+    UI::WindowsAndMessaging::{SWP_NOACTIVATE, SW_MAXIMIZE, SW_SHOWMINNOACTIVE},
 };
 
 use crate::{
     error_handler::Result,
     log_error,
-    modules::virtual_desk::{get_vd_manager, VirtualDesktop},
-    seelen::get_app_handle,
-    seelen_weg::SeelenWeg,
+    modules::{
+        accessibility::Announcer,
+        event_registry::EventRegistry,
+        ipc::status_feed::StatusFeed,
+        session::SessionManager,
+        virtual_desk::{get_vd_manager, VirtualDesktop},
+        window_filter::{FilterChain, WindowFilter},
+    },
+    seelen::{get_app_handle, Seelen},
     state::application::FULL_STATE,
     trace_lock,
     windows_api::{monitor::Monitor, window::Window, WindowEnumerator, WindowsApi},
 };
 
-impl WindowManagerV2 {
-    fn is_manageable_window(hwnd: HWND) -> bool {
-        let exe = WindowsApi::exe(hwnd);
+/// set when a retile is skipped because the session is locked
+static RETILE_PENDING: AtomicBool = AtomicBool::new(false);
 
-        if let Ok(exe) = &exe {
-            if exe.ends_with("ApplicationFrameHost.exe") && SeelenWeg::should_be_added(hwnd) {
-                return true;
-            }
-        }
+impl WindowManagerV2 {
+    /// Decides if the window should be tiled, explaining each check if requested
+    pub fn management_verdict(window: &Window, explain: bool) -> WindowFilterVerdict {
+        let address = window.address();
+        let full_state = FULL_STATE.load();
+        let config = full_state.get_app_config_by_window(window.hwnd());
+        let has_option =
+            |flag: AppExtraFlag| config.is_some_and(|config| config.options.contains(&flag));
 
-        // Without admin some apps does not return the exe path so these should be unmanaged
-        exe.is_ok()
-        && SeelenWeg::should_be_added(hwnd)
-        // Ignore windows without a title bar, and top most windows normally are widgets or tools so they should not be managed
-        && (WindowsApi::get_styles(hwnd).contains(WS_CAPTION) && !WindowsApi::get_ex_styles(hwnd).contains(WS_EX_TOPMOST))
-        && !WindowsApi::is_iconic(hwnd)
-        && (get_vd_manager().uses_cloak() || !WindowsApi::is_cloaked(hwnd).unwrap_or(false))
+        FilterChain::new(explain)
+            .check(WindowFilterKind::WmState, || {
+                let state = trace_lock!(WM_STATE);
+                let floating = state.floating.contains(&address);
+                let fullscreen = state.fullscreen.contains(&address);
+                let outcome = match floating || fullscreen {
+                    true => WindowFilterOutcome::Rejected,
+                    false => WindowFilterOutcome::Passed,
+                };
+                let detail = format!("floating: {}, fullscreen: {}", floating, fullscreen);
+                (outcome, detail)
+            })
+            .check(WindowFilterKind::WmAppConfig, || {
+                let outcome = if has_option(AppExtraFlag::Force) {
+                    WindowFilterOutcome::Forced
+                } else if has_option(AppExtraFlag::Unmanage)
+                    || has_option(AppExtraFlag::Pinned)
+                    || has_option(AppExtraFlag::PictureInPicture)
+                {
+                    WindowFilterOutcome::Rejected
+                } else {
+                    WindowFilterOutcome::Passed
+                };
+                let detail = match config {
+                    Some(config) => format!("{}: {:?}", config.name, config.options),
+                    None => "none".to_string(),
+                };
+                (outcome, detail)
+            })
+            .chain(|explain| WindowFilter::tileable(window, explain))
+            .verdict()
     }
 
     fn should_be_managed(hwnd: HWND) -> bool {
-        if let Some(config) = FULL_STATE.load().get_app_config_by_window(hwnd) {
-            if config.options.contains(&AppExtraFlag::Force) {
-                return true;
-            }
-
-            if config.options.contains(&AppExtraFlag::Unmanage)
-                || config.options.contains(&AppExtraFlag::Pinned)
-            {
-                return false;
-            }
-        }
-        Self::is_manageable_window(hwnd)
+        Self::management_verdict(&Window::from(hwnd), false).included
     }
 
     fn is_managed(window: &Window) -> bool {
         trace_lock!(WM_STATE).contains(window)
     }
 
-    fn force_retiling() -> Result<()> {
-        get_app_handle().emit(SeelenEvent::WMForceRetiling, ())?;
+    /// Windows are resized on the lock screen (e.g. monitors turned off) so retiles are deferred
+    fn defer_while_locked() -> bool {
+        if SessionManager::is_locked() {
+            RETILE_PENDING.store(true, Ordering::Release);
+            return true;
+        }
+        false
+    }
+
+    pub fn force_retiling() -> Result<()> {
+        if Self::defer_while_locked() {
+            return Ok(());
+        }
+        if Seelen::is_headless() {
+            return Self::workspace_changed(&get_vd_manager().get_current()?);
+        }
+        EventRegistry::emit(SeelenEvent::WMForceRetiling, ())?;
         Ok(())
     }
 
-    fn render_workspace(monitor_id: &str, w: &WmV2StateWorkspace) -> Result<()> {
+    /// Retiles only the given monitor, e.g. after its scale changed
+    pub fn force_retiling_on(monitor_id: &str) -> Result<()> {
+        if Self::defer_while_locked() {
+            return Ok(());
+        }
+        if Seelen::is_headless() {
+            return Self::workspace_changed(&get_vd_manager().get_current()?);
+        }
+        get_app_handle().emit_to(
+            format!("{}/{}", Self::TARGET, monitor_id),
+            SeelenEvent::WMForceRetiling,
+            (),
+        )?;
+        Ok(())
+    }
+
+    /// Sends the layout to the webview of the monitor or tiles it natively on headless mode
+    fn emit_layout(monitor_id: &str, root: Option<&WmNode>) -> Result<()> {
+        StatusFeed::on_layout(monitor_id, root);
+        if Seelen::is_headless() {
+            return Self::tile_natively(monitor_id, root);
+        }
         get_app_handle().emit_to(
             format!("{}/{}", Self::TARGET, monitor_id),
             SeelenEvent::WMSetLayout,
-            w.get_root_node(),
+            root,
         )?;
         Ok(())
     }
 
+    fn render_workspace(monitor_id: &str, w: &WmV2StateWorkspace) -> Result<()> {
+        if Self::defer_while_locked() {
+            return Ok(());
+        }
+        Self::emit_layout(monitor_id, w.get_root_node())
+    }
+
     fn set_overlay_visibility(visible: bool) -> Result {
-        get_app_handle().emit(SeelenEvent::WMSetOverlayVisibility, visible)?;
+        EventRegistry::emit(SeelenEvent::WMSetOverlayVisibility, visible)?;
         Ok(())
     }
 
     fn set_active_window(window: &Window) -> Result {
-        get_app_handle().emit(SeelenEvent::WMSetActiveWindow, window.address())?;
+        EventRegistry::emit(SeelenEvent::WMSetActiveWindow, window.address())?;
         Ok(())
     }
 
@@ -116,13 +200,14 @@ impl WindowManagerV2 {
             let workspace = monitor.get_workspace_mut(&workspace_id);
             workspace.add_window(window);
             if workspace_id == current_workspace_id {
-                get_app_handle().emit_to(
-                    format!("{}/{}", Self::TARGET, monitor_id),
-                    SeelenEvent::WMSetLayout,
-                    workspace.get_root_node(),
-                )?;
+                Self::emit_layout(&monitor_id, workspace.get_root_node())?;
             }
         }
+        drop(state);
+        WmCorners::apply(window, true);
+        if window.is_foreground() {
+            FocusBorder::track(Some(window))?;
+        }
         Ok(())
     }
 
@@ -135,14 +220,139 @@ impl WindowManagerV2 {
             for (workspace_id, workspace) in monitor.workspaces.iter_mut() {
                 workspace.remove_window(window);
                 if workspace_id == &current_workspace {
-                    get_app_handle().emit_to(
-                        format!("{}/{}", Self::TARGET, monitor_id),
-                        SeelenEvent::WMSetLayout,
-                        workspace.get_root_node(),
-                    )?;
+                    Self::emit_layout(monitor_id, workspace.get_root_node())?;
                 }
             }
         }
+        drop(state);
+        if WindowsApi::is_window(window.hwnd()) {
+            WmCorners::apply(window, false);
+        }
+        FocusBorder::untrack(window)
+    }
+
+    /// Removes the window from the layout as if the user had floated it
+    fn float_window(window: &Window) -> Result<()> {
+        if Self::is_managed(window) {
+            trace_lock!(WM_STATE).floating.insert(window.address());
+            Self::remove(window)?;
+        }
+        Ok(())
+    }
+
+    /// Removes the window from the layout or adds it back if it was floating
+    fn toggle_float(window: &Window) -> Result<()> {
+        if Self::is_managed(window) {
+            Self::float_window(window)?;
+        } else {
+            trace_lock!(WM_STATE).floating.remove(&window.address());
+            if Self::should_be_managed(window.hwnd()) {
+                Self::add(window)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Focuses the nearest managed window in the given direction
+    fn focus(side: AllowedFocus) -> Result<()> {
+        let foreground = Window::from(WindowsApi::get_foreground_window());
+        let origin = foreground.outer_rect()?;
+        let center = |r: &Rect| ((r.left + r.right) / 2, (r.top + r.bottom) / 2);
+        let (x, y) = center(&origin);
+
+        let mut candidates = Vec::new();
+        WindowEnumerator::new().for_each(|hwnd| {
+            let window = Window::from(hwnd);
+            if hwnd != foreground.hwnd() && !window.is_cloaked() && Self::is_managed(&window) {
+                candidates.push(window);
+            }
+        })?;
+
+        let target = candidates
+            .into_iter()
+            .filter_map(|window| {
+                let (cx, cy) = center(&window.outer_rect().ok()?);
+                let is_in_direction = match side {
+                    AllowedFocus::Left => cx < x,
+                    AllowedFocus::Right => cx > x,
+                    AllowedFocus::Up => cy < y,
+                    AllowedFocus::Down => cy > y,
+                    // enumeration follows the z-order so the first one is the latest focused
+                    AllowedFocus::Latest => true,
+                };
+                let distance = (cx - x).pow(2) + (cy - y).pow(2);
+                is_in_direction.then_some((window, distance))
+            })
+            .min_by_key(|(_, distance)| match side {
+                AllowedFocus::Latest => 0,
+                _ => *distance,
+            });
+
+        if let Some((window, _)) = target {
+            WindowsApi::async_force_set_foreground(window.hwnd());
+            Announcer::on_focus_moved(&window);
+        }
+        Ok(())
+    }
+
+    /// Moves the window to the monitor keeping its relative position and rescaling it to the
+    /// scale factor of the monitor, tiled windows are inserted on the layout of the monitor.
+    pub fn send_to_monitor(window: &Window, target: Monitor) -> Result<()> {
+        let hwnd = window.hwnd();
+        let current = window.monitor();
+        if current == target {
+            return Ok(());
+        }
+
+        let from_dpi = MonitorGeometry::from_handle(current.raw())?.dpi;
+        let to_dpi = MonitorGeometry::from_handle(target.raw())?.dpi;
+        let from_area = current.work_area()?;
+        let to_area = target.work_area()?;
+
+        if window.is_minimized() {
+            // only the restored rect can be changed, it is relative to the primary work area
+            let primary = Monitor::from(WindowsApi::primary_monitor()).work_area()?;
+            let relative = |r: RECT| RECT {
+                left: r.left - primary.left,
+                top: r.top - primary.top,
+                right: r.right - primary.left,
+                bottom: r.bottom - primary.top,
+            };
+            let mut placement = WindowsApi::get_window_placement(hwnd)?;
+            placement.rcNormalPosition = teleport_rect(
+                &placement.rcNormalPosition,
+                &relative(from_area),
+                from_dpi,
+                &relative(to_area),
+                to_dpi,
+            );
+            placement.showCmd = SW_SHOWMINNOACTIVE.0 as u32;
+            WindowsApi::set_window_placement(hwnd, &placement)?;
+        } else {
+            let maximized = window.is_maximized();
+            if maximized {
+                WindowsApi::unmaximize_window(hwnd)?;
+            }
+            let rect = teleport_rect(
+                &WindowsApi::get_outer_window_rect(hwnd)?,
+                &from_area,
+                from_dpi,
+                &to_area,
+                to_dpi,
+            );
+            // the window rescales itself on WM_DPICHANGED when it arrives to the monitor,
+            // so the size is set after the move to not be affected by it
+            WindowsApi::move_window(hwnd, &rect)?;
+            WindowsApi::set_position(hwnd, None, &rect, SWP_NOACTIVATE)?;
+            if maximized {
+                WindowsApi::show_window(hwnd, SW_MAXIMIZE)?;
+            }
+        }
+
+        if Self::is_managed(window) {
+            Self::remove(window)?;
+            Self::add(window)?;
+        }
         Ok(())
     }
 
@@ -151,17 +361,47 @@ impl WindowManagerV2 {
         let workspace_id = current.id();
         for (monitor_id, monitor) in state.monitors.iter_mut() {
             let workspace = monitor.get_workspace_mut(&workspace_id);
-            get_app_handle().emit_to(
-                format!("{}/{}", Self::TARGET, monitor_id),
-                SeelenEvent::WMSetLayout,
-                workspace.get_root_node(),
-            )?;
+            Self::emit_layout(monitor_id, workspace.get_root_node())?;
         }
         Ok(())
     }
 
+    pub fn on_session_unlocked() -> Result<()> {
+        if !RETILE_PENDING.swap(false, Ordering::AcqRel) {
+            return Ok(());
+        }
+        Self::workspace_changed(&get_vd_manager().get_current()?)?;
+        Self::force_retiling()
+    }
+
+    /// Called when monitors are connected, disconnected or woke up. The windows tiled on
+    /// disconnected monitors are tiled again on the monitor where Windows moved them.
+    pub fn sync_monitors() -> Result<()> {
+        let mut state = trace_lock!(WM_STATE);
+        let orphaned = state.remove_disconnected()?;
+        state.init()?;
+        drop(state);
+
+        if !orphaned.is_empty() {
+            log::info!(
+                "Retiling {} windows of disconnected monitors",
+                orphaned.len()
+            );
+        }
+        for addr in orphaned {
+            let window = Window::from(addr);
+            if window.is_window() && Self::should_be_managed(window.hwnd()) {
+                log_error!(Self::add(&window));
+            }
+        }
+        Self::force_retiling()
+    }
+
     pub fn clear_state() {
         trace_lock!(WM_STATE).monitors.clear();
+        WmCorners::restore_all();
+        Self::clear_history();
+        StatusFeed::clear_layouts();
     }
 
     pub fn init_state() -> Result<()> {
@@ -171,8 +411,13 @@ impl WindowManagerV2 {
     pub fn enumerate_all_windows() -> Result<()> {
         WindowEnumerator::new().for_each(|hwnd| {
             let window = Window::from(hwnd);
-            if !Self::is_managed(&window) && Self::should_be_managed(hwnd) {
+            if Self::is_managed(&window) {
+                // the corners settings could have changed
+                WmCorners::apply(&window, true);
+            } else if Self::should_be_managed(hwnd) {
                 log_error!(Self::add(&window));
+            } else {
+                WmCorners::apply(&window, false);
             }
         })
     }
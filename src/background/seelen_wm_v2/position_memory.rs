@@ -0,0 +1,130 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{rect::Rect, state::AppExtraFlag};
+use tauri::Manager;
+use windows::Win32::{
+    Foundation::RECT,
+    UI::WindowsAndMessaging::{WS_CAPTION, WS_CHILD, WS_EX_TOOLWINDOW},
+};
+
+use crate::{
+    error_handler::Result,
+    seelen::get_app_handle,
+    state::application::FULL_STATE,
+    trace_lock,
+    windows_api::{window::Window, WindowsApi},
+};
+
+use super::{
+    geometry::{monitor_for_rect, MonitorGeometry},
+    handler::set_window_position,
+    WindowManagerV2,
+};
+
+lazy_static! {
+    /// last visible rect of the floating windows by app identity
+    static ref POSITIONS: Mutex<HashMap<String, Rect>> = Mutex::new(PositionMemory::load());
+    /// windows already restored, so later show events don't move them back
+    static ref RESTORED: Mutex<HashSet<isize>> = Mutex::new(HashSet::new());
+}
+
+/// Remembers where the floating windows were and restores them when reopened
+pub struct PositionMemory;
+
+impl PositionMemory {
+    fn path() -> Result<PathBuf> {
+        Ok(get_app_handle()
+            .path()
+            .app_data_dir()?
+            .join("window_positions.json"))
+    }
+
+    fn load() -> HashMap<String, Rect> {
+        let Ok(path) = Self::path() else {
+            return HashMap::new();
+        };
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(positions: &HashMap<String, Rect>) -> Result<()> {
+        std::fs::write(Self::path()?, serde_json::to_string(positions)?)?;
+        Ok(())
+    }
+
+    /// Dialogs use another class than the main window of the app, so both are remembered apart
+    fn key_of(window: &Window) -> Option<String> {
+        let app = match window.app_user_model_id() {
+            Some(umid) => umid,
+            None => window.exe().ok()?.to_string_lossy().to_lowercase(),
+        };
+        Some(format!("{}|{}", app, window.class()))
+    }
+
+    /// Unlike the dock filter, owned windows like dialogs are included and the window can be hidden
+    fn is_floating_app_window(window: &Window) -> bool {
+        let styles = WindowsApi::get_styles(window.hwnd());
+        styles.contains(WS_CAPTION)
+            && !styles.contains(WS_CHILD)
+            && !WindowsApi::get_ex_styles(window.hwnd()).contains(WS_EX_TOOLWINDOW)
+            && !window.is_seelen_overlay()
+            && !WindowManagerV2::is_managed(window)
+    }
+
+    fn is_enabled_for(window: &Window) -> bool {
+        if !Self::is_floating_app_window(window) {
+            return false;
+        }
+        match FULL_STATE.load().get_app_config_by_window(window.hwnd()) {
+            Some(config) => !config.options.contains(&AppExtraFlag::NoPositionMemory),
+            None => true,
+        }
+    }
+
+    /// Stores the current rect of the window if it is floating, also called when the window is
+    /// hidden because on destroy the rect is not available anymore
+    pub fn record(window: &Window) -> Result<()> {
+        if !Self::is_enabled_for(window) || window.is_minimized() || window.is_maximized() {
+            return Ok(());
+        }
+        let Some(key) = Self::key_of(window) else {
+            return Ok(());
+        };
+        let rect = window.inner_rect()?;
+        let mut positions = trace_lock!(POSITIONS);
+        positions.insert(key, rect);
+        Self::save(&positions)
+    }
+
+    /// Moves the window to its last known rect, only once per window
+    pub fn restore(window: &Window) -> Result<()> {
+        if !Self::is_enabled_for(window)
+            || window.is_minimized()
+            || window.is_maximized()
+            || !trace_lock!(RESTORED).insert(window.address())
+        {
+            return Ok(());
+        }
+        let Some(rect) =
+            Self::key_of(window).and_then(|key| trace_lock!(POSITIONS).get(&key).cloned())
+        else {
+            return Ok(());
+        };
+        // the monitor where it was could be disconnected now
+        if monitor_for_rect(&RECT::from(rect.clone()), &MonitorGeometry::all()?).is_none() {
+            return Ok(());
+        }
+        set_window_position(window.address(), rect)
+    }
+
+    pub fn forget(window: &Window) {
+        trace_lock!(RESTORED).remove(&window.address());
+    }
+}
@@ -0,0 +1,233 @@
+use evalexpr::{context_map, eval_with_context};
+use itertools::Itertools;
+use seelen_core::state::{
+    WindowManagerLayout, WmArea, WmLayoutError, WmLayoutPreview, WmLayoutValidation, WmNode,
+};
+
+use crate::windows_api::window::Window;
+
+use super::node_impl::WmNodeImpl;
+
+/// Parses a layout file (json or yaml) and checks the values that serde can't validate
+pub fn validate_layout(content: &str) -> WmLayoutValidation {
+    let parsed: Result<WindowManagerLayout, String> = match content.trim_start().starts_with('{') {
+        true => serde_json::from_str(content).map_err(|e| e.to_string()),
+        false => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+    };
+
+    let layout = match parsed {
+        Ok(layout) => layout,
+        Err(message) => {
+            return WmLayoutValidation {
+                layout: None,
+                errors: vec![WmLayoutError {
+                    path: String::new(),
+                    message,
+                }],
+            }
+        }
+    };
+
+    let mut errors = Vec::new();
+    let mut fallbacks = Vec::new();
+    validate_node(&layout.structure, "structure", &mut errors, &mut fallbacks);
+    // windows are added to the first fallback found, the others never receive windows
+    for path in fallbacks.iter().skip(1) {
+        errors.push(error(path, "Only the first Fallback node receives windows"));
+    }
+
+    for (idx, zone) in layout.zones.iter().enumerate() {
+        let in_range = |start: f32, size: f32| {
+            (0.0..=1.0).contains(&start) && size > 0.0 && start + size <= 1.0 + f32::EPSILON
+        };
+        if !in_range(zone.x, zone.width) || !in_range(zone.y, zone.height) {
+            errors.push(error(
+                &format!("zones[{}]", idx),
+                "Zones must be inside the work area, values are fractions from 0 to 1",
+            ));
+        }
+    }
+
+    WmLayoutValidation {
+        layout: Some(layout),
+        errors,
+    }
+}
+
+fn error(path: &str, message: &str) -> WmLayoutError {
+    WmLayoutError {
+        path: path.to_string(),
+        message: message.to_string(),
+    }
+}
+
+fn validate_node(
+    node: &WmNode,
+    path: &str,
+    errors: &mut Vec<WmLayoutError>,
+    fallbacks: &mut Vec<String>,
+) {
+    let grow_factor = node.grow_factor().get();
+    if !grow_factor.is_finite() || grow_factor <= 0.0 {
+        errors.push(error(
+            &format!("{}.growFactor", path),
+            "Grow factor must be a positive number",
+        ));
+    }
+
+    if let Some(condition) = node.condition() {
+        let context = context_map! { "managed" => 1, "is_reindexing" => false }
+            .expect("Failed to create context");
+        if let Err(err) = eval_with_context(condition, &context).and_then(|v| v.as_boolean()) {
+            errors.push(error(&format!("{}.condition", path), &err.to_string()));
+        }
+    }
+
+    match node {
+        WmNode::Vertical(n) => validate_children(&n.children, path, errors, fallbacks),
+        WmNode::Horizontal(n) => validate_children(&n.children, path, errors, fallbacks),
+        WmNode::Fallback(_) => fallbacks.push(path.to_string()),
+        WmNode::Leaf(_) | WmNode::Stack(_) => {}
+    }
+}
+
+fn validate_children(
+    children: &[WmNode],
+    path: &str,
+    errors: &mut Vec<WmLayoutError>,
+    fallbacks: &mut Vec<String>,
+) {
+    if children.is_empty() {
+        errors.push(error(
+            &format!("{}.children", path),
+            "Containers must have at least one child",
+        ));
+    }
+    // same traversal order used to add the windows
+    for (idx, child) in children
+        .iter()
+        .enumerate()
+        .sorted_by_key(|(_, child)| child.priority())
+    {
+        validate_node(
+            child,
+            &format!("{}.children[{}]", path, idx),
+            errors,
+            fallbacks,
+        );
+    }
+}
+
+/// Adds the given amount of fake windows to the layout using the same logic as the tiler
+pub fn preview_layout(layout: &WindowManagerLayout, windows: usize) -> WmLayoutPreview {
+    let mut tree = WmNodeImpl::new(layout.structure.clone());
+    let mut unplaced = Vec::new();
+    for index in 1..=windows {
+        let residual = tree.try_add_window(&Window::from(index as isize));
+        unplaced.extend(residual.into_iter().map(|handle| handle as usize));
+    }
+    unplaced.sort();
+
+    let mut placed = Vec::new();
+    let full = WmArea {
+        x: 0.0,
+        y: 0.0,
+        width: 1.0,
+        height: 1.0,
+    };
+    tree.inner().place(full, 0.0, &mut placed);
+    placed.sort_by_key(|window| window.index);
+
+    WmLayoutPreview {
+        windows: placed,
+        unplaced,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUNDLED: [&str; 4] = [
+        include_str!("../../../static/layouts/BSP.json"),
+        include_str!("../../../static/layouts/Grid.json"),
+        include_str!("../../../static/layouts/Tall.json"),
+        include_str!("../../../static/layouts/Wide.json"),
+    ];
+
+    #[test]
+    fn test_bundled_layouts_are_valid() {
+        for content in BUNDLED {
+            let validation = validate_layout(content);
+            assert!(validation.layout.is_some());
+            assert_eq!(validation.errors, vec![]);
+        }
+    }
+
+    #[test]
+    fn test_validate_layout_errors() {
+        let content = r#"
+structure:
+  type: Horizontal
+  children:
+    - type: Fallback
+    - type: Leaf
+      growFactor: 0
+      condition: "managed >"
+    - type: Vertical
+      children: []
+    - type: Fallback
+zones:
+  - { x: 0.5, y: 0, width: 0.6, height: 1 }
+"#;
+        let validation = validate_layout(content);
+        let paths = validation
+            .errors
+            .iter()
+            .map(|e| e.path.as_str())
+            .collect_vec();
+        assert_eq!(
+            paths,
+            vec![
+                "structure.children[1].growFactor",
+                "structure.children[1].condition",
+                "structure.children[2].children",
+                "structure.children[3]",
+                "zones[0]",
+            ]
+        );
+
+        let validation = validate_layout("structure: [");
+        assert!(validation.layout.is_none());
+        assert_eq!(validation.errors.len(), 1);
+    }
+
+    #[test]
+    fn test_preview_layout() {
+        let layout = validate_layout(BUNDLED[2]).layout.unwrap();
+        let preview = preview_layout(&layout, 3);
+        let rects = preview
+            .windows
+            .iter()
+            .map(|w| (w.index, [w.x, w.y, w.width, w.height]))
+            .collect_vec();
+        let expected = [
+            (1, [0.0, 0.0, 0.6, 1.0]),
+            (2, [0.6, 0.0, 0.4, 0.5]),
+            (3, [0.6, 0.5, 0.4, 0.5]),
+        ];
+        assert_eq!(rects.len(), expected.len());
+        for ((index, rect), (expected_index, expected_rect)) in rects.iter().zip(expected) {
+            assert_eq!(*index, expected_index);
+            for (value, expected) in rect.iter().zip(expected_rect) {
+                assert!((value - expected).abs() < 1e-5, "{:?}", rects);
+            }
+        }
+        assert!(preview.unplaced.is_empty());
+
+        // tall has 5 leaves and no fallback
+        let preview = preview_layout(&layout, 6);
+        assert_eq!(preview.windows.len(), 5);
+        assert_eq!(preview.unplaced, vec![6]);
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::{HashMap, HashSet};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::state::WmArea;
+use winreg::{
+    enums::{HKEY_CURRENT_USER, KEY_READ, KEY_WRITE},
+    RegKey,
+};
+
+use crate::{
+    error_handler::Result, state::application::FULL_STATE, trace_lock, windows_api::window::Window,
+};
+
+use super::{cli::Axis, history::WmCommand, instance::WindowManagerV2, state::WM_STATE};
+
+const EXPLORER_ADVANCED: &str = r"Software\Microsoft\Windows\CurrentVersion\Explorer\Advanced";
+/// native snap layouts shown on hover of the maximize button and at the top of the monitor
+const FLYOUT_VALUES: [&str; 2] = ["EnableSnapAssistFlyout", "EnableSnapBar"];
+/// size differences below this are not adopted, to ignore rounding and gaps
+const TOLERANCE: f32 = 0.02;
+
+lazy_static! {
+    /// managed windows currently snapped by Windows and already adopted into the layout
+    static ref SNAPPED: Mutex<HashSet<isize>> = Mutex::new(HashSet::new());
+    /// values of the registry before replacing the flyout, `None` if they were not set
+    static ref REPLACED_FLYOUT: Mutex<Option<HashMap<&'static str, Option<u32>>>> = Mutex::new(None);
+}
+
+/// Cooperation with the native snap layouts of Windows
+pub struct WmSnap;
+
+impl WmSnap {
+    fn is_interop_enabled() -> bool {
+        FULL_STATE.load().settings.window_manager.snap.interop
+    }
+
+    /// Size of the tile of the window as fraction of the work area
+    fn tile_size(window: &Window) -> Option<(f32, f32)> {
+        let state = trace_lock!(WM_STATE);
+        let (monitor_id, workspace_id) = state.locate(window)?;
+        let root = state
+            .get_workspace(&monitor_id, &workspace_id)?
+            .get_root_node()?
+            .clone();
+        drop(state);
+
+        let full = WmArea {
+            x: 0.0,
+            y: 0.0,
+            width: 1.0,
+            height: 1.0,
+        };
+        let mut placed = Vec::new();
+        root.place(full, 0.0, &mut placed);
+        placed
+            .into_iter()
+            .find(|tile| tile.index == window.address() as usize)
+            .map(|tile| (tile.width, tile.height))
+    }
+
+    /// Resizes the tile of the window to the size given by the native snap, as a single operation
+    /// so it can be undone like any resize.
+    fn adopt(window: &Window) -> Result<()> {
+        let Some((tile_width, tile_height)) = Self::tile_size(window) else {
+            return Ok(());
+        };
+        let work_area = window.monitor().work_area()?;
+        let rect = window.inner_rect()?;
+        let width = (rect.right - rect.left) as f32 / (work_area.right - work_area.left) as f32;
+        let height = (rect.bottom - rect.top) as f32 / (work_area.bottom - work_area.top) as f32;
+
+        let mut changes = Vec::new();
+        for (axis, snapped, tile) in [
+            (Axis::Horizontal, width, tile_width),
+            (Axis::Vertical, height, tile_height),
+        ] {
+            if (snapped - tile).abs() > TOLERANCE && tile > 0.0 {
+                changes.push((axis, (snapped / tile - 1.0) * 100.0));
+            }
+        }
+
+        log::trace!("adopting snapped window {:?}: {:?}", window, changes);
+        if changes.is_empty() {
+            return Self::force_retiling();
+        }
+        Self::execute(WmCommand::Resize {
+            window: window.address(),
+            changes,
+            relative: true,
+        })
+    }
+
+    /// Called on location changes and at the end of a drag, adopts the window the first time
+    /// it is found snapped. Returns true if the window is snapped.
+    pub fn on_window_moved(window: &Window) -> Result<bool> {
+        if !Self::is_interop_enabled() || !WindowManagerV2::is_managed(window) {
+            return Ok(false);
+        }
+        if !window.is_arranged() {
+            trace_lock!(SNAPPED).remove(&window.address());
+            return Ok(false);
+        }
+        if trace_lock!(SNAPPED).insert(window.address()) {
+            Self::adopt(window)?;
+        }
+        Ok(true)
+    }
+
+    pub fn forget(window: &Window) {
+        trace_lock!(SNAPPED).remove(&window.address());
+    }
+
+    fn explorer_key() -> Result<RegKey> {
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        Ok(hkcu.open_subkey_with_flags(EXPLORER_ADVANCED, KEY_READ | KEY_WRITE)?)
+    }
+
+    /// Disables or restores the native snap flyout according to the settings
+    pub fn refresh() -> Result<()> {
+        let settings = &FULL_STATE.load().settings.window_manager;
+        let replace = settings.enabled && settings.snap.replace_flyout;
+        let mut replaced = trace_lock!(REPLACED_FLYOUT);
+        match (replace, replaced.is_some()) {
+            (true, false) => {
+                let key = Self::explorer_key()?;
+                let mut previous = HashMap::new();
+                for name in FLYOUT_VALUES {
+                    previous.insert(name, key.get_value::<u32, _>(name).ok());
+                    key.set_value(name, &0u32)?;
+                }
+                *replaced = Some(previous);
+            }
+            (false, true) => {
+                drop(replaced);
+                Self::restore()?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Leaves the native snap flyout as it was before, used on exit
+    pub fn restore() -> Result<()> {
+        let Some(previous) = trace_lock!(REPLACED_FLYOUT).take() else {
+            return Ok(());
+        };
+        let key = Self::explorer_key()?;
+        for (name, value) in previous {
+            match value {
+                Some(value) => key.set_value(name, &value)?,
+                None => key.delete_value(name)?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,12 +1,12 @@
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, HashSet},
     sync::Arc,
 };
 
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
-use seelen_core::state::{NoFallbackBehavior, WManagerLayoutInfo, WmNode};
+use seelen_core::state::{NoFallbackBehavior, WManagerLayoutInfo, WmNode, WmZone};
 
 use crate::{
     error_handler::Result,
@@ -32,6 +32,7 @@ pub struct WmV2StateWorkspace {
     root: Option<WmNodeImpl>,
     layout_info: Option<WManagerLayoutInfo>,
     no_fallback_behavior: NoFallbackBehavior,
+    zones: Vec<WmZone>,
 }
 
 #[derive(Debug, Default)]
@@ -43,6 +44,19 @@ pub struct WmV2StateMonitor {
 #[derive(Debug, Default)]
 pub struct WmV2State {
     pub monitors: HashMap<String, WmV2StateMonitor>,
+    /// windows excluded from tiling by the user
+    pub floating: HashSet<isize>,
+    /// windows excluded from tiling while fullscreen, see `FullscreenPolicy::never_tile`
+    pub fullscreen: HashSet<isize>,
+}
+
+fn visit_nodes<'a>(node: &'a WmNode, f: &mut impl FnMut(&'a WmNode)) {
+    f(node);
+    match node {
+        WmNode::Vertical(inner) => inner.children.iter().for_each(|n| visit_nodes(n, f)),
+        WmNode::Horizontal(inner) => inner.children.iter().for_each(|n| visit_nodes(n, f)),
+        _ => {}
+    }
 }
 
 impl WmV2StateWorkspace {
@@ -51,6 +65,7 @@ impl WmV2StateWorkspace {
             layout_info: None,
             root: None,
             no_fallback_behavior: NoFallbackBehavior::Float,
+            zones: Vec::new(),
         };
 
         let settings = FULL_STATE.load();
@@ -59,6 +74,7 @@ impl WmV2StateWorkspace {
             workspace.layout_info = Some(l.info);
             workspace.root = Some(WmNodeImpl::new(l.structure));
             workspace.no_fallback_behavior = l.no_fallback_behavior;
+            workspace.zones = l.zones;
         }
 
         workspace
@@ -68,6 +84,10 @@ impl WmV2StateWorkspace {
         self.root.as_ref().map(|n| n.inner())
     }
 
+    pub fn zones(&self) -> &[WmZone] {
+        &self.zones
+    }
+
     pub fn add_window(&mut self, window: &Window) {
         if let Some(node) = &mut self.root {
             let residual = node.try_add_window(window);
@@ -92,10 +112,51 @@ impl WmV2StateWorkspace {
         self.root.as_ref().map_or(false, |n| n.contains(window))
     }
 
+    /// Windows on the layout
+    pub fn handles(&self) -> Vec<isize> {
+        let mut handles = Vec::new();
+        if let Some(root) = &self.root {
+            visit_nodes(root.inner(), &mut |node| match node {
+                WmNode::Leaf(leaf) => handles.extend(leaf.handle),
+                WmNode::Stack(stack) => handles.extend(&stack.handles),
+                WmNode::Fallback(fallback) => handles.extend(&fallback.handles),
+                _ => {}
+            });
+        }
+        handles
+    }
+
     pub fn trace_to(&self, window: &Window) -> Vec<&WmNode> {
         self.root.as_ref().map_or(vec![], |n| n.trace(window))
     }
 
+    /// Grow factors of all the nodes in pre-order, the structure of the layout never changes
+    /// so these can be restored later with `set_sizes`
+    pub fn sizes(&self) -> Vec<f32> {
+        let mut sizes = Vec::new();
+        if let Some(root) = &self.root {
+            visit_nodes(root.inner(), &mut |node| {
+                sizes.push(node.grow_factor().get())
+            });
+        }
+        sizes
+    }
+
+    pub fn set_sizes(&self, sizes: &[f32]) -> Result<()> {
+        let Some(root) = &self.root else {
+            return Ok(());
+        };
+        let mut nodes = Vec::new();
+        visit_nodes(root.inner(), &mut |node| nodes.push(node.grow_factor()));
+        if nodes.len() != sizes.len() {
+            return Err("The layout of the workspace has changed".into());
+        }
+        for (node, size) in nodes.into_iter().zip(sizes) {
+            node.set(*size);
+        }
+        Ok(())
+    }
+
     pub fn get_node_at_point(&mut self, point: &Point) -> Option<&mut WmNode> {
         if let Some(root) = &mut self.root {
             return root.get_node_at_point(point).ok()?;
@@ -171,6 +232,25 @@ impl WmV2State {
         Ok(())
     }
 
+    /// Removes the monitors that are no longer connected, returning the windows tiled on them
+    pub fn remove_disconnected(&mut self) -> Result<Vec<isize>> {
+        let connected = MonitorEnumerator::get_all()?
+            .into_iter()
+            .filter_map(|hmonitor| WindowsApi::monitor_name(hmonitor).ok())
+            .collect_vec();
+        let mut orphaned = Vec::new();
+        self.monitors.retain(|id, monitor| {
+            if connected.contains(id) {
+                return true;
+            }
+            for workspace in monitor.workspaces.values() {
+                orphaned.extend(workspace.handles());
+            }
+            false
+        });
+        Ok(orphaned)
+    }
+
     pub fn get_monitor_mut(&mut self, monitor_id: &str) -> Option<&mut WmV2StateMonitor> {
         self.monitors.get_mut(monitor_id)
     }
@@ -179,6 +259,25 @@ impl WmV2State {
         self.trace_to(window).is_some()
     }
 
+    pub fn get_workspace(
+        &self,
+        monitor_id: &str,
+        workspace_id: &str,
+    ) -> Option<&WmV2StateWorkspace> {
+        self.monitors.get(monitor_id)?.workspaces.get(workspace_id)
+    }
+
+    /// Monitor and workspace ids of the workspace containing the window
+    pub fn locate(&self, window: &Window) -> Option<(String, String)> {
+        self.monitors.iter().find_map(|(monitor_id, monitor)| {
+            monitor
+                .workspaces
+                .iter()
+                .find(|(_, w)| w.contains(window))
+                .map(|(workspace_id, _)| (monitor_id.clone(), workspace_id.clone()))
+        })
+    }
+
     pub fn trace_to(
         &self,
         window: &Window,
@@ -0,0 +1,309 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::{
+    handlers::SeelenEvent,
+    rect::Rect,
+    state::{WmZone, WmZonesOverlay, ZonesModifier},
+};
+use tauri::Emitter;
+use windows::Win32::Foundation::RECT;
+
+use crate::{
+    error_handler::Result,
+    modules::{
+        input::{domain::Point, Mouse},
+        keybindings::domain::Modifiers,
+        virtual_desk::get_vd_manager,
+    },
+    seelen::get_app_handle,
+    seelen_weg::SeelenWeg,
+    state::application::FULL_STATE,
+    trace_lock,
+    windows_api::{monitor::Monitor, window::Window, WindowsApi},
+};
+
+use super::{handler::set_window_position, instance::WindowManagerV2, state::WM_STATE};
+
+/// distance in physical pixels to the top of the monitor to suggest the zones
+const SUGGEST_EDGE: i32 = 8;
+
+/// read by the mouse hook on every frame, so it lives outside of the drag mutex
+static DRAGGING: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    static ref DRAG: Mutex<Option<ZonesDrag>> = Mutex::new(None);
+}
+
+#[derive(Debug)]
+struct ZonesDrag {
+    window: Window,
+    initial_rect: RECT,
+    /// `None` until the window changes, resizing a window never snaps it to a zone
+    is_move: Option<bool>,
+    /// monitor where the overlay is shown and the zone under the cursor
+    shown: Option<(String, Option<usize>)>,
+    /// the window was dragged to the top of the monitor, replacing the native snap flyout
+    suggested: bool,
+}
+
+#[derive(Debug)]
+struct ZonesTarget {
+    monitor_id: String,
+    work_area: RECT,
+    zones: Vec<WmZone>,
+    active: Option<usize>,
+}
+
+/// Snapping of dragged windows to the static zones of the layout
+pub struct WmZones;
+
+impl WmZones {
+    fn is_modifier_held() -> bool {
+        let held = Modifiers::current();
+        match FULL_STATE.load().settings.window_manager.zones.modifier {
+            ZonesModifier::Shift => held.shift,
+            ZonesModifier::Ctrl => held.ctrl,
+            ZonesModifier::Alt => held.alt,
+        }
+    }
+
+    fn emit_overlay(monitor_id: &str, overlay: Option<WmZonesOverlay>) -> Result<()> {
+        get_app_handle().emit_to(
+            format!("{}/{}", WindowManagerV2::TARGET, monitor_id),
+            SeelenEvent::WMSetZonesOverlay,
+            overlay,
+        )?;
+        Ok(())
+    }
+
+    /// Zones of the current workspace on the monitor under the point
+    fn target_at(point: &Point) -> Result<Option<ZonesTarget>> {
+        let monitor = Monitor::from(point);
+        let monitor_id = monitor.id()?;
+        let workspace_id = get_vd_manager().get_current()?.id();
+        let zones = match trace_lock!(WM_STATE).get_monitor_mut(&monitor_id) {
+            Some(m) => m.get_workspace_mut(&workspace_id).zones().to_vec(),
+            None => return Ok(None),
+        };
+        if zones.is_empty() {
+            return Ok(None);
+        }
+        let work_area = monitor.work_area()?;
+        let active = zone_at(&work_area, &zones, point.get_x(), point.get_y());
+        Ok(Some(ZonesTarget {
+            monitor_id,
+            work_area,
+            zones,
+            active,
+        }))
+    }
+
+    pub fn is_dragging() -> bool {
+        DRAGGING.load(Ordering::Acquire)
+    }
+
+    pub fn start_drag(window: &Window) -> Result<()> {
+        let settings = &FULL_STATE.load().settings.window_manager;
+        if !(settings.zones.enabled || settings.snap.replace_flyout)
+            || !SeelenWeg::should_be_added(window.hwnd())
+        {
+            return Ok(());
+        }
+        *trace_lock!(DRAG) = Some(ZonesDrag {
+            window: *window,
+            initial_rect: RECT::from(window.inner_rect()?),
+            is_move: None,
+            shown: None,
+            suggested: false,
+        });
+        DRAGGING.store(true, Ordering::Release);
+        Ok(())
+    }
+
+    /// Called by the mouse hook on each frame while a window is being dragged
+    pub fn on_cursor_move(point: &Point) -> Result<()> {
+        let mut guard = trace_lock!(DRAG);
+        let Some(drag) = guard.as_mut() else {
+            return Ok(());
+        };
+
+        if drag.is_move.is_none() {
+            let rect = RECT::from(drag.window.inner_rect()?);
+            if rect != drag.initial_rect {
+                drag.is_move = Some(size_of(&rect) == size_of(&drag.initial_rect));
+            }
+        }
+
+        if drag.is_move == Some(true)
+            && !drag.suggested
+            && FULL_STATE
+                .load()
+                .settings
+                .window_manager
+                .snap
+                .replace_flyout
+        {
+            let monitor = WindowsApi::monitor_rect(Monitor::from(point).raw())?;
+            drag.suggested = point.get_y() - monitor.top < SUGGEST_EDGE;
+        }
+
+        let wants_zones = drag.suggested || Self::is_modifier_held();
+        let target = match drag.is_move == Some(true) && wants_zones {
+            true => Self::target_at(point)?,
+            false => None,
+        };
+        let shown = target.as_ref().map(|t| (t.monitor_id.clone(), t.active));
+        if shown == drag.shown {
+            return Ok(());
+        }
+
+        if let Some((previous, _)) = &drag.shown {
+            if shown.as_ref().map(|(id, _)| id) != Some(previous) {
+                Self::emit_overlay(previous, None)?;
+            }
+        }
+        if let Some(target) = target {
+            Self::emit_overlay(
+                &target.monitor_id,
+                Some(WmZonesOverlay {
+                    zones: target.zones,
+                    active: target.active,
+                }),
+            )?;
+        }
+        drag.shown = shown;
+        Ok(())
+    }
+
+    /// Hides the overlay and snaps the window if it was dropped on a zone, returns true if snapped
+    pub fn end_drag(window: &Window) -> Result<bool> {
+        let Some(drag) = trace_lock!(DRAG).take() else {
+            return Ok(false);
+        };
+        DRAGGING.store(false, Ordering::Release);
+
+        if let Some((monitor_id, _)) = &drag.shown {
+            Self::emit_overlay(monitor_id, None)?;
+        }
+        if drag.window != *window
+            || drag.is_move != Some(true)
+            || !(drag.suggested || Self::is_modifier_held())
+        {
+            return Ok(false);
+        }
+
+        let Some(target) = Self::target_at(&Mouse::get_cursor_pos()?)? else {
+            return Ok(false);
+        };
+        let Some(zone) = target.active.and_then(|idx| target.zones.get(idx)) else {
+            return Ok(false);
+        };
+
+        // snapped windows leave the tiling layout, otherwise the next retiling would move them back
+        if WindowManagerV2::is_managed(window) {
+            trace_lock!(WM_STATE).floating.insert(window.address());
+            WindowManagerV2::remove(window)?;
+        }
+        set_window_position(
+            window.address(),
+            Rect::from(zone_rect(&target.work_area, zone)),
+        )?;
+        Ok(true)
+    }
+}
+
+fn size_of(rect: &RECT) -> (i32, i32) {
+    (rect.right - rect.left, rect.bottom - rect.top)
+}
+
+/// Converts the relative zone to physical pixels inside the work area
+fn zone_rect(work_area: &RECT, zone: &WmZone) -> RECT {
+    let width = (work_area.right - work_area.left) as f32;
+    let height = (work_area.bottom - work_area.top) as f32;
+    RECT {
+        left: work_area.left + (width * zone.x).round() as i32,
+        top: work_area.top + (height * zone.y).round() as i32,
+        right: work_area.left + (width * (zone.x + zone.width)).round() as i32,
+        bottom: work_area.top + (height * (zone.y + zone.height)).round() as i32,
+    }
+}
+
+/// Index of the zone under the point, the smallest one wins if zones overlap
+fn zone_at(work_area: &RECT, zones: &[WmZone], x: i32, y: i32) -> Option<usize> {
+    let width = (work_area.right - work_area.left) as f32;
+    let height = (work_area.bottom - work_area.top) as f32;
+    if width <= 0.0 || height <= 0.0 {
+        return None;
+    }
+    let x = (x - work_area.left) as f32 / width;
+    let y = (y - work_area.top) as f32 / height;
+    zones
+        .iter()
+        .enumerate()
+        .filter(|(_, zone)| zone.contains(x, y))
+        .min_by(|(_, a), (_, b)| (a.width * a.height).total_cmp(&(b.width * b.height)))
+        .map(|(idx, _)| idx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zone(x: f32, y: f32, width: f32, height: f32) -> WmZone {
+        WmZone {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// 1080p monitor on the right of the primary one, with the toolbar reserving 30px
+    fn work_area() -> RECT {
+        RECT {
+            left: 1920,
+            top: 30,
+            right: 3840,
+            bottom: 1080,
+        }
+    }
+
+    #[test]
+    fn test_zone_rect() {
+        let rect = zone_rect(&work_area(), &zone(0.5, 0.0, 0.5, 0.5));
+        assert_eq!(rect.left, 2880);
+        assert_eq!(rect.top, 30);
+        assert_eq!(rect.right, 3840);
+        assert_eq!(rect.bottom, 555);
+
+        let thirds = [
+            zone(0.0, 0.0, 1.0 / 3.0, 1.0),
+            zone(1.0 / 3.0, 0.0, 1.0 / 3.0, 1.0),
+        ];
+        // adjacent zones share the edge, no gaps due to rounding
+        assert_eq!(
+            zone_rect(&work_area(), &thirds[0]).right,
+            zone_rect(&work_area(), &thirds[1]).left
+        );
+    }
+
+    #[test]
+    fn test_zone_at() {
+        let zones = [
+            zone(0.0, 0.0, 0.5, 1.0),
+            zone(0.5, 0.0, 0.5, 1.0),
+            // centered zone over the other two
+            zone(0.25, 0.25, 0.5, 0.5),
+        ];
+        let find = |x, y| zone_at(&work_area(), &zones, x, y);
+
+        assert_eq!(find(2000, 100), Some(0));
+        assert_eq!(find(3800, 1000), Some(1));
+        assert_eq!(find(2880, 555), Some(2));
+        // outside of the work area
+        assert_eq!(find(100, 100), None);
+        assert_eq!(find(2000, 10), None);
+    }
+}
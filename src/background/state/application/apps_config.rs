@@ -36,4 +36,21 @@ impl FullState {
 
         None
     }
+
+    /// All the configs matching the window in priority order, only the first one is applied
+    pub fn get_app_configs_matching_window(&self, hwnd: HWND) -> Vec<&AppConfig> {
+        if let (title, Ok(path), Ok(exe), Ok(class)) = (
+            WindowsApi::get_window_text(hwnd),
+            WindowsApi::exe_path(hwnd),
+            WindowsApi::exe(hwnd),
+            WindowsApi::get_class(hwnd),
+        ) {
+            return self
+                .settings_by_app
+                .iter()
+                .filter(|app| app.identifier.validate(&title, &class, &exe, &path))
+                .collect();
+        }
+        Vec::new()
+    }
 }
@@ -1,29 +1,64 @@
 use itertools::Itertools;
-use seelen_core::{handlers::SeelenEvent, state::WegItems};
-use tauri::Emitter;
+use seelen_core::{
+    handlers::SeelenEvent,
+    state::{SettingsConflict, WegItems},
+};
+use serde::Serialize;
 
 use crate::{
     error_handler::Result,
-    seelen::{get_app_handle, SEELEN},
+    modules::{event_registry::EventRegistry, perf::PerfMonitor},
+    seelen::SEELEN,
     trace_lock,
 };
 
 use super::FullState;
 
+fn emit<S: Serialize + Clone>(event: &str, payload: S) -> Result<()> {
+    EventRegistry::emit(event, payload)
+}
+
 impl FullState {
     pub(super) fn emit_settings(&self) -> Result<()> {
-        get_app_handle().emit(SeelenEvent::StateSettingsChanged, self.settings())?;
-        trace_lock!(SEELEN).on_settings_change()?;
+        emit(SeelenEvent::StateSettingsChanged, self.settings())?;
+        PerfMonitor::measure("settings change", || {
+            trace_lock!(SEELEN).on_settings_change()
+        })?;
+        Ok(())
+    }
+
+    pub(super) fn emit_settings_conflict(conflict: &SettingsConflict) -> Result<()> {
+        emit(SeelenEvent::StateSettingsConflict, conflict)
+    }
+
+    pub(super) fn emit_locked_settings() -> Result<()> {
+        emit(
+            SeelenEvent::StateLockedSettingsChanged,
+            Self::locked_settings_pointers(),
+        )
+    }
+
+    /// Emits all the state again without applying it, used when the UI could be out of sync
+    pub fn emit_all(&self) -> Result<()> {
+        emit(SeelenEvent::StateSettingsChanged, self.settings())?;
+        self.emit_weg_items(&trace_lock!(self.weg_items))?;
+        self.emit_themes()?;
+        self.emit_placeholders()?;
+        self.emit_layouts()?;
+        self.emit_settings_by_app()?;
+        self.emit_history()?;
+        self.emit_widgets()?;
+        self.emit_icon_packs()?;
         Ok(())
     }
 
     pub fn emit_weg_items(&self, items: &WegItems) -> Result<()> {
-        get_app_handle().emit(SeelenEvent::StateWegItemsChanged, items)?;
+        emit(SeelenEvent::StateWegItemsChanged, items)?;
         Ok(())
     }
 
     pub(super) fn emit_themes(&self) -> Result<()> {
-        get_app_handle().emit(
+        emit(
             SeelenEvent::StateThemesChanged,
             self.themes().values().collect_vec(),
         )?;
@@ -31,7 +66,7 @@ impl FullState {
     }
 
     pub(super) fn emit_placeholders(&self) -> Result<()> {
-        get_app_handle().emit(
+        emit(
             SeelenEvent::StatePlaceholdersChanged,
             self.placeholders().values().collect_vec(),
         )?;
@@ -39,7 +74,7 @@ impl FullState {
     }
 
     pub(super) fn emit_layouts(&self) -> Result<()> {
-        get_app_handle().emit(
+        emit(
             SeelenEvent::StateLayoutsChanged,
             self.layouts().values().collect_vec(),
         )?;
@@ -47,7 +82,7 @@ impl FullState {
     }
 
     pub(super) fn emit_settings_by_app(&self) -> Result<()> {
-        get_app_handle().emit(
+        emit(
             SeelenEvent::StateSettingsByAppChanged,
             self.settings_by_app(),
         )?;
@@ -55,12 +90,20 @@ impl FullState {
     }
 
     pub(super) fn emit_history(&self) -> Result<()> {
-        get_app_handle().emit(SeelenEvent::StateHistoryChanged, self.history())?;
+        emit(SeelenEvent::StateHistoryChanged, self.history())?;
+        Ok(())
+    }
+
+    pub(super) fn emit_widgets(&self) -> Result<()> {
+        emit(
+            SeelenEvent::StateWidgetsChanged,
+            self.widgets().values().collect_vec(),
+        )?;
         Ok(())
     }
 
-    pub(super) fn emit_icon_packs(&self) -> Result<()> {
-        get_app_handle().emit(
+    pub fn emit_icon_packs(&self) -> Result<()> {
+        emit(
             SeelenEvent::StateIconPacksChanged,
             trace_lock!(self.icon_packs()).values().collect_vec(),
         )?;
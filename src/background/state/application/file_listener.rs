@@ -0,0 +1,80 @@
+use std::{collections::HashSet, path::PathBuf, sync::atomic::Ordering};
+
+use clap::Command;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+
+use crate::{error_handler::Result, get_subcommands, trace_lock};
+
+use super::{FILE_LISTENER_PAUSED, FULL_STATE};
+
+lazy_static! {
+    /// files changed while the listener was paused
+    static ref CHANGED_WHILE_PAUSED: Mutex<HashSet<PathBuf>> = Mutex::new(HashSet::new());
+}
+
+get_subcommands![
+    /** Stops reloading the config files when they change, useful for bulk edits. */
+    Pause,
+    /** Reloads once the files changed while paused and listens again. */
+    Resume,
+];
+
+/// Control of the watcher of the config files, while paused the changes are only recorded
+/// so edits of many files (like a checkout of a dotfiles repo) are applied in a single reload.
+pub struct FileListener;
+
+impl FileListener {
+    pub const CLI_IDENTIFIER: &'static str = "state";
+
+    pub fn get_cli() -> Command {
+        Command::new(Self::CLI_IDENTIFIER)
+            .about("Control of the reload of the config files on external edits.")
+            .arg_required_else_help(true)
+            .subcommands(SubCommand::commands())
+    }
+
+    pub fn process(matches: &clap::ArgMatches) -> Result<()> {
+        let subcommand = SubCommand::try_from(matches)?;
+        match subcommand {
+            SubCommand::Pause => Self::pause(),
+            SubCommand::Resume => {
+                Self::resume()?;
+            }
+        };
+        Ok(())
+    }
+
+    pub fn is_paused() -> bool {
+        FILE_LISTENER_PAUSED.load(Ordering::Acquire)
+    }
+
+    pub fn pause() {
+        if !FILE_LISTENER_PAUSED.swap(true, Ordering::AcqRel) {
+            log::info!("Files listener paused");
+        }
+    }
+
+    pub(super) fn record(paths: Vec<PathBuf>) {
+        trace_lock!(CHANGED_WHILE_PAUSED).extend(paths);
+    }
+
+    /// Applies the changes made while paused, returns the changed files
+    pub fn resume() -> Result<Vec<PathBuf>> {
+        if !FILE_LISTENER_PAUSED.swap(false, Ordering::AcqRel) {
+            return Ok(Vec::new());
+        }
+
+        let mut changed: Vec<PathBuf> = trace_lock!(CHANGED_WHILE_PAUSED).drain().collect();
+        changed.sort();
+        log::info!(
+            "Files listener resumed, {} files changed while paused",
+            changed.len()
+        );
+        if !changed.is_empty() {
+            let mut state = FULL_STATE.load().cloned();
+            state.process_changes(&changed)?;
+        }
+        Ok(changed)
+    }
+}
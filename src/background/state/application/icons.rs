@@ -51,25 +51,53 @@ impl FullState {
         Ok(())
     }
 
+    fn save_system_icon_pack(&self, icon_pack: &IconPack) -> Result<()> {
+        let folder = self.icon_packs_folder().join("system");
+        std::fs::create_dir_all(&folder)?;
+        std::fs::write(
+            folder.join("metadata.yml"),
+            serde_yaml::to_string(icon_pack)?,
+        )?;
+        Ok(())
+    }
+
+    /// Adds or replaces the icon of the key, the replaced icon file is removed
     pub fn push_and_save_system_icon(&self, key: &str, icon: &Path) -> Result<()> {
         let mut icon_packs = trace_lock!(self.icon_packs);
         let default_icon_pack = icon_packs.get_mut("system").unwrap();
-        default_icon_pack.apps.insert(
+        let previous = default_icon_pack.apps.insert(
             key.trim_start_matches("\\\\?\\").to_string(),
             icon.to_owned(),
         );
+        if let Some(previous) = previous.filter(|previous| previous != icon) {
+            let _ = std::fs::remove_file(self.icon_packs_folder().join("system").join(previous));
+        }
+        self.save_system_icon_pack(default_icon_pack)
+    }
 
+    /// Removes the extracted icon of the key or all of them if `None`, so they are extracted again
+    pub fn remove_system_icons(&self, key: Option<&str>) -> Result<()> {
+        let mut icon_packs = trace_lock!(self.icon_packs);
+        let default_icon_pack = icon_packs.get_mut("system").unwrap();
+        let removed = match key {
+            Some(key) => default_icon_pack
+                .apps
+                .remove(key.trim_start_matches("\\\\?\\"))
+                .into_iter()
+                .collect(),
+            None => std::mem::take(&mut default_icon_pack.apps)
+                .into_values()
+                .collect::<Vec<_>>(),
+        };
         let folder = self.icon_packs_folder().join("system");
-        std::fs::create_dir_all(&folder)?;
-        std::fs::write(
-            folder.join("metadata.yml"),
-            serde_yaml::to_string(default_icon_pack)?,
-        )?;
-        Ok(())
+        for icon in removed {
+            let _ = std::fs::remove_file(folder.join(icon));
+        }
+        self.save_system_icon_pack(default_icon_pack)
     }
 
-    /// Get icon pack by app user model id, filename or path
-    pub fn get_icon_by_key(&self, key: &str) -> Option<PathBuf> {
+    /// Get icon by app user model id, filename or path, returns the icon pack and the icon path
+    pub fn find_icon_by_key(&self, key: &str) -> Option<(String, PathBuf)> {
         let filename = PathBuf::from(key)
             .file_name()
             .map(|p| p.to_string_lossy().to_string());
@@ -81,11 +109,12 @@ impl FullState {
                     None => None,
                 });
                 if let Some(icon) = maybe_icon {
-                    return Some(
+                    return Some((
+                        icon_pack.info.filename.clone(),
                         self.icon_packs_folder()
                             .join(&icon_pack.info.filename)
                             .join(icon),
-                    );
+                    ));
                 }
             }
         }
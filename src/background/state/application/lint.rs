@@ -0,0 +1,378 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    error_handler::Result, modules::actions::domain::ActionCatalog,
+    seelen_wm_v2::preview::validate_layout,
+};
+
+use super::FullState;
+
+/// modules that can be styled by a theme, `theme.<target>.css` on folders
+const CSS_TARGETS: [&str; 5] = ["weg", "toolbar", "wm", "launcher", "wall"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintResourceKind {
+    Theme,
+    Placeholder,
+    Layout,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintIssue {
+    pub severity: LintSeverity,
+    /// path to the value, example: `right[0].onClick`. Empty for the whole file.
+    pub path: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintReport {
+    pub file: PathBuf,
+    pub kind: Option<LintResourceKind>,
+    pub issues: Vec<LintIssue>,
+}
+
+impl LintReport {
+    fn new(file: &Path, kind: Option<LintResourceKind>) -> Self {
+        Self {
+            file: file.to_path_buf(),
+            kind,
+            issues: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, severity: LintSeverity, path: &str, message: impl Into<String>) {
+        self.issues.push(LintIssue {
+            severity,
+            path: path.to_string(),
+            message: message.into(),
+        });
+    }
+
+    fn error(&mut self, path: &str, message: impl Into<String>) {
+        self.push(LintSeverity::Error, path, message);
+    }
+
+    fn warning(&mut self, path: &str, message: impl Into<String>) {
+        self.push(LintSeverity::Warning, path, message);
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity == LintSeverity::Error)
+    }
+}
+
+fn join_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+/// Keys present on the file but not on the loaded value are ignored by serde
+fn find_unknown_fields(raw: &Value, loaded: &Value, path: &str, report: &mut LintReport) {
+    match (raw, loaded) {
+        (Value::Object(raw), Value::Object(loaded)) => {
+            for (key, value) in raw {
+                let child = join_path(path, key);
+                match loaded.get(key) {
+                    Some(loaded) => find_unknown_fields(value, loaded, &child, report),
+                    None => report.warning(&child, "Unknown field, it will be ignored"),
+                }
+            }
+        }
+        (Value::Array(raw), Value::Array(loaded)) => {
+            for (idx, (raw, loaded)) in raw.iter().zip(loaded).enumerate() {
+                find_unknown_fields(raw, loaded, &format!("{}[{}]", path, idx), report);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The loaders errors include the backtrace, only the message is useful here
+fn loader_error(err: crate::error_handler::AppError) -> String {
+    err.to_string()
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+impl FullState {
+    fn read_raw(path: &Path) -> std::result::Result<Value, String> {
+        let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&content).map_err(|e| e.to_string())
+        } else {
+            serde_yaml::from_str(&content).map_err(|e| e.to_string())
+        }
+    }
+
+    /// Guesses the kind of a file outside of the resource folders
+    fn guess_resource_kind(path: &Path) -> Option<LintResourceKind> {
+        let parent = path.parent().and_then(|p| p.file_name())?.to_string_lossy();
+        match parent.as_ref() {
+            "themes" => return Some(LintResourceKind::Theme),
+            "placeholders" => return Some(LintResourceKind::Placeholder),
+            "layouts" => return Some(LintResourceKind::Layout),
+            _ => {}
+        }
+
+        let raw = Self::read_raw(path).ok()?;
+        if raw.get("structure").is_some() {
+            Some(LintResourceKind::Layout)
+        } else if ["left", "center", "right"]
+            .iter()
+            .any(|key| raw.get(key).is_some())
+        {
+            Some(LintResourceKind::Placeholder)
+        } else if raw.get("styles").is_some() {
+            Some(LintResourceKind::Theme)
+        } else {
+            None
+        }
+    }
+
+    /// Resources found on the path, a resource, a resource folder (`themes`, `placeholders`,
+    /// `layouts`) or a folder containing resource folders like the data folder of Seelen.
+    fn collect_resources(path: &Path, found: &mut Vec<(PathBuf, Option<LintResourceKind>)>) {
+        if path.is_file() {
+            found.push((path.to_path_buf(), Self::guess_resource_kind(path)));
+            return;
+        }
+
+        if path.join("theme.yml").exists() {
+            found.push((path.to_path_buf(), Some(LintResourceKind::Theme)));
+            return;
+        }
+
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let entries = match std::fs::read_dir(path) {
+            Ok(entries) => entries.flatten().map(|e| e.path()).collect::<Vec<_>>(),
+            Err(_) => return,
+        };
+
+        let files = entries.iter().filter(|e| e.is_file()).cloned();
+        match name.as_str() {
+            "themes" => found.extend(
+                entries
+                    .iter()
+                    .map(|e| (e.clone(), Some(LintResourceKind::Theme))),
+            ),
+            "placeholders" => found.extend(files.map(|f| (f, Some(LintResourceKind::Placeholder)))),
+            "layouts" => found.extend(files.map(|f| (f, Some(LintResourceKind::Layout)))),
+            _ => {
+                let folders = ["themes", "placeholders", "layouts"].map(|f| path.join(f));
+                if folders.iter().any(|f| f.is_dir()) {
+                    for folder in folders.iter().filter(|f| f.is_dir()) {
+                        Self::collect_resources(folder, found);
+                    }
+                } else {
+                    for file in files {
+                        let kind = Self::guess_resource_kind(&file);
+                        found.push((file, kind));
+                    }
+                }
+            }
+        }
+    }
+
+    fn lint_theme(path: &Path, report: &mut LintReport) {
+        let is_folder = path.is_dir();
+        let loaded = if is_folder {
+            Self::load_theme_from_dir(path.to_path_buf())
+        } else {
+            Self::load_theme_from_file(path.to_path_buf())
+        };
+        let theme = match loaded {
+            Ok(theme) => theme,
+            Err(err) => return report.error("", loader_error(err)),
+        };
+
+        let metadata = if is_folder {
+            path.join("theme.yml")
+        } else {
+            path.to_path_buf()
+        };
+        if let (Ok(raw), Ok(loaded)) = (Self::read_raw(&metadata), serde_json::to_value(&theme)) {
+            find_unknown_fields(&raw, &loaded, "", report);
+
+            if is_folder {
+                for target in CSS_TARGETS {
+                    let inline = raw
+                        .pointer(&format!("/styles/{}", target))
+                        .and_then(Value::as_str)
+                        .is_some_and(|css| !css.is_empty());
+                    if inline && path.join(format!("theme.{}.css", target)).exists() {
+                        report.warning(
+                            &format!("styles.{}", target),
+                            format!("Ignored, theme.{}.css is used instead", target),
+                        );
+                    }
+                }
+            }
+        }
+
+        if is_folder {
+            for entry in std::fs::read_dir(path).into_iter().flatten().flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if let Some(target) = name
+                    .strip_prefix("theme.")
+                    .and_then(|n| n.strip_suffix(".css"))
+                {
+                    if !CSS_TARGETS.contains(&target) {
+                        report.warning(
+                            "",
+                            format!(
+                                "{} is not loaded, valid targets are: {}",
+                                name,
+                                CSS_TARGETS.join(", ")
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+
+        if theme.info.display_name.is_empty() {
+            report.warning("info.displayName", "Missing display name");
+        }
+
+        let styles = &theme.styles;
+        let css = [
+            &styles.weg,
+            &styles.toolbar,
+            &styles.wm,
+            &styles.launcher,
+            &styles.wall,
+        ];
+        if css.iter().all(|css| css.trim().is_empty()) {
+            report.warning("styles", "The theme doesn't have css for any target");
+        }
+    }
+
+    fn lint_placeholder(path: &Path, report: &mut LintReport) {
+        let placeholder = match Self::load_placeholder_from_file(path.to_path_buf()) {
+            Ok(placeholder) => placeholder,
+            Err(err) => return report.error("", loader_error(err)),
+        };
+
+        let raw = match Self::read_raw(path) {
+            Ok(raw) => raw,
+            Err(err) => return report.error("", err),
+        };
+        if let Ok(loaded) = serde_json::to_value(&placeholder) {
+            find_unknown_fields(&raw, &loaded, "", report);
+        }
+
+        let catalog = ActionCatalog::build();
+        let mut ids = Vec::new();
+        for side in ["left", "center", "right"] {
+            let items = raw.get(side).and_then(Value::as_array);
+            for (idx, item) in items.into_iter().flatten().enumerate() {
+                let path = format!("{}[{}]", side, idx);
+                if item.get("onClick").is_some_and(|v| !v.is_null()) {
+                    report.warning(
+                        &format!("{}.onClick", path),
+                        "Deprecated, use onClickV2 instead",
+                    );
+                }
+                if item.get("type").and_then(Value::as_str) == Some("date") {
+                    for key in ["each", "format"] {
+                        if item.get(key).is_some() {
+                            report.warning(
+                                &format!("{}.{}", path, key),
+                                "Deprecated, the date format of the settings is used instead",
+                            );
+                        }
+                    }
+                }
+                for event in ["onClick", "onMiddleClick", "onWheel/up", "onWheel/down"] {
+                    let action_id = item
+                        .pointer(&format!("/actions/{}/action/id", event))
+                        .and_then(Value::as_str);
+                    if let Some(action_id) = action_id {
+                        if !catalog.contains(action_id) {
+                            report.error(
+                                &format!("{}.actions.{}", path, event.replace('/', ".")),
+                                format!("Unknown action {}", action_id),
+                            );
+                        }
+                    }
+                }
+                if let Some(id) = item.get("id").and_then(Value::as_str) {
+                    if ids.contains(&id) {
+                        report.warning(
+                            &format!("{}.id", path),
+                            format!("Duplicated id {}, the item will be removed", id),
+                        );
+                    }
+                    ids.push(id);
+                }
+            }
+        }
+    }
+
+    fn lint_layout(path: &Path, report: &mut LintReport) {
+        let layout = match Self::load_layout_from_file(path.to_path_buf()) {
+            Ok(layout) => layout,
+            Err(err) => return report.error("", loader_error(err)),
+        };
+
+        if let (Ok(raw), Ok(loaded)) = (Self::read_raw(path), serde_json::to_value(&layout)) {
+            find_unknown_fields(&raw, &loaded, "", report);
+        }
+
+        let content = std::fs::read_to_string(path).unwrap_or_default();
+        for error in validate_layout(&content).errors {
+            report.error(&error.path, error.message);
+        }
+    }
+
+    /// Runs the loaders of the resources found on the path and reports the problems,
+    /// used to validate community resources before publishing them.
+    pub fn lint_resources(path: &Path) -> Result<Vec<LintReport>> {
+        if !path.exists() {
+            return Err(format!("{} doesn't exist", path.display()).into());
+        }
+
+        let mut found = Vec::new();
+        Self::collect_resources(path, &mut found);
+
+        let mut reports = Vec::new();
+        for (file, kind) in found {
+            let mut report = LintReport::new(&file, kind);
+            match kind {
+                Some(LintResourceKind::Theme) => Self::lint_theme(&file, &mut report),
+                Some(LintResourceKind::Placeholder) => Self::lint_placeholder(&file, &mut report),
+                Some(LintResourceKind::Layout) => Self::lint_layout(&file, &mut report),
+                None => report.error(
+                    "",
+                    "Unknown resource, expected a theme, placeholder or layout",
+                ),
+            }
+            reports.push(report);
+        }
+        Ok(reports)
+    }
+}
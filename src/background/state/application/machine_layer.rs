@@ -0,0 +1,208 @@
+use std::path::{Path, PathBuf};
+
+use lazy_static::lazy_static;
+use serde_json::Value;
+
+use crate::{error_handler::Result, state::domain::Settings};
+
+use super::FullState;
+
+lazy_static! {
+    /// Machine level config shipped by the administrators, shared by all the users
+    pub(super) static ref MACHINE_DIR: PathBuf = std::env::var_os("ProgramData")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("C:\\ProgramData"))
+        .join("Seelen UI");
+    /// defaults of settings.json, the user file is merged over it
+    pub(super) static ref MACHINE_SETTINGS_PATH: PathBuf = MACHINE_DIR.join("settings.json");
+    /// app configurations applied after the ones of the user
+    pub(super) static ref MACHINE_APPS_PATH: PathBuf = MACHINE_DIR.join("applications.yml");
+    /// values forced over all the other layers, can not be changed by the users
+    pub(super) static ref MACHINE_LOCKED_PATH: PathBuf = MACHINE_DIR.join("locked.json");
+}
+
+/// Removes the keys of `settings` holding the same value as the machine layer, so they
+/// keep following the organization defaults instead of being pinned on the user file.
+fn strip_machine_defaults(settings: &mut Value, machine: &Value) {
+    let (Value::Object(settings_map), Value::Object(machine_map)) = (settings, machine) else {
+        return;
+    };
+    for (key, machine_value) in machine_map {
+        let Some(value) = settings_map.get_mut(key) else {
+            continue;
+        };
+        if value == machine_value {
+            settings_map.remove(key);
+            continue;
+        }
+        if value.is_object() && machine_value.is_object() {
+            strip_machine_defaults(value, machine_value);
+            if value.as_object().is_some_and(|map| map.is_empty()) {
+                settings_map.remove(key);
+            }
+        }
+    }
+}
+
+/// JSON pointers of the leaf values of `value`, objects are walked key by key
+fn leaf_pointers(value: &Value, prefix: &str, pointers: &mut Vec<String>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                let key = key.replace('~', "~0").replace('/', "~1");
+                leaf_pointers(value, &format!("{prefix}/{key}"), pointers);
+            }
+        }
+        _ => pointers.push(prefix.to_string()),
+    }
+}
+
+fn read_machine_json(path: &Path) -> Result<Value> {
+    let value: Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+    if !value.is_object() {
+        return Err("the file should be a json object".into());
+    }
+    Ok(value)
+}
+
+/// A broken file is ignored instead of discarding the user settings
+fn machine_json(path: &Path) -> Option<Value> {
+    if !path.exists() {
+        return None;
+    }
+    match read_machine_json(path) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            log::error!("Ignoring machine file {}: {}", path.display(), err);
+            None
+        }
+    }
+}
+
+impl FullState {
+    /// Settings of the machine layer
+    pub(super) fn machine_settings() -> Option<Value> {
+        machine_json(&MACHINE_SETTINGS_PATH)
+    }
+
+    /// Values forced by the administrators
+    pub(super) fn locked_settings() -> Option<Value> {
+        machine_json(&MACHINE_LOCKED_PATH)
+    }
+
+    /// JSON pointers of the settings managed by the administrators (e.g. `/fancyToolbar/height`)
+    pub fn locked_settings_pointers() -> Vec<String> {
+        let mut pointers = Vec::new();
+        if let Some(locked) = Self::locked_settings() {
+            leaf_pointers(&locked, "", &mut pointers);
+        }
+        pointers
+    }
+
+    /// Forces the locked values, the lock is ignored if it doesn't produce valid settings
+    pub(super) fn apply_locked_settings(&mut self, locked: Value) -> Result<()> {
+        let value = Self::apply_settings_overlays(serde_json::to_value(&self.settings)?, &[locked]);
+        match serde_json::from_value::<Settings>(value) {
+            Ok(mut settings) => {
+                settings.sanitize();
+                self.settings = settings;
+            }
+            Err(err) => log::error!(
+                "Ignoring locked settings {}: {}",
+                MACHINE_LOCKED_PATH.display(),
+                err
+            ),
+        }
+        Ok(())
+    }
+
+    /// Whether changing the value at `pointer` would override a locked setting
+    pub(super) fn is_locked_pointer(pointer: &str, locked: &[String]) -> bool {
+        locked.iter().any(|locked| {
+            pointer == locked
+                || pointer.starts_with(&format!("{locked}/"))
+                || locked.starts_with(&format!("{pointer}/"))
+        })
+    }
+
+    /// Rejects the writes to locked settings, shared by all the ways to change a single setting
+    /// (settings window, IPC, scripts and the HTTP API) so they don't get reverted on reload
+    pub(super) fn ensure_unlocked(pointer: &str, locked: &[String]) -> Result<()> {
+        if Self::is_locked_pointer(pointer, locked) {
+            return Err(format!("{pointer} is managed by your organization").into());
+        }
+        Ok(())
+    }
+
+    /// User settings.json merged over the machine settings
+    pub(super) fn apply_machine_settings(file: Value, machine: Option<Value>) -> Value {
+        match machine {
+            Some(machine) => Self::apply_settings_overlays(machine, &[file]),
+            None => file,
+        }
+    }
+
+    /// Content to be written on settings.json, values equal to the machine layer are not written
+    pub(super) fn without_machine_settings(mut settings: Value, machine: Option<&Value>) -> Value {
+        if let Some(machine) = machine {
+            strip_machine_defaults(&mut settings, machine);
+        }
+        settings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_machine_settings_merge() {
+        let machine = json!({ "a": 1, "nested": { "x": 1, "y": 1 } });
+        let file = json!({ "nested": { "y": 2 }, "b": true });
+        assert_eq!(
+            FullState::apply_machine_settings(file, Some(machine)),
+            json!({ "a": 1, "b": true, "nested": { "x": 1, "y": 2 } })
+        );
+    }
+
+    #[test]
+    fn test_locked_pointers() {
+        let mut pointers = Vec::new();
+        leaf_pointers(
+            &json!({ "a": 1, "nested": { "x": [1], "a/b": {} } }),
+            "",
+            &mut pointers,
+        );
+        pointers.sort();
+        assert_eq!(pointers, vec!["/a", "/nested/a~1b", "/nested/x"]);
+
+        let locked = ["/nested/x".to_string()];
+        assert!(FullState::is_locked_pointer("/nested/x", &locked));
+        assert!(FullState::is_locked_pointer("/nested/x/0", &locked));
+        assert!(FullState::is_locked_pointer("/nested", &locked));
+        assert!(!FullState::is_locked_pointer("/nested/y", &locked));
+        assert!(!FullState::is_locked_pointer("/nested/xy", &locked));
+    }
+
+    #[test]
+    fn test_locked_settings_are_rejected() {
+        let locked = ["/fancyToolbar/height".to_string()];
+        let err = FullState::ensure_unlocked("/fancyToolbar/height", &locked).unwrap_err();
+        assert!(err.to_string().contains("managed by your organization"));
+        assert!(FullState::ensure_unlocked("/fancyToolbar", &locked).is_err());
+        assert!(FullState::ensure_unlocked("/fancyToolbar/enabled", &locked).is_ok());
+    }
+
+    #[test]
+    fn test_machine_settings_strip() {
+        let machine =
+            json!({ "a": 1, "c": [1], "nested": { "x": 1, "y": 1 }, "other": { "z": 1 } });
+        let effective = json!({ "a": 1, "b": true, "c": [1, 2], "nested": { "x": 1, "y": 2 }, "other": { "z": 1 } });
+        assert_eq!(
+            FullState::without_machine_settings(effective, Some(&machine)),
+            json!({ "b": true, "c": [1, 2], "nested": { "y": 2 } })
+        );
+    }
+}
@@ -1,6 +1,12 @@
 mod apps_config;
 mod events;
+mod file_listener;
 mod icons;
+pub mod lint;
+mod machine_layer;
+mod settings_overlays;
+mod settings_sync;
+mod toolbar_pins;
 
 use arc_swap::ArcSwap;
 use getset::Getters;
@@ -9,30 +15,42 @@ use lazy_static::lazy_static;
 use notify_debouncer_full::{
     new_debouncer,
     notify::{ReadDirectoryChangesWatcher, RecursiveMode, Watcher},
-    DebounceEventResult, DebouncedEvent, Debouncer, FileIdMap,
+    DebounceEventResult, Debouncer, FileIdMap,
 };
 use parking_lot::Mutex;
-use seelen_core::state::{IconPack, VirtualDesktopStrategy, WegItems, WindowManagerLayout};
+use seelen_core::state::{
+    IconPack, PlaceholderFragment, PlaceholderInclude, VirtualDesktopStrategy, WegItems,
+    WegOrphanedItems, Widget, WindowManagerLayout,
+};
 use std::{
     collections::{HashMap, VecDeque},
     fs::{File, OpenOptions},
     io::{Seek, Write},
     path::{Path, PathBuf},
-    sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc,
-    },
+    sync::{atomic::AtomicBool, Arc},
     time::Duration,
 };
 use tauri::Manager;
 
 use crate::{
-    error_handler::Result, log_error, modules::cli::domain::Resource, seelen::get_app_handle,
-    trace_lock, utils::is_virtual_desktop_supported, windows_api::WindowsApi,
+    error_handler::Result,
+    log_error,
+    modules::{
+        audio_policy::AudioPolicy, cli::domain::Resource, crash_guard::CrashGuard,
+        perf::PerfMonitor, scripting::ScriptingEngine, window_effects::WindowEffects,
+    },
+    seelen::get_app_handle,
+    seelen_weg::{native_pins::NativeTaskbarPins, SeelenWeg},
+    trace_lock,
+    utils::is_virtual_desktop_supported,
+    windows_api::WindowsApi,
 };
 
 use super::domain::{AppConfig, Placeholder, Settings, Theme};
 
+pub use file_listener::FileListener;
+use machine_layer::{MACHINE_APPS_PATH, MACHINE_DIR, MACHINE_LOCKED_PATH, MACHINE_SETTINGS_PATH};
+
 lazy_static! {
     static ref DATA_DIR: PathBuf = get_app_handle().path().app_data_dir().unwrap();
     pub static ref FULL_STATE: Arc<ArcSwap<FullState>> = Arc::new(ArcSwap::from_pointee({
@@ -45,6 +63,8 @@ lazy_static! {
         options
     };
     static ref USER_SETTINGS_PATH: PathBuf = DATA_DIR.join("settings.json");
+    /// json files merged over settings.json in order of name
+    static ref USER_SETTINGS_OVERLAYS_DIR: PathBuf = DATA_DIR.join("settings.d");
     static ref USER_SETTINGS_FILE: Arc<Mutex<File>> = Arc::new(Mutex::new(
         OPEN_OPTIONS.open(USER_SETTINGS_PATH.as_path()).unwrap()
     ));
@@ -55,6 +75,8 @@ lazy_static! {
 }
 
 static FILE_LISTENER_PAUSED: AtomicBool = AtomicBool::new(false);
+/// serializes the patches so consecutive ones are applied over the previous result
+static SETTINGS_PATCH_LOCK: Mutex<()> = Mutex::new(());
 
 pub type LauncherHistory = HashMap<String, Vec<String>>;
 
@@ -73,6 +95,7 @@ pub struct FullState {
     pub layouts: HashMap<String, WindowManagerLayout>,
     pub weg_items: Arc<Mutex<WegItems>>,
     pub history: LauncherHistory,
+    pub widgets: HashMap<String, Widget>,
 }
 
 unsafe impl Sync for FullState {}
@@ -93,6 +116,7 @@ impl FullState {
             layouts: HashMap::new(),
             weg_items: Arc::new(Mutex::new(WegItems::default())),
             history: HashMap::new(),
+            widgets: HashMap::new(),
         };
         manager.load_all()?;
         manager.start_listeners()?;
@@ -113,9 +137,8 @@ impl FullState {
         FULL_STATE.store(Arc::new(self.cloned()));
     }
 
-    fn process_event(&mut self, event: DebouncedEvent) -> Result<()> {
-        let event = event.event;
-
+    /// Reloads the resources of the changed files
+    fn process_changes(&mut self, paths: &[PathBuf]) -> Result<()> {
         let history_path = self.data_dir.join("history");
 
         let user_themes = self.data_dir.join("themes");
@@ -127,39 +150,56 @@ impl FullState {
         let user_layouts = self.data_dir.join("layouts");
         let bundled_layouts = self.resources_dir.join("static/layouts");
 
+        let user_scripts = self.data_dir.join("scripts");
+
+        let user_widgets = self.data_dir.join("widgets");
+
         let user_app_configs = self.data_dir.join("applications.yml");
         let bundled_app_configs = self.resources_dir.join("static/apps_templates");
 
-        if event.paths.contains(&self.icon_packs_folder()) {
+        if paths.contains(&self.icon_packs_folder()) {
             log::info!("Icons Packs changed");
             self.load_icons_packs()?;
             self.store_cloned();
             self.emit_icon_packs()?;
         }
 
-        if event.paths.contains(&WEG_ITEMS_PATH) {
+        if paths.contains(&WEG_ITEMS_PATH) {
             log::info!("Weg Items changed");
+            let previous = serde_yaml::to_string(&*trace_lock!(self.weg_items))?;
             self.load_weg_items()?;
             self.store_cloned();
-            self.emit_weg_items(&*trace_lock!(self.weg_items))?;
+            let items = trace_lock!(self.weg_items);
+            // changes made by weg commands are already applied and emitted
+            if serde_yaml::to_string(&*items)? != previous {
+                self.emit_weg_items(&items)?;
+            }
         }
 
-        if event.paths.contains(&history_path) {
+        if paths.contains(&history_path) {
             log::info!("History changed");
             self.load_history()?;
             self.store_cloned();
             self.emit_history()?;
         }
 
-        if event.paths.contains(&USER_SETTINGS_PATH) {
+        if paths.contains(&USER_SETTINGS_PATH)
+            || paths.contains(&MACHINE_SETTINGS_PATH)
+            || paths.contains(&MACHINE_LOCKED_PATH)
+            || paths
+                .iter()
+                .any(|p| p.starts_with(USER_SETTINGS_OVERLAYS_DIR.as_path()))
+        {
             log::info!("Seelen Settings changed");
             self.load_settings()?;
             self.store_cloned();
             self.emit_settings()?;
+            if paths.contains(&MACHINE_LOCKED_PATH) {
+                Self::emit_locked_settings()?;
+            }
         }
 
-        if event
-            .paths
+        if paths
             .iter()
             .any(|p| p.starts_with(&user_themes) || p.starts_with(&bundled_themes))
         {
@@ -169,8 +209,7 @@ impl FullState {
             self.emit_themes()?;
         }
 
-        if event
-            .paths
+        if paths
             .iter()
             .any(|p| p.starts_with(&user_placeholders) || p.starts_with(&bundled_placeholders))
         {
@@ -180,8 +219,7 @@ impl FullState {
             self.emit_placeholders()?;
         }
 
-        if event
-            .paths
+        if paths
             .iter()
             .any(|p| p.starts_with(&user_layouts) || p.starts_with(&bundled_layouts))
         {
@@ -191,15 +229,31 @@ impl FullState {
             self.emit_layouts()?;
         }
 
-        if event
-            .paths
+        if paths
             .iter()
             .any(|p| p.starts_with(&user_app_configs) || p.starts_with(&bundled_app_configs))
+            || paths.contains(&MACHINE_APPS_PATH)
         {
             log::info!("Specific App Configuration changed");
             self.load_settings_by_app()?;
             self.store_cloned();
             self.emit_settings_by_app()?;
+            log_error!(WindowEffects::refresh_all());
+            log_error!(AudioPolicy::refresh());
+            if self.is_weg_enabled() {
+                SeelenWeg::update_all_apps();
+            }
+        }
+
+        if paths.iter().any(|p| p.starts_with(&user_widgets)) {
+            log::info!("Widgets changed");
+            self.load_widgets()?;
+            self.store_cloned();
+            self.emit_widgets()?;
+        }
+
+        if paths.iter().any(|p| p.starts_with(&user_scripts)) {
+            ScriptingEngine::reload();
         }
 
         Ok(())
@@ -212,13 +266,19 @@ impl FullState {
             None,
             |result: DebounceEventResult| match result {
                 Ok(events) => {
-                    // log::info!("Seelen UI File Watcher events: {:?}", events);
-                    if !FILE_LISTENER_PAUSED.load(Ordering::Acquire) {
-                        let mut state = FULL_STATE.load().cloned();
-                        for event in events {
-                            log_error!(state.process_event(event));
-                        }
+                    let paths = events
+                        .into_iter()
+                        .flat_map(|event| event.event.paths)
+                        .unique()
+                        .collect_vec();
+                    if FileListener::is_paused() {
+                        FileListener::record(paths);
+                        return;
                     }
+                    let mut state = FULL_STATE.load().cloned();
+                    log_error!(
+                        PerfMonitor::measure("state reload", || state.process_changes(&paths))
+                    );
                 }
                 Err(errors) => errors
                     .iter()
@@ -226,9 +286,10 @@ impl FullState {
             },
         )?;
 
-        let paths: Vec<PathBuf> = vec![
+        let mut paths: Vec<PathBuf> = vec![
             // settings & user data
             USER_SETTINGS_PATH.to_path_buf(),
+            USER_SETTINGS_OVERLAYS_DIR.to_path_buf(),
             WEG_ITEMS_PATH.to_path_buf(),
             self.data_dir.join("applications.yml"),
             self.data_dir.join("history"),
@@ -237,12 +298,19 @@ impl FullState {
             self.icon_packs_folder(),
             self.data_dir.join("placeholders"),
             self.data_dir.join("layouts"),
+            self.data_dir.join("scripts"),
+            self.data_dir.join("widgets"),
             self.resources_dir.join("static/themes"),
             self.resources_dir.join("static/placeholders"),
             self.resources_dir.join("static/layouts"),
             self.resources_dir.join("static/apps_templates"),
         ];
 
+        // the machine layer is optional, it is created by the administrators
+        if MACHINE_DIR.exists() {
+            paths.push(MACHINE_DIR.to_path_buf());
+        }
+
         for path in paths {
             debouncer.watcher().watch(&path, RecursiveMode::Recursive)?;
         }
@@ -261,18 +329,47 @@ impl FullState {
     }
 
     fn load_settings(&mut self) -> Result<()> {
+        // the user files are kept untouched, they are used again on the next launch
+        if CrashGuard::is_recovering() {
+            self.settings = Settings::default();
+            return Ok(());
+        }
+
         let path_exists = USER_SETTINGS_PATH.exists();
+        let overlays = Self::settings_overlays();
+        let machine = Self::machine_settings();
+        let mut content = None;
         if path_exists {
-            self.settings = Self::get_settings_from_path(&USER_SETTINGS_PATH)?;
+            let raw = std::fs::read_to_string(USER_SETTINGS_PATH.as_path())?;
+            let file = Self::apply_machine_settings(serde_json::from_str(&raw)?, machine);
+            self.settings = serde_json::from_value(Self::apply_settings_overlays(file, &overlays))?;
             self.settings.sanitize();
+            content = Some(raw);
+        } else if !overlays.is_empty() || machine.is_some() {
+            let defaults =
+                Self::apply_machine_settings(serde_json::to_value(&self.settings)?, machine);
+            self.settings =
+                serde_json::from_value(Self::apply_settings_overlays(defaults, &overlays))?;
+            self.settings.sanitize();
+        }
+
+        // managed by the administrators, applied over everything else
+        if let Some(locked) = Self::locked_settings() {
+            self.apply_locked_settings(locked)?;
         }
 
         if !is_virtual_desktop_supported() {
             self.settings.virtual_desktop_strategy = VirtualDesktopStrategy::Seelen;
         }
 
-        if !path_exists {
-            self.save_settings()?;
+        match content {
+            Some(content) => {
+                if let Some(conflict) = Self::on_settings_read(&content, &self.settings) {
+                    log::warn!("settings.json was overwritten before applying the last changes");
+                    Self::emit_settings_conflict(&conflict)?;
+                }
+            }
+            None => self.save_settings()?,
         }
         Ok(())
     }
@@ -281,14 +378,23 @@ impl FullState {
         let mut current = trace_lock!(self.weg_items);
         if WEG_ITEMS_PATH.exists() {
             *current = serde_yaml::from_str(&std::fs::read_to_string(WEG_ITEMS_PATH.as_path())?)?;
-            current.sanitize();
+            current.sanitize(self.settings.seelenweg.orphaned_items);
         } else {
+            // first run, start with the apps pinned to the native taskbar
+            match NativeTaskbarPins::read() {
+                Ok(pins) if !pins.is_empty() => {
+                    current.center = pins;
+                    current.sanitize(self.settings.seelenweg.orphaned_items);
+                }
+                Ok(_) => {}
+                Err(err) => log::error!("Failed to import the taskbar pins: {:?}", err),
+            }
             self.save_weg_items(&current)?;
         }
         Ok(())
     }
 
-    fn load_theme_from_file(path: PathBuf) -> Result<Theme> {
+    pub(crate) fn load_theme_from_file(path: PathBuf) -> Result<Theme> {
         match path.extension() {
             Some(ext) if ext == "yml" || ext == "yaml" => {
                 Ok(serde_yaml::from_str(&std::fs::read_to_string(&path)?)?)
@@ -350,10 +456,52 @@ impl FullState {
         Ok(())
     }
 
+    /// nested includes allowed, also stops the include cycles
+    const MAX_INCLUDE_DEPTH: usize = 8;
+
+    /// Items of the included fragments, the paths are relative to `dir`. The fragments are
+    /// expected inside the placeholders folder, so the watcher reloads the placeholders on
+    /// their changes too.
+    fn load_placeholder_fragments(
+        dir: &Path,
+        include: &PlaceholderInclude,
+        depth: usize,
+    ) -> Result<PlaceholderFragment> {
+        if depth > Self::MAX_INCLUDE_DEPTH {
+            return Err("Too many nested placeholder includes, possible include cycle".into());
+        }
+
+        let mut items = PlaceholderFragment::default();
+        for path in include.paths() {
+            let path = dir.join(path);
+            let fragment: PlaceholderFragment = std::fs::read_to_string(&path)
+                .map_err(|err| format!("Failed to read fragment {:?}: {}", path, err))
+                .and_then(|content| {
+                    serde_yaml::from_str(&content)
+                        .map_err(|err| format!("Invalid fragment {:?}: {}", path, err))
+                })?;
+            let nested_dir = path.parent().unwrap_or(dir);
+            items.extend(Self::load_placeholder_fragments(
+                nested_dir,
+                &fragment.include,
+                depth + 1,
+            )?);
+            items.extend(fragment);
+        }
+        Ok(items)
+    }
+
     fn load_placeholder_from_file(path: PathBuf) -> Result<Placeholder> {
         match path.extension() {
             Some(ext) if ext == "yml" || ext == "yaml" => {
-                Ok(serde_yaml::from_str(&std::fs::read_to_string(&path)?)?)
+                let mut placeholder: Placeholder =
+                    serde_yaml::from_str(&std::fs::read_to_string(&path)?)?;
+                if !placeholder.include.is_empty() {
+                    let dir = path.parent().ok_or("Placeholder without parent folder")?;
+                    let fragments = Self::load_placeholder_fragments(dir, &placeholder.include, 0)?;
+                    placeholder.apply_fragments(fragments);
+                }
+                Ok(placeholder)
             }
             _ => Err("Invalid placeholder file extension".into()),
         }
@@ -437,6 +585,45 @@ impl FullState {
         Ok(())
     }
 
+    fn load_widget_from_dir(path: PathBuf) -> Result<Widget> {
+        let file = path.join("widget.yml");
+        if !file.exists() {
+            return Err("widget.yml not found".into());
+        }
+
+        let mut widget: Widget = serde_yaml::from_str(&std::fs::read_to_string(file)?)?;
+        let entry = path.join(&widget.entry).canonicalize()?;
+        // the entry should be inside the package
+        if !entry.starts_with(path.canonicalize()?) || !entry.is_file() {
+            return Err(format!("Invalid widget entry: {}", widget.entry).into());
+        }
+        widget.html = std::fs::read_to_string(entry)?;
+        Ok(widget)
+    }
+
+    fn load_widgets(&mut self) -> Result<()> {
+        let user_path = self.data_dir.join("widgets");
+        self.widgets.clear();
+        for entry in std::fs::read_dir(&user_path)?.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+
+            match Self::load_widget_from_dir(path) {
+                Ok(mut widget) => {
+                    widget.id = entry.file_name().to_string_lossy().to_string();
+                    widget.info.filename = widget.id.clone();
+                    self.widgets.insert(widget.id.clone(), widget);
+                }
+                Err(err) => {
+                    log::error!("Failed to load widget ({:?}): {:?}", entry.path(), err)
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn save_settings_by_app(&self) -> Result<()> {
         let data = self
             .settings_by_app
@@ -476,9 +663,24 @@ impl FullState {
             self.settings_by_app.extend(apps);
         }
 
+        // organization configs go after the user ones so personal overrides take precedence,
+        // they are read-only like the bundled templates
+        if MACHINE_APPS_PATH.exists() {
+            let content = std::fs::read_to_string(MACHINE_APPS_PATH.as_path())?;
+            match serde_yaml::from_str::<Vec<AppConfig>>(&content) {
+                Ok(mut apps) => {
+                    for app in apps.iter_mut() {
+                        app.is_bundled = true;
+                    }
+                    self.settings_by_app.extend(apps);
+                }
+                Err(err) => log::error!("Ignoring machine applications.yml: {}", err),
+            }
+        }
+
         self.settings_by_app
             .iter_mut()
-            .for_each(|app| app.identifier.cache_regex());
+            .for_each(|app| app.cache_regex());
         Ok(())
     }
 
@@ -492,7 +694,7 @@ impl FullState {
         Ok(())
     }
 
-    fn load_all(&mut self) -> Result<()> {
+    pub(crate) fn load_all(&mut self) -> Result<()> {
         self.load_settings()?;
         self.load_weg_items()?;
         self.load_themes()?;
@@ -501,24 +703,90 @@ impl FullState {
         self.load_layouts()?;
         self.load_settings_by_app()?;
         self.load_history()?;
+        self.load_widgets()?;
         Ok(())
     }
 
     pub fn save_settings(&self) -> Result<()> {
+        if CrashGuard::is_recovering() {
+            return Err("Settings are not saved while recovering from repeated crashes, reload Seelen UI to use your settings again".into());
+        }
+        let mut overlays = Self::settings_overlays();
+        // locked values are not written either, the user file keeps its own
+        overlays.extend(Self::locked_settings());
+        let machine = Self::machine_settings();
+        let content = if overlays.is_empty() && machine.is_none() {
+            serde_json::to_string_pretty(&self.settings)?
+        } else {
+            // values coming from settings.d are not copied into settings.json
+            let current = std::fs::read_to_string(USER_SETTINGS_PATH.as_path())
+                .ok()
+                .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok());
+            let value = Self::without_settings_overlays(
+                serde_json::to_value(&self.settings)?,
+                &overlays,
+                current.as_ref(),
+            );
+            // neither the ones of the machine layer, so org defaults keep applying
+            let value = Self::without_machine_settings(value, machine.as_ref());
+            serde_json::to_string_pretty(&value)?
+        };
+
         let mut file = trace_lock!(USER_SETTINGS_FILE);
         file.rewind()?;
-        file.write_all(serde_json::to_string_pretty(&self.settings)?.as_bytes())?;
+        file.set_len(0)?;
+        file.write_all(content.as_bytes())?;
+        Self::on_settings_written(&content, &self.settings);
         Ok(())
     }
 
+    /// Applies `value` at the JSON pointer `pointer` (e.g. `/fancyToolbar/enabled`) of the settings
+    /// and persists the result, the value is validated by deserializing the patched settings.
+    pub fn patch_settings(pointer: &str, value: serde_json::Value) -> Result<()> {
+        Self::ensure_unlocked(pointer, &Self::locked_settings_pointers())?;
+        let _guard = SETTINGS_PATCH_LOCK.lock();
+        let mut state = FULL_STATE.load().cloned();
+
+        let mut json = serde_json::to_value(&state.settings)?;
+        let target = json
+            .pointer_mut(pointer)
+            .ok_or(format!("Invalid settings path: {pointer}"))?;
+        *target = value;
+
+        let mut settings: Settings = serde_json::from_value(json)?;
+        settings.sanitize();
+        state.settings = settings;
+
+        // stored before the file watcher reloads it, so the next patch starts from this one
+        state.store_cloned();
+        state.save_settings()
+    }
+
     pub fn save_weg_items(&self, items: &WegItems) -> Result<()> {
         let mut file = trace_lock!(WEG_ITEMS_FILE);
         file.rewind()?;
+        file.set_len(0)?;
         file.write_all(serde_yaml::to_string(items)?.as_bytes())?;
         file.flush()?;
         Ok(())
     }
 
+    /// Rechecks the targets of the weg items, used when drives are connected or removed
+    pub fn refresh_weg_items(&self) -> Result<()> {
+        let mut items = trace_lock!(self.weg_items);
+        if !items.refresh_orphaned() {
+            return Ok(());
+        }
+        match self.settings.seelenweg.orphaned_items {
+            WegOrphanedItems::Keep => self.emit_weg_items(&items),
+            WegOrphanedItems::Remove => {
+                items.sanitize(WegOrphanedItems::Remove);
+                // the file watcher will reload and emit the new items
+                self.save_weg_items(&items)
+            }
+        }
+    }
+
     async fn set_wallpaper(url: &str, path: &Path) -> Result<()> {
         let response = tauri_plugin_http::reqwest::get(url).await?;
         let contents = response.bytes().await?;
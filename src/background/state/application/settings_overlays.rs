@@ -0,0 +1,138 @@
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::error_handler::Result;
+
+use super::{FullState, USER_SETTINGS_OVERLAYS_DIR};
+
+/// Deep merge of objects, any other value of `overlay` replaces the one of `base`
+fn merge_overlay(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base_map), Value::Object(overlay_map)) => {
+            for (key, value) in overlay_map {
+                match base_map.get_mut(&key) {
+                    Some(base_value) => merge_overlay(base_value, value),
+                    None => {
+                        base_map.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Restores the values of `file` on the keys defined by the overlay, so the values
+/// coming from the overlays are not written to settings.json.
+fn unapply_overlay(effective: &mut Value, overlay: &Value, file: Option<&Value>) {
+    let (Value::Object(effective_map), Value::Object(overlay_map)) = (effective, overlay) else {
+        return;
+    };
+    for (key, overlay_value) in overlay_map {
+        let file_value = file.and_then(|file| file.get(key));
+        match effective_map.get_mut(key) {
+            Some(value) if value.is_object() && overlay_value.is_object() => {
+                unapply_overlay(value, overlay_value, file_value);
+            }
+            _ => match file_value {
+                Some(file_value) => {
+                    effective_map.insert(key.clone(), file_value.clone());
+                }
+                None => {
+                    effective_map.remove(key);
+                }
+            },
+        }
+    }
+}
+
+impl FullState {
+    /// The json files of settings.d in order of name
+    fn settings_overlay_files() -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = std::fs::read_dir(USER_SETTINGS_OVERLAYS_DIR.as_path())
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        files.sort();
+        files
+    }
+
+    fn read_settings_overlay(path: &Path) -> Result<Value> {
+        let overlay: Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        if !overlay.is_object() {
+            return Err("the overlay should be a json object".into());
+        }
+        Ok(overlay)
+    }
+
+    /// Overlays of settings.d, a broken file is skipped instead of discarding all the settings
+    pub(super) fn settings_overlays() -> Vec<Value> {
+        Self::settings_overlay_files()
+            .into_iter()
+            .filter_map(|path| match Self::read_settings_overlay(&path) {
+                Ok(overlay) => Some(overlay),
+                Err(err) => {
+                    log::error!("Skipping settings overlay {}: {}", path.display(), err);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Settings.json merged with the overlays, later overlays override the earlier ones
+    pub(super) fn apply_settings_overlays(mut settings: Value, overlays: &[Value]) -> Value {
+        for overlay in overlays {
+            merge_overlay(&mut settings, overlay.clone());
+        }
+        settings
+    }
+
+    /// Content to be written on settings.json, the keys defined by the overlays keep
+    /// the value of the current file.
+    pub(super) fn without_settings_overlays(
+        mut settings: Value,
+        overlays: &[Value],
+        file: Option<&Value>,
+    ) -> Value {
+        for overlay in overlays {
+            unapply_overlay(&mut settings, overlay, file);
+        }
+        settings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_overlays_merge() {
+        let base = json!({ "a": 1, "nested": { "x": 1, "y": [1, 2] } });
+        let overlays = [
+            json!({ "nested": { "x": 2 } }),
+            json!({ "nested": { "x": 3, "y": [3] }, "b": true }),
+        ];
+        assert_eq!(
+            FullState::apply_settings_overlays(base, &overlays),
+            json!({ "a": 1, "b": true, "nested": { "x": 3, "y": [3] } })
+        );
+    }
+
+    #[test]
+    fn test_overlays_unapply() {
+        let file = json!({ "a": 1, "nested": { "x": 1, "y": 1 } });
+        let overlays = [json!({ "nested": { "x": 5 }, "b": true })];
+        // `a` and `y` were changed from the gui, `x` and `b` come from the overlay
+        let effective = json!({ "a": 2, "b": true, "nested": { "x": 5, "y": 2 } });
+        assert_eq!(
+            FullState::without_settings_overlays(effective, &overlays, Some(&file)),
+            json!({ "a": 2, "nested": { "x": 1, "y": 2 } })
+        );
+    }
+}
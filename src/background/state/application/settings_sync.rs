@@ -0,0 +1,198 @@
+use std::{
+    collections::{hash_map::DefaultHasher, VecDeque},
+    hash::{Hash, Hasher},
+};
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use seelen_core::state::{SettingsConflict, SettingsConflictResolution};
+use serde_json::Value;
+
+use crate::{error_handler::Result, state::domain::Settings, trace_lock};
+
+use super::{FullState, FULL_STATE};
+
+/// amount of known versions of settings.json kept to be used as base of a conflict
+const SNAPSHOTS_LIMIT: usize = 10;
+
+struct SettingsSync {
+    /// hash of settings.json content as last read or written by Seelen
+    hash: u64,
+    /// known versions of the file, the last one is the current
+    snapshots: VecDeque<(u64, Settings)>,
+    /// (base, mine) of the writes made by Seelen not yet read back by the file watcher
+    pending: Option<(Settings, Settings)>,
+    conflict: Option<SettingsConflict>,
+}
+
+impl SettingsSync {
+    fn snapshot(&mut self, hash: u64, settings: &Settings) {
+        self.snapshots.retain(|(h, _)| *h != hash);
+        self.snapshots.push_back((hash, settings.clone()));
+        while self.snapshots.len() > SNAPSHOTS_LIMIT {
+            self.snapshots.pop_front();
+        }
+    }
+
+    fn current(&self) -> Option<&Settings> {
+        self.snapshots.back().map(|(_, settings)| settings)
+    }
+}
+
+lazy_static! {
+    static ref SETTINGS_SYNC: Mutex<SettingsSync> = Mutex::new(SettingsSync {
+        hash: 0,
+        snapshots: VecDeque::new(),
+        pending: None,
+        conflict: None,
+    });
+}
+
+fn hash_content(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn format_hash(hash: u64) -> String {
+    format!("{:016x}", hash)
+}
+
+/// Three-way merge, changed values of `mine` take priority over the ones of `theirs`
+fn merge_values(base: &Value, mine: &Value, theirs: &Value) -> Value {
+    match (mine, theirs) {
+        (Value::Object(mine_map), Value::Object(theirs_map)) => {
+            let mut merged = theirs_map.clone();
+            for (key, mine_value) in mine_map {
+                let base_value = base.get(key).unwrap_or(&Value::Null);
+                let value = match theirs_map.get(key) {
+                    Some(theirs_value) => merge_values(base_value, mine_value, theirs_value),
+                    None => mine_value.clone(),
+                };
+                merged.insert(key.clone(), value);
+            }
+            Value::Object(merged)
+        }
+        _ if mine != base => mine.clone(),
+        _ => theirs.clone(),
+    }
+}
+
+impl FullState {
+    /// Registers the content read from settings.json, returns a conflict if a write made
+    /// by Seelen was overwritten before the file watcher could read it back.
+    pub(super) fn on_settings_read(content: &str, settings: &Settings) -> Option<SettingsConflict> {
+        let hash = hash_content(content);
+        let mut sync = trace_lock!(SETTINGS_SYNC);
+
+        let mut conflict = None;
+        if hash != sync.hash {
+            if let Some((base, mine)) = sync.pending.take() {
+                conflict = Some(SettingsConflict {
+                    base,
+                    mine,
+                    theirs: settings.clone(),
+                });
+            }
+        }
+
+        sync.pending = None;
+        sync.hash = hash;
+        sync.snapshot(hash, settings);
+        if conflict.is_some() {
+            sync.conflict = conflict.clone();
+        }
+        conflict
+    }
+
+    /// Registers the content written by Seelen to settings.json
+    pub(super) fn on_settings_written(content: &str, settings: &Settings) {
+        let hash = hash_content(content);
+        let mut sync = trace_lock!(SETTINGS_SYNC);
+        match &mut sync.pending {
+            Some((_, mine)) => *mine = settings.clone(),
+            None => {
+                let base = sync.current().cloned().unwrap_or_else(|| settings.clone());
+                sync.pending = Some((base, settings.clone()));
+            }
+        }
+        sync.hash = hash;
+        sync.snapshot(hash, settings);
+    }
+
+    /// Hash of settings.json as currently known, used by editors as base of their writes
+    pub fn settings_hash() -> String {
+        format_hash(trace_lock!(SETTINGS_SYNC).hash)
+    }
+
+    pub fn settings_conflict() -> Option<SettingsConflict> {
+        trace_lock!(SETTINGS_SYNC).conflict.clone()
+    }
+
+    /// Writes settings edited from the version `base_hash` of the file. If the file was changed
+    /// after that, a conflict is emitted instead of overwriting it.
+    /// Returns the new hash of the file if the settings were written.
+    pub fn write_settings(settings: Settings, base_hash: &str) -> Result<Option<String>> {
+        let conflict = {
+            let mut sync = trace_lock!(SETTINGS_SYNC);
+            if format_hash(sync.hash) == base_hash {
+                None
+            } else {
+                let base = sync
+                    .snapshots
+                    .iter()
+                    .find(|(hash, _)| format_hash(*hash) == base_hash)
+                    .map(|(_, settings)| settings.clone());
+                let theirs = sync
+                    .current()
+                    .cloned()
+                    .unwrap_or_else(|| FULL_STATE.load().settings.clone());
+                let conflict = SettingsConflict {
+                    base: base.unwrap_or_else(|| theirs.clone()),
+                    mine: settings.clone(),
+                    theirs,
+                };
+                sync.conflict = Some(conflict.clone());
+                Some(conflict)
+            }
+        };
+
+        if let Some(conflict) = conflict {
+            log::warn!("settings.json was modified by someone else while it was being edited");
+            Self::emit_settings_conflict(&conflict)?;
+            return Ok(None);
+        }
+
+        let mut state = FULL_STATE.load().cloned();
+        state.settings = settings;
+        state.save_settings()?;
+        Ok(Some(Self::settings_hash()))
+    }
+
+    pub fn resolve_settings_conflict(resolution: SettingsConflictResolution) -> Result<()> {
+        let conflict = match trace_lock!(SETTINGS_SYNC).conflict.take() {
+            Some(conflict) => conflict,
+            None => return Ok(()),
+        };
+
+        let settings = match resolution {
+            SettingsConflictResolution::KeepMine => conflict.mine,
+            // their version is the one already on the file and loaded
+            SettingsConflictResolution::TakeTheirs => return Ok(()),
+            SettingsConflictResolution::Merge => {
+                let merged = merge_values(
+                    &serde_json::to_value(&conflict.base)?,
+                    &serde_json::to_value(&conflict.mine)?,
+                    &serde_json::to_value(&conflict.theirs)?,
+                );
+                let mut settings: Settings = serde_json::from_value(merged)?;
+                settings.sanitize();
+                settings
+            }
+        };
+
+        let mut state = FULL_STATE.load().cloned();
+        state.settings = settings;
+        state.save_settings()
+    }
+}
@@ -0,0 +1,103 @@
+use seelen_core::state::{ToolbarItem, ToolbarPinSource};
+use serde_json::json;
+
+use crate::error_handler::Result;
+
+use super::FullState;
+
+/// same file used by the toolbar when the user customizes the placeholder
+const CUSTOM_PLACEHOLDER: &str = "custom.yml";
+
+/// Returns a mathjs expression that finds the item of `list` whose `key` is `value`
+fn lookup(list: &str, key: &str, value: &str) -> Result<String> {
+    Ok(format!(
+        "findBy({}, \"{}\", {})",
+        list,
+        key,
+        serde_json::to_string(value)?
+    ))
+}
+
+fn toolbar_item_for(source: &ToolbarPinSource) -> Result<ToolbarItem> {
+    let item = match source {
+        ToolbarPinSource::RemovableDrive { letter } => {
+            if letter.len() != 1 || !letter.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(format!("Invalid drive letter: {}", letter).into());
+            }
+            let drive = lookup("removableDrives", "letter", letter)?;
+            let root = serde_json::to_string(&format!("{}:\\", letter))?;
+            json!({
+                "type": "generic",
+                "id": format!("pinned-drive-{}", letter),
+                "template": format!(
+                    "unequal({drive}, null) ? concat(icon.BsUsbDriveFill, \" {letter}:\") : \"\""
+                ),
+                "tooltip": format!("unequal({drive}, null) ? {drive}.label : \"\""),
+                "onClickV2": format!("open({root})"),
+            })
+        }
+        ToolbarPinSource::Printer { name } => {
+            let printer = lookup("printers", "name", name)?;
+            json!({
+                "type": "generic",
+                "id": format!("pinned-printer-{}", name),
+                "template": format!(
+                    "unequal({printer}, null) ? concat(icon.BsPrinterFill, \" \", string(count({printer}.jobs))) : \"\""
+                ),
+                "tooltip": serde_json::to_string(name)?,
+            })
+        }
+        ToolbarPinSource::NetworkInterface { name } => {
+            let interface = lookup("interfaces", "name", name)?;
+            json!({
+                "type": "network",
+                "id": format!("pinned-network-{}", name),
+                "template": format!(
+                    "unequal({interface}, null) ? (equalText({interface}.status, \"up\") ? icon.MdVpnLock : \"\") : \"\""
+                ),
+                "tooltip": serde_json::to_string(name)?,
+            })
+        }
+    };
+    Ok(serde_json::from_value(item)?)
+}
+
+impl FullState {
+    /// Adds an item showing the source to the custom placeholder of the toolbar,
+    /// the file watcher will load the placeholder and the settings selecting it.
+    pub fn pin_toolbar_item(&self, source: &ToolbarPinSource) -> Result<()> {
+        let item = toolbar_item_for(source)?;
+
+        let mut placeholder = self
+            .placeholders
+            .get(&self.settings.fancy_toolbar.placeholder)
+            .cloned()
+            .unwrap_or_default();
+
+        let already_pinned = placeholder
+            .left
+            .iter()
+            .chain(placeholder.center.iter())
+            .chain(placeholder.right.iter())
+            .any(|current| current.id() == item.id());
+        if already_pinned {
+            return Ok(());
+        }
+
+        placeholder.right.insert(0, item);
+        placeholder.info.filename = CUSTOM_PLACEHOLDER.to_string();
+        placeholder.info.display_name = "Custom".to_string();
+        placeholder.info.author = std::env::var("USERNAME").unwrap_or_else(|_| "Me".to_string());
+        placeholder.info.description = "Customized by me".to_string();
+
+        let path = self.data_dir.join("placeholders").join(CUSTOM_PLACEHOLDER);
+        std::fs::write(path, serde_yaml::to_string(&placeholder)?)?;
+
+        if self.settings.fancy_toolbar.placeholder != CUSTOM_PLACEHOLDER {
+            let mut state = self.cloned();
+            state.settings.fancy_toolbar.placeholder = CUSTOM_PLACEHOLDER.to_string();
+            state.save_settings()?;
+        }
+        Ok(())
+    }
+}
@@ -1,12 +1,21 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use itertools::Itertools;
-use seelen_core::state::{WegItems, WindowManagerLayout};
+use seelen_core::state::{
+    SettingsConflict, SettingsConflictResolution, ToolbarPinSource, WallpaperFit, WegItems, Widget,
+    WindowManagerLayout,
+};
+use tauri::Manager;
 
-use crate::{error_handler::Result, trace_lock, windows_api::WindowsApi};
+use crate::{
+    error_handler::Result,
+    seelen::get_app_handle,
+    trace_lock,
+    windows_api::{DesktopWallpaper, WindowsApi},
+};
 
 use super::{
-    application::{FullState, LauncherHistory, FULL_STATE},
+    application::{FileListener, FullState, LauncherHistory, FULL_STATE},
     domain::{AppConfig, Placeholder, Settings, Theme},
 };
 
@@ -25,6 +34,12 @@ pub fn state_get_placeholders() -> Vec<Placeholder> {
         .collect_vec()
 }
 
+/// Pins a live data source as an item of the toolbar
+#[tauri::command(async)]
+pub fn state_pin_toolbar_item(source: ToolbarPinSource) -> Result<()> {
+    FULL_STATE.load().pin_toolbar_item(&source)
+}
+
 #[tauri::command(async)]
 pub fn state_get_layouts() -> Vec<WindowManagerLayout> {
     FULL_STATE.load().layouts().values().cloned().collect_vec()
@@ -42,6 +57,23 @@ pub fn state_get_history() -> LauncherHistory {
     FULL_STATE.load().history().clone()
 }
 
+#[tauri::command(async)]
+pub fn state_get_widgets() -> Vec<Widget> {
+    FULL_STATE.load().widgets().values().cloned().collect_vec()
+}
+
+/// Stops reloading the config files on external edits until resumed
+#[tauri::command(async)]
+pub fn state_pause_file_listener() {
+    FileListener::pause();
+}
+
+/// Applies the external edits made while paused, returns the changed files
+#[tauri::command(async)]
+pub fn state_resume_file_listener() -> Result<Vec<PathBuf>> {
+    FileListener::resume()
+}
+
 #[tauri::command(async)]
 pub fn state_get_settings(path: Option<PathBuf>) -> Result<Settings> {
     if let Some(path) = path {
@@ -53,6 +85,39 @@ pub fn state_get_settings(path: Option<PathBuf>) -> Result<Settings> {
     }
 }
 
+/// Changes a single setting without sending the entire settings object
+#[tauri::command(async)]
+pub fn patch_settings(pointer: String, value: serde_json::Value) -> Result<()> {
+    FullState::patch_settings(&pointer, value)
+}
+
+#[tauri::command(async)]
+pub fn state_get_settings_hash() -> String {
+    FullState::settings_hash()
+}
+
+/// Returns the new hash of the file, or `None` if a conflict was emitted instead
+#[tauri::command(async)]
+pub fn state_write_settings(settings: Settings, base_hash: String) -> Result<Option<String>> {
+    FullState::write_settings(settings, &base_hash)
+}
+
+#[tauri::command(async)]
+pub fn state_get_settings_conflict() -> Option<SettingsConflict> {
+    FullState::settings_conflict()
+}
+
+#[tauri::command(async)]
+pub fn state_resolve_settings_conflict(resolution: SettingsConflictResolution) -> Result<()> {
+    FullState::resolve_settings_conflict(resolution)
+}
+
+/// JSON pointers of the settings managed by the administrators
+#[tauri::command(async)]
+pub fn state_get_locked_settings() -> Vec<String> {
+    FullState::locked_settings_pointers()
+}
+
 #[tauri::command(async)]
 pub fn state_get_specific_apps_configurations() -> Vec<AppConfig> {
     FULL_STATE
@@ -69,6 +134,19 @@ pub fn state_get_wallpaper() -> Result<PathBuf> {
 }
 
 #[tauri::command(async)]
-pub fn state_set_wallpaper(path: String) -> Result<()> {
-    WindowsApi::set_wallpaper(path)
+pub fn state_set_wallpaper(
+    path: String,
+    fit: Option<WallpaperFit>,
+    monitor: Option<usize>,
+) -> Result<()> {
+    let cache_dir = get_app_handle()
+        .path()
+        .app_cache_dir()?
+        .join("fitted_wallpapers");
+    DesktopWallpaper::set(
+        Path::new(&path),
+        fit.unwrap_or_default(),
+        monitor,
+        &cache_dir,
+    )
 }
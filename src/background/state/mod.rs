@@ -6,10 +6,13 @@ use std::collections::HashMap;
 
 use application::FullState;
 use domain::AhkVar;
+use seelen_core::state::NativeTaskbarMode;
+
+use crate::seelen::Seelen;
 
 impl FullState {
     pub fn is_weg_enabled(&self) -> bool {
-        self.settings.seelenweg.enabled
+        self.settings.seelenweg.enabled && !Seelen::is_headless()
     }
 
     pub fn is_weg_enabled_on_monitor(&self, monitor_idx: usize) -> bool {
@@ -21,7 +24,7 @@ impl FullState {
     }
 
     pub fn is_bar_enabled(&self) -> bool {
-        self.settings.fancy_toolbar.enabled
+        self.settings.fancy_toolbar.enabled && !Seelen::is_headless()
     }
 
     pub fn is_bar_enabled_on_monitor(&self, monitor_idx: usize) -> bool {
@@ -32,26 +35,59 @@ impl FullState {
         }
     }
 
+    pub fn should_hide_native_taskbar_on_monitor(&self, monitor_idx: usize) -> bool {
+        let mode = self
+            .settings
+            .monitors
+            .get(monitor_idx)
+            .and_then(|monitor| monitor.taskbar.mode)
+            .unwrap_or(self.settings.native_taskbar.mode);
+        match mode {
+            NativeTaskbarMode::Auto => self.is_weg_enabled_on_monitor(monitor_idx),
+            NativeTaskbarMode::Visible => false,
+            NativeTaskbarMode::Hidden => true,
+        }
+    }
+
+    /// start menu is only suppressed if there is a launcher to replace it
+    pub fn should_suppress_start_menu(&self) -> bool {
+        self.settings.native_taskbar.suppress_start_menu && self.is_rofi_enabled()
+    }
+
     pub fn is_window_manager_enabled(&self) -> bool {
         self.settings.window_manager.enabled
     }
 
     pub fn is_rofi_enabled(&self) -> bool {
-        self.settings.launcher.enabled
+        self.settings.launcher.enabled && !Seelen::is_headless()
     }
 
     pub fn is_wall_enabled(&self) -> bool {
-        self.settings.wall.enabled
+        self.settings.wall.enabled && !Seelen::is_headless()
     }
 
     pub fn is_ahk_enabled(&self) -> bool {
         self.settings.ahk_enabled
     }
 
+    pub fn is_keybindings_enabled(&self) -> bool {
+        self.settings.keybindings.enabled
+    }
+
     pub fn get_ahk_variables(&self) -> HashMap<String, AhkVar> {
         self.settings.ahk_variables.as_hash_map()
     }
 
+    /// `None` if the night light is disabled on the monitor
+    pub fn get_night_light_temperature(&self, monitor_idx: usize) -> Option<u32> {
+        let global = self.settings.night_light.temperature;
+        match self.settings.monitors.get(monitor_idx) {
+            Some(monitor) if !monitor.night_light.enabled => None,
+            Some(monitor) => Some(monitor.night_light.temperature.unwrap_or(global)),
+            None => Some(global),
+        }
+    }
+
     pub fn get_wm_layout_id(&self, monitor_idx: usize, workspace_idx: usize) -> String {
         let default = self.settings.window_manager.default_layout.clone();
         match self.settings.monitors.get(monitor_idx) {
@@ -1,17 +1,18 @@
-pub mod brightness;
-
 use tauri::Listener;
 
 use crate::{
     error_handler::Result,
     log_error,
     modules::{
+        bluetooth::BluetoothManager,
+        brightness::BrightnessManager,
         media::infrastructure::{register_media_events, release_media_events},
         network::infrastructure::register_network_events,
         notifications::infrastructure::{
             register_notification_events, release_notification_events,
         },
         power::infrastructure::PowerManager,
+        quick_settings::QuickSettings,
         system_settings::infrastructure::{register_colors_events, release_colors_events},
         tray::infrastructure::register_tray_events,
     },
@@ -32,8 +33,12 @@ pub fn declare_system_events_handlers() -> Result<()> {
         log_error!(register_network_events());
     });
 
+    handle.listen("register-brightness-events", move |_| {
+        log_error!(BrightnessManager::register_events());
+    });
+
     handle.listen("register-bluetooth-events", move |_| {
-        // todo
+        log_error!(BluetoothManager::register_events());
     });
 
     handle.listen("register-media-events", move |_| {
@@ -44,6 +49,10 @@ pub fn declare_system_events_handlers() -> Result<()> {
         register_notification_events();
     });
 
+    handle.listen("register-quick-settings-events", move |_| {
+        log_error!(QuickSettings::register_events());
+    });
+
     register_colors_events();
     Ok(())
 }
@@ -52,4 +61,5 @@ pub fn release_system_events_handlers() {
     release_media_events();
     release_notification_events();
     release_colors_events();
+    BluetoothManager::release_events();
 }
@@ -1,17 +1,25 @@
+use seelen_core::state::MonitorConfiguration;
 use tauri::image::Image;
+use tauri::menu::{CheckMenuItemBuilder, Menu, SubmenuBuilder};
 use tauri::path::BaseDirectory;
 use tauri::tray::{MouseButton, MouseButtonState, TrayIconEvent};
-use tauri::Manager;
 use tauri::{
     menu::{MenuBuilder, MenuEvent, MenuItemBuilder},
     tray::TrayIconBuilder,
     App, AppHandle,
 };
+use tauri::{Manager, Wry};
 
 use crate::error_handler::Result;
 use crate::log_error;
-use crate::seelen::Seelen;
+use crate::modules::crash_guard::CrashGuard;
+use crate::seelen::{get_app_handle, Seelen};
+use crate::state::application::{FullState, FULL_STATE};
+use crate::t;
 use crate::utils::sleep_millis;
+use crate::windows_api::MonitorEnumerator;
+
+const TRAY_ID: &str = "seelen-ui";
 
 pub fn try_register_tray_icon(app: &mut App) -> Result<()> {
     log::trace!("registering tray icon");
@@ -30,41 +38,143 @@ pub fn try_register_tray_icon(app: &mut App) -> Result<()> {
     Ok(())
 }
 
-fn register_tray_icon(app: &mut App) -> Result<()> {
-    let settings = MenuItemBuilder::with_id("settings", "Open Settings").build(app)?;
+/// The menu is built natively so the basics can be changed even if every webview is broken
+fn build_menu<M: Manager<Wry>>(manager: &M) -> Result<Menu<Wry>> {
+    let state = FULL_STATE.load();
+    // on safe mode only the settings are available, the modules are not running
+    let running = !Seelen::is_safe_mode();
+
+    let settings = MenuItemBuilder::with_id("settings", t!("tray.open_settings")).build(manager)?;
 
-    let toggle_pause = MenuItemBuilder::with_id("pause", "Pause/Resume").build(app)?;
-    let restart = MenuItemBuilder::with_id("restart", "Reload").build(app)?;
+    let toggle_wm = CheckMenuItemBuilder::with_id("toggle_wm", t!("tray.window_manager"))
+        .checked(state.settings.window_manager.enabled)
+        .enabled(running)
+        .build(manager)?;
 
-    let quit = MenuItemBuilder::with_id("quit", "Quit").build(app)?;
+    let toggle_weg = CheckMenuItemBuilder::with_id("toggle_weg", t!("tray.dock_enabled"))
+        .checked(state.settings.seelenweg.enabled)
+        .enabled(running)
+        .build(manager)?;
+    let mut dock = SubmenuBuilder::new(manager, t!("tray.dock"))
+        .item(&toggle_weg)
+        .separator();
+    let monitors = MonitorEnumerator::get_all()?.len();
+    for index in 0..monitors {
+        let enabled = state
+            .settings
+            .monitors
+            .get(index)
+            .map_or(true, |m| m.weg.enabled);
+        let item = CheckMenuItemBuilder::with_id(
+            format!("toggle_weg:{}", index),
+            t!("tray.monitor", index = index + 1),
+        )
+        .checked(enabled)
+        .enabled(running && state.settings.seelenweg.enabled)
+        .build(manager)?;
+        dock = dock.item(&item);
+    }
+    let dock = dock.build()?;
 
-    let menu = MenuBuilder::new(app)
+    let restart = MenuItemBuilder::with_id("restart", t!("tray.reload")).build(manager)?;
+    let safe_mode = MenuItemBuilder::with_id("safe_mode", t!("tray.safe_mode"))
+        .enabled(running)
+        .build(manager)?;
+    let quit = MenuItemBuilder::with_id("quit", t!("tray.quit")).build(manager)?;
+
+    let menu = MenuBuilder::new(manager)
         .item(&settings)
         .separator()
-        .item(&toggle_pause)
+        .item(&toggle_wm)
+        .item(&dock)
+        .separator()
         .item(&restart)
+        .item(&safe_mode)
         .separator()
         .item(&quit)
         .build()?;
+    Ok(menu)
+}
+
+/// Rebuilds the menu to reflect the current settings and monitors
+pub fn refresh_tray_menu() -> Result<()> {
+    let handle = get_app_handle();
+    if let Some(tray) = handle.tray_by_id(TRAY_ID) {
+        tray.set_menu(Some(build_menu(handle)?))?;
+    }
+    Ok(())
+}
+
+/// Changes are applied by the settings file watcher as if they were made on the settings window
+fn update_settings(f: impl FnOnce(&mut FullState)) -> Result<()> {
+    let mut state = FULL_STATE.load().cloned();
+    f(&mut state);
+    state.save_settings()
+}
+
+fn toggle_weg_on_monitor(index: usize) -> Result<()> {
+    update_settings(|state| {
+        let monitors = &mut state.settings.monitors;
+        if monitors.len() <= index {
+            monitors.resize(index + 1, MonitorConfiguration::default());
+        }
+        monitors[index].weg.enabled = !monitors[index].weg.enabled;
+    })
+}
+
+/// Replaces the running instance with a new one on safe mode
+fn restart_on_safe_mode() -> Result<()> {
+    std::process::Command::new(std::env::current_exe()?)
+        .args(["--replace", "--safe-mode"])
+        .spawn()?;
+    Ok(())
+}
+
+fn on_menu_event(app: &AppHandle, event: MenuEvent) {
+    match event.id().as_ref() {
+        "settings" => {
+            log_error!(Seelen::show_settings());
+        }
+        "toggle_wm" => {
+            log_error!(update_settings(|state| {
+                state.settings.window_manager.enabled = !state.settings.window_manager.enabled;
+            }));
+        }
+        "toggle_weg" => {
+            log_error!(update_settings(|state| {
+                state.settings.seelenweg.enabled = !state.settings.seelenweg.enabled;
+            }));
+        }
+        "restart" => {
+            log_error!(CrashGuard::on_exit());
+            app.restart();
+        }
+        "safe_mode" => {
+            log_error!(restart_on_safe_mode());
+        }
+        "quit" => app.exit(0),
+        id => {
+            if let Some(index) = id
+                .strip_prefix("toggle_weg:")
+                .and_then(|index| index.parse().ok())
+            {
+                log_error!(toggle_weg_on_monitor(index));
+            }
+        }
+    }
+}
 
-    TrayIconBuilder::new()
+fn register_tray_icon(app: &mut App) -> Result<()> {
+    let menu = build_menu(app)?;
+
+    TrayIconBuilder::with_id(TRAY_ID)
         .icon(Image::from_path(app.path().resolve(
             "static/icons/32x32.png",
             BaseDirectory::Resource,
         )?)?)
         .tooltip("Seelen UI")
         .menu(&menu)
-        .on_menu_event(
-            move |app: &AppHandle, event: MenuEvent| match event.id().as_ref() {
-                "settings" => {
-                    log_error!(Seelen::show_settings());
-                }
-                "pause" => {}
-                "restart" => app.restart(),
-                "quit" => app.exit(0),
-                _ => (),
-            },
-        )
+        .on_menu_event(on_menu_event)
         .on_tray_icon_event(move |_, event| {
             if let TrayIconEvent::Click {
                 id: _,
@@ -25,6 +25,16 @@ pub static NATIVE_UI_POPUP_CLASSES: [&str; 3] = [
     "ControlCenterWindow",          // Windows 11 right panel with quick settings
 ];
 
+/// Composition windows, candidate lists and language bars of the input methods (IME)
+pub static IME_WINDOW_CLASSES: [&str; 6] = [
+    "IME",
+    "MSCTFIME UI",
+    "CiceroUIWndFrame",
+    "Microsoft.IME.UIManager.CandidateWindow.Host",
+    "mscandui21.candidate",
+    "mscandui40.candidate",
+];
+
 pub static OVERLAP_BLACK_LIST_BY_EXE: [&str; 4] = [
     "msedgewebview2.exe",
     "SearchHost.exe",
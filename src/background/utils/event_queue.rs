@@ -0,0 +1,197 @@
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+use crossbeam_channel::Sender;
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::{error_handler::Result, trace_lock};
+
+use super::spawn_named_thread;
+
+#[derive(Debug, Default)]
+struct Counters {
+    max_depth: AtomicUsize,
+    processed: AtomicU64,
+    coalesced: AtomicU64,
+    dropped: AtomicU64,
+    /// time since the event was enqueued until the handler finished, in microseconds
+    total_latency_us: AtomicU64,
+    max_latency_us: AtomicU64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventQueueMetrics {
+    pub name: &'static str,
+    pub depth: usize,
+    pub max_depth: usize,
+    pub processed: u64,
+    pub coalesced: u64,
+    pub dropped: u64,
+    pub avg_latency_ms: f64,
+    pub max_latency_ms: f64,
+}
+
+/// Long-lived worker fed through a channel.
+///
+/// Events with a coalescing key are only enqueued once until the worker picks them up,
+/// so storms of the same event (ex: ObjectLocationChange on a window being dragged)
+/// are collapsed into a single item. Only those events are dropped when the queue is
+/// over its capacity, the rest (ex: ObjectDestroy, ObjectHide) are always delivered.
+pub struct EventQueue<T: Send + 'static> {
+    name: &'static str,
+    sender: Sender<(T, Instant)>,
+    coalesce_key: fn(&T) -> Option<isize>,
+    pending_keys: Arc<Mutex<HashSet<isize>>>,
+    counters: Arc<Counters>,
+}
+
+impl<T: Send + 'static> EventQueue<T> {
+    pub const CAPACITY: usize = 512;
+
+    pub fn new<F>(name: &'static str, coalesce_key: fn(&T) -> Option<isize>, cb: F) -> Result<Self>
+    where
+        F: Fn(T) + Send + 'static,
+    {
+        let (sender, receiver) = crossbeam_channel::unbounded::<(T, Instant)>();
+        let pending_keys = Arc::new(Mutex::new(HashSet::new()));
+        let counters = Arc::new(Counters::default());
+
+        let worker_keys = pending_keys.clone();
+        let worker_counters = counters.clone();
+        spawn_named_thread(name, move || {
+            for (event, enqueued_at) in receiver {
+                if let Some(key) = coalesce_key(&event) {
+                    trace_lock!(worker_keys).remove(&key);
+                }
+                cb(event);
+                let latency = enqueued_at.elapsed().as_micros() as u64;
+                worker_counters
+                    .total_latency_us
+                    .fetch_add(latency, Ordering::Relaxed);
+                worker_counters
+                    .max_latency_us
+                    .fetch_max(latency, Ordering::Relaxed);
+                worker_counters.processed.fetch_add(1, Ordering::Relaxed);
+            }
+        })?;
+
+        Ok(Self {
+            name,
+            sender,
+            coalesce_key,
+            pending_keys,
+            counters,
+        })
+    }
+
+    pub fn send(&self, event: T) {
+        if let Some(key) = (self.coalesce_key)(&event) {
+            if !trace_lock!(self.pending_keys).insert(key) {
+                self.counters.coalesced.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+            if self.sender.len() >= Self::CAPACITY {
+                trace_lock!(self.pending_keys).remove(&key);
+                self.on_dropped();
+                return;
+            }
+        }
+
+        match self.sender.send((event, Instant::now())) {
+            Ok(_) => {
+                self.counters
+                    .max_depth
+                    .fetch_max(self.sender.len(), Ordering::Relaxed);
+            }
+            // the worker thread is gone
+            Err(_) => self.on_dropped(),
+        }
+    }
+
+    fn on_dropped(&self) {
+        let dropped = self.counters.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+        if dropped % 100 == 1 {
+            log::warn!("{} queue is full, {} events dropped", self.name, dropped);
+        }
+    }
+
+    pub fn metrics(&self) -> EventQueueMetrics {
+        let processed = self.counters.processed.load(Ordering::Relaxed);
+        let total_latency_us = self.counters.total_latency_us.load(Ordering::Relaxed);
+        EventQueueMetrics {
+            name: self.name,
+            depth: self.sender.len(),
+            max_depth: self.counters.max_depth.load(Ordering::Relaxed),
+            processed,
+            coalesced: self.counters.coalesced.load(Ordering::Relaxed),
+            dropped: self.counters.dropped.load(Ordering::Relaxed),
+            avg_latency_ms: match processed {
+                0 => 0.0,
+                _ => total_latency_us as f64 / processed as f64 / 1000.0,
+            },
+            max_latency_ms: self.counters.max_latency_us.load(Ordering::Relaxed) as f64 / 1000.0,
+        }
+    }
+}
+
+/// Queues are stored on lazy statics, so a failure creating the worker is reported
+/// on each send instead of panicking on the first access.
+pub trait QueueSender<T> {
+    fn send(&self, event: T);
+    fn metrics(&self) -> Option<EventQueueMetrics>;
+}
+
+impl<T: Send + 'static> QueueSender<T> for Result<EventQueue<T>> {
+    fn send(&self, event: T) {
+        match self {
+            Ok(queue) => queue.send(event),
+            Err(err) => log::error!("Event dropped, the queue is not available: {:?}", err),
+        }
+    }
+
+    fn metrics(&self) -> Option<EventQueueMetrics> {
+        self.as_ref().ok().map(|queue| queue.metrics())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_coalescable_events_are_dropped() {
+        let (release, wait) = crossbeam_channel::bounded::<()>(0);
+        let (done, finished) = crossbeam_channel::unbounded::<i32>();
+        let queue = EventQueue::new(
+            "Test Queue",
+            |event: &i32| (*event < 0).then_some(*event as isize),
+            move |event| {
+                // the worker waits on the first event so the queue fills up
+                if event == 0 {
+                    let _ = wait.recv();
+                }
+                let _ = done.send(event);
+            },
+        )
+        .unwrap();
+
+        let total = EventQueue::<i32>::CAPACITY as i32 + 10;
+        for event in 0..total {
+            queue.send(event);
+        }
+        queue.send(-1);
+        assert_eq!(queue.metrics().dropped, 1);
+
+        release.send(()).unwrap();
+        let received: Vec<i32> = finished.iter().take(total as usize).collect();
+        assert_eq!(received, (0..total).collect::<Vec<_>>());
+    }
+}
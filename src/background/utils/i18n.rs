@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde_yaml::Value;
+use tauri::{path::BaseDirectory, Manager};
+
+use crate::{seelen::get_app_handle, state::application::FULL_STATE, trace_lock};
+
+const FALLBACK_LANGUAGE: &str = "en";
+
+lazy_static! {
+    /// language -> translations, None if the language has no translation file
+    static ref TRANSLATIONS: Mutex<HashMap<String, Option<Value>>> = Mutex::new(HashMap::new());
+}
+
+/// Translates a message emitted by the background to the language of the settings.
+/// Interpolates `{{name}}` placeholders with the given arguments.
+///
+/// ```ignore
+/// t!("break_reminder.body", minutes = settings.duration)
+/// ```
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::utils::i18n::translate($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::utils::i18n::translate($key, &[$((stringify!($name), $value.to_string())),+])
+    };
+}
+
+fn load(lang: &str) -> Option<Value> {
+    let path = get_app_handle()
+        .path()
+        .resolve(format!("static/i18n/{}.yml", lang), BaseDirectory::Resource)
+        .ok()?;
+    let content = std::fs::read_to_string(path).ok()?;
+    match serde_yaml::from_str(&content) {
+        Ok(translations) => Some(translations),
+        Err(err) => {
+            log::error!("Invalid translations for {}: {}", lang, err);
+            None
+        }
+    }
+}
+
+/// Language of the settings followed by the less specific ones, `pt-BR` -> `pt-BR`, `pt`, `en`
+fn fallback_chain(lang: &str) -> Vec<String> {
+    let mut chain = vec![lang.to_string()];
+    if let Some((base, _)) = lang.split_once(['-', '_']) {
+        chain.push(base.to_string());
+    }
+    if !chain.iter().any(|l| l == FALLBACK_LANGUAGE) {
+        chain.push(FALLBACK_LANGUAGE.to_string());
+    }
+    chain
+}
+
+fn lookup<'a>(translations: &'a Value, key: &str) -> Option<&'a str> {
+    key.split('.')
+        .try_fold(translations, |value, part| value.get(part))?
+        .as_str()
+}
+
+fn interpolate(message: &str, args: &[(&str, String)]) -> String {
+    args.iter()
+        .fold(message.to_string(), |message, (name, value)| {
+            message.replace(&format!("{{{{{}}}}}", name), value)
+        })
+}
+
+/// Use the `t!` macro instead, if the key is not found on any language the key itself is returned
+pub fn translate(key: &str, args: &[(&str, String)]) -> String {
+    try_translate(key, args).unwrap_or_else(|| {
+        log::warn!("Missing translation for {}", key);
+        interpolate(key, args)
+    })
+}
+
+/// Same as `translate` but for optional keys, `None` if the key is not found on any language
+pub fn try_translate(key: &str, args: &[(&str, String)]) -> Option<String> {
+    let lang = FULL_STATE
+        .load()
+        .settings
+        .language
+        .clone()
+        .unwrap_or_else(|| FALLBACK_LANGUAGE.to_string());
+
+    let mut translations = trace_lock!(TRANSLATIONS);
+    for lang in fallback_chain(&lang) {
+        let loaded = translations
+            .entry(lang.clone())
+            .or_insert_with(|| load(&lang));
+        if let Some(message) = loaded.as_ref().and_then(|t| lookup(t, key)) {
+            return Some(interpolate(message, args));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallback_chain() {
+        assert_eq!(fallback_chain("pt-BR"), vec!["pt-BR", "pt", "en"]);
+        assert_eq!(fallback_chain("es"), vec!["es", "en"]);
+        assert_eq!(fallback_chain("en"), vec!["en"]);
+    }
+
+    #[test]
+    fn test_translations_are_complete() {
+        fn keys(value: &Value, prefix: &str, out: &mut Vec<String>) {
+            if let Value::Mapping(map) = value {
+                for (k, v) in map {
+                    let key = format!("{}{}", prefix, k.as_str().unwrap_or_default());
+                    match v {
+                        Value::Mapping(_) => keys(v, &format!("{}.", key), out),
+                        _ => out.push(key),
+                    }
+                }
+            }
+        }
+
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("static/i18n");
+        let parse = |lang: &str| -> Value {
+            serde_yaml::from_str(&std::fs::read_to_string(dir.join(lang)).unwrap()).unwrap()
+        };
+        let english = parse("en.yml");
+        let mut english_keys = Vec::new();
+        keys(&english, "", &mut english_keys);
+
+        for entry in std::fs::read_dir(&dir).unwrap() {
+            let file = entry.unwrap().file_name().to_string_lossy().to_string();
+            let translations = parse(&file);
+            for key in &english_keys {
+                assert!(
+                    lookup(&translations, key).is_some(),
+                    "{} is missing {}",
+                    file,
+                    key
+                );
+            }
+        }
+
+        assert_eq!(
+            interpolate(
+                lookup(&english, "break_reminder.body").unwrap(),
+                &[("minutes", "5".into())]
+            ),
+            "Look away from the screen for 5 minutes"
+        );
+    }
+}
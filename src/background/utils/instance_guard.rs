@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use windows::{
+    core::w,
+    Win32::{
+        Foundation::{
+            CloseHandle, GetLastError, ERROR_ALREADY_EXISTS, HANDLE, WAIT_ABANDONED, WAIT_OBJECT_0,
+        },
+        System::Threading::{CreateMutexW, ReleaseMutex, WaitForSingleObject},
+    },
+};
+
+use crate::error_handler::Result;
+
+/// Named mutex owned by the running instance for its whole lifetime,
+/// used to know if another instance is already running and to wait for it to exit.
+pub struct InstanceGuard {
+    handle: HANDLE,
+    owned: bool,
+}
+
+unsafe impl Send for InstanceGuard {}
+unsafe impl Sync for InstanceGuard {}
+
+impl InstanceGuard {
+    pub fn acquire() -> Result<Self> {
+        let handle = unsafe { CreateMutexW(None, true, w!("Local\\Seelen-UI-Instance"))? };
+        let owned = unsafe { GetLastError() } != ERROR_ALREADY_EXISTS;
+        Ok(Self { handle, owned })
+    }
+
+    /// Returns true if this process is the main instance
+    pub fn is_owned(&self) -> bool {
+        self.owned
+    }
+
+    /// Blocks until the running instance exits (releasing the mutex) or the timeout is reached
+    pub fn wait_ownership(&mut self, timeout: Duration) -> bool {
+        if self.owned {
+            return true;
+        }
+        let result = unsafe { WaitForSingleObject(self.handle, timeout.as_millis() as u32) };
+        // abandoned means the previous instance exited without releasing it (crash or kill)
+        self.owned = result == WAIT_OBJECT_0 || result == WAIT_ABANDONED;
+        self.owned
+    }
+}
+
+impl Drop for InstanceGuard {
+    fn drop(&mut self) {
+        unsafe {
+            if self.owned {
+                let _ = ReleaseMutex(self.handle);
+            }
+            let _ = CloseHandle(self.handle);
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+
+use crate::error_handler::Result;
+
+use super::spawn_named_thread;
+
+/// Locks waited or held for longer than this are reported as warnings
+pub const LOCK_WARNING_THRESHOLD: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Default, Clone)]
+pub struct LockSiteStats {
+    pub acquisitions: u64,
+    pub total_wait: Duration,
+    pub max_wait: Duration,
+    pub total_hold: Duration,
+    pub max_hold: Duration,
+}
+
+lazy_static! {
+    // these mutexes are intentionally not locked via `trace_lock!` to avoid recursion,
+    // they are only locked the first time a site or a thread is seen and by the reports
+    static ref SITES: Mutex<Vec<&'static LockSite>> = Mutex::new(Vec::new());
+    static ref THREAD_NAMES: Mutex<HashMap<u64, String>> = Mutex::new(HashMap::new());
+    static ref EPOCH: Instant = Instant::now();
+}
+
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Name of the thread registered once per thread, so acquiring a lock doesn't allocate it
+struct ThreadEntry(u64);
+
+impl ThreadEntry {
+    fn register() -> Self {
+        let id = NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed);
+        let name = std::thread::current()
+            .name()
+            .unwrap_or("unnamed")
+            .to_string();
+        THREAD_NAMES.lock().insert(id, name);
+        Self(id)
+    }
+}
+
+impl Drop for ThreadEntry {
+    fn drop(&mut self) {
+        THREAD_NAMES.lock().remove(&self.0);
+    }
+}
+
+thread_local! {
+    static CURRENT_THREAD: ThreadEntry = ThreadEntry::register();
+}
+
+fn current_thread_id() -> u64 {
+    // 0 while the thread is being destroyed
+    CURRENT_THREAD.try_with(|thread| thread.0).unwrap_or(0)
+}
+
+fn thread_name(id: u64) -> String {
+    THREAD_NAMES
+        .lock()
+        .get(&id)
+        .cloned()
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn nanos_since_epoch(instant: Instant) -> u64 {
+    instant.saturating_duration_since(*EPOCH).as_nanos() as u64
+}
+
+/// Stats of a `trace_lock!` call site, declared as a static by the macro and updated
+/// with atomics so tracing doesn't add contention between the sites.
+pub struct LockSite {
+    name: &'static str,
+    site: &'static str,
+    registered: AtomicBool,
+    acquisitions: AtomicU64,
+    // durations in nanoseconds
+    total_wait: AtomicU64,
+    max_wait: AtomicU64,
+    total_hold: AtomicU64,
+    max_hold: AtomicU64,
+    /// guards of this site currently alive
+    holders: AtomicU64,
+    /// last acquisition, in nanoseconds since `EPOCH`
+    held_since: AtomicU64,
+    /// thread of the last acquisition
+    holder_thread: AtomicU64,
+}
+
+impl LockSite {
+    pub const fn new(name: &'static str, site: &'static str) -> Self {
+        Self {
+            name,
+            site,
+            registered: AtomicBool::new(false),
+            acquisitions: AtomicU64::new(0),
+            total_wait: AtomicU64::new(0),
+            max_wait: AtomicU64::new(0),
+            total_hold: AtomicU64::new(0),
+            max_hold: AtomicU64::new(0),
+            holders: AtomicU64::new(0),
+            held_since: AtomicU64::new(0),
+            holder_thread: AtomicU64::new(0),
+        }
+    }
+
+    fn on_acquired(&'static self, waited: Duration, acquired: Instant) {
+        if !self.registered.swap(true, Ordering::Relaxed) {
+            SITES.lock().push(self);
+        }
+        let waited = waited.as_nanos() as u64;
+        self.acquisitions.fetch_add(1, Ordering::Relaxed);
+        self.total_wait.fetch_add(waited, Ordering::Relaxed);
+        self.max_wait.fetch_max(waited, Ordering::Relaxed);
+        self.held_since
+            .store(nanos_since_epoch(acquired), Ordering::Relaxed);
+        self.holder_thread
+            .store(current_thread_id(), Ordering::Relaxed);
+        self.holders.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_released(&self, held: Duration) {
+        let held = held.as_nanos() as u64;
+        self.holders.fetch_sub(1, Ordering::Relaxed);
+        self.total_hold.fetch_add(held, Ordering::Relaxed);
+        self.max_hold.fetch_max(held, Ordering::Relaxed);
+    }
+
+    fn is_held(&self) -> bool {
+        self.holders.load(Ordering::Relaxed) > 0
+    }
+
+    /// Time since the last acquisition of the site
+    fn held_for(&self) -> Duration {
+        let since = self.held_since.load(Ordering::Relaxed);
+        Duration::from_nanos(nanos_since_epoch(Instant::now()).saturating_sub(since))
+    }
+
+    fn stats(&self) -> LockSiteStats {
+        LockSiteStats {
+            acquisitions: self.acquisitions.load(Ordering::Relaxed),
+            total_wait: Duration::from_nanos(self.total_wait.load(Ordering::Relaxed)),
+            max_wait: Duration::from_nanos(self.max_wait.load(Ordering::Relaxed)),
+            total_hold: Duration::from_nanos(self.total_hold.load(Ordering::Relaxed)),
+            max_hold: Duration::from_nanos(self.max_hold.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// Guard returned by `trace_lock!`, records wait and hold times of the lock site.
+pub struct TracedGuard<G> {
+    guard: G,
+    site: &'static LockSite,
+    acquired: Instant,
+}
+
+impl<G> TracedGuard<G> {
+    pub fn new(guard: G, site: &'static LockSite, wait_start: Instant) -> Self {
+        let acquired = Instant::now();
+        let waited = acquired.duration_since(wait_start);
+        if waited > LOCK_WARNING_THRESHOLD {
+            log::warn!(
+                "{} waited {:.2?} to be acquired at {}",
+                site.name,
+                waited,
+                site.site
+            );
+        }
+        site.on_acquired(waited, acquired);
+        Self {
+            guard,
+            site,
+            acquired,
+        }
+    }
+}
+
+impl<G> Drop for TracedGuard<G> {
+    fn drop(&mut self) {
+        let held = self.acquired.elapsed();
+        self.site.on_released(held);
+        if held > LOCK_WARNING_THRESHOLD {
+            log::warn!(
+                "{} was held {:.2?} from {}",
+                self.site.name,
+                held,
+                self.site.site
+            );
+        }
+    }
+}
+
+impl<G: Deref> Deref for TracedGuard<G> {
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<G: DerefMut> DerefMut for TracedGuard<G> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+fn sites() -> Vec<&'static LockSite> {
+    SITES.lock().clone()
+}
+
+/// Returns a description of the sites currently holding the lock
+pub fn current_holders(name: &str) -> Vec<String> {
+    sites()
+        .into_iter()
+        .filter(|site| site.name == name && site.is_held())
+        .map(|site| {
+            format!(
+                "{} (thread: {}, for {:.2?})",
+                site.site,
+                thread_name(site.holder_thread.load(Ordering::Relaxed)),
+                site.held_for()
+            )
+        })
+        .collect()
+}
+
+pub fn get_lock_stats() -> Vec<((&'static str, &'static str), LockSiteStats)> {
+    sites()
+        .into_iter()
+        .map(|site| ((site.name, site.site), site.stats()))
+        .sorted_by(|(_, a), (_, b)| b.max_hold.cmp(&a.max_hold))
+        .collect()
+}
+
+pub fn log_lock_stats() {
+    for ((name, site), stats) in get_lock_stats() {
+        log::info!(
+            "{} at {} => acquisitions: {}, avg wait: {:.2?}, max wait: {:.2?}, avg hold: {:.2?}, max hold: {:.2?}",
+            name,
+            site,
+            stats.acquisitions,
+            stats.total_wait / stats.acquisitions.max(1) as u32,
+            stats.max_wait,
+            stats.total_hold / stats.acquisitions.max(1) as u32,
+            stats.max_hold,
+        );
+    }
+}
+
+/// Periodically reports locks that are still held beyond the threshold,
+/// so stalls are visible before the deadlock panic of `trace_lock!`
+pub fn start_lock_watchdog() -> Result<()> {
+    spawn_named_thread("Lock Watchdog", || {
+        // (site, acquisition) already reported
+        let mut reported = HashSet::new();
+        loop {
+            std::thread::sleep(Duration::from_secs(1));
+            let held = sites()
+                .into_iter()
+                .filter(|site| site.is_held())
+                .map(|site| ((site.site, site.held_since.load(Ordering::Relaxed)), site))
+                .collect::<HashMap<_, _>>();
+            reported.retain(|key| held.contains_key(key));
+            for (key, site) in held {
+                let held_for = site.held_for();
+                if held_for > LOCK_WARNING_THRESHOLD * 4 && reported.insert(key) {
+                    log::warn!(
+                        "{} is being held for {:.2?} at {} on thread {}",
+                        site.name,
+                        held_for,
+                        site.site,
+                        thread_name(site.holder_thread.load(Ordering::Relaxed))
+                    );
+                }
+            }
+        }
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_site_stats_are_recorded() {
+        static SITE: LockSite = LockSite::new("TEST_MUTEX", "lock_stats.rs:1");
+        let mutex = Mutex::new(0);
+
+        for _ in 0..3 {
+            let mut guard = TracedGuard::new(mutex.lock(), &SITE, Instant::now());
+            *guard += 1;
+            assert_eq!(current_holders("TEST_MUTEX").len(), 1);
+        }
+
+        assert_eq!(*mutex.lock(), 3);
+        assert!(current_holders("TEST_MUTEX").is_empty());
+        let stats = SITE.stats();
+        assert_eq!(stats.acquisitions, 3);
+        assert!(stats.max_hold <= stats.total_hold);
+    }
+}
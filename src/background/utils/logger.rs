@@ -0,0 +1,231 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{atomic::Ordering, Arc},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use arc_swap::ArcSwap;
+use lazy_static::lazy_static;
+use log::{LevelFilter, Metadata, Record};
+use parking_lot::Mutex;
+use seelen_core::state::{LogLevel, LoggingSettings};
+use serde::Serialize;
+use tauri_plugin_log::fern;
+
+use crate::{error_handler::Result, hook::LOG_WIN_EVENTS};
+
+/// Max amount of lines to keep in memory while the log folder is still not known
+const MAX_PENDING_LINES: usize = 1000;
+
+lazy_static! {
+    static ref LOG_CONFIG: ArcSwap<LoggingSettings> =
+        ArcSwap::from_pointee(LoggingSettings::default());
+    // this mutex is intentionally not locked via `trace_lock!` to avoid recursion
+    static ref JSON_LOG_FILE: Mutex<JsonLogFile> = Mutex::new(JsonLogFile::default());
+}
+
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    /// unix timestamp in milliseconds
+    ts: u128,
+    level: &'a str,
+    target: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread: Option<&'a str>,
+    message: String,
+}
+
+#[derive(Default)]
+struct JsonLogFile {
+    folder: Option<PathBuf>,
+    file: Option<File>,
+    size: u64,
+    /// lines logged before the folder was set
+    pending: Vec<String>,
+}
+
+impl JsonLogFile {
+    const FILE_NAME: &str = "seelen-ui";
+    const EXTENSION: &str = "jsonl";
+
+    fn path(folder: &Path, index: usize) -> PathBuf {
+        match index {
+            0 => folder.join(format!("{}.{}", Self::FILE_NAME, Self::EXTENSION)),
+            _ => folder.join(format!("{}.{}.{}", Self::FILE_NAME, index, Self::EXTENSION)),
+        }
+    }
+
+    fn open(&mut self, folder: PathBuf) -> Result<()> {
+        std::fs::create_dir_all(&folder)?;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(Self::path(&folder, 0))?;
+        self.size = file.metadata()?.len();
+        self.file = Some(file);
+        self.folder = Some(folder);
+        for line in std::mem::take(&mut self.pending) {
+            self.write(line)?;
+        }
+        Ok(())
+    }
+
+    /// Shifts `seelen-ui.jsonl` -> `seelen-ui.1.jsonl` -> ... dropping the oldest one
+    fn rotate(&mut self, max_files: usize) -> Result<()> {
+        let folder = match &self.folder {
+            Some(folder) => folder.clone(),
+            None => return Ok(()),
+        };
+        self.file = None;
+        let oldest = Self::path(&folder, max_files);
+        if oldest.exists() {
+            std::fs::remove_file(oldest)?;
+        }
+        for index in (0..max_files).rev() {
+            let path = Self::path(&folder, index);
+            if path.exists() {
+                std::fs::rename(path, Self::path(&folder, index + 1))?;
+            }
+        }
+        self.open(folder)
+    }
+
+    fn write(&mut self, line: String) -> Result<()> {
+        let file = match &mut self.file {
+            Some(file) => file,
+            None => {
+                if self.pending.len() < MAX_PENDING_LINES {
+                    self.pending.push(line);
+                }
+                return Ok(());
+            }
+        };
+        file.write_all(line.as_bytes())?;
+        file.write_all(b"\n")?;
+        self.size += line.len() as u64 + 1;
+
+        let config = LOG_CONFIG.load();
+        if self.size >= config.max_file_size {
+            self.rotate(config.max_files)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct SeelenLogger;
+
+impl SeelenLogger {
+    fn level_filter(level: LogLevel) -> LevelFilter {
+        match level {
+            LogLevel::Off => LevelFilter::Off,
+            LogLevel::Error => LevelFilter::Error,
+            LogLevel::Warn => LevelFilter::Warn,
+            LogLevel::Info => LevelFilter::Info,
+            LogLevel::Debug => LevelFilter::Debug,
+            LogLevel::Trace => LevelFilter::Trace,
+        }
+    }
+
+    /// Applies the logging settings, can be called at any time to change the verbosity
+    pub fn set_config(config: &LoggingSettings) {
+        LOG_WIN_EVENTS.store(config.win_events, Ordering::Release);
+        LOG_CONFIG.store(Arc::new(config.clone()));
+    }
+
+    pub fn parse_level(level: &str) -> Result<LogLevel> {
+        Ok(match level.to_lowercase().as_str() {
+            "off" => LogLevel::Off,
+            "error" => LogLevel::Error,
+            "warn" => LogLevel::Warn,
+            "info" => LogLevel::Info,
+            "debug" => LogLevel::Debug,
+            "trace" => LogLevel::Trace,
+            _ => return Err(format!("Invalid log level: {}", level).into()),
+        })
+    }
+
+    /// Changes the level of a module (or the default level if `None`) without persisting it
+    pub fn set_level(module: Option<String>, level: LogLevel) {
+        let mut config = LoggingSettings::clone(&LOG_CONFIG.load());
+        match module {
+            Some(module) => {
+                config.modules.insert(module, level);
+            }
+            None => config.level = level,
+        }
+        LOG_CONFIG.store(Arc::new(config));
+    }
+
+    /// Used as global filter, the most specific module override wins
+    pub fn is_enabled(metadata: &Metadata) -> bool {
+        let config = LOG_CONFIG.load();
+        let target = metadata.target();
+        let level = config
+            .modules
+            .iter()
+            .filter(|(module, _)| {
+                target == module.as_str()
+                    || target
+                        .strip_prefix(module.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .max_by_key(|(module, _)| module.len())
+            .map(|(_, level)| *level)
+            .unwrap_or(config.level);
+        metadata.level() <= Self::level_filter(level)
+    }
+
+    /// Starts writing the json lines to the given folder, lines logged before are flushed
+    pub fn init_file(folder: PathBuf) -> Result<()> {
+        JSON_LOG_FILE.lock().open(folder)
+    }
+
+    /// Last lines of the current log file, attached to the crash reports
+    pub fn recent_lines(max: usize) -> Vec<String> {
+        // not blocking as this is called while crashing, maybe inside the logger itself
+        let folder = match JSON_LOG_FILE.try_lock() {
+            Some(file) => file.folder.clone(),
+            None => None,
+        };
+        let Some(folder) = folder else {
+            return Vec::new();
+        };
+        let content = std::fs::read_to_string(JsonLogFile::path(&folder, 0)).unwrap_or_default();
+        let lines: Vec<&str> = content.lines().collect();
+        lines[lines.len().saturating_sub(max)..]
+            .iter()
+            .map(|line| line.to_string())
+            .collect()
+    }
+
+    fn write_json(record: &Record) {
+        let thread = std::thread::current();
+        let json = JsonRecord {
+            ts: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis(),
+            level: record.level().as_str(),
+            target: record.target(),
+            file: record.file(),
+            line: record.line(),
+            thread: thread.name(),
+            message: record.args().to_string(),
+        };
+        if let Ok(line) = serde_json::to_string(&json) {
+            // errors are ignored to avoid logging inside the logger
+            let _ = JSON_LOG_FILE.lock().write(line);
+        }
+    }
+
+    /// Dispatch writing each record as a json line in rotating files
+    pub fn json_dispatch() -> fern::Dispatch {
+        fern::Dispatch::new().chain(fern::Output::call(Self::write_json))
+    }
+}
@@ -1,7 +1,11 @@
 pub mod ahk;
 pub mod constants;
+pub mod event_queue;
+pub mod i18n;
+pub mod instance_guard;
+pub mod lock_stats;
+pub mod logger;
 pub mod pwsh;
-pub mod updater;
 pub mod virtual_desktop;
 mod winver;
 
@@ -9,7 +13,7 @@ pub use winver::*;
 
 use std::{
     collections::HashMap,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::atomic::AtomicBool,
     time::{Duration, Instant},
 };
@@ -117,6 +121,7 @@ macro_rules! trace_lock {
         trace_lock!($mutex, 5)
     };
     ($mutex:expr, $duration:expr) => {{
+        let wait_start = std::time::Instant::now();
         let guard = $mutex.try_lock_for(std::time::Duration::from_secs($duration));
         let guard_name = stringify!($mutex);
         if guard.is_none() {
@@ -124,6 +129,10 @@ macro_rules! trace_lock {
             if let Some(path) = $crate::utils::LAST_SUCCESSFUL_LOCK.lock().get(guard_name) {
                 panic_msg = format!("{}, last successful aquire was at {}", panic_msg, path);
             }
+            let holders = $crate::utils::lock_stats::current_holders(guard_name);
+            if !holders.is_empty() {
+                panic_msg = format!("{}, currently held by {}", panic_msg, holders.join(", "));
+            }
             panic!("{}", panic_msg);
         }
 
@@ -134,7 +143,15 @@ macro_rules! trace_lock {
             map.insert(guard_name.to_owned(), location);
         }
 
-        guard.expect("Mutex deadlocked")
+        static SITE: $crate::utils::lock_stats::LockSite = $crate::utils::lock_stats::LockSite::new(
+            stringify!($mutex),
+            concat!(file!(), ":", line!()),
+        );
+        $crate::utils::lock_stats::TracedGuard::new(
+            guard.expect("Mutex deadlocked"),
+            &SITE,
+            wait_start,
+        )
     }};
 }
 
@@ -176,3 +193,21 @@ where
         .spawn(cb)
         .map_err(|e| format!("Failed to spawn thread: {}", e).into())
 }
+
+/// Copies the folder recursively, a missing source is copied as an empty folder
+pub fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    std::fs::create_dir_all(to)?;
+    if !from.exists() {
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(from)?.flatten() {
+        let path = entry.path();
+        match path.is_dir() {
+            true => copy_dir(&path, &to.join(entry.file_name()))?,
+            false => {
+                std::fs::copy(&path, to.join(entry.file_name()))?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -5,7 +5,8 @@ use windows::Win32::{
     Foundation::{HWND, LPARAM, RECT},
     UI::Shell::{
         SHAppBarMessage, ABE_BOTTOM, ABE_LEFT, ABE_RIGHT, ABE_TOP, ABM_GETSTATE, ABM_NEW,
-        ABM_REMOVE, ABM_SETPOS, ABM_SETSTATE, ABS_ALWAYSONTOP, ABS_AUTOHIDE, APPBARDATA,
+        ABM_QUERYPOS, ABM_REMOVE, ABM_SETPOS, ABM_SETSTATE, ABS_ALWAYSONTOP, ABS_AUTOHIDE,
+        APPBARDATA,
     },
 };
 
@@ -90,6 +91,8 @@ impl AppBarData {
         self.0.rc = rect;
     }
 
+    /// Reserves the rect on the work area of the monitor, the system can move it to not overlap
+    /// other bars on the same edge, the size is kept.
     pub fn register_as_new_bar(&mut self) {
         let mut data = self.0;
         let mut registered = trace_lock!(RegisteredBars);
@@ -98,6 +101,17 @@ impl AppBarData {
             registered.push(addr);
             unsafe { SHAppBarMessage(ABM_NEW, &mut data) };
         }
+
+        let width = data.rc.right - data.rc.left;
+        let height = data.rc.bottom - data.rc.top;
+        unsafe { SHAppBarMessage(ABM_QUERYPOS, &mut data) };
+        match data.uEdge {
+            ABE_LEFT => data.rc.right = data.rc.left + width,
+            ABE_TOP => data.rc.bottom = data.rc.top + height,
+            ABE_RIGHT => data.rc.left = data.rc.right - width,
+            ABE_BOTTOM => data.rc.top = data.rc.bottom - height,
+            _ => {}
+        }
         unsafe { SHAppBarMessage(ABM_SETPOS, &mut data) };
     }
 
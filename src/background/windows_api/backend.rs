@@ -0,0 +1,45 @@
+use windows::Win32::{Foundation::HWND, Graphics::Gdi::HMONITOR};
+
+use crate::{utils::constants::NATIVE_UI_POPUP_CLASSES, winevent::FullscreenKind};
+
+use super::{window::Window, WindowsApi};
+
+/// Desktop queries made while processing the win events, so the event pipeline can be
+/// driven by synthetic sequences on tests without a live desktop.
+///
+/// It covers the `HookManager` pipeline (skipped events, synthetic events and fullscreen
+/// tracking) up to the dispatch. The consumers, like the window manager and the dock, still
+/// query `WindowsApi` directly, so their handlers can't be replayed with the mock.
+pub trait WindowsApiBackend {
+    fn foreground_window(&self) -> HWND;
+    fn is_window_visible(&self, hwnd: HWND) -> bool;
+    fn monitor_from_window(&self, hwnd: HWND) -> HMONITOR;
+    /// Only for windows that can be considered fullscreen apps (no desktop, overlays or popups)
+    fn fullscreen_kind(&self, hwnd: HWND) -> Option<FullscreenKind>;
+}
+
+/// Implementation over the real desktop
+pub struct LiveWindowsApi;
+
+impl WindowsApiBackend for LiveWindowsApi {
+    fn foreground_window(&self) -> HWND {
+        WindowsApi::get_foreground_window()
+    }
+
+    fn is_window_visible(&self, hwnd: HWND) -> bool {
+        WindowsApi::is_window_visible(hwnd)
+    }
+
+    fn monitor_from_window(&self, hwnd: HWND) -> HMONITOR {
+        WindowsApi::monitor_from_window(hwnd)
+    }
+
+    fn fullscreen_kind(&self, hwnd: HWND) -> Option<FullscreenKind> {
+        let window = Window::from(hwnd);
+        window.fullscreen_kind().filter(|_| {
+            !window.is_desktop()
+                && !window.is_seelen_overlay()
+                && !NATIVE_UI_POPUP_CLASSES.contains(&window.class().as_str())
+        })
+    }
+}
@@ -0,0 +1,55 @@
+use windows::Win32::{
+    Foundation::{HANDLE, HWND},
+    System::{
+        DataExchange::{CloseClipboard, EmptyClipboard, OpenClipboard, SetClipboardData},
+        Memory::{GlobalAlloc, GlobalFree, GlobalLock, GlobalUnlock, GMEM_MOVEABLE},
+        Ole::{CF_DIB, CF_UNICODETEXT},
+    },
+};
+
+use crate::error_handler::Result;
+
+pub struct Clipboard;
+impl Clipboard {
+    /// Replaces the content of the clipboard with the data in the given format
+    pub fn set(format: u32, data: &[u8]) -> Result<()> {
+        unsafe {
+            let global = GlobalAlloc(GMEM_MOVEABLE, data.len())?;
+            let ptr = GlobalLock(global);
+            if ptr.is_null() {
+                let _ = GlobalFree(global);
+                return Err("Failed to allocate the clipboard data".into());
+            }
+            std::ptr::copy_nonoverlapping(data.as_ptr(), ptr as *mut u8, data.len());
+            let _ = GlobalUnlock(global);
+
+            if let Err(err) = OpenClipboard(HWND::default()) {
+                let _ = GlobalFree(global);
+                return Err(err.into());
+            }
+            // on success the clipboard owns the memory
+            let result = EmptyClipboard()
+                .and_then(|_| SetClipboardData(format, HANDLE(global.0)).map(|_| ()));
+            let _ = CloseClipboard();
+            if result.is_err() {
+                let _ = GlobalFree(global);
+            }
+            result?;
+        }
+        Ok(())
+    }
+
+    pub fn set_text(text: &str) -> Result<()> {
+        let data: Vec<u8> = text
+            .encode_utf16()
+            .chain(Some(0))
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        Self::set(CF_UNICODETEXT.0 as u32, &data)
+    }
+
+    /// `data` is a `BITMAPINFOHEADER` followed by the pixels
+    pub fn set_dib(data: &[u8]) -> Result<()> {
+        Self::set(CF_DIB.0 as u32, data)
+    }
+}
@@ -0,0 +1,59 @@
+use std::collections::{HashMap, HashSet};
+
+use windows::Win32::{Foundation::HWND, Graphics::Gdi::HMONITOR};
+
+use crate::winevent::FullscreenKind;
+
+use super::WindowsApiBackend;
+
+pub fn hwnd(address: isize) -> HWND {
+    HWND(address as _)
+}
+
+/// Fake desktop to replay win event sequences on tests
+#[derive(Debug, Default)]
+pub struct MockWindowsApi {
+    pub foreground: isize,
+    pub visible: HashSet<isize>,
+    pub fullscreen: HashMap<isize, FullscreenKind>,
+    /// window -> monitor, the monitor 1 is used by default
+    pub monitors: HashMap<isize, isize>,
+}
+
+impl MockWindowsApi {
+    /// Adds a visible window
+    pub fn with_window(mut self, address: isize) -> Self {
+        self.visible.insert(address);
+        self
+    }
+
+    pub fn focus(&mut self, address: isize) {
+        self.foreground = address;
+    }
+
+    pub fn set_fullscreen(&mut self, address: isize, kind: Option<FullscreenKind>) {
+        match kind {
+            Some(kind) => self.fullscreen.insert(address, kind),
+            None => self.fullscreen.remove(&address),
+        };
+    }
+}
+
+impl WindowsApiBackend for MockWindowsApi {
+    fn foreground_window(&self) -> HWND {
+        hwnd(self.foreground)
+    }
+
+    fn is_window_visible(&self, hwnd: HWND) -> bool {
+        self.visible.contains(&(hwnd.0 as isize))
+    }
+
+    fn monitor_from_window(&self, hwnd: HWND) -> HMONITOR {
+        let monitor = self.monitors.get(&(hwnd.0 as isize)).copied().unwrap_or(1);
+        HMONITOR(monitor as _)
+    }
+
+    fn fullscreen_kind(&self, hwnd: HWND) -> Option<FullscreenKind> {
+        self.fullscreen.get(&(hwnd.0 as isize)).copied()
+    }
+}
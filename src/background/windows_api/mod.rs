@@ -1,14 +1,22 @@
 mod app_bar;
+mod backend;
+mod clipboard;
 mod com;
 mod iterator;
+#[cfg(test)]
+pub mod mock;
 pub mod monitor;
 mod process;
 mod string_utils;
+mod wallpaper;
 pub mod window;
 
 pub use app_bar::*;
+pub use backend::*;
+pub use clipboard::*;
 pub use com::*;
 pub use iterator::*;
+pub use wallpaper::*;
 use itertools::Itertools;
 use process::ProcessInformationFlag;
 use string_utils::WindowsString;
@@ -24,7 +32,7 @@ use std::{
 };
 
 use windows::{
-    core::{BSTR, GUID, PCWSTR, PWSTR},
+    core::{BSTR, GUID, HSTRING, PCWSTR, PWSTR},
     Storage::Streams::{
         DataReader, IRandomAccessStreamReference, IRandomAccessStreamWithContentType,
     },
@@ -38,14 +46,14 @@ use windows::{
             PHYSICAL_MONITOR,
         },
         Foundation::{
-            CloseHandle, FALSE, HANDLE, HMODULE, HWND, LPARAM, LUID, MAX_PATH, RECT,
-            STATUS_SUCCESS, WPARAM,
+            CloseHandle, COLORREF, FALSE, HANDLE, HMODULE, HWND, LPARAM, LUID, MAX_PATH, RECT,
+            STATUS_SUCCESS, TRUE, WPARAM,
         },
         Graphics::{
             Dwm::{
-                DwmGetWindowAttribute, DWMWA_CLOAKED, DWMWA_EXTENDED_FRAME_BOUNDS,
-                DWMWA_VISIBLE_FRAME_BORDER_THICKNESS, DWMWINDOWATTRIBUTE, DWM_CLOAKED_APP,
-                DWM_CLOAKED_INHERITED, DWM_CLOAKED_SHELL,
+                DwmGetWindowAttribute, DwmSetWindowAttribute, DWMWA_CLOAKED,
+                DWMWA_EXTENDED_FRAME_BOUNDS, DWMWA_VISIBLE_FRAME_BORDER_THICKNESS,
+                DWMWINDOWATTRIBUTE, DWM_CLOAKED_APP, DWM_CLOAKED_INHERITED, DWM_CLOAKED_SHELL,
             },
             Gdi::{
                 EnumDisplayMonitors, GetMonitorInfoW, MonitorFromPoint, MonitorFromWindow, HDC,
@@ -67,6 +75,7 @@ use windows::{
             Power::{GetSystemPowerStatus, SetSuspendState, SYSTEM_POWER_STATUS},
             RemoteDesktop::ProcessIdToSessionId,
             Shutdown::{ExitWindowsEx, EXIT_WINDOWS_FLAGS, SHUTDOWN_REASON},
+            SystemInformation::GetTickCount,
             Threading::{
                 GetCurrentProcess, GetCurrentProcessId, OpenProcess, OpenProcessToken,
                 QueryFullProcessImageNameW, PROCESS_ACCESS_RIGHTS, PROCESS_NAME_WIN32,
@@ -75,26 +84,32 @@ use windows::{
         },
         UI::{
             HiDpi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI},
+            Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO},
             Shell::{
                 IShellItem2, IShellLinkW, IVirtualDesktopManager,
                 PropertiesSystem::{IPropertyStore, SHGetPropertyStoreForWindow},
-                SHCreateItemFromParsingName, SHQueryUserNotificationState, ShellLink,
-                VirtualDesktopManager, QUERY_USER_NOTIFICATION_STATE, QUNS_RUNNING_D3D_FULL_SCREEN,
+                SHCreateItemFromParsingName, SHQueryUserNotificationState, ShellExecuteExW,
+                ShellLink, VirtualDesktopManager, QUERY_USER_NOTIFICATION_STATE,
+                QUNS_RUNNING_D3D_FULL_SCREEN, SEE_MASK_FLAG_NO_UI, SHELLEXECUTEINFOW,
                 SIGDN_NORMALDISPLAY,
             },
             WindowsAndMessaging::{
-                EnumWindows, GetClassNameW, GetDesktopWindow, GetForegroundWindow, GetParent,
-                GetSystemMetrics, GetWindow, GetWindowLongW, GetWindowRect, GetWindowTextW,
-                GetWindowThreadProcessId, IsIconic, IsWindow, IsWindowVisible, IsZoomed,
-                PostMessageW, SetForegroundWindow, SetWindowPos, ShowWindow, ShowWindowAsync,
-                SystemParametersInfoW, ANIMATIONINFO, GWL_EXSTYLE, GWL_STYLE, GW_OWNER, HWND_TOP,
-                SET_WINDOW_POS_FLAGS, SHOW_WINDOW_CMD, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN,
-                SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN, SPIF_SENDCHANGE, SPIF_UPDATEINIFILE,
-                SPI_GETANIMATION, SPI_GETDESKWALLPAPER, SPI_SETANIMATION, SPI_SETDESKWALLPAPER,
-                SWP_ASYNCWINDOWPOS, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER,
-                SW_FORCEMINIMIZE, SW_MINIMIZE, SW_NORMAL, SW_RESTORE,
-                SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS, WINDOW_EX_STYLE, WINDOW_STYLE, WNDENUMPROC,
-                WS_SIZEBOX, WS_THICKFRAME,
+                EnumWindows, GetAncestor, GetClassNameW, GetDesktopWindow, GetForegroundWindow,
+                GetLayeredWindowAttributes, GetParent, GetSystemMetrics, GetWindow, GetWindowLongW,
+                GetWindowPlacement, GetWindowRect, GetWindowTextW, GetWindowThreadProcessId,
+                IsIconic, IsWindow, IsWindowArranged, IsWindowVisible, IsZoomed, PostMessageW,
+                SendMessageTimeoutW, SetForegroundWindow, SetLayeredWindowAttributes,
+                SetWindowLongW, SetWindowPlacement, SetWindowPos, ShowWindow, ShowWindowAsync,
+                SystemParametersInfoW, WindowFromPoint, ANIMATIONINFO, GA_ROOT, GWL_EXSTYLE,
+                GWL_STYLE, GW_OWNER, HWND_TOP, LAYERED_WINDOW_ATTRIBUTES_FLAGS, LWA_ALPHA,
+                MINMAXINFO, SET_WINDOW_POS_FLAGS, SHOW_WINDOW_CMD, SMTO_ABORTIFHUNG,
+                SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN,
+                SPIF_SENDCHANGE, SPIF_UPDATEINIFILE, SPI_GETANIMATION, SPI_GETDESKWALLPAPER,
+                SPI_SETANIMATION, SPI_SETDESKWALLPAPER, SWP_ASYNCWINDOWPOS, SWP_NOACTIVATE,
+                SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, SW_FORCEMINIMIZE, SW_MINIMIZE, SW_NORMAL,
+                SW_RESTORE, SW_SHOWNORMAL, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS, WINDOWPLACEMENT,
+                WINDOW_EX_STYLE, WINDOW_STYLE, WM_GETMINMAXINFO, WNDENUMPROC, WS_SIZEBOX,
+                WS_THICKFRAME,
             },
         },
     },
@@ -161,12 +176,16 @@ impl WindowsApi {
         Ok(())
     }
 
-    pub fn get_device_pixel_ratio(hmonitor: HMONITOR) -> Result<f32> {
+    pub fn get_monitor_dpi(hmonitor: HMONITOR) -> Result<u32> {
         let mut dpi_x: u32 = 0;
         let mut _dpi_y: u32 = 0;
         unsafe { GetDpiForMonitor(hmonitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut _dpi_y)? };
+        Ok(dpi_x)
+    }
+
+    pub fn get_device_pixel_ratio(hmonitor: HMONITOR) -> Result<f32> {
         // 96 is the default DPI value on Windows
-        Ok(dpi_x as f32 / 96_f32)
+        Ok(Self::get_monitor_dpi(hmonitor)? as f32 / 96_f32)
     }
 
     pub fn window_thread_process_id(hwnd: HWND) -> (u32, u32) {
@@ -206,6 +225,24 @@ impl WindowsApi {
         unsafe { GetForegroundWindow() }
     }
 
+    /// Top level window under the mouse cursor, transparent windows are skipped
+    pub fn window_at_cursor() -> Result<HWND> {
+        let point = Mouse::get_cursor_pos()?;
+        Ok(unsafe { GetAncestor(WindowFromPoint(*point.as_ref()), GA_ROOT) })
+    }
+
+    /// Time since the last keyboard or mouse input of the session
+    pub fn get_idle_time() -> Result<Duration> {
+        let mut info = LASTINPUTINFO {
+            cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+            ..Default::default()
+        };
+        unsafe { GetLastInputInfo(&mut info).ok()? };
+        // both are milliseconds since boot, wrapping after 49.7 days
+        let now = unsafe { GetTickCount() };
+        Ok(Duration::from_millis(now.wrapping_sub(info.dwTime) as u64))
+    }
+
     pub fn is_window(hwnd: HWND) -> bool {
         unsafe { IsWindow(hwnd) }.into()
     }
@@ -222,6 +259,11 @@ impl WindowsApi {
         unsafe { IsZoomed(hwnd) }.into()
     }
 
+    /// True if the window was snapped by the native snap layouts
+    pub fn is_window_arranged(hwnd: HWND) -> bool {
+        unsafe { IsWindowArranged(hwnd) }.into()
+    }
+
     pub fn get_notification_state() -> Result<QUERY_USER_NOTIFICATION_STATE> {
         Ok(unsafe { SHQueryUserNotificationState()? })
     }
@@ -239,15 +281,25 @@ impl WindowsApi {
             && window_rect.bottom >= rc_monitor.bottom)
     }
 
-    pub fn is_cloaked(hwnd: HWND) -> Result<bool> {
+    /// returns the cloak reason flags, 0 if the window is not cloaked
+    pub fn get_cloak_flags(hwnd: HWND) -> Result<u32> {
         let mut cloaked: u32 = 0;
         Self::dwm_get_window_attribute(hwnd, DWMWA_CLOAKED, &mut cloaked)?;
+        Ok(cloaked)
+    }
+
+    pub fn is_cloaked(hwnd: HWND) -> Result<bool> {
         Ok(matches!(
-            cloaked,
+            Self::get_cloak_flags(hwnd)?,
             DWM_CLOAKED_APP | DWM_CLOAKED_SHELL | DWM_CLOAKED_INHERITED
         ))
     }
 
+    /// cloaked by the owner app instead of the shell (virtual desktops)
+    pub fn is_cloaked_by_app(hwnd: HWND) -> Result<bool> {
+        Ok(Self::get_cloak_flags(hwnd)? & DWM_CLOAKED_APP != 0)
+    }
+
     pub fn show_window(hwnd: HWND, command: SHOW_WINDOW_CMD) -> Result<()> {
         // BOOL is returned but does not signify whether or not the operation was succesful
         // https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-showwindow
@@ -284,6 +336,31 @@ impl WindowsApi {
         WINDOW_EX_STYLE(unsafe { GetWindowLongW(hwnd, GWL_EXSTYLE) } as u32)
     }
 
+    pub fn set_ex_styles(hwnd: HWND, styles: WINDOW_EX_STYLE) {
+        unsafe { SetWindowLongW(hwnd, GWL_EXSTYLE, styles.0 as i32) };
+    }
+
+    /// Alpha set by `SetLayeredWindowAttributes`, `None` if the window doesn't use it
+    pub fn get_layered_alpha(hwnd: HWND) -> Option<u8> {
+        let mut alpha = 0u8;
+        let mut flags = LAYERED_WINDOW_ATTRIBUTES_FLAGS::default();
+        unsafe {
+            GetLayeredWindowAttributes(
+                hwnd,
+                None,
+                Some(&mut alpha as *mut u8),
+                Some(&mut flags as *mut LAYERED_WINDOW_ATTRIBUTES_FLAGS),
+            )
+        }
+        .ok()?;
+        flags.contains(LWA_ALPHA).then_some(alpha)
+    }
+
+    pub fn set_layered_alpha(hwnd: HWND, alpha: u8) -> Result<()> {
+        unsafe { SetLayeredWindowAttributes(hwnd, COLORREF(0), alpha, LWA_ALPHA)? };
+        Ok(())
+    }
+
     fn _set_position(
         hwnd: HWND,
         order: HWND,
@@ -340,6 +417,21 @@ impl WindowsApi {
         Self::show_window(hwnd, SW_MINIMIZE)
     }
 
+    /// The restored rect of the placement is in workspace coordinates (relative to the work area)
+    pub fn get_window_placement(hwnd: HWND) -> Result<WINDOWPLACEMENT> {
+        let mut placement = WINDOWPLACEMENT {
+            length: std::mem::size_of::<WINDOWPLACEMENT>() as u32,
+            ..Default::default()
+        };
+        unsafe { GetWindowPlacement(hwnd, &mut placement)? };
+        Ok(placement)
+    }
+
+    pub fn set_window_placement(hwnd: HWND, placement: &WINDOWPLACEMENT) -> Result<()> {
+        unsafe { SetWindowPlacement(hwnd, placement)? };
+        Ok(())
+    }
+
     pub fn restore_window(hwnd: HWND) -> Result<()> {
         Self::show_window(hwnd, SW_RESTORE)
     }
@@ -555,6 +647,59 @@ impl WindowsApi {
         })
     }
 
+    /// Creates a shortcut to the program, overwriting the file if it exists
+    pub fn create_lnk(
+        lnk_path: &Path,
+        target: &Path,
+        arguments: Option<&str>,
+        working_dir: Option<&Path>,
+    ) -> Result<()> {
+        Com::run_with_context(|| {
+            let shell_link: IShellLinkW = Com::create_instance(&ShellLink)?;
+            unsafe {
+                shell_link.SetPath(&HSTRING::from(target.as_os_str()))?;
+                if let Some(arguments) = arguments {
+                    shell_link.SetArguments(&HSTRING::from(arguments))?;
+                }
+                if let Some(working_dir) = working_dir {
+                    shell_link.SetWorkingDirectory(&HSTRING::from(working_dir.as_os_str()))?;
+                }
+                let persist_file: IPersistFile = shell_link.cast()?;
+                persist_file.Save(&HSTRING::from(lnk_path.as_os_str()), TRUE)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Starts the program as administrator, fails if the user rejects the UAC prompt
+    pub fn run_as_admin(
+        file: &str,
+        arguments: Option<&str>,
+        working_dir: Option<&Path>,
+    ) -> Result<()> {
+        let file = HSTRING::from(file);
+        let arguments = arguments.map(HSTRING::from);
+        let working_dir = working_dir.map(|dir| HSTRING::from(dir.as_os_str()));
+        let as_pcwstr = |value: &Option<HSTRING>| {
+            value
+                .as_ref()
+                .map_or(PCWSTR::null(), |value| PCWSTR(value.as_ptr()))
+        };
+
+        let mut info = SHELLEXECUTEINFOW {
+            cbSize: std::mem::size_of::<SHELLEXECUTEINFOW>() as u32,
+            fMask: SEE_MASK_FLAG_NO_UI,
+            lpVerb: pcwstr!("runas"),
+            lpFile: PCWSTR(file.as_ptr()),
+            lpParameters: as_pcwstr(&arguments),
+            lpDirectory: as_pcwstr(&working_dir),
+            nShow: SW_SHOWNORMAL.0,
+            ..Default::default()
+        };
+        unsafe { ShellExecuteExW(&mut info)? };
+        Ok(())
+    }
+
     pub fn get_executable_display_name(hwnd: HWND) -> Result<String> {
         let shell_item = Self::get_shell_item(&Self::exe_path(hwnd)?)?;
         unsafe {
@@ -591,6 +736,22 @@ impl WindowsApi {
         Ok(())
     }
 
+    pub fn dwm_set_window_attribute<T>(
+        hwnd: HWND,
+        attribute: DWMWINDOWATTRIBUTE,
+        value: &T,
+    ) -> Result<()> {
+        unsafe {
+            DwmSetWindowAttribute(
+                hwnd,
+                attribute,
+                (value as *const T).cast(),
+                u32::try_from(std::mem::size_of::<T>())?,
+            )?;
+        }
+        Ok(())
+    }
+
     /// Get the window rect including drop shadow
     pub fn get_outer_window_rect(hwnd: HWND) -> Result<RECT> {
         let mut rect = RECT::default();
@@ -651,10 +812,8 @@ impl WindowsApi {
         unsafe { MonitorFromWindow(GetDesktopWindow(), MONITOR_DEFAULTTOPRIMARY) }
     }
 
-    /// handle of PHYSICAL_MONITOR is bugged and will be always 0
-    pub fn primary_physical_monitor() -> Result<PHYSICAL_MONITOR> {
-        let hmonitor = Self::primary_monitor();
-
+    /// the handles should be released with `DestroyPhysicalMonitors` after use
+    pub fn physical_monitors(hmonitor: HMONITOR) -> Result<Vec<PHYSICAL_MONITOR>> {
         let mut c_physical_monitors: u32 = 0;
         let mut p_physical_monitors: Vec<PHYSICAL_MONITOR> = Vec::new();
 
@@ -664,7 +823,7 @@ impl WindowsApi {
             GetPhysicalMonitorsFromHMONITOR(hmonitor, p_physical_monitors.as_mut())?;
         };
 
-        Ok(p_physical_monitors[0])
+        Ok(p_physical_monitors)
     }
 
     pub fn monitor_index(hmonitor: HMONITOR) -> Result<usize> {
@@ -718,6 +877,27 @@ impl WindowsApi {
         })
     }
 
+    /// Size limits of the window including the shadow, zeroed fields mean no limit
+    /// because the system only fills the defaults when it sends the message itself.
+    pub fn get_min_max_info(hwnd: HWND) -> Result<MINMAXINFO> {
+        let mut info = MINMAXINFO::default();
+        let responded = unsafe {
+            SendMessageTimeoutW(
+                hwnd,
+                WM_GETMINMAXINFO,
+                WPARAM(0),
+                LPARAM(&mut info as *mut MINMAXINFO as isize),
+                SMTO_ABORTIFHUNG,
+                100,
+                None,
+            )
+        };
+        if responded.0 == 0 {
+            return Err("Window did not respond to WM_GETMINMAXINFO".into());
+        }
+        Ok(info)
+    }
+
     pub fn _get_virtual_desktop_manager() -> Result<IVirtualDesktopManager> {
         Com::create_instance(&VirtualDesktopManager)
     }
@@ -1,4 +1,4 @@
-use windows::Win32::Graphics::Gdi::HMONITOR;
+use windows::Win32::{Foundation::RECT, Graphics::Gdi::HMONITOR};
 
 use crate::{error_handler::Result, modules::input::domain::Point};
 
@@ -41,6 +41,11 @@ impl Monitor {
         WindowsApi::monitor_index(self.0)
     }
 
+    /// Area of the monitor not reserved by the appbars (toolbar, dock and native taskbar)
+    pub fn work_area(&self) -> Result<RECT> {
+        Ok(WindowsApi::monitor_info(self.0)?.monitorInfo.rcWork)
+    }
+
     pub fn at(index: usize) -> Option<Monitor> {
         let monitors = MonitorEnumerator::get_all().ok()?;
         monitors.get(index).map(|m| Self::from(*m))
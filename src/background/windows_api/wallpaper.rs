@@ -0,0 +1,191 @@
+use std::path::{Path, PathBuf};
+
+use image::{imageops::FilterType, DynamicImage, RgbaImage};
+use seelen_core::state::WallpaperFit;
+use windows::{
+    core::{HSTRING, PCWSTR},
+    Win32::{
+        Foundation::RECT,
+        System::Com::CoTaskMemFree,
+        UI::Shell::{
+            DesktopWallpaper as DesktopWallpaperClass, IDesktopWallpaper,
+            DESKTOP_WALLPAPER_POSITION, DWPOS_FILL, DWPOS_FIT, DWPOS_SPAN, DWPOS_TILE,
+        },
+    },
+};
+
+use crate::error_handler::Result;
+
+use super::{monitor::Monitor, Com, WindowsApi};
+
+/// Scales `image` to the exact size of a monitor so the system doesn't need to resample it
+fn fit_to_size(image: &DynamicImage, width: u32, height: u32, fit: WallpaperFit) -> DynamicImage {
+    match fit {
+        WallpaperFit::Fit => {
+            let scaled = image.resize(width, height, FilterType::Lanczos3);
+            let mut canvas = RgbaImage::from_pixel(width, height, image::Rgba([0, 0, 0, 255]));
+            let x = (width - scaled.width()) / 2;
+            let y = (height - scaled.height()) / 2;
+            image::imageops::overlay(&mut canvas, &scaled.to_rgba8(), x as i64, y as i64);
+            DynamicImage::ImageRgba8(canvas)
+        }
+        _ => image.resize_to_fill(width, height, FilterType::Lanczos3),
+    }
+}
+
+/// Monitor of the system wallpaper api (device path) with its rect in physical pixels
+struct WallpaperMonitor {
+    id: HSTRING,
+    rect: RECT,
+}
+
+pub struct DesktopWallpaper;
+impl DesktopWallpaper {
+    fn position(fit: WallpaperFit) -> DESKTOP_WALLPAPER_POSITION {
+        match fit {
+            WallpaperFit::Fill => DWPOS_FILL,
+            WallpaperFit::Fit => DWPOS_FIT,
+            WallpaperFit::Span => DWPOS_SPAN,
+            WallpaperFit::Tile => DWPOS_TILE,
+        }
+    }
+
+    fn monitors(api: &IDesktopWallpaper) -> Result<Vec<WallpaperMonitor>> {
+        let mut monitors = Vec::new();
+        unsafe {
+            for index in 0..api.GetMonitorDevicePathCount()? {
+                let raw = api.GetMonitorDevicePathAt(index)?;
+                let id = HSTRING::from_wide(raw.as_wide())?;
+                CoTaskMemFree(Some(raw.0 as _));
+                // detached monitors are listed too but have no rect
+                if let Ok(rect) = api.GetMonitorRECT(PCWSTR(id.as_ptr())) {
+                    monitors.push(WallpaperMonitor { id, rect });
+                }
+            }
+        }
+        Ok(monitors)
+    }
+
+    /// Writes the image scaled for the given size on the cache folder, reusing it if already exists
+    fn fitted_image(
+        image: &DynamicImage,
+        source: &Path,
+        cache_dir: &Path,
+        width: u32,
+        height: u32,
+        fit: WallpaperFit,
+    ) -> Result<PathBuf> {
+        let stem = source
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let path = cache_dir.join(format!("{stem}-{width}x{height}-{fit:?}.png").to_lowercase());
+        let is_fresh = match (std::fs::metadata(&path), std::fs::metadata(source)) {
+            (Ok(cached), Ok(source)) => cached.modified()? >= source.modified()?,
+            _ => false,
+        };
+        if !is_fresh {
+            std::fs::create_dir_all(cache_dir)?;
+            fit_to_size(image, width, height, fit).save(&path)?;
+        }
+        Ok(path)
+    }
+
+    /// Sets the native wallpaper. Fill and Fit images are cropped/scaled here to the resolution
+    /// of each monitor, so mixed DPI setups get a crisp image instead of the system resampling.
+    /// If `monitor` is given only that monitor is changed, the others keep their image.
+    pub fn set(
+        path: &Path,
+        fit: WallpaperFit,
+        monitor: Option<usize>,
+        cache_dir: &Path,
+    ) -> Result<()> {
+        if !path.exists() {
+            return Err("File not found".into());
+        }
+
+        if monitor.is_some() && fit == WallpaperFit::Span {
+            return Err("Span wallpapers are applied to all the monitors".into());
+        }
+
+        let target = match monitor {
+            Some(index) => Some(Monitor::at(index).ok_or("Monitor not found")?),
+            None => None,
+        };
+
+        // span and tile draw the original image
+        if monitor.is_none() && matches!(fit, WallpaperFit::Span | WallpaperFit::Tile) {
+            WindowsApi::set_wallpaper(path.to_string_lossy().to_string())?;
+            return Com::run_with_context(|| {
+                let api: IDesktopWallpaper = Com::create_instance(&DesktopWallpaperClass)?;
+                unsafe { api.SetPosition(Self::position(fit))? };
+                Ok(())
+            });
+        }
+
+        let image = match fit {
+            WallpaperFit::Fill | WallpaperFit::Fit => Some(image::open(path)?),
+            _ => None,
+        };
+        let target_rect = match &target {
+            Some(monitor) => Some(WindowsApi::monitor_rect(monitor.raw())?),
+            None => None,
+        };
+
+        Com::run_with_context(|| {
+            let api: IDesktopWallpaper = Com::create_instance(&DesktopWallpaperClass)?;
+            for monitor in Self::monitors(&api)? {
+                if target_rect.is_some_and(|rect| rect != monitor.rect) {
+                    continue;
+                }
+                let wallpaper = match &image {
+                    Some(image) => Self::fitted_image(
+                        image,
+                        path,
+                        cache_dir,
+                        (monitor.rect.right - monitor.rect.left) as u32,
+                        (monitor.rect.bottom - monitor.rect.top) as u32,
+                        fit,
+                    )?,
+                    None => path.to_path_buf(),
+                };
+                let wallpaper = HSTRING::from(wallpaper.as_os_str());
+                unsafe {
+                    api.SetWallpaper(PCWSTR(monitor.id.as_ptr()), PCWSTR(wallpaper.as_ptr()))?
+                };
+            }
+            // fitted images already have the size of the monitor, fill avoids any resampling
+            let position = match fit {
+                WallpaperFit::Fit => DWPOS_FILL,
+                fit => Self::position(fit),
+            };
+            unsafe { api.SetPosition(position)? };
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_to_size() {
+        let image = DynamicImage::ImageRgba8(RgbaImage::from_pixel(
+            400,
+            100,
+            image::Rgba([255, 255, 255, 255]),
+        ));
+
+        let filled = fit_to_size(&image, 200, 200, WallpaperFit::Fill);
+        assert_eq!((filled.width(), filled.height()), (200, 200));
+        assert_eq!(filled.to_rgba8().get_pixel(0, 0).0, [255, 255, 255, 255]);
+
+        let fitted = fit_to_size(&image, 200, 200, WallpaperFit::Fit);
+        assert_eq!((fitted.width(), fitted.height()), (200, 200));
+        // 200x50 centered, with bars on top and bottom
+        let fitted = fitted.to_rgba8();
+        assert_eq!(fitted.get_pixel(100, 10).0, [0, 0, 0, 255]);
+        assert_eq!(fitted.get_pixel(100, 100).0, [255, 255, 255, 255]);
+    }
+}
@@ -4,7 +4,10 @@ use std::{
     path::PathBuf,
 };
 
-use windows::Win32::Foundation::HWND;
+use windows::Win32::{
+    Foundation::HWND,
+    UI::WindowsAndMessaging::{WS_CAPTION, WS_EX_TOPMOST},
+};
 
 use crate::{
     error_handler::Result,
@@ -14,6 +17,9 @@ use crate::{
     seelen_wall::SeelenWall,
     seelen_weg::SeelenWeg,
     seelen_wm_v2::instance::WindowManagerV2,
+    state::application::FULL_STATE,
+    utils::constants::IME_WINDOW_CLASSES,
+    winevent::FullscreenKind,
 };
 
 use super::{monitor::Monitor, process::Process, WindowEnumerator, WindowsApi};
@@ -75,6 +81,15 @@ impl Window {
         WindowsApi::get_window_text(self.0)
     }
 
+    /// title rewritten by the title rules of the app
+    pub fn display_title(&self) -> String {
+        let title = self.title();
+        match FULL_STATE.load().get_app_config_by_window(self.0) {
+            Some(config) => config.display_title(&title),
+            None => title,
+        }
+    }
+
     pub fn class(&self) -> String {
         WindowsApi::get_class(self.0).unwrap_or_default()
     }
@@ -142,10 +157,18 @@ impl Window {
         WindowsApi::is_maximized(self.0)
     }
 
+    pub fn is_arranged(&self) -> bool {
+        WindowsApi::is_window_arranged(self.0)
+    }
+
     pub fn is_cloaked(&self) -> bool {
         WindowsApi::is_cloaked(self.0).unwrap_or(false)
     }
 
+    pub fn is_cloaked_by_app(&self) -> bool {
+        WindowsApi::is_cloaked_by_app(self.0).unwrap_or(false)
+    }
+
     pub fn is_foreground(&self) -> bool {
         WindowsApi::get_foreground_window() == self.0
     }
@@ -154,6 +177,27 @@ impl Window {
         WindowsApi::is_fullscreen(self.0).unwrap_or(false)
     }
 
+    /// Exclusive fullscreen is reported by the system for Direct3D apps, borderless fullscreen
+    /// is a visible window covering the whole monitor.
+    pub fn fullscreen_kind(&self) -> Option<FullscreenKind> {
+        if self.is_foreground() && WindowsApi::is_gaming_mode().unwrap_or(false) {
+            return Some(FullscreenKind::Exclusive);
+        }
+        // hidden or cloaked by DWM windows can keep the size of the monitor
+        if !self.is_visible() || self.is_minimized() || self.is_cloaked() {
+            return None;
+        }
+        // maximized windows also cover the monitor if the taskbar auto hides, but keep the caption
+        if self.is_maximized() && WindowsApi::get_styles(self.0).contains(WS_CAPTION) {
+            return None;
+        }
+        self.is_fullscreen().then_some(FullscreenKind::Borderless)
+    }
+
+    pub fn is_always_on_top(&self) -> bool {
+        WindowsApi::get_ex_styles(self.0).contains(WS_EX_TOPMOST)
+    }
+
     /// is the window an Application Frame Host
     pub fn is_frame(&self) -> Result<bool> {
         Ok(self.exe()? == PathBuf::from(APP_FRAME_HOST_PATH))
@@ -172,6 +216,23 @@ impl Window {
         Ok(None)
     }
 
+    /// Application Frame Host keeps the frames of closed UWP apps alive, cloaked by the app
+    /// or without the app window inside, these should be ignored.
+    pub fn is_ghost_frame(&self) -> bool {
+        self.is_frame().unwrap_or(false)
+            && (self.is_cloaked_by_app() || !matches!(self.get_frame_creator(), Ok(Some(_))))
+    }
+
+    /// will be false if the window is not a frame
+    pub fn is_frame_suspended(&self) -> bool {
+        match self.get_frame_creator() {
+            Ok(Some(creator)) => {
+                WindowsApi::window_is_uwp_suspended(creator.hwnd()).unwrap_or(false)
+            }
+            _ => false,
+        }
+    }
+
     /// this means all windows that are part of the UI desktop not the real desktop window
     pub fn is_desktop(&self) -> bool {
         let class = self.class();
@@ -185,6 +246,15 @@ impl Window {
                 }))
     }
 
+    /// Windows of the input methods, they are shown while typing and can take the focus
+    pub fn is_ime(&self) -> bool {
+        let class = self.class();
+        IME_WINDOW_CLASSES.contains(&class.as_str())
+            // candidates, emoji panel and touch keyboard on Windows 11
+            || (class == "Windows.UI.Core.CoreWindow"
+                && self.exe().is_ok_and(|exe| exe.ends_with("TextInputHost.exe")))
+    }
+
     pub fn is_seelen_overlay(&self) -> bool {
         if let Ok(exe) = self.exe() {
             return exe.ends_with("seelen-ui.exe")
@@ -1,7 +1,6 @@
 #![allow(clippy::use_self)]
 
-use lazy_static::lazy_static;
-use parking_lot::Mutex;
+use seelen_core::state::FullscreenPolicy;
 
 use windows::Win32::Foundation::HWND;
 use windows::Win32::Graphics::Gdi::HMONITOR;
@@ -91,24 +90,37 @@ use windows::Win32::UI::WindowsAndMessaging::{
     EVENT_SYSTEM_SWITCHEND, EVENT_SYSTEM_SWITCHER_APPDROPPED,
 };
 
-use crate::error_handler::Result;
-use crate::trace_lock;
-use crate::utils::constants::NATIVE_UI_POPUP_CLASSES;
-use crate::windows_api::window::Window;
-use crate::windows_api::WindowsApi;
+use crate::state::application::FULL_STATE;
+use crate::windows_api::WindowsApiBackend;
 
-lazy_static! {
-    static ref FULLSCREENED: Mutex<Option<SyntheticFullscreenData>> = Mutex::new(None);
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FullscreenKind {
+    /// Direct3D exclusive fullscreen, usually games
+    Exclusive,
+    /// a window without borders covering the whole monitor, games, videos, presentations, etc.
+    Borderless,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct SyntheticFullscreenData {
     pub handle: HWND,
     pub monitor: HMONITOR,
+    pub kind: FullscreenKind,
 }
 
 unsafe impl Send for SyntheticFullscreenData {}
 
+impl SyntheticFullscreenData {
+    /// Policy configured for the app of the window, the default one if not configured
+    pub fn policy(&self) -> FullscreenPolicy {
+        FULL_STATE
+            .load()
+            .get_app_config_by_window(self.handle)
+            .and_then(|config| config.fullscreen.clone())
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 #[repr(u32)]
 #[allow(dead_code)]
@@ -299,47 +311,75 @@ impl From<u32> for WinEvent {
 }
 
 impl WinEvent {
-    pub fn get_synthetics(&self, origin: HWND) -> Result<Vec<WinEvent>> {
-        let mut synthetics = Vec::new();
+    /// Synthetic events that could be generated from this event, used to know if the event
+    /// should be processed even if nobody is subscribed to it directly
+    pub fn possible_synthetics(&self, origin: HWND) -> Vec<WinEvent> {
         match self {
             Self::SystemForeground | Self::ObjectLocationChange => {
-                if origin == WindowsApi::get_foreground_window() {
-                    let mut latest_fullscreened = trace_lock!(FULLSCREENED);
-                    let window = Window::from(origin);
-                    let is_origin_fullscreen = window.is_fullscreen()
-                        && !window.is_desktop()
-                        && !window.is_seelen_overlay()
-                        && !NATIVE_UI_POPUP_CLASSES.contains(&window.class().as_str());
+                let data = SyntheticFullscreenData {
+                    handle: origin,
+                    monitor: HMONITOR::default(),
+                    kind: FullscreenKind::Borderless,
+                };
+                vec![
+                    Self::SyntheticFullscreenStart(data),
+                    Self::SyntheticFullscreenEnd(data),
+                ]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
 
-                    match *latest_fullscreened {
-                        Some(latest) if latest.handle == origin => {
-                            // exiting fullscreen
-                            if !is_origin_fullscreen {
-                                *latest_fullscreened = None;
-                                synthetics.push(Self::SyntheticFullscreenEnd(latest));
-                            }
-                        }
-                        _ => {
-                            // remove fullscreen of latest when foregrounding another window
-                            if let Some(old) = latest_fullscreened.take() {
-                                synthetics.push(Self::SyntheticFullscreenEnd(old));
-                            }
-                            // if new foregrounded window is fullscreen emit it
-                            if is_origin_fullscreen {
-                                log::trace!("Fullscreened: {:?}", window);
-                                let data = SyntheticFullscreenData {
-                                    handle: origin,
-                                    monitor: WindowsApi::monitor_from_window(origin),
-                                };
-                                *latest_fullscreened = Some(data);
-                                synthetics.push(Self::SyntheticFullscreenStart(data));
-                            }
-                        }
-                    }
+/// Keeps the last fullscreened window to produce the synthetic fullscreen events
+#[derive(Debug, Default)]
+pub struct FullscreenTracker {
+    latest: Option<SyntheticFullscreenData>,
+}
+
+impl FullscreenTracker {
+    pub fn synthetics<A: WindowsApiBackend>(
+        &mut self,
+        event: &WinEvent,
+        origin: HWND,
+        api: &A,
+    ) -> Vec<WinEvent> {
+        let mut synthetics = Vec::new();
+        if !matches!(
+            event,
+            WinEvent::SystemForeground | WinEvent::ObjectLocationChange
+        ) || origin != api.foreground_window()
+        {
+            return synthetics;
+        }
+
+        let fullscreen_kind = api.fullscreen_kind(origin);
+        match self.latest {
+            Some(latest) if latest.handle == origin => {
+                // exiting fullscreen
+                if fullscreen_kind.is_none() {
+                    self.latest = None;
+                    synthetics.push(WinEvent::SyntheticFullscreenEnd(latest));
                 }
             }
-            _ => {}
-        };
-        Ok(synthetics)
+            _ => {
+                // remove fullscreen of latest when foregrounding another window
+                if let Some(old) = self.latest.take() {
+                    synthetics.push(WinEvent::SyntheticFullscreenEnd(old));
+                }
+                // if new foregrounded window is fullscreen emit it
+                if let Some(kind) = fullscreen_kind {
+                    log::trace!("Fullscreened ({:?}): {:?}", kind, origin);
+                    let data = SyntheticFullscreenData {
+                        handle: origin,
+                        monitor: api.monitor_from_window(origin),
+                        kind,
+                    };
+                    self.latest = Some(data);
+                    synthetics.push(WinEvent::SyntheticFullscreenStart(data));
+                }
+            }
+        }
+        synthetics
     }
 }